@@ -6,9 +6,9 @@ use crate::{
         LogArgs,
     },
     commands::{
-        config_cmd, db, debug_cmd, dump_genesis, import, init_cmd, init_state,
+        config_cmd, db, debug_cmd, dump_genesis, engine, import, init_cmd, init_state,
         node::{self, NoArgs},
-        p2p, recover, stage, test_vectors,
+        p2p, recover, stage, test_vectors, txpool,
     },
     version::{LONG_VERSION, SHORT_VERSION},
 };
@@ -143,7 +143,7 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
         info!(target: "reth::cli", "Initialized tracing, debug log directory: {}", self.logs.log_file_directory);
 
         let runner = CliRunner::default();
-        match self.command {
+        let result = match self.command {
             Commands::Node(command) => {
                 runner.run_command_until_exit(|ctx| command.execute(ctx, launcher))
             }
@@ -163,8 +163,12 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Engine(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Txpool(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
-        }
+        };
+        reth_tracing::shutdown_otlp();
+        result
     }
 
     /// Initializes tracing with the configured options.
@@ -220,6 +224,12 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Various debug routines
     #[command(name = "debug")]
     Debug(debug_cmd::Command),
+    /// Tools for the engine API of a node
+    #[command(name = "engine")]
+    Engine(engine::Command),
+    /// Tools for the transaction pool of a node
+    #[command(name = "txpool")]
+    Txpool(txpool::Command),
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command),
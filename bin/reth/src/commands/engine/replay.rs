@@ -0,0 +1,191 @@
+//! `reth engine replay` command.
+
+use clap::Parser;
+use eyre::Context;
+use jsonrpsee::{
+    core::{client::ClientT, traits::ToRpcParams},
+    http_client::HttpClientBuilder,
+};
+use reth_rpc_builder::engine_recorder::RecordedEngineCall;
+use reth_rpc_layer::{AuthClientLayer, JwtSecret};
+use serde_json::{value::RawValue, Value};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+use tracing::*;
+
+/// `reth engine replay` command
+///
+/// Sends the recorded engine API requests to a node in the order they were recorded, and compares
+/// the payload statuses of the responses with the recording.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The file with the recorded requests, as written with `--authrpc.record`.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// The URL of the auth server of the node.
+    #[arg(long = "engine.url", default_value = "http://localhost:8551")]
+    engine_url: String,
+
+    /// The path to the JWT secret of the auth server of the node.
+    #[arg(long = "engine.jwtsecret", value_name = "PATH")]
+    jwt_secret: PathBuf,
+
+    /// Wait as long between requests as between the recorded requests.
+    #[arg(long)]
+    realtime: bool,
+
+    /// Stop at the first response that doesn't match the recording.
+    #[arg(long)]
+    stop_on_mismatch: bool,
+}
+
+impl Command {
+    /// Execute `engine replay` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let secret = JwtSecret::from_file(&self.jwt_secret)
+            .wrap_err_with(|| format!("failed to read JWT secret {}", self.jwt_secret.display()))?;
+        let client = HttpClientBuilder::default()
+            .set_http_middleware(tower::ServiceBuilder::new().layer(AuthClientLayer::new(secret)))
+            .build(&self.engine_url)?;
+
+        // the payload ids of the recording, mapped to the payload ids of the node
+        let mut payload_ids = HashMap::<String, String>::new();
+        let mut last_timestamp = None;
+        let (mut replayed, mut mismatches) = (0usize, 0usize);
+
+        let calls = RecordedEngineCall::read_all(&self.path)
+            .wrap_err_with(|| format!("failed to open recording {}", self.path.display()))?;
+        for call in calls {
+            let call = call?;
+            // other methods of the auth server don't change the state of the node
+            if !call.method.starts_with("engine_") {
+                continue
+            }
+
+            if self.realtime {
+                if let Some(last_timestamp) = last_timestamp {
+                    let delay = call.timestamp.saturating_sub(last_timestamp);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+                last_timestamp = Some(call.timestamp);
+            }
+
+            let mut params = call.params;
+            if call.method.starts_with("engine_getPayloadV") {
+                params = map_payload_id(params, &payload_ids)?;
+            }
+            let response = client.request::<Value, _>(&call.method, RawParams(params)).await;
+            replayed += 1;
+
+            let recorded: Value = serde_json::from_str(call.response.get())?;
+            let recorded = recorded.get("result");
+            let payload_id = |result: &Value| {
+                result.get("payloadId").and_then(Value::as_str).map(str::to_string)
+            };
+            if let (Some(recorded_id), Some(id)) =
+                (recorded.and_then(payload_id), response.as_ref().ok().and_then(payload_id))
+            {
+                payload_ids.insert(recorded_id, id);
+            }
+
+            let expected = Outcome::of(recorded);
+            let got = Outcome::of(response.as_ref().ok());
+            if expected != got {
+                mismatches += 1;
+                warn!(
+                    target: "reth::cli",
+                    method = %call.method,
+                    recorded_at = call.timestamp,
+                    ?expected,
+                    ?got,
+                    error = ?response.err(),
+                    "Response doesn't match the recording"
+                );
+                if self.stop_on_mismatch {
+                    eyre::bail!("response to {} doesn't match the recording", call.method)
+                }
+            }
+        }
+
+        info!(target: "reth::cli", replayed, mismatches, "Replayed engine API requests");
+        Ok(())
+    }
+}
+
+/// The params of a recorded request.
+struct RawParams(Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Replaces the recorded payload id in the params of `engine_getPayload` with the payload id that
+/// the node returned for the same `engine_forkchoiceUpdated` request.
+fn map_payload_id(
+    params: Option<Box<RawValue>>,
+    payload_ids: &HashMap<String, String>,
+) -> eyre::Result<Option<Box<RawValue>>> {
+    let Some(raw) = params else { return Ok(None) };
+    let mut params: Value = serde_json::from_str(raw.get())?;
+    let Some(id) = params.get_mut(0) else { return Ok(Some(raw)) };
+    match id.as_str().and_then(|recorded| payload_ids.get(recorded)) {
+        Some(mapped) => {
+            *id = Value::String(mapped.clone());
+            Ok(Some(serde_json::value::to_raw_value(&params)?))
+        }
+        None => Ok(Some(raw)),
+    }
+}
+
+/// The part of a response that is compared with the recording.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    /// The payload status of `engine_newPayload` and `engine_forkchoiceUpdated`.
+    Status(String),
+    /// Any other successful response.
+    Ok,
+    /// An error response.
+    Error,
+}
+
+impl Outcome {
+    fn of(result: Option<&Value>) -> Self {
+        let Some(result) = result else { return Self::Error };
+        result
+            .pointer("/payloadStatus/status")
+            .or_else(|| result.get("status"))
+            .and_then(Value::as_str)
+            .map_or(Self::Ok, |status| Self::Status(status.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn compares_payload_statuses() {
+        let fcu = json!({ "payloadStatus": { "status": "VALID" }, "payloadId": "0x01" });
+        assert_eq!(Outcome::of(Some(&fcu)), Outcome::Status("VALID".to_string()));
+        assert_eq!(
+            Outcome::of(Some(&json!({ "status": "SYNCING" }))),
+            Outcome::Status("SYNCING".to_string())
+        );
+        assert_eq!(Outcome::of(Some(&json!(["engine_newPayloadV3"]))), Outcome::Ok);
+        assert_eq!(Outcome::of(None), Outcome::Error);
+    }
+
+    #[test]
+    fn maps_payload_ids() {
+        let payload_ids = HashMap::from([("0x01".to_string(), "0x02".to_string())]);
+        let params = |id: &str| Some(serde_json::value::to_raw_value(&json!([id])).unwrap());
+
+        let mapped = map_payload_id(params("0x01"), &payload_ids).unwrap().unwrap();
+        assert_eq!(mapped.get(), r#"["0x02"]"#);
+        let unknown = map_payload_id(params("0x03"), &payload_ids).unwrap().unwrap();
+        assert_eq!(unknown.get(), r#"["0x03"]"#);
+    }
+}
@@ -4,6 +4,7 @@ pub mod config_cmd;
 pub mod db;
 pub mod debug_cmd;
 pub mod dump_genesis;
+pub mod engine;
 pub mod import;
 pub mod import_op;
 pub mod import_receipts_op;
@@ -16,5 +17,6 @@ pub mod p2p;
 pub mod recover;
 pub mod stage;
 pub mod test_vectors;
+pub mod txpool;
 
 pub mod common;
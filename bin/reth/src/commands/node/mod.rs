@@ -2,8 +2,8 @@
 
 use crate::args::{
     utils::{chain_help, chain_value_parser, parse_socket_address, SUPPORTED_CHAINS},
-    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-    RpcServerArgs, TxPoolArgs,
+    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, FirehoseArgs, NetworkArgs,
+    PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
 };
 use clap::{value_parser, Args, Parser};
 use reth_chainspec::ChainSpec;
@@ -95,10 +95,18 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub dev: DevArgs,
 
+    /// All engine related arguments
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
     /// All pruning related arguments
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All firehose related arguments with --firehose prefix
+    #[command(flatten)]
+    pub firehose: FirehoseArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -146,7 +154,9 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             debug,
             db,
             dev,
+            engine,
             pruning,
+            firehose,
             ext,
         } = self;
 
@@ -164,7 +174,9 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             debug,
             db,
             dev,
+            engine,
             pruning,
+            firehose,
         };
 
         // Register the prometheus recorder before creating the database,
@@ -11,7 +11,7 @@ use reth_db::{static_file::iter_static_files, tables, DatabaseEnv};
 use reth_db_api::transaction::DbTxMut;
 use reth_db_common::init::{insert_genesis_header, insert_genesis_history, insert_genesis_state};
 use reth_primitives::{static_file::find_fixed_range, StaticFileSegment};
-use reth_provider::{providers::StaticFileWriter, StaticFileProviderFactory};
+use reth_provider::{providers::StaticFileWriter, HistoryWriter, StaticFileProviderFactory};
 use reth_stages::StageId;
 
 /// `reth drop-stage` command
@@ -163,6 +163,12 @@ impl Command {
                 )?;
                 insert_genesis_history(&provider_rw, self.env.chain.genesis.alloc.iter())?;
             }
+            StageEnum::LogIndex => {
+                provider_rw.drop_log_indices()?;
+            }
+            StageEnum::SenderNonceIndex => {
+                provider_rw.drop_sender_nonce_index()?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 tx.put::<tables::StageCheckpoints>(
@@ -21,8 +21,8 @@ use reth_provider::{
 use reth_stages::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, ExecutionStageThresholds,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogsStage, IndexSenderNoncesStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     ExecInput, ExecOutput, Stage, StageExt, UnwindInput, UnwindOutput,
 };
@@ -228,6 +228,16 @@ impl Command {
                     )),
                     None,
                 ),
+                StageEnum::LogIndex => {
+                    (Box::new(IndexLogsStage::new(config.stages.index_logs, etl_config)), None)
+                }
+                StageEnum::SenderNonceIndex => (
+                    Box::new(IndexSenderNoncesStage::new(
+                        config.stages.index_sender_nonces,
+                        etl_config,
+                    )),
+                    None,
+                ),
                 _ => return Ok(()),
             };
         if let Some(unwind_stage) = &unwind_stage {
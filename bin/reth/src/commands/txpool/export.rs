@@ -0,0 +1,56 @@
+//! `reth txpool export` command.
+
+use clap::Parser;
+use eyre::Context;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_rpc_api::AdminApiClient;
+use reth_rpc_types::MAX_TXPOOL_SNAPSHOT_PAGE_SIZE;
+use std::{fs::File, io::BufWriter, path::PathBuf};
+use tracing::*;
+
+/// `reth txpool export` command
+///
+/// Fetches a snapshot of the transaction pool page by page with `admin_exportTxpool` and writes it
+/// to a file as JSON.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The file to write the snapshot to.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// The URL of the HTTP RPC server of the node. The `admin` namespace must be enabled.
+    #[arg(long = "rpc.url", default_value = "http://localhost:8545")]
+    rpc_url: String,
+}
+
+impl Command {
+    /// Execute `txpool export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let client = HttpClientBuilder::default().build(&self.rpc_url)?;
+
+        // the pool is exported one page at a time, the first page determines the block the
+        // snapshot was taken at
+        let limit = MAX_TXPOOL_SNAPSHOT_PAGE_SIZE as u64;
+        let mut snapshot = client.export_txpool(None, Some(limit)).await?;
+        let mut page_len = snapshot.transactions.len();
+        while page_len == MAX_TXPOOL_SNAPSHOT_PAGE_SIZE {
+            let offset = snapshot.transactions.len() as u64;
+            let page = client.export_txpool(Some(offset), Some(limit)).await?;
+            page_len = page.transactions.len();
+            snapshot.transactions.extend(page.transactions);
+        }
+
+        let file = File::create(&self.path)
+            .wrap_err_with(|| format!("failed to create {}", self.path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &snapshot)?;
+
+        info!(
+            target: "reth::cli",
+            transactions = snapshot.transactions.len(),
+            block = snapshot.block_number,
+            path = %self.path.display(),
+            "Exported transaction pool"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! `reth txpool import` command.
+
+use clap::Parser;
+use eyre::Context;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_rpc_api::AdminApiClient;
+use reth_rpc_types::{TxpoolImportOutcome, TxpoolSnapshot, MAX_TXPOOL_SNAPSHOT_PAGE_SIZE};
+use std::{fs::File, io::BufReader, path::PathBuf};
+use tracing::*;
+
+/// `reth txpool import` command
+///
+/// Reads a snapshot written with `reth txpool export` and adds its transactions to the pool of a
+/// node with `admin_importTxpool`, in chunks as large as the node accepts.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The file with the snapshot, as written with `reth txpool export`.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// The URL of the HTTP RPC server of the node. The `admin` namespace must be enabled.
+    #[arg(long = "rpc.url", default_value = "http://localhost:8545")]
+    rpc_url: String,
+}
+
+impl Command {
+    /// Execute `txpool import` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let file = File::open(&self.path)
+            .wrap_err_with(|| format!("failed to open {}", self.path.display()))?;
+        let mut snapshot: TxpoolSnapshot = serde_json::from_reader(BufReader::new(file))
+            .wrap_err_with(|| format!("failed to parse snapshot {}", self.path.display()))?;
+
+        let client = HttpClientBuilder::default().build(&self.rpc_url)?;
+
+        // import in chunks the node accepts, in nonce order across chunks
+        let mut transactions = std::mem::take(&mut snapshot.transactions);
+        transactions.sort_by_key(|tx| (tx.sender, tx.nonce));
+        let mut outcome = TxpoolImportOutcome::default();
+        for chunk in transactions.chunks(MAX_TXPOOL_SNAPSHOT_PAGE_SIZE) {
+            let chunk = TxpoolSnapshot { transactions: chunk.to_vec(), ..snapshot.clone() };
+            let chunk_outcome = client.import_txpool(chunk).await?;
+            outcome.imported.extend(chunk_outcome.imported);
+            outcome.failed.extend(chunk_outcome.failed);
+        }
+
+        for (hash, error) in &outcome.failed {
+            warn!(target: "reth::cli", %hash, %error, "Failed to import transaction");
+        }
+        info!(
+            target: "reth::cli",
+            imported = outcome.imported.len(),
+            failed = outcome.failed.len(),
+            "Imported transaction pool"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! `reth txpool` command. Tools for the transaction pool of a node.
+
+use clap::{Parser, Subcommand};
+
+mod export;
+mod import;
+
+/// `reth txpool` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth txpool` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Write the transactions of the pool of a node, with their subpools, to a file.
+    Export(export::Command),
+    /// Add the transactions of a file written with `reth txpool export` to the pool of a node.
+    Import(import::Command),
+}
+
+impl Command {
+    /// Execute `txpool` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Export(command) => command.execute().await,
+            Subcommands::Import(command) => command.execute().await,
+        }
+    }
+}
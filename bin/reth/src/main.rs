@@ -10,8 +10,15 @@ compile_error!("Cannot build the `reth` binary with the `optimism` feature flag
 
 #[cfg(not(feature = "optimism"))]
 fn main() {
-    use reth::cli::Cli;
-    use reth_node_ethereum::EthereumNode;
+    use reth::{
+        cli::Cli,
+        rpc::{api::EthBundleApiServer, eth::EthBundle},
+    };
+    use reth_basic_payload_builder::BundleQueue;
+    use reth_node_ethereum::{
+        node::EthereumPayloadBuilder, EthPayloadAttributesBuilder, EthereumNode,
+    };
+    use std::sync::Arc;
 
     reth::sigsegv_handler::install();
 
@@ -21,7 +28,30 @@ fn main() {
     }
 
     if let Err(err) = Cli::parse_args().run(|builder, _| async {
-        let handle = builder.launch_node(EthereumNode::default()).await?;
+        // bundles submitted via `eth_sendBundle` are force-included in the built payloads
+        let bundles = builder.config().builder.bundles.then(BundleQueue::default);
+        let mut payload_builder = EthereumPayloadBuilder::default();
+        if let Some(queue) = bundles.clone() {
+            payload_builder = payload_builder.with_transactions_hook(Arc::new(queue));
+        }
+
+        let handle = builder
+            .with_types::<EthereumNode>()
+            .with_components(EthereumNode::components().payload(payload_builder))
+            .extend_rpc_modules(move |ctx| {
+                if let Some(queue) = bundles {
+                    let bundle_api = EthBundle::with_bundle_pool(
+                        ctx.registry.eth_api(),
+                        ctx.registry.blocking_pool_guard().clone(),
+                        Arc::new(queue),
+                    );
+                    ctx.modules.add_or_replace_configured(bundle_api.into_rpc())?;
+                }
+                Ok(())
+            })
+            .with_engine_driver(EthPayloadAttributesBuilder::default())
+            .launch()
+            .await?;
         handle.node_exit_future.await
     }) {
         eprintln!("Error: {err:?}");
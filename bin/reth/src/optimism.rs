@@ -1,8 +1,21 @@
 #![allow(missing_docs, rustdoc::missing_crate_level_docs)]
 
 use clap::Parser;
-use reth::cli::Cli;
-use reth_node_optimism::{args::RollupArgs, rpc::SequencerClient, OptimismNode};
+use reth::{api::FullNodeComponents, cli::Cli, rpc::builder::RethRpcModule};
+use reth_node_optimism::{
+    args::RollupArgs,
+    rpc::{
+        conditional::{EthConditional, EthConditionalApiServer},
+        historical::{HistoricalRpc, HistoricalRpcClient},
+        interop::SupervisorValidator,
+        preconf::{PreconfirmationFeed, PreconfirmationRpc, DEFAULT_PRECONF_POLL_INTERVAL},
+        rollup::{Rollup, RollupApiServer},
+        tx_status::{SequencerTransactionStatusApiServer, TransactionStatusTracker},
+        ForwardingFailureMode, SequencerApiServer, SequencerClient,
+        DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL,
+    },
+    OptimismNode,
+};
 use std::sync::Arc;
 
 // We use jemalloc for performance reasons
@@ -27,10 +40,94 @@ fn main() {
             .node(OptimismNode::new(rollup_args.clone()))
             .extend_rpc_modules(move |ctx| {
                 // register sequencer tx forwarder
+                let sequencer_http_config = rollup_args.sequencer_http_config()?;
                 if let Some(sequencer_http) = rollup_args.sequencer_http {
-                    ctx.registry.set_eth_raw_transaction_forwarder(Arc::new(SequencerClient::new(
+                    let failure_mode = if rollup_args.sequencer_best_effort {
+                        ForwardingFailureMode::Warn
+                    } else {
+                        ForwardingFailureMode::Error
+                    };
+
+                    // tracks the outcome of forwarded transactions
+                    let status_tracker = TransactionStatusTracker::default();
+                    ctx.node()
+                        .task_executor()
+                        .spawn(status_tracker.clone().run(ctx.provider().clone()));
+
+                    let sequencer_client = SequencerClient::new_with_http_config(
                         sequencer_http,
-                    )));
+                        rollup_args.sequencer_http_fallbacks,
+                        &sequencer_http_config,
+                    )?
+                    .with_failure_mode(failure_mode)
+                    .with_forward_only(rollup_args.sequencer_forward_only)
+                    .with_status_tracker(status_tracker.clone());
+
+                    // probe the sequencer and stop forwarding while it's unreachable
+                    let health_check_interval = rollup_args
+                        .sequencer_health_check_interval
+                        .unwrap_or(DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL);
+                    ctx.node()
+                        .task_executor()
+                        .spawn(sequencer_client.clone().run_health_checks(health_check_interval));
+                    ctx.registry
+                        .set_eth_raw_transaction_forwarder(Arc::new(sequencer_client.clone()));
+
+                    // expose the state of the sequencer client and forwarded transactions
+                    ctx.modules.merge_configured(sequencer_client.into_rpc())?;
+                    ctx.modules.merge_configured(status_tracker.into_rpc())?;
+                }
+
+                // check cross-chain messages of submitted transactions
+                if let Some(supervisor_http) = rollup_args.supervisor_http {
+                    ctx.registry.set_eth_raw_transaction_validator(Arc::new(
+                        SupervisorValidator::new(supervisor_http)?,
+                    ));
+                }
+
+                // register `eth_sendRawTransactionConditional`
+                let conditional =
+                    EthConditional::new(ctx.provider().clone(), ctx.registry.eth_api());
+                ctx.modules.merge_configured(conditional.into_rpc())?;
+
+                // register the `rollup` namespace
+                let rollup = Rollup::new(
+                    ctx.provider().clone(),
+                    Box::new(ctx.node().task_executor().clone()),
+                );
+                ctx.modules.merge_configured(rollup.into_rpc())?;
+
+                // methods that are wrapped by the historical and preconfirmation methods
+                let mut local = ctx.registry.module_for(
+                    &[RethRpcModule::Eth, RethRpcModule::Debug, RethRpcModule::Trace].into(),
+                );
+
+                // forward requests for pre-bedrock history
+                if let Some(historical_rpc) = rollup_args.historical_rpc {
+                    let historical = HistoricalRpc::new(
+                        HistoricalRpcClient::new(historical_rpc),
+                        local.clone(),
+                        ctx.config().chain.clone(),
+                    )
+                    .into_rpc_module();
+                    ctx.modules.replace_configured(historical.clone())?;
+
+                    // the preconfirmation methods wrap the historical ones
+                    for method in historical.method_names() {
+                        local.remove_method(method);
+                    }
+                    local.merge(historical)?;
+                }
+
+                // serve `pending` queries from the preconfirmations of the sequencer
+                if let Some(preconf_http) = rollup_args.preconf_http {
+                    let feed = PreconfirmationFeed::new(
+                        preconf_http,
+                        rollup_args.preconf_interval.unwrap_or(DEFAULT_PRECONF_POLL_INTERVAL),
+                    );
+                    ctx.node().task_executor().spawn(feed.clone().run());
+                    let preconf = PreconfirmationRpc::new(feed, local, ctx.provider().clone());
+                    ctx.modules.replace_configured(preconf.into_rpc_module())?;
                 }
 
                 Ok(())
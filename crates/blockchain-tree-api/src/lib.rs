@@ -157,6 +157,53 @@ impl std::fmt::Display for BlockValidationKind {
     }
 }
 
+/// Determines how the state root of a block that extends the canonical chain is computed.
+///
+/// The state root of a block that doesn't extend the canonical chain is always computed
+/// sequentially.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateRootStrategy {
+    /// Computes the state root on the calling thread.
+    Sequential,
+    /// Computes the storage roots of the changed accounts in parallel, before computing the state
+    /// root.
+    #[default]
+    Parallel,
+}
+
+impl StateRootStrategy {
+    /// Returns the other strategy.
+    pub const fn other(&self) -> Self {
+        match self {
+            Self::Sequential => Self::Parallel,
+            Self::Parallel => Self::Sequential,
+        }
+    }
+}
+
+impl std::fmt::Display for StateRootStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sequential => f.write_str("sequential"),
+            Self::Parallel => f.write_str("parallel"),
+        }
+    }
+}
+
+impl std::str::FromStr for StateRootStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sequential" => Ok(Self::Sequential),
+            "parallel" => Ok(Self::Parallel),
+            _ => Err(format!(
+                "invalid state root strategy: {s}, expected `sequential` or `parallel`"
+            )),
+        }
+    }
+}
+
 /// All possible outcomes of a canonicalization attempt of [`BlockchainTreeEngine::make_canonical`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CanonicalOutcome {
@@ -30,7 +30,7 @@ use std::{
     collections::{btree_map::Entry, BTreeMap, HashSet},
     sync::Arc,
 };
-use tracing::{debug, error, info, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, trace_span, warn};
 
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// A Tree of chains.
@@ -430,6 +430,7 @@ where
             canonical_chain.inner(),
             parent,
             &self.externals,
+            &self.config,
             block_attachment,
             block_validation_kind,
         )?;
@@ -486,6 +487,7 @@ where
                 block_hashes,
                 canonical_chain.inner(),
                 &self.externals,
+                &self.config,
                 canonical_fork,
                 block_attachment,
                 block_validation_kind,
@@ -502,6 +504,7 @@ where
                 canonical_chain.inner(),
                 canonical_fork,
                 &self.externals,
+                &self.config,
                 block_validation_kind,
             )?;
             self.insert_chain(chain);
@@ -1207,6 +1210,7 @@ where
     }
 
     /// Write the given chain to the database as canonical.
+    #[instrument(level = "trace", skip_all, target = "blockchain_tree")]
     fn commit_canonical_to_database(
         &self,
         chain: Chain,
@@ -1225,6 +1229,7 @@ where
                 updates
             }
             None => {
+                let _span = trace_span!(target: "blockchain_tree", "state_root").entered();
                 debug!(target: "blockchain_tree", blocks = ?block_hash_numbers, "Recomputing state root for insert");
                 let provider = self
                     .externals
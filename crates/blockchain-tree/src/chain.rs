@@ -4,10 +4,10 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 
 use super::externals::TreeExternals;
-use crate::BundleStateDataRef;
+use crate::{metrics::StateRootMetrics, BlockchainTreeConfig, BundleStateDataRef};
 use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, InsertBlockErrorKind},
-    BlockAttachment, BlockValidationKind,
+    BlockAttachment, BlockValidationKind, StateRootStrategy,
 };
 use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
 use reth_db_api::database::Database;
@@ -72,6 +72,7 @@ impl AppendableChain {
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
     ) -> Result<Self, InsertBlockErrorKind>
@@ -94,6 +95,7 @@ impl AppendableChain {
             parent_header,
             state_provider,
             externals,
+            config,
             block_attachment,
             block_validation_kind,
         )?;
@@ -111,6 +113,7 @@ impl AppendableChain {
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_validation_kind: BlockValidationKind,
     ) -> Result<Self, InsertBlockErrorKind>
     where
@@ -140,6 +143,7 @@ impl AppendableChain {
             parent,
             bundle_state_data,
             externals,
+            config,
             BlockAttachment::HistoricalFork,
             block_validation_kind,
         )?;
@@ -172,6 +176,7 @@ impl AppendableChain {
         parent_block: &SealedHeader,
         bundle_state_data_provider: EDP,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
     ) -> Result<(ExecutionOutcome, Option<TrieUpdates>), BlockExecutionError>
@@ -209,7 +214,13 @@ impl AppendableChain {
         let block_hash = block.hash();
         let block = block.unseal();
 
-        let state = executor.execute((&block, U256::MAX).into())?;
+        let state = tracing::trace_span!(
+            target: "blockchain_tree::chain",
+            "execute_block",
+            number = block.number,
+            hash = %block_hash
+        )
+        .in_scope(|| executor.execute((&block, U256::MAX).into()))?;
         let BlockExecutionOutput { state, receipts, requests, .. } = state;
         externals
             .consensus
@@ -222,16 +233,77 @@ impl AppendableChain {
         // validation was requested.
         if block_validation_kind.is_exhaustive() {
             // calculate and check state root
+            let _span = tracing::trace_span!(
+                target: "blockchain_tree::chain",
+                "state_root",
+                number = block.number,
+                hash = %block_hash
+            )
+            .entered();
             let start = Instant::now();
             let (state_root, trie_updates) = if block_attachment.is_canonical() {
-                let mut execution_outcome =
-                    provider.block_execution_data_provider.execution_outcome().clone();
-                execution_outcome.extend(initial_execution_outcome.clone());
-                let hashed_state = execution_outcome.hash_state_slow();
-                ParallelStateRoot::new(consistent_view, hashed_state)
-                    .incremental_root_with_updates()
-                    .map(|(root, updates)| (root, Some(updates)))
-                    .map_err(ProviderError::from)?
+                let compute_state_root = |strategy| -> Result<_, ProviderError> {
+                    let start = Instant::now();
+                    let root = match strategy {
+                        StateRootStrategy::Sequential => {
+                            provider.state_root_with_updates(initial_execution_outcome.state())?
+                        }
+                        StateRootStrategy::Parallel => {
+                            let mut execution_outcome =
+                                provider.block_execution_data_provider.execution_outcome().clone();
+                            execution_outcome.extend(initial_execution_outcome.clone());
+                            let hashed_state = execution_outcome.hash_state_slow();
+                            ParallelStateRoot::new(consistent_view.clone(), hashed_state)
+                                .incremental_root_with_updates()?
+                        }
+                    };
+                    Ok((root, start.elapsed()))
+                };
+
+                let metrics = StateRootMetrics::default();
+                let strategy = config.state_root_strategy();
+                let ((state_root, trie_updates), elapsed) = compute_state_root(strategy)?;
+                metrics.record(strategy, elapsed);
+
+                if config.compare_state_root_strategies() {
+                    let other_strategy = strategy.other();
+                    let ((other_state_root, _), other_elapsed) =
+                        compute_state_root(other_strategy)?;
+                    metrics.record(other_strategy, other_elapsed);
+                    match strategy {
+                        StateRootStrategy::Sequential => {
+                            metrics.record_comparison(elapsed, other_elapsed)
+                        }
+                        StateRootStrategy::Parallel => {
+                            metrics.record_comparison(other_elapsed, elapsed)
+                        }
+                    }
+                    if other_state_root != state_root {
+                        metrics.record_mismatch();
+                        tracing::warn!(
+                            target: "blockchain_tree::chain",
+                            number = block.number,
+                            hash = %block_hash,
+                            %strategy,
+                            %state_root,
+                            %other_strategy,
+                            %other_state_root,
+                            "State root strategies computed different state roots"
+                        );
+                    }
+                    tracing::debug!(
+                        target: "blockchain_tree::chain",
+                        number = block.number,
+                        hash = %block_hash,
+                        %strategy,
+                        ?elapsed,
+                        %other_strategy,
+                        ?other_elapsed,
+                        "Compared state root strategies"
+                    );
+                }
+
+                (state_root, Some(trie_updates))
             } else {
                 (provider.state_root(initial_execution_outcome.state())?, None)
             };
@@ -275,6 +347,7 @@ impl AppendableChain {
         side_chain_block_hashes: BTreeMap<BlockNumber, BlockHash>,
         canonical_block_hashes: &BTreeMap<BlockNumber, BlockHash>,
         externals: &TreeExternals<DB, E>,
+        config: &BlockchainTreeConfig,
         canonical_fork: ForkBlock,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
@@ -297,6 +370,7 @@ impl AppendableChain {
             parent_block,
             bundle_state_data,
             externals,
+            config,
             block_attachment,
             block_validation_kind,
         )?;
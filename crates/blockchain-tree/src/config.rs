@@ -1,5 +1,7 @@
 //! Blockchain tree configuration
 
+use reth_blockchain_tree_api::StateRootStrategy;
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Copy, Debug)]
 pub struct BlockchainTreeConfig {
@@ -19,6 +21,11 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// How the state root of a block that extends the canonical chain is computed.
+    state_root_strategy: StateRootStrategy,
+    /// Whether the state root of a block that extends the canonical chain is also computed with
+    /// the other strategy, to compare the durations of both strategies.
+    compare_state_root_strategies: bool,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +40,8 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            state_root_strategy: StateRootStrategy::default(),
+            compare_state_root_strategies: false,
         }
     }
 }
@@ -54,9 +63,30 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            state_root_strategy: StateRootStrategy::default(),
+            compare_state_root_strategies: false,
         }
     }
 
+    /// Sets how the state root of a block that extends the canonical chain is computed.
+    pub const fn with_state_root_strategy(
+        mut self,
+        state_root_strategy: StateRootStrategy,
+    ) -> Self {
+        self.state_root_strategy = state_root_strategy;
+        self
+    }
+
+    /// Sets whether the state root of a block that extends the canonical chain is also computed
+    /// with the other strategy.
+    ///
+    /// This doubles the state root work of every block, and is meant for comparing the strategies
+    /// on the hardware of the node.
+    pub const fn with_compare_state_root_strategies(mut self, compare: bool) -> Self {
+        self.compare_state_root_strategies = compare;
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub const fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -88,4 +118,14 @@ impl BlockchainTreeConfig {
     pub const fn max_unconnected_blocks(&self) -> u32 {
         self.max_unconnected_blocks
     }
+
+    /// Return how the state root of a block that extends the canonical chain is computed.
+    pub const fn state_root_strategy(&self) -> StateRootStrategy {
+        self.state_root_strategy
+    }
+
+    /// Return whether the state root is also computed with the other strategy for comparison.
+    pub const fn compare_state_root_strategies(&self) -> bool {
+        self.compare_state_root_strategies
+    }
 }
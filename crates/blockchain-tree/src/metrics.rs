@@ -1,4 +1,5 @@
 use metrics::Histogram;
+use reth_blockchain_tree_api::StateRootStrategy;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
@@ -33,6 +34,43 @@ pub struct BlockBufferMetrics {
     pub blocks: Gauge,
 }
 
+/// Metrics for the state root computation of blocks that extend the canonical chain.
+#[derive(Metrics)]
+#[metrics(scope = "blockchain_tree.state_root")]
+pub(crate) struct StateRootMetrics {
+    /// Duration of the sequential state root computation.
+    sequential: Histogram,
+    /// Duration of the parallel state root computation.
+    parallel: Histogram,
+    /// The duration of the sequential computation divided by the duration of the parallel
+    /// computation, of the latest block for which both strategies were compared.
+    parallel_speedup: Gauge,
+    /// The number of blocks for which the strategies computed different state roots.
+    mismatches: Counter,
+}
+
+impl StateRootMetrics {
+    /// Records the duration of a state root computation with the given strategy.
+    pub(crate) fn record(&self, strategy: StateRootStrategy, duration: Duration) {
+        match strategy {
+            StateRootStrategy::Sequential => self.sequential.record(duration),
+            StateRootStrategy::Parallel => self.parallel.record(duration),
+        }
+    }
+
+    /// Records the comparison of the durations of both strategies for the same block.
+    pub(crate) fn record_comparison(&self, sequential: Duration, parallel: Duration) {
+        if !parallel.is_zero() {
+            self.parallel_speedup.set(sequential.as_secs_f64() / parallel.as_secs_f64());
+        }
+    }
+
+    /// Records that the strategies computed different state roots for the same block.
+    pub(crate) fn record_mismatch(&self) {
+        self.mismatches.increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct MakeCanonicalDurationsRecorder {
     start: Instant,
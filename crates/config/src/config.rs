@@ -1,6 +1,8 @@
 //! Configuration files.
 
-use reth_network::{PeersConfig, SessionsConfig};
+use reth_network::{
+    broadcast::BroadcastConfig, light::LightServingConfig, PeersConfig, SessionsConfig,
+};
 use reth_prune_types::PruneModes;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
@@ -25,6 +27,10 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for serving light clients.
+    pub light: LightServingConfig,
+    /// Configuration for the fan-out of transaction and block broadcasts.
+    pub broadcast: BroadcastConfig,
 }
 
 impl Config {
@@ -82,6 +88,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Index Sender Nonces stage configuration.
+    pub index_sender_nonces: IndexSenderNoncesConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -310,6 +320,40 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether to build the index of log addresses and topics, which is used to answer
+    /// `eth_getLogs` without scanning the receipts of every block in the range.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Sender nonce index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexSenderNoncesConfig {
+    /// Whether to build the index of the transaction hash of each sender and nonce, which is used
+    /// to answer `ots_getTransactionBySenderAndNonce` without searching the sender's history.
+    pub enabled: bool,
+    /// The maximum number of transactions to process before writing to disk.
+    pub chunk_size: u64,
+}
+
+impl Default for IndexSenderNoncesConfig {
+    fn default() -> Self {
+        Self { enabled: false, chunk_size: 5_000_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -723,4 +767,62 @@ connect_trusted_nodes_only = true
         let conf: Config = toml::from_str(trusted_nodes_only).unwrap();
         assert!(conf.peers.trusted_nodes_only);
     }
+
+    #[test]
+    fn test_conf_max_message_sizes() {
+        let max_message_sizes = r"#
+[sessions.max_message_sizes]
+light = 65536
+#";
+        let conf: Config = toml::from_str(max_message_sizes).unwrap();
+        assert_eq!(conf.sessions.max_message_sizes.get("light"), Some(&65536));
+        assert_eq!(
+            conf.sessions.session_command_buffer,
+            SessionsConfig::default().session_command_buffer
+        );
+    }
+
+    #[test]
+    fn test_conf_light() {
+        let light = r"#
+[light]
+enabled = true
+max_bytes_per_second = 1024
+#";
+        let conf: Config = toml::from_str(light).unwrap();
+        assert!(conf.light.enabled);
+        assert_eq!(conf.light.max_bytes_per_second, 1024);
+        assert_eq!(
+            conf.light.max_request_items,
+            reth_network::light::DEFAULT_MAX_LIGHT_REQUEST_ITEMS
+        );
+
+        let conf: Config = toml::from_str("").unwrap();
+        assert!(!conf.light.enabled);
+    }
+
+    #[test]
+    fn test_conf_broadcast() {
+        use reth_network::broadcast::{BroadcastFanout, EthVersionFanout};
+
+        let broadcast = r#"
+[broadcast.transactions]
+eth68 = { Fixed = 0 }
+
+[broadcast.blocks]
+eth66 = "All"
+"#;
+        let conf: Config = toml::from_str(broadcast).unwrap();
+        assert_eq!(
+            conf.broadcast.transactions,
+            EthVersionFanout::default().with_eth68(BroadcastFanout::Fixed(0))
+        );
+        assert_eq!(
+            conf.broadcast.blocks,
+            EthVersionFanout::default().with_eth66(BroadcastFanout::All)
+        );
+
+        let conf: Config = toml::from_str("").unwrap();
+        assert_eq!(conf.broadcast, BroadcastConfig::default());
+    }
 }
@@ -0,0 +1,268 @@
+//! A driver of the beacon consensus engine that takes the place of a consensus client.
+
+use reth_beacon_consensus::{
+    BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError, BeaconOnNewPayloadError,
+};
+use reth_chainspec::ChainSpec;
+use reth_engine_primitives::{BuiltPayload, EngineTypes};
+use reth_payload_builder::{error::PayloadBuilderError, PayloadBuilderHandle, PayloadId};
+use reth_primitives::{Address, SealedHeader, B256};
+use reth_provider::{BlockReaderIdExt, ProviderError};
+use reth_rpc_types::engine::{
+    CancunPayloadFields, ForkchoiceState, PayloadAttributes, PayloadStatusEnum,
+};
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, warn};
+
+/// Builds the attributes of the payloads that the [`LocalEngineDriver`] requests.
+pub trait PayloadAttributesBuilder<Attributes>: fmt::Debug + Send + Sync + 'static {
+    /// Returns the attributes of the payload that is built on top of the given parent.
+    fn payload_attributes(&self, chain_spec: &ChainSpec, parent: &SealedHeader) -> Attributes;
+}
+
+/// Builds the attributes of Ethereum payloads.
+///
+/// The withdrawals of the payloads are empty after Shanghai, and the parent beacon block root is
+/// zero after Cancun.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EthPayloadAttributesBuilder {
+    /// The suggested recipient of the fees of the payloads.
+    suggested_fee_recipient: Address,
+}
+
+impl EthPayloadAttributesBuilder {
+    /// Sets the suggested recipient of the fees of the payloads.
+    pub const fn with_suggested_fee_recipient(mut self, suggested_fee_recipient: Address) -> Self {
+        self.suggested_fee_recipient = suggested_fee_recipient;
+        self
+    }
+}
+
+impl PayloadAttributesBuilder<PayloadAttributes> for EthPayloadAttributesBuilder {
+    fn payload_attributes(
+        &self,
+        chain_spec: &ChainSpec,
+        parent: &SealedHeader,
+    ) -> PayloadAttributes {
+        let timestamp = next_timestamp(parent);
+        PayloadAttributes {
+            timestamp,
+            // there is no beacon chain randomness, the parent hash is used instead
+            prev_randao: parent.hash(),
+            suggested_fee_recipient: self.suggested_fee_recipient,
+            withdrawals: chain_spec.is_shanghai_active_at_timestamp(timestamp).then(Vec::new),
+            parent_beacon_block_root: chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+        }
+    }
+}
+
+/// Returns the timestamp of the child of the given header: the current time, but at least one
+/// second after the parent.
+fn next_timestamp(parent: &SealedHeader) -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    now.max(parent.timestamp + 1)
+}
+
+/// Errors of the [`LocalEngineDriver`].
+#[derive(Debug, thiserror::Error)]
+pub enum LocalEngineDriverError {
+    /// The head of the chain couldn't be read.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// There is no canonical head.
+    #[error("the canonical head is unknown")]
+    UnknownHead,
+    /// The payload builder doesn't know the requested payload.
+    #[error("payload {0} is unknown")]
+    UnknownPayload(PayloadId),
+    /// The payload couldn't be built.
+    #[error(transparent)]
+    PayloadBuilder(#[from] PayloadBuilderError),
+    /// The engine failed to process a new payload.
+    #[error(transparent)]
+    NewPayload(#[from] BeaconOnNewPayloadError),
+    /// The engine failed to process a forkchoice update.
+    #[error(transparent)]
+    ForkchoiceUpdate(#[from] BeaconForkChoiceUpdateError),
+    /// The engine didn't accept a payload or forkchoice update.
+    #[error("the engine returned {status} for block {block_hash}")]
+    Rejected {
+        /// The hash of the block.
+        block_hash: B256,
+        /// The status returned by the engine.
+        status: PayloadStatusEnum,
+    },
+}
+
+/// Drives the beacon consensus engine on an interval, in place of a consensus client, for dev and
+/// private networks.
+///
+/// On every tick, the driver resolves the payload that it requested on the previous tick, submits
+/// it to the engine as a new payload and makes it the head, safe and finalized block with a
+/// forkchoice update. The forkchoice update carries the attributes of the next payload, which
+/// starts the payload job that is resolved on the next tick.
+pub struct LocalEngineDriver<Engine: EngineTypes, Provider> {
+    /// The chain spec the payload attributes are built for.
+    chain_spec: Arc<ChainSpec>,
+    /// The provider to read the initial head from.
+    provider: Provider,
+    /// The handle to the beacon consensus engine.
+    to_engine: BeaconConsensusEngineHandle<Engine>,
+    /// The handle to the payload builder the payloads are resolved from.
+    payload_builder: PayloadBuilderHandle<Engine>,
+    /// Builds the attributes of the requested payloads.
+    attributes_builder: Box<dyn PayloadAttributesBuilder<Engine::PayloadAttributes>>,
+    /// The interval at which blocks are produced.
+    interval: Duration,
+}
+
+impl<Engine, Provider> LocalEngineDriver<Engine, Provider>
+where
+    Engine: EngineTypes,
+    Provider: BlockReaderIdExt,
+{
+    /// Creates a new [`LocalEngineDriver`].
+    pub fn new(
+        chain_spec: Arc<ChainSpec>,
+        provider: Provider,
+        to_engine: BeaconConsensusEngineHandle<Engine>,
+        payload_builder: PayloadBuilderHandle<Engine>,
+        attributes_builder: Box<dyn PayloadAttributesBuilder<Engine::PayloadAttributes>>,
+        interval: Duration,
+    ) -> Self {
+        Self { chain_spec, provider, to_engine, payload_builder, attributes_builder, interval }
+    }
+
+    /// Runs the driver until the engine stops.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut head = None;
+        let mut pending_payload = None;
+        loop {
+            interval.tick().await;
+            match self.advance(&mut head, &mut pending_payload).await {
+                Ok(()) => {}
+                Err(LocalEngineDriverError::NewPayload(
+                    BeaconOnNewPayloadError::EngineUnavailable,
+                )) |
+                Err(LocalEngineDriverError::ForkchoiceUpdate(
+                    BeaconForkChoiceUpdateError::EngineUnavailable,
+                )) => {
+                    debug!(target: "consensus::driver", "Beacon consensus engine stopped");
+                    return
+                }
+                Err(err) => {
+                    warn!(target: "consensus::driver", %err, "Failed to advance the chain");
+                }
+            }
+        }
+    }
+
+    /// Submits the pending payload, if any, and requests the next payload on top of the head.
+    ///
+    /// If the pending payload fails, the next payload is requested on top of the current head on
+    /// the next tick.
+    async fn advance(
+        &self,
+        head: &mut Option<SealedHeader>,
+        pending_payload: &mut Option<PayloadId>,
+    ) -> Result<(), LocalEngineDriverError> {
+        if let Some(payload_id) = pending_payload.take() {
+            let payload = self
+                .payload_builder
+                .resolve(payload_id)
+                .await
+                .ok_or(LocalEngineDriverError::UnknownPayload(payload_id))??;
+            let block = payload.block().clone();
+            let block_hash = block.hash();
+
+            let cancun_fields = block.parent_beacon_block_root.map(|parent_beacon_block_root| {
+                CancunPayloadFields {
+                    parent_beacon_block_root,
+                    versioned_hashes: block.blob_versioned_hashes_iter().copied().collect(),
+                }
+            });
+            let header = block.header.clone();
+            let (payload, _) = block_to_payload(block);
+
+            let status = self.to_engine.new_payload(payload, cancun_fields).await?;
+            if !status.is_valid() {
+                return Err(LocalEngineDriverError::Rejected { block_hash, status: status.status })
+            }
+            *head = Some(header);
+        }
+
+        let parent = match head {
+            Some(head) => head.clone(),
+            None => self.provider.latest_header()?.ok_or(LocalEngineDriverError::UnknownHead)?,
+        };
+        let state = ForkchoiceState {
+            head_block_hash: parent.hash(),
+            safe_block_hash: parent.hash(),
+            finalized_block_hash: parent.hash(),
+        };
+        let attributes = self.attributes_builder.payload_attributes(&self.chain_spec, &parent);
+
+        let updated = self.to_engine.fork_choice_updated(state, Some(attributes)).await?;
+        if !updated.is_valid() {
+            return Err(LocalEngineDriverError::Rejected {
+                block_hash: parent.hash(),
+                status: updated.payload_status.status,
+            })
+        }
+        debug!(
+            target: "consensus::driver",
+            head = ?parent.num_hash(),
+            payload_id = ?updated.payload_id,
+            "Requested next payload"
+        );
+        *head = Some(parent);
+        *pending_payload = updated.payload_id;
+
+        Ok(())
+    }
+}
+
+impl<Engine: EngineTypes, Provider> fmt::Debug for LocalEngineDriver<Engine, Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalEngineDriver")
+            .field("attributes_builder", &self.attributes_builder)
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_primitives::Header;
+
+    #[test]
+    fn eth_payload_attributes_follow_forks() {
+        let parent = Header { timestamp: 1, ..Default::default() }.seal_slow();
+
+        let paris = ChainSpecBuilder::default().chain(MAINNET.chain).genesis(Default::default());
+        let attributes = EthPayloadAttributesBuilder::default()
+            .payload_attributes(&paris.clone().paris_activated().build(), &parent);
+        assert!(attributes.timestamp > parent.timestamp);
+        assert_eq!(attributes.prev_randao, parent.hash());
+        assert_eq!(attributes.withdrawals, None);
+        assert_eq!(attributes.parent_beacon_block_root, None);
+
+        let attributes = EthPayloadAttributesBuilder::default()
+            .payload_attributes(&paris.cancun_activated().build(), &parent);
+        assert_eq!(attributes.withdrawals, Some(Vec::new()));
+        assert_eq!(attributes.parent_beacon_block_root, Some(B256::ZERO));
+    }
+}
@@ -4,6 +4,9 @@
 //! The Mining task polls a [`MiningMode`], and will return a list of transactions that are ready to
 //! be mined.
 //!
+//! Alternatively, the [`LocalEngineDriver`] drives the beacon consensus engine with the blocks of
+//! the payload builder, in place of a consensus client.
+//!
 //! These downloaders poll the miner, assemble the block, and return transactions that are ready to
 //! be mined.
 
@@ -38,10 +41,15 @@ use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGua
 use tracing::trace;
 
 mod client;
+mod driver;
 mod mode;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use driver::{
+    EthPayloadAttributesBuilder, LocalEngineDriver, LocalEngineDriverError,
+    PayloadAttributesBuilder,
+};
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
 use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
 pub use task::MiningTask;
@@ -18,7 +18,7 @@ use std::{
     task::{Context, Poll},
 };
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
-use tracing::{debug, error, warn};
+use tracing::{debug, error, warn, Span};
 
 /// A Future that listens for new ready transactions and puts new blocks into storage
 pub struct MiningTask<Client, Pool: TransactionPool, Executor, Engine: EngineTypes> {
@@ -161,6 +161,7 @@ where
                                     state,
                                     payload_attrs: None,
                                     tx,
+                                    span: Span::current(),
                                 });
                                 debug!(target: "consensus::auto", ?state, "Sent fork choice update");
 
@@ -1,7 +1,7 @@
 use crate::engine::forkchoice::ForkchoiceStatus;
-use reth_primitives::{SealedBlock, SealedHeader, B256};
-use reth_rpc_types::engine::ForkchoiceState;
-use std::{sync::Arc, time::Duration};
+use reth_primitives::{BlockNumHash, SealedBlock, SealedHeader, B256};
+use reth_rpc_types::{engine::ForkchoiceState, EngineEvent, EngineForkchoiceStatus};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 /// Events emitted by [`crate::BeaconConsensusEngine`].
 #[derive(Clone, Debug)]
@@ -16,6 +16,10 @@ pub enum BeaconConsensusEngineEvent {
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// A block was found to be invalid, and the validation error
+    InvalidBlock(Box<SealedHeader>, String),
+    /// An invalid block was dumped, and the path of the dump
+    InvalidBlockDumped(BlockNumHash, PathBuf),
 }
 
 /// Progress of the consensus engine during live sync.
@@ -29,3 +33,51 @@ pub enum ConsensusEngineLiveSyncProgress {
         target: B256,
     },
 }
+
+impl From<BeaconConsensusEngineEvent> for EngineEvent {
+    fn from(event: BeaconConsensusEngineEvent) -> Self {
+        match event {
+            BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status) => {
+                let status = match status {
+                    ForkchoiceStatus::Valid => EngineForkchoiceStatus::Valid,
+                    ForkchoiceStatus::Invalid => EngineForkchoiceStatus::Invalid,
+                    ForkchoiceStatus::Syncing => EngineForkchoiceStatus::Syncing,
+                };
+                Self::ForkchoiceUpdated { state, status }
+            }
+            BeaconConsensusEngineEvent::CanonicalBlockAdded(block, elapsed) => {
+                Self::CanonicalBlockAdded {
+                    number: block.number,
+                    hash: block.hash(),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                }
+            }
+            BeaconConsensusEngineEvent::CanonicalChainCommitted(head, elapsed) => {
+                Self::CanonicalChainCommitted {
+                    number: head.number,
+                    hash: head.hash(),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                }
+            }
+            BeaconConsensusEngineEvent::LiveSyncProgress(
+                ConsensusEngineLiveSyncProgress::DownloadingBlocks { remaining_blocks, target },
+            ) => Self::LiveSyncProgress { remaining_blocks, target },
+            BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
+                Self::ForkBlockAdded { number: block.number, hash: block.hash() }
+            }
+            BeaconConsensusEngineEvent::InvalidBlock(header, error) => Self::InvalidBlock {
+                number: header.number,
+                hash: header.hash(),
+                parent_hash: header.parent_hash,
+                error,
+            },
+            BeaconConsensusEngineEvent::InvalidBlockDumped(block, path) => {
+                Self::InvalidBlockDumped {
+                    number: block.number,
+                    hash: block.hash,
+                    path: path.display().to_string(),
+                }
+            }
+        }
+    }
+}
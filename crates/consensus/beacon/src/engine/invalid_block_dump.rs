@@ -0,0 +1,289 @@
+//! Dumps of invalid blocks for post-mortem analysis.
+
+use reth_fs_util::{self as fs, FsPathError};
+use reth_primitives::{BlockNumber, Header, SealedBlock, B256};
+use reth_rpc_types::engine::{CancunPayloadFields, ExecutionPayload};
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The default maximum number of dumps kept in the dump directory.
+pub const DEFAULT_MAX_INVALID_BLOCK_DUMPS: usize = 100;
+
+/// How the engine received an invalid block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvalidBlockSource {
+    /// The block was sent by the consensus layer in an `engine_newPayload` call.
+    NewPayload,
+    /// The block was downloaded to reach the head of a forkchoice update.
+    DownloadedBlock,
+    /// The block is the head of a forkchoice update that failed to become canonical.
+    ForkchoiceUpdated,
+    /// The block was found to be invalid by the pipeline, while syncing to the head of a
+    /// forkchoice update.
+    PipelineSync,
+}
+
+/// The number, hash and state root of a block that is referenced by an [`InvalidBlockDump`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockReference {
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The state root of the block.
+    pub state_root: B256,
+}
+
+impl BlockReference {
+    /// Creates the reference of the block with the given header and hash.
+    pub const fn new(header: &Header, hash: B256) -> Self {
+        Self { number: header.number, hash, state_root: header.state_root }
+    }
+}
+
+/// The on-disk representation of an invalid block.
+///
+/// The payload and cancun fields can be sent in an `engine_newPayload` call to reproduce the
+/// validation of the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlockDump {
+    /// How the engine received the block.
+    pub source: InvalidBlockSource,
+    /// The number of the block.
+    pub number: BlockNumber,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The hash of the parent of the block.
+    pub parent_hash: B256,
+    /// Why the block is invalid.
+    pub error: String,
+    /// The block as execution payload, if it is known.
+    pub payload: Option<ExecutionPayload>,
+    /// The Cancun-specific fields of the payload, if any.
+    pub cancun_fields: Option<CancunPayloadFields>,
+    /// The parent of the block, if it is known.
+    pub parent: Option<BlockReference>,
+    /// The canonical head when the block was found to be invalid.
+    pub canonical_head: Option<BlockReference>,
+}
+
+impl InvalidBlockDump {
+    /// Creates the dump of an invalid payload, as sent by the consensus layer.
+    pub fn from_payload(
+        source: InvalidBlockSource,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+        error: impl ToString,
+    ) -> Self {
+        Self {
+            source,
+            number: payload.block_number(),
+            hash: payload.block_hash(),
+            parent_hash: payload.parent_hash(),
+            error: error.to_string(),
+            payload: Some(payload),
+            cancun_fields,
+            parent: None,
+            canonical_head: None,
+        }
+    }
+
+    /// Creates the dump of an invalid block.
+    pub fn from_block(
+        source: InvalidBlockSource,
+        block: SealedBlock,
+        error: impl ToString,
+    ) -> Self {
+        let cancun_fields =
+            block.parent_beacon_block_root.map(|parent_beacon_block_root| CancunPayloadFields {
+                parent_beacon_block_root,
+                versioned_hashes: block.blob_versioned_hashes_iter().copied().collect(),
+            });
+        let (payload, _) = block_to_payload(block);
+        Self::from_payload(source, payload, cancun_fields, error)
+    }
+
+    /// Creates the dump of an invalid block whose body is unknown.
+    pub fn from_header(
+        source: InvalidBlockSource,
+        header: &Header,
+        hash: B256,
+        error: impl ToString,
+    ) -> Self {
+        Self {
+            source,
+            number: header.number,
+            hash,
+            parent_hash: header.parent_hash,
+            error: error.to_string(),
+            payload: None,
+            cancun_fields: None,
+            parent: None,
+            canonical_head: None,
+        }
+    }
+
+    /// Sets the parent of the block.
+    pub const fn with_parent(mut self, parent: Option<BlockReference>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Sets the canonical head when the block was found to be invalid.
+    pub const fn with_canonical_head(mut self, canonical_head: BlockReference) -> Self {
+        self.canonical_head = Some(canonical_head);
+        self
+    }
+}
+
+/// Errors of the [`InvalidBlockDumper`].
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidBlockDumpError {
+    /// The dump couldn't be written.
+    #[error(transparent)]
+    Fs(#[from] FsPathError),
+    /// The dump couldn't be serialized.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Writes [`InvalidBlockDump`]s as JSON files to a directory.
+///
+/// Only the most recent dumps are kept, older dumps are removed when a new dump is written.
+#[derive(Debug, Clone)]
+pub struct InvalidBlockDumper {
+    /// The path to the directory that stores the dumps.
+    path: PathBuf,
+    /// The maximum number of dumps kept in the directory.
+    max_dumps: usize,
+}
+
+impl InvalidBlockDumper {
+    /// Creates a new [`InvalidBlockDumper`] that writes to the given directory and keeps at most
+    /// [`DEFAULT_MAX_INVALID_BLOCK_DUMPS`] dumps.
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, max_dumps: DEFAULT_MAX_INVALID_BLOCK_DUMPS }
+    }
+
+    /// Sets the maximum number of dumps kept in the directory.
+    pub const fn with_max_dumps(mut self, max_dumps: usize) -> Self {
+        self.max_dumps = max_dumps;
+        self
+    }
+
+    /// Returns the path to the directory that stores the dumps.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the dump to a file named after the current time and the block, and returns the path
+    /// of the file.
+    pub fn dump(&self, dump: &InvalidBlockDump) -> Result<PathBuf, InvalidBlockDumpError> {
+        fs::create_dir_all(&self.path)?;
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.path.join(format!("{timestamp:020}-{}-{}.json", dump.number, dump.hash));
+        fs::write(&path, serde_json::to_vec_pretty(dump)?)?;
+        self.remove_old_dumps()?;
+        Ok(path)
+    }
+
+    /// Removes the oldest dumps until at most `max_dumps` dumps are left.
+    fn remove_old_dumps(&self) -> Result<(), InvalidBlockDumpError> {
+        let mut dumps = fs::read_dir(&self.path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .collect::<Vec<_>>();
+        if dumps.len() <= self.max_dumps {
+            return Ok(())
+        }
+
+        // file names start with the zero-padded timestamp, so they sort from oldest to newest
+        dumps.sort_unstable();
+        let excess = dumps.len() - self.max_dumps;
+        for path in dumps.into_iter().take(excess) {
+            match fs::remove_file(&path) {
+                // the dump may have been removed concurrently by another write
+                Err(FsPathError::RemoveFile { source, .. })
+                    if source.kind() == std::io::ErrorKind::NotFound => {}
+                res => res?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Bytes, Withdrawals};
+
+    #[test]
+    fn dump_invalid_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let dumper = InvalidBlockDumper::new(dir.path().join("invalid"));
+
+        let block = Block {
+            header: Header {
+                number: 10,
+                parent_hash: B256::with_last_byte(9),
+                extra_data: Bytes::from_static(b"invalid"),
+                ..Default::default()
+            },
+            withdrawals: Some(Withdrawals::default()),
+            ..Default::default()
+        }
+        .seal_slow();
+        let parent = Header { number: 9, ..Default::default() };
+        let dump = InvalidBlockDump::from_block(
+            InvalidBlockSource::NewPayload,
+            block.clone(),
+            "state root mismatch",
+        )
+        .with_parent(Some(BlockReference::new(&parent, block.parent_hash)));
+        assert_eq!(dump.number, 10);
+        assert_eq!(dump.hash, block.hash());
+        assert_eq!(dump.parent.map(|parent| parent.number), Some(9));
+        assert!(dump.cancun_fields.is_none());
+
+        let path = dumper.dump(&dump).unwrap();
+        assert!(path.starts_with(dumper.path()));
+        let read: InvalidBlockDump = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(read, dump);
+    }
+    #[test]
+    fn remove_old_dumps() {
+        let dir = tempfile::tempdir().unwrap();
+        let dumper = InvalidBlockDumper::new(dir.path().to_path_buf()).with_max_dumps(2);
+
+        let paths = (0..4u64)
+            .map(|number| {
+                let header = Header { number, ..Default::default() };
+                let dump = InvalidBlockDump::from_header(
+                    InvalidBlockSource::PipelineSync,
+                    &header,
+                    B256::with_last_byte(number as u8),
+                    "bad block",
+                );
+                let path = dumper.dump(&dump).unwrap();
+                // dumps are named after the current time in milliseconds
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                path
+            })
+            .collect::<Vec<_>>();
+
+        assert!(!paths[0].exists());
+        assert!(!paths[1].exists());
+        assert!(paths[2].exists());
+        assert!(paths[3].exists());
+    }
+}
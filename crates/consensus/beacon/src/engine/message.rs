@@ -13,6 +13,7 @@ use std::{
     task::{ready, Context, Poll},
 };
 use tokio::sync::oneshot;
+use tracing::Span;
 
 /// Represents the outcome of forkchoice update.
 ///
@@ -147,6 +148,8 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
         cancun_fields: Option<CancunPayloadFields>,
         /// The sender for returning payload status result.
         tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
+        /// The span of the engine API call, which the processing of the payload is recorded in.
+        span: Span,
     },
     /// Message with updated forkchoice state.
     ForkchoiceUpdated {
@@ -156,6 +159,8 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
         payload_attrs: Option<Engine::PayloadAttributes>,
         /// The sender for returning forkchoice updated result.
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
+        /// The span of the engine API call, which the processing of the update is recorded in.
+        span: Span,
     },
     /// Message with exchanged transition configuration.
     TransitionConfigurationExchanged,
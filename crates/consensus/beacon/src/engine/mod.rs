@@ -55,9 +55,18 @@ pub use error::{
 mod invalid_headers;
 use invalid_headers::InvalidHeaderCache;
 
+mod new_payload_cache;
+use new_payload_cache::NewPayloadCache;
+
 mod event;
 pub use event::{BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress};
 
+mod invalid_block_dump;
+pub use invalid_block_dump::{
+    BlockReference, InvalidBlockDump, InvalidBlockDumpError, InvalidBlockDumper,
+    InvalidBlockSource, DEFAULT_MAX_INVALID_BLOCK_DUMPS,
+};
+
 mod handle;
 pub use handle::BeaconConsensusEngineHandle;
 
@@ -82,6 +91,9 @@ pub mod test_utils;
 /// The maximum number of invalid headers that can be tracked by the engine.
 const MAX_INVALID_HEADERS: u32 = 512u32;
 
+/// The maximum number of `engine_newPayload` outcomes that are cached by the engine.
+const MAX_NEW_PAYLOAD_STATUSES: u32 = 64u32;
+
 /// The largest gap for which the tree will be used for sync. See docs for `pipeline_run_threshold`
 /// for more information.
 ///
@@ -184,8 +196,8 @@ where
     payload_builder: PayloadBuilderHandle<EngineT>,
     /// Validator for execution payloads
     payload_validator: ExecutionPayloadValidator,
-    /// Current blockchain tree action.
-    blockchain_tree_action: Option<BlockchainTreeAction<EngineT>>,
+    /// Current blockchain tree action, and the span of the engine API call it originates from.
+    blockchain_tree_action: Option<(BlockchainTreeAction<EngineT>, Span)>,
     /// Pending forkchoice update.
     /// It is recorded if we cannot process the forkchoice update because
     /// a hook with database read-write access is active.
@@ -199,6 +211,9 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Caches the outcome of recent `engine_newPayload` calls, so that retried calls for the same
+    /// payload don't execute the block again.
+    new_payload_cache: NewPayloadCache,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
     /// check whether or not we can connect the block to the current canonical chain. If we can't,
     /// we need to download and execute the missing parents of that block.
@@ -214,6 +229,10 @@ where
     hooks: EngineHooksController,
     /// Sender for engine events.
     event_sender: EventSender<BeaconConsensusEngineEvent>,
+    /// Writes the blocks that are found to be invalid to disk, if configured.
+    invalid_block_dumper: Option<InvalidBlockDumper>,
+    /// Used to write the dumps of invalid blocks off the engine loop.
+    task_spawner: Box<dyn TaskSpawner>,
     /// Consensus engine metrics.
     metrics: EngineMetrics,
 }
@@ -310,11 +329,14 @@ where
             forkchoice_state_tracker: Default::default(),
             payload_builder,
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            new_payload_cache: NewPayloadCache::new(MAX_NEW_PAYLOAD_STATUSES),
             blockchain_tree_action: None,
             pending_forkchoice_update: None,
             pipeline_run_threshold,
             hooks: EngineHooksController::new(hooks),
             event_sender,
+            invalid_block_dumper: None,
+            task_spawner,
             metrics: EngineMetrics::default(),
         };
 
@@ -331,6 +353,63 @@ where
         Ok((this, handle))
     }
 
+    /// Writes the blocks that are found to be invalid to disk with the given dumper, see
+    /// [`InvalidBlockDump`].
+    ///
+    /// A [`BeaconConsensusEngineEvent::InvalidBlockDumped`] event is emitted for every dump.
+    pub fn with_invalid_block_dumper(mut self, dumper: InvalidBlockDumper) -> Self {
+        self.invalid_block_dumper = Some(dumper);
+        self
+    }
+
+    /// Writes the dump of an invalid block to disk, adding references to the parent of the block
+    /// and to the canonical head.
+    ///
+    /// The dump is only created if a dumper is configured, and is written on the blocking pool.
+    fn dump_invalid_block(&self, dump: impl FnOnce() -> InvalidBlockDump) {
+        let Some(dumper) = self.invalid_block_dumper.clone() else { return };
+
+        let dump = dump();
+        let parent = self
+            .blockchain
+            .find_block_by_hash(dump.parent_hash, BlockSource::Any)
+            .ok()
+            .flatten()
+            .map(|parent| BlockReference::new(&parent.header, dump.parent_hash));
+        let head = self.blockchain.get_canonical_head();
+        let dump = dump
+            .with_parent(parent)
+            .with_canonical_head(BlockReference::new(head.header(), head.hash()));
+
+        let event_sender = self.event_sender.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            match dumper.dump(&dump) {
+                Ok(path) => {
+                    debug!(
+                        target: "consensus::engine",
+                        number=dump.number,
+                        hash=?dump.hash,
+                        ?path,
+                        "Dumped invalid block"
+                    );
+                    event_sender.notify(BeaconConsensusEngineEvent::InvalidBlockDumped(
+                        BlockNumHash::new(dump.number, dump.hash),
+                        path,
+                    ));
+                }
+                Err(error) => {
+                    warn!(
+                        target: "consensus::engine",
+                        number=dump.number,
+                        hash=?dump.hash,
+                        %error,
+                        "Failed to dump invalid block"
+                    );
+                }
+            }
+        }));
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -343,8 +422,11 @@ where
     }
 
     /// Set the next blockchain tree action.
+    ///
+    /// The action is processed in the current span, so that it is recorded in the span of the
+    /// engine API call it originates from.
     fn set_blockchain_tree_action(&mut self, action: BlockchainTreeAction<EngineT>) {
-        let previous_action = self.blockchain_tree_action.replace(action);
+        let previous_action = self.blockchain_tree_action.replace((action, Span::current()));
         debug_assert!(previous_action.is_none(), "Pre-existing action found");
     }
 
@@ -987,6 +1069,20 @@ where
         match &error {
             CanonicalError::Validation(BlockValidationError::BlockPreMerge { .. }) => {
                 warn!(target: "consensus::engine", %error, ?state, "Failed to canonicalize the head hash");
+                if self.invalid_block_dumper.is_some() {
+                    // the dump is best-effort, a failed lookup must not change the verdict
+                    if let Ok(Some(block)) =
+                        self.blockchain.find_block_by_hash(state.head_block_hash, BlockSource::Any)
+                    {
+                        self.dump_invalid_block(|| {
+                            InvalidBlockDump::from_block(
+                                InvalidBlockSource::ForkchoiceUpdated,
+                                block.seal(state.head_block_hash),
+                                &error,
+                            )
+                        });
+                    }
+                }
                 return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
                     validation_error: error.to_string(),
                 })
@@ -1104,6 +1200,10 @@ where
         //
         // This validation **MUST** be instantly run in all cases even during active sync process.
         let parent_hash = payload.parent_hash();
+        // the payload is consumed by the validation, so it's kept for the dump of a malformed
+        // payload
+        let dump_payload =
+            self.invalid_block_dumper.is_some().then(|| (payload.clone(), cancun_fields.clone()));
         let block = match self
             .payload_validator
             .ensure_well_formed_payload(payload, cancun_fields.into())
@@ -1111,6 +1211,16 @@ where
             Ok(block) => block,
             Err(error) => {
                 error!(target: "consensus::engine", %error, "Invalid payload");
+                if let Some((payload, cancun_fields)) = dump_payload {
+                    self.dump_invalid_block(|| {
+                        InvalidBlockDump::from_payload(
+                            InvalidBlockSource::NewPayload,
+                            payload,
+                            cancun_fields,
+                            &error,
+                        )
+                    });
+                }
                 // we need to convert the error to a payload status (response to the CL)
 
                 let latest_valid_hash =
@@ -1129,6 +1239,17 @@ where
             }
         };
 
+        // the payload is well formed, so a cached outcome for its hash applies to it
+        if let Some(status) = self.new_payload_cache.get(&block.hash()) {
+            trace!(
+                target: "consensus::engine",
+                hash=?block.hash(),
+                ?status,
+                "Returning cached payload status"
+            );
+            return Ok(Either::Left(status))
+        }
+
         let mut lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(block.hash());
         if lowest_buffered_ancestor == block.hash() {
             lowest_buffered_ancestor = block.parent_hash;
@@ -1439,6 +1560,14 @@ where
         // Pipeline unwound, memorize the invalid block and wait for CL for next sync target.
         if let ControlFlow::Unwind { bad_block, .. } = ctrl {
             warn!(target: "consensus::engine", invalid_hash=?bad_block.hash(), invalid_number=?bad_block.number, "Bad block detected in unwind");
+            self.dump_invalid_block(|| {
+                InvalidBlockDump::from_header(
+                    InvalidBlockSource::PipelineSync,
+                    bad_block.header(),
+                    bad_block.hash(),
+                    "bad block detected in pipeline unwind",
+                )
+            });
             // update the `invalid_headers` cache with the new invalid header
             self.invalid_headers.insert(*bad_block);
             return Ok(())
@@ -1657,6 +1786,17 @@ where
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
                         // keep track of the invalid header
+                        self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                            Box::new(block.header.clone()),
+                            error.to_string(),
+                        ));
+                        self.dump_invalid_block(|| {
+                            InvalidBlockDump::from_block(
+                                InvalidBlockSource::NewPayload,
+                                block.clone(),
+                                &error,
+                            )
+                        });
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
                             PayloadStatusEnum::Invalid { validation_error: error.to_string() },
@@ -1685,6 +1825,7 @@ where
                     self.sync.cancel_full_block_request(block_hash);
                 }
 
+                self.new_payload_cache.insert(block_hash, &status);
                 trace!(target: "consensus::engine", ?status, "Returning payload status");
                 let _ = tx.send(Ok(status));
             }
@@ -1714,6 +1855,7 @@ where
                     }
                 };
 
+                self.new_payload_cache.insert(payload_num_hash.hash, &status);
                 trace!(target: "consensus::engine", ?status, "Returning payload status");
                 let _ = tx.send(Ok(status));
             }
@@ -1766,6 +1908,17 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                                Box::new(block.header.clone()),
+                                err.to_string(),
+                            ));
+                            self.dump_invalid_block(|| {
+                                InvalidBlockDump::from_block(
+                                    InvalidBlockSource::DownloadedBlock,
+                                    block.clone(),
+                                    &err,
+                                )
+                            });
                             self.invalid_headers.insert(block.header);
                         }
                     }
@@ -1818,8 +1971,8 @@ where
 
                 // Process any blockchain tree action result as set forth during engine message
                 // processing.
-                if let Some(action) = this.blockchain_tree_action.take() {
-                    match this.on_blockchain_tree_action(action) {
+                if let Some((action, span)) = this.blockchain_tree_action.take() {
+                    match span.in_scope(|| this.on_blockchain_tree_action(action)) {
                         Ok(EngineEventOutcome::Processed) => {}
                         Ok(EngineEventOutcome::ReachedMaxBlock) => return Poll::Ready(Ok(())),
                         Err(error) => {
@@ -1850,10 +2003,16 @@ where
                 // sensitive, hence they are polled first.
                 if let Poll::Ready(Some(msg)) = this.engine_message_stream.poll_next_unpin(cx) {
                     match msg {
-                        BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
-                            this.on_forkchoice_updated(state, payload_attrs, tx);
+                        BeaconEngineMessage::ForkchoiceUpdated {
+                            state,
+                            payload_attrs,
+                            tx,
+                            span,
+                        } => {
+                            span.in_scope(|| this.on_forkchoice_updated(state, payload_attrs, tx));
                         }
-                        BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
+                        BeaconEngineMessage::NewPayload { payload, cancun_fields, tx, span } => {
+                            let _entered = span.enter();
                             match this.on_new_payload(payload, cancun_fields) {
                                 Ok(Either::Right(block)) => {
                                     this.set_blockchain_tree_action(
@@ -0,0 +1,83 @@
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives::B256;
+use reth_rpc_types::engine::PayloadStatus;
+use schnellru::{ByLength, LruMap};
+
+/// Keeps track of the validation outcome of recent `engine_newPayload` calls.
+///
+/// Consensus clients commonly retry `engine_newPayload` for the same payload, for example after a
+/// timeout. The cached outcome is returned for these calls, so that the block is not executed
+/// again.
+///
+/// Only final outcomes are cached: a `SYNCING` or `ACCEPTED` payload can become valid or invalid
+/// later on.
+pub(crate) struct NewPayloadCache {
+    /// This maps a block hash to the status that was returned for the payload.
+    statuses: LruMap<B256, PayloadStatus>,
+    /// Metrics for the cache.
+    metrics: NewPayloadCacheMetrics,
+}
+
+impl NewPayloadCache {
+    pub(crate) fn new(max_length: u32) -> Self {
+        Self { statuses: LruMap::new(ByLength::new(max_length)), metrics: Default::default() }
+    }
+
+    /// Returns the cached status of the payload with the given block hash, if any.
+    pub(crate) fn get(&mut self, hash: &B256) -> Option<PayloadStatus> {
+        let status = self.statuses.get(hash).cloned();
+        if status.is_some() {
+            self.metrics.hits.increment(1);
+        }
+        status
+    }
+
+    /// Caches the status of the payload with the given block hash, if it is final.
+    pub(crate) fn insert(&mut self, hash: B256, status: &PayloadStatus) {
+        if status.is_valid() || status.is_invalid() {
+            self.statuses.insert(hash, status.clone());
+            self.metrics.count.set(self.statuses.len() as f64);
+        }
+    }
+}
+
+/// Metrics for the new payload cache.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon.new_payload_cache")]
+struct NewPayloadCacheMetrics {
+    /// The total number of payload statuses in the cache.
+    count: Gauge,
+    /// The number of `engine_newPayload` calls that were answered from the cache.
+    hits: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::engine::PayloadStatusEnum;
+
+    #[test]
+    fn caches_only_final_statuses() {
+        let mut cache = NewPayloadCache::new(10);
+
+        let valid = PayloadStatus::new(PayloadStatusEnum::Valid, Some(B256::with_last_byte(1)));
+        cache.insert(B256::with_last_byte(1), &valid);
+        assert_eq!(cache.get(&B256::with_last_byte(1)), Some(valid));
+
+        let invalid = PayloadStatus::new(
+            PayloadStatusEnum::Invalid { validation_error: "bad block".to_string() },
+            None,
+        );
+        cache.insert(B256::with_last_byte(2), &invalid);
+        assert_eq!(cache.get(&B256::with_last_byte(2)), Some(invalid));
+
+        cache.insert(
+            B256::with_last_byte(3),
+            &PayloadStatus::from_status(PayloadStatusEnum::Syncing),
+        );
+        assert_eq!(cache.get(&B256::with_last_byte(3)), None);
+    }
+}
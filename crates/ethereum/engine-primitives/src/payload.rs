@@ -7,9 +7,12 @@ use reth_primitives::{
     constants::EIP1559_INITIAL_BASE_FEE, revm::config::revm_spec_by_timestamp_after_merge, Address,
     BlobTransactionSidecar, Hardfork, Header, SealedBlock, Withdrawals, B256, U256,
 };
-use reth_rpc_types::engine::{
-    ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
-    ExecutionPayloadV1, PayloadAttributes, PayloadId,
+use reth_rpc_types::{
+    engine::{
+        ExecutionPayloadEnvelopeV2, ExecutionPayloadEnvelopeV3, ExecutionPayloadEnvelopeV4,
+        ExecutionPayloadV1, PayloadAttributes, PayloadId,
+    },
+    PayloadBuildTimings,
 };
 use reth_rpc_types_compat::engine::payload::{
     block_to_payload_v1, block_to_payload_v3, block_to_payload_v4,
@@ -34,6 +37,8 @@ pub struct EthBuiltPayload {
     /// The blobs, proofs, and commitments in the block. If the block is pre-cancun, this will be
     /// empty.
     pub(crate) sidecars: Vec<BlobTransactionSidecar>,
+    /// How long the payload builder spent in each phase of building the block, if it recorded it
+    pub(crate) timings: Option<PayloadBuildTimings>,
 }
 
 // === impl BuiltPayload ===
@@ -41,7 +46,7 @@ pub struct EthBuiltPayload {
 impl EthBuiltPayload {
     /// Initializes the payload with the given initial block.
     pub const fn new(id: PayloadId, block: SealedBlock, fees: U256) -> Self {
-        Self { id, block, fees, sidecars: Vec::new() }
+        Self { id, block, fees, sidecars: Vec::new(), timings: None }
     }
 
     /// Returns the identifier of the payload.
@@ -68,6 +73,17 @@ impl EthBuiltPayload {
     pub fn extend_sidecars(&mut self, sidecars: Vec<BlobTransactionSidecar>) {
         self.sidecars.extend(sidecars)
     }
+
+    /// Returns how long the payload builder spent in each phase of building the block.
+    pub const fn timings(&self) -> Option<PayloadBuildTimings> {
+        self.timings
+    }
+
+    /// Sets how long the payload builder spent in each phase of building the block.
+    pub const fn with_timings(mut self, timings: PayloadBuildTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
 }
 
 impl BuiltPayload for EthBuiltPayload {
@@ -78,6 +94,10 @@ impl BuiltPayload for EthBuiltPayload {
     fn fees(&self) -> U256 {
         self.fees
     }
+
+    fn timings(&self) -> Option<PayloadBuildTimings> {
+        self.timings
+    }
 }
 
 impl<'a> BuiltPayload for &'a EthBuiltPayload {
@@ -88,6 +108,10 @@ impl<'a> BuiltPayload for &'a EthBuiltPayload {
     fn fees(&self) -> U256 {
         (**self).fees()
     }
+
+    fn timings(&self) -> Option<PayloadBuildTimings> {
+        (**self).timings()
+    }
 }
 
 // V1 engine_getPayloadV1 response
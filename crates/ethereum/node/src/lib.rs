@@ -10,6 +10,8 @@
 
 pub use reth_ethereum_engine_primitives::EthEngineTypes;
 
+pub use reth_auto_seal_consensus::EthPayloadAttributesBuilder;
+
 pub mod evm;
 pub use evm::{EthEvmConfig, EthExecutorProvider};
 
@@ -2,7 +2,9 @@
 
 use crate::{EthEngineTypes, EthEvmConfig};
 use reth_auto_seal_consensus::AutoSealConsensus;
-use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_basic_payload_builder::{
+    BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig, PayloadTransactionsHook,
+};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
@@ -11,7 +13,7 @@ use reth_evm_ethereum::execute::EthExecutorProvider;
 use reth_network::NetworkHandle;
 use reth_node_builder::{
     components::{
-        ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder,
+        ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder, PayloadJobSettings,
         PayloadServiceBuilder, PoolBuilder,
     },
     node::{FullNodeTypes, NodeTypes},
@@ -21,7 +23,9 @@ use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
+    blobstore::DiskFileBlobStore,
+    validate::{AddressFilter, FeeFloor, ValidatorWithAdmission},
+    CoinbaseTipOrdering, EthPooledTransaction, EthTransactionValidator, TransactionPool,
     TransactionValidationTaskExecutor,
 };
 use std::sync::Arc;
@@ -49,10 +53,12 @@ impl EthereumNode {
             PayloadBuilderAttributes = EthPayloadBuilderAttributes,
         >,
     {
+        // the pool and the payload builder share the address lists
+        let address_filter = AddressFilter::default();
         ComponentsBuilder::default()
             .node_types::<Node>()
-            .pool(EthereumPoolBuilder::default())
-            .payload(EthereumPayloadBuilder::default())
+            .pool(EthereumPoolBuilder::default().with_address_filter(address_filter.clone()))
+            .payload(EthereumPayloadBuilder::default().with_address_filter(address_filter))
             .network(EthereumNetworkBuilder::default())
             .executor(EthereumExecutorBuilder::default())
             .consensus(EthereumConsensusBuilder::default())
@@ -106,26 +112,57 @@ where
     }
 }
 
+/// The transaction pool of the [`EthereumPoolBuilder`], which only admits the transactions that
+/// the address lists allow and that pay the fee floor, if enabled.
+pub type EthereumTransactionPool<Client, S> = reth_transaction_pool::Pool<
+    TransactionValidationTaskExecutor<
+        ValidatorWithAdmission<
+            EthTransactionValidator<Client, EthPooledTransaction>,
+            (AddressFilter, Option<FeeFloor>),
+        >,
+    >,
+    CoinbaseTipOrdering<EthPooledTransaction>,
+    S,
+>;
+
 /// A basic ethereum transaction pool.
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// The filter of the configured address lists, see
+    /// [`BuilderContext::spawn_address_lists_task`].
+    pub address_filter: AddressFilter,
+}
+
+impl EthereumPoolBuilder {
+    /// Sets the filter that the configured address lists are loaded into.
+    ///
+    /// Share it with the payload builder, so that it skips the same transactions.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.address_filter = address_filter;
+        self
+    }
 }
 
 impl<Node> PoolBuilder<Node> for EthereumPoolBuilder
 where
     Node: FullNodeTypes,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = EthereumTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        if let Some(fee_floor_config) = pool_config.fee_floor {
+            fee_floor_config.validate()?;
+        }
+        let fee_floor = pool_config
+            .fee_floor
+            .map(|_| FeeFloor::new(pool_config.local_transactions_config.clone()));
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -135,11 +172,17 @@ where
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
                 blob_store.clone(),
-            );
+            )
+            .with_admission_validator((self.address_filter.clone(), fee_floor.clone()));
 
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+        let transaction_pool = reth_transaction_pool::Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store,
+            pool_config.clone(),
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
+        ctx.spawn_address_lists_task(transaction_pool.clone(), self.address_filter)?;
         let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
@@ -147,8 +190,11 @@ where
             let pool = transaction_pool.clone();
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            let transactions_backup_config = ctx
+                .config()
+                .txpool
+                .local_transactions_backup_config(transactions_path)
+                .with_blob_txs_backup(data_dir.txpool_blob_transactions());
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -175,6 +221,15 @@ where
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(fee_floor) = fee_floor {
+            ctx.task_executor().spawn(reth_transaction_pool::maintain::fee_floor_task(
+                transaction_pool.clone(),
+                fee_floor,
+                pool_config,
+            ));
+            debug!(target: "reth::cli", "Spawned txpool fee floor task");
+        }
+
         Ok(transaction_pool)
     }
 }
@@ -182,7 +237,37 @@ where
 /// A basic ethereum payload service.
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Settings of the payload jobs that take precedence over the node's config.
+    pub job_settings: PayloadJobSettings,
+    /// Provides transactions that must be included in the built payloads.
+    pub transactions_hook: Option<Arc<dyn PayloadTransactionsHook>>,
+    /// The filter of the address lists whose disallowed transactions are skipped.
+    pub address_filter: AddressFilter,
+}
+
+impl EthereumPayloadBuilder {
+    /// Sets the settings of the payload jobs that take precedence over the node's config.
+    pub const fn with_job_settings(mut self, job_settings: PayloadJobSettings) -> Self {
+        self.job_settings = job_settings;
+        self
+    }
+
+    /// Sets the hook that is invoked when payload attributes arrive, and provides transactions
+    /// that must be included in the payloads built for them.
+    pub fn with_transactions_hook(mut self, hook: Arc<dyn PayloadTransactionsHook>) -> Self {
+        self.transactions_hook = Some(hook);
+        self
+    }
+
+    /// Sets the filter of the address lists whose disallowed transactions are skipped.
+    ///
+    /// This is the filter of the pool builder, which loads the configured lists into it.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.address_filter = address_filter;
+        self
+    }
+}
 
 impl<Node, Pool> PayloadServiceBuilder<Node, Pool> for EthereumPayloadBuilder
 where
@@ -199,16 +284,18 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
         let conf = ctx.payload_builder_config();
+        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default()
+            .with_priority_senders(conf.priority_senders().iter().copied())
+            .with_address_filter(self.address_filter);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
-            .interval(conf.interval())
-            .deadline(conf.deadline())
-            .max_payload_tasks(conf.max_payload_tasks())
+            .interval(self.job_settings.interval(conf))
+            .deadline(self.job_settings.deadline(conf))
+            .max_payload_tasks(self.job_settings.max_payload_tasks(conf)?)
             .extradata(conf.extradata_bytes());
 
-        let payload_generator = BasicPayloadJobGenerator::with_builder(
+        let mut payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
             pool,
             ctx.task_executor().clone(),
@@ -216,8 +303,12 @@ where
             ctx.chain_spec(),
             payload_builder,
         );
+        if let Some(hook) = self.transactions_hook {
+            payload_generator = payload_generator.with_transactions_hook(hook);
+        }
         let (payload_service, payload_builder) =
             PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
+        let payload_service = payload_service.with_retention(conf.payload_retention());
 
         ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
@@ -12,7 +12,7 @@
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, post_block_withdrawal_requests_contract_call,
     pre_block_beacon_root_contract_call, BuildArguments, BuildOutcome, PayloadBuilder,
-    PayloadConfig, WithdrawalsOutcome,
+    PayloadConfig, PoolTransactionsOptions, WithdrawalsOutcome,
 };
 use reth_errors::RethError;
 use reth_evm::ConfigureEvm;
@@ -28,29 +28,57 @@ use reth_primitives::{
     eip4844::calculate_excess_blob_gas,
     proofs::{self, calculate_requests_root},
     revm::env::tx_env_with_recovered,
-    Block, Header, IntoRecoveredTransaction, Receipt, EMPTY_OMMER_ROOT_HASH, U256,
+    Address, Block, Header, IntoRecoveredTransaction, Receipt, EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::StateProviderFactory;
 use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_rpc_types::PayloadBuildTimings;
+use reth_transaction_pool::{
+    pool::BestTransactionsWithPriority, validate::AddressFilter, BestTransactionsAttributes,
+    TransactionPool, ValidPoolTransaction,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
     DatabaseCommit, State,
 };
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::{debug, trace, warn};
 
 /// Ethereum payload builder
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// Selects the pool transactions that are included.
+    pool_transactions: PoolTransactionsOptions,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, pool_transactions: PoolTransactionsOptions::default() }
+    }
+
+    /// Sets the options for selecting the pool transactions that are included.
+    pub fn with_pool_transactions(mut self, pool_transactions: PoolTransactionsOptions) -> Self {
+        self.pool_transactions = pool_transactions;
+        self
+    }
+
+    /// Only includes the pool transactions whose sender and recipient are allowed by the filter.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.pool_transactions = self.pool_transactions.with_address_filter(address_filter);
+        self
+    }
+
+    /// Includes the pool transactions of the given senders first, regardless of their tip.
+    pub fn with_priority_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.pool_transactions = self.pool_transactions.with_priority_senders(senders);
+        self
     }
 }
 
@@ -74,7 +102,7 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(self.evm_config.clone(), args)
+        default_ethereum_payload_builder(self.evm_config.clone(), &self.pool_transactions, args)
     }
 
     fn build_empty_payload(
@@ -242,9 +270,12 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The pool transactions are selected as configured by the [`PoolTransactionsOptions`].
 #[inline]
 pub fn default_ethereum_payload_builder<EvmConfig, Pool, Client>(
     evm_config: EvmConfig,
+    pool_transactions: &PoolTransactionsOptions,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
@@ -265,6 +296,7 @@ where
         parent_block,
         attributes,
         chain_spec,
+        forced_bundles,
         ..
     } = config;
 
@@ -276,15 +308,32 @@ where
 
     let mut executed_txs = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
+    let best_attributes = BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut best_txs = pool.best_transactions_with_attributes(best_attributes);
+    let PoolTransactionsOptions { address_filter, priority_senders } = pool_transactions;
+    if let Some(priority_senders) = priority_senders.as_ref().filter(|senders| !senders.is_empty())
+    {
+        // the transactions of the priority senders are included first, regardless of their tip
+        let priority_senders = Arc::clone(priority_senders);
+        best_txs = Box::new(BestTransactionsWithPriority::new(
+            best_txs,
+            pool.best_transactions_with_attributes(best_attributes),
+            move |tx: &Arc<ValidPoolTransaction<Pool::Transaction>>| {
+                priority_senders.contains(&tx.sender())
+            },
+        ));
+    }
 
     let mut total_fees = U256::ZERO;
 
     let block_number = initialized_block_env.number.to::<u64>();
 
+    // the system calls and forced transactions are part of the execution time
+    let execution_start = Instant::now();
+
     // apply eip-4788 pre block contract call
     pre_block_beacon_root_contract_call(
         &mut db,
@@ -306,6 +355,82 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
+
+    // execute the bundles of transactions that must be included before the transactions of the
+    // pool, these were checked to fit into the block when the payload attributes arrived
+    for bundle in forced_bundles {
+        // bundles are included atomically, so the state is restored if a transaction fails
+        let checkpoint = (db.cache.clone(), db.transition_state.clone());
+        let mut bundle_gas_used = 0;
+        let mut bundle_fees = U256::ZERO;
+        let mut bundle_receipts = Vec::with_capacity(bundle.transactions.len());
+        let mut failed = false;
+        for tx in &bundle.transactions {
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                initialized_cfg.clone(),
+                initialized_block_env.clone(),
+                tx_env_with_recovered(tx),
+            );
+            let mut evm = evm_config.evm_with_env(&mut db, env);
+
+            let ResultAndState { result, state } = match evm.transact() {
+                Ok(res) => res,
+                Err(EVMError::Transaction(err)) => {
+                    warn!(
+                        target: "payload_builder",
+                        %err,
+                        tx=?tx.hash,
+                        "skipping forced bundle with invalid transaction"
+                    );
+                    failed = true;
+                    break
+                }
+                Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+            };
+            drop(evm);
+            if !result.is_success() && !bundle.may_revert(&tx.hash) {
+                warn!(
+                    target: "payload_builder",
+                    tx=?tx.hash,
+                    "skipping forced bundle with reverted transaction"
+                );
+                failed = true;
+                break
+            }
+            db.commit(state);
+
+            let gas_used = result.gas_used();
+            bundle_gas_used += gas_used;
+
+            #[allow(clippy::needless_update)] // side-effect of optimism fields
+            bundle_receipts.push(Some(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used: cumulative_gas_used + bundle_gas_used,
+                logs: result.into_logs().into_iter().map(Into::into).collect(),
+                ..Default::default()
+            }));
+
+            let miner_fee = tx
+                .effective_tip_per_gas(Some(base_fee))
+                .expect("fee is always valid; execution succeeded");
+            bundle_fees += U256::from(miner_fee) * U256::from(gas_used);
+        }
+
+        if failed {
+            (db.cache, db.transition_state) = checkpoint;
+            continue
+        }
+        cumulative_gas_used += bundle_gas_used;
+        total_fees += bundle_fees;
+        receipts.extend(bundle_receipts);
+        executed_txs.extend(bundle.transactions.into_iter().map(|tx| tx.into_signed()));
+    }
+    let mut execution = execution_start.elapsed();
+
+    // the time spent in the pool loop that isn't spent executing is the transaction selection time
+    let selection_start = Instant::now();
+    let mut pool_execution = Duration::ZERO;
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -321,6 +446,17 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // skip transactions of senders or to recipients that aren't allowed, and their
+        // descendants
+        if let Some(address) = address_filter
+            .as_ref()
+            .and_then(|filter| filter.disallowed_address(pool_tx.sender(), pool_tx.to()))
+        {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), %address, "skipping transaction of disallowed address");
+            best_txs.mark_invalid(&pool_tx);
+            continue
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_recovered_transaction();
 
@@ -348,7 +484,10 @@ where
         // Configure the environment for the block.
         let mut evm = evm_config.evm_with_env(&mut db, env);
 
-        let ResultAndState { result, state } = match evm.transact() {
+        let transact_start = Instant::now();
+        let res = evm.transact();
+        pool_execution += transact_start.elapsed();
+        let ResultAndState { result, state } = match res {
             Ok(res) => res,
             Err(err) => {
                 match err {
@@ -412,6 +551,8 @@ where
         // append transaction to the list of executed transactions
         executed_txs.push(tx.into_signed());
     }
+    let tx_selection = selection_start.elapsed().saturating_sub(pool_execution);
+    execution += pool_execution;
 
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
@@ -419,6 +560,9 @@ where
         return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
     }
 
+    // the post block system calls and withdrawals are part of the execution time
+    let execution_start = Instant::now();
+
     // calculate the requests and the requests root
     let (requests, requests_root) = if chain_spec
         .is_prague_active_at_timestamp(attributes.timestamp)
@@ -444,7 +588,9 @@ where
     // merge all transitions into bundle state, this would apply the withdrawal balance changes
     // and 4788 contract call
     db.merge_transitions(BundleRetention::PlainState);
+    execution += execution_start.elapsed();
 
+    let root_computation_start = Instant::now();
     let execution_outcome = ExecutionOutcome::new(
         db.take_bundle(),
         vec![receipts].into(),
@@ -463,6 +609,7 @@ where
 
     // create the block header
     let transactions_root = proofs::calculate_transaction_root(&executed_txs);
+    let root_computation = root_computation_start.elapsed();
 
     // initialize empty blob sidecars at first. If cancun is active then this will
     let mut blob_sidecars = Vec::new();
@@ -519,7 +666,8 @@ where
     let sealed_block = block.seal_slow();
     debug!(target: "payload_builder", ?sealed_block, "sealed built block");
 
-    let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees);
+    let mut payload = EthBuiltPayload::new(attributes.id, sealed_block, total_fees)
+        .with_timings(PayloadBuildTimings { tx_selection, execution, root_computation });
 
     // extend the payload with the blob sidecars from the executed txs
     payload.extend_sidecars(blob_sidecars);
@@ -0,0 +1,136 @@
+//! Conversion of canonical blocks into firehose messages.
+
+use crate::proto;
+use reth_db_api::database::Database;
+use reth_primitives::{Address, BlockNumber, Log, Receipt, B256};
+use reth_provider::{
+    AccountReader, BlockHashReader, BlockReader, ChangeSetReader, DatabaseProviderFactory,
+    ProviderResult, StateProvider, StateProviderFactory, StorageReader, TransactionVariant,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Reads the canonical block with the given number, along with its receipts and the state it
+/// changed.
+///
+/// Returns `None` if there's no canonical block with this number, or if the block was reorged
+/// while it was read.
+pub(crate) fn read_block<DB, Provider>(
+    provider: &Provider,
+    number: BlockNumber,
+) -> ProviderResult<Option<proto::Block>>
+where
+    DB: Database,
+    Provider: DatabaseProviderFactory<DB> + StateProviderFactory,
+{
+    let db = provider.database_provider_ro()?;
+    let Some(hash) = db.block_hash(number)? else { return Ok(None) };
+    let Some(block) = db.block_with_senders(number.into(), TransactionVariant::WithHash)? else {
+        return Ok(None)
+    };
+    let receipts = db.receipts_by_block(number.into())?.unwrap_or_default();
+
+    // the changed storage slots of every account that was touched by the block
+    let mut changed = db.changed_storages_with_range(number..=number)?;
+    for account in db.account_block_changeset(number)? {
+        changed.entry(account.address).or_default();
+    }
+    drop(db);
+
+    let before = number.checked_sub(1).map(|parent| provider.history_by_block_number(parent));
+    let before = before.transpose()?;
+    let after = provider.history_by_block_number(number)?;
+    let account_changes = account_changes(before.as_deref(), &*after, changed)?;
+
+    // the state is read in separate transactions, so the chain may have changed in between
+    if provider.block_hash(number)? != Some(hash) {
+        return Ok(None)
+    }
+
+    let transactions = block
+        .body
+        .iter()
+        .zip(&block.senders)
+        .map(|(tx, sender)| proto::Transaction {
+            hash: tx.hash().to_vec(),
+            from: sender.to_vec(),
+            raw: tx.envelope_encoded().to_vec(),
+        })
+        .collect();
+
+    Ok(Some(proto::Block {
+        number,
+        hash: hash.to_vec(),
+        parent_hash: block.parent_hash.to_vec(),
+        timestamp: block.timestamp,
+        header_rlp: alloy_rlp::encode(&block.header),
+        transactions,
+        receipts: receipts.iter().map(receipt).collect(),
+        account_changes,
+    }))
+}
+
+/// Returns the changes of the given accounts and storage slots between the two states.
+///
+/// The state before the genesis block is empty.
+fn account_changes(
+    before: Option<&dyn StateProvider>,
+    after: &dyn StateProvider,
+    changed: BTreeMap<Address, BTreeSet<B256>>,
+) -> ProviderResult<Vec<proto::AccountChange>> {
+    let account_state = |state: Option<&dyn StateProvider>, address| -> ProviderResult<_> {
+        let Some(state) = state else { return Ok(None) };
+        Ok(state.basic_account(address)?.map(|account| proto::AccountState {
+            balance: account.balance.to_be_bytes::<32>().to_vec(),
+            nonce: account.nonce,
+            code_hash: account.get_bytecode_hash().to_vec(),
+        }))
+    };
+    let storage_value = |state: Option<&dyn StateProvider>, address, key| -> ProviderResult<_> {
+        let value = match state {
+            Some(state) => state.storage(address, key)?.unwrap_or_default(),
+            None => Default::default(),
+        };
+        Ok(value.to_be_bytes::<32>().to_vec())
+    };
+
+    changed
+        .into_iter()
+        .map(|(address, keys)| {
+            let storage_changes = keys
+                .into_iter()
+                .map(|key| {
+                    Ok(proto::StorageChange {
+                        key: key.to_vec(),
+                        before: storage_value(before, address, key)?,
+                        after: storage_value(Some(after), address, key)?,
+                    })
+                })
+                .collect::<ProviderResult<_>>()?;
+            Ok(proto::AccountChange {
+                address: address.to_vec(),
+                before: account_state(before, address)?,
+                after: account_state(Some(after), address)?,
+                storage_changes,
+            })
+        })
+        .collect()
+}
+
+/// Converts a receipt into its message.
+fn receipt(receipt: &Receipt) -> proto::Receipt {
+    proto::Receipt {
+        tx_type: u8::from(receipt.tx_type).into(),
+        success: receipt.success,
+        cumulative_gas_used: receipt.cumulative_gas_used,
+        logs: receipt.logs.iter().map(log).collect(),
+    }
+}
+
+/// Converts a log into its message.
+fn log(log: &Log) -> proto::Log {
+    proto::Log {
+        address: log.address.to_vec(),
+        topics: log.topics().iter().map(|topic| topic.to_vec()).collect(),
+        data: log.data.data.to_vec(),
+    }
+}
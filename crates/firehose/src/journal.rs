@@ -0,0 +1,53 @@
+//! Journal of the streamed blocks, used to undo blocks that were reorged.
+
+use parking_lot::Mutex;
+use reth_primitives::{BlockNumber, B256};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// A reference to a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlockRef {
+    /// The number of the block.
+    pub(crate) number: BlockNumber,
+    /// The hash of the block.
+    pub(crate) hash: B256,
+}
+
+/// The parents of the most recently streamed blocks.
+///
+/// Once a block is reorged, its parent can't be read from the database anymore, so a subscriber
+/// that resumes from a reorged block walks back to the canonical chain through the journal.
+///
+/// The journal is shared by all clones.
+#[derive(Clone)]
+pub(crate) struct ForkJournal {
+    /// The parents of the blocks by hash.
+    parents: Arc<Mutex<LruMap<B256, BlockRef, ByLength>>>,
+}
+
+impl ForkJournal {
+    /// Creates a new journal that holds at most `max_blocks` blocks.
+    pub(crate) fn new(max_blocks: u32) -> Self {
+        Self { parents: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_blocks)))) }
+    }
+
+    /// Records the parent of the block.
+    pub(crate) fn insert(&self, block_hash: B256, parent: BlockRef) {
+        self.parents.lock().insert(block_hash, parent);
+    }
+
+    /// Returns the parent of the block, if the block is journaled.
+    pub(crate) fn parent(&self, block_hash: B256) -> Option<BlockRef> {
+        self.parents.lock().get(&block_hash).copied()
+    }
+}
+
+impl Debug for ForkJournal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkJournal").field("blocks", &self.parents.lock().len()).finish()
+    }
+}
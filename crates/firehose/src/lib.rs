@@ -0,0 +1,31 @@
+//! Streams the canonical chain over gRPC, for consumers that run outside of the node.
+//!
+//! Every block that becomes canonical is streamed with its transactions, receipts and the state it
+//! changed. Blocks that are reorged are undone. Every response carries a cursor, and a subscriber
+//! that resumes from the cursor of the last response it processed receives every later change at
+//! least once.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod block;
+mod journal;
+mod service;
+
+pub use service::{
+    FirehoseService, DEFAULT_JOURNAL_BLOCKS, DEFAULT_MAX_REPLAY_BLOCKS, DEFAULT_MAX_SUBSCRIBERS,
+};
+
+/// The protobuf messages and the gRPC service of the firehose.
+///
+/// Generated from `proto/firehose.proto` with `tonic-build` and checked in, so that building the
+/// crate doesn't require `protoc`. Regenerate it whenever the proto file changes.
+#[allow(missing_docs, unreachable_pub, missing_debug_implementations, clippy::all, clippy::nursery)]
+pub mod proto {
+    include!("generated/firehose.rs");
+}
@@ -0,0 +1,423 @@
+//! The firehose gRPC service.
+
+use crate::{
+    block::read_block,
+    journal::{BlockRef, ForkJournal},
+    proto::{
+        self,
+        firehose_server::{Firehose, FirehoseServer},
+        BlocksRequest, BlocksResponse, Step,
+    },
+};
+use reth_db_api::database::Database;
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::{
+    BlockHashReader, BlockNumReader, CanonStateNotifications, CanonStateSubscriptions,
+    DatabaseProviderFactory, ProviderError, StateProviderFactory,
+};
+use std::{marker::PhantomData, net::SocketAddr, sync::Arc};
+use tokio::sync::{broadcast::error::RecvError, mpsc, OwnedSemaphorePermit, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{transport::Server, Request, Response, Status};
+use tracing::{debug, trace};
+
+/// The default number of streamed blocks whose parents are journaled.
+///
+/// A subscriber can resume from a reorged block only if its parent is still journaled.
+pub const DEFAULT_JOURNAL_BLOCKS: u32 = 1024;
+
+/// The default maximum number of concurrent subscribers.
+pub const DEFAULT_MAX_SUBSCRIBERS: usize = 32;
+
+/// The default maximum number of blocks behind the tip a subscriber can start streaming from.
+pub const DEFAULT_MAX_REPLAY_BLOCKS: u64 = 10_000;
+
+/// The number of responses that are buffered for a subscriber.
+///
+/// A subscriber that doesn't keep up only delays its own stream, which catches up from the
+/// database.
+const SUBSCRIBER_BUFFER: usize = 16;
+
+/// The responses that are streamed to a subscriber.
+type Responses = mpsc::Receiver<Result<BlocksResponse, Status>>;
+
+/// Streams the canonical chain to gRPC subscribers.
+///
+/// Blocks are read from the database whenever the canonical chain changes, so a subscriber that
+/// falls behind catches up from the database instead of buffering blocks in memory.
+#[derive(Debug)]
+pub struct FirehoseService<DB, Provider> {
+    /// The provider of the canonical chain.
+    provider: Provider,
+    /// The parents of the streamed blocks.
+    journal: ForkJournal,
+    /// Limits the number of concurrent subscribers.
+    subscribers: Arc<Semaphore>,
+    /// The maximum number of blocks behind the tip a subscriber can start streaming from.
+    max_replay_blocks: u64,
+    _db: PhantomData<DB>,
+}
+
+impl<DB, Provider> FirehoseService<DB, Provider>
+where
+    DB: Database + 'static,
+    Provider: DatabaseProviderFactory<DB> + StateProviderFactory + Clone + 'static,
+{
+    /// Creates a new service that journals the parents of up to `journal_blocks` streamed blocks.
+    pub fn new(provider: Provider, journal_blocks: u32) -> Self {
+        Self {
+            provider,
+            journal: ForkJournal::new(journal_blocks),
+            subscribers: Arc::new(Semaphore::new(DEFAULT_MAX_SUBSCRIBERS)),
+            max_replay_blocks: DEFAULT_MAX_REPLAY_BLOCKS,
+            _db: PhantomData,
+        }
+    }
+
+    /// Sets the maximum number of concurrent subscribers.
+    pub fn with_max_subscribers(mut self, max_subscribers: usize) -> Self {
+        self.subscribers = Arc::new(Semaphore::new(max_subscribers));
+        self
+    }
+
+    /// Sets the maximum number of blocks behind the tip a subscriber can start streaming from.
+    pub const fn with_max_replay_blocks(mut self, max_replay_blocks: u64) -> Self {
+        self.max_replay_blocks = max_replay_blocks;
+        self
+    }
+
+    /// Admits a new subscriber, and returns it with the stream of its responses.
+    ///
+    /// Fails if there are too many subscribers, or if the subscriber would start too far behind
+    /// the tip.
+    fn subscribe(
+        &self,
+        request: BlocksRequest,
+    ) -> Result<(Subscriber<DB, Provider>, Responses), Status> {
+        let permit = self
+            .subscribers
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| Status::resource_exhausted("too many firehose subscribers"))?;
+        let (head, start_block) = self.start(request)?;
+        debug!(target: "firehose", ?head, start_block, "New subscriber");
+
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_BUFFER);
+        let subscriber = Subscriber {
+            provider: self.provider.clone(),
+            journal: self.journal.clone(),
+            tx,
+            head,
+            start_block,
+            _permit: permit,
+            _db: PhantomData,
+        };
+        Ok((subscriber, rx))
+    }
+
+    /// Returns the last block the subscriber has seen, and the number of the first block to
+    /// stream if it hasn't seen any.
+    fn start(&self, request: BlocksRequest) -> Result<(Option<BlockRef>, BlockNumber), Status> {
+        let (head, start_block) = self.requested_start(request)?;
+        let best_block = self.provider.best_block_number().map_err(internal)?;
+        if best_block.saturating_sub(start_block) > self.max_replay_blocks {
+            return Err(Status::out_of_range(format!(
+                "start block {start_block} is more than {} blocks behind the tip",
+                self.max_replay_blocks
+            )))
+        }
+        Ok((head, start_block))
+    }
+
+    /// Resolves the cursor or start block of the request.
+    fn requested_start(
+        &self,
+        request: BlocksRequest,
+    ) -> Result<(Option<BlockRef>, BlockNumber), Status> {
+        if let Some(cursor) = request.cursor {
+            let hash = B256::try_from(cursor.hash.as_slice())
+                .map_err(|_| Status::invalid_argument("cursor hash must be 32 bytes"))?;
+            let cursor = BlockRef { number: cursor.number, hash };
+            let is_canonical =
+                self.provider.block_hash(cursor.number).map_err(internal)? == Some(hash);
+            if !is_canonical && self.journal.parent(hash).is_none() {
+                return Err(Status::failed_precondition(format!(
+                    "cursor block {hash} is not canonical and not journaled"
+                )))
+            }
+            return Ok((Some(cursor), cursor.number + 1))
+        }
+
+        let start_block = match request.start_block {
+            Some(start_block) => start_block,
+            None => self.provider.best_block_number().map_err(internal)?,
+        };
+        let Some(parent_number) = start_block.checked_sub(1) else { return Ok((None, 0)) };
+        let parent_hash =
+            self.provider.block_hash(parent_number).map_err(internal)?.ok_or_else(|| {
+                Status::out_of_range(format!("start block {start_block} is ahead of the chain"))
+            })?;
+        Ok((Some(BlockRef { number: parent_number, hash: parent_hash }), start_block))
+    }
+}
+
+impl<DB, Provider> FirehoseService<DB, Provider>
+where
+    DB: Database + 'static,
+    Provider: DatabaseProviderFactory<DB>
+        + StateProviderFactory
+        + CanonStateSubscriptions
+        + Clone
+        + 'static,
+{
+    /// Serves the service on the given address until the server fails.
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), tonic::transport::Error> {
+        Server::builder().add_service(FirehoseServer::new(self)).serve(addr).await
+    }
+}
+
+#[tonic::async_trait]
+impl<DB, Provider> Firehose for FirehoseService<DB, Provider>
+where
+    DB: Database + 'static,
+    Provider: DatabaseProviderFactory<DB>
+        + StateProviderFactory
+        + CanonStateSubscriptions
+        + Clone
+        + 'static,
+{
+    type BlocksStream = ReceiverStream<Result<BlocksResponse, Status>>;
+
+    async fn blocks(
+        &self,
+        request: Request<BlocksRequest>,
+    ) -> Result<Response<Self::BlocksStream>, Status> {
+        // subscribe before reading the chain, so that no change is missed
+        let notifications = self.provider.subscribe_to_canonical_state();
+        let (subscriber, rx) = self.subscribe(request.into_inner())?;
+        tokio::spawn(subscriber.run(notifications));
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Streams the canonical chain to a single subscriber.
+struct Subscriber<DB, Provider> {
+    provider: Provider,
+    journal: ForkJournal,
+    tx: mpsc::Sender<Result<BlocksResponse, Status>>,
+    /// The last block that was streamed to the subscriber.
+    head: Option<BlockRef>,
+    /// The first block to stream if no block was streamed yet.
+    start_block: BlockNumber,
+    /// Counts the subscriber towards the limit until it disconnects.
+    _permit: OwnedSemaphorePermit,
+    _db: PhantomData<DB>,
+}
+
+impl<DB, Provider> Subscriber<DB, Provider>
+where
+    DB: Database + 'static,
+    Provider: DatabaseProviderFactory<DB> + StateProviderFactory + Clone + 'static,
+{
+    /// Streams the chain until the subscriber disconnects or the node shuts down.
+    async fn run(mut self, mut notifications: CanonStateNotifications) {
+        loop {
+            if let Err(status) = self.sync().await {
+                if status.code() != tonic::Code::Cancelled {
+                    let _ = self.tx.send(Err(status)).await;
+                }
+                return
+            }
+
+            // the notifications only signal that the chain changed, blocks are read from the
+            // database, so lagging behind is fine
+            match notifications.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Undoes the streamed blocks that are no longer canonical, then streams the canonical blocks
+    /// up to the head of the chain.
+    ///
+    /// Stops early if the chain changes while blocks are streamed, the change is picked up by the
+    /// next sync.
+    async fn sync(&mut self) -> Result<(), Status> {
+        while let Some(head) = self.head {
+            if self.provider.block_hash(head.number).map_err(internal)? == Some(head.hash) {
+                break
+            }
+            let parent = self.journal.parent(head.hash).ok_or_else(|| {
+                Status::data_loss(format!("reorged block {} is not journaled", head.hash))
+            })?;
+            trace!(target: "firehose", number = head.number, hash = %head.hash, "Undoing block");
+            self.send(BlocksResponse {
+                step: Step::Undo.into(),
+                block_ref: Some(head.into()),
+                cursor: Some(parent.into()),
+                block: None,
+            })
+            .await?;
+            self.head = Some(parent);
+        }
+
+        let best_block = self.provider.best_block_number().map_err(internal)?;
+        let mut number = self.head.map_or(self.start_block, |head| head.number + 1);
+        while number <= best_block {
+            let provider = self.provider.clone();
+            let block = tokio::task::spawn_blocking(move || read_block(&provider, number))
+                .await
+                .map_err(|err| Status::internal(err.to_string()))?
+                .map_err(internal)?;
+            let Some(block) = block else { break };
+            let hash = B256::from_slice(&block.hash);
+            if let Some(head) = self.head {
+                if block.parent_hash != head.hash.as_slice() {
+                    break
+                }
+                self.journal.insert(hash, head);
+            }
+
+            let block_ref = BlockRef { number, hash };
+            trace!(target: "firehose", number, %hash, "Streaming block");
+            self.send(BlocksResponse {
+                step: Step::New.into(),
+                block_ref: Some(block_ref.into()),
+                cursor: Some(block_ref.into()),
+                block: Some(block),
+            })
+            .await?;
+            self.head = Some(block_ref);
+            number += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the response, fails with [`tonic::Code::Cancelled`] if the subscriber disconnected.
+    async fn send(&self, response: BlocksResponse) -> Result<(), Status> {
+        self.tx.send(Ok(response)).await.map_err(|_| Status::cancelled("subscriber disconnected"))
+    }
+}
+
+impl From<BlockRef> for proto::BlockRef {
+    fn from(block: BlockRef) -> Self {
+        Self { number: block.number, hash: block.hash.to_vec() }
+    }
+}
+
+/// Maps a provider error to an internal status.
+fn internal(err: ProviderError) -> Status {
+    Status::internal(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::SealedBlock;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, BlockWriter, ProviderFactory,
+        StageCheckpointWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_testing_utils::generators::{self, random_block};
+
+    /// Inserts the blocks as canonical blocks, and makes the last one the tip.
+    fn insert_blocks<DB: Database>(factory: &ProviderFactory<DB>, blocks: &[SealedBlock]) {
+        let provider = factory.provider_rw().unwrap();
+        for block in blocks {
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap(), None).unwrap();
+        }
+        let tip = blocks.last().unwrap().number;
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(tip)).unwrap();
+        provider.commit().unwrap();
+    }
+
+    /// Syncs the subscriber and returns the steps and blocks it streamed.
+    async fn sync<DB, Provider>(
+        subscriber: &mut Subscriber<DB, Provider>,
+        rx: &mut Responses,
+    ) -> Vec<(Step, B256)>
+    where
+        DB: Database + 'static,
+        Provider: DatabaseProviderFactory<DB> + StateProviderFactory + Clone + 'static,
+    {
+        subscriber.sync().await.unwrap();
+        let mut streamed = Vec::new();
+        while let Ok(response) = rx.try_recv() {
+            let response = response.unwrap();
+            let block_ref = response.block_ref.unwrap();
+            streamed.push((response.step(), B256::from_slice(&block_ref.hash)));
+        }
+        streamed
+    }
+
+    #[tokio::test]
+    async fn streams_undoes_and_resumes() {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let genesis = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let block1 = random_block(&mut rng, 1, Some(genesis.hash()), Some(0), Some(0));
+        let block2 = random_block(&mut rng, 2, Some(block1.hash()), Some(0), Some(0));
+        insert_blocks(&factory, &[genesis.clone(), block1.clone(), block2.clone()]);
+
+        let service = FirehoseService::new(factory.clone(), DEFAULT_JOURNAL_BLOCKS);
+        let request = BlocksRequest { cursor: None, start_block: Some(0) };
+        let (mut subscriber, mut rx) = service.subscribe(request).unwrap();
+        assert_eq!(
+            sync(&mut subscriber, &mut rx).await,
+            vec![
+                (Step::New, genesis.hash()),
+                (Step::New, block1.hash()),
+                (Step::New, block2.hash())
+            ]
+        );
+
+        // reorg block 2
+        let fork2 = random_block(&mut rng, 2, Some(block1.hash()), Some(0), Some(0));
+        let fork3 = random_block(&mut rng, 3, Some(fork2.hash()), Some(0), Some(0));
+        insert_blocks(&factory, &[fork2.clone(), fork3.clone()]);
+        let reorg =
+            vec![(Step::Undo, block2.hash()), (Step::New, fork2.hash()), (Step::New, fork3.hash())];
+        assert_eq!(sync(&mut subscriber, &mut rx).await, reorg);
+
+        // a subscriber that resumes from the reorged block undoes it
+        let cursor = BlockRef { number: 2, hash: block2.hash() };
+        let request = BlocksRequest { cursor: Some(cursor.into()), start_block: None };
+        let (mut subscriber, mut rx) = service.subscribe(request).unwrap();
+        assert_eq!(sync(&mut subscriber, &mut rx).await, reorg);
+
+        // a cursor that is neither canonical nor journaled can't be resolved
+        let cursor = BlockRef { number: 2, hash: B256::random() };
+        let request = BlocksRequest { cursor: Some(cursor.into()), start_block: None };
+        let err = service.subscribe(request).err().unwrap();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn limits_subscribers_and_replay() {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let genesis = random_block(&mut rng, 0, None, Some(0), Some(0));
+        let block1 = random_block(&mut rng, 1, Some(genesis.hash()), Some(0), Some(0));
+        let block2 = random_block(&mut rng, 2, Some(block1.hash()), Some(0), Some(0));
+        insert_blocks(&factory, &[genesis, block1, block2]);
+
+        let service = FirehoseService::new(factory, DEFAULT_JOURNAL_BLOCKS)
+            .with_max_subscribers(1)
+            .with_max_replay_blocks(1);
+
+        let err = service.subscribe(BlocksRequest { cursor: None, start_block: Some(0) }).err();
+        assert_eq!(err.unwrap().code(), tonic::Code::OutOfRange);
+
+        let subscriber = service.subscribe(BlocksRequest { cursor: None, start_block: Some(1) });
+        let err = service.subscribe(BlocksRequest::default()).err();
+        assert_eq!(err.unwrap().code(), tonic::Code::ResourceExhausted);
+
+        // the slot is released once the subscriber disconnects
+        drop(subscriber);
+        assert!(service.subscribe(BlocksRequest::default()).is_ok());
+    }
+}
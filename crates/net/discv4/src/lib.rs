@@ -29,6 +29,7 @@ use crate::{
     proto::{FindNode, Message, Neighbours, Packet, Ping, Pong},
 };
 use alloy_primitives::{bytes::Bytes, hex, B256};
+use alloy_rlp::Decodable;
 use discv5::{
     kbucket,
     kbucket::{
@@ -41,7 +42,7 @@ use enr::Enr;
 use parking_lot::Mutex;
 use proto::{EnrRequest, EnrResponse};
 use reth_ethereum_forks::ForkId;
-use reth_network_peers::{pk2id, PeerId};
+use reth_network_peers::{pk2id, EnrCapabilities, PeerId, ENR_CAPABILITIES_KEY};
 use secp256k1::SecretKey;
 use std::{
     cell::RefCell,
@@ -76,6 +77,9 @@ use node::{kad_key, NodeKey};
 
 mod table;
 
+mod metrics;
+use metrics::NatMetrics;
+
 // reexport NodeRecord primitive
 pub use reth_network_peers::NodeRecord;
 
@@ -85,7 +89,7 @@ pub mod test_utils;
 use crate::table::PongTable;
 use reth_net_nat::ResolveNatInterval;
 /// reexport to get public ip.
-pub use reth_net_nat::{external_ip, NatResolver};
+pub use reth_net_nat::{external_ip, NatResolver, NatStatus};
 
 /// The default address for discv4 via UDP
 ///
@@ -171,6 +175,8 @@ pub struct Discv4 {
     ///
     /// This includes the currently tracked external IP address of the node.
     node_record: Arc<Mutex<NodeRecord>>,
+    /// Tracks the external address resolution, if a [`NatResolver`] is configured.
+    nat_status: Arc<Mutex<Option<NatStatus>>>,
 }
 
 // === impl Discv4 ===
@@ -206,6 +212,7 @@ impl Discv4 {
                 "127.0.0.1:3030".parse().unwrap(),
                 PeerId::random(),
             ))),
+            nat_status: Default::default(),
         }
     }
 
@@ -276,6 +283,21 @@ impl Discv4 {
         self.node_record.lock().address
     }
 
+    /// Returns the status of the external address resolution.
+    ///
+    /// Returns `None` if no [`NatResolver`] is configured.
+    pub fn nat_status(&self) -> Option<NatStatus> {
+        *self.nat_status.lock()
+    }
+
+    /// Triggers a new resolution of the external address, without waiting for the next scheduled
+    /// attempt.
+    ///
+    /// This has no effect if no [`NatResolver`] is configured.
+    pub fn refresh_nat(&self) {
+        self.send_to_service(Discv4Command::RefreshNat)
+    }
+
     /// Sets the [Interval] used for periodically looking up targets over the network
     pub fn set_lookup_interval(&self, duration: Duration) {
         self.send_to_service(Discv4Command::SetLookupInterval(duration))
@@ -431,6 +453,8 @@ pub struct Discv4Service {
     local_node_record: NodeRecord,
     /// Keeps track of the node record of the local node.
     shared_node_record: Arc<Mutex<NodeRecord>>,
+    /// Keeps track of the external address resolution, shared with the [`Discv4`] handles.
+    shared_nat_status: Arc<Mutex<Option<NatStatus>>>,
     /// The secret key used to sign payloads
     secret_key: SecretKey,
     /// The UDP socket for sending and receiving messages.
@@ -483,6 +507,8 @@ pub struct Discv4Service {
     ping_interval: Interval,
     /// The interval at which to attempt resolving external IP again.
     resolve_external_ip_interval: Option<ResolveNatInterval>,
+    /// Metrics for the external IP resolution.
+    nat_metrics: NatMetrics,
     /// How this services is configured
     config: Discv4Config,
     /// Buffered events populated during poll.
@@ -553,21 +579,37 @@ impl Discv4Service {
                 builder.tcp6(local_node_record.tcp_port);
             }
 
+            // the capabilities are truncated to fit into the record
+            let mut capabilities = None;
             for (key, val) in &config.additional_eip868_rlp_pairs {
+                if key.as_slice() == ENR_CAPABILITIES_KEY {
+                    capabilities = EnrCapabilities::decode(&mut &val[..]).ok();
+                    continue
+                }
                 builder.add_value_rlp(key, val.clone());
             }
-            builder.build(&secret_key).expect("v4 is set")
+            match capabilities {
+                Some(capabilities) => capabilities.build_enr(&mut builder, &secret_key),
+                None => builder.build(&secret_key),
+            }
+            .expect("v4 is set")
         };
 
         let (to_service, commands_rx) = mpsc::unbounded_channel();
 
         let shared_node_record = Arc::new(Mutex::new(local_node_record));
 
+        let resolve_external_ip_interval = config.resolve_external_ip_interval();
+        let shared_nat_status = Arc::new(Mutex::new(
+            resolve_external_ip_interval.as_ref().map(|interval| *interval.status()),
+        ));
+
         Self {
             local_address,
             local_eip_868_enr,
             local_node_record,
             shared_node_record,
+            shared_nat_status,
             _socket: socket,
             kbuckets,
             secret_key,
@@ -586,7 +628,8 @@ impl Discv4Service {
             ping_interval,
             evict_expired_requests_interval,
             lookup_rotator,
-            resolve_external_ip_interval: config.resolve_external_ip_interval(),
+            resolve_external_ip_interval,
+            nat_metrics: Default::default(),
             config,
             queued_events: Default::default(),
             received_pongs: Default::default(),
@@ -600,6 +643,7 @@ impl Discv4Service {
             local_addr: self.local_address,
             to_service: self.to_service.clone(),
             node_record: self.shared_node_record.clone(),
+            nat_status: self.shared_nat_status.clone(),
         }
     }
 
@@ -1251,6 +1295,7 @@ impl Discv4Service {
             if resp.echo_hash == msg.request_hash {
                 let key = kad_key(id);
                 let fork_id = msg.eth_fork_id();
+                let capabilities = msg.capabilities();
                 let (record, old_fork_id) = match self.kbuckets.entry(&key) {
                     kbucket::Entry::Present(mut entry, _) => {
                         let id = entry.value_mut().update_with_fork_id(fork_id);
@@ -1271,6 +1316,9 @@ impl Discv4Service {
                     (Some(new), None) => self.notify(DiscoveryUpdate::EnrForkId(record, new)),
                     _ => {}
                 }
+                if let Some(capabilities) = capabilities {
+                    self.notify(DiscoveryUpdate::EnrCapabilities(record, capabilities))
+                }
             }
         }
     }
@@ -1602,10 +1650,15 @@ impl Discv4Service {
                 self.re_ping_oldest();
             }
 
-            if let Some(Poll::Ready(Some(ip))) =
-                self.resolve_external_ip_interval.as_mut().map(|r| r.poll_tick(cx))
-            {
-                self.set_external_ip_addr(ip);
+            if let Some(resolve) = self.resolve_external_ip_interval.as_mut() {
+                if let Poll::Ready(ip) = resolve.poll_tick(cx) {
+                    let status = *resolve.status();
+                    self.nat_metrics.on_refresh(&status);
+                    *self.shared_nat_status.lock() = Some(status);
+                    if let Some(ip) = ip {
+                        self.set_external_ip_addr(ip);
+                    }
+                }
             }
 
             // drain all incoming `Discv4` commands, this channel can never close
@@ -1651,6 +1704,15 @@ impl Discv4Service {
                         }
                     }
 
+                    Discv4Command::RefreshNat => {
+                        if let Some(resolve) = self.resolve_external_ip_interval.as_mut() {
+                            debug!(target: "discv4", "Refreshing external ip");
+                            self.nat_metrics.on_forced_refresh();
+                            resolve.refresh();
+                            // make sure the new attempt is polled
+                            cx.waker().wake_by_ref();
+                        }
+                    }
                     Discv4Command::Terminated => {
                         // terminate the service
                         self.queued_events.push_back(Discv4Event::Terminated);
@@ -1938,6 +2000,7 @@ enum Discv4Command {
     Remove(PeerId),
     Lookup { node_id: Option<PeerId>, tx: Option<NodeRecordSender> },
     SetLookupInterval(Duration),
+    RefreshNat,
     Updates(OneshotSender<ReceiverStream<DiscoveryUpdate>>),
     Terminated,
 }
@@ -2275,6 +2338,8 @@ pub enum DiscoveryUpdate {
     DiscoveredAtCapacity(NodeRecord),
     /// Received a [`ForkId`] via EIP-868 for the given [`NodeRecord`].
     EnrForkId(NodeRecord, ForkId),
+    /// Received the advertised `RLPx` capabilities via EIP-868 for the given [`NodeRecord`].
+    EnrCapabilities(NodeRecord, EnrCapabilities),
     /// Node that was removed from the table
     Removed(PeerId),
     /// A series of updates
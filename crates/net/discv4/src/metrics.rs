@@ -0,0 +1,47 @@
+//! Tracks the external address resolution of [`Discv4`](crate::Discv4).
+
+use metrics::{Counter, Gauge};
+use reth_metrics::Metrics;
+use reth_net_nat::NatStatus;
+use std::time::UNIX_EPOCH;
+
+/// Metrics for the external address resolution.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "discv4.nat")]
+pub(crate) struct NatMetrics {
+    /// Whether an external address is currently known, 1 if so, otherwise 0.
+    external_addr_known: Gauge,
+    /// Unix timestamp in seconds of the last resolution attempt.
+    last_refresh_timestamp: Gauge,
+    /// Number of consecutive failed resolution attempts.
+    consecutive_failures: Gauge,
+    /// Total number of resolution attempts.
+    refreshes_total: Counter,
+    /// Total number of failed resolution attempts.
+    failed_refreshes_total: Counter,
+    /// Total number of resolution attempts that were forced via
+    /// [`Discv4::refresh_nat`](crate::Discv4::refresh_nat).
+    forced_refreshes_total: Counter,
+}
+
+impl NatMetrics {
+    /// Updates the metrics after a resolution attempt completed.
+    pub(crate) fn on_refresh(&self, status: &NatStatus) {
+        self.refreshes_total.increment(1);
+        if status.consecutive_failures > 0 {
+            self.failed_refreshes_total.increment(1);
+        }
+        self.external_addr_known.set(status.external_addr.is_some() as u8 as f64);
+        self.consecutive_failures.set(status.consecutive_failures as f64);
+        if let Some(last_refresh) = status.last_refresh {
+            let timestamp =
+                last_refresh.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+            self.last_refresh_timestamp.set(timestamp as f64);
+        }
+    }
+
+    /// Records a forced resolution attempt.
+    pub(crate) fn on_forced_refresh(&self) {
+        self.forced_refreshes_total.increment(1);
+    }
+}
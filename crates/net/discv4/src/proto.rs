@@ -8,7 +8,7 @@ use alloy_primitives::{
 use alloy_rlp::{Decodable, Encodable, Error as RlpError, Header, RlpDecodable, RlpEncodable};
 use enr::Enr;
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
-use reth_network_peers::{pk2id, NodeRecord, PeerId};
+use reth_network_peers::{pk2id, EnrCapabilities, NodeRecord, PeerId};
 use secp256k1::{
     ecdsa::{RecoverableSignature, RecoveryId},
     SecretKey, SECP256K1,
@@ -364,6 +364,11 @@ impl EnrResponse {
         let mut maybe_fork_id = self.enr.get_raw_rlp(b"eth")?;
         EnrForkIdEntry::decode(&mut maybe_fork_id).ok().map(Into::into)
     }
+
+    /// Returns the advertised [`EnrCapabilities`] if set.
+    pub fn capabilities(&self) -> Option<EnrCapabilities> {
+        EnrCapabilities::from_enr(&self.enr)
+    }
 }
 
 /// Represents a Ping packet.
@@ -755,6 +760,8 @@ mod tests {
         let tcp = 3000;
 
         let fork_id: ForkId = ForkId { hash: ForkHash([220, 233, 108, 45]), next: 0u64 };
+        let capabilities: EnrCapabilities =
+            [("eth".to_string(), 68), ("snap".to_string(), 1)].into_iter().collect();
 
         let enr = {
             let mut builder = Enr::builder();
@@ -764,6 +771,7 @@ mod tests {
             let forkentry = EnrForkIdEntry { fork_id };
             forkentry.encode(&mut buf);
             builder.add_value_rlp("eth", buf.into());
+            builder.add_value(reth_network_peers::ENR_CAPABILITIES_KEY, &capabilities);
             builder.build(&key).unwrap()
         };
 
@@ -776,6 +784,7 @@ mod tests {
 
         let fork_id_decoded = decoded.eth_fork_id().unwrap();
         assert_eq!(fork_id, fork_id_decoded);
+        assert_eq!(decoded.capabilities(), Some(capabilities));
     }
 
     // test vector from the enr library rlp encoding tests
@@ -11,7 +11,7 @@ use derive_more::Display;
 use discv5::ListenConfig;
 use multiaddr::{Multiaddr, Protocol};
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
-use reth_network_peers::NodeRecord;
+use reth_network_peers::{EnrCapabilities, NodeRecord, ENR_CAPABILITIES_KEY};
 use tracing::warn;
 
 use crate::{enr::discv4_id_to_multiaddr_id, filter::MustNotIncludeKeys, NetworkStackId};
@@ -176,6 +176,13 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the `RLPx` capabilities to advertise in the local [`Enr`](discv5::enr::Enr), under the
+    /// [`ENR_CAPABILITIES_KEY`] key. This lets peers on the discovery network skip dialing this
+    /// node if they don't share any capabilities.
+    pub fn capabilities(self, capabilities: &EnrCapabilities) -> Self {
+        self.add_enr_kv_pair(ENR_CAPABILITIES_KEY, alloy_rlp::encode(capabilities).into())
+    }
+
     /// Sets the interval at which to run lookup queries, in order to fill kbuckets. Lookup queries
     /// are done periodically at the given interval for the whole run of the program.
     pub const fn lookup_interval(mut self, seconds: u64) -> Self {
@@ -18,13 +18,14 @@ use std::{
 
 use ::enr::Enr;
 use alloy_primitives::bytes::Bytes;
+use alloy_rlp::Decodable;
 use discv5::ListenConfig;
 use enr::{discv4_id_to_discv5_id, EnrCombinedKeyWrapper};
 use futures::future::join_all;
 use itertools::Itertools;
 use rand::{Rng, RngCore};
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_network_peers::{EnrCapabilities, NodeRecord, PeerId, ENR_CAPABILITIES_KEY};
 use secp256k1::SecretKey;
 use tokio::{sync::mpsc, task};
 use tracing::{debug, error, trace};
@@ -322,13 +323,16 @@ impl Discv5 {
             .then(|| self.get_fork_id(enr).ok())
             .flatten();
 
+        let capabilities = EnrCapabilities::from_enr(enr);
+
         trace!(target: "net::discv5",
             ?fork_id,
+            ?capabilities,
             ?enr,
             "discovered peer"
         );
 
-        Some(DiscoveredPeer { node_record, fork_id })
+        Some(DiscoveredPeer { node_record, fork_id, capabilities })
     }
 
     /// Tries to convert an [`Enr`](discv5::Enr) into the backwards compatible type [`NodeRecord`],
@@ -415,6 +419,8 @@ pub struct DiscoveredPeer {
     pub node_record: NodeRecord,
     /// [`ForkId`] extracted from ENR w.r.t. configured
     pub fork_id: Option<ForkId>,
+    /// `RLPx` capabilities advertised in the ENR, if any.
+    pub capabilities: Option<EnrCapabilities>,
 }
 
 /// Builds the local ENR with the supplied key.
@@ -469,14 +475,23 @@ pub fn build_local_enr(
         *network_stack_id
     });
 
-    // add other data
+    // add other data, the capabilities are truncated to fit into the record
+    let mut capabilities = None;
     for (key, value) in other_enr_kv_pairs {
+        if *key == ENR_CAPABILITIES_KEY {
+            capabilities = EnrCapabilities::decode(&mut &value[..]).ok();
+            continue
+        }
         builder.add_value_rlp(key, value.clone().into());
     }
 
     // enr v4 not to get confused with discv4, independent versioning enr and
     // discovery
-    let enr = builder.build(sk).expect("should build enr v4");
+    let enr = match capabilities {
+        Some(capabilities) => capabilities.build_enr(&mut builder, sk),
+        None => builder.build(sk),
+    }
+    .expect("should build enr v4");
 
     // backwards compatible enr
     let bc_enr = NodeRecord::from_secret_key(socket, sk);
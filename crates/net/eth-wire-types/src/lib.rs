@@ -34,3 +34,6 @@ pub use state::*;
 
 pub mod receipts;
 pub use receipts::*;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_vectors;
@@ -0,0 +1,249 @@
+//! Golden RLP test vectors for the `eth` protocol messages.
+//!
+//! Vectors are stored as hex encoded files in `testdata/vectors/eth<version>/<message>.rlp`, one
+//! file per message. Each file contains a single complete protocol message: the message ID
+//! followed by its RLP payload. Whitespace is ignored and lines starting with `#` are comments.
+//!
+//! A vector conforms if it decodes as a [`ProtocolMessage`] for its version without any trailing
+//! bytes and re-encodes to exactly the same bytes.
+//!
+//! Note: `eth/69` is not supported yet and has no vectors.
+
+use crate::{message::MessageError, EthVersion, ProtocolMessage};
+use alloy_rlp::Encodable;
+use reth_primitives::{hex, Bytes};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The directory that contains the vectors shipped with this crate.
+pub const TEST_VECTORS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/vectors");
+
+/// The file extension of a test vector.
+const TEST_VECTOR_EXTENSION: &str = "rlp";
+
+/// Errors that can occur when loading or checking a [`TestVector`].
+#[derive(Debug, thiserror::Error)]
+pub enum TestVectorError {
+    /// Failed to read a vector file or directory.
+    #[error("failed to read {}: {err}", path.display())]
+    Io {
+        /// The path that couldn't be read.
+        path: PathBuf,
+        /// The underlying error.
+        err: io::Error,
+    },
+    /// The vector file doesn't contain valid hex.
+    #[error("invalid hex in {}: {err}", path.display())]
+    InvalidHex {
+        /// The path of the vector.
+        path: PathBuf,
+        /// The underlying error.
+        err: hex::FromHexError,
+    },
+    /// The vector failed to decode.
+    #[error("vector {name} failed to decode for {version}: {err}")]
+    Decode {
+        /// The name of the vector.
+        name: String,
+        /// The version the vector was decoded with.
+        version: EthVersion,
+        /// The underlying error.
+        err: MessageError,
+    },
+    /// The vector has bytes left after decoding the message.
+    #[error("vector {name} has {remaining} trailing bytes for {version}")]
+    TrailingBytes {
+        /// The name of the vector.
+        name: String,
+        /// The version the vector was decoded with.
+        version: EthVersion,
+        /// The number of bytes left after decoding.
+        remaining: usize,
+    },
+    /// The re-encoded message differs from the vector.
+    #[error(
+        "vector {name} re-encodes differently for {version}: expected {expected}, got {actual}"
+    )]
+    Mismatch {
+        /// The name of the vector.
+        name: String,
+        /// The version the vector was decoded with.
+        version: EthVersion,
+        /// The bytes of the vector.
+        expected: Bytes,
+        /// The re-encoded bytes.
+        actual: Bytes,
+    },
+}
+
+/// A single golden protocol message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestVector {
+    /// The name of the vector, this is the file name without extension.
+    pub name: String,
+    /// The `eth` version the vector is valid for.
+    pub version: EthVersion,
+    /// The encoded protocol message, including the message ID.
+    pub rlp: Bytes,
+}
+
+impl TestVector {
+    /// Loads the vector from the given file.
+    pub fn from_file(version: EthVersion, path: &Path) -> Result<Self, TestVectorError> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| TestVectorError::Io { path: path.to_path_buf(), err })?;
+        let hex = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .flat_map(|line| line.split_whitespace())
+            .collect::<String>();
+        let rlp = hex::decode(hex)
+            .map_err(|err| TestVectorError::InvalidHex { path: path.to_path_buf(), err })?;
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+
+        Ok(Self { name, version, rlp: rlp.into() })
+    }
+
+    /// Decodes the vector as a [`ProtocolMessage`] of the given version.
+    ///
+    /// This is useful to check that a vector is rejected by other versions.
+    pub fn decode_as(&self, version: EthVersion) -> Result<ProtocolMessage, TestVectorError> {
+        let mut buf = &self.rlp[..];
+        let msg = ProtocolMessage::decode_message(version, &mut buf)
+            .map_err(|err| TestVectorError::Decode { name: self.name.clone(), version, err })?;
+        if !buf.is_empty() {
+            return Err(TestVectorError::TrailingBytes {
+                name: self.name.clone(),
+                version,
+                remaining: buf.len(),
+            })
+        }
+        Ok(msg)
+    }
+
+    /// Checks that the vector decodes for its version and re-encodes to the same bytes.
+    ///
+    /// Returns the decoded message on success.
+    pub fn check(&self) -> Result<ProtocolMessage, TestVectorError> {
+        let msg = self.decode_as(self.version)?;
+        let mut encoded = Vec::with_capacity(msg.length());
+        msg.encode(&mut encoded);
+        if encoded != self.rlp[..] {
+            return Err(TestVectorError::Mismatch {
+                name: self.name.clone(),
+                version: self.version,
+                expected: self.rlp.clone(),
+                actual: encoded.into(),
+            })
+        }
+        Ok(msg)
+    }
+}
+
+/// Loads all vectors for the given version from `dir/eth<version>`, sorted by name.
+///
+/// Returns an empty list if there are no vectors for the version.
+pub fn load_version(dir: &Path, version: EthVersion) -> Result<Vec<TestVector>, TestVectorError> {
+    let dir = dir.join(format!("eth{}", u8::from(version)));
+    if !dir.exists() {
+        return Ok(Vec::new())
+    }
+
+    let entries =
+        fs::read_dir(&dir).map_err(|err| TestVectorError::Io { path: dir.clone(), err })?;
+    let mut vectors = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|err| TestVectorError::Io { path: dir.clone(), err })?.path();
+        if path.extension().is_some_and(|ext| ext == TEST_VECTOR_EXTENSION) {
+            vectors.push(TestVector::from_file(version, &path)?);
+        }
+    }
+    vectors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(vectors)
+}
+
+/// Loads the vectors of all supported versions from the given directory.
+pub fn load_all(dir: &Path) -> Result<Vec<TestVector>, TestVectorError> {
+    let mut vectors = Vec::new();
+    for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68] {
+        vectors.extend(load_version(dir, version)?);
+    }
+    Ok(vectors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthMessageID;
+    use std::collections::HashSet;
+
+    fn vectors(version: EthVersion) -> Vec<TestVector> {
+        load_version(Path::new(TEST_VECTORS_DIR), version).unwrap()
+    }
+
+    #[test]
+    fn vectors_conform() {
+        let vectors = load_all(Path::new(TEST_VECTORS_DIR)).unwrap();
+        assert!(!vectors.is_empty());
+        for vector in vectors {
+            if let Err(err) = vector.check() {
+                panic!("{err}");
+            }
+        }
+    }
+
+    #[test]
+    fn vectors_cover_all_messages() {
+        for version in [EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68] {
+            let ids = vectors(version)
+                .iter()
+                .map(|vector| vector.check().unwrap().message_type as u8)
+                .collect::<HashSet<_>>();
+            assert_eq!(ids.len(), version.total_messages() as usize, "{version}");
+        }
+    }
+
+    #[test]
+    fn node_data_rejected_after_eth66() {
+        let node_data = vectors(EthVersion::Eth66)
+            .into_iter()
+            .filter(|vector| {
+                matches!(
+                    vector.check().unwrap().message_type,
+                    EthMessageID::GetNodeData | EthMessageID::NodeData
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(node_data.len(), 2);
+
+        for vector in node_data {
+            for version in [EthVersion::Eth67, EthVersion::Eth68] {
+                assert!(matches!(
+                    vector.decode_as(version),
+                    Err(TestVectorError::Decode { err: MessageError::Invalid(..), .. })
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn vector_with_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("get_receipts.rlp");
+        fs::write(
+            &path,
+            "# GetReceipts with request id 1111\n0ff847820457f842\n\
+             a000000000000000000000000000000000000000000000000000000000deadc0de\n\
+             a000000000000000000000000000000000000000000000000000000000feedbeef\n",
+        )
+        .unwrap();
+
+        let vector = TestVector::from_file(EthVersion::Eth68, &path).unwrap();
+        assert_eq!(vector.name, "get_receipts");
+        assert_eq!(vector.check().unwrap().message_type, EthMessageID::GetReceipts);
+    }
+}
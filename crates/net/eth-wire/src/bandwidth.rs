@@ -0,0 +1,132 @@
+//! Per capability bandwidth accounting of a single `RLPx` connection.
+
+use crate::capability::{Capability, SharedCapabilities};
+use reth_metrics::metrics::{counter, Counter};
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Tracks the number of bytes sent and received per shared capability of a connection.
+///
+/// This is a cheaply cloneable handle, all clones share the same counters. The counted bytes are
+/// the (snappy compressed) message sizes as they are written to and read from the wire, excluding
+/// the reserved `p2p` messages.
+///
+/// In addition to the per connection counters, the bytes are also recorded in the global
+/// `p2pstream.capability_ingress_bytes` and `p2pstream.capability_egress_bytes` metrics, labeled
+/// by capability.
+#[derive(Debug, Clone, Default)]
+pub struct CapabilityBandwidthMeter {
+    counters: Arc<Vec<CapabilityCounter>>,
+}
+
+impl CapabilityBandwidthMeter {
+    /// Creates a new meter for the given shared capabilities.
+    pub fn new(shared_capabilities: &SharedCapabilities) -> Self {
+        let counters = shared_capabilities
+            .iter_caps()
+            .map(|cap| CapabilityCounter::new(cap.capability().into_owned()))
+            .collect();
+        Self { counters: Arc::new(counters) }
+    }
+
+    /// Records `bytes` received for the shared capability at the given index.
+    #[inline]
+    pub(crate) fn record_ingress(&self, idx: usize, bytes: usize) {
+        if let Some(counter) = self.counters.get(idx) {
+            counter.ingress.fetch_add(bytes as u64, Ordering::Relaxed);
+            counter.ingress_metric.increment(bytes as u64);
+        }
+    }
+
+    /// Records `bytes` sent for the shared capability at the given index.
+    #[inline]
+    pub(crate) fn record_egress(&self, idx: usize, bytes: usize) {
+        if let Some(counter) = self.counters.get(idx) {
+            counter.egress.fetch_add(bytes as u64, Ordering::Relaxed);
+            counter.egress_metric.increment(bytes as u64);
+        }
+    }
+
+    /// Returns the current bandwidth usage of the given capability, if it is shared.
+    pub fn get(&self, capability: &Capability) -> Option<CapabilityBandwidth> {
+        self.counters.iter().find(|c| &c.capability == capability).map(CapabilityCounter::snapshot)
+    }
+
+    /// Returns the current bandwidth usage of all shared capabilities.
+    pub fn snapshot(&self) -> Vec<CapabilityBandwidth> {
+        self.counters.iter().map(CapabilityCounter::snapshot).collect()
+    }
+
+    /// Returns the total number of bytes received across all shared capabilities.
+    pub fn total_ingress(&self) -> u64 {
+        self.counters.iter().map(|c| c.ingress.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the total number of bytes sent across all shared capabilities.
+    pub fn total_egress(&self) -> u64 {
+        self.counters.iter().map(|c| c.egress.load(Ordering::Relaxed)).sum()
+    }
+}
+
+/// A snapshot of the bytes exchanged for a single capability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CapabilityBandwidth {
+    /// The shared capability.
+    pub capability: Capability,
+    /// Number of bytes received for this capability.
+    pub ingress: u64,
+    /// Number of bytes sent for this capability.
+    pub egress: u64,
+}
+
+/// Counters of a single capability.
+struct CapabilityCounter {
+    capability: Capability,
+    ingress: AtomicU64,
+    egress: AtomicU64,
+    ingress_metric: Counter,
+    egress_metric: Counter,
+}
+
+impl CapabilityCounter {
+    fn new(capability: Capability) -> Self {
+        let label = capability.to_string();
+        Self {
+            ingress_metric: counter!(
+                "p2pstream.capability_ingress_bytes",
+                "capability" => label.clone()
+            ),
+            egress_metric: counter!("p2pstream.capability_egress_bytes", "capability" => label),
+            capability,
+            ingress: AtomicU64::new(0),
+            egress: AtomicU64::new(0),
+        }
+    }
+
+    fn snapshot(&self) -> CapabilityBandwidth {
+        CapabilityBandwidth {
+            capability: self.capability.clone(),
+            ingress: self.ingress.load(Ordering::Relaxed),
+            egress: self.egress.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl fmt::Debug for CapabilityCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapabilityCounter")
+            .field("capability", &self.capability)
+            .field("ingress", &self.ingress)
+            .field("egress", &self.egress)
+            .finish_non_exhaustive()
+    }
+}
@@ -7,16 +7,21 @@ use crate::{
     version::ParseVersionError,
     EthMessage, EthMessageID, EthVersion,
 };
-use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use alloy_rlp::{Buf, Decodable, Encodable, Header, RlpDecodable, RlpEncodable};
 use derive_more::{Deref, DerefMut};
 use reth_codecs::add_arbitrary_tests;
+use reth_network_peers::EnrCapability;
 use reth_primitives::bytes::{BufMut, Bytes};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::{
     borrow::Cow,
     collections::{BTreeSet, HashMap},
     fmt,
+    num::ParseIntError,
+    str::FromStr,
 };
 
 /// A Capability message consisting of the message-id and the payload
@@ -29,6 +34,12 @@ pub struct RawCapabilityMessage {
     pub payload: Bytes,
 }
 
+/// The maximum number of capabilities accepted when decoding [`Capabilities`].
+pub const MAX_CAPABILITIES: usize = 64;
+
+/// The maximum length of a capability name accepted when decoding [`Capabilities`].
+pub const MAX_CAPABILITY_NAME_LENGTH: usize = 32;
+
 /// Various protocol related event types bubbled up from a session that need to be handled by the
 /// network.
 #[derive(Debug)]
@@ -41,9 +52,12 @@ pub enum CapabilityMessage {
 }
 
 /// A message indicating a supported capability and capability version.
+///
+/// With the `serde` feature enabled this is (de)serialized as its `name/version` string
+/// representation, see [`Capability::from_str`].
 #[add_arbitrary_tests(rlp)]
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(SerializeDisplay, DeserializeFromStr))]
 pub struct Capability {
     /// The name of the subprotocol
     pub name: Cow<'static, str>,
@@ -105,6 +119,15 @@ impl Capability {
     pub fn is_eth(&self) -> bool {
         self.is_eth_v66() || self.is_eth_v67() || self.is_eth_v68()
     }
+
+    /// Whether the name is a valid capability name: non-empty, at most
+    /// [`MAX_CAPABILITY_NAME_LENGTH`] bytes and only printable ASCII characters.
+    #[inline]
+    pub fn has_valid_name(&self) -> bool {
+        !self.name.is_empty() &&
+            self.name.len() <= MAX_CAPABILITY_NAME_LENGTH &&
+            self.name.bytes().all(|b| b.is_ascii_graphic())
+    }
 }
 
 impl fmt::Display for Capability {
@@ -113,6 +136,56 @@ impl fmt::Display for Capability {
     }
 }
 
+/// Error returned when parsing a [`Capability`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseCapabilityError {
+    /// The string is not of the form `name/version`.
+    #[error("invalid capability `{0}`, expected `name/version`")]
+    InvalidFormat(String),
+    /// The capability name is empty.
+    #[error("empty capability name in `{0}`")]
+    EmptyName(String),
+    /// The capability version is not a valid number.
+    #[error("invalid capability version in `{input}`: {err}")]
+    InvalidVersion {
+        /// The input that failed to parse.
+        input: String,
+        /// The underlying integer parse error.
+        #[source]
+        err: ParseIntError,
+    },
+}
+
+/// Parses a [`Capability`] from its `name/version` representation, e.g. `eth/68` or `snap/1`.
+///
+/// This is the inverse of the [`Display`](fmt::Display) implementation.
+///
+/// # Example
+/// ```
+/// use reth_eth_wire::Capability;
+///
+/// let cap: Capability = "eth/68".parse().unwrap();
+/// assert_eq!(cap, Capability::eth_68());
+/// assert_eq!(cap.to_string(), "eth/68");
+/// ```
+impl FromStr for Capability {
+    type Err = ParseCapabilityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, version) = s
+            .trim()
+            .rsplit_once('/')
+            .ok_or_else(|| ParseCapabilityError::InvalidFormat(s.to_string()))?;
+        if name.is_empty() {
+            return Err(ParseCapabilityError::EmptyName(s.to_string()))
+        }
+        let version = version
+            .parse()
+            .map_err(|err| ParseCapabilityError::InvalidVersion { input: s.to_string(), err })?;
+        Ok(Self::new(name.to_string(), version))
+    }
+}
+
 impl From<EthVersion> for Capability {
     #[inline]
     fn from(value: EthVersion) -> Self {
@@ -120,11 +193,36 @@ impl From<EthVersion> for Capability {
     }
 }
 
+impl From<EnrCapability> for Capability {
+    #[inline]
+    fn from(value: EnrCapability) -> Self {
+        Self::new(value.name, value.version as usize)
+    }
+}
+
+impl From<&Capability> for EnrCapability {
+    #[inline]
+    fn from(value: &Capability) -> Self {
+        Self { name: value.name.to_string(), version: value.version as u64 }
+    }
+}
+
+/// Generates `eth` capabilities about half of the time, otherwise a random but valid capability,
+/// see [`Capability::has_valid_name`], so that unknown protocols are exercised as well.
 #[cfg(any(test, feature = "arbitrary"))]
 impl<'a> arbitrary::Arbitrary<'a> for Capability {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let version = u.int_in_range(0..=32)?; // TODO: What's the max?
-        let name = String::arbitrary(u)?; // TODO: what possible values?
+        if u.arbitrary()? {
+            let version = *u.choose(&[EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68])?;
+            return Ok(Self::eth(version))
+        }
+
+        const NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        let len = u.int_in_range(1..=8)?;
+        let name = (0..len)
+            .map(|_| u.choose(NAME_CHARS).map(|c| *c as char))
+            .collect::<arbitrary::Result<String>>()?;
+        let version = u.int_in_range(0..=u8::MAX as usize)?;
         Ok(Self::new(name, version))
     }
 }
@@ -194,17 +292,61 @@ impl Encodable for Capabilities {
     }
 }
 
+/// Decodes the list of capabilities, rejecting lists with more than [`MAX_CAPABILITIES`] entries
+/// and capabilities with an invalid name, see [`Capability::has_valid_name`].
 impl Decodable for Capabilities {
     fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
-        let inner = Vec::<Capability>::decode(buf)?;
+        Ok(decode_capability_list(buf)?.into())
+    }
+}
 
-        Ok(Self {
-            eth_66: inner.iter().any(Capability::is_eth_v66),
-            eth_67: inner.iter().any(Capability::is_eth_v67),
-            eth_68: inner.iter().any(Capability::is_eth_v68),
-            inner,
-        })
+/// Decodes an RLP list of capabilities within the limits of [`check_capabilities`].
+pub(crate) fn decode_capability_list(buf: &mut &[u8]) -> alloy_rlp::Result<Vec<Capability>> {
+    let header = Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort)
+    }
+    let mut payload = &buf[..header.payload_length];
+    buf.advance(header.payload_length);
+
+    let mut capabilities = Vec::new();
+    while !payload.is_empty() {
+        if capabilities.len() == MAX_CAPABILITIES {
+            return Err(alloy_rlp::Error::Custom("too many capabilities"))
+        }
+        let cap = Capability::decode(&mut payload)?;
+        if !cap.has_valid_name() {
+            return Err(alloy_rlp::Error::Custom("invalid capability name"))
+        }
+        capabilities.push(cap);
+    }
+    Ok(capabilities)
+}
+
+/// Ensures that there are at most [`MAX_CAPABILITIES`] capabilities and that all of them have a
+/// valid name, see [`Capability::has_valid_name`].
+pub fn check_capabilities(capabilities: &[Capability]) -> Result<(), InvalidCapabilitiesError> {
+    if capabilities.len() > MAX_CAPABILITIES {
+        return Err(InvalidCapabilitiesError::TooMany(capabilities.len()))
+    }
+    if let Some(cap) = capabilities.iter().find(|cap| !cap.has_valid_name()) {
+        return Err(InvalidCapabilitiesError::InvalidName(cap.name.to_string()))
     }
+    Ok(())
+}
+
+/// Error of a list of capabilities that exceeds the limits that apply when it's decoded.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidCapabilitiesError {
+    /// More than [`MAX_CAPABILITIES`] capabilities.
+    #[error("too many capabilities: {0}, max {MAX_CAPABILITIES}")]
+    TooMany(usize),
+    /// A capability with an invalid name, see [`Capability::has_valid_name`].
+    #[error("invalid capability name {0:?}")]
+    InvalidName(String),
 }
 
 /// This represents a shared capability, its version, and its message id offset.
@@ -421,6 +563,19 @@ impl SharedCapabilities {
         Some(cap)
     }
 
+    /// Returns the index of the shared capability that the given message id belongs to.
+    ///
+    /// Same as [`Self::find_by_offset`] but returns the position of the capability in the list of
+    /// shared capabilities.
+    #[inline]
+    pub fn position_by_offset(&self, offset: u8) -> Option<usize> {
+        if offset < self.0.first()?.message_id_offset() {
+            // reserved message id space
+            return None
+        }
+        self.0.iter().rposition(|cap| cap.message_id_offset() <= offset)
+    }
+
     /// Returns the shared capability for the given capability or an error if it's not compatible.
     #[inline]
     pub fn ensure_matching_capability(
@@ -549,6 +704,34 @@ pub struct UnsupportedCapabilityError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn parse_capability() {
+        let cap: Capability = "eth/68".parse().unwrap();
+        assert_eq!(cap, Capability::eth_68());
+        assert!(cap.is_eth_v68());
+
+        let cap: Capability = "snap/1".parse().unwrap();
+        assert_eq!(cap, Capability::new_static("snap", 1));
+        assert_eq!(cap.to_string().parse::<Capability>().unwrap(), cap);
+
+        assert!(matches!("eth".parse::<Capability>(), Err(ParseCapabilityError::InvalidFormat(_))));
+        assert!(matches!("/68".parse::<Capability>(), Err(ParseCapabilityError::EmptyName(_))));
+        assert!(matches!(
+            "eth/latest".parse::<Capability>(),
+            Err(ParseCapabilityError::InvalidVersion { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn capability_serde_roundtrip() {
+        let cap = Capability::new_static("snap", 1);
+        let json = serde_json::to_string(&cap).unwrap();
+        assert_eq!(json, r#""snap/1""#);
+        assert_eq!(serde_json::from_str::<Capability>(&json).unwrap(), cap);
+    }
 
     #[test]
     fn from_eth_68() {
@@ -708,4 +891,54 @@ mod tests {
         let shared_eth = shared.find_by_relative_offset(1 + proto.messages()).unwrap();
         assert_eq!(shared_eth.name(), "eth");
     }
+
+    #[test]
+    fn test_position_by_offset() {
+        let cap = Capability::new_static("aaa", 1);
+        let proto = Protocol::new(cap.clone(), 5);
+        let local_capabilities = vec![proto, EthVersion::Eth66.into()];
+        let peer_capabilities = vec![cap, EthVersion::Eth66.into()];
+
+        let shared = SharedCapabilities::try_new(local_capabilities, peer_capabilities).unwrap();
+
+        assert_eq!(shared.position_by_offset(MAX_RESERVED_MESSAGE_ID), None);
+        assert_eq!(shared.position_by_offset(MAX_RESERVED_MESSAGE_ID + 1), Some(0));
+        assert_eq!(shared.position_by_offset(MAX_RESERVED_MESSAGE_ID + 5), Some(0));
+        assert_eq!(shared.position_by_offset(MAX_RESERVED_MESSAGE_ID + 6), Some(1));
+        assert_eq!(shared[1].name(), "eth");
+    }
+
+    #[test]
+    fn arbitrary_capability_names() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i.wrapping_mul(2654435761) >> 7) as u8).collect();
+        let mut u = Unstructured::new(&data);
+        let caps = (0..64).map(|_| Capability::arbitrary(&mut u).unwrap()).collect::<Vec<_>>();
+        assert!(caps.iter().all(Capability::has_valid_name));
+        assert!(caps.iter().any(|cap| !cap.is_eth()));
+
+        let caps = Capabilities::from(caps);
+        let decoded = Capabilities::decode(&mut &alloy_rlp::encode(&caps)[..]).unwrap();
+        assert_eq!(decoded, caps);
+    }
+
+    #[test]
+    fn decode_capabilities_limits() {
+        let caps = vec![Capability::new_static("snap", 1); MAX_CAPABILITIES + 1];
+        let encoded = alloy_rlp::encode(caps);
+        assert!(Capabilities::decode(&mut &encoded[..]).is_err());
+        assert!(Capabilities::decode(&mut &encoded[..encoded.len() - 2]).is_err());
+
+        let caps = vec![Capability::new("a".repeat(MAX_CAPABILITY_NAME_LENGTH + 1), 1)];
+        assert!(Capabilities::decode(&mut &alloy_rlp::encode(caps)[..]).is_err());
+
+        let caps = vec![Capability::new_static("", 1)];
+        assert!(Capabilities::decode(&mut &alloy_rlp::encode(caps)[..]).is_err());
+
+        let caps = vec![Capability::new_static("eth\n", 68)];
+        assert!(Capabilities::decode(&mut &alloy_rlp::encode(caps)[..]).is_err());
+
+        let caps = vec![Capability::eth_68(); MAX_CAPABILITIES];
+        let decoded = Capabilities::decode(&mut &alloy_rlp::encode(caps)[..]).unwrap();
+        assert!(decoded.supports_eth_v68());
+    }
 }
@@ -1,6 +1,9 @@
-use crate::{capability::Capability, EthVersion, ProtocolVersion};
-use alloy_rlp::{RlpDecodable, RlpEncodable};
-use reth_codecs::derive_arbitrary;
+use crate::{
+    capability::{decode_capability_list, Capability},
+    EthVersion, ProtocolVersion,
+};
+use alloy_rlp::{Decodable, Header, RlpEncodable};
+use reth_codecs::add_arbitrary_tests;
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_network_peers::PeerId;
 use reth_primitives::constants::RETH_CLIENT_VERSION;
@@ -97,8 +100,8 @@ impl HelloMessageWithProtocols {
 /// supported RLPx protocol version and capabilities.
 ///
 /// See also <https://github.com/ethereum/devp2p/blob/master/rlpx.md#hello-0x00>
-#[derive_arbitrary(rlp)]
-#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[add_arbitrary_tests(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct HelloMessage {
     /// The version of the `p2p` protocol.
@@ -107,6 +110,10 @@ pub struct HelloMessage {
     /// "Ethereum(++)/1.0.0").
     pub client_version: String,
     /// The list of supported capabilities and their versions.
+    ///
+    /// Decoding and deserializing reject lists that exceed the limits of
+    /// [`check_capabilities`](crate::capability::check_capabilities).
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_capabilities"))]
     pub capabilities: Vec<Capability>,
     /// The port that the client is listening on, zero indicates the client is not listening.
     pub port: u16,
@@ -132,6 +139,64 @@ impl HelloMessage {
     }
 }
 
+/// Deserializes a list of capabilities within the limits of
+/// [`check_capabilities`](crate::capability::check_capabilities).
+#[cfg(feature = "serde")]
+fn deserialize_capabilities<'de, D>(deserializer: D) -> Result<Vec<Capability>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let capabilities = Vec::<Capability>::deserialize(deserializer)?;
+    crate::capability::check_capabilities(&capabilities).map_err(serde::de::Error::custom)?;
+    Ok(capabilities)
+}
+
+/// Decodes the message, rejecting lists of capabilities that exceed the limits of
+/// [`check_capabilities`](crate::capability::check_capabilities).
+impl Decodable for HelloMessage {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        if started_len < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let this = Self {
+            protocol_version: Decodable::decode(buf)?,
+            client_version: Decodable::decode(buf)?,
+            capabilities: decode_capability_list(buf)?,
+            port: Decodable::decode(buf)?,
+            id: Decodable::decode(buf)?,
+        };
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// Generates at most [`MAX_CAPABILITIES`](crate::capability::MAX_CAPABILITIES) capabilities, so
+/// that the message can be decoded.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for HelloMessage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let len = u.int_in_range(0..=crate::capability::MAX_CAPABILITIES)?;
+        Ok(Self {
+            protocol_version: u.arbitrary()?,
+            client_version: u.arbitrary()?,
+            capabilities: (0..len).map(|_| u.arbitrary()).collect::<arbitrary::Result<_>>()?,
+            port: u.arbitrary()?,
+            id: u.arbitrary()?,
+        })
+    }
+}
+
 /// Builder for [`HelloMessageWithProtocols`]
 #[derive(Debug)]
 pub struct HelloMessageBuilder {
@@ -214,7 +279,9 @@ mod tests {
     use secp256k1::{SecretKey, SECP256K1};
 
     use crate::{
-        capability::Capability, p2pstream::P2PMessage, EthVersion, HelloMessage, ProtocolVersion,
+        capability::{Capability, MAX_CAPABILITIES},
+        p2pstream::P2PMessage,
+        EthVersion, HelloMessage, ProtocolVersion,
     };
 
     #[test]
@@ -274,4 +341,41 @@ mod tests {
         // zero is encoded as 0x80, the empty string code in RLP
         assert_eq!(hello_encoded[0], EMPTY_STRING_CODE);
     }
+
+    #[test]
+    fn hello_capabilities_limits() {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let id = pk2id(&secret_key.public_key(SECP256K1));
+        let mut hello = HelloMessage::builder(id).build().into_message();
+
+        // a message with too many capabilities can't be decoded
+        hello.capabilities = vec![Capability::new_static("snap", 1); MAX_CAPABILITIES + 1];
+        let mut encoded = Vec::new();
+        hello.encode(&mut encoded);
+        assert!(HelloMessage::decode(&mut &encoded[..]).is_err());
+
+        hello.capabilities = vec![Capability::eth_68()];
+        let mut encoded = Vec::new();
+        hello.encode(&mut encoded);
+        assert_eq!(HelloMessage::decode(&mut &encoded[..]).unwrap(), hello);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn hello_capabilities_limits_serde() {
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+        let id = pk2id(&secret_key.public_key(SECP256K1));
+        let mut hello = HelloMessage::builder(id).build().into_message();
+
+        let json = serde_json::to_string(&hello).unwrap();
+        assert_eq!(serde_json::from_str::<HelloMessage>(&json).unwrap(), hello);
+
+        hello.capabilities = vec![Capability::new_static("snap", 1); MAX_CAPABILITIES + 1];
+        let json = serde_json::to_string(&hello).unwrap();
+        assert!(serde_json::from_str::<HelloMessage>(&json).is_err());
+
+        hello.capabilities = vec![Capability::new_static("eth\n", 68)];
+        let json = serde_json::to_string(&hello).unwrap();
+        assert!(serde_json::from_str::<HelloMessage>(&json).is_err());
+    }
 }
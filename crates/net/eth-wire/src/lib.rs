@@ -15,6 +15,7 @@
 #![allow(unknown_lints, non_local_definitions)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod bandwidth;
 pub mod capability;
 mod disconnect;
 pub mod errors;
@@ -34,13 +35,14 @@ pub use tokio_util::codec::{
 };
 
 pub use crate::{
+    bandwidth::{CapabilityBandwidth, CapabilityBandwidthMeter},
     capability::Capability,
     disconnect::{CanDisconnect, DisconnectReason},
     ethstream::{EthStream, UnauthedEthStream, MAX_MESSAGE_SIZE},
     hello::{HelloMessage, HelloMessageBuilder, HelloMessageWithProtocols},
     p2pstream::{
         DisconnectP2P, P2PMessage, P2PMessageID, P2PStream, ProtocolVersion, UnauthedP2PStream,
-        MAX_RESERVED_MESSAGE_ID,
+        HANDSHAKE_TIMEOUT, MAX_RESERVED_MESSAGE_ID,
     },
 };
 
@@ -14,18 +14,22 @@ use std::{
     io,
     pin::{pin, Pin},
     task::{ready, Context, Poll},
+    time::Duration,
 };
 
 use crate::{
     capability::{Capability, SharedCapabilities, SharedCapability, UnsupportedCapabilityError},
-    errors::{EthStreamError, P2PStreamError},
-    p2pstream::DisconnectP2P,
+    errors::{EthStreamError, P2PHandshakeError, P2PStreamError},
+    p2pstream::{DisconnectP2P, HANDSHAKE_TIMEOUT},
     CanDisconnect, DisconnectReason, EthStream, P2PStream, Status, UnauthedEthStream,
 };
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
 use reth_primitives::ForkFilter;
-use tokio::sync::{mpsc, mpsc::UnboundedSender};
+use tokio::{
+    sync::{mpsc, mpsc::UnboundedSender},
+    time::{sleep, Sleep},
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// A Stream and Sink type that wraps a raw rlpx stream [`P2PStream`] and handles message ID
@@ -51,6 +55,8 @@ impl<St> RlpxProtocolMultiplexer<St> {
     ///
     /// This accepts a closure that receives a [`ProtocolConnection`] that will yield messages for
     /// the given capability.
+    ///
+    /// The protocol is installed with [`ProtocolPriority::Normal`].
     pub fn install_protocol<F, Proto>(
         &mut self,
         cap: &Capability,
@@ -60,7 +66,23 @@ impl<St> RlpxProtocolMultiplexer<St> {
         F: FnOnce(ProtocolConnection) -> Proto,
         Proto: Stream<Item = BytesMut> + Send + 'static,
     {
-        self.inner.install_protocol(cap, f)
+        self.inner.install_protocol(cap, ProtocolPriority::Normal, f)
+    }
+
+    /// Installs a new protocol on top of the raw p2p stream with the given [`ProtocolPriority`].
+    ///
+    /// See also [`Self::install_protocol`].
+    pub fn install_protocol_with_priority<F, Proto>(
+        &mut self,
+        cap: &Capability,
+        priority: ProtocolPriority,
+        f: F,
+    ) -> Result<(), UnsupportedCapabilityError>
+    where
+        F: FnOnce(ProtocolConnection) -> Proto,
+        Proto: Stream<Item = BytesMut> + Send + 'static,
+    {
+        self.inner.install_protocol(cap, priority, f)
     }
 
     /// Returns the [`SharedCapabilities`] of the underlying raw p2p stream
@@ -209,6 +231,20 @@ impl<St> RlpxProtocolMultiplexer<St> {
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
+    where
+        St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
+    {
+        self.into_eth_satellite_stream_with_timeout(status, fork_filter, HANDSHAKE_TIMEOUT).await
+    }
+
+    /// Same as [`Self::into_eth_satellite_stream`], but waits at most `timeout_limit` for the
+    /// remote's `Status` message.
+    pub async fn into_eth_satellite_stream_with_timeout(
+        self,
+        status: Status,
+        fork_filter: ForkFilter,
+        timeout_limit: Duration,
+    ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
     where
         St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
     {
@@ -216,7 +252,9 @@ impl<St> RlpxProtocolMultiplexer<St> {
         self.into_satellite_stream_with_tuple_handshake(
             &Capability::eth(eth_cap),
             move |proxy| async move {
-                UnauthedEthStream::new(proxy).handshake(status, fork_filter).await
+                UnauthedEthStream::new(proxy)
+                    .handshake_with_timeout(status, fork_filter, timeout_limit)
+                    .await
             },
         )
         .await
@@ -230,7 +268,7 @@ struct MultiplexInner<St> {
     /// All the subprotocols that are multiplexed on top of the raw p2p stream
     protocols: Vec<ProtocolStream>,
     /// Buffer for outgoing messages on the wire.
-    out_buffer: VecDeque<Bytes>,
+    out_buffer: OutboundScheduler,
 }
 
 impl<St> MultiplexInner<St> {
@@ -252,6 +290,7 @@ impl<St> MultiplexInner<St> {
     fn install_protocol<F, Proto>(
         &mut self,
         cap: &Capability,
+        priority: ProtocolPriority,
         f: F,
     ) -> Result<(), UnsupportedCapabilityError>
     where
@@ -263,7 +302,15 @@ impl<St> MultiplexInner<St> {
         let (to_satellite, rx) = mpsc::unbounded_channel();
         let proto_conn = ProtocolConnection { from_wire: UnboundedReceiverStream::new(rx) };
         let st = f(proto_conn);
-        let st = ProtocolStream { shared_cap, to_satellite, satellite_st: Box::pin(st) };
+        let st = ProtocolStream {
+            shared_cap,
+            priority,
+            to_satellite,
+            satellite_st: Box::pin(st),
+            received_message: false,
+            first_message_deadline: None,
+            max_message_size: None,
+        };
         self.protocols.push(st);
         Ok(())
     }
@@ -404,6 +451,8 @@ impl<St, Primary> RlpxSatelliteStream<St, Primary> {
     ///
     /// This accepts a closure that receives a [`ProtocolConnection`] that will yield messages for
     /// the given capability.
+    ///
+    /// The protocol is installed with [`ProtocolPriority::Normal`].
     pub fn install_protocol<F, Proto>(
         &mut self,
         cap: &Capability,
@@ -413,7 +462,50 @@ impl<St, Primary> RlpxSatelliteStream<St, Primary> {
         F: FnOnce(ProtocolConnection) -> Proto,
         Proto: Stream<Item = BytesMut> + Send + 'static,
     {
-        self.inner.install_protocol(cap, f)
+        self.inner.install_protocol(cap, ProtocolPriority::Normal, f)
+    }
+
+    /// Installs a new protocol on top of the raw p2p stream with the given [`ProtocolPriority`].
+    ///
+    /// See also [`Self::install_protocol`].
+    pub fn install_protocol_with_priority<F, Proto>(
+        &mut self,
+        cap: &Capability,
+        priority: ProtocolPriority,
+        f: F,
+    ) -> Result<(), UnsupportedCapabilityError>
+    where
+        F: FnOnce(ProtocolConnection) -> Proto,
+        Proto: Stream<Item = BytesMut> + Send + 'static,
+    {
+        self.inner.install_protocol(cap, priority, f)
+    }
+
+    /// Requires that every installed protocol receives its first message from the remote within
+    /// the given timeout, otherwise the stream fails with a [`P2PHandshakeError::Timeout`].
+    ///
+    /// This only applies to protocols that are installed when this is called and haven't received
+    /// a message yet.
+    pub fn set_first_message_timeout(&mut self, timeout: Duration) {
+        for proto in &mut self.inner.protocols {
+            if !proto.received_message {
+                proto.first_message_deadline = Some(Box::pin(sleep(timeout)));
+            }
+        }
+    }
+
+    /// Limits the size of the messages the remote may send for the installed protocols with the
+    /// given capability name.
+    ///
+    /// If the remote exceeds the limit, it is disconnected with
+    /// [`DisconnectReason::ProtocolBreach`] and the stream fails with
+    /// [`P2PStreamError::MessageTooBig`]. Messages of the primary protocol are not affected.
+    pub fn set_max_message_size(&mut self, name: &str, max_size: usize) {
+        for proto in &mut self.inner.protocols {
+            if proto.shared_cap.name() == name {
+                proto.max_message_size = Some(max_size);
+            }
+        }
     }
 
     /// Returns the primary protocol.
@@ -468,7 +560,7 @@ where
             loop {
                 match this.inner.conn.poll_ready_unpin(cx) {
                     Poll::Ready(Ok(())) => {
-                        if let Some(msg) = this.inner.out_buffer.pop_front() {
+                        if let Some(msg) = this.inner.out_buffer.pop() {
                             if let Err(err) = this.inner.conn.start_send_unpin(msg) {
                                 return Poll::Ready(Some(Err(err.into())))
                             }
@@ -495,7 +587,7 @@ where
             loop {
                 match this.primary.from_primary.poll_next_unpin(cx) {
                     Poll::Ready(Some(msg)) => {
-                        this.inner.out_buffer.push_back(msg);
+                        this.inner.out_buffer.push(ProtocolPriority::PRIMARY, msg);
                     }
                     Poll::Ready(None) => {
                         // primary closed
@@ -508,13 +600,21 @@ where
             // advance all satellites
             for idx in (0..this.inner.protocols.len()).rev() {
                 let mut proto = this.inner.protocols.swap_remove(idx);
+                if let Some(deadline) = proto.first_message_deadline.as_mut() {
+                    if deadline.as_mut().poll(cx).is_ready() {
+                        return Poll::Ready(Some(Err(P2PStreamError::HandshakeError(
+                            P2PHandshakeError::Timeout,
+                        )
+                        .into())))
+                    }
+                }
                 loop {
                     match proto.poll_next_unpin(cx) {
                         Poll::Ready(Some(Err(err))) => {
                             return Poll::Ready(Some(Err(P2PStreamError::Io(err).into())))
                         }
                         Poll::Ready(Some(Ok(msg))) => {
-                            this.inner.out_buffer.push_back(msg);
+                            this.inner.out_buffer.push(proto.priority, msg);
                         }
                         Poll::Ready(None) => return Poll::Ready(None),
                         Poll::Pending => {
@@ -545,12 +645,35 @@ where
                                 let _ = this.primary.to_primary.send(msg);
                             } else {
                                 // delegate to installed satellite if any
-                                for proto in &this.inner.protocols {
+                                let mut too_big = None;
+                                for proto in &mut this.inner.protocols {
                                     if proto.shared_cap == *cap {
-                                        proto.send_raw(msg);
+                                        match proto.max_message_size {
+                                            Some(max_size) if msg.len() > max_size => {
+                                                too_big = Some((msg.len(), max_size));
+                                            }
+                                            _ => {
+                                                proto.on_message_received();
+                                                proto.send_raw(msg);
+                                            }
+                                        }
                                         break
                                     }
                                 }
+
+                                if let Some((message_size, max_size)) = too_big {
+                                    // the session is terminated regardless, so the disconnect
+                                    // message is sent on a best effort basis
+                                    let _ = this
+                                        .inner
+                                        .conn
+                                        .start_disconnect(DisconnectReason::ProtocolBreach);
+                                    return Poll::Ready(Some(Err(P2PStreamError::MessageTooBig {
+                                        message_size,
+                                        max_size,
+                                    }
+                                    .into())))
+                                }
                             }
                         } else {
                             return Poll::Ready(Some(Err(P2PStreamError::UnknownReservedMessageId(
@@ -610,12 +733,27 @@ where
 /// Wraps a `RLPx` subprotocol and handles message ID multiplexing.
 struct ProtocolStream {
     shared_cap: SharedCapability,
+    /// the priority class of outgoing messages of this protocol
+    priority: ProtocolPriority,
     /// the channel shared with the satellite stream
     to_satellite: UnboundedSender<BytesMut>,
     satellite_st: Pin<Box<dyn Stream<Item = BytesMut> + Send>>,
+    /// whether the remote has sent at least one message for this protocol
+    received_message: bool,
+    /// the deadline for the first message from the remote, if any
+    first_message_deadline: Option<Pin<Box<Sleep>>>,
+    /// the maximum size of a message from the remote, if any
+    max_message_size: Option<usize>,
 }
 
 impl ProtocolStream {
+    /// Records that a message from the remote was received for this protocol.
+    #[inline]
+    fn on_message_received(&mut self) {
+        self.received_message = true;
+        self.first_message_deadline = None;
+    }
+
     /// Masks the message ID of a message to be sent on the wire.
     #[inline]
     fn mask_msg_id(&self, mut msg: BytesMut) -> Result<Bytes, io::Error> {
@@ -660,7 +798,96 @@ impl Stream for ProtocolStream {
 
 impl fmt::Debug for ProtocolStream {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ProtocolStream").field("cap", &self.shared_cap).finish_non_exhaustive()
+        f.debug_struct("ProtocolStream")
+            .field("cap", &self.shared_cap)
+            .field("priority", &self.priority)
+            .field("max_message_size", &self.max_message_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The priority class of a multiplexed `RLPx` subprotocol.
+///
+/// If the connection can't keep up with the outgoing messages, buffered messages are written to
+/// the wire in weighted round-robin order: per round, each class may send up to
+/// [`ProtocolPriority::weight`] messages, higher classes first. This prioritizes higher classes
+/// without starving lower ones.
+///
+/// The primary protocol (e.g. `eth`) always uses [`ProtocolPriority::PRIMARY`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolPriority {
+    /// Low priority traffic, for example bulk data of custom subprotocols.
+    Low = 0,
+    /// Default priority.
+    #[default]
+    Normal = 1,
+    /// High priority traffic.
+    High = 2,
+}
+
+impl ProtocolPriority {
+    /// The priority of the primary protocol.
+    pub const PRIMARY: Self = Self::High;
+
+    /// All priority classes, ordered from highest to lowest.
+    const ALL: [Self; 3] = [Self::High, Self::Normal, Self::Low];
+
+    /// Returns the number of messages this class can send per scheduling round.
+    pub const fn weight(&self) -> usize {
+        match self {
+            Self::Low => 1,
+            Self::Normal => 2,
+            Self::High => 4,
+        }
+    }
+
+    /// Returns the index of this class in the scheduler's queues.
+    const fn idx(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Buffer for outgoing messages that schedules [`ProtocolPriority`] classes with weighted
+/// round-robin.
+#[derive(Debug, Default)]
+struct OutboundScheduler {
+    /// Queued messages, indexed by [`ProtocolPriority`].
+    queues: [VecDeque<Bytes>; 3],
+    /// Remaining number of messages each class can send in the current round.
+    credits: [usize; 3],
+}
+
+impl OutboundScheduler {
+    /// Queues a message with the given priority.
+    fn push(&mut self, priority: ProtocolPriority, msg: Bytes) {
+        self.queues[priority.idx()].push_back(msg);
+    }
+
+    /// Returns the next message that should be written to the wire.
+    fn pop(&mut self) -> Option<Bytes> {
+        if self.is_empty() {
+            return None
+        }
+
+        loop {
+            for priority in ProtocolPriority::ALL {
+                let idx = priority.idx();
+                if self.credits[idx] > 0 && !self.queues[idx].is_empty() {
+                    self.credits[idx] -= 1;
+                    return self.queues[idx].pop_front()
+                }
+            }
+
+            // all classes with pending messages used up their credits, start a new round
+            for priority in ProtocolPriority::ALL {
+                self.credits[priority.idx()] = priority.weight();
+            }
+        }
+    }
+
+    /// Returns `true` if there are no queued messages.
+    fn is_empty(&self) -> bool {
+        self.queues.iter().all(VecDeque::is_empty)
     }
 }
 
@@ -677,6 +904,32 @@ mod tests {
     use tokio::{net::TcpListener, sync::oneshot};
     use tokio_util::codec::Decoder;
 
+    #[test]
+    fn outbound_scheduler_weighted() {
+        let mut scheduler = OutboundScheduler::default();
+        assert!(scheduler.pop().is_none());
+
+        for i in 0..8u8 {
+            scheduler.push(ProtocolPriority::Low, Bytes::from(vec![0, i]));
+            scheduler.push(ProtocolPriority::High, Bytes::from(vec![2, i]));
+        }
+
+        let classes = std::iter::from_fn(|| scheduler.pop()).map(|msg| msg[0]).collect::<Vec<_>>();
+        // 4 high priority messages are sent per low priority message
+        assert_eq!(classes, vec![2, 2, 2, 2, 0, 2, 2, 2, 2, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(scheduler.is_empty());
+    }
+
+    #[test]
+    fn outbound_scheduler_fifo_per_class() {
+        let mut scheduler = OutboundScheduler::default();
+        for i in 0..3u8 {
+            scheduler.push(ProtocolPriority::Normal, Bytes::from(vec![i]));
+        }
+        let msgs = std::iter::from_fn(|| scheduler.pop()).map(|msg| msg[0]).collect::<Vec<_>>();
+        assert_eq!(msgs, vec![0, 1, 2]);
+    }
+
     #[tokio::test]
     async fn eth_satellite() {
         reth_tracing::init_test_tracing();
@@ -804,4 +1057,111 @@ mod tests {
             }
         }
     }
+
+    /// The remote installs the test protocol but never sends anything on it.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn satellite_first_message_timeout() {
+        reth_tracing::init_test_tracing();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let (status, fork_filter) = eth_handshake();
+        let other_status = status;
+        let other_fork_filter = fork_filter.clone();
+        let _handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+            let (server_hello, _) = test_hello();
+            let (conn, _) = UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+
+            let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+                .into_eth_satellite_stream(other_status, other_fork_filter)
+                .await
+                .unwrap();
+
+            loop {
+                let _ = st.next().await;
+            }
+        });
+
+        let conn = connect_passthrough(local_addr, test_hello().0).await;
+        let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+            .into_eth_satellite_stream_with_timeout(status, fork_filter, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        st.install_protocol(&TestProtoMessage::capability(), |_conn| {
+            futures::stream::pending::<BytesMut>()
+        })
+        .unwrap();
+        st.set_first_message_timeout(Duration::from_millis(100));
+
+        let err = tokio::time::timeout(Duration::from_secs(5), st.next())
+            .await
+            .expect("stream should fail before the test timeout")
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EthStreamError::P2PStreamError(P2PStreamError::HandshakeError(
+                P2PHandshakeError::Timeout
+            ))
+        ));
+    }
+
+    /// The remote sends a test protocol message that exceeds the configured limit.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn satellite_max_message_size() {
+        reth_tracing::init_test_tracing();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let (status, fork_filter) = eth_handshake();
+        let other_status = status;
+        let other_fork_filter = fork_filter.clone();
+        let _handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+            let (server_hello, _) = test_hello();
+            let (conn, _) = UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+
+            let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+                .into_eth_satellite_stream(other_status, other_fork_filter)
+                .await
+                .unwrap();
+
+            st.install_protocol(&TestProtoMessage::capability(), |_conn| {
+                async_stream::stream! {
+                    yield TestProtoMessage::message("a".repeat(1024)).encoded();
+                    futures::future::pending::<()>().await;
+                    unreachable!()
+                }
+            })
+            .unwrap();
+
+            loop {
+                let _ = st.next().await;
+            }
+        });
+
+        let conn = connect_passthrough(local_addr, test_hello().0).await;
+        let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
+            .into_eth_satellite_stream(status, fork_filter)
+            .await
+            .unwrap();
+
+        st.install_protocol(&TestProtoMessage::capability(), |_conn| {
+            futures::stream::pending::<BytesMut>()
+        })
+        .unwrap();
+        st.set_max_message_size(&TestProtoMessage::capability().name, 512);
+
+        let err = tokio::time::timeout(Duration::from_secs(5), st.next())
+            .await
+            .expect("stream should fail before the test timeout")
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EthStreamError::P2PStreamError(P2PStreamError::MessageTooBig { max_size: 512, .. })
+        ));
+    }
 }
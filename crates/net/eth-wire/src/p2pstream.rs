@@ -1,4 +1,5 @@
 use crate::{
+    bandwidth::CapabilityBandwidthMeter,
     capability::SharedCapabilities,
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
@@ -40,7 +41,7 @@ const MAX_P2P_MESSAGE_ID: u8 = P2PMessageID::Pong as u8;
 
 /// [`HANDSHAKE_TIMEOUT`] determines the amount of time to wait before determining that a `p2p`
 /// handshake has timed out.
-pub(crate) const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// [`PING_TIMEOUT`] determines the amount of time to wait before determining that a `p2p` ping has
 /// timed out.
@@ -86,15 +87,25 @@ where
     /// Consumes the `UnauthedP2PStream` and returns a `P2PStream` after the `Hello` handshake is
     /// completed successfully. This also returns the `Hello` message sent by the remote peer.
     pub async fn handshake(
+        self,
+        hello: HelloMessageWithProtocols,
+    ) -> Result<(P2PStream<S>, HelloMessage), P2PStreamError> {
+        self.handshake_with_timeout(hello, HANDSHAKE_TIMEOUT).await
+    }
+
+    /// Same as [`UnauthedP2PStream::handshake`], but waits at most `timeout_limit` for the remote's
+    /// `Hello` message.
+    pub async fn handshake_with_timeout(
         mut self,
         hello: HelloMessageWithProtocols,
+        timeout_limit: Duration,
     ) -> Result<(P2PStream<S>, HelloMessage), P2PStreamError> {
         trace!(?hello, "sending p2p hello to peer");
 
         // send our hello message with the Sink
         self.inner.send(alloy_rlp::encode(P2PMessage::Hello(hello.message())).into()).await?;
 
-        let first_message_bytes = tokio::time::timeout(HANDSHAKE_TIMEOUT, self.inner.next())
+        let first_message_bytes = tokio::time::timeout(timeout_limit, self.inner.next())
             .await
             .or(Err(P2PStreamError::HandshakeError(P2PHandshakeError::Timeout)))?
             .ok_or(P2PStreamError::HandshakeError(P2PHandshakeError::NoResponse))??;
@@ -250,6 +261,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Tracks the bytes exchanged per shared capability.
+    bandwidth: CapabilityBandwidthMeter,
 }
 
 impl<S> P2PStream<S> {
@@ -262,6 +276,7 @@ impl<S> P2PStream<S> {
             encoder: snap::raw::Encoder::new(),
             decoder: snap::raw::Decoder::new(),
             pinger: Pinger::new(PING_INTERVAL, PING_TIMEOUT),
+            bandwidth: CapabilityBandwidthMeter::new(&shared_capabilities),
             shared_capabilities,
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
@@ -291,6 +306,11 @@ impl<S> P2PStream<S> {
         &self.shared_capabilities
     }
 
+    /// Returns the bandwidth meter that tracks the bytes exchanged per shared capability.
+    pub const fn bandwidth(&self) -> &CapabilityBandwidthMeter {
+        &self.bandwidth
+    }
+
     /// Returns `true` if the stream has outgoing capacity.
     fn has_outgoing_capacity(&self) -> bool {
         self.outgoing_messages.len() < self.outgoing_message_buffer_capacity
@@ -516,6 +536,10 @@ where
                     //
                     decompress_buf[0] = bytes[0] - MAX_RESERVED_MESSAGE_ID - 1;
 
+                    if let Some(idx) = this.shared_capabilities.position_by_offset(id) {
+                        this.bandwidth.record_ingress(idx, bytes.len());
+                    }
+
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
             }
@@ -606,6 +630,9 @@ where
         // all messages sent in this stream are subprotocol messages, so we need to switch the
         // message id based on the offset
         compressed[0] = item[0] + MAX_RESERVED_MESSAGE_ID + 1;
+        if let Some(idx) = this.shared_capabilities.position_by_offset(compressed[0]) {
+            this.bandwidth.record_egress(idx, compressed.len());
+        }
         this.outgoing_messages.push_back(compressed.freeze());
 
         Ok(())
@@ -826,7 +853,12 @@ impl Decodable for ProtocolVersion {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{capability::SharedCapability, test_utils::eth_hello, EthVersion};
+    use crate::{
+        bandwidth::CapabilityBandwidth,
+        capability::{Capability, SharedCapability},
+        test_utils::eth_hello,
+        EthVersion,
+    };
     use tokio::net::{TcpListener, TcpStream};
     use tokio_util::codec::Decoder;
 
@@ -913,6 +945,65 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_capability_bandwidth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let messages = vec![
+            Bytes::from_static(&[0x03, 0xc0]),
+            Bytes::from(vec![0x05; 100]),
+            Bytes::from([&[0x01][..], &[0xab; 1000][..]].concat()),
+        ];
+        // the wire size of each message is its id plus the snappy compressed payload
+        let expected: u64 = messages
+            .iter()
+            .map(|msg| 1 + snap::raw::Encoder::new().compress_vec(&msg[1..]).unwrap().len() as u64)
+            .sum();
+        let count = messages.len();
+
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = crate::PassthroughCodec::default().framed(incoming);
+            let (server_hello, _) = eth_hello();
+            let (mut p2p_stream, _) =
+                UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
+
+            for _ in 0..count {
+                p2p_stream.next().await.unwrap().unwrap();
+            }
+
+            let bandwidth = p2p_stream.bandwidth();
+            assert_eq!(
+                bandwidth.snapshot(),
+                vec![CapabilityBandwidth {
+                    capability: Capability::eth_67(),
+                    ingress: expected,
+                    egress: 0
+                }]
+            );
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = crate::PassthroughCodec::default().framed(outgoing);
+        let (client_hello, _) = eth_hello();
+        let (mut p2p_stream, _) =
+            UnauthedP2PStream::new(sink).handshake(client_hello).await.unwrap();
+
+        // the handshake isn't counted
+        assert_eq!(p2p_stream.bandwidth().total_egress(), 0);
+
+        for msg in messages {
+            p2p_stream.send(msg).await.unwrap();
+        }
+
+        assert_eq!(p2p_stream.bandwidth().total_egress(), expected);
+        assert_eq!(p2p_stream.bandwidth().total_ingress(), 0);
+        assert_eq!(p2p_stream.bandwidth().get(&Capability::eth_68()), None);
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_handshake_passthrough() {
         // create a p2p stream and server, then confirm that the two are authed
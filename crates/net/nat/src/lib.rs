@@ -19,7 +19,7 @@ use std::{
     pin::Pin,
     str::FromStr,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 #[cfg(feature = "serde")]
@@ -100,12 +100,44 @@ impl FromStr for NatResolver {
     }
 }
 
+/// The outcome of the external address resolution tracked by [`ResolveNatInterval`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NatStatus {
+    /// The configured resolver.
+    pub resolver: NatResolver,
+    /// The most recently resolved external address, if any.
+    pub external_addr: Option<IpAddr>,
+    /// When the last resolution attempt completed.
+    pub last_refresh: Option<SystemTime>,
+    /// Number of consecutive failed resolution attempts.
+    pub consecutive_failures: u64,
+}
+
+impl NatStatus {
+    /// Creates a new [`NatStatus`] for the given resolver, before any resolution attempt.
+    pub const fn new(resolver: NatResolver) -> Self {
+        Self { resolver, external_addr: None, last_refresh: None, consecutive_failures: 0 }
+    }
+
+    /// Records the outcome of a resolution attempt.
+    pub fn on_refresh(&mut self, addr: Option<IpAddr>) {
+        self.last_refresh = Some(SystemTime::now());
+        if addr.is_some() {
+            self.external_addr = addr;
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+        }
+    }
+}
+
 /// With this type you can resolve the external public IP address on an interval basis.
 #[must_use = "Does nothing unless polled"]
 pub struct ResolveNatInterval {
     resolver: NatResolver,
     future: Option<Pin<Box<dyn Future<Output = Option<IpAddr>> + Send>>>,
     interval: tokio::time::Interval,
+    status: NatStatus,
 }
 
 impl fmt::Debug for ResolveNatInterval {
@@ -114,13 +146,32 @@ impl fmt::Debug for ResolveNatInterval {
             .field("resolver", &self.resolver)
             .field("future", &self.future.as_ref().map(drop))
             .field("interval", &self.interval)
+            .field("status", &self.status)
             .finish()
     }
 }
 
 impl ResolveNatInterval {
     fn with_interval(resolver: NatResolver, interval: tokio::time::Interval) -> Self {
-        Self { resolver, future: None, interval }
+        Self { resolver, future: None, interval, status: NatStatus::new(resolver) }
+    }
+
+    /// Returns the configured [`NatResolver`].
+    pub const fn resolver(&self) -> NatResolver {
+        self.resolver
+    }
+
+    /// Returns the outcome of the resolution attempts so far.
+    pub const fn status(&self) -> &NatStatus {
+        &self.status
+    }
+
+    /// Triggers a new resolution attempt on the next poll, independent of the interval.
+    ///
+    /// This restarts the interval and discards an attempt that's currently in progress.
+    pub fn refresh(&mut self) {
+        self.future = None;
+        self.interval.reset_immediately();
     }
 
     /// Creates a new [`ResolveNatInterval`] that attempts to resolve the public IP with interval of
@@ -162,7 +213,10 @@ impl ResolveNatInterval {
 
         if let Some(mut fut) = self.future.take() {
             match fut.as_mut().poll(cx) {
-                Poll::Ready(ip) => return Poll::Ready(ip),
+                Poll::Ready(ip) => {
+                    self.status.on_refresh(ip);
+                    return Poll::Ready(ip)
+                }
                 Poll::Pending => self.future = Some(fut),
             }
         }
@@ -226,6 +280,27 @@ mod tests {
         dbg!(ip);
     }
 
+    #[tokio::test]
+    async fn refresh_interval_status() {
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let mut interval =
+            ResolveNatInterval::interval(NatResolver::ExternalIp(ip), Duration::from_secs(60));
+        assert_eq!(interval.status().last_refresh, None);
+
+        assert_eq!(interval.tick().await, Some(ip));
+        assert_eq!(interval.status().external_addr, Some(ip));
+        assert!(interval.status().last_refresh.is_some());
+
+        // resolves again without waiting for the next interval tick
+        interval.refresh();
+        let ip_refreshed = tokio::time::timeout(Duration::from_secs(5), interval.tick()).await;
+        assert_eq!(ip_refreshed, Ok(Some(ip)));
+
+        let mut interval = ResolveNatInterval::interval(NatResolver::None, Duration::from_secs(60));
+        assert_eq!(interval.tick().await, None);
+        assert_eq!(interval.status().consecutive_failures, 1);
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(NatResolver::Any, "any".parse().unwrap());
@@ -16,7 +16,10 @@
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use error::NetworkError;
 pub use reputation::{Reputation, ReputationChangeKind};
-use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use reth_eth_wire::{
+    capability::Capabilities, CapabilityBandwidthMeter, DisconnectReason, EthVersion, Status,
+};
+pub use reth_net_nat::{NatResolver, NatStatus};
 use reth_network_peers::NodeRecord;
 use serde::{Deserialize, Serialize};
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
@@ -48,6 +51,19 @@ pub trait NetworkInfo: Send + Sync {
 
     /// Returns `true` when the node is undergoing the very first Pipeline sync.
     fn is_initially_syncing(&self) -> bool;
+
+    /// Returns the status of the external address resolution.
+    ///
+    /// Returns `None` if external address resolution is disabled, which is the default.
+    fn nat_status(&self) -> Option<NatStatus> {
+        None
+    }
+
+    /// Triggers a new resolution of the external address, without waiting for the next scheduled
+    /// attempt.
+    ///
+    /// Does nothing by default.
+    fn refresh_nat(&self) {}
 }
 
 /// Provides general purpose information about Peers in the network.
@@ -184,6 +200,8 @@ pub struct PeerInfo {
     pub status: Arc<Status>,
     /// The timestamp when the session to that peer has been established.
     pub session_established: Instant,
+    /// Bytes exchanged with the peer, tracked per shared capability.
+    pub bandwidth: CapabilityBandwidthMeter,
 }
 
 /// The direction of the connection.
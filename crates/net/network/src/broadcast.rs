@@ -0,0 +1,252 @@
+//! Configuration of how many peers receive full broadcasts, per `eth` version.
+
+use reth_eth_wire::EthVersion;
+
+/// Determines how many of the connected peers receive a full broadcast.
+///
+/// Peers that are not selected for a full broadcast are only sent an announcement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BroadcastFanout {
+    /// The square root of the number of peers, plus one.
+    ///
+    /// All versions configured with [`BroadcastFanout::Sqrt`] share a single budget, derived from
+    /// their combined number of peers, see [`EthVersionFanout`].
+    #[default]
+    Sqrt,
+    /// A fixed number of peers.
+    ///
+    /// `Fixed(0)` disables full broadcasts, so that peers only receive announcements.
+    Fixed(usize),
+    /// All peers.
+    All,
+}
+
+impl BroadcastFanout {
+    /// Returns the number of peers out of `num_peers` that receive a full broadcast.
+    pub fn num_peers(&self, num_peers: usize) -> usize {
+        match self {
+            Self::Sqrt => (num_peers as f64).sqrt() as usize + 1,
+            Self::Fixed(count) => *count,
+            Self::All => num_peers,
+        }
+        .min(num_peers)
+    }
+}
+
+/// A [`BroadcastFanout`] for each `eth` version.
+///
+/// A [`BroadcastFanout::Fixed`] or [`BroadcastFanout::All`] fan-out of a version is applied to the
+/// peers of that version only, so that, for example, `eth/68` peers which receive size-aware
+/// announcements can be served differently than older peers.
+///
+/// The peers of all versions configured with [`BroadcastFanout::Sqrt`] share a single budget of the
+/// square root of their combined number, plus one. With the default, where all versions use
+/// [`BroadcastFanout::Sqrt`], this is the square root of all peers plus one, regardless of their
+/// versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EthVersionFanout {
+    /// Fan-out for `eth/66` peers.
+    pub eth66: BroadcastFanout,
+    /// Fan-out for `eth/67` peers.
+    pub eth67: BroadcastFanout,
+    /// Fan-out for `eth/68` peers.
+    pub eth68: BroadcastFanout,
+}
+
+impl EthVersionFanout {
+    /// Uses the same fan-out for all versions.
+    pub const fn uniform(fanout: BroadcastFanout) -> Self {
+        Self { eth66: fanout, eth67: fanout, eth68: fanout }
+    }
+
+    /// Sets the fan-out for `eth/66` peers.
+    pub const fn with_eth66(mut self, fanout: BroadcastFanout) -> Self {
+        self.eth66 = fanout;
+        self
+    }
+
+    /// Sets the fan-out for `eth/67` peers.
+    pub const fn with_eth67(mut self, fanout: BroadcastFanout) -> Self {
+        self.eth67 = fanout;
+        self
+    }
+
+    /// Sets the fan-out for `eth/68` peers.
+    pub const fn with_eth68(mut self, fanout: BroadcastFanout) -> Self {
+        self.eth68 = fanout;
+        self
+    }
+
+    /// Returns the fan-out for peers of the given version.
+    pub const fn get(&self, version: EthVersion) -> BroadcastFanout {
+        match version {
+            EthVersion::Eth66 => self.eth66,
+            EthVersion::Eth67 => self.eth67,
+            EthVersion::Eth68 => self.eth68,
+        }
+    }
+
+    /// Returns a tracker of the remaining full broadcasts per version, for the given versions of
+    /// the peers that are broadcast to.
+    pub(crate) fn budget(&self, versions: impl IntoIterator<Item = EthVersion>) -> FanoutBudget {
+        let mut num_peers = [0usize; 3];
+        for version in versions {
+            num_peers[FanoutBudget::index(version)] += 1;
+        }
+        let fanouts = [self.eth66, self.eth67, self.eth68];
+        let shared = fanouts.map(|fanout| fanout == BroadcastFanout::Sqrt);
+
+        let num_shared_peers =
+            num_peers.iter().zip(shared).filter(|(_, shared)| *shared).map(|(num, _)| num).sum();
+        let mut remaining = [0; 3];
+        for (idx, fanout) in fanouts.iter().enumerate() {
+            if !shared[idx] {
+                remaining[idx] = fanout.num_peers(num_peers[idx]);
+            }
+        }
+
+        FanoutBudget {
+            remaining,
+            shared,
+            remaining_shared: BroadcastFanout::Sqrt.num_peers(num_shared_peers),
+        }
+    }
+}
+
+/// Fan-out of transaction and block broadcasts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BroadcastConfig {
+    /// How many peers of each version receive full transactions, the remaining peers receive
+    /// transaction hashes.
+    pub transactions: EthVersionFanout,
+    /// How many peers of each version receive `NewBlock` messages, the remaining peers receive
+    /// `NewBlockHashes` messages.
+    pub blocks: EthVersionFanout,
+}
+
+/// Tracks the number of full broadcasts that are left for each `eth` version.
+#[derive(Debug)]
+pub(crate) struct FanoutBudget {
+    /// Remaining full broadcasts of the versions that don't share the budget.
+    remaining: [usize; 3],
+    /// Whether a version draws from the shared budget.
+    shared: [bool; 3],
+    /// Remaining full broadcasts shared by the [`BroadcastFanout::Sqrt`] versions.
+    remaining_shared: usize,
+}
+
+impl FanoutBudget {
+    const fn index(version: EthVersion) -> usize {
+        match version {
+            EthVersion::Eth66 => 0,
+            EthVersion::Eth67 => 1,
+            EthVersion::Eth68 => 2,
+        }
+    }
+
+    /// Returns `true` if a peer of the given version can receive a full broadcast, and consumes
+    /// it.
+    pub(crate) fn try_consume(&mut self, version: EthVersion) -> bool {
+        let idx = Self::index(version);
+        let remaining =
+            if self.shared[idx] { &mut self.remaining_shared } else { &mut self.remaining[idx] };
+        if *remaining == 0 {
+            return false
+        }
+        *remaining -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fanout_num_peers() {
+        assert_eq!(BroadcastFanout::Sqrt.num_peers(0), 0);
+        assert_eq!(BroadcastFanout::Sqrt.num_peers(1), 1);
+        assert_eq!(BroadcastFanout::Sqrt.num_peers(16), 5);
+        assert_eq!(BroadcastFanout::Fixed(3).num_peers(2), 2);
+        assert_eq!(BroadcastFanout::Fixed(3).num_peers(10), 3);
+        assert_eq!(BroadcastFanout::Fixed(0).num_peers(10), 0);
+        assert_eq!(BroadcastFanout::All.num_peers(10), 10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_serde() {
+        let config: BroadcastConfig = serde_json::from_str(
+            r#"{"transactions":{"eth68":{"Fixed":2}},"blocks":{"eth66":"All"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.transactions,
+            EthVersionFanout::default().with_eth68(BroadcastFanout::Fixed(2))
+        );
+        assert_eq!(config.blocks, EthVersionFanout::default().with_eth66(BroadcastFanout::All));
+    }
+
+    #[test]
+    fn budget_per_version() {
+        let fanout = EthVersionFanout::default()
+            .with_eth66(BroadcastFanout::All)
+            .with_eth68(BroadcastFanout::Fixed(0));
+        let versions = [EthVersion::Eth66, EthVersion::Eth66, EthVersion::Eth67, EthVersion::Eth68];
+        let mut budget = fanout.budget(versions);
+
+        assert!(budget.try_consume(EthVersion::Eth66));
+        assert!(budget.try_consume(EthVersion::Eth66));
+        assert!(!budget.try_consume(EthVersion::Eth66));
+
+        assert!(budget.try_consume(EthVersion::Eth67));
+        assert!(!budget.try_consume(EthVersion::Eth67));
+
+        assert!(!budget.try_consume(EthVersion::Eth68));
+    }
+
+    #[test]
+    fn default_budget_is_global_sqrt() {
+        // 16 peers across all versions get sqrt(16) + 1 = 5 full broadcasts in total, like a
+        // single global fan-out
+        let versions = std::iter::repeat(EthVersion::Eth66)
+            .take(4)
+            .chain(std::iter::repeat(EthVersion::Eth67).take(4))
+            .chain(std::iter::repeat(EthVersion::Eth68).take(8));
+        let mut budget = EthVersionFanout::default().budget(versions);
+
+        for _ in 0..5 {
+            assert!(budget.try_consume(EthVersion::Eth68));
+        }
+        assert!(!budget.try_consume(EthVersion::Eth66));
+        assert!(!budget.try_consume(EthVersion::Eth67));
+        assert!(!budget.try_consume(EthVersion::Eth68));
+    }
+
+    #[test]
+    fn sqrt_budget_shared_by_sqrt_versions_only() {
+        // the 9 `eth/67` and `eth/68` peers share sqrt(9) + 1 = 4 full broadcasts, `eth/66` peers
+        // are served on their own
+        let fanout = EthVersionFanout::default().with_eth66(BroadcastFanout::Fixed(1));
+        let versions = std::iter::repeat(EthVersion::Eth66)
+            .take(16)
+            .chain(std::iter::repeat(EthVersion::Eth67).take(4))
+            .chain(std::iter::repeat(EthVersion::Eth68).take(5));
+        let mut budget = fanout.budget(versions);
+
+        assert!(budget.try_consume(EthVersion::Eth66));
+        assert!(!budget.try_consume(EthVersion::Eth66));
+
+        for _ in 0..2 {
+            assert!(budget.try_consume(EthVersion::Eth67));
+            assert!(budget.try_consume(EthVersion::Eth68));
+        }
+        assert!(!budget.try_consume(EthVersion::Eth67));
+        assert!(!budget.try_consume(EthVersion::Eth68));
+    }
+}
@@ -1,6 +1,8 @@
 //! Network config support
 
 use crate::{
+    broadcast::EthVersionFanout,
+    discovery::enr_capabilities,
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
     peers::PeersConfig,
@@ -83,6 +85,8 @@ pub struct NetworkConfig<C> {
     pub tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// How many peers of each `eth` version receive `NewBlock` messages.
+    pub block_broadcast_fanout: EthVersionFanout,
 }
 
 // === impl NetworkConfig ===
@@ -174,6 +178,8 @@ pub struct NetworkConfigBuilder {
     block_import: Option<Box<dyn BlockImport>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// How many peers of each `eth` version receive `NewBlock` messages.
+    block_broadcast_fanout: EthVersionFanout,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -200,6 +206,7 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            block_broadcast_fanout: Default::default(),
         }
     }
 
@@ -268,7 +275,7 @@ impl NetworkConfigBuilder {
     }
 
     /// Sets a custom config for how sessions are handled.
-    pub const fn sessions_config(mut self, config: SessionsConfig) -> Self {
+    pub fn sessions_config(mut self, config: SessionsConfig) -> Self {
         self.sessions_config = Some(config);
         self
     }
@@ -447,12 +454,28 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the maximum size of inbound messages of the additional `RLPx` subprotocol with the
+    /// given capability name.
+    ///
+    /// See also [`SessionsConfig::with_max_message_size`].
+    pub fn max_message_size(mut self, name: impl Into<String>, max_size: usize) -> Self {
+        let config = self.sessions_config.take().unwrap_or_default();
+        self.sessions_config = Some(config.with_max_message_size(name, max_size));
+        self
+    }
+
     /// Sets whether tx gossip is disabled.
     pub const fn disable_tx_gossip(mut self, disable_tx_gossip: bool) -> Self {
         self.tx_gossip_disabled = disable_tx_gossip;
         self
     }
 
+    /// Sets how many peers of each `eth` version receive `NewBlock` messages.
+    pub const fn block_broadcast_fanout(mut self, fanout: EthVersionFanout) -> Self {
+        self.block_broadcast_fanout = fanout;
+        self
+    }
+
     /// Sets the block import type.
     pub fn block_import(mut self, block_import: Box<dyn BlockImport>) -> Self {
         self.block_import = Some(block_import);
@@ -495,6 +518,7 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            block_broadcast_fanout,
         } = self;
 
         let listener_addr = listener_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS);
@@ -534,7 +558,8 @@ impl NetworkConfigBuilder {
             boot_nodes,
             dns_discovery_config,
             discovery_v4_config: discovery_v4_builder.map(|builder| builder.build()),
-            discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
+            discovery_v5_config: discovery_v5_builder
+                .map(|builder| builder.capabilities(&enr_capabilities(&hello_message)).build()),
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
             peers_config: peers_config.unwrap_or_default(),
@@ -549,6 +574,7 @@ impl NetworkConfigBuilder {
             fork_filter,
             tx_gossip_disabled,
             transactions_manager_config,
+            block_broadcast_fanout,
         }
     }
 }
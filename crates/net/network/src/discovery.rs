@@ -12,7 +12,8 @@ use reth_discv5::{DiscoveredPeer, Discv5};
 use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_eth_wire::{Capability, HelloMessageWithProtocols};
+use reth_network_peers::{EnrCapabilities, NodeRecord, PeerId};
 use reth_primitives::{EnrForkIdEntry, ForkId};
 use secp256k1::SecretKey;
 use std::{
@@ -216,6 +217,15 @@ impl Discovery {
             })
     }
 
+    /// Processes the `RLPx` capabilities a node advertises in its ENR
+    fn on_enr_capabilities(&mut self, peer_id: PeerId, capabilities: EnrCapabilities) {
+        if capabilities.is_empty() {
+            return
+        }
+        let capabilities = capabilities.0.into_iter().map(Into::into).collect();
+        self.queued_events.push_back(DiscoveryEvent::EnrCapabilities(peer_id, capabilities))
+    }
+
     fn on_discv4_update(&mut self, update: DiscoveryUpdate) {
         match update {
             DiscoveryUpdate::Added(record) | DiscoveryUpdate::DiscoveredAtCapacity(record) => {
@@ -224,6 +234,9 @@ impl Discovery {
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
             }
+            DiscoveryUpdate::EnrCapabilities(node, capabilities) => {
+                self.on_enr_capabilities(node.id, capabilities)
+            }
             DiscoveryUpdate::Removed(node) => {
                 self.discovered_nodes.remove(&node);
             }
@@ -255,10 +268,13 @@ impl Discovery {
                 self.discv5_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
                 if let Some(discv5) = self.discv5.as_mut() {
-                    if let Some(DiscoveredPeer { node_record, fork_id }) =
+                    if let Some(DiscoveredPeer { node_record, fork_id, capabilities }) =
                         discv5.on_discv5_update(update)
                     {
                         self.on_node_record_update(node_record, fork_id);
+                        if let Some(capabilities) = capabilities {
+                            self.on_enr_capabilities(node_record.id, capabilities);
+                        }
                     }
                 }
             }
@@ -268,6 +284,7 @@ impl Discovery {
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
                 self.add_discv4_node(update.node_record);
+                let capabilities = EnrCapabilities::from_enr(&update.enr);
                 if let Err(err) = self.add_discv5_node(update.enr) {
                     trace!(target: "net::discovery",
                         %err,
@@ -275,6 +292,9 @@ impl Discovery {
                     );
                 }
                 self.on_node_record_update(update.node_record, update.fork_id);
+                if let Some(capabilities) = capabilities {
+                    self.on_enr_capabilities(update.node_record.id, capabilities);
+                }
             }
 
             if self.queued_events.is_empty() {
@@ -323,6 +343,11 @@ impl Discovery {
     }
 }
 
+/// Returns the capabilities of the local hello message that are advertised in the ENR.
+pub(crate) fn enr_capabilities(hello: &HelloMessageWithProtocols) -> EnrCapabilities {
+    EnrCapabilities(hello.protocols.iter().map(|protocol| (&protocol.cap).into()).collect())
+}
+
 /// Events produced by the [`Discovery`] manager.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DiscoveryEvent {
@@ -330,6 +355,8 @@ pub enum DiscoveryEvent {
     NewNode(DiscoveredEvent),
     /// Retrieved a [`ForkId`] from the peer via ENR request, See <https://eips.ethereum.org/EIPS/eip-868>
     EnrForkId(PeerId, ForkId),
+    /// Retrieved the `RLPx` capabilities the peer advertises in its ENR.
+    EnrCapabilities(PeerId, Vec<Capability>),
 }
 
 #[cfg(test)]
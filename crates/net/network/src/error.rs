@@ -235,14 +235,14 @@ impl SessionError for PendingSessionHandshakeError {
         match self {
             Self::Eth(eth) => eth.merits_discovery_ban(),
             Self::Ecies(_) => true,
-            Self::Timeout => false,
+            Self::Timeout | Self::MissingCapability(_) => false,
         }
     }
 
     fn is_fatal_protocol_error(&self) -> bool {
         match self {
             Self::Eth(eth) => eth.is_fatal_protocol_error(),
-            Self::Ecies(_) => true,
+            Self::Ecies(_) | Self::MissingCapability(_) => true,
             Self::Timeout => false,
         }
     }
@@ -252,6 +252,7 @@ impl SessionError for PendingSessionHandshakeError {
             Self::Eth(eth) => eth.should_backoff(),
             Self::Ecies(_) => Some(BackoffKind::Low),
             Self::Timeout => Some(BackoffKind::Medium),
+            Self::MissingCapability(_) => Some(BackoffKind::High),
         }
     }
 }
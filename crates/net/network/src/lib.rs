@@ -114,6 +114,7 @@
 /// Common helpers for network testing.
 pub mod test_utils;
 
+pub mod broadcast;
 mod budget;
 mod builder;
 pub mod cache;
@@ -124,6 +125,7 @@ pub mod eth_requests;
 mod fetch;
 mod flattened_response;
 pub mod import;
+pub mod light;
 mod listener;
 mod manager;
 pub mod message;
@@ -135,6 +137,7 @@ mod session;
 mod state;
 mod swarm;
 pub mod transactions;
+pub mod witness;
 
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
@@ -143,12 +146,12 @@ pub use fetch::FetchClient;
 pub use manager::{NetworkEvent, NetworkManager};
 pub use message::PeerRequest;
 pub use network::{NetworkEvents, NetworkHandle, NetworkProtocols};
-pub use peers::PeersConfig;
+pub use peers::{PeersConfig, PersistedPeer};
 pub use session::{
-    ActiveSessionHandle, ActiveSessionMessage, Direction, PeerInfo, PendingSessionEvent,
-    PendingSessionHandle, PendingSessionHandshakeError, SessionCommand, SessionEvent, SessionId,
-    SessionLimits, SessionManager, SessionsConfig,
+    ActiveSessionHandle, ActiveSessionMessage, Direction, HandshakeTimeouts, PeerInfo,
+    PendingSessionEvent, PendingSessionHandle, PendingSessionHandshakeError, SessionCommand,
+    SessionEvent, SessionId, SessionLimits, SessionManager, SessionsConfig,
 };
 pub use transactions::{FilterAnnouncement, MessageFilter, ValidateTx68};
 
-pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
+pub use reth_eth_wire::{Capability, DisconnectReason, HelloMessageWithProtocols};
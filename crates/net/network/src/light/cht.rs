@@ -0,0 +1,176 @@
+//! A CHT-like index of canonical block hashes.
+//!
+//! The canonical chain is split into sections of [`CHT_SECTION_SIZE`] blocks. Each section is
+//! committed to by the root of a binary merkle tree, whose leaves are the `(number, hash)` pairs
+//! of the section's blocks. A light client that knows the root of a section can verify any header
+//! of the section with a branch of [`CHT_DEPTH`] sibling hashes.
+
+use reth_primitives::{keccak256, BlockNumber, B256};
+
+/// The number of blocks in a section of the index.
+///
+/// This must be a power of two.
+pub const CHT_SECTION_SIZE: u64 = 4096;
+
+/// The number of blocks that must be on top of a section before it is indexed.
+///
+/// This ensures that sections are only indexed once they are unlikely to be reorged.
+pub const CHT_CONFIRMATIONS: u64 = 128;
+
+/// The depth of a section's merkle tree, this is the length of all branches.
+pub const CHT_DEPTH: usize = CHT_SECTION_SIZE.trailing_zeros() as usize;
+
+/// Returns the section that contains the given block.
+pub const fn cht_section(number: BlockNumber) -> u64 {
+    number / CHT_SECTION_SIZE
+}
+
+/// Returns `true` if the given section is indexed if the chain's tip is at the given block.
+pub const fn is_cht_section_available(section: u64, best_number: BlockNumber) -> bool {
+    // the section is available if its last block has enough confirmations
+    let Some(end) = section.checked_add(1) else { return false };
+    let Some(end) = end.checked_mul(CHT_SECTION_SIZE) else { return false };
+    let Some(confirmed) = end.checked_add(CHT_CONFIRMATIONS - 1) else { return false };
+    confirmed <= best_number
+}
+
+/// Returns the leaf of the given block.
+pub fn cht_leaf(number: BlockNumber, hash: B256) -> B256 {
+    let mut buf = [0u8; 40];
+    buf[..8].copy_from_slice(&number.to_be_bytes());
+    buf[8..].copy_from_slice(hash.as_slice());
+    keccak256(buf)
+}
+
+fn cht_node(left: &B256, right: &B256) -> B256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left.as_slice());
+    buf[32..].copy_from_slice(right.as_slice());
+    keccak256(buf)
+}
+
+/// Verifies that the given block is part of the section with the given root.
+pub fn verify_cht_branch(number: BlockNumber, hash: B256, branch: &[B256], root: B256) -> bool {
+    if branch.len() != CHT_DEPTH {
+        return false
+    }
+
+    let mut index = number % CHT_SECTION_SIZE;
+    let mut node = cht_leaf(number, hash);
+    for sibling in branch {
+        node = if index & 1 == 0 { cht_node(&node, sibling) } else { cht_node(sibling, &node) };
+        index >>= 1;
+    }
+    node == root
+}
+
+/// The merkle tree of a complete section.
+#[derive(Debug, Clone)]
+pub(crate) struct ChtTree {
+    section: u64,
+    /// All levels of the tree, starting with the leaves and ending with the root.
+    levels: Vec<Vec<B256>>,
+}
+
+impl ChtTree {
+    /// Builds the tree of the given section from the canonical hashes of all its blocks.
+    ///
+    /// Returns `None` if the number of hashes doesn't match [`CHT_SECTION_SIZE`].
+    pub(crate) fn new(section: u64, hashes: &[B256]) -> Option<Self> {
+        if hashes.len() as u64 != CHT_SECTION_SIZE {
+            return None
+        }
+
+        let first = section * CHT_SECTION_SIZE;
+        let leaves = hashes
+            .iter()
+            .enumerate()
+            .map(|(idx, hash)| cht_leaf(first + idx as u64, *hash))
+            .collect::<Vec<_>>();
+
+        let mut levels = Vec::with_capacity(CHT_DEPTH + 1);
+        levels.push(leaves);
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let level = levels.last().expect("not empty");
+            let next = level.chunks_exact(2).map(|pair| cht_node(&pair[0], &pair[1])).collect();
+            levels.push(next);
+        }
+
+        Some(Self { section, levels })
+    }
+
+    /// Returns the section of the tree.
+    pub(crate) const fn section(&self) -> u64 {
+        self.section
+    }
+
+    /// Returns the root of the tree.
+    pub(crate) fn root(&self) -> B256 {
+        self.levels.last().expect("not empty")[0]
+    }
+
+    /// Returns the branch of the given block, if it is part of the section.
+    pub(crate) fn branch(&self, number: BlockNumber) -> Option<Vec<B256>> {
+        if cht_section(number) != self.section {
+            return None
+        }
+
+        let mut index = (number % CHT_SECTION_SIZE) as usize;
+        let mut branch = Vec::with_capacity(CHT_DEPTH);
+        for level in &self.levels[..CHT_DEPTH] {
+            branch.push(level[index ^ 1]);
+            index >>= 1;
+        }
+        Some(branch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(section: u64) -> Vec<B256> {
+        (0..CHT_SECTION_SIZE)
+            .map(|n| keccak256((section * CHT_SECTION_SIZE + n).to_be_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn verify_branches() {
+        let section = 2;
+        let hashes = hashes(section);
+        let tree = ChtTree::new(section, &hashes).unwrap();
+        let root = tree.root();
+
+        for offset in [0, 1, 2, 1337, CHT_SECTION_SIZE - 1] {
+            let number = section * CHT_SECTION_SIZE + offset;
+            let hash = hashes[offset as usize];
+            let branch = tree.branch(number).unwrap();
+            assert_eq!(branch.len(), CHT_DEPTH);
+            assert!(verify_cht_branch(number, hash, &branch, root));
+
+            // wrong hash, number or root
+            assert!(!verify_cht_branch(number, B256::random(), &branch, root));
+            assert!(!verify_cht_branch(number + 1, hash, &branch, root));
+            assert!(!verify_cht_branch(number, hash, &branch, B256::random()));
+        }
+
+        assert!(tree.branch(CHT_SECTION_SIZE - 1).is_none());
+    }
+
+    #[test]
+    fn incomplete_section() {
+        let mut hashes = hashes(0);
+        hashes.pop();
+        assert!(ChtTree::new(0, &hashes).is_none());
+    }
+
+    #[test]
+    fn section_availability() {
+        assert!(!is_cht_section_available(0, 0));
+        assert!(!is_cht_section_available(0, CHT_SECTION_SIZE + CHT_CONFIRMATIONS - 2));
+        assert!(is_cht_section_available(0, CHT_SECTION_SIZE + CHT_CONFIRMATIONS - 1));
+        assert!(!is_cht_section_available(1, CHT_SECTION_SIZE + CHT_CONFIRMATIONS - 1));
+        assert!(!is_cht_section_available(u64::MAX, u64::MAX));
+    }
+}
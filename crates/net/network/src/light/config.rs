@@ -0,0 +1,136 @@
+//! Configuration of the `light` subprotocol and the budget that limits serving bandwidth.
+
+use parking_lot::Mutex;
+use std::{sync::Arc, time::Instant};
+
+/// Default number of bytes per second that are served to light clients.
+pub const DEFAULT_LIGHT_SERVING_BYTES_PER_SECOND: u64 = 512 * 1024;
+
+/// Default maximum number of items that are served for a single request.
+pub const DEFAULT_MAX_LIGHT_REQUEST_ITEMS: usize = 64;
+
+/// Configuration for serving light clients via the `light` subprotocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LightServingConfig {
+    /// Whether light clients are served.
+    ///
+    /// This is disabled by default.
+    pub enabled: bool,
+    /// The number of bytes per second that are served to all light clients combined.
+    ///
+    /// Responses are truncated once the budget is exhausted.
+    pub max_bytes_per_second: u64,
+    /// The maximum number of items that are served for a single request.
+    pub max_request_items: usize,
+}
+
+impl Default for LightServingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes_per_second: DEFAULT_LIGHT_SERVING_BYTES_PER_SECOND,
+            max_request_items: DEFAULT_MAX_LIGHT_REQUEST_ITEMS,
+        }
+    }
+}
+
+impl LightServingConfig {
+    /// Sets whether light clients are served.
+    pub const fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Sets the number of bytes per second that are served to all light clients combined.
+    pub const fn with_max_bytes_per_second(mut self, max_bytes_per_second: u64) -> Self {
+        self.max_bytes_per_second = max_bytes_per_second;
+        self
+    }
+
+    /// Sets the maximum number of items that are served for a single request.
+    pub const fn with_max_request_items(mut self, max_request_items: usize) -> Self {
+        self.max_request_items = max_request_items;
+        self
+    }
+}
+
+/// A token bucket that limits the number of bytes served to light clients.
+///
+/// The bucket holds at most one second worth of bytes and is shared by all connections.
+#[derive(Debug, Clone)]
+pub(crate) struct ServingBudget {
+    bytes_per_second: u64,
+    state: Arc<Mutex<BudgetState>>,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    available: u64,
+    last_refill: Instant,
+}
+
+impl ServingBudget {
+    /// Creates a new, full budget.
+    pub(crate) fn new(bytes_per_second: u64) -> Self {
+        let state = BudgetState { available: bytes_per_second, last_refill: Instant::now() };
+        Self { bytes_per_second, state: Arc::new(Mutex::new(state)) }
+    }
+
+    /// Consumes the given number of bytes if they are available.
+    ///
+    /// Returns `false` if the budget is exhausted.
+    pub(crate) fn try_consume(&self, bytes: u64) -> bool {
+        let mut state = self.state.lock();
+
+        let now = Instant::now();
+        let refill = (now - state.last_refill).as_secs_f64() * self.bytes_per_second as f64;
+        if refill >= 1.0 {
+            state.available = (state.available + refill as u64).min(self.bytes_per_second);
+            state.last_refill = now;
+        }
+
+        if state.available < bytes {
+            return false
+        }
+        state.available -= bytes;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_exhausted() {
+        let budget = ServingBudget::new(100);
+        assert!(budget.try_consume(60));
+        assert!(!budget.try_consume(60));
+        assert!(budget.try_consume(40));
+        assert!(!budget.try_consume(1_000));
+
+        let empty = ServingBudget::new(0);
+        assert!(!empty.try_consume(1));
+    }
+
+    #[test]
+    fn budget_refills() {
+        let budget = ServingBudget::new(1_000_000);
+        assert!(budget.try_consume(1_000_000));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(budget.try_consume(1_000));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn config_serde() {
+        let config: LightServingConfig =
+            serde_json::from_str(r#"{"enabled":true,"max_bytes_per_second":1024}"#).unwrap();
+        assert_eq!(
+            config,
+            LightServingConfig::default().with_enabled(true).with_max_bytes_per_second(1024)
+        );
+    }
+}
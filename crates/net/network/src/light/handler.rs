@@ -0,0 +1,508 @@
+//! Protocol and connection handlers of the `light` subprotocol.
+
+use crate::{
+    cache::LruMap,
+    light::{
+        cht::{cht_section, is_cht_section_available, ChtTree, CHT_SECTION_SIZE},
+        config::{LightServingConfig, ServingBudget},
+        message::{
+            ChtRoots, GetChtRoots, GetHeaderProofs, HeaderProof, HeaderProofs, LightMessage,
+        },
+    },
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+};
+use alloy_rlp::Encodable;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::SharedCapabilities, message::RequestPair, multiplex::ProtocolConnection,
+    protocol::Protocol,
+};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::error::{RequestError, RequestResult};
+use reth_primitives::{BlockNumber, BytesMut, B256};
+use reth_provider::{BlockNumReader, HeaderProvider, ProviderResult};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace};
+
+/// The number of section roots that are cached.
+const CHT_ROOTS_CACHE_SIZE: u32 = 1024;
+
+/// The number of section trees that are cached.
+///
+/// A tree takes up roughly 256 KiB, requests for proofs of recent headers typically hit the same
+/// few sections.
+const CHT_TREES_CACHE_SIZE: u32 = 8;
+
+/// The maximum number of requests of a single peer that are served concurrently.
+///
+/// Further messages of the peer are only read once one of these requests was answered.
+const MAX_CONCURRENT_LIGHT_REQUESTS: usize = 4;
+
+/// Events emitted by the [`LightProtocolHandler`].
+#[derive(Debug)]
+pub enum LightProtocolEvent {
+    /// A `light` connection with a peer was established.
+    Established {
+        /// The direction of the connection.
+        direction: Direction,
+        /// The peer the connection was established with.
+        peer_id: PeerId,
+        /// Sends commands to the connection.
+        to_connection: mpsc::UnboundedSender<LightCommand>,
+    },
+}
+
+/// Commands that can be sent to a [`LightConnection`].
+#[derive(Debug)]
+pub enum LightCommand {
+    /// Requests the roots of the given sections of the index from the peer.
+    GetChtRoots {
+        /// The requested sections.
+        sections: Vec<u64>,
+        /// The response channel.
+        response: oneshot::Sender<RequestResult<Vec<B256>>>,
+    },
+    /// Requests headers with their inclusion proofs from the peer.
+    GetHeaderProofs {
+        /// The numbers of the requested headers.
+        numbers: Vec<BlockNumber>,
+        /// The response channel.
+        response: oneshot::Sender<RequestResult<Vec<HeaderProof>>>,
+    },
+}
+
+/// Serves light client requests from the provider, shared by all connections.
+struct LightServer<P> {
+    provider: P,
+    config: LightServingConfig,
+    budget: ServingBudget,
+    roots: Mutex<LruMap<u64, B256>>,
+    trees: Mutex<LruMap<u64, Arc<ChtTree>>>,
+}
+
+impl<P> LightServer<P>
+where
+    P: HeaderProvider + BlockNumReader,
+{
+    /// Returns the tree of the given section, if it is indexed.
+    ///
+    /// Building a tree reads the canonical hashes of the whole section, so built trees are cached.
+    fn cht_tree(
+        &self,
+        section: u64,
+        best_number: BlockNumber,
+    ) -> ProviderResult<Option<Arc<ChtTree>>> {
+        if !is_cht_section_available(section, best_number) {
+            return Ok(None)
+        }
+        if let Some(tree) = self.trees.lock().get(&section) {
+            return Ok(Some(tree.clone()))
+        }
+
+        let start = section * CHT_SECTION_SIZE;
+        let hashes = self.provider.canonical_hashes_range(start, start + CHT_SECTION_SIZE)?;
+        let Some(tree) = ChtTree::new(section, &hashes).map(Arc::new) else { return Ok(None) };
+        self.roots.lock().insert(section, tree.root());
+        self.trees.lock().insert(section, tree.clone());
+        Ok(Some(tree))
+    }
+
+    /// Returns the root of the given section, if it is indexed.
+    fn cht_root(&self, section: u64, best_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        if !is_cht_section_available(section, best_number) {
+            return Ok(None)
+        }
+        if let Some(root) = self.roots.lock().get(&section) {
+            return Ok(Some(*root))
+        }
+        Ok(self.cht_tree(section, best_number)?.map(|tree| tree.root()))
+    }
+
+    fn get_cht_roots(&self, sections: Vec<u64>) -> ProviderResult<Vec<B256>> {
+        let best_number = self.provider.best_block_number()?;
+
+        let mut roots = Vec::new();
+        for section in sections.into_iter().take(self.config.max_request_items) {
+            let Some(root) = self.cht_root(section, best_number)? else { break };
+            if !self.budget.try_consume(root.length() as u64) {
+                break
+            }
+            roots.push(root);
+        }
+        Ok(roots)
+    }
+
+    fn get_header_proofs(&self, numbers: Vec<BlockNumber>) -> ProviderResult<Vec<HeaderProof>> {
+        let best_number = self.provider.best_block_number()?;
+
+        let mut proofs = Vec::new();
+        let mut tree: Option<Arc<ChtTree>> = None;
+        for number in numbers.into_iter().take(self.config.max_request_items) {
+            let section = cht_section(number);
+            if tree.as_ref().map(|tree| tree.section()) != Some(section) {
+                tree = self.cht_tree(section, best_number)?;
+            }
+            let Some(branch) = tree.as_ref().and_then(|tree| tree.branch(number)) else { break };
+            let Some(header) = self.provider.header_by_number(number)? else { break };
+
+            let proof = HeaderProof { header, branch };
+            if !self.budget.try_consume(proof.length() as u64) {
+                break
+            }
+            proofs.push(proof);
+        }
+        Ok(proofs)
+    }
+
+    fn on_get_cht_roots(&self, peer_id: PeerId, request: RequestPair<GetChtRoots>) -> BytesMut {
+        let RequestPair { request_id, message: GetChtRoots(sections) } = request;
+        let roots = if self.config.enabled {
+            self.get_cht_roots(sections).unwrap_or_else(|err| {
+                debug!(target: "net::light", %err, %peer_id, "failed to read cht roots");
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        trace!(target: "net::light", roots=roots.len(), %peer_id, "serving cht roots");
+        LightMessage::ChtRoots(RequestPair { request_id, message: ChtRoots(roots) }).encoded()
+    }
+
+    fn on_get_header_proofs(
+        &self,
+        peer_id: PeerId,
+        request: RequestPair<GetHeaderProofs>,
+    ) -> BytesMut {
+        let RequestPair { request_id, message: GetHeaderProofs(numbers) } = request;
+        let proofs = if self.config.enabled {
+            self.get_header_proofs(numbers).unwrap_or_else(|err| {
+                debug!(target: "net::light", %err, %peer_id, "failed to read header proofs");
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+        trace!(target: "net::light", proofs=proofs.len(), %peer_id, "serving header proofs");
+        LightMessage::HeaderProofs(RequestPair { request_id, message: HeaderProofs(proofs) })
+            .encoded()
+    }
+}
+
+/// The [`ProtocolHandler`] of the `light/1` protocol.
+///
+/// Serves the roots of the CHT-like index and headers with their inclusion proofs from the given
+/// provider, limited by the [`LightServingConfig`]. The protocol is only announced to peers if
+/// serving is enabled, or if light client data should be requested from peers, see
+/// [`LightProtocolHandler::with_events`].
+pub struct LightProtocolHandler<P> {
+    server: Arc<LightServer<P>>,
+    events: Option<mpsc::UnboundedSender<LightProtocolEvent>>,
+}
+
+impl<P> LightProtocolHandler<P> {
+    /// Creates a new handler that serves light clients from the given provider.
+    pub fn new(provider: P, config: LightServingConfig) -> Self {
+        let server = LightServer {
+            provider,
+            budget: ServingBudget::new(config.max_bytes_per_second),
+            config,
+            roots: Mutex::new(LruMap::new(CHT_ROOTS_CACHE_SIZE)),
+            trees: Mutex::new(LruMap::new(CHT_TREES_CACHE_SIZE)),
+        };
+        Self { server: Arc::new(server), events: None }
+    }
+
+    /// Sets the channel [`LightProtocolEvent`]s are sent to.
+    ///
+    /// This is required to request light client data from peers.
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<LightProtocolEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    fn connection_handler(&self) -> Option<LightConnectionHandler<P>> {
+        let announce = self.server.config.enabled || self.events.is_some();
+        announce.then(|| LightConnectionHandler {
+            server: self.server.clone(),
+            events: self.events.clone(),
+        })
+    }
+}
+
+impl<P> fmt::Debug for LightProtocolHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightProtocolHandler")
+            .field("config", &self.server.config)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ProtocolHandler for LightProtocolHandler<P>
+where
+    P: HeaderProvider + BlockNumReader + 'static,
+{
+    type ConnectionHandler = LightConnectionHandler<P>;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+}
+
+/// The [`ConnectionHandler`] of the `light/1` protocol.
+pub struct LightConnectionHandler<P> {
+    server: Arc<LightServer<P>>,
+    events: Option<mpsc::UnboundedSender<LightProtocolEvent>>,
+}
+
+impl<P> fmt::Debug for LightConnectionHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightConnectionHandler").finish_non_exhaustive()
+    }
+}
+
+impl<P> ConnectionHandler for LightConnectionHandler<P>
+where
+    P: HeaderProvider + BlockNumReader + 'static,
+{
+    type Connection = LightConnection<P>;
+
+    fn protocol(&self) -> Protocol {
+        LightMessage::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        if let Some(events) = &self.events {
+            events
+                .send(LightProtocolEvent::Established { direction, peer_id, to_connection: tx })
+                .ok();
+        }
+        LightConnection {
+            server: self.server,
+            peer_id,
+            conn,
+            commands: UnboundedReceiverStream::new(rx),
+            next_request_id: 0,
+            inflight_requests: HashMap::new(),
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// A request that was sent to the peer and awaits a response.
+#[derive(Debug)]
+enum PendingRequest {
+    ChtRoots(oneshot::Sender<RequestResult<Vec<B256>>>),
+    HeaderProofs(oneshot::Sender<RequestResult<Vec<HeaderProof>>>),
+}
+
+impl PendingRequest {
+    fn send_err(self, err: RequestError) {
+        match self {
+            Self::ChtRoots(response) => {
+                let _ = response.send(Err(err));
+            }
+            Self::HeaderProofs(response) => {
+                let _ = response.send(Err(err));
+            }
+        }
+    }
+}
+
+/// An established `light/1` connection with a peer.
+///
+/// Answers requests of the peer if serving is enabled and forwards requests received via
+/// [`LightCommand`]s. The connection is closed if the peer sends an invalid message.
+///
+/// Requests of the peer are served on the blocking pool, because they read from the database.
+pub struct LightConnection<P> {
+    server: Arc<LightServer<P>>,
+    peer_id: PeerId,
+    conn: ProtocolConnection,
+    commands: UnboundedReceiverStream<LightCommand>,
+    next_request_id: u64,
+    inflight_requests: HashMap<u64, PendingRequest>,
+    /// Responses to requests of the peer that are being served.
+    pending_responses: FuturesUnordered<JoinHandle<BytesMut>>,
+}
+
+impl<P> fmt::Debug for LightConnection<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightConnection")
+            .field("peer_id", &self.peer_id)
+            .field("next_request_id", &self.next_request_id)
+            .field("inflight_requests", &self.inflight_requests)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> LightConnection<P>
+where
+    P: HeaderProvider + BlockNumReader + 'static,
+{
+    fn on_command(&mut self, cmd: LightCommand) -> BytesMut {
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        match cmd {
+            LightCommand::GetChtRoots { sections, response } => {
+                self.inflight_requests.insert(request_id, PendingRequest::ChtRoots(response));
+                LightMessage::GetChtRoots(RequestPair {
+                    request_id,
+                    message: GetChtRoots(sections),
+                })
+                .encoded()
+            }
+            LightCommand::GetHeaderProofs { numbers, response } => {
+                self.inflight_requests.insert(request_id, PendingRequest::HeaderProofs(response));
+                LightMessage::GetHeaderProofs(RequestPair {
+                    request_id,
+                    message: GetHeaderProofs(numbers),
+                })
+                .encoded()
+            }
+        }
+    }
+
+    fn on_get_cht_roots(&mut self, request: RequestPair<GetChtRoots>) {
+        let (server, peer_id) = (self.server.clone(), self.peer_id);
+        self.pending_responses
+            .push(tokio::task::spawn_blocking(move || server.on_get_cht_roots(peer_id, request)));
+    }
+
+    fn on_get_header_proofs(&mut self, request: RequestPair<GetHeaderProofs>) {
+        let (server, peer_id) = (self.server.clone(), self.peer_id);
+        self.pending_responses.push(tokio::task::spawn_blocking(move || {
+            server.on_get_header_proofs(peer_id, request)
+        }));
+    }
+
+    fn on_cht_roots(&mut self, response: RequestPair<ChtRoots>) -> bool {
+        match self.inflight_requests.remove(&response.request_id) {
+            Some(PendingRequest::ChtRoots(tx)) => {
+                let _ = tx.send(Ok(response.message.0));
+                true
+            }
+            Some(pending) => {
+                pending.send_err(RequestError::BadResponse);
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn on_header_proofs(&mut self, response: RequestPair<HeaderProofs>) -> bool {
+        match self.inflight_requests.remove(&response.request_id) {
+            Some(PendingRequest::HeaderProofs(tx)) => {
+                let _ = tx.send(Ok(response.message.0));
+                true
+            }
+            Some(pending) => {
+                pending.send_err(RequestError::BadResponse);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+impl<P> Stream for LightConnection<P>
+where
+    P: HeaderProvider + BlockNumReader + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                return Poll::Ready(Some(this.on_command(cmd)))
+            }
+
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                match response {
+                    Ok(response) => return Poll::Ready(Some(response)),
+                    Err(err) => {
+                        debug!(target: "net::light", %err, peer_id=%this.peer_id, "failed to serve light request");
+                        continue
+                    }
+                }
+            }
+
+            if this.pending_responses.len() >= MAX_CONCURRENT_LIGHT_REQUESTS {
+                // woken up once one of the requests was served
+                return Poll::Pending
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            match LightMessage::decode_message(&mut &msg[..]) {
+                Ok(LightMessage::GetChtRoots(request)) => this.on_get_cht_roots(request),
+                Ok(LightMessage::GetHeaderProofs(request)) => this.on_get_header_proofs(request),
+                Ok(LightMessage::ChtRoots(response)) => {
+                    let request_id = response.request_id;
+                    if !this.on_cht_roots(response) {
+                        debug!(target: "net::light", request_id, peer_id=%this.peer_id, "received unsolicited cht roots");
+                        return Poll::Ready(None)
+                    }
+                }
+                Ok(LightMessage::HeaderProofs(response)) => {
+                    let request_id = response.request_id;
+                    if !this.on_header_proofs(response) {
+                        debug!(target: "net::light", request_id, peer_id=%this.peer_id, "received unsolicited header proofs");
+                        return Poll::Ready(None)
+                    }
+                }
+                Err(err) => {
+                    debug!(target: "net::light", %err, peer_id=%this.peer_id, "received invalid light message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl<P> Drop for LightConnection<P> {
+    fn drop(&mut self) {
+        for (_, pending) in self.inflight_requests.drain() {
+            pending.send_err(RequestError::ConnectionDropped);
+        }
+    }
+}
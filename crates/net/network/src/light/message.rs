@@ -0,0 +1,185 @@
+//! Message types of the `light` subprotocol.
+
+use crate::light::cht::verify_cht_branch;
+use alloy_rlp::{
+    Decodable, Encodable, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper,
+};
+use reth_eth_wire::{capability::Capability, message::RequestPair, protocol::Protocol};
+use reth_primitives::{BlockNumber, BufMut, BytesMut, Header, B256};
+
+/// Message ids of the `light` subprotocol, relative to the protocol's message id offset.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LightMessageId {
+    /// Requests the roots of sections of the index.
+    GetChtRoots = 0x00,
+    /// Response to a [`LightMessageId::GetChtRoots`] request.
+    ChtRoots = 0x01,
+    /// Requests headers with their inclusion proofs.
+    GetHeaderProofs = 0x02,
+    /// Response to a [`LightMessageId::GetHeaderProofs`] request.
+    HeaderProofs = 0x03,
+}
+
+impl LightMessageId {
+    /// The number of messages of the protocol.
+    pub const COUNT: u8 = 4;
+}
+
+impl TryFrom<u8> for LightMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            0x00 => Ok(Self::GetChtRoots),
+            0x01 => Ok(Self::ChtRoots),
+            0x02 => Ok(Self::GetHeaderProofs),
+            0x03 => Ok(Self::HeaderProofs),
+            _ => Err(alloy_rlp::Error::Custom("unknown light message id")),
+        }
+    }
+}
+
+/// Requests the roots of the given sections of the index.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct GetChtRoots(pub Vec<u64>);
+
+/// The roots of the requested sections, in the order they were requested.
+///
+/// The response stops at the first section that isn't indexed yet, or if the serving budget is
+/// exhausted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct ChtRoots(pub Vec<B256>);
+
+/// Requests the canonical headers with the given numbers, together with their inclusion proofs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct GetHeaderProofs(pub Vec<BlockNumber>);
+
+/// A canonical header and the branch that proves its inclusion in its section of the index.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct HeaderProof {
+    /// The header.
+    pub header: Header,
+    /// The sibling hashes from the header's leaf up to the section's root.
+    pub branch: Vec<B256>,
+}
+
+impl HeaderProof {
+    /// Returns `true` if the header is part of the section with the given root.
+    pub fn verify(&self, root: B256) -> bool {
+        verify_cht_branch(self.header.number, self.header.hash_slow(), &self.branch, root)
+    }
+}
+
+/// The requested headers with their proofs, in the order they were requested.
+///
+/// The response stops at the first header that isn't part of an indexed section, or if the
+/// serving budget is exhausted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct HeaderProofs(pub Vec<HeaderProof>);
+
+/// A message of the `light` subprotocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LightMessage {
+    /// Requests the roots of sections of the index.
+    GetChtRoots(RequestPair<GetChtRoots>),
+    /// The roots of sections of the index.
+    ChtRoots(RequestPair<ChtRoots>),
+    /// Requests headers with their inclusion proofs.
+    GetHeaderProofs(RequestPair<GetHeaderProofs>),
+    /// Headers with their inclusion proofs.
+    HeaderProofs(RequestPair<HeaderProofs>),
+}
+
+impl LightMessage {
+    /// Returns the capability of the `light/1` protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("light", 1)
+    }
+
+    /// Returns the `light/1` protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), LightMessageId::COUNT)
+    }
+
+    /// Returns the message id of this message.
+    pub const fn message_id(&self) -> LightMessageId {
+        match self {
+            Self::GetChtRoots(_) => LightMessageId::GetChtRoots,
+            Self::ChtRoots(_) => LightMessageId::ChtRoots,
+            Self::GetHeaderProofs(_) => LightMessageId::GetHeaderProofs,
+            Self::HeaderProofs(_) => LightMessageId::HeaderProofs,
+        }
+    }
+
+    /// Encodes the message, prefixed with its message id.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetChtRoots(req) => req.encode(&mut buf),
+            Self::ChtRoots(resp) => resp.encode(&mut buf),
+            Self::GetHeaderProofs(req) => req.encode(&mut buf),
+            Self::HeaderProofs(resp) => resp.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the given buffer, which starts with the message id.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        *buf = rest;
+        let msg = match LightMessageId::try_from(id)? {
+            LightMessageId::GetChtRoots => Self::GetChtRoots(RequestPair::decode(buf)?),
+            LightMessageId::ChtRoots => Self::ChtRoots(RequestPair::decode(buf)?),
+            LightMessageId::GetHeaderProofs => Self::GetHeaderProofs(RequestPair::decode(buf)?),
+            LightMessageId::HeaderProofs => Self::HeaderProofs(RequestPair::decode(buf)?),
+        };
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_message_roundtrip() {
+        let msgs = [
+            LightMessage::GetChtRoots(RequestPair {
+                request_id: 1,
+                message: GetChtRoots(vec![0, 7]),
+            }),
+            LightMessage::ChtRoots(RequestPair {
+                request_id: 1,
+                message: ChtRoots(vec![B256::random(), B256::random()]),
+            }),
+            LightMessage::GetHeaderProofs(RequestPair {
+                request_id: 2,
+                message: GetHeaderProofs(vec![1, 4096]),
+            }),
+            LightMessage::HeaderProofs(RequestPair {
+                request_id: 2,
+                message: HeaderProofs(vec![HeaderProof {
+                    header: Header { number: 1, ..Default::default() },
+                    branch: vec![B256::random(); 12],
+                }]),
+            }),
+            LightMessage::HeaderProofs(RequestPair {
+                request_id: 3,
+                message: HeaderProofs::default(),
+            }),
+        ];
+        for msg in msgs {
+            let encoded = msg.encoded();
+            assert_eq!(encoded[0], msg.message_id() as u8);
+            assert_eq!(LightMessage::decode_message(&mut &encoded[..]).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn reject_unknown_message_id() {
+        assert!(LightMessage::decode_message(&mut &[0x04, 0xc0][..]).is_err());
+        assert!(LightMessage::decode_message(&mut &[][..]).is_err());
+    }
+}
@@ -0,0 +1,24 @@
+//! The `light` subprotocol for serving resource-limited light clients.
+//!
+//! Light clients don't download and verify the full chain. Instead, they trust the roots of a
+//! CHT-like index of the canonical chain, see [`cht`], and request individual headers together with
+//! a proof of their inclusion in the index.
+//!
+//! The protocol is an optional [`RlpxSubProtocol`](crate::protocol::RlpxSubProtocol) that can be
+//! installed via [`LightProtocolHandler`]. Serving is opt-in and limited by a bandwidth budget that
+//! is shared by all peers, see [`LightServingConfig`].
+
+pub mod cht;
+mod config;
+mod handler;
+mod message;
+
+pub use config::{
+    LightServingConfig, DEFAULT_LIGHT_SERVING_BYTES_PER_SECOND, DEFAULT_MAX_LIGHT_REQUEST_ITEMS,
+};
+pub use handler::{
+    LightCommand, LightConnection, LightConnectionHandler, LightProtocolEvent, LightProtocolHandler,
+};
+pub use message::{
+    ChtRoots, GetChtRoots, GetHeaderProofs, HeaderProof, HeaderProofs, LightMessage, LightMessageId,
+};
@@ -18,7 +18,7 @@
 use crate::{
     budget::{DEFAULT_BUDGET_TRY_DRAIN_NETWORK_HANDLE_CHANNEL, DEFAULT_BUDGET_TRY_DRAIN_SWARM},
     config::NetworkConfig,
-    discovery::Discovery,
+    discovery::{enr_capabilities, Discovery},
     error::{NetworkError, ServiceKind},
     eth_requests::IncomingEthRequest,
     import::{BlockImport, BlockImportOutcome, BlockValidation},
@@ -26,7 +26,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::{DisconnectMetrics, NetworkMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedPeer},
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
     session::SessionManager,
@@ -43,7 +43,7 @@ use reth_eth_wire::{
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
 use reth_network_api::{EthProtocolInfo, NetworkStatus, ReputationChangeKind};
-use reth_network_peers::{NodeRecord, PeerId};
+use reth_network_peers::{NodeRecord, PeerId, ENR_CAPABILITIES_KEY};
 use reth_primitives::ForkId;
 use reth_provider::{BlockNumReader, BlockReader};
 use reth_tasks::shutdown::GracefulShutdown;
@@ -132,6 +132,19 @@ impl<C> NetworkManager<C> {
         self.swarm.add_rlpx_sub_protocol(protocol)
     }
 
+    /// Adds an additional protocol handler to the `RLPx` sub-protocol list and re-establishes the
+    /// sessions of connected peers that announced the protocol, so that it is negotiated with them
+    /// as well.
+    ///
+    /// See also [`ProtocolHandler::on_connected_peer`](crate::protocol::ProtocolHandler::on_connected_peer).
+    pub fn add_rlpx_sub_protocol_and_reconnect(&mut self, protocol: impl IntoRlpxSubProtocol) {
+        let peers = self.swarm.sessions_mut().add_rlpx_sub_protocol_and_reconnect(protocol);
+        for peer_id in peers {
+            trace!(target: "net", ?peer_id, "re-establishing session to negotiate added protocol");
+            self.swarm.state_mut().peers_mut().reconnect_after_disconnect(&peer_id);
+        }
+    }
+
     /// Returns the [`NetworkHandle`] that can be cloned and shared.
     ///
     /// The [`NetworkHandle`] can be used to interact with this [`NetworkManager`]
@@ -188,6 +201,7 @@ where
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
+            block_broadcast_fanout,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
@@ -211,6 +225,7 @@ where
             // merge configured boot nodes
             disc_config.bootstrap_nodes.extend(resolved_boot_nodes.clone());
             disc_config.add_eip868_pair("eth", status.forkid);
+            disc_config.add_eip868_pair(ENR_CAPABILITIES_KEY, enr_capabilities(&hello_message));
             disc_config
         });
 
@@ -229,7 +244,7 @@ where
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
-        let sessions = SessionManager::new(
+        let mut sessions = SessionManager::new(
             secret_key,
             sessions_config,
             executor,
@@ -238,9 +253,15 @@ where
             fork_filter,
             extra_protocols,
         );
+        sessions.set_trusted_peer_ids(peers_manager.trusted_peer_ids());
 
-        let state =
-            NetworkState::new(client, discovery, peers_manager, Arc::clone(&num_active_peers));
+        let state = NetworkState::new(
+            client,
+            discovery,
+            peers_manager,
+            Arc::clone(&num_active_peers),
+            block_broadcast_fanout,
+        );
 
         let swarm = Swarm::new(incoming, sessions, state);
 
@@ -336,6 +357,14 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns an iterator over all peers in the peer set, including the capabilities they
+    /// announced in their last session.
+    ///
+    /// This is what should be written to the persistent peers file.
+    pub fn all_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.swarm.state().peers().iter_persisted_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -617,6 +646,9 @@ where
                 let _ = tx.send(self.swarm.sessions().get_peer_infos_by_ids(peers));
             }
             NetworkHandleMessage::AddRlpxSubProtocol(proto) => self.add_rlpx_sub_protocol(proto),
+            NetworkHandleMessage::AddRlpxSubProtocolAndReconnect(proto) => {
+                self.add_rlpx_sub_protocol_and_reconnect(proto)
+            }
             NetworkHandleMessage::GetTransactionsHandle(tx) => {
                 if let Some(ref tx_inner) = self.to_transactions_manager {
                     let _ = tx_inner.send(NetworkTransactionEvent::GetTransactionsHandle(tx));
@@ -687,6 +719,11 @@ where
                     self.swarm.state_mut().peers_mut().on_active_outgoing_established(peer_id);
                 }
 
+                self.swarm
+                    .state_mut()
+                    .peers_mut()
+                    .on_session_capabilities(peer_id, Arc::clone(&capabilities));
+
                 self.update_active_connection_metrics();
 
                 self.event_sender.notify(NetworkEvent::SessionEstablished {
@@ -115,6 +115,23 @@ pub struct TransactionsManagerMetrics {
     /// Total number of times a transaction is sent that is already in the local pool.
     pub(crate) occurrences_transactions_already_in_pool: Counter,
 
+    /* ================ BLOB GOSSIP ================ */
+    /// Total size of the blob transactions announced to peers, in bytes. Only `eth/68`
+    /// announcements carry sizes.
+    pub(crate) announced_blob_bytes: Counter,
+    /// Total size of the blob transactions, including sidecars, that peers fetched from us, in
+    /// bytes.
+    pub(crate) served_blob_bytes: Counter,
+    /// Total size of the blob transactions, including sidecars, that we fetched from peers, in
+    /// bytes.
+    pub(crate) fetched_blob_bytes: Counter,
+    /// Total size of the blob transactions that peers pushed to us in full broadcasts, which is
+    /// disallowed, in bytes.
+    pub(crate) pushed_blob_bytes: Counter,
+    /// Total number of blob transactions left out of responses to peers, because serving them
+    /// would exceed a blob bandwidth budget.
+    pub(crate) throttled_blob_transactions: Counter,
+
     /* ================ POOL IMPORTS ================ */
     /// Number of transactions about to be imported into the pool.
     pub(crate) pending_pool_imports: Gauge,
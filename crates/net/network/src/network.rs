@@ -8,8 +8,8 @@ use parking_lot::Mutex;
 use reth_discv4::Discv4;
 use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, SharedTransactions};
 use reth_network_api::{
-    NetworkError, NetworkInfo, NetworkStatus, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
-    ReputationChangeKind,
+    NatStatus, NetworkError, NetworkInfo, NetworkStatus, PeerInfo, PeerKind, Peers, PeersInfo,
+    Reputation, ReputationChangeKind,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -208,6 +208,10 @@ impl NetworkProtocols for NetworkHandle {
     fn add_rlpx_sub_protocol(&self, protocol: RlpxSubProtocol) {
         self.send_message(NetworkHandleMessage::AddRlpxSubProtocol(protocol))
     }
+
+    fn add_rlpx_sub_protocol_and_reconnect(&self, protocol: RlpxSubProtocol) {
+        self.send_message(NetworkHandleMessage::AddRlpxSubProtocolAndReconnect(protocol))
+    }
 }
 
 impl PeersInfo for NetworkHandle {
@@ -337,6 +341,16 @@ impl NetworkInfo for NetworkHandle {
     fn is_initially_syncing(&self) -> bool {
         SyncStateProvider::is_initially_syncing(self)
     }
+
+    fn nat_status(&self) -> Option<NatStatus> {
+        self.inner.discv4.as_ref().and_then(|discv4| discv4.nat_status())
+    }
+
+    fn refresh_nat(&self) {
+        if let Some(discv4) = &self.inner.discv4 {
+            discv4.refresh_nat()
+        }
+    }
 }
 
 impl SyncStateProvider for NetworkHandle {
@@ -411,7 +425,21 @@ pub trait NetworkEvents: Send + Sync {
 /// Provides access to modify the network's additional protocol handlers.
 pub trait NetworkProtocols: Send + Sync {
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
+    ///
+    /// The protocol is only negotiated with peers on new sessions.
     fn add_rlpx_sub_protocol(&self, protocol: RlpxSubProtocol);
+
+    /// Adds an additional protocol handler to the `RLPx` sub-protocol list and re-establishes the
+    /// sessions of connected peers that announced the protocol.
+    ///
+    /// `RLPx` capabilities can't be renegotiated on a live session, so these peers are
+    /// disconnected and dialed again right away. Handlers can opt out per peer, see
+    /// [`ProtocolHandler::on_connected_peer`](crate::protocol::ProtocolHandler::on_connected_peer).
+    ///
+    /// By default this only adds the protocol, see [`NetworkProtocols::add_rlpx_sub_protocol`].
+    fn add_rlpx_sub_protocol_and_reconnect(&self, protocol: RlpxSubProtocol) {
+        self.add_rlpx_sub_protocol(protocol)
+    }
 }
 
 /// Internal messages that can be passed to the  [`NetworkManager`](crate::NetworkManager).
@@ -479,4 +507,6 @@ pub(crate) enum NetworkHandleMessage {
     DiscoveryListener(UnboundedSender<DiscoveryEvent>),
     /// Adds an additional `RlpxSubProtocol`.
     AddRlpxSubProtocol(RlpxSubProtocol),
+    /// Adds an additional `RlpxSubProtocol` and re-establishes sessions of peers that support it.
+    AddRlpxSubProtocolAndReconnect(RlpxSubProtocol),
 }
@@ -11,7 +11,12 @@ use crate::{
     swarm::NetworkConnectionState,
 };
 use futures::StreamExt;
-use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
+use parking_lot::RwLock;
+use reth_eth_wire::{
+    capability::{Capabilities, Capability},
+    errors::EthStreamError,
+    DisconnectReason,
+};
 use reth_net_banlist::BanList;
 use reth_network_api::{PeerKind, ReputationChangeKind};
 use reth_network_peers::{NodeRecord, PeerId};
@@ -22,6 +27,7 @@ use std::{
     io::{self, ErrorKind},
     net::{IpAddr, SocketAddr},
     path::Path,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -93,7 +99,7 @@ pub struct PeersManager {
     ///
     /// This tracks peer ids that are considered trusted, but for which we don't necessarily have
     /// an address: [`Self::add_trusted_peer_id`]
-    trusted_peer_ids: HashSet<PeerId>,
+    trusted_peer_ids: TrustedPeerIds,
     /// Copy of the sender half, so new [`PeersHandle`] can be created on demand.
     manager_tx: mpsc::UnboundedSender<PeerCommand>,
     /// Receiver half of the command channel.
@@ -126,6 +132,8 @@ pub struct PeersManager {
     max_backoff_count: u8,
     /// Tracks the connection state of the node
     net_connection_state: NetworkConnectionState,
+    /// Capabilities that peers should preferably support when dialing new outbound connections.
+    preferred_capabilities: Vec<Capability>,
 }
 
 impl PeersManager {
@@ -141,6 +149,8 @@ impl PeersManager {
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            known_capabilities,
+            preferred_capabilities,
             max_backoff_count,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -150,7 +160,7 @@ impl PeersManager {
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
         let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
-        let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
+        let trusted_peer_ids = TrustedPeerIds::with_capacity(trusted_nodes.len());
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
             trusted_peer_ids.insert(id);
@@ -161,6 +171,13 @@ impl PeersManager {
             peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
         }
 
+        // restore the capabilities peers announced in previous sessions
+        for (id, capabilities) in known_capabilities {
+            if let Some(peer) = peers.get_mut(&id) {
+                peer.capabilities = Some(Arc::new(Capabilities::from(capabilities)));
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -179,6 +196,7 @@ impl PeersManager {
             last_tick: Instant::now(),
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
+            preferred_capabilities,
         }
     }
 
@@ -198,6 +216,15 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns an iterator over all peers and the capabilities they announced in their last
+    /// session, if any.
+    pub(crate) fn iter_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.peers.iter().map(|(peer_id, v)| PersistedPeer {
+            record: NodeRecord::new(v.addr, *peer_id),
+            capabilities: v.capabilities.as_ref().map(|caps| caps.capabilities().to_vec()),
+        })
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -227,6 +254,13 @@ impl PeersManager {
         self.backed_off_peers.len()
     }
 
+    /// Returns the set of trusted peer ids.
+    ///
+    /// The returned set is shared and reflects all future changes to the trusted peers.
+    pub(crate) fn trusted_peer_ids(&self) -> TrustedPeerIds {
+        self.trusted_peer_ids.clone()
+    }
+
     /// Invoked when a new _incoming_ tcp connection is accepted.
     ///
     /// returns an error if the inbound ip address is on the ban list
@@ -501,8 +535,21 @@ impl PeersManager {
                     // reset the peer's state
                     // we reset the backoff counter since we're able to establish a successful
                     // session to that peer
-                    entry.get_mut().severe_backoff_counter = 0;
-                    entry.get_mut().state = PeerConnectionState::Idle;
+                    let peer = entry.get_mut();
+                    peer.severe_backoff_counter = 0;
+                    peer.state = PeerConnectionState::Idle;
+
+                    // the session was closed to re-establish it, so we dial the peer right away
+                    if std::mem::take(&mut peer.reconnect_after_disconnect) &&
+                        self.net_connection_state.is_active() &&
+                        (peer.is_trusted() || self.connection_info.has_out_capacity())
+                    {
+                        trace!(target: "net::peers", ?peer_id, addr=?peer.addr, "schedule reconnect");
+                        peer.state = PeerConnectionState::PendingOut;
+                        self.connection_info.inc_pending_out();
+                        self.queued_actions
+                            .push_back(PeerAction::Connect { peer_id, remote_addr: peer.addr });
+                    }
                     return
                 }
             }
@@ -512,6 +559,30 @@ impl PeersManager {
         self.fill_outbound_slots();
     }
 
+    /// Marks the peer to be dialed again as soon as its active session was gracefully closed.
+    ///
+    /// This is used to re-establish sessions, for example to negotiate a protocol that was added
+    /// at runtime.
+    pub(crate) fn reconnect_after_disconnect(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.reconnect_after_disconnect = true;
+        }
+    }
+
+    /// Records the capabilities the peer announced in its established session.
+    ///
+    /// These are kept after the session is closed so that they can be persisted and used to
+    /// prefer peers that support the [`PeersConfig::preferred_capabilities`] when dialing.
+    pub(crate) fn on_session_capabilities(
+        &mut self,
+        peer_id: PeerId,
+        capabilities: Arc<Capabilities>,
+    ) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.capabilities = Some(capabilities);
+        }
+    }
+
     /// Called when a _pending_ outbound connection is successful.
     pub(crate) fn on_active_outgoing_established(&mut self, peer_id: PeerId) {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
@@ -659,6 +730,24 @@ impl PeersManager {
         }
     }
 
+    /// Called as follow-up for a discovered peer.
+    ///
+    /// The capabilities are retrieved from an ENR record that the peer announces over the
+    /// discovery protocol. They are only used as a hint for dialing until a session with the peer
+    /// was established, so capabilities of a previous session take precedence.
+    pub(crate) fn set_discovered_capabilities(
+        &mut self,
+        peer_id: PeerId,
+        capabilities: Vec<Capability>,
+    ) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if peer.capabilities.is_none() {
+                trace!(target: "net::peers", ?peer_id, ?capabilities, "set discovered capabilities");
+                peer.capabilities = Some(Arc::new(capabilities.into()));
+            }
+        }
+    }
+
     /// Called for a newly discovered peer.
     ///
     /// If the peer already exists, then the address, kind and `fork_id` will be updated.
@@ -766,6 +855,10 @@ impl PeersManager {
     /// Peers that are `trusted`, see [`PeerKind`], are prioritized as long as they're not currently
     /// marked as banned or backed off.
     ///
+    /// Otherwise, peers known to support all [`PeersConfig::preferred_capabilities`] are preferred
+    /// over peers with unknown capabilities, which in turn are preferred over peers known to lack
+    /// them. Within the same group, the peer with the highest reputation wins.
+    ///
     /// If `trusted_nodes_only` is enabled, see [`PeersConfig`], then this will only consider
     /// `trusted` peers.
     ///
@@ -785,15 +878,20 @@ impl PeersManager {
             return Some((*best_peer.0, best_peer.1))
         }
 
+        let preferred = &self.preferred_capabilities;
+        let mut best_rank = best_peer.1.dial_rank(preferred);
+
         for maybe_better in unconnected {
             // if the peer is trusted, return it immediately
             if maybe_better.1.is_trusted() {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer using the known capabilities and reputation
+            let rank = maybe_better.1.dial_rank(preferred);
+            if rank > best_rank {
                 best_peer = maybe_better;
+                best_rank = rank;
             }
         }
         Some((*best_peer.0, best_peer.1))
@@ -917,6 +1015,38 @@ impl Default for PeersManager {
     }
 }
 
+/// The set of trusted peer ids, shared between the [`PeersManager`] and the
+/// [`SessionManager`](crate::session::SessionManager).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrustedPeerIds(Arc<RwLock<HashSet<PeerId>>>);
+
+impl TrustedPeerIds {
+    /// Creates an empty set with at least the given capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Arc::new(RwLock::new(HashSet::with_capacity(capacity))))
+    }
+
+    /// Returns `true` if the given peer is trusted.
+    pub(crate) fn contains(&self, peer_id: &PeerId) -> bool {
+        self.0.read().contains(peer_id)
+    }
+
+    /// Returns `true` if there are no trusted peers.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.read().is_empty()
+    }
+
+    /// Marks the given peer as trusted.
+    fn insert(&self, peer_id: PeerId) {
+        self.0.write().insert(peer_id);
+    }
+
+    /// Removes the given peer from the trusted set.
+    fn remove(&self, peer_id: &PeerId) {
+        self.0.write().remove(peer_id);
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -1025,12 +1155,16 @@ pub struct Peer {
     fork_id: Option<ForkId>,
     /// Whether the entry should be removed after an existing session was terminated.
     remove_after_disconnect: bool,
+    /// Whether the peer should be dialed again after the existing session was terminated.
+    reconnect_after_disconnect: bool,
     /// The kind of peer
     kind: PeerKind,
     /// Whether the peer is currently backed off.
     backed_off: bool,
     /// Counts number of times the peer was backed off due to a severe [`BackoffKind`].
     severe_backoff_counter: u8,
+    /// The capabilities the peer announced in its last session, if any.
+    capabilities: Option<Arc<Capabilities>>,
 }
 
 // === impl Peer ===
@@ -1049,6 +1183,32 @@ impl Peer {
         self.reputation
     }
 
+    /// Returns the capabilities the peer announced in its last session, if any.
+    pub const fn capabilities(&self) -> Option<&Arc<Capabilities>> {
+        self.capabilities.as_ref()
+    }
+
+    /// Returns `Some(true)` if the peer is known to support all of the given capabilities,
+    /// `Some(false)` if it is known to lack any of them, or `None` if its capabilities are unknown.
+    fn supports_capabilities(&self, capabilities: &[Capability]) -> Option<bool> {
+        let known = self.capabilities.as_ref()?.capabilities();
+        Some(capabilities.iter().all(|cap| known.contains(cap)))
+    }
+
+    /// Returns the rank of the peer when selecting the next peer to dial, higher is better.
+    fn dial_rank(&self, preferred_capabilities: &[Capability]) -> (u8, i32) {
+        let capabilities_rank = if preferred_capabilities.is_empty() {
+            0
+        } else {
+            match self.supports_capabilities(preferred_capabilities) {
+                Some(true) => 2,
+                None => 1,
+                Some(false) => 0,
+            }
+        };
+        (capabilities_rank, self.reputation)
+    }
+
     fn with_state(addr: SocketAddr, state: PeerConnectionState) -> Self {
         Self {
             addr,
@@ -1056,9 +1216,11 @@ impl Peer {
             reputation: DEFAULT_REPUTATION,
             fork_id: None,
             remove_after_disconnect: false,
+            reconnect_after_disconnect: false,
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            capabilities: None,
         }
     }
 
@@ -1289,6 +1451,15 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// Capabilities that known peers announced in previous sessions, usually restored from the
+    /// persistent peers file.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub known_capabilities: HashMap<PeerId, Vec<Capability>>,
+    /// Capabilities that peers should preferably support.
+    ///
+    /// When dialing new outbound connections, peers known to support all of these capabilities
+    /// are preferred over other peers.
+    pub preferred_capabilities: Vec<Capability>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1319,6 +1490,8 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            known_capabilities: Default::default(),
+            preferred_capabilities: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -1401,6 +1574,21 @@ impl PeersConfig {
         self
     }
 
+    /// Capabilities that known peers announced in previous sessions.
+    pub fn with_known_capabilities(
+        mut self,
+        known_capabilities: HashMap<PeerId, Vec<Capability>>,
+    ) -> Self {
+        self.known_capabilities = known_capabilities;
+        self
+    }
+
+    /// Capabilities that peers should preferably support when dialing.
+    pub fn with_preferred_capabilities(mut self, capabilities: Vec<Capability>) -> Self {
+        self.preferred_capabilities = capabilities;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1428,6 +1616,9 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file contains a list of [`PersistedPeer`]s, the capabilities of the peers are restored
+    /// as well.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1439,8 +1630,16 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let peers: Vec<PersistedPeer> = serde_json::from_reader(reader)?;
+        let mut nodes = HashSet::with_capacity(peers.len());
+        let mut known_capabilities = HashMap::new();
+        for PersistedPeer { record, capabilities } in peers {
+            if let Some(capabilities) = capabilities {
+                known_capabilities.insert(record.id, capabilities);
+            }
+            nodes.insert(record);
+        }
+        Ok(self.with_basic_nodes(nodes).with_known_capabilities(known_capabilities))
     }
 
     /// Returns settings for testing
@@ -1454,6 +1653,45 @@ impl PeersConfig {
     }
 }
 
+/// A known peer as stored in the persistent peers file.
+///
+/// For backwards compatibility, a plain [`NodeRecord`] is accepted as well when deserializing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "PersistedPeerRepr"))]
+pub struct PersistedPeer {
+    /// The node record of the peer.
+    pub record: NodeRecord,
+    /// The capabilities the peer announced in its last session, if known.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub capabilities: Option<Vec<Capability>>,
+}
+
+/// The supported serialized forms of a [`PersistedPeer`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PersistedPeerRepr {
+    /// Legacy format that only stores the node record.
+    Record(NodeRecord),
+    /// Node record with optional capabilities.
+    Peer {
+        record: NodeRecord,
+        #[serde(default)]
+        capabilities: Option<Vec<Capability>>,
+    },
+}
+
+#[cfg(feature = "serde")]
+impl From<PersistedPeerRepr> for PersistedPeer {
+    fn from(repr: PersistedPeerRepr) -> Self {
+        match repr {
+            PersistedPeerRepr::Record(record) => Self { record, capabilities: None },
+            PersistedPeerRepr::Peer { record, capabilities } => Self { record, capabilities },
+        }
+    }
+}
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`].
@@ -1545,13 +1783,14 @@ mod tests {
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
             reputation::DEFAULT_REPUTATION,
-            InboundConnectionError, PeerAction,
+            InboundConnectionError, PeerAction, PersistedPeer,
         },
         session::PendingSessionHandshakeError,
         PeersConfig,
     };
     use reth_discv4::NodeRecord;
     use reth_eth_wire::{
+        capability::{Capabilities, Capability},
         errors::{EthHandshakeError, EthStreamError, P2PHandshakeError, P2PStreamError},
         DisconnectReason,
     };
@@ -1560,11 +1799,12 @@ mod tests {
     use reth_network_peers::PeerId;
     use reth_primitives::B512;
     use std::{
-        collections::HashSet,
+        collections::{HashMap, HashSet},
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
         pin::Pin,
+        sync::Arc,
         task::{Context, Poll},
         time::Duration,
     };
@@ -2380,6 +2620,40 @@ mod tests {
         assert_eq!(peers.connection_info.num_pending_out, 0);
     }
 
+    #[tokio::test]
+    async fn test_reconnect_after_disconnect() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::default();
+        peers.add_peer(peer, socket_addr, None);
+
+        assert!(matches!(event!(peers), PeerAction::PeerAdded(_)));
+        assert!(matches!(event!(peers), PeerAction::Connect { .. }));
+        peers.on_active_outgoing_established(peer);
+        assert_eq!(peers.num_outbound_connections(), 1);
+
+        peers.reconnect_after_disconnect(&peer);
+        peers.on_active_session_gracefully_closed(peer);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, peer);
+                assert_eq!(remote_addr, socket_addr);
+            }
+            _ => unreachable!(),
+        }
+        let p = peers.peers.get(&peer).unwrap();
+        assert_eq!(p.state, PeerConnectionState::PendingOut);
+        assert!(!p.reconnect_after_disconnect);
+        assert_eq!(peers.connection_info.num_pending_out, 1);
+
+        // without the flag the peer stays idle
+        peers.on_active_outgoing_established(peer);
+        peers.on_active_session_gracefully_closed(peer);
+        assert_eq!(peers.peers.get(&peer).unwrap().state, PeerConnectionState::Idle);
+        assert_eq!(peers.connection_info.num_pending_out, 0);
+    }
+
     #[tokio::test]
     async fn test_discovery_ban_list() {
         let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
@@ -2514,6 +2788,72 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_peers_with_preferred_capabilities_are_prioritized() {
+        let snap = Capability::new_static("snap", 1);
+        let with_snap = NodeRecord {
+            address: IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)),
+            tcp_port: 8008,
+            udp_port: 8008,
+            id: PeerId::random(),
+        };
+        let without_snap = NodeRecord { tcp_port: 8009, id: PeerId::random(), ..with_snap };
+
+        let config = PeersConfig::test()
+            .with_max_outbound(1)
+            .with_basic_nodes(HashSet::from([with_snap, without_snap]))
+            .with_known_capabilities(HashMap::from([
+                (with_snap.id, vec![Capability::eth_68(), snap.clone()]),
+                (without_snap.id, vec![Capability::eth_68()]),
+            ]))
+            .with_preferred_capabilities(vec![snap]);
+        let mut peers = PeersManager::new(config);
+
+        match event!(peers) {
+            PeerAction::Connect { peer_id, remote_addr } => {
+                assert_eq!(peer_id, with_snap.id);
+                assert_eq!(remote_addr, with_snap.tcp_addr());
+            }
+            _ => unreachable!(),
+        }
+
+        let persisted = peers.iter_persisted_peers().find(|p| p.record == with_snap).unwrap();
+        assert_eq!(persisted.capabilities.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_discovered_capabilities() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let mut peers = PeersManager::new(PeersConfig::test());
+        peers.add_peer(peer, socket_addr, None);
+
+        peers.set_discovered_capabilities(peer, vec![Capability::eth_68()]);
+        let caps = peers.peers.get(&peer).unwrap().capabilities().unwrap().clone();
+        assert!(caps.supports_eth_v68());
+
+        // capabilities of a session take precedence over discovered capabilities
+        let session_caps = Arc::new(Capabilities::from(vec![Capability::eth_67()]));
+        peers.on_session_capabilities(peer, session_caps.clone());
+        peers.set_discovered_capabilities(peer, vec![Capability::eth_68()]);
+        assert_eq!(peers.peers.get(&peer).unwrap().capabilities(), Some(&session_caps));
+    }
+
+    #[test]
+    fn test_persisted_peer_serde() {
+        let enode = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303?discport=30301";
+        let record: NodeRecord = enode.parse().unwrap();
+
+        // legacy format: plain node records
+        let legacy: Vec<PersistedPeer> = serde_json::from_str(&format!(r#"["{enode}"]"#)).unwrap();
+        assert_eq!(legacy, vec![PersistedPeer { record, capabilities: None }]);
+
+        let peer = PersistedPeer { record, capabilities: Some(vec![Capability::eth_68()]) };
+        let json = serde_json::to_string(&peer).unwrap();
+        assert_eq!(json, format!(r#"{{"record":"{enode}","capabilities":["eth/68"]}}"#));
+        assert_eq!(serde_json::from_str::<PersistedPeer>(&json).unwrap(), peer);
+    }
+
     #[tokio::test]
     async fn test_connect_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
@@ -3,8 +3,10 @@
 mod manager;
 mod reputation;
 
-pub(crate) use manager::InboundConnectionError;
-pub use manager::{ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager};
+pub use manager::{
+    ConnectionInfo, Peer, PeerAction, PeersConfig, PeersHandle, PeersManager, PersistedPeer,
+};
+pub(crate) use manager::{InboundConnectionError, TrustedPeerIds};
 pub use reputation::ReputationChangeWeights;
 pub use reth_network_api::PeerKind;
 
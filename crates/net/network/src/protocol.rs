@@ -4,7 +4,9 @@
 
 use futures::Stream;
 use reth_eth_wire::{
-    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+    capability::{Capabilities, SharedCapabilities},
+    multiplex::{ProtocolConnection, ProtocolPriority},
+    protocol::Protocol,
 };
 use reth_network_api::{Direction, PeerId};
 use reth_primitives::BytesMut;
@@ -36,6 +38,20 @@ pub trait ProtocolHandler: fmt::Debug + Send + Sync + 'static {
         socket_addr: SocketAddr,
         peer_id: PeerId,
     ) -> Option<Self::ConnectionHandler>;
+
+    /// Invoked when the protocol is added at runtime for every connected peer that announced the
+    /// protocol, see
+    /// [`NetworkProtocols::add_rlpx_sub_protocol_and_reconnect`](crate::NetworkProtocols::add_rlpx_sub_protocol_and_reconnect).
+    ///
+    /// Capabilities are only negotiated when a session is established, so the protocol can only be
+    /// attached to an already connected peer by re-establishing its session. Returning `false`
+    /// keeps the existing session, the protocol is then only negotiated with the peer on the next
+    /// session.
+    ///
+    /// Defaults to `true`.
+    fn on_connected_peer(&self, _peer_id: PeerId, _capabilities: &Capabilities) -> bool {
+        true
+    }
 }
 
 /// A trait that allows to authenticate a protocol after the `RLPx` connection was established.
@@ -50,6 +66,14 @@ pub trait ConnectionHandler: Send + Sync + 'static {
     /// This will be negotiated with the remote peer.
     fn protocol(&self) -> Protocol;
 
+    /// Returns the priority class of the protocol's outgoing messages.
+    ///
+    /// If the connection is congested, messages of higher priority classes are written to the wire
+    /// first, see [`ProtocolPriority`]. Defaults to [`ProtocolPriority::Normal`].
+    fn priority(&self) -> ProtocolPriority {
+        ProtocolPriority::Normal
+    }
+
     /// Invoked when the `RLPx` connection has been established by the peer does not share the
     /// protocol.
     fn on_unsupported_by_peer(
@@ -99,6 +123,25 @@ where
     }
 }
 
+impl RlpxSubProtocol {
+    /// Returns `true` if the session with the given connected peer should be re-established so
+    /// that this protocol is negotiated with the peer.
+    ///
+    /// This is the case if the protocol would be announced to the peer, the peer announced the
+    /// protocol as well and the handler agrees, see [`ProtocolHandler::on_connected_peer`].
+    pub(crate) fn should_reconnect(
+        &self,
+        remote_addr: SocketAddr,
+        peer_id: PeerId,
+        capabilities: &Capabilities,
+    ) -> bool {
+        let Some(handler) = self.0.on_outgoing(remote_addr, peer_id) else { return false };
+        let protocol = handler.protocol();
+        capabilities.capabilities().contains(&protocol.cap) &&
+            self.0.on_connected_peer(peer_id, capabilities)
+    }
+}
+
 impl IntoRlpxSubProtocol for RlpxSubProtocol {
     fn into_rlpx_sub_protocol(self) -> RlpxSubProtocol {
         self
@@ -178,6 +221,8 @@ pub(crate) trait DynProtocolHandler: fmt::Debug + Send + Sync + 'static {
         socket_addr: SocketAddr,
         peer_id: PeerId,
     ) -> Option<Box<dyn DynConnectionHandler>>;
+
+    fn on_connected_peer(&self, peer_id: PeerId, capabilities: &Capabilities) -> bool;
 }
 
 impl<T: ProtocolHandler> DynProtocolHandler for T {
@@ -194,12 +239,18 @@ impl<T: ProtocolHandler> DynProtocolHandler for T {
         T::on_outgoing(self, socket_addr, peer_id)
             .map(|handler| Box::new(handler) as Box<dyn DynConnectionHandler>)
     }
+
+    fn on_connected_peer(&self, peer_id: PeerId, capabilities: &Capabilities) -> bool {
+        T::on_connected_peer(self, peer_id, capabilities)
+    }
 }
 
 /// Wrapper trait for internal ease of use.
 pub(crate) trait DynConnectionHandler: Send + Sync + 'static {
     fn protocol(&self) -> Protocol;
 
+    fn priority(&self) -> ProtocolPriority;
+
     fn into_connection(
         self: Box<Self>,
         direction: Direction,
@@ -213,6 +264,10 @@ impl<T: ConnectionHandler> DynConnectionHandler for T {
         T::protocol(self)
     }
 
+    fn priority(&self) -> ProtocolPriority {
+        T::priority(self)
+    }
+
     fn into_connection(
         self: Box<Self>,
         direction: Direction,
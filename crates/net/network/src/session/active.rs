@@ -849,6 +849,9 @@ mod tests {
                 self.status,
                 self.fork_filter.clone(),
                 Default::default(),
+                None,
+                Default::default(),
+                Default::default(),
             ));
 
             let mut stream = ReceiverStream::new(pending_sessions_rx);
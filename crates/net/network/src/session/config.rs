@@ -4,7 +4,8 @@ use crate::{
     peers::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND},
     session::{Direction, ExceedsSessionLimit},
 };
-use std::time::Duration;
+use reth_eth_wire::Capability;
+use std::{collections::HashMap, time::Duration};
 
 /// Default request timeout for a single request.
 ///
@@ -19,6 +20,9 @@ pub const PENDING_SESSION_TIMEOUT: Duration = Duration::from_secs(20);
 /// This is the time a peer has to answer a response.
 pub const PROTOCOL_BREACH_REQUEST_TIMEOUT: Duration = Duration::from_secs(2 * 60);
 
+/// Default timeout for the `p2p` hello exchange and the `eth` status exchange.
+pub const HANDSHAKE_TIMEOUT: Duration = reth_eth_wire::HANDSHAKE_TIMEOUT;
+
 /// The default maximum number of peers.
 const DEFAULT_MAX_PEERS: usize =
     DEFAULT_MAX_COUNT_PEERS_OUTBOUND as usize + DEFAULT_MAX_COUNT_PEERS_INBOUND as usize;
@@ -53,6 +57,23 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// Timeouts for the individual phases of the session handshake.
+    pub handshake_timeouts: HandshakeTimeouts,
+    /// Whether additional `RLPx` subprotocols are only negotiated with trusted peers.
+    ///
+    /// If enabled, the additional subprotocols are neither advertised to nor accepted from peers
+    /// that aren't in the trusted set, these sessions only use `eth`.
+    pub extra_protocols_trusted_only: bool,
+    /// The maximum size of inbound messages of additional `RLPx` subprotocols, by capability
+    /// name.
+    ///
+    /// Peers that exceed the limit are disconnected and penalized. Protocols without a limit are
+    /// only bound by the maximum payload size of the `p2p` layer.
+    pub max_message_sizes: HashMap<String, usize>,
+    /// Capabilities that peers must advertise in their `Hello` message.
+    ///
+    /// Peers that lack any of them are disconnected as useless after the `p2p` handshake.
+    pub required_capabilities: Vec<Capability>,
 }
 
 impl Default for SessionsConfig {
@@ -72,6 +93,10 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            handshake_timeouts: Default::default(),
+            extra_protocols_trusted_only: false,
+            max_message_sizes: Default::default(),
+            required_capabilities: Vec::new(),
         }
     }
 }
@@ -87,6 +112,31 @@ impl SessionsConfig {
         self
     }
 
+    /// Sets the timeouts for the individual phases of the session handshake.
+    pub const fn with_handshake_timeouts(mut self, timeouts: HandshakeTimeouts) -> Self {
+        self.handshake_timeouts = timeouts;
+        self
+    }
+
+    /// Sets whether additional `RLPx` subprotocols are only negotiated with trusted peers.
+    pub const fn with_extra_protocols_trusted_only(mut self, trusted_only: bool) -> Self {
+        self.extra_protocols_trusted_only = trusted_only;
+        self
+    }
+
+    /// Sets the maximum size of inbound messages of the additional `RLPx` subprotocol with the
+    /// given capability name.
+    pub fn with_max_message_size(mut self, name: impl Into<String>, max_size: usize) -> Self {
+        self.max_message_sizes.insert(name.into(), max_size);
+        self
+    }
+
+    /// Sets the capabilities that peers must advertise to keep their session.
+    pub fn with_required_capabilities(mut self, capabilities: Vec<Capability>) -> Self {
+        self.required_capabilities = capabilities;
+        self
+    }
+
     /// Helper function to set the buffer size for the bounded communication channel between the
     /// manager and its sessions for events emitted by the sessions.
     ///
@@ -105,6 +155,53 @@ impl SessionsConfig {
     }
 }
 
+/// Timeouts for the individual phases of a pending session.
+///
+/// These are enforced in addition to the overall
+/// [`pending_session_timeout`](SessionsConfig::pending_session_timeout), so that peers that stall
+/// early in the handshake release their slot sooner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct HandshakeTimeouts {
+    /// The time we wait for the remote's `Hello` message, which advertises its capabilities.
+    pub hello: Duration,
+    /// The time we wait for the remote's `eth` `Status` message once the capabilities were
+    /// negotiated.
+    pub status: Duration,
+    /// The time an additional `RLPx` subprotocol has to receive its first message from the remote
+    /// once the session is established.
+    ///
+    /// By default, this is not enforced.
+    pub first_protocol_message: Option<Duration>,
+}
+
+impl Default for HandshakeTimeouts {
+    fn default() -> Self {
+        Self { hello: HANDSHAKE_TIMEOUT, status: HANDSHAKE_TIMEOUT, first_protocol_message: None }
+    }
+}
+
+impl HandshakeTimeouts {
+    /// Sets the timeout for the `Hello` exchange.
+    pub const fn with_hello(mut self, timeout: Duration) -> Self {
+        self.hello = timeout;
+        self
+    }
+
+    /// Sets the timeout for the `eth` `Status` exchange.
+    pub const fn with_status(mut self, timeout: Duration) -> Self {
+        self.status = timeout;
+        self
+    }
+
+    /// Sets the timeout for the first message of each additional `RLPx` subprotocol.
+    pub const fn with_first_protocol_message(mut self, timeout: Duration) -> Self {
+        self.first_protocol_message = Some(timeout);
+        self
+    }
+}
+
 /// Limits for sessions.
 ///
 /// By default, no session limits will be enforced
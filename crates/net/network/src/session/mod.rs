@@ -3,12 +3,16 @@
 use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
-    session::{active::ActiveSession, config::SessionCounter},
+    peers::TrustedPeerIds,
+    session::{
+        active::ActiveSession,
+        config::{HandshakeTimeouts, SessionCounter},
+    },
 };
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    capability::{Capabilities, CapabilityMessage},
+    capability::{Capabilities, Capability, CapabilityMessage},
     errors::EthStreamError,
     DisconnectReason, EthVersion, HelloMessageWithProtocols, Status, UnauthedEthStream,
     UnauthedP2PStream,
@@ -42,7 +46,7 @@ mod conn;
 mod handle;
 pub use crate::message::PeerRequestSender;
 use crate::protocol::{IntoRlpxSubProtocol, RlpxSubProtocolHandlers, RlpxSubProtocols};
-pub use config::{SessionLimits, SessionsConfig};
+pub use config::{HandshakeTimeouts, SessionLimits, SessionsConfig};
 pub use handle::{
     ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
     SessionCommand,
@@ -70,6 +74,17 @@ pub struct SessionManager {
     protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pending_session_timeout: Duration,
+    /// Timeouts for the individual phases of the session handshake.
+    handshake_timeouts: HandshakeTimeouts,
+    /// Whether additional `RLPx` subprotocols are only negotiated with trusted peers.
+    extra_protocols_trusted_only: bool,
+    /// The maximum size of inbound messages of additional `RLPx` subprotocols, by capability
+    /// name.
+    max_message_sizes: Arc<HashMap<String, usize>>,
+    /// Capabilities that peers must advertise in their `Hello` message.
+    required_capabilities: Arc<Vec<Capability>>,
+    /// The set of trusted peers, shared with the [`PeersManager`](crate::peers::PeersManager).
+    trusted_peer_ids: TrustedPeerIds,
     /// The secret key used for authenticating sessions.
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
@@ -133,6 +148,11 @@ impl SessionManager {
             initial_internal_request_timeout: config.initial_internal_request_timeout,
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
             pending_session_timeout: config.pending_session_timeout,
+            handshake_timeouts: config.handshake_timeouts,
+            extra_protocols_trusted_only: config.extra_protocols_trusted_only,
+            max_message_sizes: Arc::new(config.max_message_sizes),
+            required_capabilities: Arc::new(config.required_capabilities),
+            trusted_peer_ids: Default::default(),
             secret_key,
             status,
             hello_message,
@@ -156,6 +176,26 @@ impl SessionManager {
         self.fork_filter.validate(fork_id).is_ok()
     }
 
+    /// Check whether the capabilities a peer advertises via discovery include an `eth` version
+    /// that is supported by the local node.
+    pub fn is_compatible_capabilities(&self, capabilities: &[Capability]) -> bool {
+        capabilities
+            .iter()
+            .filter(|cap| cap.is_eth())
+            .any(|cap| self.hello_message.protocols.iter().any(|protocol| &protocol.cap == cap))
+    }
+
+    /// Sets the set of trusted peers that additional `RLPx` subprotocols are restricted to, if
+    /// [`SessionsConfig::extra_protocols_trusted_only`] is enabled.
+    pub(crate) fn set_trusted_peer_ids(&mut self, trusted_peer_ids: TrustedPeerIds) {
+        self.trusted_peer_ids = trusted_peer_ids;
+    }
+
+    /// Returns the trusted peers additional `RLPx` subprotocols are restricted to, if any.
+    fn extra_protocols_trusted_peers(&self) -> Option<TrustedPeerIds> {
+        self.extra_protocols_trusted_only.then(|| self.trusted_peer_ids.clone())
+    }
+
     /// Returns the next unique [`SessionId`].
     fn next_id(&mut self) -> SessionId {
         let id = self.next_id;
@@ -183,6 +223,38 @@ impl SessionManager {
         self.extra_protocols.push(protocol)
     }
 
+    /// Adds an additional protocol handler to the `RLPx` sub-protocol list and disconnects all
+    /// active sessions that should be re-established to negotiate the protocol, see
+    /// [`RlpxSubProtocol::should_reconnect`](crate::protocol::RlpxSubProtocol::should_reconnect).
+    ///
+    /// Returns the peers that are disconnected.
+    pub(crate) fn add_rlpx_sub_protocol_and_reconnect(
+        &mut self,
+        protocol: impl IntoRlpxSubProtocol,
+    ) -> Vec<PeerId> {
+        let protocol = protocol.into_rlpx_sub_protocol();
+        let trusted_peers = self.extra_protocols_trusted_peers();
+
+        let mut peers = Vec::new();
+        for session in self.active_sessions.values() {
+            if trusted_peers.as_ref().is_some_and(|trusted| !trusted.contains(&session.remote_id)) {
+                // the protocol wouldn't be negotiated with the peer anyway
+                continue
+            }
+            if protocol.should_reconnect(
+                session.remote_addr,
+                session.remote_id,
+                &session.capabilities,
+            ) {
+                session.disconnect(Some(DisconnectReason::DisconnectRequested));
+                peers.push(session.remote_id);
+            }
+        }
+
+        self.extra_protocols.push(protocol);
+        peers
+    }
+
     /// Returns the number of currently pending connections.
     #[inline]
     pub(crate) fn num_pending_connections(&self) -> usize {
@@ -254,6 +326,10 @@ impl SessionManager {
                 status,
                 fork_filter,
                 extra_handlers,
+                self.extra_protocols_trusted_peers(),
+                self.handshake_timeouts,
+                Arc::clone(&self.max_message_sizes),
+                Arc::clone(&self.required_capabilities),
             ),
         ));
 
@@ -295,6 +371,10 @@ impl SessionManager {
                     status,
                     fork_filter,
                     extra_handlers,
+                    self.extra_protocols_trusted_peers(),
+                    self.handshake_timeouts,
+                    Arc::clone(&self.max_message_sizes),
+                    Arc::clone(&self.required_capabilities),
                 ),
             ));
 
@@ -463,6 +543,9 @@ impl SessionManager {
                 // negotiated version
                 let version = conn.version();
 
+                // per capability bandwidth counters of the connection
+                let bandwidth = conn.inner().bandwidth().clone();
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -500,6 +583,7 @@ impl SessionManager {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    bandwidth,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -738,6 +822,9 @@ pub enum PendingSessionHandshakeError {
     /// Thrown when the authentication timed out
     #[error("authentication timed out")]
     Timeout,
+    /// The peer doesn't advertise a capability that is required by the local node.
+    #[error("peer doesn't support required capability {0}")]
+    MissingCapability(Capability),
 }
 
 impl PendingSessionHandshakeError {
@@ -794,6 +881,10 @@ pub(crate) async fn start_pending_incoming_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    extra_protocols_trusted_peers: Option<TrustedPeerIds>,
+    handshake_timeouts: HandshakeTimeouts,
+    max_message_sizes: Arc<HashMap<String, usize>>,
+    required_capabilities: Arc<Vec<Capability>>,
 ) {
     authenticate(
         disconnect_rx,
@@ -807,6 +898,10 @@ pub(crate) async fn start_pending_incoming_session(
         status,
         fork_filter,
         extra_handlers,
+        extra_protocols_trusted_peers,
+        handshake_timeouts,
+        max_message_sizes,
+        required_capabilities,
     )
     .await
 }
@@ -825,6 +920,10 @@ async fn start_pending_outbound_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    extra_protocols_trusted_peers: Option<TrustedPeerIds>,
+    handshake_timeouts: HandshakeTimeouts,
+    max_message_sizes: Arc<HashMap<String, usize>>,
+    required_capabilities: Arc<Vec<Capability>>,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
         Ok(stream) => {
@@ -857,6 +956,10 @@ async fn start_pending_outbound_session(
         status,
         fork_filter,
         extra_handlers,
+        extra_protocols_trusted_peers,
+        handshake_timeouts,
+        max_message_sizes,
+        required_capabilities,
     )
     .await
 }
@@ -874,7 +977,11 @@ async fn authenticate(
     hello: HelloMessageWithProtocols,
     status: Status,
     fork_filter: ForkFilter,
-    extra_handlers: RlpxSubProtocolHandlers,
+    mut extra_handlers: RlpxSubProtocolHandlers,
+    extra_protocols_trusted_peers: Option<TrustedPeerIds>,
+    handshake_timeouts: HandshakeTimeouts,
+    max_message_sizes: Arc<HashMap<String, usize>>,
+    required_capabilities: Arc<Vec<Capability>>,
 ) {
     let local_addr = stream.local_addr().ok();
     let stream = match get_eciess_stream(stream, secret_key, direction).await {
@@ -892,6 +999,13 @@ async fn authenticate(
         }
     };
 
+    // additional protocols are neither advertised to nor accepted from untrusted peers
+    if let Some(trusted_peers) = extra_protocols_trusted_peers {
+        if !trusted_peers.contains(&stream.remote_id()) {
+            extra_handlers.clear();
+        }
+    }
+
     let unauthed = UnauthedP2PStream::new(stream);
 
     let auth = authenticate_stream(
@@ -904,6 +1018,9 @@ async fn authenticate(
         status,
         fork_filter,
         extra_handlers,
+        handshake_timeouts,
+        max_message_sizes,
+        required_capabilities,
     )
     .boxed();
 
@@ -956,22 +1073,39 @@ async fn authenticate_stream(
     mut status: Status,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
+    handshake_timeouts: HandshakeTimeouts,
+    max_message_sizes: Arc<HashMap<String, usize>>,
+    required_capabilities: Arc<Vec<Capability>>,
 ) -> PendingSessionEvent {
     // Add extra protocols to the hello message
     extra_handlers.retain(|handler| hello.try_add_protocol(handler.protocol()).is_ok());
 
     // conduct the p2p handshake and return the authenticated stream
-    let (p2p_stream, their_hello) = match stream.handshake(hello).await {
-        Ok(stream_res) => stream_res,
-        Err(err) => {
-            return PendingSessionEvent::Disconnected {
-                remote_addr,
-                session_id,
-                direction,
-                error: Some(PendingSessionHandshakeError::Eth(err.into())),
+    let (mut p2p_stream, their_hello) =
+        match stream.handshake_with_timeout(hello, handshake_timeouts.hello).await {
+            Ok(stream_res) => stream_res,
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err.into())),
+                }
             }
+        };
+
+    // Ensure the peer supports all capabilities we require
+    if let Some(missing) =
+        required_capabilities.iter().find(|cap| !their_hello.capabilities.contains(cap))
+    {
+        let _ = p2p_stream.disconnect(DisconnectReason::UselessPeer).await;
+        return PendingSessionEvent::Disconnected {
+            remote_addr,
+            session_id,
+            direction,
+            error: Some(PendingSessionHandshakeError::MissingCapability(missing.clone())),
         }
-    };
+    }
 
     // Ensure we negotiated mandatory eth protocol
     let eth_version = match p2p_stream.shared_capabilities().eth_version() {
@@ -992,7 +1126,10 @@ async fn authenticate_stream(
         // Before trying status handshake, set up the version to negotiated shared version
         status.set_eth_version(eth_version);
         let eth_unauthed = UnauthedEthStream::new(p2p_stream);
-        let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
+        let (eth_stream, their_status) = match eth_unauthed
+            .handshake_with_timeout(status, fork_filter, handshake_timeouts.status)
+            .await
+        {
             Ok(stream_res) => stream_res,
             Err(err) => {
                 return PendingSessionEvent::Disconnected {
@@ -1006,22 +1143,41 @@ async fn authenticate_stream(
         (eth_stream.into(), their_status)
     } else {
         // Multiplex the stream with the extra protocols
-        let (mut multiplex_stream, their_status) = RlpxProtocolMultiplexer::new(p2p_stream)
-            .into_eth_satellite_stream(status, fork_filter)
+        let (mut multiplex_stream, their_status) = match RlpxProtocolMultiplexer::new(p2p_stream)
+            .into_eth_satellite_stream_with_timeout(status, fork_filter, handshake_timeouts.status)
             .await
-            .unwrap();
+        {
+            Ok(stream_res) => stream_res,
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err)),
+                }
+            }
+        };
 
         // install additional handlers
         for handler in extra_handlers.into_iter() {
             let cap = handler.protocol().cap;
+            let priority = handler.priority();
             let remote_peer_id = their_hello.id;
             multiplex_stream
-                .install_protocol(&cap, move |conn| {
+                .install_protocol_with_priority(&cap, priority, move |conn| {
                     handler.into_connection(direction, remote_peer_id, conn)
                 })
                 .ok();
         }
 
+        if let Some(timeout) = handshake_timeouts.first_protocol_message {
+            multiplex_stream.set_first_message_timeout(timeout);
+        }
+
+        for (name, max_size) in max_message_sizes.iter() {
+            multiplex_stream.set_max_message_size(name, *max_size);
+        }
+
         (multiplex_stream.into(), their_status)
     };
 
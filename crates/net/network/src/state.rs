@@ -1,6 +1,7 @@
 //! Keeps track of the state of the network.
 
 use crate::{
+    broadcast::EthVersionFanout,
     cache::LruCache,
     discovery::{Discovery, DiscoveryEvent},
     fetch::{BlockResponseOutcome, FetchAction, StateFetcher},
@@ -15,7 +16,8 @@ use crate::{
 use rand::seq::SliceRandom;
 
 use reth_eth_wire::{
-    capability::Capabilities, BlockHashNumber, DisconnectReason, NewBlockHashes, Status,
+    capability::{Capabilities, Capability},
+    BlockHashNumber, DisconnectReason, EthVersion, NewBlockHashes, Status,
 };
 use reth_network_api::PeerKind;
 use reth_network_peers::PeerId;
@@ -67,6 +69,8 @@ pub struct NetworkState<C> {
     /// will then queue in the request and notify the fetcher once the result has been
     /// received.
     state_fetcher: StateFetcher,
+    /// How many peers of each version receive `NewBlock` messages.
+    block_broadcast_fanout: EthVersionFanout,
 }
 
 impl<C> NetworkState<C>
@@ -79,6 +83,7 @@ where
         discovery: Discovery,
         peers_manager: PeersManager,
         num_active_peers: Arc<AtomicUsize>,
+        block_broadcast_fanout: EthVersionFanout,
     ) -> Self {
         let state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers);
         Self {
@@ -88,6 +93,7 @@ where
             client,
             discovery,
             state_fetcher,
+            block_broadcast_fanout,
         }
     }
 
@@ -124,6 +130,7 @@ where
         &mut self,
         peer: PeerId,
         capabilities: Arc<Capabilities>,
+        version: EthVersion,
         status: Arc<Status>,
         request_tx: PeerRequestSender,
         timeout: Arc<AtomicU64>,
@@ -140,6 +147,7 @@ where
             ActivePeer {
                 best_hash: status.blockhash,
                 capabilities,
+                version,
                 request_tx,
                 pending_response: None,
                 blocks: LruCache::new(PEER_BLOCK_CACHE_LIMIT),
@@ -162,14 +170,16 @@ where
     /// > It then sends the block to a small fraction of connected peers (usually the square root of
     /// > the total number of peers) using the `NewBlock` message.
     ///
+    /// The fraction is determined per `eth` version by the configured [`EthVersionFanout`].
+    ///
     /// See also <https://github.com/ethereum/devp2p/blob/master/caps/eth.md>
     pub(crate) fn announce_new_block(&mut self, msg: NewBlockMessage) {
-        // send a `NewBlock` message to a fraction of the connected peers (square root of the total
-        // number of peers)
-        let num_propagate = (self.active_peers.len() as f64).sqrt() as u64 + 1;
+        // send a `NewBlock` message to a fraction of the connected peers of each version, by
+        // default the square root of their number
+        let mut budget =
+            self.block_broadcast_fanout.budget(self.active_peers.values().map(|peer| peer.version));
 
         let number = msg.block.block.header.number;
-        let mut count = 0;
 
         // Shuffle to propagate to a random sample of peers on every block announcement
         let mut peers: Vec<_> = self.active_peers.iter_mut().collect();
@@ -182,7 +192,7 @@ where
             }
 
             // Queue a `NewBlock` message for the peer
-            if count < num_propagate {
+            if budget.try_consume(peer.version) {
                 self.queued_messages
                     .push_back(StateAction::NewBlock { peer_id: *peer_id, block: msg.clone() });
 
@@ -193,12 +203,6 @@ where
 
                 // mark the block as seen by the peer
                 peer.blocks.insert(msg.hash);
-
-                count += 1;
-            }
-
-            if count >= num_propagate {
-                break
             }
         }
     }
@@ -303,6 +307,10 @@ where
                 self.queued_messages
                     .push_back(StateAction::DiscoveredEnrForkId { peer_id, fork_id });
             }
+            DiscoveryEvent::EnrCapabilities(peer_id, capabilities) => {
+                self.queued_messages
+                    .push_back(StateAction::DiscoveredEnrCapabilities { peer_id, capabilities });
+            }
         }
     }
 
@@ -477,6 +485,8 @@ pub(crate) struct ActivePeer {
     /// The capabilities of the remote peer.
     #[allow(dead_code)]
     pub(crate) capabilities: Arc<Capabilities>,
+    /// The negotiated `eth` version of the session.
+    pub(crate) version: EthVersion,
     /// A communication channel directly to the session task.
     pub(crate) request_tx: PeerRequestSender,
     /// The response receiver for a currently active request to that peer.
@@ -515,6 +525,12 @@ pub(crate) enum StateAction {
         /// The reported [`ForkId`] by this peer.
         fork_id: ForkId,
     },
+    /// Retrieved the `RLPx` capabilities the peer advertises in its ENR.
+    DiscoveredEnrCapabilities {
+        peer_id: PeerId,
+        /// The advertised capabilities of this peer.
+        capabilities: Vec<Capability>,
+    },
     /// A new node was found through the discovery, possibly with a `ForkId`
     DiscoveredNode { peer_id: PeerId, socket_addr: SocketAddr, fork_id: Option<ForkId> },
     /// A peer was added
@@ -555,6 +571,7 @@ mod tests {
             client: NoopProvider::default(),
             discovery: Discovery::noop(),
             state_fetcher: StateFetcher::new(handle, Default::default()),
+            block_broadcast_fanout: Default::default(),
         }
     }
 
@@ -576,6 +593,7 @@ mod tests {
         state.on_session_activated(
             peer_id,
             capabilities(),
+            EthVersion::Eth67,
             Arc::default(),
             peer_tx,
             Arc::new(AtomicU64::new(1)),
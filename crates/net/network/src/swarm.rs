@@ -131,6 +131,7 @@ where
                 self.state.on_session_activated(
                     peer_id,
                     capabilities.clone(),
+                    version,
                     status.clone(),
                     messages.clone(),
                     timeout,
@@ -264,6 +265,13 @@ where
                     self.state_mut().peers_mut().remove_peer(peer_id);
                 }
             }
+            StateAction::DiscoveredEnrCapabilities { peer_id, capabilities } => {
+                if self.sessions.is_compatible_capabilities(&capabilities) {
+                    self.state_mut().peers_mut().set_discovered_capabilities(peer_id, capabilities);
+                } else {
+                    self.state_mut().peers_mut().remove_peer(peer_id);
+                }
+            }
         }
         None
     }
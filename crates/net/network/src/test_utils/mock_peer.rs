@@ -0,0 +1,434 @@
+//! An in-process `RLPx` peer with scriptable capabilities for testing custom subprotocols.
+//!
+//! A [`MockPeer`] performs the full `RLPx` handshake (ECIES, `p2p` hello and `eth` status) with a
+//! node, announcing the configured [`Protocol`]s. Afterwards, raw messages can be exchanged on
+//! every shared capability, either manually via [`MockPeer::send`] and [`MockPeer::recv`], or by
+//! running a [`MockPeerScript`].
+
+use futures::{Stream, StreamExt};
+use reth_chainspec::ChainSpec;
+use reth_ecies::{stream::ECIESStream, ECIESError};
+use reth_eth_wire::{
+    capability::{Capability, RawCapabilityMessage, SharedCapabilities},
+    errors::EthStreamError,
+    multiplex::{ProtocolConnection, RlpxProtocolMultiplexer},
+    protocol::Protocol,
+    EthMessage, EthVersion, HelloMessage, HelloMessageWithProtocols, Status, UnauthedP2PStream,
+};
+use reth_network_peers::{pk2id, PeerId};
+use reth_primitives::{BufMut, Bytes, BytesMut, ForkFilter, Head};
+use secp256k1::{SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// The default time to wait for an expected message.
+const DEFAULT_EXPECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Configures a [`MockPeer`].
+#[derive(Debug, Clone)]
+pub struct MockPeerConfig {
+    secret_key: SecretKey,
+    protocols: Vec<Protocol>,
+    status: Status,
+    fork_filter: ForkFilter,
+    client_version: String,
+    expect_timeout: Duration,
+}
+
+impl MockPeerConfig {
+    /// Creates a new config with the given `eth` status and fork filter.
+    ///
+    /// By default, the peer only announces the `eth/68`, `eth/67` and `eth/66` capabilities.
+    pub fn new(status: Status, fork_filter: ForkFilter) -> Self {
+        Self {
+            secret_key: SecretKey::new(&mut rand::thread_rng()),
+            protocols: vec![
+                EthVersion::Eth68.into(),
+                EthVersion::Eth67.into(),
+                EthVersion::Eth66.into(),
+            ],
+            status,
+            fork_filter,
+            client_version: "reth-mock-peer".to_string(),
+            expect_timeout: DEFAULT_EXPECT_TIMEOUT,
+        }
+    }
+
+    /// Creates a new config with a status and fork filter derived from the given chain spec at
+    /// the genesis block.
+    ///
+    /// This matches the status of a node without any blocks, such as the peers of a
+    /// [`Testnet`](crate::test_utils::Testnet).
+    pub fn from_chain_spec(chain_spec: &ChainSpec) -> Self {
+        let head = Head {
+            hash: chain_spec.genesis_hash(),
+            number: 0,
+            timestamp: chain_spec.genesis.timestamp,
+            difficulty: chain_spec.genesis.difficulty,
+            total_difficulty: chain_spec.genesis.difficulty,
+        };
+        let status = Status::spec_builder(chain_spec, &head).build();
+        Self::new(status, chain_spec.fork_filter(head))
+    }
+
+    /// Sets the secret key of the peer.
+    pub const fn with_secret_key(mut self, secret_key: SecretKey) -> Self {
+        self.secret_key = secret_key;
+        self
+    }
+
+    /// Adds a protocol that will be announced in the `p2p` hello message.
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.protocols.push(protocol);
+        self
+    }
+
+    /// Replaces all announced protocols.
+    ///
+    /// Note: the handshake requires a shared `eth` capability.
+    pub fn with_protocols(mut self, protocols: impl IntoIterator<Item = Protocol>) -> Self {
+        self.protocols = protocols.into_iter().collect();
+        self
+    }
+
+    /// Sets the client version that is announced in the hello message.
+    pub fn with_client_version(mut self, client_version: impl Into<String>) -> Self {
+        self.client_version = client_version.into();
+        self
+    }
+
+    /// Sets how long to wait for expected messages, see [`MockPeerStep::Expect`].
+    pub const fn with_expect_timeout(mut self, timeout: Duration) -> Self {
+        self.expect_timeout = timeout;
+        self
+    }
+
+    /// Returns the [`PeerId`] of the mock peer.
+    pub fn peer_id(&self) -> PeerId {
+        pk2id(&self.secret_key.public_key(SECP256K1))
+    }
+
+    fn hello(&self) -> HelloMessageWithProtocols {
+        HelloMessage::builder(self.peer_id())
+            .client_version(self.client_version.clone())
+            .protocols(self.protocols.clone())
+            .port(0)
+            .build()
+    }
+}
+
+/// Errors that can occur while driving a [`MockPeer`].
+#[derive(Debug, thiserror::Error)]
+pub enum MockPeerError {
+    /// I/O error while establishing the connection.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The ECIES handshake failed.
+    #[error(transparent)]
+    Ecies(#[from] ECIESError),
+    /// The `p2p` or `eth` handshake failed.
+    #[error(transparent)]
+    Eth(#[from] EthStreamError),
+    /// The capability was not negotiated with the remote.
+    #[error("capability {0} is not shared with the remote")]
+    CapabilityNotShared(Capability),
+    /// The connection was closed.
+    #[error("connection closed")]
+    Disconnected,
+    /// No message was received in time.
+    #[error("timed out waiting for a {0} message")]
+    Timeout(Capability),
+    /// A different message than expected was received.
+    #[error("unexpected {capability} message, expected {expected:?}, got {got:?}")]
+    UnexpectedMessage {
+        /// The capability of the message.
+        capability: Capability,
+        /// The expected message.
+        expected: RawCapabilityMessage,
+        /// The received message.
+        got: RawCapabilityMessage,
+    },
+}
+
+/// A single step of a [`MockPeerScript`].
+#[derive(Debug, Clone)]
+pub enum MockPeerStep {
+    /// Sends the message on the given capability.
+    Send(Capability, RawCapabilityMessage),
+    /// Waits for the next message on the given capability and checks that it equals the given
+    /// message.
+    Expect(Capability, RawCapabilityMessage),
+    /// Waits for the next message on the given capability and checks that it has the given id,
+    /// ignoring the payload.
+    ExpectId(Capability, usize),
+    /// Sleeps for the given duration.
+    Sleep(Duration),
+}
+
+/// A sequence of [`MockPeerStep`]s that is executed in order by [`MockPeer::run_script`].
+#[derive(Debug, Clone, Default)]
+pub struct MockPeerScript {
+    steps: Vec<MockPeerStep>,
+}
+
+impl MockPeerScript {
+    /// Creates an empty script.
+    pub const fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Appends a step that sends a message.
+    pub fn send(mut self, cap: Capability, id: usize, payload: impl Into<Bytes>) -> Self {
+        self.steps
+            .push(MockPeerStep::Send(cap, RawCapabilityMessage { id, payload: payload.into() }));
+        self
+    }
+
+    /// Appends a step that expects a specific message.
+    pub fn expect(mut self, cap: Capability, id: usize, payload: impl Into<Bytes>) -> Self {
+        self.steps
+            .push(MockPeerStep::Expect(cap, RawCapabilityMessage { id, payload: payload.into() }));
+        self
+    }
+
+    /// Appends a step that expects a message with the given id.
+    pub fn expect_id(mut self, cap: Capability, id: usize) -> Self {
+        self.steps.push(MockPeerStep::ExpectId(cap, id));
+        self
+    }
+
+    /// Appends a step that sleeps for the given duration.
+    pub fn sleep(mut self, duration: Duration) -> Self {
+        self.steps.push(MockPeerStep::Sleep(duration));
+        self
+    }
+
+    /// Returns the steps of the script.
+    pub fn steps(&self) -> &[MockPeerStep] {
+        &self.steps
+    }
+}
+
+/// Channels of a single non-`eth` shared capability.
+struct CapabilityChannel {
+    /// Messages received from the remote.
+    incoming: ProtocolConnection,
+    /// Sends messages to the remote.
+    outgoing: mpsc::UnboundedSender<BytesMut>,
+}
+
+/// An in-process `RLPx` peer for testing custom subprotocols.
+///
+/// The connection is driven by a spawned task that is aborted when the [`MockPeer`] is dropped.
+pub struct MockPeer {
+    remote_id: PeerId,
+    remote_status: Status,
+    shared_capabilities: SharedCapabilities,
+    channels: HashMap<Capability, CapabilityChannel>,
+    eth_messages: UnboundedReceiverStream<EthMessage>,
+    expect_timeout: Duration,
+    driver: JoinHandle<()>,
+}
+
+impl MockPeer {
+    /// Connects to the node listening on `remote_addr` with the given [`PeerId`].
+    pub async fn connect(
+        config: MockPeerConfig,
+        remote_addr: SocketAddr,
+        remote_id: PeerId,
+    ) -> Result<Self, MockPeerError> {
+        let stream = TcpStream::connect(remote_addr).await?;
+        let stream = ECIESStream::connect(stream, config.secret_key, remote_id).await?;
+        Self::handshake(config, stream).await
+    }
+
+    /// Accepts the next incoming connection on the given listener, e.g. after adding the mock
+    /// peer to the node's peer set.
+    pub async fn accept(
+        config: MockPeerConfig,
+        listener: &TcpListener,
+    ) -> Result<Self, MockPeerError> {
+        let (stream, _) = listener.accept().await?;
+        let stream = ECIESStream::incoming(stream, config.secret_key).await?;
+        Self::handshake(config, stream).await
+    }
+
+    async fn handshake(
+        config: MockPeerConfig,
+        stream: ECIESStream<TcpStream>,
+    ) -> Result<Self, MockPeerError> {
+        let MockPeerConfig { mut status, fork_filter, expect_timeout, .. } = config.clone();
+        let (p2p_stream, their_hello) = UnauthedP2PStream::new(stream)
+            .handshake(config.hello())
+            .await
+            .map_err(EthStreamError::from)?;
+
+        let eth_version =
+            p2p_stream.shared_capabilities().eth_version().map_err(EthStreamError::from)?;
+        status.set_eth_version(eth_version);
+        let shared_capabilities = p2p_stream.shared_capabilities().clone();
+
+        let (mut conn, remote_status) = RlpxProtocolMultiplexer::new(p2p_stream)
+            .into_eth_satellite_stream(status, fork_filter)
+            .await?;
+
+        let mut channels = HashMap::new();
+        for shared in shared_capabilities.iter_caps().filter(|cap| !cap.is_eth()) {
+            let cap = shared.capability().into_owned();
+            let (outgoing, rx) = mpsc::unbounded_channel();
+            let mut incoming = None;
+            conn.install_protocol(&cap, |conn| {
+                incoming = Some(conn);
+                UnboundedReceiverStream::new(rx)
+            })
+            .map_err(|_| MockPeerError::CapabilityNotShared(cap.clone()))?;
+            let incoming = incoming.expect("protocol installed");
+            channels.insert(cap, CapabilityChannel { incoming, outgoing });
+        }
+
+        // drives the connection and forwards all received `eth` messages
+        let (eth_tx, eth_rx) = mpsc::unbounded_channel();
+        let driver = tokio::spawn(async move {
+            while let Some(Ok(msg)) = conn.next().await {
+                let _ = eth_tx.send(msg);
+            }
+        });
+
+        Ok(Self {
+            remote_id: their_hello.id,
+            remote_status,
+            shared_capabilities,
+            channels,
+            eth_messages: UnboundedReceiverStream::new(eth_rx),
+            expect_timeout,
+            driver,
+        })
+    }
+
+    /// Returns the [`PeerId`] of the remote node.
+    pub const fn remote_id(&self) -> PeerId {
+        self.remote_id
+    }
+
+    /// Returns the `eth` status the remote node sent.
+    pub const fn remote_status(&self) -> &Status {
+        &self.remote_status
+    }
+
+    /// Returns the capabilities negotiated with the remote node.
+    pub const fn shared_capabilities(&self) -> &SharedCapabilities {
+        &self.shared_capabilities
+    }
+
+    /// Sends a raw message on the given capability.
+    ///
+    /// The message id is relative to the capability's message id offset.
+    pub fn send(&self, cap: &Capability, msg: RawCapabilityMessage) -> Result<(), MockPeerError> {
+        let channel = self.channel(cap)?;
+        let mut buf = BytesMut::with_capacity(1 + msg.payload.len());
+        buf.put_u8(msg.id as u8);
+        buf.put_slice(&msg.payload);
+        channel.outgoing.send(buf).map_err(|_| MockPeerError::Disconnected)
+    }
+
+    /// Receives the next raw message on the given capability.
+    ///
+    /// Returns `None` if the connection was closed.
+    pub async fn recv(
+        &mut self,
+        cap: &Capability,
+    ) -> Result<Option<RawCapabilityMessage>, MockPeerError> {
+        let channel = self
+            .channels
+            .get_mut(cap)
+            .ok_or_else(|| MockPeerError::CapabilityNotShared(cap.clone()))?;
+        let Some(mut msg) = channel.incoming.next().await else { return Ok(None) };
+        if msg.is_empty() {
+            return Ok(None)
+        }
+        let id = msg.split_to(1)[0] as usize;
+        Ok(Some(RawCapabilityMessage { id, payload: msg.freeze() }))
+    }
+
+    /// Runs the script, stopping at the first step that fails.
+    pub async fn run_script(&mut self, script: &MockPeerScript) -> Result<(), MockPeerError> {
+        for step in script.steps() {
+            match step {
+                MockPeerStep::Send(cap, msg) => self.send(cap, msg.clone())?,
+                MockPeerStep::Expect(cap, expected) => {
+                    let got = self.expect_next(cap).await?;
+                    if &got != expected {
+                        return Err(MockPeerError::UnexpectedMessage {
+                            capability: cap.clone(),
+                            expected: expected.clone(),
+                            got,
+                        })
+                    }
+                }
+                MockPeerStep::ExpectId(cap, id) => {
+                    let got = self.expect_next(cap).await?;
+                    if got.id != *id {
+                        return Err(MockPeerError::UnexpectedMessage {
+                            capability: cap.clone(),
+                            expected: RawCapabilityMessage { id: *id, payload: Bytes::new() },
+                            got,
+                        })
+                    }
+                }
+                MockPeerStep::Sleep(duration) => tokio::time::sleep(*duration).await,
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for the next message on the capability with the configured timeout.
+    async fn expect_next(
+        &mut self,
+        cap: &Capability,
+    ) -> Result<RawCapabilityMessage, MockPeerError> {
+        match tokio::time::timeout(self.expect_timeout, self.recv(cap)).await {
+            Ok(res) => res?.ok_or(MockPeerError::Disconnected),
+            Err(_) => Err(MockPeerError::Timeout(cap.clone())),
+        }
+    }
+
+    fn channel(&self, cap: &Capability) -> Result<&CapabilityChannel, MockPeerError> {
+        self.channels.get(cap).ok_or_else(|| MockPeerError::CapabilityNotShared(cap.clone()))
+    }
+}
+
+impl Drop for MockPeer {
+    fn drop(&mut self) {
+        self.driver.abort();
+    }
+}
+
+impl fmt::Debug for MockPeer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockPeer")
+            .field("remote_id", &self.remote_id)
+            .field("shared_capabilities", &self.shared_capabilities)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Yields all `eth` messages received from the remote.
+impl Stream for MockPeer {
+    type Item = EthMessage;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.eth_messages.poll_next_unpin(cx)
+    }
+}
@@ -1,10 +1,12 @@
 //! Common helpers for network testing.
 
 mod init;
+mod mock_peer;
 mod testnet;
 
 pub use init::{
     enr_to_peer_id, unused_port, unused_tcp_addr, unused_tcp_and_udp_port, unused_tcp_udp,
     unused_udp_addr, unused_udp_port, GETH_TIMEOUT,
 };
+pub use mock_peer::{MockPeer, MockPeerConfig, MockPeerError, MockPeerScript, MockPeerStep};
 pub use testnet::{NetworkEventStream, Peer, PeerConfig, PeerHandle, Testnet};
@@ -0,0 +1,88 @@
+//! Bandwidth budgets for serving blob transactions.
+
+use super::config::BlobBandwidthConfig;
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The window that the budgets of [`BlobBandwidthConfig`] apply to.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Tracks the bytes of blob transactions served in the current window, in total and per peer.
+#[derive(Debug)]
+pub(crate) struct BlobBandwidthBudget {
+    config: BlobBandwidthConfig,
+    /// When the current window started.
+    window_start: Instant,
+    /// Bytes served to all peers in the current window.
+    served: usize,
+    /// Bytes served to each peer in the current window.
+    served_by_peer: HashMap<PeerId, usize>,
+}
+
+impl BlobBandwidthBudget {
+    pub(crate) fn new(config: BlobBandwidthConfig) -> Self {
+        Self { config, window_start: Instant::now(), served: 0, served_by_peer: HashMap::new() }
+    }
+
+    /// Returns `true` if a blob transaction of `size` bytes can be served to the peer at `now`,
+    /// and consumes the bytes from the budgets.
+    ///
+    /// The budgets are soft: a transaction that is larger than a budget on its own is still
+    /// served, if nothing was served in the window yet.
+    pub(crate) fn try_consume(&mut self, peer_id: PeerId, size: usize, now: Instant) -> bool {
+        if now.saturating_duration_since(self.window_start) >= WINDOW {
+            self.window_start = now;
+            self.served = 0;
+            self.served_by_peer.clear();
+        }
+
+        let served_to_peer = self.served_by_peer.get(&peer_id).copied().unwrap_or_default();
+        if exceeds(self.config.max_bytes_per_sec, self.served, size) ||
+            exceeds(self.config.max_bytes_per_peer_per_sec, served_to_peer, size)
+        {
+            return false
+        }
+
+        self.served += size;
+        *self.served_by_peer.entry(peer_id).or_default() += size;
+        true
+    }
+}
+
+/// Returns `true` if serving `size` more bytes on top of `served` exceeds the budget.
+fn exceeds(budget: Option<usize>, served: usize, size: usize) -> bool {
+    budget.is_some_and(|budget| served > 0 && served + size > budget)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blob_bandwidth_budgets() {
+        let config = BlobBandwidthConfig::default()
+            .with_max_bytes_per_sec(Some(300))
+            .with_max_bytes_per_peer_per_sec(Some(200));
+        let mut budget = BlobBandwidthBudget::new(config);
+        let (a, b, c) = (PeerId::random(), PeerId::random(), PeerId::random());
+        let now = Instant::now();
+
+        assert!(budget.try_consume(a, 150, now));
+        // exceeds the budget of the peer
+        assert!(!budget.try_consume(a, 100, now));
+        assert!(budget.try_consume(b, 100, now));
+        // exceeds the global budget
+        assert!(!budget.try_consume(c, 100, now));
+        assert!(budget.try_consume(c, 50, now));
+
+        // the budgets are restored in the next window
+        let later = now + WINDOW;
+        assert!(budget.try_consume(a, 200, later));
+        // a single transaction larger than the budget is served if nothing was served yet
+        assert!(budget.try_consume(b, 400, later + WINDOW));
+        assert!(!budget.try_consume(c, 1, later + WINDOW));
+    }
+}
@@ -2,7 +2,9 @@ use super::{
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
+use crate::broadcast::EthVersionFanout;
 use derive_more::Constructor;
+use std::time::Duration;
 
 /// Configuration for managing transactions within the network.
 #[derive(Debug, Default, Clone)]
@@ -10,6 +12,60 @@ use derive_more::Constructor;
 pub struct TransactionsManagerConfig {
     /// Configuration for fetching transactions.
     pub transaction_fetcher_config: TransactionFetcherConfig,
+    /// How many peers of each `eth` version receive full transactions instead of hashes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub full_transactions_fanout: EthVersionFanout,
+    /// How often the pending local transactions are announced again to all peers, until they are
+    /// included or leave the pool. Disabled if `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub local_transactions_rebroadcast_interval: Option<Duration>,
+    /// Budgets for serving blob transactions to peers.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub blob_bandwidth: BlobBandwidthConfig,
+}
+
+impl TransactionsManagerConfig {
+    /// Creates a new config with the given [`TransactionFetcherConfig`] and defaults for the
+    /// remaining settings.
+    pub fn new(transaction_fetcher_config: TransactionFetcherConfig) -> Self {
+        Self { transaction_fetcher_config, ..Default::default() }
+    }
+
+    /// Sets how many peers of each `eth` version receive full transactions.
+    pub const fn with_full_transactions_fanout(mut self, fanout: EthVersionFanout) -> Self {
+        self.full_transactions_fanout = fanout;
+        self
+    }
+}
+
+/// Budgets for the bytes of EIP-4844 transactions, including their sidecars, that are served to
+/// peers in [`PooledTransactions`](reth_eth_wire::PooledTransactions) responses.
+///
+/// Blob transactions are only ever announced and then fetched by peers, so serving them is what
+/// blob gossip costs in upload. Blob transactions that exceed a budget are left out of the
+/// response, and the peer can fetch them again later or from another peer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BlobBandwidthConfig {
+    /// Max bytes of blob transactions served to all peers per second. Unlimited if `None`.
+    pub max_bytes_per_sec: Option<usize>,
+    /// Max bytes of blob transactions served to a single peer per second. Unlimited if `None`.
+    pub max_bytes_per_peer_per_sec: Option<usize>,
+}
+
+impl BlobBandwidthConfig {
+    /// Sets the max bytes of blob transactions served to all peers per second.
+    pub const fn with_max_bytes_per_sec(mut self, max: Option<usize>) -> Self {
+        self.max_bytes_per_sec = max;
+        self
+    }
+
+    /// Sets the max bytes of blob transactions served to a single peer per second.
+    pub const fn with_max_bytes_per_peer_per_sec(mut self, max: Option<usize>) -> Self {
+        self.max_bytes_per_peer_per_sec = max;
+        self
+    }
 }
 
 /// Configuration for fetching transactions.
@@ -1,6 +1,7 @@
 //! Transactions management for the p2p network.
 
 use crate::{
+    broadcast::EthVersionFanout,
     budget::{
         DEFAULT_BUDGET_TRY_DRAIN_NETWORK_TRANSACTION_EVENTS,
         DEFAULT_BUDGET_TRY_DRAIN_PENDING_POOL_IMPORTS, DEFAULT_BUDGET_TRY_DRAIN_POOL_IMPORTS,
@@ -14,6 +15,7 @@ use crate::{
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
     NetworkEvents, NetworkHandle,
 };
+use alloy_rlp::Encodable;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
     EthVersion, GetPooledTransactions, HandleMempoolData, HandleVersionedMempoolData,
@@ -29,6 +31,7 @@ use reth_network_p2p::{
 use reth_network_peers::PeerId;
 use reth_primitives::{
     FromRecoveredPooledTransaction, PooledTransactionsElement, TransactionSigned, TxHash, B256,
+    EIP4844_TX_TYPE_ID,
 };
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
@@ -46,10 +49,14 @@ use std::{
     task::{Context, Poll},
     time::{Duration, Instant},
 };
-use tokio::sync::{mpsc, oneshot, oneshot::error::RecvError};
+use tokio::{
+    sync::{mpsc, oneshot, oneshot::error::RecvError},
+    time::{Interval, MissedTickBehavior},
+};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
 use tracing::{debug, trace};
 
+mod blob_budget;
 /// Aggregation on configurable parameters for [`TransactionsManager`].
 pub mod config;
 /// Default and spec'd bounds.
@@ -57,8 +64,9 @@ pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
 pub mod validation;
-pub use config::{TransactionFetcherConfig, TransactionsManagerConfig};
+pub use config::{BlobBandwidthConfig, TransactionFetcherConfig, TransactionsManagerConfig};
 
+use blob_budget::BlobBandwidthBudget;
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
 pub use validation::*;
@@ -244,6 +252,13 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// How many peers of each version receive full transactions.
+    full_transactions_fanout: EthVersionFanout,
+    /// Interval at which the pending local transactions are announced again, see
+    /// [`TransactionsManagerConfig::local_transactions_rebroadcast_interval`].
+    local_rebroadcast_interval: Option<Interval>,
+    /// Budgets for serving blob transactions to peers.
+    blob_bandwidth: BlobBandwidthBudget,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -270,6 +285,13 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
         // over the network
         let pending = pool.pending_transactions_listener();
         let pending_pool_imports_info = PendingPoolImportsInfo::default();
+        let local_rebroadcast_interval =
+            transactions_manager_config.local_transactions_rebroadcast_interval.map(|period| {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                interval
+            });
         let metrics = TransactionsManagerMetrics::default();
         metrics
             .capacity_pending_pool_imports
@@ -294,6 +316,9 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            full_transactions_fanout: transactions_manager_config.full_transactions_fanout,
+            local_rebroadcast_interval,
+            blob_bandwidth: BlobBandwidthBudget::new(transactions_manager_config.blob_bandwidth),
             metrics,
         }
     }
@@ -353,11 +378,34 @@ where
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
+            let limit =
+                self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response;
+
+            // blob transactions are only served within the blob bandwidth budgets, which are
+            // checked before their sidecars are loaded from the blob store. The transactions are
+            // selected in the order the response is filled up to its soft limit.
+            let now = Instant::now();
+            let mut size = 0;
+            let mut hashes = Vec::with_capacity(request.0.len());
+            for tx in self.pool.get_all(request.0) {
+                if size > limit {
+                    break
+                }
+                let tx_size = tx.encoded_length();
+                if tx.is_eip4844() {
+                    if !self.blob_bandwidth.try_consume(peer_id, tx_size, now) {
+                        self.metrics.throttled_blob_transactions.increment(1);
+                        continue
+                    }
+                    self.metrics.served_blob_bytes.increment(tx_size as u64);
+                }
+                size += tx_size;
+                hashes.push(*tx.hash());
+            }
+
             let transactions = self.pool.get_pooled_transaction_elements(
-                request.0,
-                GetPooledTransactionLimit::ResponseSizeSoftLimit(
-                    self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
-                ),
+                hashes,
+                GetPooledTransactionLimit::ResponseSizeSoftLimit(limit),
             );
 
             // we sent a response at which point we assume that the peer is aware of the
@@ -416,12 +464,13 @@ where
             return propagated
         }
 
-        // send full transactions to a fraction of the connected peers (square root of the total
-        // number of connected peers)
-        let max_num_full = (self.peers.len() as f64).sqrt() as usize + 1;
+        // send full transactions to a fraction of the connected peers of each version, by default
+        // the square root of their number
+        let mut full_budget =
+            self.full_transactions_fanout.budget(self.peers.values().map(|peer| peer.version));
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
-        for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
+        for (peer_id, peer) in &mut self.peers {
             // filter all transactions unknown to the peer
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = FullTransactionsBuilder::default();
@@ -451,7 +500,7 @@ where
             if !new_pooled_hashes.is_empty() {
                 // determine whether to send full tx objects or hashes. If there are no full
                 // transactions, try to send hashes.
-                if peer_idx > max_num_full || full_transactions.is_empty() {
+                if full_transactions.is_empty() || !full_budget.try_consume(peer.version) {
                     // enforce tx soft limit per message for the (unlikely) event the number of
                     // hashes exceeds it
                     new_pooled_hashes.truncate(
@@ -465,6 +514,7 @@ where
                     trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
 
                     // send hashes of transactions
+                    record_announced_blob_bytes(&self.metrics, &new_pooled_hashes);
                     self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
                 } else {
                     let new_full_transactions = full_transactions.build();
@@ -491,6 +541,51 @@ where
         propagated
     }
 
+    /// Announces the pending local transactions that entered the pool at least `min_age` ago to
+    /// all connected peers, including the peers that have already seen them.
+    ///
+    /// Peers may drop a transaction after it was propagated to them, for example if their pool is
+    /// full, so a local transaction is announced again until it's included or leaves the pool.
+    fn rebroadcast_local_transactions(&mut self, min_age: Duration) {
+        if self.network.is_initially_syncing() || self.network.tx_gossip_disabled() {
+            return
+        }
+
+        let to_rebroadcast = self
+            .pool
+            .pending_transactions()
+            .into_iter()
+            .filter(|tx| tx.origin.is_local() && tx.propagate && tx.timestamp.elapsed() >= min_age)
+            .map(PropagateTransaction::new)
+            .collect::<Vec<_>>();
+        if to_rebroadcast.is_empty() {
+            return
+        }
+
+        trace!(target: "net::tx", num_txs=?to_rebroadcast.len(), "Rebroadcasting local transactions");
+
+        let mut propagated = PropagatedTransactions::default();
+        for (peer_id, peer) in &mut self.peers {
+            let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
+            for tx in &to_rebroadcast {
+                peer.seen_transactions.insert(tx.hash());
+                hashes.push(tx);
+            }
+            let mut new_pooled_hashes = hashes.build();
+            new_pooled_hashes
+                .truncate(SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE);
+
+            for hash in new_pooled_hashes.iter_hashes().copied() {
+                propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
+            }
+            record_announced_blob_bytes(&self.metrics, &new_pooled_hashes);
+            self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+        }
+
+        self.metrics.propagated_transactions.increment(propagated.0.len() as u64);
+        self.pool.on_propagated(propagated);
+    }
+
     /// Propagate the full transactions to a specific peer
     ///
     /// Returns the propagated transactions
@@ -579,6 +674,7 @@ where
             }
 
             // send hashes of transactions
+            record_announced_blob_bytes(&self.metrics, &new_pooled_hashes);
             self.network.send_transactions_hashes(peer_id, new_pooled_hashes);
 
             // Update propagated transactions metrics
@@ -815,7 +911,9 @@ where
                 // ensure we didn't receive any blob transactions as these are disallowed to be
                 // broadcasted in full
 
-                let has_blob_txs = msg.has_eip4844();
+                let blob_bytes: usize =
+                    msg.0.iter().filter(|tx| tx.is_eip4844()).map(Encodable::length).sum();
+                let has_blob_txs = blob_bytes > 0;
 
                 let non_blob_txs = msg
                     .0
@@ -828,6 +926,7 @@ where
 
                 if has_blob_txs {
                     debug!(target: "net::tx", ?peer_id, "received bad full blob transaction broadcast");
+                    self.metrics.pushed_blob_bytes.increment(blob_bytes as u64);
                     self.report_peer_bad_transactions(peer_id);
                 }
             }
@@ -1087,6 +1186,9 @@ where
     fn on_fetch_event(&mut self, fetch_event: FetchEvent) {
         match fetch_event {
             FetchEvent::TransactionsFetched { peer_id, transactions } => {
+                let blob_bytes: usize =
+                    transactions.iter().filter(|tx| tx.is_eip4844()).map(Encodable::length).sum();
+                self.metrics.fetched_blob_bytes.increment(blob_bytes as u64);
                 self.import_transactions(peer_id, transactions, TransactionSource::Response);
             }
             FetchEvent::FetchError { peer_id, error } => {
@@ -1336,6 +1438,14 @@ where
             |cmd| this.on_command(cmd)
         );
 
+        // Announce the pending local transactions again.
+        if let Some(interval) = &mut this.local_rebroadcast_interval {
+            if interval.poll_tick(cx).is_ready() {
+                let min_age = interval.period();
+                this.rebroadcast_local_transactions(min_age);
+            }
+        }
+
         this.transaction_fetcher.update_metrics();
 
         // all channels are fully drained and import futures pending
@@ -1357,6 +1467,25 @@ where
     }
 }
 
+/// Records the size of the blob transactions in an announcement to a peer.
+///
+/// Only `eth/68` announcements carry the sizes of the announced transactions.
+fn record_announced_blob_bytes(
+    metrics: &TransactionsManagerMetrics,
+    announcement: &NewPooledTransactionHashes,
+) {
+    if let NewPooledTransactionHashes::Eth68(msg) = announcement {
+        let blob_bytes: usize = msg
+            .types
+            .iter()
+            .zip(&msg.sizes)
+            .filter(|(ty, _)| **ty == EIP4844_TX_TYPE_ID)
+            .map(|(_, size)| size)
+            .sum();
+        metrics.announced_blob_bytes.increment(blob_bytes as u64);
+    }
+}
+
 /// A transaction that's about to be propagated to multiple peers.
 struct PropagateTransaction {
     size: usize,
@@ -1617,7 +1746,10 @@ mod tests {
     };
     use reth_primitives::hex;
     use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionEvent,
+    };
     use secp256k1::SecretKey;
     use std::{fmt, future::poll_fn, hash};
     use tests::fetcher::TxFetchMetadata;
@@ -2132,4 +2264,30 @@ mod tests {
         assert!(tx_fetcher.hashes_pending_fetch.is_empty());
         assert_eq!(tx_fetcher.active_peers.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_rebroadcast_local_transactions() {
+        let mut tx_manager = new_tx_manager().await;
+        let peer_id = PeerId::new([1; 64]);
+        let (mut peer, _to_mock_session_rx) = new_mock_session(peer_id, EthVersion::Eth68);
+
+        // the peer has already seen the transaction
+        let tx = MockTransaction::eip1559();
+        peer.seen_transactions.insert(tx.get_hash());
+        tx_manager.peers.insert(peer_id, peer);
+
+        let mut events = tx_manager
+            .pool
+            .add_transaction_and_subscribe(reth_transaction_pool::TransactionOrigin::Local, tx)
+            .await
+            .unwrap();
+        assert!(matches!(events.next().await, Some(TransactionEvent::Pending)));
+
+        tx_manager.rebroadcast_local_transactions(Duration::ZERO);
+
+        let Some(TransactionEvent::Propagated(propagated)) = events.next().await else {
+            panic!("local transaction was not rebroadcast")
+        };
+        assert_eq!(*propagated, vec![PropagateKind::Hash(peer_id)]);
+    }
 }
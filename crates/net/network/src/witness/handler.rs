@@ -0,0 +1,346 @@
+//! Protocol and connection handlers of the `wit` subprotocol.
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    witness::message::{GetWitness, Witness, WitnessMessage},
+};
+use alloy_rlp::Encodable;
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, message::RequestPair, multiplex::ProtocolConnection,
+    protocol::Protocol, MAX_MESSAGE_SIZE,
+};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::error::{RequestError, RequestResult};
+use reth_primitives::{Bytes, BytesMut, B256};
+use reth_provider::WitnessReader;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace};
+
+/// The default maximum size of a witness served to a peer.
+///
+/// Witnesses that are larger aren't served, since a partial witness is of no use to the peer.
+pub const DEFAULT_MAX_WITNESS_RESPONSE_SIZE: usize = MAX_MESSAGE_SIZE;
+
+/// The maximum number of requests of a single peer that are served concurrently.
+///
+/// Serving a witness re-executes the block, further messages of the peer are only read once one of
+/// these requests was answered.
+const MAX_CONCURRENT_WITNESS_REQUESTS: usize = 2;
+
+/// Events emitted by the [`WitnessProtocolHandler`].
+#[derive(Debug)]
+pub enum WitnessProtocolEvent {
+    /// A `wit` connection with a peer was established.
+    Established {
+        /// The direction of the connection.
+        direction: Direction,
+        /// The peer the connection was established with.
+        peer_id: PeerId,
+        /// Sends commands to the connection.
+        to_connection: mpsc::UnboundedSender<WitnessCommand>,
+    },
+}
+
+/// Commands that can be sent to a [`WitnessConnection`].
+#[derive(Debug)]
+pub enum WitnessCommand {
+    /// Requests the execution witness of a block from the peer.
+    GetWitness {
+        /// The hash of the block.
+        block_hash: B256,
+        /// The response channel.
+        response: oneshot::Sender<RequestResult<Vec<Bytes>>>,
+    },
+}
+
+/// Reads the witness requested by the peer and encodes the response.
+///
+/// Witnesses larger than `max_response_size` are answered with an empty witness.
+fn serve_witness<P: WitnessReader>(
+    provider: &P,
+    max_response_size: usize,
+    peer_id: PeerId,
+    request: RequestPair<GetWitness>,
+) -> BytesMut {
+    let RequestPair { request_id, message: GetWitness { block_hash } } = request;
+    let mut witness = match provider.witness_by_block_hash(block_hash) {
+        Ok(witness) => witness.unwrap_or_default(),
+        Err(err) => {
+            debug!(target: "net::witness", %err, %block_hash, %peer_id, "failed to read witness");
+            Vec::new()
+        }
+    };
+    if witness.length() > max_response_size {
+        debug!(target: "net::witness", %block_hash, size=witness.length(), %peer_id, "witness exceeds the response size limit");
+        witness.clear();
+    }
+    trace!(target: "net::witness", %block_hash, items=witness.len(), %peer_id, "serving witness");
+    WitnessMessage::Witness(RequestPair { request_id, message: Witness(witness) }).encoded()
+}
+
+/// The [`ProtocolHandler`] of the `wit/1` protocol.
+///
+/// Serves execution witnesses from the given [`WitnessReader`]. The protocol is only announced to
+/// peers if the provider has witness data available, or if witnesses should be requested from
+/// peers, see [`WitnessProtocolHandler::with_events`].
+pub struct WitnessProtocolHandler<P> {
+    provider: Arc<P>,
+    max_response_size: usize,
+    events: Option<mpsc::UnboundedSender<WitnessProtocolEvent>>,
+}
+
+impl<P> WitnessProtocolHandler<P> {
+    /// Creates a new handler that serves witnesses from the given provider.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            max_response_size: DEFAULT_MAX_WITNESS_RESPONSE_SIZE,
+            events: None,
+        }
+    }
+
+    /// Sets the maximum size of a witness served to a peer, see
+    /// [`DEFAULT_MAX_WITNESS_RESPONSE_SIZE`].
+    pub const fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+        self.max_response_size = max_response_size;
+        self
+    }
+
+    /// Sets the channel [`WitnessProtocolEvent`]s are sent to.
+    ///
+    /// This is required to request witnesses from peers.
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<WitnessProtocolEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+}
+
+impl<P: WitnessReader> WitnessProtocolHandler<P> {
+    fn connection_handler(&self) -> Option<WitnessConnectionHandler<P>> {
+        let announce = self.provider.has_witnesses() || self.events.is_some();
+        announce.then(|| WitnessConnectionHandler {
+            provider: self.provider.clone(),
+            max_response_size: self.max_response_size,
+            events: self.events.clone(),
+        })
+    }
+}
+
+impl<P> fmt::Debug for WitnessProtocolHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WitnessProtocolHandler")
+            .field("max_response_size", &self.max_response_size)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ProtocolHandler for WitnessProtocolHandler<P>
+where
+    P: WitnessReader + 'static,
+{
+    type ConnectionHandler = WitnessConnectionHandler<P>;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        self.connection_handler()
+    }
+}
+
+/// The [`ConnectionHandler`] of the `wit/1` protocol.
+pub struct WitnessConnectionHandler<P> {
+    provider: Arc<P>,
+    max_response_size: usize,
+    events: Option<mpsc::UnboundedSender<WitnessProtocolEvent>>,
+}
+
+impl<P> fmt::Debug for WitnessConnectionHandler<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WitnessConnectionHandler")
+            .field("max_response_size", &self.max_response_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ConnectionHandler for WitnessConnectionHandler<P>
+where
+    P: WitnessReader + 'static,
+{
+    type Connection = WitnessConnection<P>;
+
+    fn protocol(&self) -> Protocol {
+        WitnessMessage::protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        if let Some(events) = &self.events {
+            events
+                .send(WitnessProtocolEvent::Established { direction, peer_id, to_connection: tx })
+                .ok();
+        }
+        WitnessConnection {
+            provider: self.provider,
+            max_response_size: self.max_response_size,
+            peer_id,
+            conn,
+            commands: UnboundedReceiverStream::new(rx),
+            next_request_id: 0,
+            inflight_requests: HashMap::new(),
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// An established `wit/1` connection with a peer.
+///
+/// Answers witness requests of the peer and forwards requests received via [`WitnessCommand`]s.
+/// The connection is closed if the peer sends an invalid message.
+///
+/// Requests of the peer are served on the blocking pool, because they read from the database.
+pub struct WitnessConnection<P> {
+    provider: Arc<P>,
+    max_response_size: usize,
+    peer_id: PeerId,
+    conn: ProtocolConnection,
+    commands: UnboundedReceiverStream<WitnessCommand>,
+    next_request_id: u64,
+    inflight_requests: HashMap<u64, oneshot::Sender<RequestResult<Vec<Bytes>>>>,
+    /// Responses to requests of the peer that are being served.
+    pending_responses: FuturesUnordered<JoinHandle<BytesMut>>,
+}
+
+impl<P> fmt::Debug for WitnessConnection<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WitnessConnection")
+            .field("peer_id", &self.peer_id)
+            .field("max_response_size", &self.max_response_size)
+            .field("next_request_id", &self.next_request_id)
+            .field("inflight_requests", &self.inflight_requests)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> WitnessConnection<P>
+where
+    P: WitnessReader + 'static,
+{
+    fn on_command(&mut self, cmd: WitnessCommand) -> BytesMut {
+        match cmd {
+            WitnessCommand::GetWitness { block_hash, response } => {
+                let request_id = self.next_request_id;
+                self.next_request_id += 1;
+                self.inflight_requests.insert(request_id, response);
+                WitnessMessage::GetWitness(RequestPair {
+                    request_id,
+                    message: GetWitness { block_hash },
+                })
+                .encoded()
+            }
+        }
+    }
+
+    fn on_get_witness(&mut self, request: RequestPair<GetWitness>) {
+        let provider = self.provider.clone();
+        let (max_response_size, peer_id) = (self.max_response_size, self.peer_id);
+        self.pending_responses.push(tokio::task::spawn_blocking(move || {
+            serve_witness(&*provider, max_response_size, peer_id, request)
+        }));
+    }
+}
+
+impl<P> Stream for WitnessConnection<P>
+where
+    P: WitnessReader + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                return Poll::Ready(Some(this.on_command(cmd)))
+            }
+
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                match response {
+                    Ok(response) => return Poll::Ready(Some(response)),
+                    Err(err) => {
+                        debug!(target: "net::witness", %err, peer_id=%this.peer_id, "failed to serve witness request");
+                        continue
+                    }
+                }
+            }
+
+            if this.pending_responses.len() >= MAX_CONCURRENT_WITNESS_REQUESTS {
+                // woken up once one of the requests was served
+                return Poll::Pending
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            match WitnessMessage::decode_message(&mut &msg[..]) {
+                Ok(WitnessMessage::GetWitness(request)) => this.on_get_witness(request),
+                Ok(WitnessMessage::Witness(RequestPair { request_id, message })) => {
+                    match this.inflight_requests.remove(&request_id) {
+                        Some(response) => {
+                            let _ = response.send(Ok(message.0));
+                        }
+                        None => {
+                            debug!(target: "net::witness", request_id, peer_id=%this.peer_id, "received unsolicited witness");
+                            return Poll::Ready(None)
+                        }
+                    }
+                }
+                Err(err) => {
+                    debug!(target: "net::witness", %err, peer_id=%this.peer_id, "received invalid witness message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl<P> Drop for WitnessConnection<P> {
+    fn drop(&mut self) {
+        for (_, response) in self.inflight_requests.drain() {
+            let _ = response.send(Err(RequestError::ConnectionDropped));
+        }
+    }
+}
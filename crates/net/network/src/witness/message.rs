@@ -0,0 +1,130 @@
+//! Message types of the `wit` subprotocol.
+
+use alloy_rlp::{
+    Decodable, Encodable, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper,
+};
+use reth_eth_wire::{capability::Capability, message::RequestPair, protocol::Protocol};
+use reth_primitives::{BufMut, Bytes, BytesMut, B256};
+
+/// Message ids of the `wit` subprotocol, relative to the protocol's message id offset.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WitnessMessageId {
+    /// Requests the execution witness of a block.
+    GetWitness = 0x00,
+    /// Response to a [`WitnessMessageId::GetWitness`] request.
+    Witness = 0x01,
+}
+
+impl WitnessMessageId {
+    /// The number of messages of the protocol.
+    pub const COUNT: u8 = 2;
+}
+
+impl TryFrom<u8> for WitnessMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            0x00 => Ok(Self::GetWitness),
+            0x01 => Ok(Self::Witness),
+            _ => Err(alloy_rlp::Error::Custom("unknown witness message id")),
+        }
+    }
+}
+
+/// Requests the execution witness of the block with the given hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetWitness {
+    /// The hash of the block.
+    pub block_hash: B256,
+}
+
+/// The execution witness of a block: all RLP encoded trie nodes and contract bytecodes accessed
+/// while executing the block.
+///
+/// An empty witness indicates that the peer doesn't have a witness for the requested block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct Witness(pub Vec<Bytes>);
+
+/// A message of the `wit` subprotocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WitnessMessage {
+    /// Requests the execution witness of a block.
+    GetWitness(RequestPair<GetWitness>),
+    /// The execution witness of a block.
+    Witness(RequestPair<Witness>),
+}
+
+impl WitnessMessage {
+    /// Returns the capability of the `wit/1` protocol.
+    pub const fn capability() -> Capability {
+        Capability::new_static("wit", 1)
+    }
+
+    /// Returns the `wit/1` protocol.
+    pub const fn protocol() -> Protocol {
+        Protocol::new(Self::capability(), WitnessMessageId::COUNT)
+    }
+
+    /// Returns the message id of this message.
+    pub const fn message_id(&self) -> WitnessMessageId {
+        match self {
+            Self::GetWitness(_) => WitnessMessageId::GetWitness,
+            Self::Witness(_) => WitnessMessageId::Witness,
+        }
+    }
+
+    /// Encodes the message, prefixed with its message id.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetWitness(req) => req.encode(&mut buf),
+            Self::Witness(resp) => resp.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message from the given buffer, which starts with the message id.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        *buf = rest;
+        let msg = match WitnessMessageId::try_from(id)? {
+            WitnessMessageId::GetWitness => Self::GetWitness(RequestPair::decode(buf)?),
+            WitnessMessageId::Witness => Self::Witness(RequestPair::decode(buf)?),
+        };
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn witness_message_roundtrip() {
+        let msgs = [
+            WitnessMessage::GetWitness(RequestPair {
+                request_id: 1,
+                message: GetWitness { block_hash: B256::random() },
+            }),
+            WitnessMessage::Witness(RequestPair {
+                request_id: 1,
+                message: Witness(vec![Bytes::from_static(&[0xc0]), Bytes::from_static(b"code")]),
+            }),
+            WitnessMessage::Witness(RequestPair { request_id: 2, message: Witness::default() }),
+        ];
+        for msg in msgs {
+            let encoded = msg.encoded();
+            assert_eq!(encoded[0], msg.message_id() as u8);
+            assert_eq!(WitnessMessage::decode_message(&mut &encoded[..]).unwrap(), msg);
+        }
+    }
+
+    #[test]
+    fn reject_unknown_message_id() {
+        assert!(WitnessMessage::decode_message(&mut &[0x02, 0xc0][..]).is_err());
+        assert!(WitnessMessage::decode_message(&mut &[][..]).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+//! The `wit` subprotocol for serving execution witnesses to peers.
+//!
+//! Stateless clients can't execute blocks without the parts of the state that are accessed during
+//! execution. The `wit/1` protocol allows them to request the execution witness of a block, which
+//! contains all trie nodes and contract bytecodes accessed while executing the block.
+//!
+//! The protocol is an optional [`RlpxSubProtocol`](crate::protocol::RlpxSubProtocol) that can be
+//! installed via [`WitnessProtocolHandler`]. It is only announced to peers if the node has witness
+//! data available, see [`WitnessReader::has_witnesses`](reth_provider::WitnessReader), or if the
+//! node wants to request witnesses from peers.
+
+mod handler;
+mod message;
+
+pub use handler::{
+    WitnessCommand, WitnessConnection, WitnessConnectionHandler, WitnessProtocolEvent,
+    WitnessProtocolHandler, DEFAULT_MAX_WITNESS_RESPONSE_SIZE,
+};
+pub use message::{GetWitness, Witness, WitnessMessage, WitnessMessageId};
@@ -0,0 +1,87 @@
+//! Testing the `light` subprotocol.
+
+use reth_network::{
+    light::{
+        cht::{CHT_CONFIRMATIONS, CHT_SECTION_SIZE},
+        LightCommand, LightProtocolEvent, LightProtocolHandler, LightServingConfig,
+    },
+    test_utils::Testnet,
+};
+use reth_primitives::{Block, Header, B256};
+use reth_provider::test_utils::MockEthProvider;
+use tokio::sync::{mpsc, oneshot};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_request_header_proofs() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut parent_hash = B256::ZERO;
+    for number in 0..CHT_SECTION_SIZE + CHT_CONFIRMATIONS {
+        let header = Header { number, parent_hash, ..Default::default() };
+        parent_hash = header.hash_slow();
+        provider.add_block(parent_hash, Block { header, ..Default::default() });
+    }
+
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    let config = LightServingConfig::default().with_enabled(true);
+    net.peers_mut()[0].add_rlpx_sub_protocol(LightProtocolHandler::new(provider.clone(), config));
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(
+        LightProtocolHandler::new(provider, LightServingConfig::default()).with_events(tx),
+    );
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let LightProtocolEvent::Established { peer_id, to_connection, .. } =
+        events.recv().await.unwrap();
+    assert_eq!(peer_id, *handle.peers()[0].peer_id());
+
+    // only the first section is indexed
+    let (tx, rx) = oneshot::channel();
+    to_connection.send(LightCommand::GetChtRoots { sections: vec![0, 1], response: tx }).unwrap();
+    let roots = rx.await.unwrap().unwrap();
+    assert_eq!(roots.len(), 1);
+
+    let (tx, rx) = oneshot::channel();
+    to_connection
+        .send(LightCommand::GetHeaderProofs {
+            numbers: vec![0, 1337, CHT_SECTION_SIZE - 1, CHT_SECTION_SIZE],
+            response: tx,
+        })
+        .unwrap();
+    let proofs = rx.await.unwrap().unwrap();
+    assert_eq!(proofs.len(), 3);
+    assert_eq!(proofs[1].header.number, 1337);
+    assert!(proofs.iter().all(|proof| proof.verify(roots[0])));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_serving_budget_exhausted() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    for number in 0..CHT_SECTION_SIZE + CHT_CONFIRMATIONS {
+        let header = Header { number, ..Default::default() };
+        provider.add_block(header.hash_slow(), Block { header, ..Default::default() });
+    }
+
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    // the budget only covers a single root
+    let config = LightServingConfig::default().with_enabled(true).with_max_bytes_per_second(40);
+    net.peers_mut()[0].add_rlpx_sub_protocol(LightProtocolHandler::new(provider.clone(), config));
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(
+        LightProtocolHandler::new(provider, LightServingConfig::default()).with_events(tx),
+    );
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let LightProtocolEvent::Established { to_connection, .. } = events.recv().await.unwrap();
+
+    let (tx, rx) = oneshot::channel();
+    to_connection.send(LightCommand::GetChtRoots { sections: vec![0, 0], response: tx }).unwrap();
+    assert_eq!(rx.await.unwrap().unwrap().len(), 1);
+}
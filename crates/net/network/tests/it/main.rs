@@ -1,9 +1,11 @@
 mod big_pooled_txs_req;
 mod connect;
+mod light;
 mod multiplex;
 mod requests;
 mod session;
 mod startup;
 mod txgossip;
+mod witness;
 
 const fn main() {}
@@ -1,20 +1,25 @@
 #![allow(unreachable_pub)]
 //! Testing gossiping of transactions.
 
-use crate::multiplex::proto::{PingPongProtoMessage, PingPongProtoMessageKind};
+use crate::multiplex::proto::{
+    PingPongProtoMessage, PingPongProtoMessageId, PingPongProtoMessageKind,
+};
 use futures::{Stream, StreamExt};
+use reth_chainspec::MAINNET;
 use reth_eth_wire::{
     capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
 };
 use reth_network::{
-    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
-    test_utils::Testnet,
+    protocol::{ConnectionHandler, IntoRlpxSubProtocol, OnNotSupported, ProtocolHandler},
+    test_utils::{MockPeer, MockPeerConfig, MockPeerScript, Testnet},
+    NetworkConfigBuilder, NetworkManager, NetworkProtocols, SessionsConfig,
 };
-use reth_network_api::{Direction, PeerId};
-use reth_primitives::BytesMut;
+use reth_network_api::{Direction, PeerId, Peers};
+use reth_primitives::{Bytes, BytesMut};
 use reth_provider::test_utils::MockEthProvider;
+use secp256k1::SecretKey;
 use std::{
-    net::SocketAddr,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     pin::Pin,
     task::{ready, Context, Poll},
 };
@@ -323,3 +328,109 @@ async fn test_proto_multiplex() {
     let response = rx.await.unwrap();
     assert_eq!(response, "hello from peer1!");
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_proto_added_to_connected_peer() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+
+    let (tx, mut from_peer1) = mpsc::unbounded_channel();
+    net.peers_mut()[1]
+        .add_rlpx_sub_protocol(PingPongProtoHandler { state: ProtocolState { events: tx } });
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    // the protocol is added after the session was established, so the session is re-established
+    // to negotiate it
+    let (tx, mut from_peer0) = mpsc::unbounded_channel();
+    handle.peers()[0].network().add_rlpx_sub_protocol_and_reconnect(
+        PingPongProtoHandler { state: ProtocolState { events: tx } }.into_rlpx_sub_protocol(),
+    );
+
+    let ProtocolEvent::Established { peer_id, to_connection: peer0_conn, .. } =
+        from_peer0.recv().await.unwrap();
+    assert_eq!(peer_id, *handle.peers()[1].peer_id());
+
+    let ProtocolEvent::Established { peer_id, .. } = from_peer1.recv().await.unwrap();
+    assert_eq!(peer_id, *handle.peers()[0].peer_id());
+
+    let (tx, rx) = oneshot::channel();
+    peer0_conn.send(Command::PingMessage { msg: "hello!".to_string(), response: tx }).unwrap();
+    assert_eq!(rx.await.unwrap(), "hello!");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_mock_peer_proto() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut net = Testnet::create_with(1, provider.clone()).await;
+
+    let (tx, mut from_peer0) = mpsc::unbounded_channel();
+    net.peers_mut()[0]
+        .add_rlpx_sub_protocol(PingPongProtoHandler { state: ProtocolState { events: tx } });
+
+    let handle = net.spawn();
+    let peer0 = &handle.peers()[0];
+
+    let config =
+        MockPeerConfig::from_chain_spec(&MAINNET).with_protocol(PingPongProtoMessage::protocol());
+    let mock_id = config.peer_id();
+    let mut mock = MockPeer::connect(config, peer0.local_addr(), *peer0.peer_id()).await.unwrap();
+    assert_eq!(mock.remote_id(), *peer0.peer_id());
+
+    let ProtocolEvent::Established { peer_id, .. } = from_peer0.recv().await.unwrap();
+    assert_eq!(peer_id, mock_id);
+
+    let cap = PingPongProtoMessage::capability();
+    let script = MockPeerScript::new()
+        .send(cap.clone(), PingPongProtoMessageId::Ping as usize, Bytes::new())
+        .expect(cap.clone(), PingPongProtoMessageId::Pong as usize, Bytes::new())
+        .send(cap.clone(), PingPongProtoMessageId::PingMessage as usize, &b"hello"[..])
+        .expect(cap, PingPongProtoMessageId::PongMessage as usize, &b"hello"[..]);
+    mock.run_script(&script).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_proto_trusted_only() {
+    reth_tracing::init_test_tracing();
+    let secret_key = SecretKey::new(&mut rand::thread_rng());
+    let config = NetworkConfigBuilder::new(secret_key)
+        .listener_addr(SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0)))
+        .disable_discovery()
+        .sessions_config(SessionsConfig::default().with_extra_protocols_trusted_only(true))
+        .build(MockEthProvider::default());
+    let mut network = NetworkManager::new(config).await.unwrap();
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    network.add_rlpx_sub_protocol(PingPongProtoHandler { state: ProtocolState { events: tx } });
+    let local_addr = network.local_addr();
+    let handle = network.handle().clone();
+    tokio::task::spawn(network);
+
+    // the subprotocol is not negotiated with an untrusted peer
+    let config =
+        MockPeerConfig::from_chain_spec(&MAINNET).with_protocol(PingPongProtoMessage::protocol());
+    let mock = MockPeer::connect(config, local_addr, *handle.peer_id()).await.unwrap();
+    let cap = PingPongProtoMessage::capability();
+    assert!(!mock.shared_capabilities().iter_caps().any(|shared| *shared.capability() == cap));
+    assert!(events.try_recv().is_err());
+
+    // but it is negotiated once the peer is trusted
+    let config =
+        MockPeerConfig::from_chain_spec(&MAINNET).with_protocol(PingPongProtoMessage::protocol());
+    let mock_id = config.peer_id();
+    handle.add_trusted_peer_id(mock_id);
+    // ensure the manager processed the trusted peer before connecting
+    handle.get_trusted_peers().await.unwrap();
+    let mut mock = MockPeer::connect(config, local_addr, *handle.peer_id()).await.unwrap();
+
+    let ProtocolEvent::Established { peer_id, .. } = events.recv().await.unwrap();
+    assert_eq!(peer_id, mock_id);
+
+    let script = MockPeerScript::new()
+        .send(cap.clone(), PingPongProtoMessageId::Ping as usize, Bytes::new())
+        .expect(cap, PingPongProtoMessageId::Pong as usize, Bytes::new());
+    mock.run_script(&script).await.unwrap();
+}
@@ -0,0 +1,145 @@
+//! Testing the `wit` subprotocol.
+
+use reth_network::{
+    test_utils::{NetworkEventStream, Testnet},
+    witness::{WitnessCommand, WitnessProtocolEvent, WitnessProtocolHandler},
+};
+use reth_network_api::{Peers, PeersInfo};
+use reth_primitives::{Bytes, B256};
+use reth_provider::test_utils::MockEthProvider;
+use std::time::Duration;
+use tokio::sync::{
+    mpsc::{self, error::TryRecvError},
+    oneshot,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_request_witness() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let block_hash = B256::random();
+    let witness = vec![Bytes::from_static(&[0xc0]), Bytes::from_static(b"bytecode")];
+    provider.add_witness(block_hash, witness.clone());
+
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    net.peers_mut()[0].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider.clone()));
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider).with_events(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let WitnessProtocolEvent::Established { peer_id, to_connection, .. } =
+        events.recv().await.unwrap();
+    assert_eq!(peer_id, *handle.peers()[0].peer_id());
+
+    let (tx, rx) = oneshot::channel();
+    to_connection.send(WitnessCommand::GetWitness { block_hash, response: tx }).unwrap();
+    assert_eq!(rx.await.unwrap().unwrap(), witness);
+
+    // unknown blocks are answered with an empty witness
+    let (tx, rx) = oneshot::channel();
+    to_connection
+        .send(WitnessCommand::GetWitness { block_hash: B256::random(), response: tx })
+        .unwrap();
+    assert!(rx.await.unwrap().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_witness_exceeding_response_size() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let block_hash = B256::random();
+    provider.add_witness(block_hash, vec![Bytes::from_static(b"bytecode")]);
+
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    net.peers_mut()[0].add_rlpx_sub_protocol(
+        WitnessProtocolHandler::new(provider.clone()).with_max_response_size(8),
+    );
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider).with_events(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let WitnessProtocolEvent::Established { to_connection, .. } = events.recv().await.unwrap();
+
+    // the encoded witness is 10 bytes, which isn't served
+    let (tx, rx) = oneshot::channel();
+    to_connection.send(WitnessCommand::GetWitness { block_hash, response: tx }).unwrap();
+    assert!(rx.await.unwrap().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_witness_unsupported_by_peer() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    provider.add_witness(B256::random(), vec![Bytes::from_static(b"bytecode")]);
+
+    // only the second peer speaks `wit`
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider).with_events(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    // the session is kept alive without a `wit` connection
+    assert_eq!(handle.peers()[1].network().num_connected_peers(), 1);
+    assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_witness_not_announced_without_witnesses() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+
+    // the first peer neither has witnesses nor requests them
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    net.peers_mut()[0].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider.clone()));
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider).with_events(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    assert_eq!(handle.peers()[1].network().num_connected_peers(), 1);
+    assert!(matches!(events.try_recv(), Err(TryRecvError::Empty)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_witness_request_after_disconnect() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let block_hash = B256::random();
+    provider.add_witness(block_hash, vec![Bytes::from_static(b"bytecode")]);
+
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+    net.peers_mut()[0].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider.clone()));
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    net.peers_mut()[1].add_rlpx_sub_protocol(WitnessProtocolHandler::new(provider).with_events(tx));
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let WitnessProtocolEvent::Established { peer_id, to_connection, .. } =
+        events.recv().await.unwrap();
+
+    let network = handle.peers()[1].network();
+    let mut network_events = NetworkEventStream::new(network.event_listener());
+    network.disconnect_peer(peer_id);
+    let (closed, _) = network_events.next_session_closed().await.unwrap();
+    assert_eq!(closed, peer_id);
+
+    // the connection is dropped together with the session
+    tokio::time::timeout(Duration::from_secs(10), to_connection.closed()).await.unwrap();
+
+    // requests are rejected and never answered
+    let (tx, rx) = oneshot::channel();
+    assert!(to_connection.send(WitnessCommand::GetWitness { block_hash, response: tx }).is_err());
+    assert!(rx.await.is_err());
+}
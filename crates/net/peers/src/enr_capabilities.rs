@@ -0,0 +1,116 @@
+//! Capability hints advertised in the Ethereum Node Record.
+//!
+//! In addition to the `eth` fork id entry (see [EIP-868](https://eips.ethereum.org/EIPS/eip-868)),
+//! nodes can advertise the `RLPx` capabilities they support under the [`ENR_CAPABILITIES_KEY`]
+//! key. This allows to skip dialing peers that don't share any capabilities with the local node.
+
+use alloy_rlp::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use enr::{Builder, Enr, EnrKey};
+
+/// ENR key of the capabilities kv-pair.
+pub const ENR_CAPABILITIES_KEY: &[u8] = b"caps";
+
+/// A single `RLPx` capability advertised in the ENR.
+///
+/// This is encoded as the list `[name, version]`, identical to the capabilities of the `RLPx`
+/// hello message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+pub struct EnrCapability {
+    /// The name of the subprotocol.
+    pub name: String,
+    /// The version of the subprotocol.
+    pub version: u64,
+}
+
+/// The list of `RLPx` capabilities a node advertises in its ENR.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct EnrCapabilities(pub Vec<EnrCapability>);
+
+impl EnrCapabilities {
+    /// Extracts the capabilities from the given ENR.
+    ///
+    /// Returns `None` if the ENR doesn't advertise capabilities or the entry is malformed.
+    pub fn from_enr<K: EnrKey>(enr: &Enr<K>) -> Option<Self> {
+        enr.get_decodable(ENR_CAPABILITIES_KEY)?.ok()
+    }
+
+    /// Returns `true` if no capabilities are advertised.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds the capabilities to the given builder and builds the ENR.
+    ///
+    /// An ENR is limited to 300 bytes, so capabilities are dropped from the end of the list until
+    /// the record fits. The entry is left out if not even the first capability fits. Returns an
+    /// error if the record can't be built without the capabilities.
+    pub fn build_enr<K: EnrKey>(
+        mut self,
+        builder: &mut Builder<K>,
+        key: &K,
+    ) -> Result<Enr<K>, enr::Error> {
+        let without_capabilities = builder.build(key)?;
+        while !self.is_empty() {
+            match builder.add_value(ENR_CAPABILITIES_KEY, &self).build(key) {
+                Err(enr::Error::ExceedsMaxSize) => {
+                    self.0.pop();
+                }
+                res => return res,
+            }
+        }
+        Ok(without_capabilities)
+    }
+}
+
+impl FromIterator<(String, u64)> for EnrCapabilities {
+    fn from_iter<T: IntoIterator<Item = (String, u64)>>(iter: T) -> Self {
+        Self(iter.into_iter().map(|(name, version)| EnrCapability { name, version }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Decodable;
+
+    #[test]
+    fn enr_capabilities_roundtrip() {
+        let caps: EnrCapabilities =
+            [("eth".to_string(), 68), ("snap".to_string(), 1)].into_iter().collect();
+        let encoded = alloy_rlp::encode(&caps);
+        assert_eq!(EnrCapabilities::decode(&mut &encoded[..]).unwrap(), caps);
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn enr_capabilities_from_enr() {
+        let key = secp256k1::SecretKey::new(&mut rand::thread_rng());
+        let caps: EnrCapabilities = [("eth".to_string(), 68)].into_iter().collect();
+
+        let enr = Enr::builder().build(&key).unwrap();
+        assert_eq!(EnrCapabilities::from_enr(&enr), None);
+
+        let enr = Enr::builder().add_value(ENR_CAPABILITIES_KEY, &caps).build(&key).unwrap();
+        assert_eq!(EnrCapabilities::from_enr(&enr), Some(caps));
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn enr_capabilities_truncated_to_fit() {
+        let key = secp256k1::SecretKey::new(&mut rand::thread_rng());
+        let caps: EnrCapabilities =
+            (0..30).map(|version| ("protocol".to_string(), version)).collect();
+
+        let enr = caps.clone().build_enr(&mut Enr::builder(), &key).unwrap();
+        assert!(enr.size() <= 300);
+        let advertised = EnrCapabilities::from_enr(&enr).unwrap();
+        assert!(!advertised.is_empty());
+        assert!(advertised.0.len() < caps.0.len());
+        assert!(caps.0.starts_with(&advertised.0));
+
+        // the entry is left out if no capability fits
+        let caps: EnrCapabilities = [("a".repeat(300), 1)].into_iter().collect();
+        let enr = caps.build_enr(&mut Enr::builder(), &key).unwrap();
+        assert_eq!(EnrCapabilities::from_enr(&enr), None);
+    }
+}
@@ -1,11 +1,12 @@
 //! clap [Args](clap::Args) for debugging purposes
 
 use clap::Args;
+use reth_beacon_consensus::DEFAULT_MAX_INVALID_BLOCK_DUMPS;
 use reth_primitives::B256;
 use std::path::PathBuf;
 
 /// Parameters for debugging purposes
-#[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Debug")]
 pub struct DebugArgs {
     /// Flag indicating whether the node should be terminated after the pipeline sync.
@@ -55,6 +56,40 @@ pub struct DebugArgs {
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
+
+    /// The path to dump invalid blocks at.
+    /// If specified, every block that the engine finds to be invalid is written to the specified
+    /// location, together with its validation error and references to its parent and the
+    /// canonical head.
+    #[arg(long = "debug.invalid-block-dump", help_heading = "Debug", value_name = "PATH")]
+    pub invalid_block_dump: Option<PathBuf>,
+
+    /// The maximum number of invalid block dumps to keep. Older dumps are removed when a new
+    /// invalid block is dumped.
+    #[arg(
+        long = "debug.invalid-block-dump-max",
+        help_heading = "Debug",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_MAX_INVALID_BLOCK_DUMPS
+    )]
+    pub invalid_block_dump_max: usize,
+}
+
+impl Default for DebugArgs {
+    fn default() -> Self {
+        Self {
+            terminate: false,
+            tip: None,
+            max_block: None,
+            etherscan: None,
+            rpc_consensus_ws: None,
+            skip_fcu: None,
+            skip_new_payload: None,
+            engine_api_store: None,
+            invalid_block_dump: None,
+            invalid_block_dump_max: DEFAULT_MAX_INVALID_BLOCK_DUMPS,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -75,4 +110,18 @@ mod tests {
         let args = CommandParser::<DebugArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_invalid_block_dump() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.invalid-block-dump",
+            "invalid-blocks",
+            "--debug.invalid-block-dump-max",
+            "10",
+        ])
+        .args;
+        assert_eq!(args.invalid_block_dump, Some(PathBuf::from("invalid-blocks")));
+        assert_eq!(args.invalid_block_dump_max, 10);
+    }
 }
@@ -39,6 +39,25 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Drive the consensus engine with locally built blocks at the given interval, instead of
+    /// running a consensus client.
+    ///
+    /// The blocks are built by the payload builder of the node and immediately finalized.
+    /// Unlike dev mode, this keeps the chain, networking and consensus of the node as
+    /// configured, e.g. for private networks.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --dev.engine-driver 12s
+    #[arg(
+        long = "dev.engine-driver",
+        help_heading = "Dev testnet",
+        conflicts_with = "dev",
+        value_name = "INTERVAL",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub engine_driver: Option<Duration>,
 }
 
 #[cfg(test)]
@@ -56,13 +75,37 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: false,
+                block_max_transactions: None,
+                block_time: None,
+                engine_driver: None
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                engine_driver: None
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: None,
+                block_time: None,
+                engine_driver: None
+            }
+        );
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,7 +114,15 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_max_transactions: Some(2),
+                block_time: None,
+                engine_driver: None
+            }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
@@ -80,7 +131,19 @@ mod tests {
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(1)),
+                engine_driver: None,
+            }
+        );
+
+        let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev.engine-driver", "2s"]).args;
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: false,
+                block_max_transactions: None,
+                block_time: None,
+                engine_driver: Some(std::time::Duration::from_secs(2)),
             }
         );
     }
@@ -96,6 +159,14 @@ mod tests {
             "1s",
         ]);
         assert!(args.is_err());
+
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.engine-driver",
+            "2s",
+        ]);
+        assert!(args.is_err());
     }
 
     #[test]
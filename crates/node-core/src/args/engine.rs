@@ -0,0 +1,63 @@
+//! clap [Args](clap::Args) for engine purposes
+
+use clap::Args;
+use reth_blockchain_tree_api::StateRootStrategy;
+
+/// Parameters for configuring the engine
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "Engine")]
+pub struct EngineArgs {
+    /// How the state root of a new payload that extends the canonical chain is computed:
+    /// `sequential` or `parallel`.
+    #[arg(long = "engine.state-root", default_value_t = StateRootStrategy::Parallel, value_name = "STRATEGY")]
+    pub state_root_strategy: StateRootStrategy,
+
+    /// Also compute the state root of every such payload with the other strategy, and record the
+    /// durations of both strategies as metrics.
+    ///
+    /// This doubles the state root work, and is meant for picking the faster strategy for the
+    /// hardware of the node.
+    #[arg(long = "engine.compare-state-root")]
+    pub compare_state_root_strategies: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_engine_args() {
+        let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EngineArgs::default());
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.state-root",
+            "sequential",
+            "--engine.compare-state-root",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            EngineArgs {
+                state_root_strategy: StateRootStrategy::Sequential,
+                compare_state_root_strategies: true,
+            }
+        );
+
+        assert!(CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.state-root",
+            "sparse"
+        ])
+        .is_err());
+    }
+}
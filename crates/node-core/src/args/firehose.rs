@@ -0,0 +1,78 @@
+//! clap [Args](clap::Args) for the firehose
+
+use clap::Args;
+use std::net::SocketAddr;
+
+/// Parameters for streaming the canonical chain over gRPC
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Firehose")]
+pub struct FirehoseArgs {
+    /// Stream the canonical chain over gRPC on the given address.
+    ///
+    /// Subscribers receive every canonical block with its transactions, receipts and state
+    /// changes, and undo reorged blocks. A subscriber resumes from the cursor of the last response
+    /// it processed.
+    #[arg(long = "firehose.addr", value_name = "SOCKET")]
+    pub addr: Option<SocketAddr>,
+
+    /// The number of streamed blocks that are journaled, so that subscribers can resume from a
+    /// block that was reorged since.
+    #[arg(long = "firehose.journal-blocks", value_name = "BLOCKS", default_value_t = 1024)]
+    pub journal_blocks: u32,
+
+    /// The maximum number of concurrent subscribers.
+    #[arg(long = "firehose.max-subscribers", value_name = "COUNT", default_value_t = 32)]
+    pub max_subscribers: usize,
+
+    /// The maximum number of blocks behind the tip a subscriber can start streaming from, with
+    /// its cursor or start block.
+    #[arg(long = "firehose.max-replay-blocks", value_name = "BLOCKS", default_value_t = 10_000)]
+    pub max_replay_blocks: u64,
+}
+
+impl Default for FirehoseArgs {
+    fn default() -> Self {
+        Self { addr: None, journal_blocks: 1024, max_subscribers: 32, max_replay_blocks: 10_000 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_firehose_args() {
+        let args = CommandParser::<FirehoseArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, FirehoseArgs::default());
+
+        let args = CommandParser::<FirehoseArgs>::parse_from([
+            "reth",
+            "--firehose.addr",
+            "127.0.0.1:9000",
+            "--firehose.journal-blocks",
+            "64",
+            "--firehose.max-subscribers",
+            "4",
+            "--firehose.max-replay-blocks",
+            "128",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            FirehoseArgs {
+                addr: Some("127.0.0.1:9000".parse().unwrap()),
+                journal_blocks: 64,
+                max_subscribers: 4,
+                max_replay_blocks: 128,
+            }
+        );
+    }
+}
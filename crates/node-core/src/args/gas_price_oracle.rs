@@ -1,9 +1,9 @@
 use crate::primitives::U256;
 use clap::Args;
-use reth_rpc::eth::gas_oracle::GasPriceOracleConfig;
+use reth_rpc::eth::gas_oracle::{GasPriceOracleConfig, GasPriceOracleStrategy};
 use reth_rpc_server_types::constants::gas_oracle::{
     DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
-    DEFAULT_MAX_GAS_PRICE,
+    DEFAULT_MAX_GAS_PRICE, DEFAULT_MIN_SUGGESTED_PRIORITY_FEE,
 };
 
 /// Parameters to configure Gas Price Oracle
@@ -25,17 +25,45 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// Minimum transaction priority fee to be recommended by gpo on OP Stack chains.
+    ///
+    /// Only used by the OP Stack build of reth, ignored otherwise.
+    #[arg(
+        long = "gpo.minsuggestedpriorityfee",
+        default_value_t = DEFAULT_MIN_SUGGESTED_PRIORITY_FEE.to()
+    )]
+    pub min_suggested_priority_fee: u64,
+
+    /// How the estimate is derived from the tips of recent blocks: `percentile` or `ema`
+    #[arg(long = "gpo.strategy", default_value_t = GasPriceOracleStrategy::Percentile)]
+    pub strategy: GasPriceOracleStrategy,
+
+    /// Minimum gas price to be recommended by gpo, also used for blocks without transactions
+    #[arg(long = "gpo.minprice")]
+    pub min_price: Option<u64>,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [`GasPriceOracleConfig`] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self {
+            blocks,
+            ignore_price,
+            max_price,
+            percentile,
+            min_suggested_priority_fee,
+            strategy,
+            min_price,
+        } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            min_suggested_priority_fee: Some(U256::from(*min_suggested_priority_fee)),
+            strategy: *strategy,
+            min_price: min_price.map(U256::from),
             ..Default::default()
         }
     }
@@ -48,6 +76,9 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            min_suggested_priority_fee: DEFAULT_MIN_SUGGESTED_PRIORITY_FEE.to(),
+            strategy: GasPriceOracleStrategy::Percentile,
+            min_price: None,
         }
     }
 }
@@ -73,10 +104,28 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                min_suggested_priority_fee: DEFAULT_MIN_SUGGESTED_PRIORITY_FEE.to(),
+                strategy: GasPriceOracleStrategy::Percentile,
+                min_price: None,
             }
         );
     }
 
+    #[test]
+    fn test_parse_gpo_strategy_args() {
+        let args = CommandParser::<GasPriceOracleArgs>::parse_from([
+            "reth",
+            "--gpo.strategy",
+            "ema",
+            "--gpo.minprice",
+            "1000",
+        ])
+        .args;
+        let config = args.gas_price_oracle_config();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::Ema);
+        assert_eq!(config.min_price, Some(U256::from(1000)));
+    }
+
     #[test]
     fn gpo_args_default_sanity_test() {
         let default_args = GasPriceOracleArgs::default();
@@ -2,6 +2,7 @@
 
 use crate::dirs::{LogsDir, PlatformPath};
 use clap::{ArgAction, Args, ValueEnum};
+use reth_rpc_server_types::constants::{SLOW_QUERY_LOG_FILE_NAME, SLOW_QUERY_LOG_TARGET};
 use reth_tracing::{
     tracing_subscriber::filter::Directive, FileInfo, FileWorkerGuard, LayerInfo, LogFormat,
     RethTracer, Tracer,
@@ -57,6 +58,23 @@ pub struct LogArgs {
     )]
     pub journald_filter: String,
 
+    /// Export spans to the OpenTelemetry collector at the given gRPC endpoint, e.g. Jaeger.
+    ///
+    /// The trace context of engine API calls is taken from their `traceparent` header, if present.
+    #[cfg(feature = "otlp")]
+    #[arg(long = "log.otlp", value_name = "URL", global = true)]
+    pub otlp: Option<String>,
+
+    /// The filter to use for spans exported to the OpenTelemetry collector.
+    #[cfg(feature = "otlp")]
+    #[arg(
+        long = "log.otlp.filter",
+        value_name = "FILTER",
+        global = true,
+        default_value = "rpc::engine=trace,consensus::engine=trace,blockchain_tree=trace"
+    )]
+    pub otlp_filter: String,
+
     /// Sets whether or not the formatter emits ANSI terminal escape codes for colors and other
     /// text formatting.
     #[arg(
@@ -104,10 +122,22 @@ impl LogArgs {
             tracer = tracer.with_journald(self.journald_filter.clone());
         }
 
+        #[cfg(feature = "otlp")]
+        if let Some(endpoint) = &self.otlp {
+            tracer = tracer.with_otlp(endpoint.clone(), self.otlp_filter.clone());
+        }
+
         if self.log_file_max_files > 0 {
             let info = self.file_info();
             let file = self.layer(self.log_file_format, self.log_file_filter.clone(), false);
             tracer = tracer.with_file(file, info);
+
+            // calls are only written to the slow query log, which is created on the first write,
+            // if `--rpc.slow-query-threshold` is set
+            tracer = tracer.with_target_file(
+                SLOW_QUERY_LOG_TARGET.to_string(),
+                self.file_info().with_file_name(SLOW_QUERY_LOG_FILE_NAME),
+            );
         }
 
         let guard = tracer.init()?;
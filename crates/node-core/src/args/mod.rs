@@ -47,6 +47,14 @@ pub use txpool::TxPoolArgs;
 mod dev;
 pub use dev::DevArgs;
 
+/// EngineArgs for configuring the engine
+mod engine;
+pub use engine::EngineArgs;
+
+/// FirehoseArgs for streaming the canonical chain over gRPC
+mod firehose;
+pub use firehose::FirehoseArgs;
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
 pub use pruning::PruningArgs;
@@ -61,4 +69,7 @@ pub use benchmark_args::BenchmarkArgs;
 
 pub mod utils;
 
+#[cfg(feature = "optimism")]
+pub mod superchain;
+
 pub mod types;
@@ -1,6 +1,6 @@
 //! clap [Args](clap::Args) for network related arguments.
 
-use crate::version::P2P_CLIENT_VERSION;
+use crate::{args::utils::parse_duration_from_secs, version::P2P_CLIENT_VERSION};
 use clap::Args;
 use reth_chainspec::{net::mainnet_nodes, ChainSpec};
 use reth_config::Config;
@@ -12,11 +12,11 @@ use reth_discv5::{
 use reth_net_nat::NatResolver;
 use reth_network::{
     transactions::{
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        BlobBandwidthConfig, TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
+    Capability, HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
 };
 use reth_network_peers::TrustedPeer;
 use secp256k1::SecretKey;
@@ -25,8 +25,12 @@ use std::{
     ops::Not,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
+/// Default interval at which the pending local transactions are announced again.
+const DEFAULT_LOCAL_TX_REBROADCAST_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -115,6 +119,31 @@ pub struct NetworkArgs {
     /// Default is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ, verbatim_doc_comment)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Interval at which the pending local transactions are announced again to all peers, until
+    /// they're included or leave the pool (in seconds). Set to 0 to disable.
+    #[arg(long = "local-tx-rebroadcast-interval", value_parser = parse_duration_from_secs, default_value = "300", value_name = "SECONDS")]
+    pub local_tx_rebroadcast_interval: Duration,
+
+    /// Max bytes of blob transactions, including their sidecars, served to all peers per second.
+    /// Blob transactions over the limit are left out of responses. Unlimited by default.
+    #[arg(long = "blob-tx-upload-limit", value_name = "BYTES")]
+    pub blob_tx_upload_limit: Option<usize>,
+
+    /// Max bytes of blob transactions, including their sidecars, served to a single peer per
+    /// second. Unlimited by default.
+    #[arg(long = "blob-tx-peer-upload-limit", value_name = "BYTES")]
+    pub blob_tx_peer_upload_limit: Option<usize>,
+
+    /// Comma separated capabilities that peers must advertise, e.g. `eth/68,snap/1`. Peers that
+    /// lack any of them are disconnected.
+    #[arg(long = "network.required-caps", value_delimiter = ',', value_name = "CAPS")]
+    pub required_capabilities: Vec<Capability>,
+
+    /// Serve execution witnesses of blocks to peers over the `wit/1` protocol. Witnesses are
+    /// generated by re-executing the requested block.
+    #[arg(long = "network.serve-witnesses")]
+    pub serve_witnesses: bool,
 }
 
 impl NetworkArgs {
@@ -146,6 +175,15 @@ impl NetworkArgs {
                 self.soft_limit_byte_size_pooled_transactions_response,
                 self.soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             ),
+            full_transactions_fanout: config.broadcast.transactions,
+            local_transactions_rebroadcast_interval: self
+                .local_tx_rebroadcast_interval
+                .is_zero()
+                .not()
+                .then_some(self.local_tx_rebroadcast_interval),
+            blob_bandwidth: BlobBandwidthConfig::default()
+                .with_max_bytes_per_sec(self.blob_tx_upload_limit)
+                .with_max_bytes_per_peer_per_sec(self.blob_tx_peer_upload_limit),
         };
 
         // Configure basic network stack
@@ -155,12 +193,15 @@ impl NetworkArgs {
             ))
             .external_ip_resolver(self.nat)
             .sessions_config(
-                SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
+                SessionsConfig::default()
+                    .with_upscaled_event_buffer(peers_config.max_peers())
+                    .with_required_capabilities(self.required_capabilities.clone()),
             )
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .chain_spec(chain_spec)
             .transactions_manager_config(transactions_manager_config)
+            .block_broadcast_fanout(config.broadcast.blocks)
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -233,6 +274,11 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
+            local_tx_rebroadcast_interval: DEFAULT_LOCAL_TX_REBROADCAST_INTERVAL,
+            blob_tx_upload_limit: None,
+            blob_tx_peer_upload_limit: None,
+            required_capabilities: Vec::new(),
+            serve_witnesses: false,
         }
     }
 }
@@ -413,6 +459,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_required_caps_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--network.required-caps",
+            "eth/68,snap/1",
+        ])
+        .args;
+        assert_eq!(
+            args.required_capabilities,
+            vec![Capability::eth_68(), Capability::new_static("snap", 1)]
+        );
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--network.required-caps",
+            "eth",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_retry_strategy_args() {
         let tests = vec![0, 10];
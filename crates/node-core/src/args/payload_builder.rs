@@ -6,8 +6,12 @@ use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
-use reth_primitives::constants::{
-    ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
+use reth_payload_builder::{
+    PayloadEvictionPolicy, DEFAULT_RETAINED_PAYLOADS, DEFAULT_RETAINED_PAYLOAD_TTL,
+};
+use reth_primitives::{
+    constants::{ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION},
+    Address,
 };
 use std::{borrow::Cow, ffi::OsStr, time::Duration};
 
@@ -34,6 +38,29 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Maximum number of payloads that are retained after their jobs ended, so that late
+    /// `engine_getPayload` calls can still be served.
+    #[arg(long = "builder.retained-payloads", default_value_t = DEFAULT_RETAINED_PAYLOADS)]
+    pub retained_payloads: usize,
+
+    /// The duration for which a payload is retained after its job ended (in seconds).
+    #[arg(long = "builder.retained-payload-ttl", value_parser = parse_duration_from_secs, default_value = "60", value_name = "SECONDS")]
+    pub retained_payload_ttl: Duration,
+
+    /// Determines which retained payload is evicted if the maximum is reached: `oldest` or `lru`.
+    #[arg(long = "builder.payload-eviction", default_value_t = PayloadEvictionPolicy::Oldest, value_name = "POLICY")]
+    pub payload_eviction_policy: PayloadEvictionPolicy,
+
+    /// Accept bundles via `eth_sendBundle` and include them at the top of the locally built
+    /// payloads for their target block.
+    #[arg(long = "builder.bundles")]
+    pub bundles: bool,
+
+    /// Senders whose pool transactions are included first in the locally built payloads,
+    /// regardless of their tip.
+    #[arg(long = "builder.priority-senders", value_delimiter = ',', value_name = "ADDRESSES")]
+    pub priority_senders: Vec<Address>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -44,6 +71,11 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            retained_payloads: DEFAULT_RETAINED_PAYLOADS,
+            retained_payload_ttl: DEFAULT_RETAINED_PAYLOAD_TTL,
+            payload_eviction_policy: PayloadEvictionPolicy::default(),
+            bundles: false,
+            priority_senders: Vec::new(),
         }
     }
 }
@@ -68,6 +100,22 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn retained_payloads(&self) -> usize {
+        self.retained_payloads
+    }
+
+    fn retained_payload_ttl(&self) -> Duration {
+        self.retained_payload_ttl
+    }
+
+    fn payload_eviction_policy(&self) -> PayloadEvictionPolicy {
+        self.payload_eviction_policy
+    }
+
+    fn priority_senders(&self) -> &[Address] {
+        &self.priority_senders
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -127,6 +175,30 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_payload_retention() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.retained-payloads",
+            "4",
+            "--builder.retained-payload-ttl",
+            "30",
+            "--builder.payload-eviction",
+            "lru",
+        ])
+        .args;
+        assert_eq!(args.retained_payloads, 4);
+        assert_eq!(args.retained_payload_ttl, Duration::from_secs(30));
+        assert_eq!(args.payload_eviction_policy, PayloadEvictionPolicy::LeastRecentlyUsed);
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.payload-eviction",
+            "newest"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
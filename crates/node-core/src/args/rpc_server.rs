@@ -2,6 +2,7 @@
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
+    utils::revert_abi_value_parser,
     GasPriceOracleArgs, RpcStateCacheArgs,
 };
 use alloy_rpc_types_engine::JwtSecret;
@@ -9,16 +10,23 @@ use clap::{
     builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use humantime::parse_duration;
 use rand::Rng;
 use reth_rpc::eth::RPC_DEFAULT_GAS_CAP;
 
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{
+    constants, BlobAvailabilityCheck, NamespaceResponseSize, RethRpcModule, RpcModuleSelection,
+};
 use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
+/// The maximum depth of nested calls of the EVM.
+const MAX_CALL_DEPTH: u64 = 1024;
+
 /// Default max number of subscriptions per connection.
 pub(crate) const RPC_DEFAULT_MAX_SUBS_PER_CONN: u32 = 1024;
 
@@ -77,6 +85,34 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Interval at which WS clients are pinged to check that the connection is still alive.
+    ///
+    /// A value of 0 disables the pings, and with them the idle timeout.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --ws.ping-interval 30s
+    #[arg(
+        long = "ws.ping-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "30s",
+        verbatim_doc_comment
+    )]
+    pub ws_ping_interval: Duration,
+
+    /// Duration without a pong or a request after which a WS connection is closed.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --ws.idle-timeout 60s
+    #[arg(
+        long = "ws.idle-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "60s",
+        verbatim_doc_comment
+    )]
+    pub ws_idle_timeout: Duration,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -102,6 +138,13 @@ pub struct RpcServerArgs {
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     pub auth_jwtsecret: Option<PathBuf>,
 
+    /// Paths to JWT secrets that are accepted in addition to `--authrpc.jwtsecret`.
+    ///
+    /// This allows the consensus layer to switch to another secret, e.g. when rotating the secret
+    /// or migrating to another consensus client, without a restart of the node at the same time.
+    #[arg(long = "authrpc.jwtsecret.additional", value_name = "PATH", value_delimiter = ',')]
+    pub auth_additional_jwtsecrets: Vec<PathBuf>,
+
     /// Enable auth engine API over IPC
     #[arg(long)]
     pub auth_ipc: bool,
@@ -110,6 +153,78 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// Duration of `engine_newPayload` calls above which a warning is logged.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --authrpc.slow-new-payload-threshold 1s
+    #[arg(
+        long = "authrpc.slow-new-payload-threshold",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "1s",
+        verbatim_doc_comment
+    )]
+    pub auth_slow_new_payload_threshold: Duration,
+
+    /// Duration of `engine_forkchoiceUpdated` calls above which a warning is logged.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --authrpc.slow-forkchoice-updated-threshold 500ms
+    #[arg(
+        long = "authrpc.slow-forkchoice-updated-threshold",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "500ms",
+        verbatim_doc_comment
+    )]
+    pub auth_slow_forkchoice_updated_threshold: Duration,
+
+    /// Duration of `engine_getPayload` calls above which a warning is logged.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --authrpc.slow-get-payload-threshold 500ms
+    #[arg(
+        long = "authrpc.slow-get-payload-threshold",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "500ms",
+        verbatim_doc_comment
+    )]
+    pub auth_slow_get_payload_threshold: Duration,
+
+    /// Path of a file to record every request of the auth server to.
+    ///
+    /// The requests and their responses are appended as JSON lines, and can be replayed against
+    /// another node with `reth engine replay`. The file is moved to `<PATH>.1` once it reaches 1
+    /// GiB, and calls are dropped if they can't be written as fast as they arrive.
+    #[arg(long = "authrpc.record", value_name = "PATH")]
+    pub auth_record: Option<PathBuf>,
+
+    /// Maximum number of payload bodies that can be requested with
+    /// `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`.
+    #[arg(long = "authrpc.max-payload-bodies", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_PAYLOAD_BODIES)]
+    pub auth_max_payload_bodies: u64,
+
+    /// Maximum size of the payload bodies of an `engine_getPayloadBodies` response in megabytes.
+    ///
+    /// Larger requests are rejected with the request too large error.
+    #[arg(long = "authrpc.max-payload-bodies-size", value_name = "MB", default_value_t = constants::DEFAULT_MAX_PAYLOAD_BODIES_SIZE_MB)]
+    pub auth_max_payload_bodies_size: u32,
+
+    /// Retain how long the payload builder spent selecting, executing and computing the roots of
+    /// the payloads returned by `engine_getPayload`.
+    ///
+    /// The timings are served by `engine_getPayloadBuildTimingsV1`, in microseconds.
+    #[arg(long = "authrpc.payload-build-timings")]
+    pub auth_payload_build_timings: bool,
+
+    /// Whether `engine_newPayload` checks that the blobs of accepted payloads are available in the
+    /// blob store of the transaction pool: `off` or `warn`.
+    ///
+    /// Missing blobs are logged and recorded in metrics, and never make a payload invalid.
+    #[arg(long = "authrpc.blob-availability", value_name = "CHECK", default_value_t = BlobAvailabilityCheck::Off)]
+    pub auth_blob_availability: BlobAvailabilityCheck,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -126,6 +241,21 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-response-size", alias = "rpc-max-response-size", visible_alias = "rpc.returndata.limit", default_value_t = RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into())]
     pub rpc_max_response_size: MaxU32,
 
+    /// Set the maximum RPC response payload size of the methods of a namespace in megabytes,
+    /// overriding `--rpc.max-response-size`.
+    ///
+    /// Calls whose response exceeds the size fail with an error that asks to request less data
+    /// per call. The size can't exceed `--rpc.max-response-size`, which caps every namespace.
+    ///
+    /// Example: --rpc.max-response-size-for eth=50,trace=100
+    #[arg(
+        long = "rpc.max-response-size-for",
+        value_name = "NAMESPACE=MB",
+        value_delimiter = ',',
+        verbatim_doc_comment
+    )]
+    pub rpc_namespace_max_response_sizes: Vec<NamespaceResponseSize>,
+
     /// Set the maximum concurrent subscriptions per connection.
     #[arg(long = "rpc.max-subscriptions-per-connection", alias = "rpc-max-subscriptions-per-connection", default_value_t = RPC_DEFAULT_MAX_SUBS_PER_CONN.into())]
     pub rpc_max_subscriptions_per_connection: MaxU32,
@@ -138,6 +268,16 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Maximum number of threads that trace the transactions of a block in parallel in
+    /// `debug_traceBlock` calls. (1 = serial)
+    #[arg(
+        long = "rpc.trace-block-parallelism",
+        value_name = "COUNT",
+        default_value_t = constants::DEFAULT_TRACE_BLOCK_PARALLELISM,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+    )]
+    pub rpc_trace_block_parallelism: usize,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -156,6 +296,201 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum gas limit of `eth_call` requests.
+    ///
+    /// Requests with a higher gas limit are rejected, and the gas limit of requests without one
+    /// is lowered to the cap.
+    #[arg(
+        long = "rpc.call-gas-cap",
+        value_name = "GAS",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub rpc_call_gas_cap: Option<u64>,
+
+    /// Maximum depth of nested calls of `eth_call` requests, including the top-level call.
+    #[arg(
+        long = "rpc.call-max-depth",
+        value_name = "DEPTH",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..=MAX_CALL_DEPTH)
+    )]
+    pub rpc_call_max_depth: Option<u64>,
+
+    /// Maximum execution time of `eth_call` requests.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.call-timeout 5s
+    #[arg(
+        long = "rpc.call-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub rpc_call_timeout: Option<Duration>,
+
+    /// Maximum gas limit of `eth_estimateGas` requests.
+    ///
+    /// Requests with a higher gas limit are rejected, and the estimation of requests without one
+    /// fails if the transaction needs more gas.
+    #[arg(
+        long = "rpc.estimate-gas-cap",
+        value_name = "GAS",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub rpc_estimate_gas_cap: Option<u64>,
+
+    /// Maximum depth of nested calls of `eth_estimateGas` requests, including the top-level call.
+    #[arg(
+        long = "rpc.estimate-max-depth",
+        value_name = "DEPTH",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..=MAX_CALL_DEPTH)
+    )]
+    pub rpc_estimate_max_depth: Option<u64>,
+
+    /// Maximum execution time of `eth_estimateGas` requests, for all executions of the estimation
+    /// together.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.estimate-timeout 10s
+    #[arg(
+        long = "rpc.estimate-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub rpc_estimate_timeout: Option<Duration>,
+
+    /// Enables `eth_sendRawTransactionSync` and sets the maximum duration it waits for the
+    /// transaction to be included in a canonical block.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.send-raw-tx-sync-timeout 30s
+    #[arg(
+        long = "rpc.send-raw-tx-sync-timeout",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub rpc_send_raw_transaction_sync_timeout: Option<Duration>,
+
+    /// Maximum number of blocks into the past for which `eth_getProof` generates proofs.
+    /// (0 = latest block only)
+    #[arg(
+        long = "rpc.eth-proof-window",
+        value_name = "COUNT",
+        default_value_t = constants::DEFAULT_ETH_PROOF_WINDOW,
+        value_parser = RangedU64ValueParser::<u64>::new().range(..=constants::MAX_ETH_PROOF_WINDOW),
+    )]
+    pub rpc_eth_proof_window: u64,
+
+    /// Maximum number of times `eth_createAccessList` executes the transaction with the access
+    /// list of the previous execution until the access list no longer changes.
+    #[arg(
+        long = "rpc.access-list-max-iterations",
+        value_name = "COUNT",
+        default_value_t = constants::DEFAULT_ACCESS_LIST_MAX_ITERATIONS,
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..),
+        verbatim_doc_comment
+    )]
+    pub rpc_access_list_max_iterations: u64,
+
+    /// JSON ABI file, or compiler artifact with an `abi` field, whose custom errors are used to
+    /// decode the output of reverted `eth_call` and `eth_estimateGas` requests.
+    ///
+    /// The error data of a decoded revert is an object with the raw output and the decoded error,
+    /// instead of the raw output only. Can be specified multiple times. Startup fails if a file
+    /// can't be loaded.
+    #[arg(
+        long = "rpc.revert-abi",
+        value_name = "PATH",
+        value_parser = revert_abi_value_parser,
+        verbatim_doc_comment
+    )]
+    pub rpc_revert_abi: Vec<PathBuf>,
+
+    /// Number of recent blocks whose emitted logs each `logs` subscription buffers.
+    ///
+    /// If a buffered block is reorged out, the subscriber receives exactly the logs it was sent
+    /// for the block again with `removed: true`. (0 = removed logs are derived from the reorg
+    /// notification only)
+    #[arg(
+        long = "rpc.logs-reorg-depth",
+        value_name = "BLOCKS",
+        default_value_t = constants::DEFAULT_LOGS_REORG_DEPTH,
+        verbatim_doc_comment
+    )]
+    pub rpc_logs_reorg_depth: u64,
+
+    /// Maximum number of canonical blocks a `newHeads` or `logs` subscription replays.
+    ///
+    /// If set, subscriptions whose params have a numeric `fromBlock` first receive the blocks, or
+    /// the matching logs, from that block to the tip, and then the new blocks. Subscriptions with
+    /// a `fromBlock` further behind the tip are rejected. Disabled if not set.
+    #[arg(
+        long = "rpc.max-subscription-replay-blocks",
+        value_name = "BLOCKS",
+        verbatim_doc_comment
+    )]
+    pub rpc_max_subscription_replay_blocks: Option<u64>,
+
+    /// Number of blocks whose trace addresses are indexed for `trace_filter`.
+    ///
+    /// Indexed blocks that can't contain a matching trace are skipped without being traced, which
+    /// allows `trace_filter` ranges of up to this many blocks. The most recent blocks are traced
+    /// and indexed in the background, and new canonical blocks as they arrive. Disabled if not
+    /// set.
+    #[arg(long = "rpc.trace-index", value_name = "BLOCKS", verbatim_doc_comment)]
+    pub rpc_trace_index: Option<u32>,
+
+    /// Duration of calls above which they are written to the slow query log.
+    ///
+    /// The method, params, duration and database reads of the calls are written to
+    /// `slow-queries.log` in the log directory, if file logging is enabled.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.slow-query-threshold 500ms
+    #[arg(
+        long = "rpc.slow-query-threshold",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub rpc_slow_query_threshold: Option<Duration>,
+
+    /// JSON file with the API keys that are required by the HTTP and WS servers.
+    ///
+    /// Requests must carry one of the keys in the `x-api-key` header. The file maps each key to
+    /// its optional quotas (`maxCalls`, `maxComputeUnits`, `maxEgressBytes`), and can assign
+    /// compute units to methods with `computeUnits` (1 by default). Calls of keys that exceeded
+    /// a quota fail with error code -32005.
+    #[arg(long = "rpc.api-keys", value_name = "PATH", verbatim_doc_comment)]
+    pub rpc_api_keys: Option<PathBuf>,
+
+    /// File the usage of the API keys is persisted to, and restored from on startup.
+    ///
+    /// The usage is never reset by the node, so removing the file resets all quotas.
+    #[arg(
+        long = "rpc.api-keys-usage",
+        value_name = "PATH",
+        requires = "rpc_api_keys",
+        verbatim_doc_comment
+    )]
+    pub rpc_api_keys_usage: Option<PathBuf>,
+
+    /// Interval at which the usage of the API keys is persisted.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc.api-keys-usage-interval 60s
+    #[arg(
+        long = "rpc.api-keys-usage-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "60s",
+        verbatim_doc_comment
+    )]
+    pub rpc_api_keys_usage_interval: Duration,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -270,22 +605,52 @@ impl Default for RpcServerArgs {
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_ping_interval: Duration::from_secs(30),
+            ws_idle_timeout: Duration::from_secs(60),
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
+            auth_additional_jwtsecrets: Vec::new(),
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_slow_new_payload_threshold: Duration::from_secs(1),
+            auth_slow_forkchoice_updated_threshold: Duration::from_millis(500),
+            auth_slow_get_payload_threshold: Duration::from_millis(500),
+            auth_record: None,
+            auth_max_payload_bodies: constants::DEFAULT_MAX_PAYLOAD_BODIES,
+            auth_max_payload_bodies_size: constants::DEFAULT_MAX_PAYLOAD_BODIES_SIZE_MB,
+            auth_payload_build_timings: false,
+            auth_blob_availability: BlobAvailabilityCheck::Off,
             rpc_jwtsecret: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
+            rpc_namespace_max_response_sizes: Vec::new(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_trace_block_parallelism: constants::DEFAULT_TRACE_BLOCK_PARALLELISM,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_call_gas_cap: None,
+            rpc_call_max_depth: None,
+            rpc_call_timeout: None,
+            rpc_estimate_gas_cap: None,
+            rpc_estimate_max_depth: None,
+            rpc_estimate_timeout: None,
+            rpc_send_raw_transaction_sync_timeout: None,
+            rpc_eth_proof_window: constants::DEFAULT_ETH_PROOF_WINDOW,
+            rpc_access_list_max_iterations: constants::DEFAULT_ACCESS_LIST_MAX_ITERATIONS,
+            rpc_revert_abi: Vec::new(),
+            rpc_logs_reorg_depth: constants::DEFAULT_LOGS_REORG_DEPTH,
+            rpc_max_subscription_replay_blocks: None,
+            rpc_trace_index: None,
+            rpc_slow_query_threshold: None,
+            rpc_api_keys: None,
+            rpc_api_keys_usage: None,
+            rpc_api_keys_usage_interval: constants::DEFAULT_API_KEY_USAGE_PERSIST_INTERVAL,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
@@ -380,4 +745,256 @@ mod tests {
 
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_rpc_server_slow_call_thresholds() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.slow-new-payload-threshold",
+            "2s",
+            "--authrpc.slow-get-payload-threshold",
+            "100ms",
+        ])
+        .args;
+        assert_eq!(args.auth_slow_new_payload_threshold, Duration::from_secs(2));
+        assert_eq!(args.auth_slow_forkchoice_updated_threshold, Duration::from_millis(500));
+        assert_eq!(args.auth_slow_get_payload_threshold, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_rpc_server_send_raw_transaction_sync_timeout() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_send_raw_transaction_sync_timeout, None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.send-raw-tx-sync-timeout",
+            "30s",
+        ])
+        .args;
+        assert_eq!(args.rpc_send_raw_transaction_sync_timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_rpc_server_trace_index() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_trace_index, None);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.trace-index", "100000"])
+                .args;
+        assert_eq!(args.rpc_trace_index, Some(100_000));
+    }
+
+    #[test]
+    fn test_rpc_server_eth_proof_window() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_eth_proof_window, 0);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.eth-proof-window", "7200"])
+                .args;
+        assert_eq!(args.rpc_eth_proof_window, 7200);
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.eth-proof-window",
+            "100801",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_access_list_max_iterations() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_access_list_max_iterations, 10);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.access-list-max-iterations",
+            "3",
+        ])
+        .args;
+        assert_eq!(args.rpc_access_list_max_iterations, 3);
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.access-list-max-iterations",
+            "0",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_revert_abi() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_revert_abi.is_empty());
+
+        let dir = tempfile::tempdir().unwrap();
+        let abi = dir.path().join("vault.json");
+        std::fs::write(&abi, r#"[{"type":"error","name":"Unauthorized","inputs":[]}]"#).unwrap();
+        let artifact = dir.path().join("Token.json");
+        std::fs::write(&artifact, r#"{"abi":[]}"#).unwrap();
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.revert-abi",
+            abi.to_str().unwrap(),
+            "--rpc.revert-abi",
+            artifact.to_str().unwrap(),
+        ])
+        .args;
+        assert_eq!(args.rpc_revert_abi, vec![abi, artifact]);
+
+        // files that can't be loaded are rejected
+        let missing = dir.path().join("missing.json");
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.revert-abi",
+            missing.to_str().unwrap(),
+        ])
+        .is_err());
+        let invalid = dir.path().join("invalid.json");
+        std::fs::write(&invalid, "{}").unwrap();
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.revert-abi",
+            invalid.to_str().unwrap(),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_response_cache() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_state_cache.max_responses, 0);
+        assert_eq!(args.rpc_state_cache.response_ttl, Duration::from_secs(3600));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc-cache.max-responses",
+            "1000",
+            "--rpc-cache.response-ttl",
+            "10m",
+        ])
+        .args;
+        assert_eq!(args.rpc_state_cache.max_responses, 1000);
+        assert_eq!(args.rpc_state_cache.response_ttl, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_rpc_server_trace_block_parallelism() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_trace_block_parallelism, 1);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.trace-block-parallelism",
+            "8",
+        ])
+        .args;
+        assert_eq!(args.rpc_trace_block_parallelism, 8);
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.trace-block-parallelism",
+            "0",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_payload_bodies_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.max-payload-bodies",
+            "128",
+            "--authrpc.max-payload-bodies-size",
+            "64",
+        ])
+        .args;
+        assert_eq!(args.auth_max_payload_bodies, 128);
+        assert_eq!(args.auth_max_payload_bodies_size, 64);
+    }
+
+    #[test]
+    fn test_rpc_server_blob_availability() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.auth_blob_availability, BlobAvailabilityCheck::Off);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.blob-availability",
+            "warn",
+        ])
+        .args;
+        assert_eq!(args.auth_blob_availability, BlobAvailabilityCheck::Warn);
+    }
+
+    #[test]
+    fn test_rpc_server_namespace_max_response_sizes() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-response-size-for",
+            "debug=1000,eth=50",
+        ])
+        .args;
+        assert_eq!(
+            args.rpc_namespace_max_response_sizes,
+            vec!["debug=1000".parse().unwrap(), "eth=50".parse().unwrap()]
+        );
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.max-response-size-for",
+            "debug",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_server_ws_keepalive() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.ws_ping_interval, Duration::from_secs(30));
+        assert_eq!(args.ws_idle_timeout, Duration::from_secs(60));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--ws.ping-interval",
+            "0s",
+            "--ws.idle-timeout",
+            "5m",
+        ])
+        .args;
+        assert!(args.ws_ping_interval.is_zero());
+        assert_eq!(args.ws_idle_timeout, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_rpc_server_api_keys() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rpc_api_keys, None);
+        assert_eq!(args.rpc_api_keys_usage_interval, Duration::from_secs(60));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.api-keys",
+            "keys.json",
+            "--rpc.api-keys-usage",
+            "usage.json",
+            "--rpc.api-keys-usage-interval",
+            "5m",
+        ])
+        .args;
+        assert_eq!(args.rpc_api_keys, Some(PathBuf::from("keys.json")));
+        assert_eq!(args.rpc_api_keys_usage, Some(PathBuf::from("usage.json")));
+        assert_eq!(args.rpc_api_keys_usage_interval, Duration::from_secs(300));
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.api-keys-usage",
+            "usage.json",
+        ])
+        .is_err());
+    }
 }
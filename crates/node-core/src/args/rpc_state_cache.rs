@@ -1,8 +1,10 @@
 use clap::Args;
+use humantime::parse_duration;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_ENV_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_RESPONSE_CACHE_MAX_LEN, DEFAULT_RESPONSE_CACHE_TTL,
 };
+use std::time::Duration;
 
 /// Parameters to configure RPC state cache.
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -35,6 +37,28 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max number of cached `debug_traceTransaction`, `eth_getBlockReceipts` and `eth_getProof`
+    /// responses, per method. Traces and receipts are only cached for finalized blocks. (0 =
+    /// disabled)
+    #[arg(
+        long = "rpc-cache.max-responses",
+        default_value_t = DEFAULT_RESPONSE_CACHE_MAX_LEN,
+    )]
+    pub max_responses: u32,
+
+    /// How long responses are cached.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rpc-cache.response-ttl 10m
+    #[arg(
+        long = "rpc-cache.response-ttl",
+        value_name = "DURATION",
+        default_value = "1h",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub response_ttl: Duration,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -44,6 +68,8 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_responses: DEFAULT_RESPONSE_CACHE_MAX_LEN,
+            response_ttl: DEFAULT_RESPONSE_CACHE_TTL,
         }
     }
 }
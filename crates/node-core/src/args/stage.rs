@@ -50,4 +50,12 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The optional log index stage within the pipeline.
+    ///
+    /// Indexes the blocks that contain logs of each address and topic.
+    LogIndex,
+    /// The optional sender nonce index stage within the pipeline.
+    ///
+    /// Indexes the transaction hash of each sender and nonce.
+    SenderNonceIndex,
 }
@@ -0,0 +1,317 @@
+//! OP Stack chain definitions in the format of the superchain registry.
+//!
+//! This allows running OP Stack chains that aren't built into the binary, by passing the chain
+//! definition to `--chain`. The definition references a genesis file with the L2 genesis state,
+//! and the hardforks and fee parameters of the definition are applied to it.
+
+use alloy_genesis::Genesis;
+use reth_chainspec::ChainSpec;
+use reth_fs_util::{self as fs, FsPathError};
+use reth_primitives::{BlockNumber, B256};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Errors of loading a chain definition.
+#[derive(Debug, thiserror::Error)]
+pub enum SuperchainConfigError {
+    /// The genesis file couldn't be read.
+    #[error(transparent)]
+    Io(#[from] FsPathError),
+    /// The definition or the genesis file isn't valid JSON.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// The definition isn't valid TOML.
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    /// The chain id of the genesis file doesn't match the definition.
+    #[error("genesis file is for chain {got}, expected chain {expected}")]
+    ChainIdMismatch {
+        /// The chain id of the definition.
+        expected: u64,
+        /// The chain id of the genesis file.
+        got: u64,
+    },
+    /// The hash of the genesis block doesn't match the definition.
+    #[error("genesis block hash mismatch: expected {expected}, got {got}")]
+    GenesisHashMismatch {
+        /// The genesis hash of the definition.
+        expected: B256,
+        /// The hash of the genesis block of the genesis file.
+        got: B256,
+    },
+}
+
+/// The definition of an OP Stack chain, as in the chain configs of the superchain registry.
+///
+/// The registry doesn't include the L2 genesis state, so `genesis_file` is a local extension that
+/// isn't part of the registry format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperchainConfig {
+    /// The name of the chain.
+    pub name: String,
+    /// The chain id of the chain.
+    pub chain_id: u64,
+    /// The activation times of the hardforks.
+    #[serde(default)]
+    pub hardforks: SuperchainHardforks,
+    /// The EIP-1559 parameters, if they differ from the defaults of Ethereum.
+    #[serde(default)]
+    pub optimism: Option<SuperchainFeeParams>,
+    /// The genesis of the rollup.
+    pub genesis: SuperchainGenesis,
+    /// The genesis file with the state of the L2 genesis block, relative to the definition.
+    ///
+    /// This is not a field of the superchain registry.
+    pub genesis_file: PathBuf,
+}
+
+/// The activation times of the hardforks of an OP Stack chain.
+///
+/// Hardforks without an activation time are not scheduled. Hardforks that aren't supported are
+/// rejected, instead of running the chain without them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SuperchainHardforks {
+    /// The activation time of Regolith, active at genesis if not set.
+    pub regolith_time: Option<u64>,
+    /// The activation time of Canyon, which also activates Shanghai.
+    pub canyon_time: Option<u64>,
+    /// The activation time of Ecotone, which also activates Cancun.
+    pub ecotone_time: Option<u64>,
+    /// The activation time of Delta, which only changes the derivation of the rollup and has no
+    /// effect on execution.
+    pub delta_time: Option<u64>,
+    /// The activation time of Fjord.
+    pub fjord_time: Option<u64>,
+}
+
+/// The EIP-1559 parameters of an OP Stack chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperchainFeeParams {
+    /// The elasticity multiplier.
+    pub eip1559_elasticity: u64,
+    /// The base fee max change denominator.
+    pub eip1559_denominator: u64,
+    /// The base fee max change denominator since Canyon.
+    pub eip1559_denominator_canyon: Option<u64>,
+}
+
+/// The genesis of the rollup of an OP Stack chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperchainGenesis {
+    /// The timestamp of the L2 genesis block.
+    pub l2_time: u64,
+    /// The L2 genesis block, which is also the first bedrock block.
+    pub l2: SuperchainBlockId,
+}
+
+/// A block of a chain definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuperchainBlockId {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block.
+    pub number: BlockNumber,
+}
+
+impl SuperchainConfig {
+    /// Parses a chain definition in TOML format.
+    pub fn from_toml(s: &str) -> Result<Self, SuperchainConfigError> {
+        Ok(toml::from_str(s)?)
+    }
+
+    /// Parses a chain definition in JSON format.
+    pub fn from_json(s: &str) -> Result<Self, SuperchainConfigError> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Parses the chain definition at the given path, or returns `None` if it's a genesis file.
+    ///
+    /// Chain definitions are TOML files, or JSON files with a `genesis_file` field.
+    pub fn parse(path: &Path, raw: &str) -> Result<Option<Self>, SuperchainConfigError> {
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            return Self::from_toml(raw).map(Some)
+        }
+        let value: serde_json::Value = serde_json::from_str(raw)?;
+        if value.get("genesis_file").is_none() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    /// Returns the [`ChainSpec`] of the chain, with the genesis file that is resolved relative to
+    /// the given directory.
+    pub fn chain_spec(&self, dir: &Path) -> Result<ChainSpec, SuperchainConfigError> {
+        let raw = fs::read_to_string(dir.join(&self.genesis_file))?;
+        let genesis: Genesis = serde_json::from_str(&raw)?;
+        self.chain_spec_with_genesis(genesis)
+    }
+
+    /// Returns the [`ChainSpec`] of the chain with the given genesis.
+    ///
+    /// The hardforks and fee parameters of the definition replace those of the genesis, and the
+    /// hash of the genesis block is checked against the definition.
+    pub fn chain_spec_with_genesis(
+        &self,
+        mut genesis: Genesis,
+    ) -> Result<ChainSpec, SuperchainConfigError> {
+        if genesis.config.chain_id != self.chain_id {
+            return Err(SuperchainConfigError::ChainIdMismatch {
+                expected: self.chain_id,
+                got: genesis.config.chain_id,
+            })
+        }
+
+        let hardforks = self.hardforks;
+        genesis.config.shanghai_time = hardforks.canyon_time;
+        genesis.config.cancun_time = hardforks.ecotone_time;
+
+        let fields = &mut genesis.config.extra_fields;
+        fields.insert("bedrockBlock".to_string(), self.genesis.l2.number.into());
+        fields.insert("regolithTime".to_string(), hardforks.regolith_time.unwrap_or(0).into());
+        for (name, time) in [
+            ("canyonTime", hardforks.canyon_time),
+            ("ecotoneTime", hardforks.ecotone_time),
+            ("fjordTime", hardforks.fjord_time),
+        ] {
+            match time {
+                Some(time) => fields.insert(name.to_string(), time.into()),
+                None => fields.remove(name),
+            };
+        }
+        if let Some(params) = self.optimism {
+            let mut optimism = serde_json::json!({
+                "eip1559Elasticity": params.eip1559_elasticity,
+                "eip1559Denominator": params.eip1559_denominator,
+            });
+            if let Some(denominator) = params.eip1559_denominator_canyon {
+                optimism["eip1559DenominatorCanyon"] = denominator.into();
+            }
+            fields.insert("optimism".to_string(), optimism);
+        }
+
+        let chain_spec = ChainSpec::from(genesis);
+
+        let genesis_hash = chain_spec.genesis_hash();
+        if genesis_hash != self.genesis.l2.hash {
+            return Err(SuperchainConfigError::GenesisHashMismatch {
+                expected: self.genesis.l2.hash,
+                got: genesis_hash,
+            })
+        }
+
+        Ok(chain_spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{BaseFeeParams, Hardfork};
+
+    const DEFINITION: &str = r#"
+name = "Test Chain"
+chain_id = 1234
+genesis_file = "genesis.json"
+
+[hardforks]
+canyon_time = 100
+ecotone_time = 200
+
+[optimism]
+eip1559_elasticity = 6
+eip1559_denominator = 50
+eip1559_denominator_canyon = 250
+
+[genesis]
+l2_time = 0
+
+[genesis.l2]
+hash = "0x0000000000000000000000000000000000000000000000000000000000000000"
+number = 0
+"#;
+
+    fn genesis() -> Genesis {
+        serde_json::from_str(
+            r#"{
+                "config": { "chainId": 1234, "londonBlock": 0, "terminalTotalDifficulty": 0 },
+                "gasLimit": "0x1c9c380",
+                "difficulty": "0x0",
+                "alloc": {}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_definition() {
+        let config = SuperchainConfig::from_toml(DEFINITION).unwrap();
+        assert_eq!(config.chain_id, 1234);
+        assert_eq!(config.hardforks.canyon_time, Some(100));
+        assert_eq!(config.hardforks.fjord_time, None);
+        assert_eq!(config.genesis_file, PathBuf::from("genesis.json"));
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(SuperchainConfig::from_json(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn reject_unsupported_hardfork() {
+        let definition = DEFINITION.replace("ecotone_time = 200", "granite_time = 200");
+        let err = SuperchainConfig::from_toml(&definition).unwrap_err();
+        assert!(err.to_string().contains("unknown field `granite_time`"), "{err}");
+
+        // delta doesn't affect execution
+        let definition = DEFINITION.replace("ecotone_time = 200", "delta_time = 200");
+        let config = SuperchainConfig::from_toml(&definition).unwrap();
+        assert_eq!(config.hardforks.delta_time, Some(200));
+    }
+
+    #[test]
+    fn applies_definition_to_genesis() {
+        let mut config = SuperchainConfig {
+            name: "Test Chain".to_string(),
+            chain_id: 1234,
+            hardforks: SuperchainHardforks {
+                canyon_time: Some(100),
+                ecotone_time: Some(200),
+                ..Default::default()
+            },
+            optimism: Some(SuperchainFeeParams {
+                eip1559_elasticity: 6,
+                eip1559_denominator: 50,
+                eip1559_denominator_canyon: Some(250),
+            }),
+            genesis: SuperchainGenesis {
+                l2_time: 0,
+                l2: SuperchainBlockId { hash: B256::ZERO, number: 0 },
+            },
+            genesis_file: PathBuf::from("genesis.json"),
+        };
+
+        let err = config.chain_spec_with_genesis(genesis()).unwrap_err();
+        let SuperchainConfigError::GenesisHashMismatch { got, .. } = err else {
+            panic!("unexpected error: {err}")
+        };
+
+        config.genesis.l2.hash = got;
+        let chain_spec = config.chain_spec_with_genesis(genesis()).unwrap();
+        assert_eq!(chain_spec.chain.id(), 1234);
+        assert!(chain_spec.is_optimism());
+        assert!(chain_spec.is_fork_active_at_block(Hardfork::Bedrock, 0));
+        assert!(chain_spec.is_fork_active_at_timestamp(Hardfork::Regolith, 0));
+        assert!(!chain_spec.is_fork_active_at_timestamp(Hardfork::Canyon, 99));
+        assert!(chain_spec.is_fork_active_at_timestamp(Hardfork::Canyon, 100));
+        assert!(chain_spec.is_shanghai_active_at_timestamp(100));
+        assert!(chain_spec.is_cancun_active_at_timestamp(200));
+        assert!(!chain_spec.is_fork_active_at_timestamp(Hardfork::Fjord, u64::MAX));
+        assert_eq!(chain_spec.base_fee_params_at_timestamp(100), BaseFeeParams::new(250, 6));
+
+        let mut other_chain = genesis();
+        other_chain.config.chain_id = 10;
+        assert!(matches!(
+            config.chain_spec_with_genesis(other_chain),
+            Err(SuperchainConfigError::ChainIdMismatch { expected: 1234, got: 10 })
+        ));
+    }
+}
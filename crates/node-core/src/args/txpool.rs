@@ -1,14 +1,23 @@
 //! Transaction pool arguments
 
-use crate::cli::config::RethTransactionPoolConfig;
+use crate::{args::utils::parse_duration_from_secs, cli::config::RethTransactionPoolConfig};
 use clap::Args;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, maintain::LocalTransactionBackupConfig,
+    validate::DEFAULT_MAX_TX_INPUT_BYTES, FeeFloorConfig, LocalTransactionConfig, PoolConfig,
+    PriceBumpConfig, SubPoolLimit, DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY, DEFAULT_FEE_FLOOR_MAX,
+    DEFAULT_FEE_FLOOR_MIN, DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY, DEFAULT_PRICE_BUMP,
+    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_MAX_TXS_DEFAULT, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, time::Duration};
+
+/// Default interval at which the local transactions are written to disk.
+const DEFAULT_REJOURNAL_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Default time a local transaction may stay in the pool.
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -34,7 +43,16 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.queued-max-size", alias = "txpool.queued_max_size", default_value_t = TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT)]
     pub queued_max_size: usize,
 
-    /// Max number of executable transaction slots guaranteed per account
+    /// Max number of transactions in all sub-pools combined. Once exceeded, transactions are
+    /// evicted from the queued, basefee, blob and pending sub-pools, in that order.
+    #[arg(long = "txpool.max-count", default_value_t = TXPOOL_MAX_TXS_DEFAULT)]
+    pub max_count: usize,
+    /// Max size of all sub-pools combined in megabytes.
+    #[arg(long = "txpool.max-size", default_value_t = TXPOOL_MAX_SIZE_MB_DEFAULT)]
+    pub max_size: usize,
+
+    /// Max number of transactions of a single non-local sender in all sub-pools combined. A
+    /// replacement transaction doesn't take another slot.
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
@@ -63,6 +81,49 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+
+    /// Interval at which the local transactions are written to disk while the node is running,
+    /// so they survive a crash (in seconds). Set to 0 to only write them on shutdown.
+    #[arg(long = "txpool.rejournal", value_parser = parse_duration_from_secs, default_value = "3600", value_name = "SECONDS")]
+    pub rejournal_interval: Duration,
+    /// Maximum time a local transaction stays queued or parked for its fees before it's dropped
+    /// (in seconds), counted from when it was added or stopped being pending. Pending local
+    /// transactions never expire, and queued ones don't expire if unset.
+    #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs, value_name = "SECONDS")]
+    pub local_lifetime: Option<Duration>,
+    /// Maximum time a remote transaction stays pending before it's dropped (in seconds), counted
+    /// from when it became pending. Pending transactions don't expire if unset.
+    #[arg(long = "txpool.pending-lifetime", value_parser = parse_duration_from_secs, value_name = "SECONDS")]
+    pub pending_lifetime: Option<Duration>,
+    /// Maximum time a remote transaction stays queued or parked for its fees before it's dropped
+    /// (in seconds), counted from when it was added or stopped being pending. Queued
+    /// transactions don't expire if unset.
+    #[arg(long = "txpool.queued-lifetime", value_parser = parse_duration_from_secs, value_name = "SECONDS")]
+    pub queued_lifetime: Option<Duration>,
+
+    /// Path to a JSON file with allow and deny lists of senders and recipients, applied when
+    /// transactions enter the pool and when payloads are built. The file is reloaded when it
+    /// changes.
+    #[arg(long = "txpool.address-lists", value_name = "PATH")]
+    pub address_lists: Option<PathBuf>,
+
+    /// Flag to enable the dynamic fee floor: the minimum tip at the current base fee that remote
+    /// transactions must pay to enter the pool is raised while the pool fills up and lowered
+    /// while it drains.
+    #[arg(long = "txpool.fee-floor")]
+    pub fee_floor: bool,
+    /// Occupancy of the fullest sub-pool (in %) at or above which the fee floor is raised.
+    #[arg(long = "txpool.fee-floor-raise-at", default_value_t = DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY, value_name = "PERCENT")]
+    pub fee_floor_raise_occupancy: u64,
+    /// Occupancy of the fullest sub-pool (in %) below which the fee floor is lowered.
+    #[arg(long = "txpool.fee-floor-lower-at", default_value_t = DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY, value_name = "PERCENT")]
+    pub fee_floor_lower_occupancy: u64,
+    /// The fee floor set when it's first raised, and below which it's dropped (in wei).
+    #[arg(long = "txpool.fee-floor-min", default_value_t = DEFAULT_FEE_FLOOR_MIN, value_name = "WEI")]
+    pub fee_floor_min: u128,
+    /// The maximum fee floor (in wei).
+    #[arg(long = "txpool.fee-floor-max", default_value_t = DEFAULT_FEE_FLOOR_MAX, value_name = "WEI")]
+    pub fee_floor_max: u128,
 }
 
 impl Default for TxPoolArgs {
@@ -74,6 +135,8 @@ impl Default for TxPoolArgs {
             basefee_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+            max_count: TXPOOL_MAX_TXS_DEFAULT,
+            max_size: TXPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
@@ -82,7 +145,31 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            rejournal_interval: DEFAULT_REJOURNAL_INTERVAL,
+            local_lifetime: None,
+            pending_lifetime: None,
+            queued_lifetime: None,
+            address_lists: None,
+            fee_floor: false,
+            fee_floor_raise_occupancy: DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY,
+            fee_floor_lower_occupancy: DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY,
+            fee_floor_min: DEFAULT_FEE_FLOOR_MIN,
+            fee_floor_max: DEFAULT_FEE_FLOOR_MAX,
+        }
+    }
+}
+
+impl TxPoolArgs {
+    /// Returns the config of the local transactions backup at the given path.
+    pub fn local_transactions_backup_config(
+        &self,
+        transactions_path: PathBuf,
+    ) -> LocalTransactionBackupConfig {
+        let mut config = LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+        if !self.rejournal_interval.is_zero() {
+            config = config.with_rejournal_interval(self.rejournal_interval);
         }
+        config
     }
 }
 
@@ -111,11 +198,25 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_txs: self.queued_max_count,
                 max_size: self.queued_max_size * 1024 * 1024,
             },
+            total_limit: SubPoolLimit {
+                max_txs: self.max_count,
+                max_size: self.max_size * 1024 * 1024,
+            },
             max_account_slots: self.max_account_slots,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            pending_lifetime: self.pending_lifetime,
+            queued_lifetime: self.queued_lifetime,
+            local_lifetime: self.local_lifetime,
+            fee_floor: self.fee_floor.then(|| FeeFloorConfig {
+                raise_occupancy: self.fee_floor_raise_occupancy,
+                lower_occupancy: self.fee_floor_lower_occupancy,
+                min_floor: self.fee_floor_min,
+                max_floor: self.fee_floor_max,
+                ..Default::default()
+            }),
         }
     }
 }
@@ -138,4 +239,45 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_parse_local_transactions_journal() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.rejournal", "0"]).args;
+        let config = args.local_transactions_backup_config(PathBuf::from("transactions.rlp"));
+        assert_eq!(config.rejournal_interval, None);
+    }
+
+    #[test]
+    fn txpool_parse_lifetimes() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.queued-lifetime",
+            "3600",
+            "--txpool.lifetime",
+            "600",
+        ])
+        .args;
+        let config = args.pool_config();
+        assert_eq!(config.pending_lifetime, None);
+        assert_eq!(config.queued_lifetime, Some(Duration::from_secs(3600)));
+        assert_eq!(config.local_lifetime, Some(Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn txpool_parse_fee_floor() {
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.pool_config().fee_floor, None);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.fee-floor",
+            "--txpool.fee-floor-raise-at",
+            "90",
+        ])
+        .args;
+        let config = args.pool_config().fee_floor.unwrap();
+        assert_eq!(config.raise_occupancy, 90);
+        assert_eq!(config.max_floor, DEFAULT_FEE_FLOOR_MAX);
+    }
 }
@@ -4,6 +4,7 @@ use alloy_genesis::Genesis;
 use reth_chainspec::ChainSpec;
 use reth_fs_util as fs;
 use reth_primitives::{BlockHashOrNumber, B256};
+use reth_rpc::eth::revert::RevertDecoder;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs},
     path::PathBuf,
@@ -14,8 +15,12 @@ use std::{
 
 use reth_chainspec::DEV;
 
+#[cfg(feature = "optimism")]
+use crate::args::superchain::SuperchainConfig;
 #[cfg(feature = "optimism")]
 use reth_chainspec::{BASE_MAINNET, BASE_SEPOLIA, OP_MAINNET, OP_SEPOLIA};
+#[cfg(feature = "optimism")]
+use std::path::Path;
 
 #[cfg(not(feature = "optimism"))]
 use reth_chainspec::{GOERLI, HOLESKY, MAINNET, SEPOLIA};
@@ -42,6 +47,9 @@ pub fn chain_help() -> String {
 ///
 /// The value parser matches either a known chain, the path
 /// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+///
+/// With the `optimism` feature, the path may also point to an OP Stack chain definition in the
+/// format of the superchain registry.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         #[cfg(not(feature = "optimism"))]
@@ -63,7 +71,8 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
         "base_sepolia" | "base-sepolia" => BASE_SEPOLIA.clone(),
         _ => {
             // try to read json from path first
-            let raw = match fs::read_to_string(PathBuf::from(shellexpand::full(s)?.into_owned())) {
+            let path = PathBuf::from(shellexpand::full(s)?.into_owned());
+            let raw = match fs::read_to_string(&path) {
                 Ok(raw) => raw,
                 Err(io_err) => {
                     // valid json may start with "\n", but must contain "{"
@@ -75,6 +84,13 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
                 }
             };
 
+            // OP Stack chain definitions, which refer to a genesis file next to them
+            #[cfg(feature = "optimism")]
+            if let Some(config) = SuperchainConfig::parse(&path, &raw)? {
+                let dir = path.parent().filter(|_| path.is_file()).unwrap_or(Path::new("."));
+                return Ok(Arc::new(config.chain_spec(dir)?))
+            }
+
             // both serialized Genesis and ChainSpec structs supported
             let genesis: Genesis = serde_json::from_str(&raw)?;
 
@@ -83,6 +99,14 @@ pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error>
     })
 }
 
+/// Validates that the custom errors of a JSON ABI file, or of a compiler artifact with an `abi`
+/// field, can be loaded.
+pub fn revert_abi_value_parser(s: &str) -> eyre::Result<PathBuf, eyre::Error> {
+    let path = PathBuf::from(s);
+    RevertDecoder::new().load_file(&path)?;
+    Ok(path)
+}
+
 /// Parse [`BlockHashOrNumber`]
 pub fn hash_or_num_value_parser(value: &str) -> eyre::Result<BlockHashOrNumber, eyre::Error> {
     match B256::from_str(value) {
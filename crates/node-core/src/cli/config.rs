@@ -1,7 +1,8 @@
 //! Config traits for various node components.
 
 use reth_network::protocol::IntoRlpxSubProtocol;
-use reth_primitives::Bytes;
+use reth_payload_builder::{PayloadEvictionPolicy, PayloadRetention};
+use reth_primitives::{Address, Bytes};
 use reth_transaction_pool::PoolConfig;
 use std::{borrow::Cow, time::Duration};
 
@@ -29,6 +30,30 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// Maximum number of payloads that are retained after their jobs ended.
+    fn retained_payloads(&self) -> usize;
+
+    /// The duration for which a payload is retained after its job ended.
+    fn retained_payload_ttl(&self) -> Duration;
+
+    /// Determines which retained payload is evicted if the maximum is reached.
+    fn payload_eviction_policy(&self) -> PayloadEvictionPolicy;
+
+    /// Senders whose pool transactions are included first in the built payloads.
+    ///
+    /// None by default.
+    fn priority_senders(&self) -> &[Address] {
+        &[]
+    }
+
+    /// Returns the settings for retaining the payloads of jobs that ended.
+    fn payload_retention(&self) -> PayloadRetention {
+        PayloadRetention::default()
+            .with_capacity(self.retained_payloads())
+            .with_ttl(self.retained_payload_ttl())
+            .with_eviction_policy(self.payload_eviction_policy())
+    }
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
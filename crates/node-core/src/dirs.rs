@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the blob transactions backup file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-blob-transactions-backup.rlp`
+    pub fn txpool_blob_transactions(&self) -> PathBuf {
+        self.data_dir().join("txpool-blob-transactions-backup.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
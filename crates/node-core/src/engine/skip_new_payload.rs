@@ -41,7 +41,7 @@ where
         loop {
             let next = ready!(this.stream.poll_next_unpin(cx));
             let item = match next {
-                Some(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx }) => {
+                Some(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx, span }) => {
                     if this.skipped < this.threshold {
                         *this.skipped += 1;
                         tracing::warn!(
@@ -56,7 +56,7 @@ where
                         continue
                     } else {
                         *this.skipped = 0;
-                        Some(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx })
+                        Some(BeaconEngineMessage::NewPayload { payload, cancun_fields, tx, span })
                     }
                 }
                 next => next,
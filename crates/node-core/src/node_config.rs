@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, FirehoseArgs, NetworkArgs,
+        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     metrics::prometheus_exporter,
@@ -143,8 +143,14 @@ pub struct NodeConfig {
     /// All dev related arguments with --dev prefix
     pub dev: DevArgs,
 
+    /// All engine related arguments with --engine prefix
+    pub engine: EngineArgs,
+
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All firehose related arguments with --firehose prefix
+    pub firehose: FirehoseArgs,
 }
 
 impl NodeConfig {
@@ -233,12 +239,24 @@ impl NodeConfig {
         self
     }
 
+    /// Set the engine args for the node
+    pub const fn with_engine(mut self, engine: EngineArgs) -> Self {
+        self.engine = engine;
+        self
+    }
+
     /// Set the pruning args for the node
     pub const fn with_pruning(mut self, pruning: PruningArgs) -> Self {
         self.pruning = pruning;
         self
     }
 
+    /// Set the firehose args for the node
+    pub const fn with_firehose(mut self, firehose: FirehoseArgs) -> Self {
+        self.firehose = firehose;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig> {
         self.pruning.prune_config(&self.chain)
@@ -420,7 +438,9 @@ impl Default for NodeConfig {
             debug: DebugArgs::default(),
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
+            engine: EngineArgs::default(),
             pruning: PruningArgs::default(),
+            firehose: FirehoseArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
@@ -11,6 +11,7 @@ use crate::{
 };
 use discv5::ListenConfig;
 use futures::Future;
+use reth_auto_seal_consensus::PayloadAttributesBuilder;
 use reth_chainspec::ChainSpec;
 use reth_db::{
     test_utils::{create_test_rw_db_with_path, tempdir_path, TempDatabase},
@@ -22,9 +23,10 @@ use reth_db_api::{
 };
 use reth_exex::ExExContext;
 use reth_network::{
-    NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
+    light::LightProtocolHandler, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
+    NetworkHandle, NetworkManager,
 };
-use reth_node_api::{FullNodeTypes, FullNodeTypesAdapter, NodeTypes};
+use reth_node_api::{FullNodeTypes, FullNodeTypesAdapter, NodeTypes, PayloadTypes};
 use reth_node_core::{
     args::{get_secret_key, DatadirArgs},
     cli::config::{PayloadBuilderConfig, RethTransactionPoolConfig},
@@ -35,8 +37,13 @@ use reth_node_core::{
 };
 use reth_primitives::revm_primitives::EnvKzgSettings;
 use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
+use reth_rpc_engine_api::ExternalPayloadBuilder;
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_tracing::tracing::debug;
+use reth_transaction_pool::{
+    validate::{AddressFilter, AddressLists},
+    PoolConfig, TransactionPool,
+};
 use secp256k1::SecretKey;
 pub use states::*;
 use std::{
@@ -386,6 +393,29 @@ where
         }
     }
 
+    /// Sets the external builder that is asked for a payload in every `engine_getPayload` call.
+    pub fn with_external_payload_builder<B>(self, builder: B) -> Self
+    where
+        B: ExternalPayloadBuilder<T::Engine>,
+    {
+        Self {
+            builder: self.builder.with_external_payload_builder(builder),
+            task_executor: self.task_executor,
+        }
+    }
+
+    /// Sets the builder of the payload attributes with which the node drives its own consensus
+    /// engine if `--dev.engine-driver` is set.
+    pub fn with_engine_driver<B>(self, attributes_builder: B) -> Self
+    where
+        B: PayloadAttributesBuilder<<T::Engine as PayloadTypes>::PayloadAttributes>,
+    {
+        Self {
+            builder: self.builder.with_engine_driver(attributes_builder),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node and returns a handle to it.
     pub async fn launch(
         self,
@@ -469,6 +499,26 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Loads the configured address lists into the filter, and spawns the task that reloads them
+    /// when their file changes and removes the transactions they disallow from the pool.
+    ///
+    /// Does nothing if no address lists are configured.
+    pub fn spawn_address_lists_task<Pool>(
+        &self,
+        pool: Pool,
+        filter: AddressFilter,
+    ) -> eyre::Result<()>
+    where
+        Pool: TransactionPool + 'static,
+    {
+        let Some(path) = self.config().txpool.address_lists.clone() else { return Ok(()) };
+        filter.set_lists(AddressLists::from_file(&path)?);
+        self.executor
+            .spawn(reth_transaction_pool::maintain::reload_address_lists_task(pool, filter, path));
+        debug!(target: "reth::cli", "Spawned address lists reload task");
+        Ok(())
+    }
+
     /// Loads `EnvKzgSettings::Default`.
     pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
         Ok(EnvKzgSettings::Default)
@@ -482,7 +532,16 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Creates the [`NetworkBuilder`] for the node.
     pub async fn network_builder(&self) -> eyre::Result<NetworkBuilder<Node::Provider, (), ()>> {
         let network_config = self.network_config()?;
-        let builder = NetworkManager::builder(network_config).await?;
+        let mut builder = NetworkManager::builder(network_config).await?;
+
+        let light = &self.reth_config().light;
+        if light.enabled {
+            builder.network_mut().add_rlpx_sub_protocol(LightProtocolHandler::new(
+                self.provider().clone(),
+                light.clone(),
+            ));
+        }
+
         Ok(builder)
     }
 
@@ -13,13 +13,15 @@ use crate::{
     rpc::{RethRpcServerHandles, RpcContext, RpcHooks},
     FullNode,
 };
+use reth_auto_seal_consensus::PayloadAttributesBuilder;
 use reth_exex::ExExContext;
 use reth_network::NetworkHandle;
-use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeTypes};
+use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeTypes, PayloadTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc_engine_api::ExternalPayloadBuilder;
 use reth_tasks::TaskExecutor;
-use std::{fmt, future::Future};
+use std::{fmt, future::Future, sync::Arc};
 
 /// A node builder that also has the configured types.
 pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
@@ -50,6 +52,8 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
                 hooks: NodeHooks::default(),
                 rpc: RpcHooks::new(),
                 exexs: Vec::new(),
+                external_payload_builder: None,
+                payload_attributes_builder: None,
             },
         }
     }
@@ -212,6 +216,31 @@ impl<T: FullNodeTypes, CB: NodeComponentsBuilder<T>> NodeBuilderWithComponents<T
         self
     }
 
+    /// Sets the external builder that is asked for a payload in every `engine_getPayload` call.
+    ///
+    /// Its payload is returned to the consensus layer instead of the payload of the local builder
+    /// if it is valid and worth more, see [`ExternalPayloadBuilder`].
+    pub fn with_external_payload_builder<B>(mut self, builder: B) -> Self
+    where
+        B: ExternalPayloadBuilder<T::Engine>,
+    {
+        self.add_ons.external_payload_builder = Some(Arc::new(builder));
+        self
+    }
+
+    /// Sets the builder of the payload attributes with which the node drives its own consensus
+    /// engine if `--dev.engine-driver` is set, see
+    /// [`LocalEngineDriver`](reth_auto_seal_consensus::LocalEngineDriver).
+    ///
+    /// The node fails to launch with `--dev.engine-driver` if no builder is set.
+    pub fn with_engine_driver<B>(mut self, attributes_builder: B) -> Self
+    where
+        B: PayloadAttributesBuilder<<T::Engine as PayloadTypes>::PayloadAttributes>,
+    {
+        self.add_ons.payload_attributes_builder = Some(Box::new(attributes_builder));
+        self
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -244,4 +273,10 @@ pub(crate) struct NodeAddOns<Node: FullNodeComponents> {
     pub(crate) rpc: RpcHooks<Node>,
     /// The `ExExs` (execution extensions) of the node.
     pub(crate) exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// The external builder that is asked for payloads in `engine_getPayload`, if any.
+    pub(crate) external_payload_builder: Option<Arc<dyn ExternalPayloadBuilder<Node::Engine>>>,
+    /// Builds the payload attributes of the local engine driver, if any.
+    pub(crate) payload_attributes_builder: Option<
+        Box<dyn PayloadAttributesBuilder<<Node::Engine as PayloadTypes>::PayloadAttributes>>,
+    >,
 }
@@ -1,9 +1,10 @@
 //! Payload service component for the node builder.
 
 use crate::{BuilderContext, FullNodeTypes};
+use reth_node_core::cli::config::PayloadBuilderConfig;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_transaction_pool::TransactionPool;
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 /// A type that knows how to spawn the payload service.
 pub trait PayloadServiceBuilder<Node: FullNodeTypes, Pool: TransactionPool>: Send {
@@ -32,3 +33,93 @@ where
         self(ctx, pool)
     }
 }
+
+/// Settings of the payload jobs that take precedence over the payload builder config of the node.
+///
+/// This allows nodes that are launched programmatically to tune the latency and the revenue of
+/// the built payloads, regardless of the `--builder.*` arguments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadJobSettings {
+    /// The interval at which a job builds a new payload after the last.
+    pub interval: Option<Duration>,
+    /// The deadline for when a job resolves.
+    pub deadline: Option<Duration>,
+    /// Maximum number of tasks to spawn for building a payload.
+    pub max_payload_tasks: Option<usize>,
+}
+
+impl PayloadJobSettings {
+    /// Sets the interval at which a job builds a new payload after the last.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets the deadline for when a job resolves.
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the maximum number of tasks to spawn for building a payload.
+    ///
+    /// Returns an error if `max_payload_tasks` is 0.
+    pub fn with_max_payload_tasks(mut self, max_payload_tasks: usize) -> eyre::Result<Self> {
+        ensure_max_payload_tasks(max_payload_tasks)?;
+        self.max_payload_tasks = Some(max_payload_tasks);
+        Ok(self)
+    }
+
+    /// Returns the configured interval, or the interval of the given config.
+    pub fn interval(&self, conf: &impl PayloadBuilderConfig) -> Duration {
+        self.interval.unwrap_or_else(|| conf.interval())
+    }
+
+    /// Returns the configured deadline, or the deadline of the given config.
+    pub fn deadline(&self, conf: &impl PayloadBuilderConfig) -> Duration {
+        self.deadline.unwrap_or_else(|| conf.deadline())
+    }
+
+    /// Returns the configured maximum number of payload tasks, or the maximum of the given config.
+    ///
+    /// Returns an error if the maximum is 0.
+    pub fn max_payload_tasks(&self, conf: &impl PayloadBuilderConfig) -> eyre::Result<usize> {
+        let max_payload_tasks = self.max_payload_tasks.unwrap_or_else(|| conf.max_payload_tasks());
+        ensure_max_payload_tasks(max_payload_tasks)?;
+        Ok(max_payload_tasks)
+    }
+}
+
+/// Payload jobs can't build payloads without any tasks.
+fn ensure_max_payload_tasks(max_payload_tasks: usize) -> eyre::Result<()> {
+    eyre::ensure!(max_payload_tasks > 0, "max payload tasks must be greater than 0");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::PayloadBuilderArgs;
+
+    #[test]
+    fn settings_take_precedence() {
+        let conf = PayloadBuilderArgs::default();
+        let settings = PayloadJobSettings::default();
+        assert_eq!(settings.max_payload_tasks(&conf).unwrap(), conf.max_payload_tasks);
+        assert_eq!(settings.interval(&conf), conf.interval);
+
+        let settings = settings.with_interval(Duration::from_millis(100));
+        let settings = settings.with_max_payload_tasks(8).unwrap();
+        assert_eq!(settings.max_payload_tasks(&conf).unwrap(), 8);
+        assert_eq!(settings.interval(&conf), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn reject_zero_max_payload_tasks() {
+        assert!(PayloadJobSettings::default().with_max_payload_tasks(0).is_err());
+
+        // the field can also be set directly
+        let settings = PayloadJobSettings { max_payload_tasks: Some(0), ..Default::default() };
+        assert!(settings.max_payload_tasks(&PayloadBuilderArgs::default()).is_err());
+    }
+}
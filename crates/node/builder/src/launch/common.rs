@@ -15,7 +15,10 @@ use reth_blockchain_tree::{
     TreeExternals,
 };
 use reth_chainspec::{Chain, ChainSpec};
-use reth_config::{config::EtlConfig, PruneConfig};
+use reth_config::{
+    config::{EtlConfig, StageConfig},
+    PruneConfig,
+};
 use reth_consensus::Consensus;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitDatabaseError};
@@ -30,12 +33,13 @@ use reth_node_core::{
 use reth_primitives::{BlockNumber, Head, B256};
 use reth_provider::{
     providers::{BlockchainProvider, StaticFileProvider},
-    CanonStateNotificationSender, ProviderFactory, StaticFileProviderFactory,
+    CanonStateNotificationSender, HistoryWriter, ProviderFactory, StageCheckpointReader,
+    StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
-use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget};
+use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget, StageId};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info, warn};
@@ -366,6 +370,8 @@ where
         )
         .with_static_files_metrics();
 
+        drop_disabled_indices(&factory, &self.toml_config().stages)?;
+
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
 
@@ -525,7 +531,11 @@ where
     where
         T: FullNodeTypes<Provider = BlockchainProvider<<T as FullNodeTypes>::DB>>,
     {
-        let tree_config = BlockchainTreeConfig::default();
+        let tree_config = BlockchainTreeConfig::default()
+            .with_state_root_strategy(self.node_config().engine.state_root_strategy)
+            .with_compare_state_root_strategies(
+                self.node_config().engine.compare_state_root_strategies,
+            );
 
         // NOTE: This is a temporary workaround to provide the canon state notification sender to the components builder because there's a cyclic dependency between the blockchain provider and the tree component. This will be removed once the Blockchain provider no longer depends on an instance of the tree: <https://github.com/paradigmxyz/reth/issues/7154>
         let (canon_state_notification_sender, _receiver) =
@@ -843,6 +853,29 @@ where
     consensus: Arc<dyn Consensus>,
 }
 
+/// Drops the optional indices whose stages are disabled.
+///
+/// The indices are only kept consistent with the chain while their stages are enabled, e.g. the
+/// pipeline doesn't unwind a disabled stage, so they must not be used once the stage is disabled.
+fn drop_disabled_indices<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    stages: &StageConfig,
+) -> eyre::Result<()> {
+    let provider = factory.provider_rw()?;
+    if !stages.index_logs.enabled && provider.get_stage_checkpoint(StageId::IndexLogs)?.is_some() {
+        info!(target: "reth::cli", "Dropping the log index, since its stage is disabled");
+        provider.drop_log_indices()?;
+    }
+    if !stages.index_sender_nonces.enabled &&
+        provider.get_stage_checkpoint(StageId::IndexSenderNonces)?.is_some()
+    {
+        info!(target: "reth::cli", "Dropping the sender nonce index, since its stage is disabled");
+        provider.drop_sender_nonce_index()?;
+    }
+    provider.commit()?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{LaunchContext, NodeConfig};
@@ -8,13 +8,18 @@ use crate::{
     NodeBuilderWithComponents, NodeHandle,
 };
 use futures::{future::Either, stream, stream_select, StreamExt};
+use reth_auto_seal_consensus::LocalEngineDriver;
 use reth_beacon_consensus::{
     hooks::{EngineHooks, PruneHook, StaticFileHook},
-    BeaconConsensusEngine,
+    BeaconConsensusEngine, InvalidBlockDumper,
 };
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
 use reth_exex::ExExManagerHandle;
-use reth_network::NetworkEvents;
+#[cfg(feature = "firehose")]
+use reth_firehose::FirehoseService;
+use reth_network::{
+    protocol::IntoRlpxSubProtocol, witness::WitnessProtocolHandler, NetworkEvents, NetworkProtocols,
+};
 use reth_node_api::FullNodeTypes;
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -25,10 +30,16 @@ use reth_node_core::{
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
 
 use reth_primitives::format_ether;
-use reth_provider::providers::BlockchainProvider;
-use reth_rpc_engine_api::EngineApi;
+use reth_provider::providers::{BlockchainProvider, ExecutionWitnessProvider};
+use reth_rpc_engine_api::{
+    EngineApi, EngineDryRun, EngineEvents, PayloadBodiesLimits, SlowCallThresholds,
+};
 use reth_rpc_types::engine::ClientVersionV1;
 use reth_tasks::TaskExecutor;
+#[cfg(feature = "firehose")]
+use reth_tracing::tracing::error;
+#[cfg(not(feature = "firehose"))]
+use reth_tracing::tracing::warn;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::TransactionPool;
 use std::{future::Future, sync::Arc};
@@ -96,7 +107,14 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: NodeAddOns { hooks, rpc, exexs: installed_exex },
+            add_ons:
+                NodeAddOns {
+                    hooks,
+                    rpc,
+                    exexs: installed_exex,
+                    external_payload_builder,
+                    payload_attributes_builder,
+                },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -131,6 +149,19 @@ where
             .with_blockchain_db::<T>().await?
             .with_components(components_builder, on_component_initialized).await?;
 
+        // witnesses are generated with the block executor, which is only available once the
+        // components are built
+        if ctx.node_config().network.serve_witnesses {
+            let witnesses = ExecutionWitnessProvider::new(
+                ctx.blockchain_db().clone(),
+                ctx.components().block_executor().clone(),
+            );
+            ctx.components().network().add_rlpx_sub_protocol(
+                WitnessProtocolHandler::new(witnesses).into_rlpx_sub_protocol(),
+            );
+            info!(target: "reth::cli", "Serving execution witnesses");
+        }
+
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
             ctx.head(),
@@ -248,20 +279,27 @@ where
         hooks.add(PruneHook::new(pruner, Box::new(ctx.task_executor().clone())));
 
         // Configure the consensus engine
-        let (beacon_consensus_engine, beacon_engine_handle) = BeaconConsensusEngine::with_channel(
-            client,
-            pipeline,
-            ctx.blockchain_db().clone(),
-            Box::new(ctx.task_executor().clone()),
-            Box::new(ctx.components().network().clone()),
-            max_block,
-            ctx.components().payload_builder().clone(),
-            initial_target,
-            reth_beacon_consensus::MIN_BLOCKS_FOR_PIPELINE_RUN,
-            consensus_engine_tx,
-            Box::pin(consensus_engine_stream),
-            hooks,
-        )?;
+        let (mut beacon_consensus_engine, beacon_engine_handle) =
+            BeaconConsensusEngine::with_channel(
+                client,
+                pipeline,
+                ctx.blockchain_db().clone(),
+                Box::new(ctx.task_executor().clone()),
+                Box::new(ctx.components().network().clone()),
+                max_block,
+                ctx.components().payload_builder().clone(),
+                initial_target,
+                reth_beacon_consensus::MIN_BLOCKS_FOR_PIPELINE_RUN,
+                consensus_engine_tx,
+                Box::pin(consensus_engine_stream),
+                hooks,
+            )?;
+        if let Some(path) = ctx.node_config().debug.invalid_block_dump.clone() {
+            info!(target: "reth::cli", ?path, "Dumping invalid blocks");
+            let dumper = InvalidBlockDumper::new(path)
+                .with_max_dumps(ctx.node_config().debug.invalid_block_dump_max);
+            beacon_consensus_engine = beacon_consensus_engine.with_invalid_block_dumper(dumper);
+        }
         info!(target: "reth::cli", "Consensus engine initialized");
 
         let events = stream_select!(
@@ -295,15 +333,45 @@ where
             version: CARGO_PKG_VERSION.to_string(),
             commit: VERGEN_GIT_SHA.to_string(),
         };
-        let engine_api = EngineApi::new(
+        let mut engine_api = EngineApi::new(
             ctx.blockchain_db().clone(),
             ctx.chain_spec(),
-            beacon_engine_handle,
+            beacon_engine_handle.clone(),
             ctx.components().payload_builder().clone().into(),
+            ctx.components().pool().clone(),
             Box::new(ctx.task_executor().clone()),
             client,
-        );
+        )
+        .with_slow_call_thresholds(SlowCallThresholds {
+            new_payload: ctx.node_config().rpc.auth_slow_new_payload_threshold,
+            fork_choice_updated: ctx.node_config().rpc.auth_slow_forkchoice_updated_threshold,
+            get_payload: ctx.node_config().rpc.auth_slow_get_payload_threshold,
+        })
+        .with_payload_bodies_limits(
+            PayloadBodiesLimits::default()
+                .with_max_count(ctx.node_config().rpc.auth_max_payload_bodies)
+                .with_max_size(
+                    (ctx.node_config().rpc.auth_max_payload_bodies_size as usize)
+                        .saturating_mul(1024 * 1024),
+                ),
+        )
+        .with_payload_build_timings(ctx.node_config().rpc.auth_payload_build_timings)
+        .with_blob_availability_check(ctx.node_config().rpc.auth_blob_availability);
+        if let Some(external_payload_builder) = external_payload_builder {
+            info!(target: "reth::cli", ?external_payload_builder, "Using external payload builder");
+            engine_api = engine_api.with_external_payload_builder(external_payload_builder);
+        }
+        let engine_capabilities = engine_api.capabilities_tracker();
         info!(target: "reth::cli", "Engine API handler initialized");
+        let engine_events =
+            EngineEvents::new(beacon_engine_handle.clone(), Box::new(ctx.task_executor().clone()));
+        let engine_dry_run = EngineDryRun::new(
+            ctx.blockchain_db().clone(),
+            ctx.chain_spec(),
+            ctx.components().block_executor().clone(),
+            ctx.consensus(),
+            Box::new(ctx.task_executor().clone()),
+        );
 
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
@@ -312,6 +380,9 @@ where
         let (rpc_server_handles, mut rpc_registry) = crate::rpc::launch_rpc_servers(
             ctx.node_adapter().clone(),
             engine_api,
+            engine_events,
+            engine_dry_run,
+            engine_capabilities,
             ctx.node_config(),
             jwt_secret,
             rpc,
@@ -373,6 +444,47 @@ where
             });
         }
 
+        if let Some(interval) = ctx.node_config().dev.engine_driver {
+            info!(target: "reth::cli", ?interval, "Using local engine driver as consensus client");
+
+            let attributes_builder = payload_attributes_builder.ok_or_else(|| {
+                eyre::eyre!("--dev.engine-driver requires a payload attributes builder")
+            })?;
+            let engine_driver = LocalEngineDriver::new(
+                ctx.chain_spec(),
+                ctx.blockchain_db().clone(),
+                beacon_engine_handle,
+                ctx.components().payload_builder().clone(),
+                attributes_builder,
+                interval,
+            );
+            ctx.task_executor().spawn_critical("local engine driver", engine_driver.run());
+        }
+
+        if let Some(addr) = ctx.node_config().firehose.addr {
+            #[cfg(feature = "firehose")]
+            {
+                info!(target: "reth::cli", %addr, "Starting firehose server");
+
+                let args = ctx.node_config().firehose;
+                let firehose =
+                    FirehoseService::new(ctx.blockchain_db().clone(), args.journal_blocks)
+                        .with_max_subscribers(args.max_subscribers)
+                        .with_max_replay_blocks(args.max_replay_blocks);
+                ctx.task_executor().spawn_critical("firehose server", async move {
+                    if let Err(err) = firehose.serve(addr).await {
+                        error!(target: "reth::cli", %err, "Firehose server failed");
+                    }
+                });
+            }
+            #[cfg(not(feature = "firehose"))]
+            warn!(
+                target: "reth::cli",
+                %addr,
+                "Firehose server not started, the `firehose` feature is disabled"
+            );
+        }
+
         let full_node = FullNode {
             evm_config: ctx.components().evm_config().clone(),
             block_executor: ctx.components().block_executor().clone(),
@@ -3,7 +3,12 @@
 use futures::TryFutureExt;
 use reth_network::NetworkHandle;
 use reth_node_api::FullNodeComponents;
-use reth_node_core::{node_config::NodeConfig, rpc::api::EngineApiServer};
+use reth_node_core::{
+    node_config::NodeConfig,
+    rpc::api::{
+        EngineApiServer, EngineCapabilitiesApiServer, EngineDryRunApiServer, EngineEventsApiServer,
+    },
+};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
@@ -247,9 +252,18 @@ impl<'a, Node: FullNodeComponents> RpcContext<'a, Node> {
 }
 
 /// Launch the rpc servers.
-pub(crate) async fn launch_rpc_servers<Node, Engine>(
+pub(crate) async fn launch_rpc_servers<
+    Node,
+    Engine,
+    EngineEvents,
+    EngineDryRun,
+    EngineCapabilities,
+>(
     node: Node,
     engine_api: Engine,
+    engine_events: EngineEvents,
+    engine_dry_run: EngineDryRun,
+    engine_capabilities: EngineCapabilities,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
@@ -257,6 +271,9 @@ pub(crate) async fn launch_rpc_servers<Node, Engine>(
 where
     Node: FullNodeComponents + Clone,
     Engine: EngineApiServer<Node::Engine>,
+    EngineEvents: EngineEventsApiServer,
+    EngineDryRun: EngineDryRunApiServer,
+    EngineCapabilities: EngineCapabilitiesApiServer,
 {
     let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
@@ -272,6 +289,9 @@ where
         .with_executor(node.task_executor().clone())
         .with_evm_config(node.evm_config().clone())
         .build_with_auth_server(module_config, engine_api);
+    auth_module.merge_auth_methods(engine_events.into_rpc())?;
+    auth_module.merge_auth_methods(engine_dry_run.into_rpc())?;
+    auth_module.merge_auth_methods(engine_capabilities.into_rpc())?;
 
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
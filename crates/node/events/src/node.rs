@@ -298,6 +298,12 @@ impl<DB> NodeState<DB> {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash(), "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::InvalidBlock(header, error) => {
+                debug!(number=header.number, hash=?header.hash(), %error, "Block is invalid");
+            }
+            BeaconConsensusEngineEvent::InvalidBlockDumped(block, path) => {
+                info!(number=block.number, hash=?block.hash, ?path, "Dumped invalid block");
+            }
         }
     }
 
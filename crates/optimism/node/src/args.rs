@@ -2,6 +2,19 @@
 
 //! clap [Args](clap::Args) for optimism rollup configuration
 
+use crate::{
+    payload_source::{ExternalBuilderClient, ExternalBuilderConfig},
+    rpc::{SequencerHeader, SequencerHttpConfig},
+};
+use eyre::WrapErr;
+use humantime::parse_duration;
+use reth_payload_primitives::{MostGasUsed, MostValuable, DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT};
+use reth_rpc_types::engine::{JwtError, JwtSecret};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
 /// Parameters for rollup configuration
 #[derive(Debug, Clone, Default, PartialEq, Eq, clap::Args)]
 #[command(next_help_heading = "Rollup")]
@@ -10,6 +23,157 @@ pub struct RollupArgs {
     #[arg(long = "rollup.sequencer-http", value_name = "HTTP_URL")]
     pub sequencer_http: Option<String>,
 
+    /// Fallback HTTP endpoints for the sequencer mempool, in order of priority.
+    ///
+    /// Transactions are forwarded to the fallbacks if the primary sequencer endpoint is
+    /// unavailable.
+    #[arg(
+        long = "rollup.sequencer-http-fallbacks",
+        value_name = "HTTP_URL",
+        value_delimiter = ',',
+        requires = "sequencer_http"
+    )]
+    pub sequencer_http_fallbacks: Vec<String>,
+
+    /// Don't fail `eth_sendRawTransaction` if the transaction can't be forwarded to the
+    /// sequencer, only log the failure and submit the transaction to the local pool.
+    ///
+    /// By default, the caller receives an error so that it knows that the transaction may not
+    /// reach the sequencer.
+    #[arg(long = "rollup.sequencer-best-effort", requires = "sequencer_http")]
+    pub sequencer_best_effort: bool,
+
+    /// Only forward transactions received via `eth_sendRawTransaction` to the sequencer, without
+    /// also submitting them to the local pool.
+    ///
+    /// This is useful for replica nodes that don't build blocks and would otherwise handle every
+    /// transaction twice.
+    #[arg(
+        long = "rollup.sequencer-forward-only",
+        requires = "sequencer_http",
+        conflicts_with = "sequencer_best_effort"
+    )]
+    pub sequencer_forward_only: bool,
+
+    /// Header that is sent with every request to the sequencer, in the format `name: value`.
+    ///
+    /// Can be specified multiple times. Arguments are visible to other users of the machine, use
+    /// `--rollup.sequencer-headers-file` for headers with credentials.
+    #[arg(
+        long = "rollup.sequencer-header",
+        value_name = "HEADER",
+        value_parser = parse_header,
+        requires = "sequencer_http"
+    )]
+    pub sequencer_headers: Vec<SequencerHeader>,
+
+    /// Path to a file with headers that are sent with every request to the sequencer, for
+    /// example to authenticate with a gateway.
+    ///
+    /// The file contains one `name: value` header per line. Empty lines and lines starting with
+    /// `#` are ignored.
+    #[arg(
+        long = "rollup.sequencer-headers-file",
+        value_name = "PATH",
+        requires = "sequencer_http"
+    )]
+    pub sequencer_headers_file: Option<PathBuf>,
+
+    /// Timeout of a request to the sequencer, defaults to 10s.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rollup.sequencer-timeout 5s
+    #[arg(
+        long = "rollup.sequencer-timeout",
+        value_parser = parse_duration,
+        requires = "sequencer_http",
+        verbatim_doc_comment
+    )]
+    pub sequencer_timeout: Option<Duration>,
+
+    /// Maximum number of idle connections to keep open per sequencer endpoint.
+    #[arg(long = "rollup.sequencer-max-idle-connections", requires = "sequencer_http")]
+    pub sequencer_max_idle_connections: Option<usize>,
+
+    /// Path to a PEM encoded CA certificate to trust for the sequencer connection, in addition to
+    /// the native roots.
+    #[arg(long = "rollup.sequencer-ca-cert", value_name = "PATH", requires = "sequencer_http")]
+    pub sequencer_ca_cert: Option<PathBuf>,
+
+    /// Interval at which the sequencer endpoints are probed. While no endpoint is reachable,
+    /// transactions are not forwarded, and are rejected unless `--rollup.sequencer-best-effort`
+    /// is set.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rollup.sequencer-health-check-interval 5s
+    #[arg(
+        long = "rollup.sequencer-health-check-interval",
+        value_parser = parse_duration,
+        requires = "sequencer_http",
+        verbatim_doc_comment
+    )]
+    pub sequencer_health_check_interval: Option<Duration>,
+
+    /// HTTP endpoint of an archive node of the legacy chain, that requests for pre-bedrock
+    /// history are forwarded to.
+    #[arg(long = "rollup.historicalrpc", alias = "rollup.historical-rpc", value_name = "HTTP_URL")]
+    pub historical_rpc: Option<String>,
+
+    /// HTTP endpoint of the sequencer that is polled for preconfirmations, which are used to
+    /// answer queries for the `pending` block and preconfirmed transactions.
+    #[arg(long = "rollup.preconf-http", value_name = "HTTP_URL")]
+    pub preconf_http: Option<String>,
+
+    /// Interval at which the sequencer is polled for preconfirmations.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rollup.preconf-interval 250ms
+    #[arg(
+        long = "rollup.preconf-interval",
+        value_parser = parse_duration,
+        requires = "preconf_http",
+        verbatim_doc_comment
+    )]
+    pub preconf_interval: Option<Duration>,
+
+    /// HTTP endpoint of the interop supervisor that the cross-chain messages of submitted
+    /// transactions are checked against, before they are forwarded or submitted to the pool.
+    #[arg(long = "rollup.supervisor-http", value_name = "HTTP_URL")]
+    pub supervisor_http: Option<String>,
+
+    /// Authenticated engine API endpoint of an external block builder. Payload attributes are
+    /// also sent to the builder, and its payloads are returned to the sequencer instead of the
+    /// local payloads if they are available in time.
+    #[arg(long = "rollup.builder-http", value_name = "HTTP_URL")]
+    pub builder_http: Option<String>,
+
+    /// Path to the hex encoded JWT secret of the engine API of the external block builder.
+    #[arg(
+        long = "rollup.builder-jwt-secret",
+        value_name = "PATH",
+        value_parser = parse_jwt_secret,
+        requires = "builder_http"
+    )]
+    pub builder_jwt_secret: Option<JwtSecret>,
+
+    /// How long to wait for the payload of the external block builder when the sequencer
+    /// requests the payload.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --rollup.builder-timeout 200ms
+    #[arg(
+        long = "rollup.builder-timeout",
+        value_parser = parse_duration,
+        requires = "builder_http",
+        verbatim_doc_comment
+    )]
+    pub builder_timeout: Option<Duration>,
+
+    /// Which payload is returned to the sequencer if both the local and the external builder
+    /// provide one.
+    #[arg(long = "rollup.builder-policy", value_enum, requires = "builder_http")]
+    pub builder_policy: Option<BuilderPolicy>,
+
     /// Disable transaction pool gossip
     #[arg(long = "rollup.disable-tx-pool-gossip")]
     pub disable_txpool_gossip: bool,
@@ -31,6 +195,83 @@ pub struct RollupArgs {
     pub compute_pending_block: bool,
 }
 
+impl RollupArgs {
+    /// Returns the HTTP options of the connection to the sequencer.
+    ///
+    /// Fails if the headers file can't be read or contains an invalid line.
+    pub fn sequencer_http_config(&self) -> eyre::Result<SequencerHttpConfig> {
+        let mut headers = self.sequencer_headers.clone();
+        if let Some(path) = &self.sequencer_headers_file {
+            headers.extend(read_headers_file(path)?);
+        }
+        Ok(SequencerHttpConfig {
+            headers,
+            timeout: self.sequencer_timeout,
+            max_idle_connections: self.sequencer_max_idle_connections,
+            ca_certificate: self.sequencer_ca_cert.clone(),
+        })
+    }
+
+    /// Returns the configuration of the external block builder, if one is configured.
+    pub fn external_builder_config(&self) -> Option<ExternalBuilderConfig> {
+        let mut client = ExternalBuilderClient::new(self.builder_http.as_ref()?);
+        if let Some(jwt_secret) = self.builder_jwt_secret {
+            client = client.with_jwt_secret(jwt_secret);
+        }
+        let config = ExternalBuilderConfig::new(client)
+            .with_timeout(self.builder_timeout.unwrap_or(DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT));
+        Some(match self.builder_policy.unwrap_or_default() {
+            BuilderPolicy::PreferExternal => config,
+            BuilderPolicy::MostGasUsed => config.with_policy(MostGasUsed),
+            BuilderPolicy::MostValuable => config.with_policy(MostValuable),
+        })
+    }
+}
+
+/// The policy that selects between the payloads of the local and the external block builder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BuilderPolicy {
+    /// Always return the payload of the external builder.
+    #[default]
+    PreferExternal,
+    /// Return the payload that uses more gas.
+    MostGasUsed,
+    /// Return the payload that collects more fees.
+    MostValuable,
+}
+
+/// Reads a hex encoded JWT secret from the file at the given path.
+fn parse_jwt_secret(path: &str) -> Result<JwtSecret, JwtError> {
+    JwtSecret::from_file(Path::new(path))
+}
+
+/// Parses a header in the format `name: value`.
+fn parse_header(arg: &str) -> Result<SequencerHeader, String> {
+    let (name, value) =
+        arg.split_once(':').ok_or_else(|| format!("expected `name: value`, got {arg:?}"))?;
+    Ok(SequencerHeader::new(name.trim(), value.trim()))
+}
+
+/// Reads the headers from the file at the given path, one `name: value` header per line.
+///
+/// Empty lines and lines starting with `#` are skipped.
+fn read_headers_file(path: &Path) -> eyre::Result<Vec<SequencerHeader>> {
+    let content = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+    content
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            // the line itself isn't included, since it may contain credentials
+            parse_header(line).map_err(|_| {
+                eyre::eyre!("invalid header on line {} of {}", idx + 1, path.display())
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +290,186 @@ mod tests {
         let args = CommandParser::<RollupArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_sequencer_fallbacks() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-http-fallbacks",
+            "http://b,http://c",
+        ])
+        .args;
+        assert_eq!(args.sequencer_http.as_deref(), Some("http://a"));
+        assert_eq!(args.sequencer_http_fallbacks, ["http://b", "http://c"]);
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.sequencer-http-fallbacks",
+            "http://b"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_sequencer_forward_only() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-forward-only",
+        ])
+        .args;
+        assert!(args.sequencer_forward_only);
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-forward-only",
+            "--rollup.sequencer-best-effort",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_sequencer_http_config() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-header",
+            "x-api-key: secret",
+            "--rollup.sequencer-header",
+            "authorization:Bearer token",
+            "--rollup.sequencer-timeout",
+            "5s",
+            "--rollup.sequencer-max-idle-connections",
+            "8",
+        ])
+        .args;
+        assert_eq!(
+            args.sequencer_http_config().unwrap(),
+            SequencerHttpConfig {
+                headers: vec![
+                    SequencerHeader::new("x-api-key", "secret"),
+                    SequencerHeader::new("authorization", "Bearer token"),
+                ],
+                timeout: Some(Duration::from_secs(5)),
+                max_idle_connections: Some(8),
+                ca_certificate: None,
+            }
+        );
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-header",
+            "missing-separator",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_sequencer_headers_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("headers");
+        std::fs::write(&path, "# gateway\n\nx-api-key: secret\n  authorization: Bearer token\n")
+            .unwrap();
+
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-header",
+            "x-client: reth",
+            "--rollup.sequencer-headers-file",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        assert_eq!(
+            args.sequencer_http_config().unwrap().headers,
+            vec![
+                SequencerHeader::new("x-client", "reth"),
+                SequencerHeader::new("x-api-key", "secret"),
+                SequencerHeader::new("authorization", "Bearer token"),
+            ]
+        );
+        let debug = format!("{args:?}");
+        assert!(!debug.contains("secret") && !debug.contains("token"));
+
+        std::fs::write(&path, "x-api-key secret\n").unwrap();
+        let err = args.sequencer_http_config().unwrap_err().to_string();
+        assert!(err.contains("line 1"));
+        assert!(!err.contains("secret"));
+    }
+
+    #[test]
+    fn test_parse_sequencer_health_check_interval() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.sequencer-http",
+            "http://a",
+            "--rollup.sequencer-health-check-interval",
+            "10s",
+        ])
+        .args;
+        assert_eq!(args.sequencer_health_check_interval, Some(Duration::from_secs(10)));
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.sequencer-health-check-interval",
+            "10s",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_builder_args() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.builder-http",
+            "http://builder:8551",
+            "--rollup.builder-timeout",
+            "500ms",
+            "--rollup.builder-policy",
+            "most-gas-used",
+        ])
+        .args;
+        assert_eq!(args.builder_http.as_deref(), Some("http://builder:8551"));
+        assert_eq!(args.builder_timeout, Some(Duration::from_millis(500)));
+        assert_eq!(args.builder_policy, Some(BuilderPolicy::MostGasUsed));
+        assert_eq!(args.external_builder_config().unwrap().timeout, Duration::from_millis(500));
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.builder-jwt-secret",
+            "/does/not/exist",
+        ])
+        .is_err());
+        assert!(RollupArgs::default().external_builder_config().is_none());
+    }
+
+    #[test]
+    fn test_parse_preconf_args() {
+        let args = CommandParser::<RollupArgs>::parse_from([
+            "reth",
+            "--rollup.preconf-http",
+            "http://a",
+            "--rollup.preconf-interval",
+            "100ms",
+        ])
+        .args;
+        assert_eq!(args.preconf_http.as_deref(), Some("http://a"));
+        assert_eq!(args.preconf_interval, Some(Duration::from_millis(100)));
+
+        assert!(CommandParser::<RollupArgs>::try_parse_from([
+            "reth",
+            "--rollup.preconf-interval",
+            "100ms",
+        ])
+        .is_err());
+    }
 }
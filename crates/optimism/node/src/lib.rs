@@ -22,6 +22,8 @@ pub use node::OptimismNode;
 
 pub mod txpool;
 
+pub mod payload_source;
+
 pub mod rpc;
 
 pub use reth_optimism_payload_builder::{
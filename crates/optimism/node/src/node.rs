@@ -2,6 +2,7 @@
 
 use crate::{
     args::RollupArgs,
+    payload_source::{ExternalBuilderConfig, SelectingPayloadJobGenerator},
     txpool::{OpTransactionPool, OpTransactionValidator},
     OptimismEngineTypes,
 };
@@ -11,7 +12,7 @@ use reth_evm_optimism::{OpExecutorProvider, OptimismEvmConfig};
 use reth_network::{NetworkHandle, NetworkManager};
 use reth_node_builder::{
     components::{
-        ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder,
+        ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder, PayloadJobSettings,
         PayloadServiceBuilder, PoolBuilder,
     },
     node::{FullNodeTypes, NodeTypes},
@@ -22,8 +23,9 @@ use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore,
+    validate::{AddressFilter, FeeFloor},
+    CoinbaseTipOrdering, TransactionPool, TransactionValidationTaskExecutor,
 };
 use std::sync::Arc;
 
@@ -55,14 +57,18 @@ impl OptimismNode {
     where
         Node: FullNodeTypes<Engine = OptimismEngineTypes>,
     {
+        let external_builder = args.external_builder_config();
         let RollupArgs { disable_txpool_gossip, compute_pending_block, .. } = args;
+        // the pool and the payload builder share the address lists
+        let address_filter = AddressFilter::default();
         ComponentsBuilder::default()
             .node_types::<Node>()
-            .pool(OptimismPoolBuilder::default())
-            .payload(OptimismPayloadBuilder::new(
-                compute_pending_block,
-                OptimismEvmConfig::default(),
-            ))
+            .pool(OptimismPoolBuilder::default().with_address_filter(address_filter.clone()))
+            .payload(
+                OptimismPayloadBuilder::new(compute_pending_block, OptimismEvmConfig::default())
+                    .with_external_builder(external_builder)
+                    .with_address_filter(address_filter),
+            )
             .network(OptimismNetworkBuilder { disable_txpool_gossip })
             .executor(OptimismExecutorBuilder::default())
             .consensus(OptimismConsensusBuilder::default())
@@ -121,9 +127,23 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct OptimismPoolBuilder;
+pub struct OptimismPoolBuilder {
+    /// The filter of the configured address lists, see
+    /// [`BuilderContext::spawn_address_lists_task`].
+    pub address_filter: AddressFilter,
+}
+
+impl OptimismPoolBuilder {
+    /// Sets the filter that the configured address lists are loaded into.
+    ///
+    /// Share it with the payload builder, so that it skips the same transactions.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.address_filter = address_filter;
+        self
+    }
+}
 
 impl<Node> PoolBuilder<Node> for OptimismPoolBuilder
 where
@@ -135,6 +155,13 @@ where
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
 
+        let pool_config = ctx.pool_config();
+        if let Some(fee_floor_config) = pool_config.fee_floor {
+            fee_floor_config.validate()?;
+        }
+        let fee_floor = pool_config
+            .fee_floor
+            .map(|_| FeeFloor::new(pool_config.local_transactions_config.clone()));
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -144,15 +171,17 @@ where
                 ctx.task_executor().clone(),
                 blob_store.clone(),
             )
-            .map(OpTransactionValidator::new);
+            .map(OpTransactionValidator::new)
+            .with_admission_validator((self.address_filter.clone(), fee_floor.clone()));
 
         let transaction_pool = reth_transaction_pool::Pool::new(
             validator,
             CoinbaseTipOrdering::default(),
             blob_store,
-            ctx.pool_config(),
+            pool_config.clone(),
         );
         info!(target: "reth::cli", "Transaction pool initialized");
+        ctx.spawn_address_lists_task(transaction_pool.clone(), self.address_filter)?;
         let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
@@ -161,7 +190,7 @@ where
             let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
             let transactions_backup_config =
-                reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+                ctx.config().txpool.local_transactions_backup_config(transactions_path);
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -188,6 +217,15 @@ where
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
 
+        if let Some(fee_floor) = fee_floor {
+            ctx.task_executor().spawn(reth_transaction_pool::maintain::fee_floor_task(
+                transaction_pool.clone(),
+                fee_floor,
+                pool_config,
+            ));
+            debug!(target: "reth::cli", "Spawned txpool fee floor task");
+        }
+
         Ok(transaction_pool)
     }
 }
@@ -206,12 +244,47 @@ pub struct OptimismPayloadBuilder<EVM = OptimismEvmConfig> {
     pub compute_pending_block: bool,
     /// The EVM configuration to use for the payload builder.
     pub evm_config: EVM,
+    /// The external builder whose payloads are selected instead of the local payloads, if any.
+    pub external_builder: Option<ExternalBuilderConfig>,
+    /// Settings of the payload jobs that take precedence over the node's config.
+    pub job_settings: PayloadJobSettings,
+    /// The filter of the address lists whose disallowed transactions are skipped.
+    pub address_filter: AddressFilter,
 }
 
 impl<EVM> OptimismPayloadBuilder<EVM> {
     /// Create a new instance with the given `compute_pending_block` flag and evm config.
     pub const fn new(compute_pending_block: bool, evm_config: EVM) -> Self {
-        Self { compute_pending_block, evm_config }
+        Self {
+            compute_pending_block,
+            evm_config,
+            external_builder: None,
+            job_settings: PayloadJobSettings::default(),
+            address_filter: AddressFilter::default(),
+        }
+    }
+
+    /// Sets the external builder whose payloads are selected instead of the local payloads.
+    pub fn with_external_builder(
+        mut self,
+        external_builder: Option<ExternalBuilderConfig>,
+    ) -> Self {
+        self.external_builder = external_builder;
+        self
+    }
+
+    /// Sets the settings of the payload jobs that take precedence over the node's config.
+    pub const fn with_job_settings(mut self, job_settings: PayloadJobSettings) -> Self {
+        self.job_settings = job_settings;
+        self
+    }
+
+    /// Sets the filter of the address lists whose disallowed transactions are skipped.
+    ///
+    /// This is the filter of the pool builder, which loads the configured lists into it.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.address_filter = address_filter;
+        self
     }
 }
 
@@ -226,17 +299,19 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
+        let conf = ctx.payload_builder_config();
         let payload_builder = reth_optimism_payload_builder::OptimismPayloadBuilder::new(
             ctx.chain_spec(),
             self.evm_config,
         )
-        .set_compute_pending_block(self.compute_pending_block);
-        let conf = ctx.payload_builder_config();
+        .set_compute_pending_block(self.compute_pending_block)
+        .with_priority_senders(conf.priority_senders().iter().copied())
+        .with_address_filter(self.address_filter);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
-            .interval(conf.interval())
-            .deadline(conf.deadline())
-            .max_payload_tasks(conf.max_payload_tasks())
+            .interval(self.job_settings.interval(conf))
+            .deadline(self.job_settings.deadline(conf))
+            .max_payload_tasks(self.job_settings.max_payload_tasks(conf)?)
             // no extradata for OP
             .extradata(Default::default());
 
@@ -248,10 +323,34 @@ where
             ctx.chain_spec(),
             payload_builder,
         );
-        let (payload_service, payload_builder) =
-            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
-        ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
+        let payload_builder = if let Some(external_builder) = self.external_builder {
+            info!(target: "reth::cli", "Selecting payloads from external builder");
+            let payload_generator = SelectingPayloadJobGenerator::new(
+                payload_generator,
+                ctx.provider().clone(),
+                ctx.task_executor().clone(),
+                external_builder,
+                ctx.chain_spec(),
+            );
+            let (payload_service, payload_builder) = PayloadBuilderService::new(
+                payload_generator,
+                ctx.provider().canonical_state_stream(),
+            );
+            let payload_service = payload_service.with_retention(conf.payload_retention());
+            ctx.task_executor()
+                .spawn_critical("payload builder service", Box::pin(payload_service));
+            payload_builder
+        } else {
+            let (payload_service, payload_builder) = PayloadBuilderService::new(
+                payload_generator,
+                ctx.provider().canonical_state_stream(),
+            );
+            let payload_service = payload_service.with_retention(conf.payload_retention());
+            ctx.task_executor()
+                .spawn_critical("payload builder service", Box::pin(payload_service));
+            payload_builder
+        };
 
         Ok(payload_builder)
     }
@@ -0,0 +1,705 @@
+//! Selection between the payloads of the local payload builder and an external block builder.
+//!
+//! Similar to rollup-boost, the payload attributes of every payload job are also sent to an
+//! external builder with `engine_forkchoiceUpdatedV3`. When the sequencer requests the payload, the
+//! payload of the external builder is fetched with `engine_getPayloadV3`, and a
+//! [`PayloadSelectionPolicy`] decides whether it or the payload of the local builder is returned.
+//! The local payload is used if the external builder fails, doesn't respond in time, or returns a
+//! payload that doesn't match the attributes. A selected external payload is executed before it
+//! is returned, and the local payload is used instead if it's invalid.
+
+use jsonrpsee::types::ErrorObjectOwned;
+use reqwest::Client;
+use reth_chainspec::ChainSpec;
+use reth_consensus::{Consensus, PostExecutionInput};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_evm_optimism::OpExecutorProvider;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_optimism_consensus::OptimismBeaconConsensus;
+use reth_optimism_payload_builder::{
+    OptimismBuiltPayload, OptimismPayloadAttributes, OptimismPayloadBuilderAttributes,
+};
+use reth_payload_builder::{
+    error::PayloadBuilderError, KeepPayloadJobAlive, PayloadId, PayloadJob, PayloadJobGenerator,
+};
+use reth_payload_primitives::{
+    ensure_matches_attributes, ExternalPayloadError, PayloadSelectionPolicy, PayloadSource,
+    PreferExternal, DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT,
+};
+use reth_primitives::{SealedBlock, U256};
+use reth_provider::{CanonStateNotification, HeaderProvider, StateProviderFactory};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_types::engine::{
+    Claims, ExecutionPayload, ForkchoiceState, ForkchoiceUpdated, JwtSecret,
+    OptimismExecutionPayloadEnvelopeV3, PayloadAttributes,
+};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// Timeout of a request to the external builder, including connecting.
+const BUILDER_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for connecting to the external builder.
+const BUILDER_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A payload of an external builder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalPayload {
+    /// The built block.
+    pub block: SealedBlock,
+    /// The value of the block to the fee recipient, as reported by the builder.
+    pub block_value: U256,
+}
+
+/// A source of payloads that are built outside of the node.
+#[async_trait::async_trait]
+pub trait ExternalPayloadSource: fmt::Debug + Send + Sync + 'static {
+    /// Starts building a payload with the given attributes on top of the given forkchoice state,
+    /// and returns the id of the payload at the source.
+    async fn start_payload(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: OptimismPayloadAttributes,
+    ) -> Result<PayloadId, ExternalPayloadError>;
+
+    /// Returns the payload with the given id.
+    async fn get_payload(&self, id: PayloadId) -> Result<ExternalPayload, ExternalPayloadError>;
+}
+
+/// An external block builder that is connected to via its authenticated engine API.
+#[derive(Debug, Clone)]
+pub struct ExternalBuilderClient {
+    /// The engine API endpoint of the builder
+    endpoint: String,
+    /// The secret to authenticate with, if the endpoint requires authentication
+    jwt_secret: Option<JwtSecret>,
+    /// The HTTP client
+    http_client: Client,
+}
+
+impl ExternalBuilderClient {
+    /// Creates a new [`ExternalBuilderClient`] for the given engine API endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        let client = Client::builder()
+            .use_rustls_tls()
+            .timeout(BUILDER_REQUEST_TIMEOUT)
+            .connect_timeout(BUILDER_CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+        Self::with_client(endpoint, client)
+    }
+
+    /// Creates a new [`ExternalBuilderClient`] with the given HTTP client.
+    pub fn with_client(endpoint: impl Into<String>, http_client: Client) -> Self {
+        Self { endpoint: endpoint.into(), jwt_secret: None, http_client }
+    }
+
+    /// Sets the secret that requests are authenticated with.
+    pub const fn with_jwt_secret(mut self, jwt_secret: JwtSecret) -> Self {
+        self.jwt_secret = Some(jwt_secret);
+        self
+    }
+
+    /// Returns the endpoint of the builder.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, ExternalPayloadError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+
+        let mut request = self.http_client.post(self.endpoint()).json(&body);
+        if let Some(secret) = &self.jwt_secret {
+            let jwt = secret
+                .encode(&Claims::with_current_timestamp())
+                .map_err(ExternalPayloadError::builder)?;
+            request = request.bearer_auth(jwt);
+        }
+
+        let response = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(ExternalPayloadError::builder)?
+            .json::<JsonRpcResponse<T>>()
+            .await
+            .map_err(ExternalPayloadError::builder)?;
+
+        match (response.result, response.error) {
+            (_, Some(err)) => Err(ExternalPayloadError::builder(err)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(ExternalPayloadError::builder("missing result")),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExternalPayloadSource for ExternalBuilderClient {
+    async fn start_payload(
+        &self,
+        forkchoice: ForkchoiceState,
+        attributes: OptimismPayloadAttributes,
+    ) -> Result<PayloadId, ExternalPayloadError> {
+        let updated: ForkchoiceUpdated = self
+            .request("engine_forkchoiceUpdatedV3", serde_json::json!([forkchoice, attributes]))
+            .await?;
+        updated
+            .payload_id
+            .ok_or_else(|| ExternalPayloadError::builder("builder didn't return a payload id"))
+    }
+
+    async fn get_payload(&self, id: PayloadId) -> Result<ExternalPayload, ExternalPayloadError> {
+        let envelope: OptimismExecutionPayloadEnvelopeV3 =
+            self.request("engine_getPayloadV3", serde_json::json!([id])).await?;
+        let block = try_into_sealed_block(
+            ExecutionPayload::V3(envelope.execution_payload),
+            Some(envelope.parent_beacon_block_root),
+        )?;
+        Ok(ExternalPayload { block, block_value: envelope.block_value })
+    }
+}
+
+/// Configuration of the external builder that payloads are selected from.
+#[derive(Debug, Clone)]
+pub struct ExternalBuilderConfig {
+    /// The source of external payloads
+    pub source: Arc<dyn ExternalPayloadSource>,
+    /// The policy that selects between the local and the external payload
+    pub policy: Arc<dyn PayloadSelectionPolicy<OptimismBuiltPayload>>,
+    /// How long to wait for the external payload when the payload is resolved
+    pub timeout: Duration,
+}
+
+impl ExternalBuilderConfig {
+    /// Creates a new [`ExternalBuilderConfig`] for the given source, which prefers its payloads.
+    pub fn new(source: impl ExternalPayloadSource) -> Self {
+        Self {
+            source: Arc::new(source),
+            policy: Arc::new(PreferExternal),
+            timeout: DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT,
+        }
+    }
+
+    /// Sets the policy that selects between the local and the external payload.
+    pub fn with_policy(
+        mut self,
+        policy: impl PayloadSelectionPolicy<OptimismBuiltPayload>,
+    ) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Sets how long to wait for the external payload when the payload is resolved.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Converts the payload builder attributes back into the attributes of the engine API.
+pub fn rpc_payload_attributes(
+    attributes: &OptimismPayloadBuilderAttributes,
+) -> OptimismPayloadAttributes {
+    let inner = &attributes.payload_attributes;
+    OptimismPayloadAttributes {
+        payload_attributes: PayloadAttributes {
+            timestamp: inner.timestamp,
+            prev_randao: inner.prev_randao,
+            suggested_fee_recipient: inner.suggested_fee_recipient,
+            withdrawals: Some(inner.withdrawals.clone().into_inner()),
+            parent_beacon_block_root: inner.parent_beacon_block_root,
+        },
+        transactions: Some(
+            attributes.transactions.iter().map(|tx| tx.envelope_encoded()).collect(),
+        ),
+        no_tx_pool: Some(attributes.no_tx_pool),
+        gas_limit: attributes.gas_limit,
+    }
+}
+
+/// Checks that the block of an external builder is built with the given payload attributes.
+///
+/// In addition to the fields of all payload attributes, see [`ensure_matches_attributes`], this
+/// checks the fee recipient and the fields of the OP Stack attributes. This only ensures that the
+/// builder didn't build a block for different attributes, for example one without the forced
+/// deposit transactions. The block itself is validated by [`execute_external_payload`].
+pub fn validate_external_payload(
+    attributes: &OptimismPayloadBuilderAttributes,
+    block: &SealedBlock,
+) -> Result<(), ExternalPayloadError> {
+    ensure_matches_attributes(block, attributes)?;
+    if block.beneficiary != attributes.payload_attributes.suggested_fee_recipient {
+        return Err(ExternalPayloadError::AttributesMismatch("fee recipient"))
+    }
+    if attributes.gas_limit.is_some_and(|gas_limit| block.gas_limit != gas_limit) {
+        return Err(ExternalPayloadError::AttributesMismatch("gas limit"))
+    }
+
+    let forced = attributes.transactions.iter().map(|tx| tx.hash());
+    if block.body.len() < attributes.transactions.len() ||
+        !forced.eq(block.body.iter().take(attributes.transactions.len()).map(|tx| tx.hash()))
+    {
+        return Err(ExternalPayloadError::AttributesMismatch("forced transactions"))
+    }
+    if attributes.no_tx_pool && block.body.len() != attributes.transactions.len() {
+        return Err(ExternalPayloadError::AttributesMismatch("no tx pool"))
+    }
+
+    Ok(())
+}
+
+/// Validates the block of an external builder like a new payload is validated: the block is
+/// checked against the consensus rules and executed on top of its parent, and the state root
+/// after the execution must match the state root of the block.
+///
+/// Caution: this is blocking and should be performed on a blocking task.
+pub fn execute_external_payload<Client>(
+    client: &Client,
+    chain_spec: Arc<ChainSpec>,
+    block: &SealedBlock,
+) -> Result<(), ExternalPayloadError>
+where
+    Client: StateProviderFactory + HeaderProvider,
+{
+    let consensus = OptimismBeaconConsensus::new(chain_spec.clone());
+    consensus
+        .validate_block_pre_execution(block)
+        .map_err(|err| ExternalPayloadError::InvalidBlock(err.to_string()))?;
+
+    let block = block.clone().unseal().with_recovered_senders().ok_or_else(|| {
+        ExternalPayloadError::InvalidBlock("invalid transaction signature".to_string())
+    })?;
+    let total_difficulty = client.header_td(&block.parent_hash)?.unwrap_or_default();
+    let state = client.state_by_block_hash(block.parent_hash)?;
+    let output = OpExecutorProvider::optimism(chain_spec)
+        .executor(StateProviderDatabase::new(&state))
+        .execute((&block, total_difficulty).into())
+        .map_err(|err| ExternalPayloadError::InvalidBlock(err.to_string()))?;
+    consensus
+        .validate_block_post_execution(
+            &block,
+            PostExecutionInput::new(&output.receipts, &output.requests),
+        )
+        .map_err(|err| ExternalPayloadError::InvalidBlock(err.to_string()))?;
+
+    let state_root = state.state_root(&output.state)?;
+    if state_root != block.state_root {
+        return Err(ExternalPayloadError::InvalidBlock(format!(
+            "state root mismatch: got {state_root}, expected {}",
+            block.state_root
+        )))
+    }
+
+    Ok(())
+}
+
+/// A [`PayloadJobGenerator`] that selects between the payloads of the jobs of a local generator
+/// and the payloads of an external builder.
+#[derive(Debug)]
+pub struct SelectingPayloadJobGenerator<Generator, Client, Tasks> {
+    /// The generator of local payload jobs
+    local: Generator,
+    /// Provides the safe and finalized blocks that are sent to the external builder, and the
+    /// state the external payloads are executed on
+    client: Client,
+    /// The task executor the requests to the external builder and the execution of its payloads
+    /// are spawned on
+    executor: Tasks,
+    /// The external builder
+    config: ExternalBuilderConfig,
+    /// The chain spec of the external payloads
+    chain_spec: Arc<ChainSpec>,
+    /// Metrics of the payload selection
+    metrics: PayloadSelectionMetrics,
+}
+
+impl<Generator, Client, Tasks> SelectingPayloadJobGenerator<Generator, Client, Tasks> {
+    /// Creates a new [`SelectingPayloadJobGenerator`] that wraps the given generator of local
+    /// payload jobs.
+    pub fn new(
+        local: Generator,
+        client: Client,
+        executor: Tasks,
+        config: ExternalBuilderConfig,
+        chain_spec: Arc<ChainSpec>,
+    ) -> Self {
+        Self { local, client, executor, config, chain_spec, metrics: Default::default() }
+    }
+}
+
+impl<Generator, Client, Tasks> PayloadJobGenerator
+    for SelectingPayloadJobGenerator<Generator, Client, Tasks>
+where
+    Generator: PayloadJobGenerator,
+    Generator::Job: PayloadJob<
+            PayloadAttributes = OptimismPayloadBuilderAttributes,
+            BuiltPayload = OptimismBuiltPayload,
+        > + Unpin,
+    Client: StateProviderFactory + HeaderProvider + Clone + Unpin + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
+{
+    type Job = SelectingPayloadJob<Generator::Job, Client, Tasks>;
+
+    fn new_payload_job(
+        &self,
+        attributes: OptimismPayloadBuilderAttributes,
+    ) -> Result<Self::Job, PayloadBuilderError> {
+        let local = self.local.new_payload_job(attributes.clone())?;
+
+        let forkchoice = ForkchoiceState {
+            head_block_hash: attributes.payload_attributes.parent,
+            safe_block_hash: self.client.safe_block_hash()?.unwrap_or_default(),
+            finalized_block_hash: self.client.finalized_block_hash()?.unwrap_or_default(),
+        };
+        let source = self.config.source.clone();
+        let rpc_attributes = rpc_payload_attributes(&attributes);
+        let (tx, external) = oneshot::channel();
+        self.executor.spawn(Box::pin(async move {
+            let _ = tx.send(source.start_payload(forkchoice, rpc_attributes).await);
+        }));
+
+        Ok(SelectingPayloadJob {
+            local,
+            external: Some(external),
+            client: self.client.clone(),
+            executor: self.executor.clone(),
+            attributes,
+            config: self.config.clone(),
+            chain_spec: self.chain_spec.clone(),
+            metrics: self.metrics.clone(),
+        })
+    }
+
+    fn on_new_state(&mut self, new_state: CanonStateNotification) {
+        self.local.on_new_state(new_state)
+    }
+}
+
+/// A [`PayloadJob`] that resolves to the payload of its local job or of the external builder,
+/// as selected by the [`PayloadSelectionPolicy`].
+#[derive(Debug)]
+pub struct SelectingPayloadJob<Job, Client, Tasks> {
+    /// The local payload job
+    local: Job,
+    /// Resolves to the id of the payload at the external builder, until the job is resolved
+    external: Option<oneshot::Receiver<Result<PayloadId, ExternalPayloadError>>>,
+    /// Provides the state the external payload is executed on
+    client: Client,
+    /// The task executor the external payload is fetched and executed on
+    executor: Tasks,
+    /// The attributes of the payload
+    attributes: OptimismPayloadBuilderAttributes,
+    /// The external builder
+    config: ExternalBuilderConfig,
+    /// The chain spec of the external payloads
+    chain_spec: Arc<ChainSpec>,
+    /// Metrics of the payload selection
+    metrics: PayloadSelectionMetrics,
+}
+
+impl<Job, Client, Tasks> Future for SelectingPayloadJob<Job, Client, Tasks>
+where
+    Job: PayloadJob + Unpin,
+    Client: Unpin,
+    Tasks: Unpin,
+{
+    type Output = Result<(), PayloadBuilderError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.get_mut().local).poll(cx)
+    }
+}
+
+impl<Job, Client, Tasks> PayloadJob for SelectingPayloadJob<Job, Client, Tasks>
+where
+    Job: PayloadJob<
+            PayloadAttributes = OptimismPayloadBuilderAttributes,
+            BuiltPayload = OptimismBuiltPayload,
+        > + Unpin,
+    Client: StateProviderFactory + HeaderProvider + Clone + Unpin + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
+{
+    type PayloadAttributes = OptimismPayloadBuilderAttributes;
+    type ResolvePayloadFuture = ResolveSelectedPayload;
+    type BuiltPayload = OptimismBuiltPayload;
+
+    fn best_payload(&self) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+        self.local.best_payload()
+    }
+
+    fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError> {
+        self.local.payload_attributes()
+    }
+
+    fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
+        let (local, keep_alive) = self.local.resolve();
+
+        // fetch the external payload in the background, while the local payload is resolved
+        let external = self.external.take().map(|started| {
+            let source = self.config.source.clone();
+            let timeout = self.config.timeout;
+            let (tx, rx) = oneshot::channel();
+            self.executor.spawn(Box::pin(async move {
+                let fetch = async {
+                    let id = started.await.map_err(|_| {
+                        ExternalPayloadError::builder("payload wasn't started at the builder")
+                    })??;
+                    source.get_payload(id).await
+                };
+                let res = tokio::time::timeout(timeout, fetch)
+                    .await
+                    .unwrap_or(Err(ExternalPayloadError::Timeout));
+                let _ = tx.send(res);
+            }));
+            rx
+        });
+
+        let client = self.client.clone();
+        let executor = self.executor.clone();
+        let attributes = self.attributes.clone();
+        let policy = self.config.policy.clone();
+        let chain_spec = self.chain_spec.clone();
+        let metrics = self.metrics.clone();
+        let fut = async move {
+            let local = local.await;
+            let Some(external) = external else { return local };
+
+            let external = external
+                .await
+                .map_err(|_| ExternalPayloadError::Timeout)
+                .and_then(|res| res)
+                .and_then(|payload| {
+                    validate_external_payload(&attributes, &payload.block)?;
+                    Ok(payload)
+                });
+            let external = match external {
+                Ok(payload) => OptimismBuiltPayload::new(
+                    attributes.payload_attributes.id,
+                    payload.block,
+                    payload.block_value,
+                    chain_spec.clone(),
+                    attributes,
+                ),
+                Err(err) => {
+                    tracing::warn!(
+                        target: "payload_builder",
+                        %err,
+                        "Failed to get payload from external builder, using local payload"
+                    );
+                    metrics.external_payload_failures.increment(1);
+                    metrics.local_payloads.increment(1);
+                    return local
+                }
+            };
+
+            let source = match &local {
+                Ok(local) => policy.select(local, &external),
+                Err(err) => {
+                    tracing::warn!(
+                        target: "payload_builder",
+                        %err,
+                        "Failed to resolve local payload, using external payload"
+                    );
+                    PayloadSource::External
+                }
+            };
+            tracing::debug!(
+                target: "payload_builder",
+                ?source,
+                block_hash = %external.block().hash(),
+                "Selected payload"
+            );
+            if source == PayloadSource::Local {
+                metrics.local_payloads.increment(1);
+                return local
+            }
+
+            // the external payload is only returned if it can be inserted as the next block
+            let (tx, rx) = oneshot::channel();
+            let block = external.block().clone();
+            executor.spawn_blocking(Box::pin(async move {
+                let _ = tx.send(execute_external_payload(&client, chain_spec, &block));
+            }));
+            let executed = rx.await.unwrap_or_else(|_| {
+                Err(ExternalPayloadError::InvalidBlock("execution was cancelled".to_string()))
+            });
+            match executed {
+                Ok(()) => {
+                    metrics.external_payloads.increment(1);
+                    Ok(external)
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        target: "payload_builder",
+                        %err,
+                        block_hash = %external.block().hash(),
+                        "Invalid payload from external builder, using local payload"
+                    );
+                    metrics.external_payload_failures.increment(1);
+                    metrics.local_payloads.increment(1);
+                    local
+                }
+            }
+        };
+
+        (Box::pin(fut), keep_alive)
+    }
+}
+
+/// The future that resolves the payload selected by a [`SelectingPayloadJob`].
+pub type ResolveSelectedPayload =
+    Pin<Box<dyn Future<Output = Result<OptimismBuiltPayload, PayloadBuilderError>> + Send + Sync>>;
+
+/// Metrics of the selection between local and external payloads.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "optimism.payload_source")]
+struct PayloadSelectionMetrics {
+    /// Number of payloads of the local builder returned to the sequencer
+    local_payloads: Counter,
+    /// Number of payloads of the external builder returned to the sequencer
+    external_payloads: Counter,
+    /// Number of payloads that couldn't be fetched from the external builder or were invalid
+    external_payload_failures: Counter,
+}
+
+/// The response of the builder.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<ErrorObjectOwned>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_api::PayloadBuilderAttributes;
+    use reth_payload_builder::EthPayloadBuilderAttributes;
+    use reth_primitives::{
+        Address, Header, SealedHeader, Signature, Transaction, TransactionSigned, TxDeposit,
+        TxLegacy, B256,
+    };
+
+    fn deposit() -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Deposit(TxDeposit {
+                source_hash: B256::with_last_byte(1),
+                ..Default::default()
+            }),
+            Signature::optimism_deposit_tx_signature(),
+        )
+    }
+
+    fn attributes(no_tx_pool: bool) -> OptimismPayloadBuilderAttributes {
+        OptimismPayloadBuilderAttributes {
+            payload_attributes: EthPayloadBuilderAttributes {
+                id: PayloadId::new([1; 8]),
+                parent: B256::with_last_byte(1),
+                timestamp: 2,
+                suggested_fee_recipient: Address::with_last_byte(3),
+                prev_randao: B256::with_last_byte(4),
+                withdrawals: Default::default(),
+                parent_beacon_block_root: Some(B256::ZERO),
+            },
+            no_tx_pool,
+            transactions: vec![deposit()],
+            gas_limit: Some(30_000_000),
+        }
+    }
+
+    fn block(body: Vec<TransactionSigned>) -> SealedBlock {
+        let header = Header {
+            parent_hash: B256::with_last_byte(1),
+            timestamp: 2,
+            beneficiary: Address::with_last_byte(3),
+            mix_hash: B256::with_last_byte(4),
+            gas_limit: 30_000_000,
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        SealedBlock { header: SealedHeader::new(header, B256::ZERO), body, ..Default::default() }
+    }
+
+    #[test]
+    fn validates_external_payload() {
+        let pool_tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy::default()),
+            Signature::default(),
+        );
+
+        assert!(validate_external_payload(&attributes(false), &block(vec![deposit()])).is_ok());
+        assert!(validate_external_payload(
+            &attributes(false),
+            &block(vec![deposit(), pool_tx.clone()])
+        )
+        .is_ok());
+
+        // the deposit is missing
+        assert!(matches!(
+            validate_external_payload(&attributes(false), &block(vec![pool_tx.clone()])),
+            Err(ExternalPayloadError::AttributesMismatch("forced transactions"))
+        ));
+        // pool transactions aren't allowed
+        assert!(matches!(
+            validate_external_payload(&attributes(true), &block(vec![deposit(), pool_tx])),
+            Err(ExternalPayloadError::AttributesMismatch("no tx pool"))
+        ));
+
+        let mut wrong_parent = block(vec![deposit()]);
+        wrong_parent.header = SealedHeader::new(
+            Header { parent_hash: B256::ZERO, ..wrong_parent.header.header().clone() },
+            B256::ZERO,
+        );
+        assert!(matches!(
+            validate_external_payload(&attributes(false), &wrong_parent),
+            Err(ExternalPayloadError::AttributesMismatch("parent hash"))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_external_block() {
+        let chain_spec = reth_chainspec::OP_MAINNET.clone();
+        // the transactions root of the block doesn't match its transactions
+        let err = execute_external_payload(
+            &reth_provider::test_utils::NoopProvider::default(),
+            chain_spec,
+            &block(vec![deposit()]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ExternalPayloadError::InvalidBlock(_)));
+    }
+
+    #[test]
+    fn converts_attributes() {
+        let attributes = attributes(true);
+        let rpc = rpc_payload_attributes(&attributes);
+        assert_eq!(rpc.payload_attributes.timestamp, 2);
+        assert_eq!(rpc.transactions, Some(vec![deposit().envelope_encoded()]));
+        assert_eq!(rpc.no_tx_pool, Some(true));
+        assert_eq!(rpc.gas_limit, Some(30_000_000));
+
+        // the builder derives the same payload id
+        let parent = attributes.payload_attributes.parent;
+        let rederived = OptimismPayloadBuilderAttributes::try_new(parent, rpc).unwrap();
+        assert_eq!(rederived.transactions, attributes.transactions);
+        assert_eq!(rederived.no_tx_pool, attributes.no_tx_pool);
+    }
+}
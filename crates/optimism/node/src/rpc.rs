@@ -1,23 +1,58 @@
 //! Helpers for optimism specific RPC implementations.
 
-use jsonrpsee::types::ErrorObject;
-use reqwest::Client;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObject};
+use parking_lot::Mutex;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Certificate, Client,
+};
+use reth_metrics::{
+    metrics::{Counter, Gauge, Histogram},
+    Metrics,
+};
+use reth_primitives::PooledTransactionsElement;
 use reth_rpc::eth::{
     error::{EthApiError, EthResult},
     traits::RawTransactionForwarder,
 };
 use reth_rpc_types::ToRpcError;
-use std::sync::{atomic::AtomicUsize, Arc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tx_status::TransactionStatusTracker;
+
+pub mod conditional;
+pub mod historical;
+pub mod interop;
+pub mod preconf;
+pub mod rollup;
+pub mod tx_status;
 
 /// Error type when interacting with the Sequencer
 #[derive(Debug, thiserror::Error)]
 pub enum SequencerRpcError {
     /// Wrapper around an [`reqwest::Error`].
-    #[error(transparent)]
+    #[error("failed to forward transaction to sequencer: {0}")]
     HttpError(#[from] reqwest::Error),
     /// Thrown when serializing transaction to forward to sequencer
     #[error("invalid sequencer transaction")]
     InvalidSequencerTransaction,
+    /// Thrown when forwarding is suspended after too many consecutive failures
+    #[error("sequencer unavailable, forwarding is suspended")]
+    CircuitOpen,
+    /// Thrown when no sequencer endpoint was reachable by the last health check
+    #[error("sequencer unreachable")]
+    Unreachable,
+    /// Thrown when the HTTP client can't be built from the [`SequencerHttpConfig`]
+    #[error("invalid sequencer http config: {0}")]
+    InvalidHttpConfig(String),
 }
 
 impl ToRpcError for SequencerRpcError {
@@ -36,32 +71,420 @@ impl From<SequencerRpcError> for EthApiError {
     }
 }
 
+/// Default timeout of a request to the sequencer, including connecting.
+pub const DEFAULT_SEQUENCER_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default timeout for connecting to the sequencer.
+pub const DEFAULT_SEQUENCER_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A header that is sent with every request to the sequencer.
+///
+/// The value is redacted in the [`Debug`] output, since headers may contain credentials.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SequencerHeader {
+    /// The name of the header.
+    pub name: String,
+    /// The value of the header.
+    pub value: String,
+}
+
+impl SequencerHeader {
+    /// Creates a new header.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { name: name.into(), value: value.into() }
+    }
+}
+
+impl fmt::Debug for SequencerHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SequencerHeader")
+            .field("name", &self.name)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// HTTP options of the connection to the sequencer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SequencerHttpConfig {
+    /// Headers that are sent with every request, for example to authenticate with a gateway.
+    pub headers: Vec<SequencerHeader>,
+    /// Timeout of a single request, including connecting.
+    ///
+    /// Defaults to [`DEFAULT_SEQUENCER_REQUEST_TIMEOUT`].
+    pub timeout: Option<Duration>,
+    /// Maximum number of idle connections that are kept open per endpoint.
+    pub max_idle_connections: Option<usize>,
+    /// Path to a PEM encoded CA certificate that is trusted in addition to the native roots.
+    pub ca_certificate: Option<PathBuf>,
+}
+
+impl SequencerHttpConfig {
+    /// Adds a header that is sent with every request.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push(SequencerHeader::new(name, value));
+        self
+    }
+
+    /// Sets the request timeout.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of idle connections per endpoint.
+    pub const fn with_max_idle_connections(mut self, max_idle_connections: usize) -> Self {
+        self.max_idle_connections = Some(max_idle_connections);
+        self
+    }
+
+    /// Sets the path of an additional trusted CA certificate.
+    pub fn with_ca_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certificate = Some(path.into());
+        self
+    }
+
+    /// Builds the HTTP client for the sequencer.
+    pub fn build_client(&self) -> Result<Client, SequencerRpcError> {
+        let mut headers = HeaderMap::with_capacity(self.headers.len());
+        for SequencerHeader { name, value } in &self.headers {
+            let name = HeaderName::try_from(name.as_str()).map_err(|err| {
+                SequencerRpcError::InvalidHttpConfig(format!("header name {name:?}: {err}"))
+            })?;
+            let mut value = HeaderValue::try_from(value.as_str()).map_err(|err| {
+                SequencerRpcError::InvalidHttpConfig(format!("value of header {name}: {err}"))
+            })?;
+            // headers may contain credentials, which must not be logged
+            value.set_sensitive(true);
+            headers.append(name, value);
+        }
+
+        let timeout = self.timeout.unwrap_or(DEFAULT_SEQUENCER_REQUEST_TIMEOUT);
+        let mut builder = Client::builder()
+            .use_rustls_tls()
+            .default_headers(headers)
+            .timeout(timeout)
+            .connect_timeout(timeout.min(DEFAULT_SEQUENCER_CONNECT_TIMEOUT));
+        if let Some(max_idle_connections) = self.max_idle_connections {
+            builder = builder.pool_max_idle_per_host(max_idle_connections);
+        }
+        if let Some(path) = &self.ca_certificate {
+            let pem = std::fs::read(path).map_err(|err| {
+                SequencerRpcError::InvalidHttpConfig(format!(
+                    "failed to read CA certificate {}: {err}",
+                    path.display()
+                ))
+            })?;
+            let certificate = Certificate::from_pem(&pem).map_err(|err| {
+                SequencerRpcError::InvalidHttpConfig(format!(
+                    "invalid CA certificate {}: {err}",
+                    path.display()
+                ))
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        builder.build().map_err(|err| SequencerRpcError::InvalidHttpConfig(err.to_string()))
+    }
+}
+
+/// Default number of times forwarding is retried after all sequencer endpoints failed.
+pub const DEFAULT_SEQUENCER_MAX_RETRIES: usize = 3;
+
+/// Default backoff before the first retry, this is doubled for every subsequent retry.
+pub const DEFAULT_SEQUENCER_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Default upper bound of the backoff between retries.
+pub const DEFAULT_SEQUENCER_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Default duration an endpoint is considered unhealthy after a failed request.
+pub const DEFAULT_SEQUENCER_UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Configures how the [`SequencerClient`] retries failed requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencerRetryConfig {
+    /// How often forwarding is retried after all endpoints failed.
+    pub max_retries: usize,
+    /// The backoff before the first retry, this is doubled for every subsequent retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the backoff between retries.
+    pub max_backoff: Duration,
+    /// How long an endpoint is deprioritized after a failed request.
+    pub unhealthy_cooldown: Duration,
+}
+
+impl Default for SequencerRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_SEQUENCER_MAX_RETRIES,
+            initial_backoff: DEFAULT_SEQUENCER_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_SEQUENCER_MAX_BACKOFF,
+            unhealthy_cooldown: DEFAULT_SEQUENCER_UNHEALTHY_COOLDOWN,
+        }
+    }
+}
+
+impl SequencerRetryConfig {
+    /// Sets how often forwarding is retried after all endpoints failed.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the backoff before the first retry.
+    pub const fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound of the backoff between retries.
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets how long an endpoint is deprioritized after a failed request.
+    pub const fn with_unhealthy_cooldown(mut self, unhealthy_cooldown: Duration) -> Self {
+        self.unhealthy_cooldown = unhealthy_cooldown;
+        self
+    }
+}
+
+/// Default interval at which the endpoints of the sequencer are probed.
+pub const DEFAULT_SEQUENCER_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Timeout of a health check of an endpoint, after which the endpoint is considered unreachable.
+pub const SEQUENCER_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default number of consecutive forwarding failures after which forwarding is suspended.
+pub const DEFAULT_SEQUENCER_CIRCUIT_BREAKER_THRESHOLD: usize = 5;
+
+/// Default duration forwarding is suspended for.
+pub const DEFAULT_SEQUENCER_CIRCUIT_BREAKER_OPEN_DURATION: Duration = Duration::from_secs(30);
+
+/// Configures the circuit breaker of the [`SequencerClient`].
+///
+/// After the configured number of consecutive failures, forwarding is suspended for a while. Once
+/// that time passed, the next transaction is forwarded again: if that succeeds, forwarding is
+/// resumed, otherwise it is suspended again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequencerCircuitBreakerConfig {
+    /// The number of consecutive failures after which forwarding is suspended.
+    ///
+    /// `0` disables the circuit breaker.
+    pub failure_threshold: usize,
+    /// How long forwarding is suspended for.
+    pub open_duration: Duration,
+}
+
+impl Default for SequencerCircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: DEFAULT_SEQUENCER_CIRCUIT_BREAKER_THRESHOLD,
+            open_duration: DEFAULT_SEQUENCER_CIRCUIT_BREAKER_OPEN_DURATION,
+        }
+    }
+}
+
+impl SequencerCircuitBreakerConfig {
+    /// Sets the number of consecutive failures after which forwarding is suspended.
+    pub const fn with_failure_threshold(mut self, failure_threshold: usize) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Sets how long forwarding is suspended for.
+    pub const fn with_open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+}
+
+/// The state of the circuit breaker of the [`SequencerClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CircuitBreakerState {
+    /// Transactions are forwarded.
+    Closed,
+    /// Forwarding is suspended.
+    Open,
+    /// Forwarding was suspended, the next transaction is forwarded to probe the sequencer.
+    HalfOpen,
+}
+
+/// The status of the [`SequencerClient`], as returned by `sequencer_status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerStatus {
+    /// The state of the circuit breaker.
+    pub circuit_breaker: CircuitBreakerState,
+    /// The number of consecutive forwarding failures.
+    pub consecutive_failures: usize,
+    /// The number of milliseconds forwarding remains suspended for, if it is.
+    pub open_remaining_ms: Option<u64>,
+    /// Whether no endpoint was reachable by the last health check, in which case transactions
+    /// are not forwarded.
+    pub degraded: bool,
+    /// The endpoints, in order of priority.
+    pub endpoints: Vec<SequencerEndpointStatus>,
+}
+
+/// The status of an endpoint of the [`SequencerClient`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerEndpointStatus {
+    /// The URL of the endpoint.
+    pub url: String,
+    /// Whether the endpoint is considered healthy.
+    pub healthy: bool,
+    /// Whether the endpoint was reachable by the last health check, if it was probed.
+    pub reachable: Option<bool>,
+    /// The latency of the last health check, in milliseconds.
+    pub latency_ms: Option<u64>,
+}
+
+/// RPC interface to inspect the [`SequencerClient`].
+#[rpc(server, namespace = "sequencer")]
+pub trait SequencerApi {
+    /// Returns the status of the sequencer client.
+    #[method(name = "status")]
+    fn status(&self) -> RpcResult<SequencerStatus>;
+}
+
+/// How a failure to forward a transaction to the sequencer is reported to the RPC caller.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForwardingFailureMode {
+    /// `eth_sendRawTransaction` fails with an error, so that the caller knows that the
+    /// transaction may not reach the sequencer.
+    #[default]
+    Error,
+    /// The failure is logged and the transaction is only submitted to the local pool.
+    ///
+    /// This also applies while forwarding is suspended by the circuit breaker.
+    Warn,
+}
+
 /// A client to interact with a Sequencer
+///
+/// The client can be configured with fallback endpoints. Transactions are forwarded to the first
+/// healthy endpoint in order of priority, an endpoint is considered unhealthy for a while after a
+/// request to it failed. If all endpoints fail, forwarding is retried with exponential backoff.
 #[derive(Debug, Clone)]
 pub struct SequencerClient {
     inner: Arc<SequencerClientInner>,
+    retry_config: SequencerRetryConfig,
+    circuit_breaker_config: SequencerCircuitBreakerConfig,
+    failure_mode: ForwardingFailureMode,
+    forward_only: bool,
+    status_tracker: Option<TransactionStatusTracker>,
 }
 
 impl SequencerClient {
     /// Creates a new [`SequencerClient`].
     pub fn new(sequencer_endpoint: impl Into<String>) -> Self {
-        let client = Client::builder().use_rustls_tls().build().unwrap();
-        Self::with_client(sequencer_endpoint, client)
+        Self::new_with_fallbacks(sequencer_endpoint, Vec::<String>::new())
+    }
+
+    /// Creates a new [`SequencerClient`] with the given fallback endpoints, in order of priority.
+    pub fn new_with_fallbacks(
+        sequencer_endpoint: impl Into<String>,
+        fallback_endpoints: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let client = Client::builder()
+            .use_rustls_tls()
+            .timeout(DEFAULT_SEQUENCER_REQUEST_TIMEOUT)
+            .connect_timeout(DEFAULT_SEQUENCER_CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+        Self::with_client_and_fallbacks(sequencer_endpoint, fallback_endpoints, client)
+    }
+
+    /// Creates a new [`SequencerClient`] with the given fallback endpoints, in order of priority,
+    /// and HTTP options.
+    pub fn new_with_http_config(
+        sequencer_endpoint: impl Into<String>,
+        fallback_endpoints: impl IntoIterator<Item = impl Into<String>>,
+        http_config: &SequencerHttpConfig,
+    ) -> Result<Self, SequencerRpcError> {
+        let client = http_config.build_client()?;
+        Ok(Self::with_client_and_fallbacks(sequencer_endpoint, fallback_endpoints, client))
     }
 
     /// Creates a new [`SequencerClient`].
     pub fn with_client(sequencer_endpoint: impl Into<String>, http_client: Client) -> Self {
+        Self::with_client_and_fallbacks(sequencer_endpoint, Vec::<String>::new(), http_client)
+    }
+
+    /// Creates a new [`SequencerClient`] with the given fallback endpoints, in order of priority.
+    pub fn with_client_and_fallbacks(
+        sequencer_endpoint: impl Into<String>,
+        fallback_endpoints: impl IntoIterator<Item = impl Into<String>>,
+        http_client: Client,
+    ) -> Self {
+        let endpoints = std::iter::once(sequencer_endpoint.into())
+            .chain(fallback_endpoints.into_iter().map(Into::into))
+            .map(SequencerEndpoint::new)
+            .collect();
         let inner = SequencerClientInner {
-            sequencer_endpoint: sequencer_endpoint.into(),
+            endpoints,
             http_client,
             id: AtomicUsize::new(0),
+            circuit_breaker: Default::default(),
+            degraded: AtomicBool::new(false),
+            metrics: Default::default(),
         };
-        Self { inner: Arc::new(inner) }
+        Self {
+            inner: Arc::new(inner),
+            retry_config: SequencerRetryConfig::default(),
+            circuit_breaker_config: SequencerCircuitBreakerConfig::default(),
+            failure_mode: ForwardingFailureMode::default(),
+            forward_only: false,
+            status_tracker: None,
+        }
+    }
+
+    /// Sets how failed requests are retried.
+    pub const fn with_retry_config(mut self, retry_config: SequencerRetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Sets when forwarding is suspended after consecutive failures.
+    pub const fn with_circuit_breaker_config(
+        mut self,
+        circuit_breaker_config: SequencerCircuitBreakerConfig,
+    ) -> Self {
+        self.circuit_breaker_config = circuit_breaker_config;
+        self
+    }
+
+    /// Sets how a failure to forward a transaction is reported to the RPC caller.
+    pub const fn with_failure_mode(mut self, failure_mode: ForwardingFailureMode) -> Self {
+        self.failure_mode = failure_mode;
+        self
+    }
+
+    /// Sets whether forwarded transactions are only sent to the sequencer, without also being
+    /// submitted to the local pool.
+    pub const fn with_forward_only(mut self, forward_only: bool) -> Self {
+        self.forward_only = forward_only;
+        self
+    }
+
+    /// Sets the tracker that is notified about the outcome of forwarded transactions.
+    pub fn with_status_tracker(mut self, status_tracker: TransactionStatusTracker) -> Self {
+        self.status_tracker = Some(status_tracker);
+        self
     }
 
-    /// Returns the network of the client
+    /// Returns the primary endpoint of the client
     pub fn endpoint(&self) -> &str {
-        &self.inner.sequencer_endpoint
+        &self.inner.endpoints[0].url
+    }
+
+    /// Returns all endpoints of the client, in order of priority.
+    pub fn endpoints(&self) -> impl Iterator<Item = &str> + '_ {
+        self.inner.endpoints.iter().map(|endpoint| endpoint.url.as_str())
     }
 
     /// Returns the client
@@ -71,11 +494,219 @@ impl SequencerClient {
 
     /// Returns the next id for the request
     fn next_request_id(&self) -> usize {
-        self.inner.id.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+        self.inner.id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// Returns the endpoints in the order they should be tried: healthy endpoints first, then
+    /// unhealthy endpoints, each in order of priority.
+    fn endpoints_by_health(&self) -> Vec<&SequencerEndpoint> {
+        let now = Instant::now();
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) =
+            self.inner.endpoints.iter().partition(|endpoint| endpoint.is_healthy(now));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Returns the current status of the client.
+    pub fn status(&self) -> SequencerStatus {
+        let now = Instant::now();
+        let circuit_breaker = self.inner.circuit_breaker.lock();
+        let open_remaining =
+            circuit_breaker.open_until.and_then(|until| until.checked_duration_since(now));
+        let state = if open_remaining.is_some() {
+            CircuitBreakerState::Open
+        } else if circuit_breaker.open_until.is_some() {
+            CircuitBreakerState::HalfOpen
+        } else {
+            CircuitBreakerState::Closed
+        };
+
+        SequencerStatus {
+            circuit_breaker: state,
+            consecutive_failures: circuit_breaker.consecutive_failures,
+            open_remaining_ms: open_remaining.map(|remaining| remaining.as_millis() as u64),
+            degraded: self.is_degraded(),
+            endpoints: self
+                .inner
+                .endpoints
+                .iter()
+                .map(|endpoint| {
+                    let probe = *endpoint.last_probe.lock();
+                    SequencerEndpointStatus {
+                        url: endpoint.url.clone(),
+                        healthy: endpoint.is_healthy(now),
+                        reachable: probe.map(|probe| probe.reachable),
+                        latency_ms: probe.map(|probe| probe.latency.as_millis() as u64),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if forwarding is currently suspended by the circuit breaker.
+    fn is_circuit_open(&self) -> bool {
+        self.inner.circuit_breaker.lock().open_until.is_some_and(|until| until > Instant::now())
+    }
+
+    /// Records the outcome of forwarding a transaction with the circuit breaker.
+    fn on_forward_outcome(&self, success: bool) {
+        let mut circuit_breaker = self.inner.circuit_breaker.lock();
+        if success {
+            *circuit_breaker = CircuitBreaker::default();
+        } else {
+            circuit_breaker.consecutive_failures += 1;
+            let threshold = self.circuit_breaker_config.failure_threshold;
+            if threshold > 0 && circuit_breaker.consecutive_failures >= threshold {
+                if circuit_breaker.open_until.is_none() {
+                    tracing::warn!(
+                        target: "rpc::eth",
+                        failures = circuit_breaker.consecutive_failures,
+                        "Suspending forwarding of transactions to sequencer"
+                    );
+                }
+                circuit_breaker.open_until =
+                    Some(Instant::now() + self.circuit_breaker_config.open_duration);
+            }
+        }
+        self.inner.metrics.circuit_open.set(circuit_breaker.open_until.is_some() as u8 as f64);
+    }
+
+    /// Returns `true` if no endpoint was reachable by the last health check.
+    fn is_degraded(&self) -> bool {
+        self.inner.degraded.load(Ordering::Relaxed)
+    }
+
+    /// Probes all endpoints with an `eth_chainId` request and updates their health.
+    ///
+    /// An endpoint that doesn't answer within [`SEQUENCER_HEALTH_CHECK_TIMEOUT`] is considered
+    /// unreachable.
+    ///
+    /// If no endpoint is reachable, transactions are not forwarded until the next health check
+    /// that reaches an endpoint, and the [`ForwardingFailureMode`] determines whether they are
+    /// rejected or only submitted to the local pool.
+    ///
+    /// Returns the number of reachable endpoints.
+    pub async fn check_health(&self) -> usize {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "eth_chainId",
+            "params": [],
+            "id": self.next_request_id()
+        })
+        .to_string();
+
+        let mut reachable = 0;
+        for endpoint in &self.inner.endpoints {
+            let start = Instant::now();
+            let res =
+                self.send(&endpoint.url, body.clone(), Some(SEQUENCER_HEALTH_CHECK_TIMEOUT)).await;
+            let latency = start.elapsed();
+            self.inner.metrics.health_check_latency.record(latency);
+
+            match &res {
+                Ok(()) => {
+                    reachable += 1;
+                    endpoint.mark_healthy();
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        target: "rpc::eth",
+                        %err,
+                        endpoint = %endpoint.url,
+                        "Sequencer health check failed"
+                    );
+                    endpoint.mark_unhealthy(self.retry_config.unhealthy_cooldown);
+                }
+            }
+            *endpoint.last_probe.lock() = Some(EndpointProbe { reachable: res.is_ok(), latency });
+        }
+
+        let degraded = reachable == 0;
+        if self.inner.degraded.swap(degraded, Ordering::Relaxed) != degraded {
+            if degraded {
+                tracing::warn!(
+                    target: "rpc::eth",
+                    "Sequencer unreachable, not forwarding transactions"
+                );
+            } else {
+                tracing::info!(
+                    target: "rpc::eth",
+                    "Sequencer reachable again, resuming forwarding"
+                );
+            }
+        }
+        self.inner.metrics.reachable_endpoints.set(reachable as f64);
+        self.inner.metrics.degraded.set(degraded as u8 as f64);
+
+        reachable
+    }
+
+    /// Checks the health of the endpoints at the given interval, until the returned future is
+    /// dropped.
+    pub async fn run_health_checks(self, interval: Duration) {
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.check_health().await;
+        }
     }
 
     /// Forwards a transaction to the sequencer endpoint.
+    ///
+    /// The transaction is sent to the endpoints in order of health and priority until one of them
+    /// accepts the request. If all endpoints fail, this is retried with backoff up to the
+    /// configured number of retries.
+    ///
+    /// Returns [`SequencerRpcError::CircuitOpen`] without forwarding while forwarding is suspended
+    /// by the circuit breaker, and [`SequencerRpcError::Unreachable`] if no endpoint was reachable
+    /// by the last health check.
     pub async fn forward_raw_transaction(&self, tx: &[u8]) -> Result<(), SequencerRpcError> {
+        let tracked = self.status_tracker.as_ref().and_then(|tracker| {
+            let tx = PooledTransactionsElement::decode_enveloped(&mut &tx[..]).ok()?;
+            Some((tracker, *tx.hash()))
+        });
+
+        let skipped = if self.is_degraded() {
+            Some(SequencerRpcError::Unreachable)
+        } else if self.is_circuit_open() {
+            Some(SequencerRpcError::CircuitOpen)
+        } else {
+            None
+        };
+        if let Some(err) = skipped {
+            self.inner.metrics.skipped_forwards.increment(1);
+            if let Some((tracker, hash)) = tracked {
+                tracker.on_forwarding_failed(hash);
+            }
+            return Err(err)
+        }
+
+        if let Some((tracker, hash)) = tracked {
+            tracker.on_forwarded(hash);
+        }
+
+        let start = Instant::now();
+        let res = self.try_forward_raw_transaction(tx).await;
+        self.inner.metrics.forward_latency.record(start.elapsed());
+
+        match &res {
+            Ok(()) => self.inner.metrics.forwarded_transactions.increment(1),
+            Err(_) => self.inner.metrics.failed_forwards.increment(1),
+        }
+        self.on_forward_outcome(res.is_ok());
+        if let Some((tracker, hash)) = tracked {
+            match &res {
+                Ok(()) => tracker.on_acknowledged(hash),
+                Err(_) => tracker.on_forwarding_failed(hash),
+            }
+        }
+
+        res
+    }
+
+    /// Forwards a transaction to the sequencer endpoints, with retries.
+    async fn try_forward_raw_transaction(&self, tx: &[u8]) -> Result<(), SequencerRpcError> {
         let body = serde_json::to_string(&serde_json::json!({
             "jsonrpc": "2.0",
             "method": "eth_sendRawTransaction",
@@ -90,13 +721,56 @@ impl SequencerClient {
             SequencerRpcError::InvalidSequencerTransaction
         })?;
 
-        self.http_client()
-            .post(self.endpoint())
+        let mut backoff = self.retry_config.initial_backoff;
+        let mut retries = 0;
+        loop {
+            let mut last_err = None;
+            for endpoint in self.endpoints_by_health() {
+                match self.send(&endpoint.url, body.clone(), None).await {
+                    Ok(()) => {
+                        endpoint.mark_healthy();
+                        return Ok(())
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            target: "rpc::eth",
+                            %err,
+                            endpoint = %endpoint.url,
+                            "Failed to forward transaction to sequencer"
+                        );
+                        endpoint.mark_unhealthy(self.retry_config.unhealthy_cooldown);
+                        last_err = Some(err);
+                    }
+                }
+            }
+
+            if retries >= self.retry_config.max_retries {
+                return Err(last_err.expect("client has at least one endpoint"))
+            }
+            retries += 1;
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.retry_config.max_backoff);
+        }
+    }
+
+    /// Sends the request body to the given endpoint.
+    ///
+    /// The timeout overrides the timeout of the HTTP client for this request.
+    async fn send(
+        &self,
+        endpoint: &str,
+        body: String,
+        timeout: Option<Duration>,
+    ) -> Result<(), SequencerRpcError> {
+        let mut request = self
+            .http_client()
+            .post(endpoint)
             .header(reqwest::header::CONTENT_TYPE, "application/json")
-            .body(body)
-            .send()
-            .await
-            .map_err(SequencerRpcError::HttpError)?;
+            .body(body);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        request.send().await?.error_for_status()?;
 
         Ok(())
     }
@@ -105,17 +779,292 @@ impl SequencerClient {
 #[async_trait::async_trait]
 impl RawTransactionForwarder for SequencerClient {
     async fn forward_raw_transaction(&self, tx: &[u8]) -> EthResult<()> {
-        Self::forward_raw_transaction(self, tx).await?;
-        Ok(())
+        match Self::forward_raw_transaction(self, tx).await {
+            Ok(()) => Ok(()),
+            // a transaction that isn't pooled would be lost, so the failure is always reported
+            Err(err) if self.forward_only => Err(err.into()),
+            Err(err) => match self.failure_mode {
+                ForwardingFailureMode::Error => Err(err.into()),
+                ForwardingFailureMode::Warn => {
+                    tracing::warn!(
+                        target: "rpc::eth",
+                        %err,
+                        "Failed to forward transaction to sequencer, only submitting it locally"
+                    );
+                    Ok(())
+                }
+            },
+        }
+    }
+
+    fn submit_to_pool(&self) -> bool {
+        !self.forward_only
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 struct SequencerClientInner {
-    /// The endpoint of the sequencer
-    sequencer_endpoint: String,
+    /// The endpoints of the sequencer, in order of priority
+    endpoints: Vec<SequencerEndpoint>,
     /// The HTTP client
     http_client: Client,
     /// Keeps track of unique request ids
     id: AtomicUsize,
+    /// The state of the circuit breaker
+    circuit_breaker: Mutex<CircuitBreaker>,
+    /// Whether no endpoint was reachable by the last health check
+    degraded: AtomicBool,
+    /// Forwarding metrics
+    metrics: SequencerClientMetrics,
+}
+
+impl SequencerApiServer for SequencerClient {
+    fn status(&self) -> RpcResult<SequencerStatus> {
+        Ok(Self::status(self))
+    }
+}
+
+/// The state of the circuit breaker.
+#[derive(Debug, Default)]
+struct CircuitBreaker {
+    /// The number of consecutive forwarding failures.
+    consecutive_failures: usize,
+    /// Until when forwarding is suspended, this is kept after it passed until the next success.
+    open_until: Option<Instant>,
+}
+
+/// Metrics of the [`SequencerClient`].
+#[derive(Metrics)]
+#[metrics(scope = "optimism.sequencer")]
+struct SequencerClientMetrics {
+    /// Number of transactions forwarded to the sequencer
+    forwarded_transactions: Counter,
+    /// Number of transactions that failed to be forwarded, after all retries
+    failed_forwards: Counter,
+    /// Number of transactions not forwarded because forwarding was suspended
+    skipped_forwards: Counter,
+    /// Time it took to forward a transaction, including retries
+    forward_latency: Histogram,
+    /// Whether forwarding is suspended by the circuit breaker
+    circuit_open: Gauge,
+    /// Number of endpoints that were reachable by the last health check
+    reachable_endpoints: Gauge,
+    /// Whether no endpoint was reachable by the last health check
+    degraded: Gauge,
+    /// Latency of the health check of an endpoint
+    health_check_latency: Histogram,
+}
+
+/// An endpoint of the sequencer and its health.
+#[derive(Debug)]
+struct SequencerEndpoint {
+    url: String,
+    /// Until when the endpoint is considered unhealthy, if it is.
+    unhealthy_until: Mutex<Option<Instant>>,
+    /// The outcome of the last health check.
+    last_probe: Mutex<Option<EndpointProbe>>,
+}
+
+impl SequencerEndpoint {
+    const fn new(url: String) -> Self {
+        Self { url, unhealthy_until: Mutex::new(None), last_probe: Mutex::new(None) }
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        !self.unhealthy_until.lock().is_some_and(|until| until > now)
+    }
+
+    fn mark_healthy(&self) {
+        *self.unhealthy_until.lock() = None;
+    }
+
+    fn mark_unhealthy(&self, cooldown: Duration) {
+        *self.unhealthy_until.lock() = Some(Instant::now() + cooldown);
+    }
+}
+
+/// The outcome of a health check of an endpoint.
+#[derive(Debug, Clone, Copy)]
+struct EndpointProbe {
+    reachable: bool,
+    latency: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint_urls(client: &SequencerClient) -> Vec<&str> {
+        client.endpoints_by_health().into_iter().map(|endpoint| endpoint.url.as_str()).collect()
+    }
+
+    #[test]
+    fn unhealthy_endpoints_are_deprioritized() {
+        let client = SequencerClient::new_with_fallbacks("http://a", ["http://b", "http://c"]);
+        assert_eq!(client.endpoint(), "http://a");
+        assert_eq!(endpoint_urls(&client), ["http://a", "http://b", "http://c"]);
+
+        client.inner.endpoints[0].mark_unhealthy(Duration::from_secs(60));
+        client.inner.endpoints[1].mark_unhealthy(Duration::from_secs(60));
+        assert_eq!(endpoint_urls(&client), ["http://c", "http://a", "http://b"]);
+
+        client.inner.endpoints[0].mark_healthy();
+        assert_eq!(endpoint_urls(&client), ["http://a", "http://c", "http://b"]);
+
+        // the cooldown expired
+        client.inner.endpoints[1].mark_unhealthy(Duration::ZERO);
+        assert_eq!(endpoint_urls(&client), ["http://a", "http://b", "http://c"]);
+    }
+
+    #[tokio::test]
+    async fn forward_fails_over_all_endpoints() {
+        // nothing listens on the discard port
+        let client = SequencerClient::new_with_fallbacks(
+            "http://127.0.0.1:9",
+            ["http://127.0.0.1:9/fallback"],
+        )
+        .with_retry_config(
+            SequencerRetryConfig::default()
+                .with_max_retries(1)
+                .with_initial_backoff(Duration::from_millis(1)),
+        );
+
+        assert!(client.forward_raw_transaction(&[0x01]).await.is_err());
+
+        let now = Instant::now();
+        assert!(client.inner.endpoints.iter().all(|endpoint| !endpoint.is_healthy(now)));
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_opens_after_consecutive_failures() {
+        let client = SequencerClient::new("http://127.0.0.1:9")
+            .with_retry_config(SequencerRetryConfig::default().with_max_retries(0))
+            .with_circuit_breaker_config(
+                SequencerCircuitBreakerConfig::default()
+                    .with_failure_threshold(2)
+                    .with_open_duration(Duration::from_secs(60)),
+            );
+
+        assert!(client.forward_raw_transaction(&[0x01]).await.is_err());
+        assert_eq!(client.status().circuit_breaker, CircuitBreakerState::Closed);
+        assert_eq!(client.status().consecutive_failures, 1);
+
+        assert!(client.forward_raw_transaction(&[0x01]).await.is_err());
+        let status = client.status();
+        assert_eq!(status.circuit_breaker, CircuitBreakerState::Open);
+        assert!(status.open_remaining_ms.is_some());
+        assert!(!status.endpoints[0].healthy);
+
+        assert!(matches!(
+            client.forward_raw_transaction(&[0x01]).await,
+            Err(SequencerRpcError::CircuitOpen)
+        ));
+
+        // the open duration passed, the next transaction probes the sequencer
+        client.inner.circuit_breaker.lock().open_until = Some(Instant::now());
+        assert_eq!(client.status().circuit_breaker, CircuitBreakerState::HalfOpen);
+        assert!(!matches!(
+            client.forward_raw_transaction(&[0x01]).await,
+            Err(SequencerRpcError::CircuitOpen)
+        ));
+        assert_eq!(client.status().circuit_breaker, CircuitBreakerState::Open);
+
+        client.on_forward_outcome(true);
+        assert_eq!(client.status().circuit_breaker, CircuitBreakerState::Closed);
+        assert_eq!(client.status().consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn forwarding_failure_mode() {
+        let client = SequencerClient::new("http://127.0.0.1:9")
+            .with_retry_config(SequencerRetryConfig::default().with_max_retries(0));
+        let forwarder: &dyn RawTransactionForwarder = &client;
+        assert!(forwarder.forward_raw_transaction(&[0x01]).await.is_err());
+
+        let client = client.with_failure_mode(ForwardingFailureMode::Warn);
+        let forwarder: &dyn RawTransactionForwarder = &client;
+        assert!(forwarder.forward_raw_transaction(&[0x01]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn degrades_while_sequencer_is_unreachable() {
+        let client = SequencerClient::new_with_fallbacks(
+            "http://127.0.0.1:9",
+            ["http://127.0.0.1:9/fallback"],
+        );
+
+        assert_eq!(client.check_health().await, 0);
+        let status = client.status();
+        assert!(status.degraded);
+        assert!(status.endpoints.iter().all(|endpoint| endpoint.reachable == Some(false)));
+        assert!(matches!(
+            client.forward_raw_transaction(&[0x01]).await,
+            Err(SequencerRpcError::Unreachable)
+        ));
+
+        let client = client.with_failure_mode(ForwardingFailureMode::Warn);
+        let forwarder: &dyn RawTransactionForwarder = &client;
+        assert!(forwarder.forward_raw_transaction(&[0x01]).await.is_ok());
+    }
+
+    #[test]
+    fn http_config_build_client() {
+        let config = SequencerHttpConfig::default()
+            .with_header("x-api-key", "secret")
+            .with_timeout(Duration::from_secs(1))
+            .with_max_idle_connections(4);
+        assert!(config.build_client().is_ok());
+
+        let config = SequencerHttpConfig::default().with_header("invalid header", "secret");
+        assert!(matches!(config.build_client(), Err(SequencerRpcError::InvalidHttpConfig(_))));
+
+        let config = SequencerHttpConfig::default().with_ca_certificate("/does/not/exist.pem");
+        assert!(matches!(config.build_client(), Err(SequencerRpcError::InvalidHttpConfig(_))));
+    }
+
+    #[test]
+    fn http_config_debug_redacts_header_values() {
+        let config = SequencerHttpConfig::default().with_header("authorization", "Bearer secret");
+        let debug = format!("{config:?}");
+        assert!(debug.contains("authorization"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn forward_only_reports_failures() {
+        let client = SequencerClient::new("http://127.0.0.1:9")
+            .with_retry_config(SequencerRetryConfig::default().with_max_retries(0))
+            .with_failure_mode(ForwardingFailureMode::Warn);
+        let forwarder: &dyn RawTransactionForwarder = &client;
+        assert!(forwarder.submit_to_pool());
+
+        let client = client.with_forward_only(true);
+        let forwarder: &dyn RawTransactionForwarder = &client;
+        assert!(!forwarder.submit_to_pool());
+        assert!(forwarder.forward_raw_transaction(&[0x01]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tracks_forwarding_outcome() {
+        use reth_primitives::{b256, hex};
+        use tx_status::{TransactionStatus, TransactionStatusEvent};
+
+        let tracker = TransactionStatusTracker::default();
+        let mut events = tracker.subscribe();
+        let client = SequencerClient::new("http://127.0.0.1:9")
+            .with_retry_config(SequencerRetryConfig::default().with_max_retries(0))
+            .with_status_tracker(tracker);
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3");
+        let hash = b256!("a694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d");
+        assert!(client.forward_raw_transaction(&tx).await.is_err());
+
+        for status in [TransactionStatus::Forwarded, TransactionStatus::ForwardingFailed] {
+            assert_eq!(
+                events.try_recv().unwrap(),
+                TransactionStatusEvent { hash, status, block: None }
+            );
+        }
+    }
 }
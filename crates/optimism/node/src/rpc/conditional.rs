@@ -0,0 +1,335 @@
+//! Support for `eth_sendRawTransactionConditional`.
+//!
+//! A conditional transaction is only accepted if the state of the chain satisfies the given
+//! preconditions, which allows builders and bundlers to avoid submitting transactions that would
+//! revert.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObject};
+use reth_primitives::{Address, Bytes, B256, U256, U64};
+use reth_provider::{BlockReaderIdExt, StateProvider, StateProviderFactory};
+use reth_rpc::eth::{error::EthApiError, EthTransactions};
+use reth_rpc_types::ToRpcError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The maximum cost of the known accounts of a [`TransactionConditional`].
+///
+/// Every storage root and every storage slot costs one.
+pub const MAX_CONDITIONAL_COST: usize = 1000;
+
+/// Error code for a transaction whose preconditions aren't met.
+pub const CONDITIONAL_REJECTED_CODE: i32 = -32003;
+
+/// Error code for a condition that exceeds [`MAX_CONDITIONAL_COST`].
+pub const CONDITIONAL_COST_EXCEEDED_CODE: i32 = -32005;
+
+/// The expected storage of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccountStorage {
+    /// The root of the account's storage trie.
+    StorageRoot(B256),
+    /// The values of individual storage slots.
+    Slots(HashMap<U256, B256>),
+}
+
+impl KnownAccountStorage {
+    /// Returns the cost of checking this condition.
+    pub fn cost(&self) -> usize {
+        match self {
+            Self::StorageRoot(_) => 1,
+            Self::Slots(slots) => slots.len(),
+        }
+    }
+}
+
+/// The preconditions of a conditional transaction.
+///
+/// Block number and timestamp bounds are inclusive and checked against the latest block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// The expected storage of accounts.
+    #[serde(default)]
+    pub known_accounts: HashMap<Address, KnownAccountStorage>,
+    /// The minimum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_min: Option<U64>,
+    /// The maximum block number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_max: Option<U64>,
+    /// The minimum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_min: Option<U64>,
+    /// The maximum block timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_max: Option<U64>,
+}
+
+impl TransactionConditional {
+    /// Returns the cost of checking the known accounts.
+    pub fn cost(&self) -> usize {
+        self.known_accounts.values().map(KnownAccountStorage::cost).sum()
+    }
+
+    /// Checks the block number and timestamp bounds against the given block.
+    pub fn check_block(&self, number: u64, timestamp: u64) -> Result<(), ConditionalError> {
+        let number = U64::from(number);
+        if self.block_number_min.is_some_and(|min| number < min) ||
+            self.block_number_max.is_some_and(|max| number > max)
+        {
+            return Err(ConditionalError::BlockNumber)
+        }
+
+        let timestamp = U64::from(timestamp);
+        if self.timestamp_min.is_some_and(|min| timestamp < min) ||
+            self.timestamp_max.is_some_and(|max| timestamp > max)
+        {
+            return Err(ConditionalError::Timestamp)
+        }
+
+        Ok(())
+    }
+
+    /// Checks the known accounts against the given state.
+    pub fn check_known_accounts(&self, state: &dyn StateProvider) -> Result<(), ConditionalError> {
+        for (address, storage) in &self.known_accounts {
+            match storage {
+                KnownAccountStorage::StorageRoot(root) => {
+                    let proof = state.proof(*address, &[])?;
+                    if proof.storage_root != *root {
+                        return Err(ConditionalError::StorageRoot(*address))
+                    }
+                }
+                KnownAccountStorage::Slots(slots) => {
+                    for (slot, expected) in slots {
+                        let value = state.storage(*address, B256::from(*slot))?.unwrap_or_default();
+                        if B256::from(value) != *expected {
+                            return Err(ConditionalError::StorageSlot(*address, *slot))
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Errors of conditional transactions.
+#[derive(Debug, thiserror::Error)]
+pub enum ConditionalError {
+    /// The known accounts exceed [`MAX_CONDITIONAL_COST`].
+    #[error("conditional cost {0} exceeds maximum of {MAX_CONDITIONAL_COST}")]
+    CostExceeded(usize),
+    /// The latest block number is out of bounds.
+    #[error("block number out of range")]
+    BlockNumber,
+    /// The latest block timestamp is out of bounds.
+    #[error("block timestamp out of range")]
+    Timestamp,
+    /// The storage root of an account doesn't match.
+    #[error("storage root of {0} does not match")]
+    StorageRoot(Address),
+    /// The value of a storage slot doesn't match.
+    #[error("storage slot {1} of {0} does not match")]
+    StorageSlot(Address, U256),
+    /// Failed to read the state.
+    #[error(transparent)]
+    Provider(#[from] reth_provider::ProviderError),
+}
+
+impl ToRpcError for ConditionalError {
+    fn to_rpc_error(&self) -> ErrorObject<'static> {
+        let code = match self {
+            Self::CostExceeded(_) => CONDITIONAL_COST_EXCEEDED_CODE,
+            Self::Provider(_) => jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+            _ => CONDITIONAL_REJECTED_CODE,
+        };
+        ErrorObject::owned(code, self.to_string(), None::<String>)
+    }
+}
+
+impl From<ConditionalError> for EthApiError {
+    fn from(err: ConditionalError) -> Self {
+        Self::other(err)
+    }
+}
+
+/// The `eth_sendRawTransactionConditional` RPC method.
+#[rpc(server, namespace = "eth")]
+pub trait EthConditionalApi {
+    /// Submits a raw transaction if the chain satisfies the given preconditions.
+    ///
+    /// Returns the hash of the transaction.
+    #[method(name = "sendRawTransactionConditional")]
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256>;
+}
+
+/// Implements [`EthConditionalApiServer`] on top of the `eth` API.
+///
+/// The preconditions are validated against the latest block before the transaction is submitted
+/// with `eth_sendRawTransaction`, which also forwards it to the sequencer if configured. The
+/// validation reads the state and computes storage roots, so it runs on the blocking pool.
+#[derive(Debug, Clone)]
+pub struct EthConditional<Provider, Eth> {
+    provider: Provider,
+    eth: Eth,
+}
+
+impl<Provider, Eth> EthConditional<Provider, Eth> {
+    /// Creates a new instance.
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
+    }
+}
+
+impl<Provider, Eth> EthConditional<Provider, Eth>
+where
+    Provider: StateProviderFactory + BlockReaderIdExt,
+{
+    /// Validates the preconditions against the latest block.
+    pub fn validate(&self, condition: &TransactionConditional) -> Result<(), ConditionalError> {
+        let cost = condition.cost();
+        if cost > MAX_CONDITIONAL_COST {
+            return Err(ConditionalError::CostExceeded(cost))
+        }
+
+        let header = self
+            .provider
+            .latest_header()?
+            .ok_or(reth_provider::ProviderError::HeaderNotFound(Default::default()))?;
+        condition.check_block(header.number, header.timestamp)?;
+
+        if !condition.known_accounts.is_empty() {
+            let state = self.provider.latest()?;
+            condition.check_known_accounts(&*state)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Provider, Eth> EthConditionalApiServer for EthConditional<Provider, Eth>
+where
+    Provider: StateProviderFactory + BlockReaderIdExt + Clone + 'static,
+    Eth: EthTransactions + Clone + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        let this = self.clone();
+        self.eth.spawn_blocking(move || Ok(this.validate(&condition)?)).await?;
+        Ok(self.eth.send_raw_transaction(bytes).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{tables, transaction::DbTxMut};
+    use reth_primitives::{address, b256, Account, StorageEntry};
+    use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
+
+    #[test]
+    fn deserialize_conditional() {
+        let condition: TransactionConditional = serde_json::from_str(
+            r#"{
+                "knownAccounts": {
+                    "0x1111111111111111111111111111111111111111": "0x2222222222222222222222222222222222222222222222222222222222222222",
+                    "0x3333333333333333333333333333333333333333": {
+                        "0x1": "0x0000000000000000000000000000000000000000000000000000000000000005"
+                    }
+                },
+                "blockNumberMax": "0x64",
+                "timestampMin": "0x10"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            condition.known_accounts[&address!("1111111111111111111111111111111111111111")],
+            KnownAccountStorage::StorageRoot(b256!(
+                "2222222222222222222222222222222222222222222222222222222222222222"
+            ))
+        );
+        assert_eq!(condition.cost(), 2);
+        assert_eq!(condition.block_number_min, None);
+        assert_eq!(condition.block_number_max, Some(U64::from(100)));
+        assert_eq!(condition.timestamp_min, Some(U64::from(16)));
+    }
+
+    #[test]
+    fn check_block_bounds() {
+        let condition = TransactionConditional {
+            block_number_min: Some(U64::from(10)),
+            block_number_max: Some(U64::from(20)),
+            timestamp_max: Some(U64::from(1000)),
+            ..Default::default()
+        };
+
+        assert!(condition.check_block(10, 0).is_ok());
+        assert!(condition.check_block(20, 1000).is_ok());
+        assert!(matches!(condition.check_block(9, 0), Err(ConditionalError::BlockNumber)));
+        assert!(matches!(condition.check_block(21, 0), Err(ConditionalError::BlockNumber)));
+        assert!(matches!(condition.check_block(15, 1001), Err(ConditionalError::Timestamp)));
+        assert!(TransactionConditional::default().check_block(u64::MAX, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn check_known_accounts_against_state() {
+        let factory = create_test_provider_factory();
+        let address = address!("1111111111111111111111111111111111111111");
+        let storage = [
+            StorageEntry { key: B256::with_last_byte(1), value: U256::from(5) },
+            StorageEntry { key: B256::with_last_byte(2), value: U256::from(7) },
+        ];
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.insert_account_for_hashing([(address, Some(Account::default()))]).unwrap();
+            provider_rw.insert_storage_for_hashing([(address, storage)]).unwrap();
+            for entry in storage {
+                provider_rw.tx_ref().put::<tables::PlainStorageState>(address, entry).unwrap();
+            }
+            provider_rw.commit().unwrap();
+        }
+        let state = factory.latest().unwrap();
+
+        let check = |storage: KnownAccountStorage| {
+            TransactionConditional {
+                known_accounts: HashMap::from([(address, storage)]),
+                ..Default::default()
+            }
+            .check_known_accounts(&*state)
+        };
+
+        let slots = |entries: &[(u8, B256)]| {
+            KnownAccountStorage::Slots(
+                entries.iter().map(|(slot, value)| (U256::from(*slot), *value)).collect(),
+            )
+        };
+        let matching = slots(&[(1, B256::with_last_byte(5)), (2, B256::with_last_byte(7))]);
+        assert!(check(matching).is_ok());
+        // unset slots are zero
+        assert!(check(slots(&[(3, B256::ZERO)])).is_ok());
+        assert!(matches!(
+            check(slots(&[(1, B256::with_last_byte(6))])),
+            Err(ConditionalError::StorageSlot(_, slot)) if slot == U256::from(1)
+        ));
+
+        let storage_root = reth_trie::test_utils::storage_root(
+            storage.iter().map(|entry| (entry.key, entry.value)),
+        );
+        assert!(check(KnownAccountStorage::StorageRoot(storage_root)).is_ok());
+        assert!(matches!(
+            check(KnownAccountStorage::StorageRoot(B256::ZERO)),
+            Err(ConditionalError::StorageRoot(account)) if account == address
+        ));
+    }
+}
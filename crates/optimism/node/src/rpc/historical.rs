@@ -0,0 +1,361 @@
+//! Forwarding of requests for pre-bedrock history to a historical RPC endpoint.
+//!
+//! Blocks before the bedrock upgrade of OP Mainnet are not executed by the node, so requests that
+//! need their state, receipts or traces are proxied to an archive node of the legacy chain, like
+//! op-geth does with `--rollup.historicalrpc`.
+
+use jsonrpsee::{
+    core::{server::MethodsError, RpcResult},
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned, Params},
+    Methods, RpcModule,
+};
+use reqwest::Client;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{BlockId, BlockNumberOrTag};
+use reth_rpc::eth::error::EthApiError;
+use reth_rpc_types::error::EthRpcErrorCode;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    sync::{atomic::AtomicUsize, Arc},
+    time::Duration,
+};
+
+/// Timeout of a request to the historical endpoint, including connecting.
+const HISTORICAL_RPC_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout for connecting to the historical endpoint.
+const HISTORICAL_RPC_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Methods that are forwarded to the historical endpoint, with the index of their block
+/// parameter.
+///
+/// Methods without a block parameter refer to a block or transaction by hash, and are forwarded
+/// if the node doesn't know the block or transaction.
+const HISTORICAL_METHODS: &[(&str, Option<usize>)] = &[
+    ("eth_getBlockByNumber", Some(0)),
+    ("eth_getBlockTransactionCountByNumber", Some(0)),
+    ("eth_getTransactionByBlockNumberAndIndex", Some(0)),
+    ("eth_getBlockReceipts", Some(0)),
+    ("eth_getBalance", Some(1)),
+    ("eth_getCode", Some(1)),
+    ("eth_getTransactionCount", Some(1)),
+    ("eth_getStorageAt", Some(2)),
+    ("eth_getProof", Some(2)),
+    ("eth_call", Some(1)),
+    ("eth_estimateGas", Some(1)),
+    ("eth_getBlockByHash", None),
+    ("eth_getBlockTransactionCountByHash", None),
+    ("eth_getTransactionByBlockHashAndIndex", None),
+    ("eth_getTransactionByHash", None),
+    ("eth_getTransactionReceipt", None),
+    ("debug_traceBlockByNumber", Some(0)),
+    ("debug_traceBlockByHash", None),
+    ("debug_traceTransaction", None),
+    ("debug_traceCall", Some(1)),
+    ("trace_block", Some(0)),
+    ("trace_transaction", None),
+];
+
+/// Error type when interacting with the historical RPC endpoint.
+#[derive(Debug, thiserror::Error)]
+pub enum HistoricalRpcError {
+    /// Wrapper around an [`reqwest::Error`].
+    #[error("failed to forward request to historical rpc: {0}")]
+    HttpError(#[from] reqwest::Error),
+    /// The historical endpoint returned an error.
+    #[error(transparent)]
+    Rpc(ErrorObjectOwned),
+}
+
+impl From<HistoricalRpcError> for ErrorObjectOwned {
+    fn from(err: HistoricalRpcError) -> Self {
+        match err {
+            HistoricalRpcError::HttpError(err) => ErrorObject::owned(
+                INTERNAL_ERROR_CODE,
+                HistoricalRpcError::HttpError(err).to_string(),
+                None::<String>,
+            ),
+            HistoricalRpcError::Rpc(err) => err,
+        }
+    }
+}
+
+/// A client to send requests to the historical RPC endpoint.
+#[derive(Debug, Clone)]
+pub struct HistoricalRpcClient {
+    inner: Arc<HistoricalRpcClientInner>,
+}
+
+impl HistoricalRpcClient {
+    /// Creates a new [`HistoricalRpcClient`].
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        let client = Client::builder()
+            .use_rustls_tls()
+            .timeout(HISTORICAL_RPC_REQUEST_TIMEOUT)
+            .connect_timeout(HISTORICAL_RPC_CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+        Self::with_client(endpoint, client)
+    }
+
+    /// Creates a new [`HistoricalRpcClient`] with the given HTTP client.
+    pub fn with_client(endpoint: impl Into<String>, http_client: Client) -> Self {
+        let inner = HistoricalRpcClientInner {
+            endpoint: endpoint.into(),
+            http_client,
+            id: AtomicUsize::new(0),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the endpoint of the client.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.endpoint
+    }
+
+    /// Sends the request to the historical endpoint and returns its result.
+    pub async fn request(
+        &self,
+        method: &str,
+        params: &[Value],
+    ) -> Result<Value, HistoricalRpcError> {
+        let id = self.inner.id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": id
+        });
+
+        let response = self
+            .inner
+            .http_client
+            .post(self.endpoint())
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| {
+                tracing::warn!(
+                    target: "rpc::eth",
+                    %err,
+                    "Failed to forward request to historical rpc",
+                );
+                err
+            })?
+            .json::<JsonRpcResponse>()
+            .await?;
+
+        match response.error {
+            Some(err) => Err(HistoricalRpcError::Rpc(err)),
+            None => Ok(response.result.unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct HistoricalRpcClientInner {
+    /// The endpoint of the historical RPC
+    endpoint: String,
+    /// The HTTP client
+    http_client: Client,
+    /// Keeps track of unique request ids
+    id: AtomicUsize,
+}
+
+/// The response of the historical endpoint.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<Value>,
+    error: Option<ErrorObjectOwned>,
+}
+
+/// Serves requests for pre-bedrock history from a historical RPC endpoint, and all other requests
+/// from the local methods.
+#[derive(Debug, Clone)]
+pub struct HistoricalRpc {
+    client: HistoricalRpcClient,
+    local: Methods,
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl HistoricalRpc {
+    /// Creates a new [`HistoricalRpc`] that wraps the given local methods.
+    pub fn new(
+        client: HistoricalRpcClient,
+        local: impl Into<Methods>,
+        chain_spec: Arc<ChainSpec>,
+    ) -> Self {
+        Self { client, local: local.into(), chain_spec }
+    }
+
+    /// Returns the methods that replace the local methods which may need pre-bedrock history.
+    ///
+    /// Methods that aren't part of the local methods are not included.
+    pub fn into_rpc_module(self) -> RpcModule<()> {
+        let this = Arc::new(self);
+        let mut module = RpcModule::new(());
+        for &(method, block_param) in HISTORICAL_METHODS {
+            if this.local.method(method).is_none() {
+                continue
+            }
+            let this = this.clone();
+            module
+                .register_async_method(method, move |params, _, _| {
+                    let this = this.clone();
+                    async move { this.call(method, block_param, params).await }
+                })
+                .expect("method names are unique");
+        }
+        module
+    }
+
+    /// Returns `true` if the given block is before the bedrock upgrade.
+    fn is_pre_bedrock(&self, block: &Value) -> bool {
+        let Ok(block) = serde_json::from_value::<BlockId>(block.clone()) else { return false };
+        match block {
+            BlockId::Number(BlockNumberOrTag::Number(number)) => {
+                !self.chain_spec.is_bedrock_active_at_block(number)
+            }
+            BlockId::Number(BlockNumberOrTag::Earliest) => {
+                !self.chain_spec.is_bedrock_active_at_block(0)
+            }
+            _ => false,
+        }
+    }
+
+    async fn call(
+        &self,
+        method: &'static str,
+        block_param: Option<usize>,
+        params: Params<'static>,
+    ) -> RpcResult<Value> {
+        let params = params.parse::<Option<Vec<Value>>>()?.unwrap_or_default();
+
+        let block = block_param.and_then(|index| params.get(index));
+        if block.is_some_and(|block| self.is_pre_bedrock(block)) {
+            return Ok(self.client.request(method, &params).await?)
+        }
+
+        let local = self.local.call::<_, Value>(method, params.clone()).await;
+
+        // blocks and transactions that are referenced by hash may be unknown to the node if they
+        // are pre-bedrock
+        let by_hash = block_param.is_none() ||
+            block.is_some_and(|block| {
+                matches!(serde_json::from_value::<BlockId>(block.clone()), Ok(BlockId::Hash(_)))
+            });
+        match local {
+            Ok(Value::Null) if by_hash => Ok(self.client.request(method, &params).await?),
+            Err(MethodsError::JsonRpc(err)) if by_hash && is_unavailable(&err) => {
+                Ok(self.client.request(method, &params).await?)
+            }
+            Ok(value) => Ok(value),
+            Err(MethodsError::JsonRpc(err)) => Err(err),
+            Err(err) => {
+                Err(ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<String>))
+            }
+        }
+    }
+}
+
+/// Returns `true` if the error of a local method means that the requested data is not available
+/// locally, because the node doesn't know the block or transaction or it was pruned.
+///
+/// Other errors, like invalid parameters or failed executions, are returned as is.
+fn is_unavailable(err: &ErrorObjectOwned) -> bool {
+    err.code() == EthRpcErrorCode::ResourceNotFound.code() ||
+        err.message() == EthApiError::TransactionNotFound.to_string() ||
+        (err.message().starts_with("state at block #") && err.message().ends_with(" is pruned"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::OP_MAINNET;
+    use reth_primitives::B256;
+
+    fn historical_rpc(local: RpcModule<()>) -> HistoricalRpc {
+        HistoricalRpc::new(
+            HistoricalRpcClient::new("http://127.0.0.1:9"),
+            local,
+            OP_MAINNET.clone(),
+        )
+    }
+
+    #[test]
+    fn pre_bedrock_blocks() {
+        let rpc = historical_rpc(RpcModule::new(()));
+        assert!(rpc.is_pre_bedrock(&serde_json::json!("0x1")));
+        assert!(rpc.is_pre_bedrock(&serde_json::json!("earliest")));
+        assert!(!rpc.is_pre_bedrock(&serde_json::json!("latest")));
+        assert!(!rpc.is_pre_bedrock(&serde_json::json!(format!("{:#x}", 105_235_063u64))));
+        assert!(!rpc.is_pre_bedrock(&serde_json::json!({ "blockHash": B256::ZERO })));
+    }
+
+    #[test]
+    fn only_replaces_local_methods() {
+        let mut local = RpcModule::new(());
+        local.register_method("eth_getBalance", |_, _, _| "0x0").unwrap();
+        local.register_method("eth_chainId", |_, _, _| "0xa").unwrap();
+
+        let module = historical_rpc(local).into_rpc_module();
+        assert_eq!(module.method_names().collect::<Vec<_>>(), ["eth_getBalance"]);
+    }
+
+    #[tokio::test]
+    async fn serves_post_bedrock_requests_locally() {
+        let mut local = RpcModule::new(());
+        local.register_method("eth_getBalance", |_, _, _| "0x1").unwrap();
+
+        let module = historical_rpc(local).into_rpc_module();
+        let balance: String = module
+            .call("eth_getBalance", ("0x0000000000000000000000000000000000000000", "latest"))
+            .await
+            .unwrap();
+        assert_eq!(balance, "0x1");
+
+        // the historical endpoint is unreachable
+        let res: Result<String, _> = module
+            .call("eth_getBalance", ("0x0000000000000000000000000000000000000000", "0x1"))
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn only_forwards_unavailable_data() {
+        let mut local = RpcModule::new(());
+        local
+            .register_method::<RpcResult<Value>, _>("eth_getTransactionByHash", |params, _, _| {
+                match params.one::<u8>()? {
+                    0 => Err(EthApiError::TransactionNotFound.into()),
+                    1 => Err(EthApiError::UnknownBlockOrTxIndex.into()),
+                    _ => Err(EthApiError::InvalidParams("invalid hash".to_string()).into()),
+                }
+            })
+            .unwrap();
+
+        let module = historical_rpc(local).into_rpc_module();
+
+        // unknown transactions are forwarded to the unreachable historical endpoint
+        for param in [0u8, 1] {
+            let err =
+                module.call::<_, Value>("eth_getTransactionByHash", [param]).await.unwrap_err();
+            assert!(err.to_string().contains("failed to forward request to historical rpc"));
+        }
+
+        // other errors are returned as is
+        let err = module.call::<_, Value>("eth_getTransactionByHash", [2u8]).await.unwrap_err();
+        assert!(err.to_string().contains("invalid hash"), "{err}");
+    }
+
+    #[test]
+    fn unavailable_errors() {
+        let pruned = EthApiError::from(reth_provider::ProviderError::StateAtBlockPruned(1));
+        assert!(is_unavailable(&pruned.into()));
+        assert!(is_unavailable(&EthApiError::UnknownBlockNumber.into()));
+        assert!(!is_unavailable(&EthApiError::InvalidBlockRange.into()));
+        assert!(!is_unavailable(&EthApiError::InternalEthError.into()));
+    }
+}
@@ -0,0 +1,253 @@
+//! Validation of cross-chain interop messages before transactions are accepted.
+//!
+//! Transactions that execute messages of other chains declare them in the access list of the
+//! `CrossL2Inbox` predeploy. These entries are checked against a supervisor, which tracks the
+//! chains of the interop set, so that transactions with invalid messages are neither forwarded to
+//! the sequencer nor submitted to the pool.
+
+use jsonrpsee::types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned};
+use reqwest::Client;
+use reth_primitives::{
+    address, AccessList, Address, PooledTransactionsElementEcRecovered, B256, U64,
+};
+use reth_rpc::eth::{
+    error::{EthApiError, EthResult},
+    traits::RawTransactionValidator,
+};
+use reth_rpc_types::ToRpcError;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The address of the `CrossL2Inbox` predeploy.
+pub const CROSS_L2_INBOX_ADDRESS: Address = address!("4200000000000000000000000000000000000022");
+
+/// Error code for a transaction whose interop messages are rejected by the supervisor.
+pub const INTEROP_REJECTED_CODE: i32 = -32003;
+
+/// Default timeout of a request to the supervisor, including connecting.
+///
+/// Transactions are checked before they are accepted, so a slow supervisor delays every
+/// submission of a transaction that executes messages.
+pub const DEFAULT_SUPERVISOR_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Default timeout for connecting to the supervisor.
+pub const DEFAULT_SUPERVISOR_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The minimum safety level of the initiating messages of a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SafetyLevel {
+    /// The initiating block is finalized.
+    Finalized,
+    /// The initiating block and its dependencies are derived from L1.
+    Safe,
+    /// The initiating block is derived from L1.
+    LocalSafe,
+    /// The initiating block and its dependencies are unsafe.
+    #[default]
+    CrossUnsafe,
+    /// The initiating block is unsafe.
+    Unsafe,
+}
+
+/// Errors of interop message validation.
+#[derive(Debug, thiserror::Error)]
+pub enum InteropError {
+    /// The supervisor rejected the messages of the transaction.
+    #[error("invalid interop messages: {}", .0.message())]
+    Rejected(ErrorObjectOwned),
+    /// The supervisor couldn't be reached.
+    #[error("failed to reach supervisor: {0}")]
+    Supervisor(#[from] reqwest::Error),
+}
+
+impl ToRpcError for InteropError {
+    fn to_rpc_error(&self) -> ErrorObject<'static> {
+        let code = match self {
+            Self::Rejected(_) => INTEROP_REJECTED_CODE,
+            Self::Supervisor(_) => INTERNAL_ERROR_CODE,
+        };
+        ErrorObject::owned(code, self.to_string(), None::<String>)
+    }
+}
+
+impl From<InteropError> for EthApiError {
+    fn from(err: InteropError) -> Self {
+        Self::other(err)
+    }
+}
+
+/// Returns the `CrossL2Inbox` entries of the access list, which identify the executed messages.
+pub fn inbox_entries(access_list: &AccessList) -> Vec<B256> {
+    access_list
+        .iter()
+        .filter(|item| item.address == CROSS_L2_INBOX_ADDRESS)
+        .flat_map(|item| item.storage_keys.iter().copied())
+        .collect()
+}
+
+/// The context in which the messages are executed.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct ExecutingDescriptor {
+    timestamp: U64,
+}
+
+/// Validates the interop messages of transactions with a supervisor, with
+/// `supervisor_checkAccessList`.
+#[derive(Debug, Clone)]
+pub struct SupervisorValidator {
+    /// The endpoint of the supervisor
+    endpoint: String,
+    /// The HTTP client
+    http_client: Client,
+    /// The minimum safety level of the initiating messages
+    min_safety: SafetyLevel,
+}
+
+impl SupervisorValidator {
+    /// Creates a new [`SupervisorValidator`] for the given supervisor endpoint.
+    ///
+    /// Requests time out after [`DEFAULT_SUPERVISOR_REQUEST_TIMEOUT`].
+    pub fn new(endpoint: impl Into<String>) -> Result<Self, InteropError> {
+        let client = Client::builder()
+            .use_rustls_tls()
+            .timeout(DEFAULT_SUPERVISOR_REQUEST_TIMEOUT)
+            .connect_timeout(DEFAULT_SUPERVISOR_CONNECT_TIMEOUT)
+            .build()?;
+        Ok(Self::with_client(endpoint, client))
+    }
+
+    /// Creates a new [`SupervisorValidator`] with the given HTTP client.
+    pub fn with_client(endpoint: impl Into<String>, http_client: Client) -> Self {
+        Self { endpoint: endpoint.into(), http_client, min_safety: SafetyLevel::default() }
+    }
+
+    /// Sets the minimum safety level of the initiating messages.
+    pub const fn with_min_safety(mut self, min_safety: SafetyLevel) -> Self {
+        self.min_safety = min_safety;
+        self
+    }
+
+    /// Returns the endpoint of the supervisor.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Checks the given `CrossL2Inbox` entries with the supervisor.
+    pub async fn check_access_list(&self, entries: &[B256]) -> Result<(), InteropError> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let descriptor = ExecutingDescriptor { timestamp: U64::from(timestamp) };
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "supervisor_checkAccessList",
+            "params": [entries, self.min_safety, descriptor],
+            "id": 1
+        });
+
+        let response = self
+            .http_client
+            .post(self.endpoint())
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())?
+            .json::<JsonRpcResponse>()
+            .await?;
+
+        match response.error {
+            Some(err) => Err(InteropError::Rejected(err)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RawTransactionValidator for SupervisorValidator {
+    async fn validate_raw_transaction(
+        &self,
+        tx: &PooledTransactionsElementEcRecovered,
+    ) -> EthResult<()> {
+        let entries = tx.access_list().map(inbox_entries).unwrap_or_default();
+        if entries.is_empty() {
+            // the transaction doesn't execute any messages
+            return Ok(())
+        }
+        self.check_access_list(&entries).await.map_err(|err| {
+            let hash = tx.hash();
+            tracing::debug!(target: "rpc::eth", %err, %hash, "Rejected interop transaction");
+            err.into()
+        })
+    }
+}
+
+/// The response of the supervisor.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    error: Option<ErrorObjectOwned>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        AccessListItem, PooledTransactionsElement, Signature, TxEip1559, TxKind,
+    };
+
+    const ENTRY: B256 = B256::repeat_byte(0x01);
+
+    fn transaction(access_list: AccessList) -> PooledTransactionsElementEcRecovered {
+        let tx = PooledTransactionsElement::Eip1559 {
+            transaction: TxEip1559 {
+                to: TxKind::Call(Address::ZERO),
+                access_list,
+                ..Default::default()
+            },
+            signature: Signature::default(),
+            hash: B256::ZERO,
+        };
+        PooledTransactionsElementEcRecovered::from_signed_transaction(tx, Address::ZERO)
+    }
+
+    fn access_list() -> AccessList {
+        AccessList(vec![
+            AccessListItem { address: Address::ZERO, storage_keys: vec![B256::ZERO] },
+            AccessListItem { address: CROSS_L2_INBOX_ADDRESS, storage_keys: vec![ENTRY] },
+        ])
+    }
+
+    #[test]
+    fn filters_inbox_entries() {
+        assert_eq!(inbox_entries(&access_list()), vec![ENTRY]);
+        assert!(inbox_entries(&AccessList::default()).is_empty());
+    }
+
+    #[test]
+    fn safety_level_serde() {
+        assert_eq!(serde_json::to_value(SafetyLevel::CrossUnsafe).unwrap(), "cross-unsafe");
+        assert_eq!(serde_json::to_value(SafetyLevel::LocalSafe).unwrap(), "local-safe");
+    }
+
+    #[test]
+    fn rejection_error_code() {
+        let err = InteropError::Rejected(ErrorObject::owned(
+            -320_600,
+            "conflicting data",
+            None::<String>,
+        ));
+        let rpc_err = err.to_rpc_error();
+        assert_eq!(rpc_err.code(), INTEROP_REJECTED_CODE);
+        assert_eq!(rpc_err.message(), "invalid interop messages: conflicting data");
+    }
+
+    #[tokio::test]
+    async fn validates_only_executing_transactions() {
+        // the supervisor is unreachable
+        let validator = SupervisorValidator::new("http://127.0.0.1:9").unwrap();
+
+        assert!(validator
+            .validate_raw_transaction(&transaction(AccessList::default()))
+            .await
+            .is_ok());
+        assert!(validator.validate_raw_transaction(&transaction(access_list())).await.is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! Serving of `pending` queries from the preconfirmations of the sequencer.
+//!
+//! The sequencer shares the block it is currently building before it is sealed. A node that
+//! polls these preconfirmations can answer `eth_getBlockByNumber("pending")` and look up
+//! preconfirmed transactions and receipts before the block is derived from L1 or received over
+//! the engine API.
+
+use jsonrpsee::{
+    core::{server::MethodsError, RpcResult},
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned, Params},
+    Methods, RpcModule,
+};
+use parking_lot::RwLock;
+use reqwest::Client;
+use reth_primitives::{B256, U64};
+use reth_provider::BlockNumReader;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tracing::{debug, trace};
+
+/// The default interval at which the sequencer is polled for preconfirmations.
+pub const DEFAULT_PRECONF_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Timeout of a poll of the sequencer, including connecting.
+///
+/// Preconfirmations are outdated quickly, so a poll that takes longer is abandoned and retried
+/// with the next one.
+const PRECONF_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for connecting to the sequencer.
+const PRECONF_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Methods that are served from the preconfirmations if the node doesn't have the answer yet.
+const PRECONFIRMATION_METHODS: &[&str] =
+    &["eth_getBlockByNumber", "eth_getTransactionByHash", "eth_getTransactionReceipt"];
+
+/// Error type when polling the sequencer for preconfirmations.
+#[derive(Debug, thiserror::Error)]
+pub enum PreconfirmationError {
+    /// Wrapper around an [`reqwest::Error`].
+    #[error("failed to poll preconfirmations: {0}")]
+    HttpError(#[from] reqwest::Error),
+    /// The sequencer returned an error.
+    #[error(transparent)]
+    Rpc(ErrorObjectOwned),
+    /// The sequencer returned a malformed block.
+    #[error("invalid preconfirmed block")]
+    InvalidBlock,
+}
+
+/// The block that the sequencer is building, with the receipts of its transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreconfirmedBlock {
+    number: u64,
+    /// The block, with full transaction objects.
+    block: Value,
+    transactions: HashMap<B256, Value>,
+    receipts: HashMap<B256, Value>,
+}
+
+impl PreconfirmedBlock {
+    /// Creates a new [`PreconfirmedBlock`] from the RPC representation of the block, with full
+    /// transaction objects, and its receipts.
+    ///
+    /// Returns `None` if the block has no number or its transactions have no hashes.
+    pub fn new(block: Value, receipts: Vec<Value>) -> Option<Self> {
+        let number = serde_json::from_value::<U64>(block.get("number")?.clone()).ok()?.to();
+        let transactions = block
+            .get("transactions")?
+            .as_array()?
+            .iter()
+            .map(|tx| Some((hash_field(tx, "hash")?, tx.clone())))
+            .collect::<Option<_>>()?;
+        let receipts = receipts
+            .into_iter()
+            .filter_map(|receipt| Some((hash_field(&receipt, "transactionHash")?, receipt)))
+            .collect();
+        Some(Self { number, block, transactions, receipts })
+    }
+
+    /// Returns the number of the block.
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Returns the RPC representation of the block, with full transaction objects or only their
+    /// hashes.
+    pub fn block(&self, full: bool) -> Value {
+        let mut block = self.block.clone();
+        if !full {
+            if let Some(transactions) = block.get_mut("transactions").and_then(Value::as_array_mut)
+            {
+                for tx in transactions {
+                    *tx = tx.get("hash").cloned().unwrap_or_default();
+                }
+            }
+        }
+        block
+    }
+
+    /// Returns the preconfirmed transaction with the given hash.
+    pub fn transaction(&self, hash: &B256) -> Option<&Value> {
+        self.transactions.get(hash)
+    }
+
+    /// Returns the receipt of the preconfirmed transaction with the given hash.
+    pub fn receipt(&self, hash: &B256) -> Option<&Value> {
+        self.receipts.get(hash)
+    }
+}
+
+/// Returns the hash in the given field of a JSON object.
+fn hash_field(value: &Value, field: &str) -> Option<B256> {
+    serde_json::from_value(value.get(field)?.clone()).ok()
+}
+
+/// Polls the sequencer for the block it is building, and keeps the latest preconfirmed block.
+#[derive(Debug, Clone)]
+pub struct PreconfirmationFeed {
+    inner: Arc<PreconfirmationFeedInner>,
+}
+
+impl PreconfirmationFeed {
+    /// Creates a new [`PreconfirmationFeed`] that polls the given endpoint at the given interval.
+    pub fn new(endpoint: impl Into<String>, interval: Duration) -> Self {
+        let client = Client::builder()
+            .use_rustls_tls()
+            .timeout(PRECONF_REQUEST_TIMEOUT)
+            .connect_timeout(PRECONF_CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+        Self::with_client(endpoint, interval, client)
+    }
+
+    /// Creates a new [`PreconfirmationFeed`] with the given HTTP client.
+    pub fn with_client(
+        endpoint: impl Into<String>,
+        interval: Duration,
+        http_client: Client,
+    ) -> Self {
+        let inner = PreconfirmationFeedInner {
+            endpoint: endpoint.into(),
+            interval,
+            http_client,
+            latest: Default::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the endpoint that is polled.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.endpoint
+    }
+
+    /// Returns the latest preconfirmed block.
+    pub fn latest(&self) -> Option<Arc<PreconfirmedBlock>> {
+        self.inner.latest.read().clone()
+    }
+
+    /// Polls the sequencer until the returned future is dropped.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.inner.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match self.poll().await {
+                Ok(block) => {
+                    let number = block.as_ref().map(|block| block.number);
+                    trace!(target: "rpc::eth", ?number, "Polled preconfirmed block");
+                    *self.inner.latest.write() = block.map(Arc::new);
+                }
+                Err(err) => {
+                    debug!(target: "rpc::eth", %err, "Failed to poll preconfirmations");
+                }
+            }
+        }
+    }
+
+    /// Fetches the block that the sequencer is building.
+    async fn poll(&self) -> Result<Option<PreconfirmedBlock>, PreconfirmationError> {
+        let block =
+            self.request("eth_getBlockByNumber", serde_json::json!(["pending", true])).await?;
+        if block.is_null() {
+            return Ok(None)
+        }
+        let receipts = self.request("eth_getBlockReceipts", serde_json::json!(["pending"])).await?;
+        let receipts = serde_json::from_value::<Option<Vec<Value>>>(receipts)
+            .map_err(|_| PreconfirmationError::InvalidBlock)?
+            .unwrap_or_default();
+        PreconfirmedBlock::new(block, receipts).map(Some).ok_or(PreconfirmationError::InvalidBlock)
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, PreconfirmationError> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+            "id": 1
+        });
+
+        let response = self
+            .inner
+            .http_client
+            .post(self.endpoint())
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())?
+            .json::<JsonRpcResponse>()
+            .await?;
+
+        match response.error {
+            Some(err) => Err(PreconfirmationError::Rpc(err)),
+            None => Ok(response.result.unwrap_or_default()),
+        }
+    }
+
+    #[cfg(test)]
+    fn set_latest(&self, block: PreconfirmedBlock) {
+        *self.inner.latest.write() = Some(Arc::new(block));
+    }
+}
+
+#[derive(Debug)]
+struct PreconfirmationFeedInner {
+    /// The endpoint of the sequencer
+    endpoint: String,
+    /// The interval at which the sequencer is polled
+    interval: Duration,
+    /// The HTTP client
+    http_client: Client,
+    /// The latest preconfirmed block
+    latest: RwLock<Option<Arc<PreconfirmedBlock>>>,
+}
+
+/// The response of the sequencer.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<Value>,
+    error: Option<ErrorObjectOwned>,
+}
+
+/// Serves `pending` block queries and lookups of preconfirmed transactions from a
+/// [`PreconfirmationFeed`], and all other requests from the local methods.
+#[derive(Debug, Clone)]
+pub struct PreconfirmationRpc<Provider> {
+    feed: PreconfirmationFeed,
+    local: Methods,
+    provider: Provider,
+}
+
+impl<Provider> PreconfirmationRpc<Provider>
+where
+    Provider: BlockNumReader + 'static,
+{
+    /// Creates a new [`PreconfirmationRpc`] that wraps the given local methods.
+    pub fn new(feed: PreconfirmationFeed, local: impl Into<Methods>, provider: Provider) -> Self {
+        Self { feed, local: local.into(), provider }
+    }
+
+    /// Returns the methods that replace the local methods which can be answered from the
+    /// preconfirmations.
+    ///
+    /// Methods that aren't part of the local methods are not included.
+    pub fn into_rpc_module(self) -> RpcModule<()> {
+        let this = Arc::new(self);
+        let mut module = RpcModule::new(());
+        for &method in PRECONFIRMATION_METHODS {
+            if this.local.method(method).is_none() {
+                continue
+            }
+            let this = this.clone();
+            module
+                .register_async_method(method, move |params, _, _| {
+                    let this = this.clone();
+                    async move { this.call(method, params).await }
+                })
+                .expect("method names are unique");
+        }
+        module
+    }
+
+    /// Returns the latest preconfirmed block, if it is ahead of the local chain.
+    fn preconfirmed_block(&self) -> Option<Arc<PreconfirmedBlock>> {
+        let block = self.feed.latest()?;
+        let best = self.provider.best_block_number().ok()?;
+        (block.number() > best).then_some(block)
+    }
+
+    async fn call(&self, method: &'static str, params: Params<'static>) -> RpcResult<Value> {
+        let params = params.parse::<Option<Vec<Value>>>()?.unwrap_or_default();
+
+        if method == "eth_getBlockByNumber" &&
+            params.first().and_then(Value::as_str) == Some("pending")
+        {
+            if let Some(block) = self.preconfirmed_block() {
+                let full = params.get(1).and_then(Value::as_bool).unwrap_or_default();
+                return Ok(block.block(full))
+            }
+        }
+
+        let local = match self.local.call::<_, Value>(method, params.clone()).await {
+            Ok(value) => value,
+            Err(MethodsError::JsonRpc(err)) => return Err(err),
+            Err(err) => {
+                return Err(ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<String>))
+            }
+        };
+        if !local.is_null() {
+            return Ok(local)
+        }
+
+        // the transaction may be preconfirmed but not yet part of the local chain
+        let hash =
+            params.first().and_then(|hash| serde_json::from_value::<B256>(hash.clone()).ok());
+        let preconfirmed =
+            hash.zip(self.preconfirmed_block()).and_then(|(hash, block)| match method {
+                "eth_getTransactionByHash" => block.transaction(&hash).cloned(),
+                "eth_getTransactionReceipt" => block.receipt(&hash).cloned(),
+                _ => None,
+            });
+        Ok(preconfirmed.unwrap_or(local))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::NoopProvider;
+    use serde_json::json;
+
+    const TX_HASH: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+    fn preconfirmed_block() -> PreconfirmedBlock {
+        let block = json!({
+            "number": "0x10",
+            "hash": B256::ZERO,
+            "transactions": [{ "hash": TX_HASH, "nonce": "0x0" }]
+        });
+        let receipts = vec![json!({ "transactionHash": TX_HASH, "status": "0x1" })];
+        PreconfirmedBlock::new(block, receipts).unwrap()
+    }
+
+    fn preconfirmation_rpc(local: RpcModule<()>) -> RpcModule<()> {
+        let feed = PreconfirmationFeed::new("http://127.0.0.1:9", DEFAULT_PRECONF_POLL_INTERVAL);
+        feed.set_latest(preconfirmed_block());
+        PreconfirmationRpc::new(feed, local, NoopProvider::default()).into_rpc_module()
+    }
+
+    #[test]
+    fn block_with_transaction_hashes() {
+        let block = preconfirmed_block();
+        assert_eq!(block.number(), 0x10);
+        assert_eq!(block.block(true)["transactions"][0]["nonce"], "0x0");
+        assert_eq!(block.block(false)["transactions"], json!([TX_HASH]));
+    }
+
+    #[test]
+    fn invalid_block() {
+        assert!(PreconfirmedBlock::new(json!({ "transactions": [] }), vec![]).is_none());
+        assert!(PreconfirmedBlock::new(json!({ "number": "0x1", "transactions": [{}] }), vec![])
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn serves_pending_block() {
+        let mut local = RpcModule::new(());
+        local.register_method("eth_getBlockByNumber", |_, _, _| Value::Null).unwrap();
+        let module = preconfirmation_rpc(local);
+
+        let block: Value = module.call("eth_getBlockByNumber", ("pending", false)).await.unwrap();
+        assert_eq!(block["number"], "0x10");
+        assert_eq!(block["transactions"], json!([TX_HASH]));
+
+        let block: Value = module.call("eth_getBlockByNumber", ("latest", false)).await.unwrap();
+        assert!(block.is_null());
+    }
+
+    #[tokio::test]
+    async fn serves_preconfirmed_receipt() {
+        let mut local = RpcModule::new(());
+        local.register_method("eth_getTransactionReceipt", |_, _, _| Value::Null).unwrap();
+        local
+            .register_method("eth_getTransactionByHash", |_, _, _| json!({ "local": true }))
+            .unwrap();
+        let module = preconfirmation_rpc(local);
+
+        let receipt: Value = module.call("eth_getTransactionReceipt", [TX_HASH]).await.unwrap();
+        assert_eq!(receipt["status"], "0x1");
+
+        let unknown: Value = module.call("eth_getTransactionReceipt", [B256::ZERO]).await.unwrap();
+        assert!(unknown.is_null());
+
+        // local results take precedence
+        let tx: Value = module.call("eth_getTransactionByHash", [TX_HASH]).await.unwrap();
+        assert_eq!(tx, json!({ "local": true }));
+    }
+}
@@ -0,0 +1,435 @@
+//! The `rollup` RPC namespace, which exposes basic information about the rollup.
+//!
+//! This allows tooling to query the rollup config, the L1 origin, the sequencer window and the
+//! safe and finalized L2 heads without a separate connection to the rollup node.
+
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_chainspec::{ChainSpec, ForkCondition, Hardfork};
+use reth_primitives::{BlockId, BlockNumHash, BlockNumberOrTag, B256};
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider};
+use reth_rpc::eth::error::{EthApiError, EthResult};
+use reth_tasks::TaskSpawner;
+use serde::{Deserialize, Serialize};
+use std::{future::Future, sync::Arc};
+use tokio::sync::oneshot;
+
+/// Selector of `setL1BlockValues`, used before the Ecotone upgrade.
+const SET_L1_BLOCK_VALUES_SELECTOR: [u8; 4] = [0x01, 0x5d, 0x8e, 0xb9];
+
+/// Selector of `setL1BlockValuesEcotone`.
+const SET_L1_BLOCK_VALUES_ECOTONE_SELECTOR: [u8; 4] = [0x44, 0x0a, 0x5e, 0x20];
+
+/// The number of L1 blocks in which the batch of an L2 block must be included, as configured on
+/// all superchain networks.
+pub const SEQ_WINDOW_SIZE: u64 = 3600;
+
+/// The maximum number of seconds the timestamp of an L2 block can be ahead of its L1 origin,
+/// before the Fjord upgrade.
+pub const MAX_SEQUENCER_DRIFT: u64 = 600;
+
+/// The maximum number of seconds the timestamp of an L2 block can be ahead of its L1 origin,
+/// since the Fjord upgrade.
+pub const MAX_SEQUENCER_DRIFT_FJORD: u64 = 1800;
+
+/// The configuration of the rollup, derived from the chain spec.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupConfig {
+    /// The chain id of the L2.
+    pub l2_chain_id: u64,
+    /// The hash of the L2 genesis block.
+    pub genesis_hash: B256,
+    /// The block of the bedrock upgrade.
+    pub bedrock_block: Option<u64>,
+    /// The timestamp of the Regolith upgrade.
+    pub regolith_time: Option<u64>,
+    /// The timestamp of the Canyon upgrade.
+    pub canyon_time: Option<u64>,
+    /// The timestamp of the Ecotone upgrade.
+    pub ecotone_time: Option<u64>,
+    /// The timestamp of the Fjord upgrade.
+    pub fjord_time: Option<u64>,
+    /// The number of L1 blocks in which the batch of an L2 block must be included.
+    pub seq_window_size: u64,
+    /// The maximum number of seconds an L2 block can be ahead of its L1 origin, before Fjord.
+    pub max_sequencer_drift: u64,
+}
+
+impl RollupConfig {
+    /// Derives the rollup config from the given chain spec.
+    pub fn from_chain_spec(chain_spec: &ChainSpec) -> Self {
+        let bedrock_block = match chain_spec.fork(Hardfork::Bedrock) {
+            ForkCondition::Block(block) => Some(block),
+            _ => None,
+        };
+        Self {
+            l2_chain_id: chain_spec.chain.id(),
+            genesis_hash: chain_spec.genesis_hash(),
+            bedrock_block,
+            regolith_time: chain_spec.fork(Hardfork::Regolith).as_timestamp(),
+            canyon_time: chain_spec.fork(Hardfork::Canyon).as_timestamp(),
+            ecotone_time: chain_spec.fork(Hardfork::Ecotone).as_timestamp(),
+            fjord_time: chain_spec.fork(Hardfork::Fjord).as_timestamp(),
+            seq_window_size: SEQ_WINDOW_SIZE,
+            max_sequencer_drift: MAX_SEQUENCER_DRIFT,
+        }
+    }
+
+    /// Returns the maximum sequencer drift that applies to an L2 block with the given timestamp.
+    pub fn max_sequencer_drift_at(&self, timestamp: u64) -> u64 {
+        if self.fjord_time.is_some_and(|fjord| timestamp >= fjord) {
+            MAX_SEQUENCER_DRIFT_FJORD
+        } else {
+            self.max_sequencer_drift
+        }
+    }
+}
+
+/// A reference to a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockRef {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The number of the block.
+    pub number: u64,
+}
+
+impl From<BlockNumHash> for BlockRef {
+    fn from(block: BlockNumHash) -> Self {
+        Self { hash: block.hash, number: block.number }
+    }
+}
+
+/// The L1 block that an L2 block is derived from, as set by the L1 attributes transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1Origin {
+    /// The hash of the L1 block.
+    pub hash: B256,
+    /// The number of the L1 block.
+    pub number: u64,
+    /// The timestamp of the L1 block.
+    pub timestamp: u64,
+    /// The number of L2 blocks since the L1 origin changed, this is `0` for the first L2 block
+    /// of an epoch.
+    pub sequence_number: u64,
+}
+
+impl L1Origin {
+    /// Decodes the L1 origin from the input of an L1 attributes transaction.
+    pub fn decode(input: &[u8]) -> Option<Self> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        if selector == SET_L1_BLOCK_VALUES_SELECTOR {
+            // ABI encoded: number, timestamp, basefee, hash, sequence number, ...
+            Some(Self {
+                number: u64_from_word(input.get(4..36)?)?,
+                timestamp: u64_from_word(input.get(36..68)?)?,
+                hash: B256::from_slice(input.get(100..132)?),
+                sequence_number: u64_from_word(input.get(132..164)?)?,
+            })
+        } else if selector == SET_L1_BLOCK_VALUES_ECOTONE_SELECTOR {
+            // packed: base fee scalar, blob base fee scalar, sequence number, timestamp, number,
+            // base fee, blob base fee, hash, ...
+            Some(Self {
+                sequence_number: u64::from_be_bytes(input.get(12..20)?.try_into().ok()?),
+                timestamp: u64::from_be_bytes(input.get(20..28)?.try_into().ok()?),
+                number: u64::from_be_bytes(input.get(28..36)?.try_into().ok()?),
+                hash: B256::from_slice(input.get(100..132)?),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Decodes an ABI encoded word that must fit into a `u64`.
+fn u64_from_word(word: &[u8]) -> Option<u64> {
+    let (high, low) = word.split_at(24);
+    if high.iter().any(|byte| *byte != 0) {
+        return None
+    }
+    Some(u64::from_be_bytes(low.try_into().ok()?))
+}
+
+/// The sync status of the L2 chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    /// The latest L2 block.
+    pub head_l2: Option<BlockRef>,
+    /// The latest safe L2 block.
+    pub safe_l2: Option<BlockRef>,
+    /// The latest finalized L2 block.
+    pub finalized_l2: Option<BlockRef>,
+    /// The L1 origin of the latest L2 block.
+    pub head_l1_origin: Option<L1Origin>,
+}
+
+/// The status of the sequencing window of the unsafe L2 blocks.
+///
+/// The batches of the blocks after the safe head must be included on L1 before the window of
+/// their L1 origin ends, otherwise they are replaced with deposit-only blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencerWindowStatus {
+    /// The number of L1 blocks in which the batch of an L2 block must be included.
+    pub seq_window_size: u64,
+    /// The L1 origin of the oldest unsafe L2 block, the block after the safe head.
+    pub oldest_unsafe_l1_origin: Option<L1Origin>,
+    /// The last L1 block in which the batch of the oldest unsafe L2 block can be included.
+    pub window_end: Option<u64>,
+    /// The number of seconds the latest L2 block is ahead of its L1 origin.
+    pub head_drift: Option<u64>,
+    /// The maximum number of seconds the latest L2 block can be ahead of its L1 origin.
+    pub max_sequencer_drift: u64,
+}
+
+impl SequencerWindowStatus {
+    /// Computes the window status from the latest L2 block and the L1 origins of the latest and
+    /// the oldest unsafe L2 block.
+    pub fn new(
+        config: &RollupConfig,
+        head_timestamp: Option<u64>,
+        head_l1_origin: Option<L1Origin>,
+        oldest_unsafe_l1_origin: Option<L1Origin>,
+    ) -> Self {
+        let head_drift = head_timestamp
+            .zip(head_l1_origin)
+            .map(|(timestamp, origin)| timestamp.saturating_sub(origin.timestamp));
+        Self {
+            seq_window_size: config.seq_window_size,
+            oldest_unsafe_l1_origin,
+            window_end: oldest_unsafe_l1_origin
+                .map(|origin| origin.number.saturating_add(config.seq_window_size)),
+            head_drift,
+            max_sequencer_drift: config.max_sequencer_drift_at(head_timestamp.unwrap_or_default()),
+        }
+    }
+}
+
+/// The `rollup` RPC namespace.
+#[rpc(server, namespace = "rollup")]
+pub trait RollupApi {
+    /// Returns the configuration of the rollup.
+    #[method(name = "config")]
+    fn config(&self) -> RpcResult<RollupConfig>;
+
+    /// Returns the latest, safe and finalized L2 blocks, and the L1 origin of the latest block.
+    #[method(name = "syncStatus")]
+    async fn sync_status(&self) -> RpcResult<SyncStatus>;
+
+    /// Returns the L1 origin of the given L2 block.
+    #[method(name = "l1Origin")]
+    async fn l1_origin(&self, block: BlockNumberOrTag) -> RpcResult<Option<L1Origin>>;
+
+    /// Returns the sequencing window of the unsafe L2 blocks and the drift of the latest block.
+    #[method(name = "sequencerWindowStatus")]
+    async fn sequencer_window_status(&self) -> RpcResult<SequencerWindowStatus>;
+}
+
+/// Implements [`RollupApiServer`] on top of the provider.
+///
+/// All database reads are performed on the blocking pool.
+pub struct Rollup<Provider> {
+    inner: Arc<RollupInner<Provider>>,
+}
+
+impl<Provider> Rollup<Provider> {
+    /// Creates a new instance.
+    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { inner: Arc::new(RollupInner { provider, task_spawner }) }
+    }
+}
+
+impl<Provider> Rollup<Provider>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+{
+    /// Executes the closure on a new blocking task.
+    async fn on_blocking_task<C, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> EthResult<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(c(this));
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Returns the L1 origin of the given block, if it exists and starts with an L1 attributes
+    /// transaction.
+    fn block_l1_origin(&self, block: BlockId) -> EthResult<Option<L1Origin>> {
+        let Some(block) = self.inner.provider.block_by_id(block)? else { return Ok(None) };
+        Ok(block.body.first().and_then(|tx| L1Origin::decode(tx.input())))
+    }
+
+    fn try_sync_status(&self) -> EthResult<SyncStatus> {
+        let provider = &self.inner.provider;
+        let head_l2 = provider
+            .latest_header()?
+            .map(|header| BlockRef { hash: header.hash(), number: header.number });
+        let head_l1_origin = match head_l2 {
+            Some(head) => self.block_l1_origin(head.hash.into())?,
+            None => None,
+        };
+        Ok(SyncStatus {
+            head_l2,
+            safe_l2: provider.safe_block_num_hash()?.map(Into::into),
+            finalized_l2: provider.finalized_block_num_hash()?.map(Into::into),
+            head_l1_origin,
+        })
+    }
+
+    fn try_sequencer_window_status(&self) -> EthResult<SequencerWindowStatus> {
+        let provider = &self.inner.provider;
+        let config = RollupConfig::from_chain_spec(&provider.chain_spec());
+        let head = provider.latest_header()?;
+        let head_l1_origin = match &head {
+            Some(head) => self.block_l1_origin(head.hash().into())?,
+            None => None,
+        };
+        // the oldest unsafe block is the one after the safe head, if the head is ahead of it
+        let oldest_unsafe = provider.safe_block_num_hash()?.map(|safe| safe.number + 1);
+        let oldest_unsafe_l1_origin = match (&head, oldest_unsafe) {
+            (Some(head), Some(oldest_unsafe)) if head.number >= oldest_unsafe => {
+                self.block_l1_origin(oldest_unsafe.into())?
+            }
+            _ => None,
+        };
+        Ok(SequencerWindowStatus::new(
+            &config,
+            head.map(|head| head.timestamp),
+            head_l1_origin,
+            oldest_unsafe_l1_origin,
+        ))
+    }
+}
+
+#[async_trait]
+impl<Provider> RollupApiServer for Rollup<Provider>
+where
+    Provider: BlockReaderIdExt + ChainSpecProvider + 'static,
+{
+    fn config(&self) -> RpcResult<RollupConfig> {
+        Ok(RollupConfig::from_chain_spec(&self.inner.provider.chain_spec()))
+    }
+
+    async fn sync_status(&self) -> RpcResult<SyncStatus> {
+        Ok(self.on_blocking_task(|this| this.try_sync_status()).await?)
+    }
+
+    async fn l1_origin(&self, block: BlockNumberOrTag) -> RpcResult<Option<L1Origin>> {
+        Ok(self.on_blocking_task(move |this| this.block_l1_origin(block.into())).await?)
+    }
+
+    async fn sequencer_window_status(&self) -> RpcResult<SequencerWindowStatus> {
+        Ok(self.on_blocking_task(|this| this.try_sequencer_window_status()).await?)
+    }
+}
+
+impl<Provider> Clone for Rollup<Provider> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Provider> std::fmt::Debug for Rollup<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rollup").finish_non_exhaustive()
+    }
+}
+
+struct RollupInner<Provider> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::OP_MAINNET;
+    use reth_primitives::hex;
+
+    #[test]
+    fn decode_bedrock_l1_origin() {
+        let input = hex!("015d8eb900000000000000000000000000000000000000000000000000000000008768240000000000000000000000000000000000000000000000000000000064443450000000000000000000000000000000000000000000000000000000000000000e0444c991c5fe1d7291ff34b3f5c3b44ee861f021396d33ba3255b83df30e357d00000000000000000000000000000000000000000000000000000000000000050000000000000000000000007431310e026b69bfc676c0013e12a1a11411eec9000000000000000000000000000000000000000000000000000000000000083400000000000000000000000000000000000000000000000000000000000f4240");
+        let origin = L1Origin::decode(&input).unwrap();
+        assert_eq!(origin.number, 0x876824);
+        assert_eq!(origin.timestamp, 0x64443450);
+        assert_eq!(
+            origin.hash,
+            B256::from(hex!("0444c991c5fe1d7291ff34b3f5c3b44ee861f021396d33ba3255b83df30e357d"))
+        );
+        assert_eq!(origin.sequence_number, 5);
+    }
+
+    #[test]
+    fn decode_ecotone_l1_origin() {
+        let input = hex!("440a5e20000f424000000000000000000000000300000000670d6d890000000000000125000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000015cb2e1b1a2b4f0c8d0b7b4ae6d7e0b1b6a0f1f2a3b4c5d6e7f8091a2b3c4d5e6000000000000000000000000e1b64045351b0b6e9821f19b39f81bc4711d2230");
+        let origin = L1Origin::decode(&input).unwrap();
+        assert_eq!(origin.sequence_number, 3);
+        assert_eq!(origin.timestamp, 0x670d6d89);
+        assert_eq!(origin.number, 0x125);
+        assert_eq!(
+            origin.hash,
+            B256::from(hex!("5cb2e1b1a2b4f0c8d0b7b4ae6d7e0b1b6a0f1f2a3b4c5d6e7f8091a2b3c4d5e6"))
+        );
+    }
+
+    #[test]
+    fn decode_unknown_input() {
+        assert_eq!(L1Origin::decode(&[]), None);
+        assert_eq!(L1Origin::decode(&[0xde, 0xad, 0xbe, 0xef]), None);
+        assert_eq!(L1Origin::decode(&SET_L1_BLOCK_VALUES_SELECTOR), None);
+    }
+
+    #[test]
+    fn op_mainnet_rollup_config() {
+        let config = RollupConfig::from_chain_spec(&OP_MAINNET);
+        assert_eq!(config.l2_chain_id, 10);
+        assert_eq!(config.bedrock_block, Some(105_235_063));
+        assert!(config.ecotone_time.is_some());
+        assert_eq!(config.seq_window_size, SEQ_WINDOW_SIZE);
+    }
+
+    fn origin(number: u64, timestamp: u64) -> L1Origin {
+        L1Origin { hash: B256::ZERO, number, timestamp, sequence_number: 0 }
+    }
+
+    #[test]
+    fn sequencer_window_status() {
+        let mut config = RollupConfig::from_chain_spec(&OP_MAINNET);
+        config.fjord_time = Some(2_000);
+
+        let status = SequencerWindowStatus::new(
+            &config,
+            Some(1_100),
+            Some(origin(110, 1_000)),
+            Some(origin(100, 900)),
+        );
+        assert_eq!(status.oldest_unsafe_l1_origin, Some(origin(100, 900)));
+        assert_eq!(status.window_end, Some(100 + SEQ_WINDOW_SIZE));
+        assert_eq!(status.head_drift, Some(100));
+        assert_eq!(status.max_sequencer_drift, MAX_SEQUENCER_DRIFT);
+
+        // fjord raises the drift limit
+        let status = SequencerWindowStatus::new(&config, Some(2_000), None, None);
+        assert_eq!(status.max_sequencer_drift, MAX_SEQUENCER_DRIFT_FJORD);
+    }
+
+    #[test]
+    fn sequencer_window_status_without_unsafe_blocks() {
+        let config = RollupConfig::from_chain_spec(&OP_MAINNET);
+        let status = SequencerWindowStatus::new(&config, Some(1_000), Some(origin(1, 1_200)), None);
+        assert_eq!(status.oldest_unsafe_l1_origin, None);
+        assert_eq!(status.window_end, None);
+        // an L2 block can't be behind its L1 origin, but don't underflow on bad data
+        assert_eq!(status.head_drift, Some(0));
+    }
+}
@@ -0,0 +1,308 @@
+//! Status updates of transactions that are forwarded to the sequencer.
+//!
+//! Instead of polling `eth_getTransactionReceipt`, clients can subscribe to
+//! `sequencer_subscribeTransactionStatus` and are notified when a transaction is forwarded to the
+//! sequencer, acknowledged by it, included in an unsafe block and finalized.
+
+use super::rollup::BlockRef;
+use jsonrpsee::{
+    core::SubscriptionResult, proc_macros::rpc, PendingSubscriptionSink, SubscriptionMessage,
+};
+use parking_lot::Mutex;
+use reth_primitives::{SealedBlock, B256};
+use reth_provider::{BlockIdReader, CanonStateNotification, CanonStateSubscriptions};
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The default number of transactions whose inclusion is tracked.
+pub const DEFAULT_MAX_TRACKED_TRANSACTIONS: u32 = 10_000;
+
+/// The capacity of the channel of status events.
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// The status of a transaction that was submitted to the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionStatus {
+    /// The transaction is being forwarded to the sequencer.
+    Forwarded,
+    /// The sequencer accepted the transaction.
+    Acknowledged,
+    /// The transaction couldn't be forwarded to the sequencer.
+    ForwardingFailed,
+    /// The transaction is included in an unsafe block.
+    Included,
+    /// The block that includes the transaction is finalized.
+    Finalized,
+}
+
+/// A status update of a transaction, as emitted by `sequencer_subscribeTransactionStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatusEvent {
+    /// The hash of the transaction.
+    pub hash: B256,
+    /// The new status of the transaction.
+    pub status: TransactionStatus,
+    /// The block that includes the transaction, for [`TransactionStatus::Included`] and
+    /// [`TransactionStatus::Finalized`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockRef>,
+}
+
+/// Tracks the transactions that are forwarded to the sequencer until they are finalized, and
+/// broadcasts their status updates.
+///
+/// Only the most recently forwarded transactions are tracked, transactions that are never
+/// included are eventually evicted.
+#[derive(Debug, Clone)]
+pub struct TransactionStatusTracker {
+    inner: Arc<TransactionStatusTrackerInner>,
+}
+
+impl TransactionStatusTracker {
+    /// Creates a new [`TransactionStatusTracker`] that tracks up to the given number of
+    /// transactions.
+    pub fn new(max_tracked: u32) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let inner = TransactionStatusTrackerInner {
+            events,
+            tracked: Mutex::new(LruMap::new(ByLength::new(max_tracked))),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns a new receiver of status updates.
+    pub fn subscribe(&self) -> broadcast::Receiver<TransactionStatusEvent> {
+        self.inner.events.subscribe()
+    }
+
+    fn emit(&self, hash: B256, status: TransactionStatus, block: Option<BlockRef>) {
+        // there may be no subscribers
+        let _ = self.inner.events.send(TransactionStatusEvent { hash, status, block });
+    }
+
+    /// Starts tracking the transaction, which is being forwarded to the sequencer.
+    pub fn on_forwarded(&self, hash: B256) {
+        self.inner.tracked.lock().insert(hash, None);
+        self.emit(hash, TransactionStatus::Forwarded, None);
+    }
+
+    /// Marks the transaction as accepted by the sequencer.
+    pub fn on_acknowledged(&self, hash: B256) {
+        self.emit(hash, TransactionStatus::Acknowledged, None);
+    }
+
+    /// Stops tracking the transaction, which couldn't be forwarded to the sequencer.
+    pub fn on_forwarding_failed(&self, hash: B256) {
+        self.inner.tracked.lock().remove(&hash);
+        self.emit(hash, TransactionStatus::ForwardingFailed, None);
+    }
+
+    /// Updates the inclusion of the tracked transactions with the new canonical chain.
+    pub fn on_canonical_state(&self, notification: &CanonStateNotification) {
+        if let Some(reverted) = notification.reverted() {
+            let mut tracked = self.inner.tracked.lock();
+            for block in reverted.blocks_iter() {
+                for tx in &block.body {
+                    if let Some(included) = tracked.peek_mut(&tx.hash()) {
+                        *included = None;
+                    }
+                }
+            }
+        }
+        for block in notification.committed().blocks_iter() {
+            self.on_block(block);
+        }
+    }
+
+    fn on_block(&self, block: &SealedBlock) {
+        let block_ref = BlockRef { hash: block.hash(), number: block.number };
+        let mut tracked = self.inner.tracked.lock();
+        for tx in &block.body {
+            let hash = tx.hash();
+            if let Some(included) = tracked.peek_mut(&hash) {
+                *included = Some(block_ref);
+                self.emit(hash, TransactionStatus::Included, Some(block_ref));
+            }
+        }
+    }
+
+    /// Emits the finalization of the transactions that are included in blocks up to the given
+    /// finalized block, and stops tracking them.
+    pub fn on_finalized(&self, finalized: u64) {
+        let mut tracked = self.inner.tracked.lock();
+        let finalized_txs = tracked
+            .iter()
+            .filter_map(|(hash, included)| {
+                (*included).filter(|block| block.number <= finalized).map(|block| (*hash, block))
+            })
+            .collect::<Vec<_>>();
+        for (hash, block) in finalized_txs {
+            tracked.remove(&hash);
+            self.emit(hash, TransactionStatus::Finalized, Some(block));
+        }
+    }
+
+    /// Follows the canonical chain of the provider to track the inclusion and finalization of
+    /// the forwarded transactions.
+    pub async fn run<Provider>(self, provider: Provider)
+    where
+        Provider: CanonStateSubscriptions + BlockIdReader,
+    {
+        let mut notifications = provider.subscribe_to_canonical_state();
+        loop {
+            match notifications.recv().await {
+                Ok(notification) => {
+                    self.on_canonical_state(&notification);
+                    if let Ok(Some(finalized)) = provider.finalized_block_number() {
+                        self.on_finalized(finalized);
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+impl Default for TransactionStatusTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TRACKED_TRANSACTIONS)
+    }
+}
+
+#[derive(Debug)]
+struct TransactionStatusTrackerInner {
+    /// Sends the status updates
+    events: broadcast::Sender<TransactionStatusEvent>,
+    /// The tracked transactions, with the block that includes them
+    tracked: Mutex<LruMap<B256, Option<BlockRef>, ByLength>>,
+}
+
+/// RPC interface to subscribe to the status of forwarded transactions.
+#[rpc(server, namespace = "sequencer")]
+pub trait SequencerTransactionStatusApi {
+    /// Creates a subscription that emits the status updates of transactions that are forwarded to
+    /// the sequencer, optionally only of the given transactions.
+    #[subscription(
+        name = "subscribeTransactionStatus" => "transactionStatus",
+        unsubscribe = "unsubscribeTransactionStatus",
+        item = TransactionStatusEvent
+    )]
+    async fn subscribe_transaction_status(&self, hashes: Option<Vec<B256>>) -> SubscriptionResult;
+}
+
+#[async_trait::async_trait]
+impl SequencerTransactionStatusApiServer for TransactionStatusTracker {
+    async fn subscribe_transaction_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        hashes: Option<Vec<B256>>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let hashes = hashes.map(HashSet::<B256>::from_iter);
+        let mut events = self.subscribe();
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    _ = sink.closed() => break,
+                    event = events.recv() => match event {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                };
+                if hashes.as_ref().is_some_and(|hashes| !hashes.contains(&event.hash)) {
+                    continue
+                }
+                let Ok(msg) = SubscriptionMessage::from_json(&event) else { break };
+                if sink.send(msg).await.is_err() {
+                    break
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Header, SealedHeader, Signature, Transaction, TransactionSigned, TxLegacy,
+    };
+
+    fn transaction(nonce: u64) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Signature::default(),
+        )
+    }
+
+    fn block(number: u64, body: Vec<TransactionSigned>) -> SealedBlock {
+        let header = Header { number, ..Default::default() };
+        SealedBlock {
+            header: SealedHeader::new(header, B256::with_last_byte(number as u8)),
+            body,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tracks_forwarded_transactions_until_finalized() {
+        let tracker = TransactionStatusTracker::default();
+        let mut events = tracker.subscribe();
+        let (tx, other) = (transaction(0), transaction(1));
+
+        tracker.on_forwarded(tx.hash());
+        tracker.on_acknowledged(tx.hash());
+        tracker.on_block(&block(1, vec![other, tx.clone()]));
+        tracker.on_finalized(0);
+        tracker.on_finalized(1);
+        tracker.on_finalized(2);
+
+        let block_ref = Some(BlockRef { hash: B256::with_last_byte(1), number: 1 });
+        let statuses = [
+            (TransactionStatus::Forwarded, None),
+            (TransactionStatus::Acknowledged, None),
+            (TransactionStatus::Included, block_ref),
+            (TransactionStatus::Finalized, block_ref),
+        ];
+        for (status, block) in statuses {
+            assert_eq!(
+                events.try_recv().unwrap(),
+                TransactionStatusEvent { hash: tx.hash(), status, block }
+            );
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn failed_transactions_are_not_tracked() {
+        let tracker = TransactionStatusTracker::default();
+        let tx = transaction(0);
+
+        tracker.on_forwarded(tx.hash());
+        tracker.on_forwarding_failed(tx.hash());
+
+        let mut events = tracker.subscribe();
+        tracker.on_block(&block(1, vec![tx]));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn event_serde() {
+        let event = TransactionStatusEvent {
+            hash: B256::ZERO,
+            status: TransactionStatus::ForwardingFailed,
+            block: None,
+        };
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            serde_json::json!({ "hash": B256::ZERO, "status": "forwardingFailed" })
+        );
+    }
+}
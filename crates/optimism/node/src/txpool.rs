@@ -6,6 +6,7 @@ use reth_primitives::{Block, GotExpected, InvalidTransactionError, SealedBlock};
 use reth_provider::{BlockReaderIdExt, StateProviderFactory};
 use reth_revm::L1BlockInfo;
 use reth_transaction_pool::{
+    validate::{AddressFilter, FeeFloor, ValidatorWithAdmission},
     CoinbaseTipOrdering, EthPoolTransaction, EthPooledTransaction, EthTransactionValidator, Pool,
     TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
     TransactionValidator,
@@ -15,9 +16,15 @@ use std::sync::{
     Arc,
 };
 
-/// Type alias for default optimism transaction pool
+/// Type alias for default optimism transaction pool, which only admits the transactions that the
+/// address lists allow and that pay the fee floor, if enabled.
 pub type OpTransactionPool<Client, S> = Pool<
-    TransactionValidationTaskExecutor<OpTransactionValidator<Client, EthPooledTransaction>>,
+    TransactionValidationTaskExecutor<
+        ValidatorWithAdmission<
+            OpTransactionValidator<Client, EthPooledTransaction>,
+            (AddressFilter, Option<FeeFloor>),
+        >,
+    >,
     CoinbaseTipOrdering<EthPooledTransaction>,
     S,
 >;
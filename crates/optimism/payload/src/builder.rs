@@ -14,12 +14,15 @@ use reth_primitives::{
     eip4844::calculate_excess_blob_gas,
     proofs,
     revm::env::tx_env_with_recovered,
-    Block, Hardfork, Header, IntoRecoveredTransaction, Receipt, TxType, EMPTY_OMMER_ROOT_HASH,
-    U256,
+    Address, Block, Hardfork, Header, IntoRecoveredTransaction, Receipt, TxType,
+    EMPTY_OMMER_ROOT_HASH, U256,
 };
 use reth_provider::StateProviderFactory;
 use reth_revm::database::StateProviderDatabase;
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{
+    pool::BestTransactionsWithPriority, validate::AddressFilter, BestTransactionsAttributes,
+    TransactionPool, ValidPoolTransaction,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
@@ -29,7 +32,7 @@ use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
 /// Optimism's payload builder
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct OptimismPayloadBuilder<EvmConfig> {
     /// The rollup's compute pending block configuration option.
     // TODO(clabby): Implement this feature.
@@ -38,12 +41,41 @@ pub struct OptimismPayloadBuilder<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// Selects the pool transactions that are included.
+    pool_transactions: PoolTransactionsOptions,
 }
 
 impl<EvmConfig> OptimismPayloadBuilder<EvmConfig> {
     /// `OptimismPayloadBuilder` constructor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { compute_pending_block: true, chain_spec, evm_config }
+        Self {
+            compute_pending_block: true,
+            chain_spec,
+            evm_config,
+            pool_transactions: PoolTransactionsOptions::default(),
+        }
+    }
+
+    /// Sets the options for selecting the pool transactions that are included.
+    ///
+    /// The transactions of the payload attributes are always included.
+    pub fn with_pool_transactions(mut self, pool_transactions: PoolTransactionsOptions) -> Self {
+        self.pool_transactions = pool_transactions;
+        self
+    }
+
+    /// Only includes the pool transactions whose sender and recipient are allowed by the filter.
+    ///
+    /// The transactions of the payload attributes are always included.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.pool_transactions = self.pool_transactions.with_address_filter(address_filter);
+        self
+    }
+
+    /// Includes the pool transactions of the given senders first, regardless of their tip.
+    pub fn with_priority_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.pool_transactions = self.pool_transactions.with_priority_senders(senders);
+        self
     }
 
     /// Sets the rollup's compute pending block configuration option.
@@ -83,7 +115,12 @@ where
         &self,
         args: BuildArguments<Pool, Client, OptimismPayloadBuilderAttributes, OptimismBuiltPayload>,
     ) -> Result<BuildOutcome<OptimismBuiltPayload>, PayloadBuilderError> {
-        optimism_payload_builder(self.evm_config.clone(), args, self.compute_pending_block)
+        optimism_payload_builder(
+            self.evm_config.clone(),
+            &self.pool_transactions,
+            args,
+            self.compute_pending_block,
+        )
     }
 
     fn on_missing_payload(
@@ -235,9 +272,12 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The pool transactions are selected as configured by the [`PoolTransactionsOptions`].
 #[inline]
 pub(crate) fn optimism_payload_builder<EvmConfig, Pool, Client>(
     evm_config: EvmConfig,
+    pool_transactions: &PoolTransactionsOptions,
     args: BuildArguments<Pool, Client, OptimismPayloadBuilderAttributes, OptimismBuiltPayload>,
     _compute_pending_block: bool,
 ) -> Result<BuildOutcome<OptimismBuiltPayload>, PayloadBuilderError>
@@ -272,10 +312,24 @@ where
 
     let mut executed_txs = Vec::with_capacity(attributes.transactions.len());
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
+    let best_attributes = BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut best_txs = pool.best_transactions_with_attributes(best_attributes);
+    let PoolTransactionsOptions { address_filter, priority_senders } = pool_transactions;
+    if let Some(priority_senders) = priority_senders.as_ref().filter(|senders| !senders.is_empty())
+    {
+        // the transactions of the priority senders are included first, regardless of their tip
+        let priority_senders = Arc::clone(priority_senders);
+        best_txs = Box::new(BestTransactionsWithPriority::new(
+            best_txs,
+            pool.best_transactions_with_attributes(best_attributes),
+            move |tx: &Arc<ValidPoolTransaction<Pool::Transaction>>| {
+                priority_senders.contains(&tx.sender())
+            },
+        ));
+    }
 
     let mut total_fees = U256::ZERO;
 
@@ -424,6 +478,17 @@ where
                 return Ok(BuildOutcome::Cancelled)
             }
 
+            // skip transactions of senders or to recipients that aren't allowed, and their
+            // descendants
+            if let Some(address) = address_filter
+                .as_ref()
+                .and_then(|filter| filter.disallowed_address(pool_tx.sender(), pool_tx.to()))
+            {
+                trace!(target: "payload_builder", tx=?pool_tx.hash(), %address, "skipping transaction of disallowed address");
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+
             // convert tx to a signed transaction
             let tx = pool_tx.to_recovered_transaction();
             let env = EnvWithHandlerCfg::new_with_cfg_env(
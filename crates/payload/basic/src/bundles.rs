@@ -0,0 +1,259 @@
+use crate::{ForcedBundle, PayloadTransactionsArgs, PayloadTransactionsHook};
+use parking_lot::Mutex;
+use reth_payload_builder::error::PayloadBuilderError;
+use reth_payload_primitives::{
+    BundlePool, BundlePoolError, QueuedBundle, MAX_BUNDLE_BLOCKS_AHEAD, MAX_BUNDLE_TRANSACTIONS,
+};
+use reth_primitives::{BlockNumber, B256};
+use std::{collections::HashSet, sync::Arc};
+
+/// The default maximum number of bundles a [`BundleQueue`] holds.
+pub const DEFAULT_MAX_QUEUED_BUNDLES: usize = 1024;
+
+/// A queue of bundles that are force-included in the payloads that are built locally.
+///
+/// As a [`PayloadTransactionsHook`], it provides the bundles that target the block of a new payload
+/// job, in the order they were inserted. A bundle is skipped if it doesn't fit into the remaining
+/// gas of the payload, or if it shares a transaction with a bundle that was already selected.
+/// Bundles are dropped once the head moves past their block.
+///
+/// The bundles are included atomically: a bundle is skipped when the payload is built if one of
+/// its transactions fails to execute, or reverts without being allowed to.
+#[derive(Debug, Clone)]
+pub struct BundleQueue {
+    inner: Arc<Mutex<BundleQueueInner>>,
+}
+
+impl BundleQueue {
+    /// Creates a new queue that holds at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self { inner: Arc::new(Mutex::new(BundleQueueInner { bundles: Vec::new(), max_bundles })) }
+    }
+
+    /// Inserts the bundle and returns its hash.
+    ///
+    /// A queued bundle with the same replacement uuid is replaced. The bundle is rejected if it
+    /// targets a block more than [`MAX_BUNDLE_BLOCKS_AHEAD`] blocks past `next_block`.
+    pub fn insert(
+        &self,
+        bundle: QueuedBundle,
+        next_block: BlockNumber,
+    ) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.transactions.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(BundlePoolError::TooManyTransactions(bundle.transactions.len()))
+        }
+        if let Some(tx) = bundle.transactions.iter().find(|tx| tx.is_eip4844()) {
+            return Err(BundlePoolError::BlobTransaction(tx.hash()))
+        }
+        QueuedBundle::ensure_block_number(bundle.block_number, next_block)?;
+
+        let hash = bundle.hash();
+        let mut inner = self.inner.lock();
+        if let Some(uuid) = &bundle.replacement_uuid {
+            inner.bundles.retain(|queued| queued.replacement_uuid.as_ref() != Some(uuid));
+        }
+        // resubmitting a bundle is a no-op
+        inner.bundles.retain(|queued| queued.hash() != hash);
+        if inner.bundles.len() >= inner.max_bundles {
+            return Err(BundlePoolError::PoolFull(inner.max_bundles))
+        }
+        inner.bundles.push(bundle);
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given hash, returns true if it was queued.
+    pub fn remove(&self, hash: B256) -> bool {
+        let mut inner = self.inner.lock();
+        let len = inner.bundles.len();
+        inner.bundles.retain(|queued| queued.hash() != hash);
+        inner.bundles.len() != len
+    }
+
+    /// Returns the number of queued bundles.
+    pub fn len(&self) -> usize {
+        self.inner.lock().bundles.len()
+    }
+
+    /// Returns true if no bundles are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for BundleQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_QUEUED_BUNDLES)
+    }
+}
+
+impl BundlePool for BundleQueue {
+    fn insert(
+        &self,
+        bundle: QueuedBundle,
+        next_block: BlockNumber,
+    ) -> Result<B256, BundlePoolError> {
+        Self::insert(self, bundle, next_block)
+    }
+
+    fn remove(&self, hash: B256) -> bool {
+        Self::remove(self, hash)
+    }
+}
+
+impl PayloadTransactionsHook for BundleQueue {
+    fn transactions(
+        &self,
+        args: PayloadTransactionsArgs<'_>,
+    ) -> Result<Vec<ForcedBundle>, PayloadBuilderError> {
+        let block_number = args.parent_block.number + 1;
+        let mut inner = self.inner.lock();
+
+        // bundles of blocks that are already built can't be included anymore
+        inner.bundles.retain(|queued| queued.block_number >= block_number);
+
+        let mut gas = 0u64;
+        let mut selected = HashSet::new();
+        let mut bundles = Vec::new();
+        for bundle in inner
+            .bundles
+            .iter()
+            .filter(|queued| queued.block_number == block_number)
+            .filter(|queued| queued.is_active_at(args.timestamp))
+        {
+            let bundle_gas = bundle.gas_limit();
+            if gas.saturating_add(bundle_gas) > args.gas_limit ||
+                bundle.transactions.iter().any(|tx| selected.contains(&tx.hash()))
+            {
+                continue
+            }
+            gas += bundle_gas;
+            selected.extend(bundle.transactions.iter().map(|tx| tx.hash()));
+            bundles.push(ForcedBundle {
+                transactions: bundle.transactions.clone(),
+                reverting_tx_hashes: bundle.reverting_tx_hashes.clone(),
+            });
+        }
+
+        Ok(bundles)
+    }
+}
+
+#[derive(Debug)]
+struct BundleQueueInner {
+    /// The queued bundles, in the order they were inserted.
+    bundles: Vec<QueuedBundle>,
+    /// The maximum number of bundles.
+    max_bundles: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_payload_builder::PayloadId;
+    use reth_primitives::{
+        Address, Header, SealedBlock, Signature, Transaction, TransactionSigned,
+        TransactionSignedEcRecovered, TxLegacy,
+    };
+
+    fn transaction(nonce: u64, gas_limit: u64) -> TransactionSignedEcRecovered {
+        let tx = Transaction::Legacy(TxLegacy { nonce, gas_limit, ..Default::default() });
+        TransactionSigned::from_transaction_and_signature(tx, Signature::default())
+            .with_signer(Address::ZERO)
+    }
+
+    fn bundle(transactions: Vec<TransactionSignedEcRecovered>, block_number: u64) -> QueuedBundle {
+        QueuedBundle {
+            transactions,
+            block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: HashSet::new(),
+            replacement_uuid: None,
+        }
+    }
+
+    fn forced(queue: &BundleQueue, parent: &SealedBlock, timestamp: u64, gas_limit: u64) -> usize {
+        queue
+            .transactions(PayloadTransactionsArgs {
+                payload_id: PayloadId::new([0; 8]),
+                parent_block: parent,
+                timestamp,
+                suggested_fee_recipient: Address::ZERO,
+                gas_limit,
+            })
+            .unwrap()
+            .iter()
+            .map(|bundle| bundle.transactions.len())
+            .sum()
+    }
+
+    #[test]
+    fn replaces_and_removes_bundles() {
+        let queue = BundleQueue::new(2);
+        let mut first = bundle(vec![transaction(0, 21_000)], 1);
+        first.replacement_uuid = Some("uuid".to_string());
+        let mut second = bundle(vec![transaction(1, 21_000)], 1);
+        second.replacement_uuid = Some("uuid".to_string());
+
+        queue.insert(first, 1).unwrap();
+        let hash = queue.insert(second, 1).unwrap();
+        assert_eq!(queue.len(), 1);
+
+        queue.insert(bundle(vec![transaction(2, 21_000)], 1), 1).unwrap();
+        assert!(matches!(
+            queue.insert(bundle(vec![transaction(3, 21_000)], 1), 1),
+            Err(BundlePoolError::PoolFull(2))
+        ));
+        assert!(matches!(queue.insert(bundle(vec![], 1), 1), Err(BundlePoolError::EmptyBundle)));
+        let too_many = (0..=MAX_BUNDLE_TRANSACTIONS as u64).map(|nonce| transaction(nonce, 21_000));
+        assert!(matches!(
+            queue.insert(bundle(too_many.collect(), 1), 1),
+            Err(BundlePoolError::TooManyTransactions(_))
+        ));
+
+        assert!(queue.remove(hash));
+        assert!(!queue.remove(hash));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn rejects_bundles_far_ahead() {
+        let queue = BundleQueue::default();
+        let max_block_number = 2 + MAX_BUNDLE_BLOCKS_AHEAD;
+        queue.insert(bundle(vec![transaction(0, 21_000)], max_block_number), 2).unwrap();
+        assert!(matches!(
+            queue.insert(bundle(vec![transaction(1, 21_000)], max_block_number + 1), 2),
+            Err(BundlePoolError::BlockNumberTooHigh { block_number, max_block_number: max })
+                if block_number == max_block_number + 1 && max == max_block_number
+        ));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn selects_bundles_of_next_block() {
+        let queue = BundleQueue::default();
+        let shared = transaction(0, 21_000);
+        queue.insert(bundle(vec![transaction(1, 21_000)], 1), 1).unwrap();
+        queue.insert(bundle(vec![shared.clone(), transaction(2, 21_000)], 2), 1).unwrap();
+        // shares a transaction with the previous bundle
+        queue.insert(bundle(vec![shared, transaction(3, 21_000)], 2), 1).unwrap();
+        let mut late = bundle(vec![transaction(4, 21_000)], 2);
+        late.min_timestamp = Some(100);
+        queue.insert(late, 1).unwrap();
+        queue.insert(bundle(vec![transaction(5, 50_000)], 2), 1).unwrap();
+
+        let parent = SealedBlock {
+            header: Header { number: 1, ..Default::default() }.seal_slow(),
+            ..Default::default()
+        };
+        assert_eq!(forced(&queue, &parent, 12, 100_000), 3);
+        assert_eq!(forced(&queue, &parent, 100, 100_000), 3);
+        assert_eq!(forced(&queue, &parent, 100, 200_000), 4);
+
+        // the bundle of the parent block was dropped
+        assert_eq!(queue.len(), 4);
+    }
+}
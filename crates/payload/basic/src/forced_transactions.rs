@@ -0,0 +1,125 @@
+use reth_payload_builder::{error::PayloadBuilderError, PayloadId};
+use reth_primitives::{Address, SealedBlock, TransactionSignedEcRecovered, B256};
+use std::{collections::HashSet, fmt};
+
+/// A hook that is invoked when payload attributes arrive, and returns transactions that must be
+/// included in every payload that is built locally for these attributes, e.g. operator
+/// transactions or oracle updates.
+///
+/// The transactions are executed in order before the transactions of the pool, grouped into
+/// [`ForcedBundle`]s that are included atomically. A bundle with a transaction that fails to
+/// execute is skipped. The empty payload, which is returned if no payload was built in time,
+/// doesn't contain them.
+pub trait PayloadTransactionsHook: fmt::Debug + Send + Sync + 'static {
+    /// Returns the bundles of transactions to include in the payload.
+    fn transactions(
+        &self,
+        args: PayloadTransactionsArgs<'_>,
+    ) -> Result<Vec<ForcedBundle>, PayloadBuilderError>;
+}
+
+/// Transactions of a [`PayloadTransactionsHook`] that are included atomically: either all of them
+/// are included in order, or none of them.
+///
+/// The bundle is skipped if one of its transactions fails to execute, or reverts without being
+/// allowed to.
+#[derive(Debug, Clone, Default)]
+pub struct ForcedBundle {
+    /// The transactions of the bundle.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// The hashes of the transactions that are allowed to revert.
+    pub reverting_tx_hashes: HashSet<B256>,
+}
+
+impl ForcedBundle {
+    /// Returns true if the transaction with the given hash is allowed to revert.
+    pub fn may_revert(&self, hash: &B256) -> bool {
+        self.reverting_tx_hashes.contains(hash)
+    }
+}
+
+impl From<TransactionSignedEcRecovered> for ForcedBundle {
+    /// Returns a bundle of the single transaction, which is included even if it reverts.
+    fn from(tx: TransactionSignedEcRecovered) -> Self {
+        Self { reverting_tx_hashes: HashSet::from([tx.hash()]), transactions: vec![tx] }
+    }
+}
+
+/// The payload attributes a [`PayloadTransactionsHook`] is invoked with.
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadTransactionsArgs<'a> {
+    /// The id of the payload.
+    pub payload_id: PayloadId,
+    /// The block the payload is built on top of.
+    pub parent_block: &'a SealedBlock,
+    /// The timestamp of the payload.
+    pub timestamp: u64,
+    /// The suggested recipient of the fees of the payload.
+    pub suggested_fee_recipient: Address,
+    /// The gas limit of the payload.
+    pub gas_limit: u64,
+}
+
+/// Errors of the transactions of a [`PayloadTransactionsHook`].
+#[derive(Debug, thiserror::Error)]
+pub enum ForcedTransactionsError {
+    /// The transactions don't fit into the gas limit of the payload.
+    #[error("forced transactions require {gas} gas, exceeding the gas limit of {gas_limit}")]
+    ExceedsGasLimit {
+        /// The sum of the gas limits of the transactions.
+        gas: u64,
+        /// The gas limit of the payload.
+        gas_limit: u64,
+    },
+    /// A blob transaction can't be forced, since its sidecar is not available to the payload.
+    #[error("forced transaction {0} is a blob transaction")]
+    BlobTransaction(B256),
+}
+
+/// Ensures that the transactions of a [`PayloadTransactionsHook`] fit into the gas limit of the
+/// payload, and that they are not blob transactions.
+pub(crate) fn ensure_valid_forced_transactions(
+    bundles: &[ForcedBundle],
+    gas_limit: u64,
+) -> Result<(), ForcedTransactionsError> {
+    let transactions = || bundles.iter().flat_map(|bundle| &bundle.transactions);
+    if let Some(tx) = transactions().find(|tx| tx.is_eip4844()) {
+        return Err(ForcedTransactionsError::BlobTransaction(tx.hash()))
+    }
+    let gas = transactions().fold(0u64, |gas, tx| gas.saturating_add(tx.gas_limit()));
+    if gas > gas_limit {
+        return Err(ForcedTransactionsError::ExceedsGasLimit { gas, gas_limit })
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Signature, Transaction, TransactionSigned, TxEip4844, TxLegacy};
+
+    fn transaction(transaction: Transaction) -> ForcedBundle {
+        TransactionSigned::from_transaction_and_signature(transaction, Signature::default())
+            .with_signer(Address::ZERO)
+            .into()
+    }
+
+    #[test]
+    fn forced_transactions_fit_gas_limit() {
+        let transactions = vec![
+            transaction(Transaction::Legacy(TxLegacy { gas_limit: 21_000, ..Default::default() })),
+            transaction(Transaction::Legacy(TxLegacy { gas_limit: 50_000, ..Default::default() })),
+        ];
+        assert!(ensure_valid_forced_transactions(&transactions, 71_000).is_ok());
+        assert!(matches!(
+            ensure_valid_forced_transactions(&transactions, 70_000),
+            Err(ForcedTransactionsError::ExceedsGasLimit { gas: 71_000, gas_limit: 70_000 })
+        ));
+
+        let blob = transaction(Transaction::Eip4844(TxEip4844::default()));
+        assert!(matches!(
+            ensure_valid_forced_transactions(&[blob], 1_000_000),
+            Err(ForcedTransactionsError::BlobTransaction(_))
+        ));
+    }
+}
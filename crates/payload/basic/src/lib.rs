@@ -49,7 +49,16 @@ use tokio::{
 };
 use tracing::{debug, trace, warn};
 
+mod bundles;
+mod forced_transactions;
 mod metrics;
+mod pool_transactions;
+
+pub use bundles::{BundleQueue, DEFAULT_MAX_QUEUED_BUNDLES};
+pub use forced_transactions::{
+    ForcedBundle, ForcedTransactionsError, PayloadTransactionsArgs, PayloadTransactionsHook,
+};
+pub use pool_transactions::PoolTransactionsOptions;
 
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
 #[derive(Debug)]
@@ -72,6 +81,8 @@ pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
     builder: Builder,
     /// Stored `cached_reads` for new payload jobs.
     pre_cached: Option<PrecachedState>,
+    /// Provides the transactions that must be included in the payloads.
+    transactions_hook: Option<Arc<dyn PayloadTransactionsHook>>,
 }
 
 // === impl BasicPayloadJobGenerator ===
@@ -96,9 +107,17 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
             chain_spec,
             builder,
             pre_cached: None,
+            transactions_hook: None,
         }
     }
 
+    /// Sets the hook that provides the transactions that must be included in the payloads of new
+    /// jobs.
+    pub fn with_transactions_hook(mut self, hook: Arc<dyn PayloadTransactionsHook>) -> Self {
+        self.transactions_hook = Some(hook);
+        self
+    }
+
     /// Returns the maximum duration a job should be allowed to run.
     ///
     /// This adheres to the following specification:
@@ -169,13 +188,27 @@ where
             block.seal(attributes.parent())
         };
 
-        let config = PayloadConfig::new(
+        let mut config = PayloadConfig::new(
             Arc::new(parent_block),
             self.config.extradata.clone(),
             attributes,
             Arc::clone(&self.chain_spec),
         );
 
+        if let Some(hook) = &self.transactions_hook {
+            let gas_limit = config.initialized_block_env.gas_limit.try_into().unwrap_or(u64::MAX);
+            let bundles = hook.transactions(PayloadTransactionsArgs {
+                payload_id: config.payload_id(),
+                parent_block: &config.parent_block,
+                timestamp: config.attributes.timestamp(),
+                suggested_fee_recipient: config.attributes.suggested_fee_recipient(),
+                gas_limit,
+            })?;
+            forced_transactions::ensure_valid_forced_transactions(&bundles, gas_limit)
+                .map_err(PayloadBuilderError::other)?;
+            config.forced_bundles = bundles;
+        }
+
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
 
@@ -690,6 +723,11 @@ pub struct PayloadConfig<Attributes> {
     pub attributes: Attributes,
     /// The chain spec.
     pub chain_spec: Arc<ChainSpec>,
+    /// Bundles of transactions that must be included in the payload, before the transactions of
+    /// the pool.
+    ///
+    /// See [`PayloadTransactionsHook`].
+    pub forced_bundles: Vec<ForcedBundle>,
 }
 
 impl<Attributes> PayloadConfig<Attributes> {
@@ -721,6 +759,7 @@ where
             extra_data,
             attributes,
             chain_spec,
+            forced_bundles: Vec::new(),
         }
     }
 
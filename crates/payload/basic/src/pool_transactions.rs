@@ -0,0 +1,26 @@
+use reth_primitives::Address;
+use reth_transaction_pool::validate::AddressFilter;
+use std::{collections::HashSet, sync::Arc};
+
+/// Options for selecting the pool transactions that are included in the built payloads.
+#[derive(Debug, Clone, Default)]
+pub struct PoolTransactionsOptions {
+    /// The senders and recipients whose pool transactions are included, all if unset.
+    pub address_filter: Option<AddressFilter>,
+    /// The senders whose pool transactions are included first, regardless of their tip.
+    pub priority_senders: Option<Arc<HashSet<Address>>>,
+}
+
+impl PoolTransactionsOptions {
+    /// Only includes the pool transactions whose sender and recipient are allowed by the filter.
+    pub fn with_address_filter(mut self, address_filter: AddressFilter) -> Self {
+        self.address_filter = Some(address_filter);
+        self
+    }
+
+    /// Includes the pool transactions of the given senders first, regardless of their tip.
+    pub fn with_priority_senders(mut self, senders: impl IntoIterator<Item = Address>) -> Self {
+        self.priority_senders = Some(Arc::new(senders.into_iter().collect()));
+        self
+    }
+}
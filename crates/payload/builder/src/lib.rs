@@ -105,6 +105,7 @@ pub mod database;
 pub mod error;
 mod events;
 mod metrics;
+mod retention;
 mod service;
 mod traits;
 
@@ -114,6 +115,10 @@ pub mod noop;
 pub mod test_utils;
 
 pub use events::Events;
+pub use retention::{
+    PayloadEvictionPolicy, PayloadRetention, DEFAULT_RETAINED_PAYLOADS,
+    DEFAULT_RETAINED_PAYLOAD_TTL,
+};
 pub use reth_rpc_types::engine::PayloadId;
 pub use service::{PayloadBuilderHandle, PayloadBuilderService, PayloadStore};
 pub use traits::{KeepPayloadJobAlive, PayloadJob, PayloadJobGenerator};
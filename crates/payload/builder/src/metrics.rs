@@ -48,3 +48,15 @@ impl PayloadBuilderServiceMetrics {
         self.resolved_revenue.set(value)
     }
 }
+
+/// Metrics of the payloads that are retained after their jobs ended
+#[derive(Metrics, Clone)]
+#[metrics(scope = "payloads.retained")]
+pub(crate) struct PayloadRetentionMetrics {
+    /// Number of retained payloads
+    pub(crate) retained: Gauge,
+    /// Total number of payloads that were evicted because the capacity was reached
+    pub(crate) evicted: Counter,
+    /// Total number of payloads that were evicted because they expired
+    pub(crate) expired: Counter,
+}
@@ -0,0 +1,208 @@
+//! Retention of the payloads of jobs that are no longer active.
+
+use crate::metrics::PayloadRetentionMetrics;
+use reth_rpc_types::engine::PayloadId;
+use std::{
+    collections::VecDeque,
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// The default number of payloads that are retained after their jobs ended.
+pub const DEFAULT_RETAINED_PAYLOADS: usize = 16;
+
+/// The default duration for which a payload is retained after its job ended.
+pub const DEFAULT_RETAINED_PAYLOAD_TTL: Duration = Duration::from_secs(60);
+
+/// Determines which payload is evicted if the capacity of the retained payloads is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadEvictionPolicy {
+    /// Evicts the payload that was retained first.
+    #[default]
+    Oldest,
+    /// Evicts the payload that was requested least recently.
+    LeastRecentlyUsed,
+}
+
+impl fmt::Display for PayloadEvictionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Oldest => f.write_str("oldest"),
+            Self::LeastRecentlyUsed => f.write_str("lru"),
+        }
+    }
+}
+
+impl FromStr for PayloadEvictionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "oldest" => Ok(Self::Oldest),
+            "lru" => Ok(Self::LeastRecentlyUsed),
+            _ => Err(format!("invalid payload eviction policy: {s}, expected `oldest` or `lru`")),
+        }
+    }
+}
+
+/// Settings for retaining the best payload of a job after the job ended, either because it was
+/// resolved or because its deadline passed.
+///
+/// The retained payloads are returned if they are requested again, e.g. by a late or repeated
+/// `engine_getPayload` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadRetention {
+    /// The maximum number of retained payloads. Payloads are not retained if this is zero.
+    pub capacity: usize,
+    /// The duration for which a payload is retained.
+    pub ttl: Duration,
+    /// Determines which payload is evicted if the capacity is reached.
+    pub eviction_policy: PayloadEvictionPolicy,
+}
+
+impl PayloadRetention {
+    /// Sets the maximum number of retained payloads.
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the duration for which a payload is retained.
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the policy that determines which payload is evicted if the capacity is reached.
+    pub const fn with_eviction_policy(mut self, eviction_policy: PayloadEvictionPolicy) -> Self {
+        self.eviction_policy = eviction_policy;
+        self
+    }
+}
+
+impl Default for PayloadRetention {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_RETAINED_PAYLOADS,
+            ttl: DEFAULT_RETAINED_PAYLOAD_TTL,
+            eviction_policy: PayloadEvictionPolicy::default(),
+        }
+    }
+}
+
+/// A payload that is retained after its job ended.
+#[derive(Debug)]
+struct RetainedPayload<Payload, Attributes> {
+    id: PayloadId,
+    payload: Payload,
+    attributes: Attributes,
+    retained_at: Instant,
+}
+
+/// The payloads that are retained after their jobs ended, see [`PayloadRetention`].
+#[derive(Debug)]
+pub(crate) struct RetainedPayloads<Payload, Attributes> {
+    settings: PayloadRetention,
+    /// The retained payloads, the payload at the front is evicted first.
+    payloads: VecDeque<RetainedPayload<Payload, Attributes>>,
+    metrics: PayloadRetentionMetrics,
+}
+
+impl<Payload, Attributes> RetainedPayloads<Payload, Attributes> {
+    pub(crate) fn new(settings: PayloadRetention) -> Self {
+        Self { settings, payloads: VecDeque::new(), metrics: Default::default() }
+    }
+
+    /// Retains the payload of the job with the given id, replacing a previously retained payload
+    /// with the same id.
+    pub(crate) fn insert(&mut self, id: PayloadId, payload: Payload, attributes: Attributes) {
+        if self.settings.capacity == 0 {
+            return
+        }
+        self.evict_expired();
+
+        self.payloads.retain(|retained| retained.id != id);
+        self.payloads.push_back(RetainedPayload {
+            id,
+            payload,
+            attributes,
+            retained_at: Instant::now(),
+        });
+        while self.payloads.len() > self.settings.capacity {
+            self.payloads.pop_front();
+            self.metrics.evicted.increment(1);
+        }
+        self.metrics.retained.set(self.payloads.len() as f64);
+    }
+
+    /// Returns the retained payload and attributes of the job with the given id.
+    pub(crate) fn get(&mut self, id: PayloadId) -> Option<(&Payload, &Attributes)> {
+        self.evict_expired();
+
+        let mut idx = self.payloads.iter().position(|retained| retained.id == id)?;
+        if self.settings.eviction_policy == PayloadEvictionPolicy::LeastRecentlyUsed {
+            let retained = self.payloads.remove(idx)?;
+            self.payloads.push_back(retained);
+            idx = self.payloads.len() - 1;
+        }
+        self.payloads.get(idx).map(|retained| (&retained.payload, &retained.attributes))
+    }
+
+    /// Removes the payloads that were retained for longer than the TTL.
+    fn evict_expired(&mut self) {
+        let len = self.payloads.len();
+        let ttl = self.settings.ttl;
+        self.payloads.retain(|retained| retained.retained_at.elapsed() < ttl);
+
+        let expired = len - self.payloads.len();
+        if expired > 0 {
+            self.metrics.expired.increment(expired as u64);
+            self.metrics.retained.set(self.payloads.len() as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(byte: u8) -> PayloadId {
+        PayloadId::new([byte; 8])
+    }
+
+    #[test]
+    fn evicts_according_to_policy() {
+        let settings = PayloadRetention::default().with_capacity(2);
+
+        let mut oldest = RetainedPayloads::new(settings);
+        oldest.insert(id(1), 1, ());
+        oldest.insert(id(2), 2, ());
+        assert!(oldest.get(id(1)).is_some());
+        oldest.insert(id(3), 3, ());
+        assert!(oldest.get(id(1)).is_none());
+        assert_eq!(oldest.get(id(2)), Some((&2, &())));
+
+        let mut lru = RetainedPayloads::new(
+            settings.with_eviction_policy(PayloadEvictionPolicy::LeastRecentlyUsed),
+        );
+        lru.insert(id(1), 1, ());
+        lru.insert(id(2), 2, ());
+        assert!(lru.get(id(1)).is_some());
+        lru.insert(id(3), 3, ());
+        assert!(lru.get(id(2)).is_none());
+        assert_eq!(lru.get(id(1)), Some((&1, &())));
+    }
+
+    #[test]
+    fn evicts_expired_payloads() {
+        let mut retained =
+            RetainedPayloads::new(PayloadRetention::default().with_ttl(Duration::ZERO));
+        retained.insert(id(1), 1, ());
+        assert!(retained.get(id(1)).is_none());
+
+        let mut disabled = RetainedPayloads::new(PayloadRetention::default().with_capacity(0));
+        disabled.insert(id(1), 1, ());
+        assert!(disabled.get(id(1)).is_none());
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
     error::PayloadBuilderError,
     events::{Events, PayloadEvents},
     metrics::PayloadBuilderServiceMetrics,
+    retention::{PayloadRetention, RetainedPayloads},
     traits::PayloadJobGenerator,
     KeepPayloadJobAlive, PayloadJob,
 };
@@ -227,8 +228,22 @@ where
     chain_events: St,
     /// Payload events handler, used to broadcast and subscribe to payload events.
     payload_events: broadcast::Sender<Events<Engine>>,
+    /// The payloads of jobs that ended, which are returned if they're requested again.
+    retained_payloads: RetainedPayloads<Engine::BuiltPayload, Engine::PayloadBuilderAttributes>,
+    /// Copy of the sender half of the channel that receives the payloads of resolved jobs, which
+    /// are retained once their resolve future completed.
+    resolved_tx: mpsc::UnboundedSender<ResolvedPayload<Engine>>,
+    /// Receiver half of the channel of resolved payloads.
+    resolved_rx: mpsc::UnboundedReceiver<ResolvedPayload<Engine>>,
 }
 
+/// A payload of a terminated job, as returned by its resolve future, with its attributes.
+type ResolvedPayload<Engine> = (
+    PayloadId,
+    <Engine as PayloadTypes>::BuiltPayload,
+    <Engine as PayloadTypes>::PayloadBuilderAttributes,
+);
+
 const PAYLOAD_EVENTS_BUFFER_SIZE: usize = 20;
 
 // === impl PayloadBuilderService ===
@@ -249,6 +264,7 @@ where
     pub fn new(generator: Gen, chain_events: St) -> (Self, PayloadBuilderHandle<Engine>) {
         let (service_tx, command_rx) = mpsc::unbounded_channel();
         let (payload_events, _) = broadcast::channel(PAYLOAD_EVENTS_BUFFER_SIZE);
+        let (resolved_tx, resolved_rx) = mpsc::unbounded_channel();
 
         let service = Self {
             generator,
@@ -258,12 +274,21 @@ where
             metrics: Default::default(),
             chain_events,
             payload_events,
+            retained_payloads: RetainedPayloads::new(PayloadRetention::default()),
+            resolved_tx,
+            resolved_rx,
         };
 
         let handle = service.handle();
         (service, handle)
     }
 
+    /// Configures how the payloads of jobs that ended are retained.
+    pub fn with_retention(mut self, retention: PayloadRetention) -> Self {
+        self.retained_payloads = RetainedPayloads::new(retention);
+        self
+    }
+
     /// Returns a handle to the service.
     pub fn handle(&self) -> PayloadBuilderHandle<Engine> {
         PayloadBuilderHandle::new(self.service_tx.clone())
@@ -276,14 +301,19 @@ where
 
     /// Returns the best payload for the given identifier that has been built so far.
     fn best_payload(
-        &self,
+        &mut self,
         id: PayloadId,
     ) -> Option<Result<Engine::BuiltPayload, PayloadBuilderError>> {
         let res = self
             .payload_jobs
             .iter()
             .find(|(_, job_id)| *job_id == id)
-            .map(|(j, _)| j.best_payload().map(|p| p.into()));
+            .map(|(j, _)| j.best_payload().map(|p| p.into()))
+            .or_else(|| {
+                let (payload, _) = self.retained_payloads.get(id)?;
+                trace!(%id, "returning retained payload");
+                Some(Ok(payload.clone()))
+            });
         if let Some(Ok(ref best)) = res {
             self.metrics.set_best_revenue(best.block().number, f64::from(best.fees()));
         }
@@ -296,11 +326,22 @@ where
     fn resolve(&mut self, id: PayloadId) -> Option<PayloadFuture<Engine::BuiltPayload>> {
         trace!(%id, "resolving payload job");
 
-        let job = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id)?;
+        let Some(job) = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id) else {
+            let (payload, _) = self.retained_payloads.get(id)?;
+            trace!(%id, "resolved retained payload");
+            return Some(Box::pin(futures_util::future::ready(Ok(payload.clone()))))
+        };
         let (fut, keep_alive) = self.payload_jobs[job].0.resolve();
 
+        // the payload of a terminated job is retained once it's resolved, since the resolved
+        // payload can differ from the best payload of the job
+        let mut retain = None;
         if keep_alive == KeepPayloadJobAlive::No {
-            let (_, id) = self.payload_jobs.remove(job);
+            let (job, id) = self.payload_jobs.remove(job);
+            retain = job
+                .payload_attributes()
+                .ok()
+                .map(|attributes| (self.resolved_tx.clone(), attributes));
             trace!(%id, "terminated resolved job");
         }
 
@@ -310,14 +351,18 @@ where
         let payload_events = self.payload_events.clone();
 
         let fut = async move {
-            let res = fut.await;
+            let res: Result<Engine::BuiltPayload, _> = fut.await.map(Into::into);
             if let Ok(ref payload) = res {
-                payload_events.send(Events::BuiltPayload(payload.clone().into())).ok();
+                payload_events.send(Events::BuiltPayload(payload.clone())).ok();
 
                 resolved_metrics
                     .set_resolved_revenue(payload.block().number, f64::from(payload.fees()));
+
+                if let Some((resolved_tx, attributes)) = retain {
+                    let _ = resolved_tx.send((id, payload.clone(), attributes));
+                }
             }
-            res.map(|p| p.into())
+            res
         };
 
         Some(Box::pin(fut))
@@ -333,14 +378,18 @@ where
 {
     /// Returns the payload attributes for the given payload.
     fn payload_attributes(
-        &self,
+        &mut self,
         id: PayloadId,
     ) -> Option<Result<<Gen::Job as PayloadJob>::PayloadAttributes, PayloadBuilderError>> {
         let attributes = self
             .payload_jobs
             .iter()
             .find(|(_, job_id)| *job_id == id)
-            .map(|(j, _)| j.payload_attributes());
+            .map(|(j, _)| j.payload_attributes())
+            .or_else(|| {
+                let (_, attributes) = self.retained_payloads.get(id)?;
+                Some(Ok(attributes.clone()))
+            });
 
         if attributes.is_none() {
             trace!(%id, "no matching payload job found to get attributes for");
@@ -348,6 +397,14 @@ where
 
         attributes
     }
+
+    /// Retains the best payload of the job that ended without being resolved, so that it can still
+    /// be requested.
+    fn retain_payload(&mut self, job: &Gen::Job, id: PayloadId) {
+        if let (Ok(payload), Ok(attributes)) = (job.best_payload(), job.payload_attributes()) {
+            self.retained_payloads.insert(id, payload.into(), attributes);
+        }
+    }
 }
 
 impl<Gen, St, Engine> Future for PayloadBuilderService<Gen, St, Engine>
@@ -369,6 +426,12 @@ where
                 this.generator.on_new_state(new_head);
             }
 
+            // retain the payloads of terminated jobs that were resolved
+            while let Poll::Ready(Some((id, payload, attributes))) = this.resolved_rx.poll_recv(cx)
+            {
+                this.retained_payloads.insert(id, payload, attributes);
+            }
+
             // we poll all jobs first, so we always have the latest payload that we can report if
             // requests
             // we don't care about the order of the jobs, so we can just swap_remove them
@@ -378,11 +441,13 @@ where
                 // drain better payloads from the job
                 match job.poll_unpin(cx) {
                     Poll::Ready(Ok(_)) => {
+                        this.retain_payload(&job, id);
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                         trace!(%id, "payload job finished");
                     }
                     Poll::Ready(Err(err)) => {
                         warn!(%err, ?id, "Payload builder job failed; resolving payload");
+                        this.retain_payload(&job, id);
                         this.metrics.inc_failed_jobs();
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                     }
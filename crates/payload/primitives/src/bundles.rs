@@ -0,0 +1,103 @@
+use reth_primitives::{keccak256, BlockNumber, TransactionSignedEcRecovered, B256};
+use std::{collections::HashSet, fmt};
+
+/// The maximum number of transactions of a bundle.
+pub const MAX_BUNDLE_TRANSACTIONS: usize = 64;
+
+/// The maximum number of blocks past the next block that a bundle can target.
+///
+/// This keeps bundles from being queued for blocks that are far in the future, where they would
+/// occupy the pool until the head reaches them.
+pub const MAX_BUNDLE_BLOCKS_AHEAD: u64 = 16;
+
+/// A bundle of transactions that should be included, in order, at the top of a specific block.
+#[derive(Debug, Clone)]
+pub struct QueuedBundle {
+    /// The transactions of the bundle.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// The number of the block the bundle targets.
+    pub block_number: BlockNumber,
+    /// The minimum timestamp of a payload the bundle can be included in.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of a payload the bundle can be included in.
+    pub max_timestamp: Option<u64>,
+    /// The hashes of the transactions that are allowed to revert.
+    pub reverting_tx_hashes: HashSet<B256>,
+    /// Identifies the bundle, so that it's replaced by a later bundle with the same id.
+    pub replacement_uuid: Option<String>,
+}
+
+impl QueuedBundle {
+    /// Returns the hash of the bundle, which is the hash of the concatenated transaction hashes.
+    pub fn hash(&self) -> B256 {
+        let hashes = self.transactions.iter().flat_map(|tx| tx.hash().0).collect::<Vec<_>>();
+        keccak256(hashes)
+    }
+
+    /// Returns the sum of the gas limits of the transactions.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().fold(0u64, |gas, tx| gas.saturating_add(tx.gas_limit()))
+    }
+
+    /// Ensures that the bundle doesn't target a block more than [`MAX_BUNDLE_BLOCKS_AHEAD`] blocks
+    /// past the next block.
+    pub fn ensure_block_number(
+        block_number: BlockNumber,
+        next_block: BlockNumber,
+    ) -> Result<(), BundlePoolError> {
+        let max_block_number = next_block.saturating_add(MAX_BUNDLE_BLOCKS_AHEAD);
+        if block_number > max_block_number {
+            return Err(BundlePoolError::BlockNumberTooHigh { block_number, max_block_number })
+        }
+        Ok(())
+    }
+
+    /// Returns true if the bundle can be included in a payload with the given timestamp.
+    pub fn is_active_at(&self, timestamp: u64) -> bool {
+        self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+}
+
+/// Errors when inserting a bundle into a [`BundlePool`].
+#[derive(Debug, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle doesn't contain any transactions.
+    #[error("bundle has no transactions")]
+    EmptyBundle,
+    /// The bundle contains more than [`MAX_BUNDLE_TRANSACTIONS`] transactions.
+    #[error("bundle has {0} transactions, exceeding the maximum of {MAX_BUNDLE_TRANSACTIONS}")]
+    TooManyTransactions(usize),
+    /// A blob transaction can't be included, since its sidecar is not available to the payload.
+    #[error("bundle transaction {0} is a blob transaction")]
+    BlobTransaction(B256),
+    /// The bundle targets a block more than [`MAX_BUNDLE_BLOCKS_AHEAD`] blocks past the next
+    /// block.
+    #[error("bundle targets block {block_number}, exceeding the maximum of {max_block_number}")]
+    BlockNumberTooHigh {
+        /// The block number of the bundle.
+        block_number: BlockNumber,
+        /// The highest block number a bundle can target.
+        max_block_number: BlockNumber,
+    },
+    /// The pool holds the maximum number of bundles.
+    #[error("bundle pool is full, holding {0} bundles")]
+    PoolFull(usize),
+}
+
+/// A pool of the bundles that are included at the top of the payloads that are built locally.
+pub trait BundlePool: fmt::Debug + Send + Sync + 'static {
+    /// Inserts the bundle and returns its hash.
+    ///
+    /// A bundle with the same replacement uuid is replaced. The bundle is rejected if it targets a
+    /// block more than [`MAX_BUNDLE_BLOCKS_AHEAD`] blocks past `next_block`, the number of the
+    /// block that is built next.
+    fn insert(
+        &self,
+        bundle: QueuedBundle,
+        next_block: BlockNumber,
+    ) -> Result<B256, BundlePoolError>;
+
+    /// Removes the bundle with the given hash, returns true if it was in the pool.
+    fn remove(&self, hash: B256) -> bool;
+}
@@ -0,0 +1,196 @@
+//! Abstractions for payloads that are built outside of the node.
+//!
+//! An external builder is asked for a payload whenever the payload of the local builder is
+//! requested. Its payload is only returned if it matches the attributes of the payload job, is
+//! selected by a [`PayloadSelectionPolicy`], and is a valid block. Otherwise the local payload is
+//! returned.
+
+use crate::{BuiltPayload, PayloadBuilderAttributes};
+use reth_errors::ProviderError;
+use reth_primitives::SealedBlock;
+use reth_rpc_types::engine::PayloadError;
+use std::{fmt, time::Duration};
+
+/// The default time to wait for the payload of an external builder when the payload is resolved.
+///
+/// The local payload is returned if it takes longer.
+pub const DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Errors of payloads of an external builder.
+#[derive(Debug, thiserror::Error)]
+pub enum ExternalPayloadError {
+    /// The builder failed to return a payload.
+    #[error("external builder failed: {0}")]
+    Builder(Box<dyn std::error::Error + Send + Sync>),
+    /// The builder didn't return the payload in time.
+    #[error("timed out waiting for the external payload")]
+    Timeout,
+    /// The payload of the builder can't be converted into a block.
+    #[error(transparent)]
+    InvalidPayload(#[from] PayloadError),
+    /// The payload doesn't match the attributes of the payload job.
+    #[error("external payload doesn't match the attributes: {0}")]
+    AttributesMismatch(&'static str),
+    /// The block of the payload failed validation or execution.
+    #[error("invalid external payload: {0}")]
+    InvalidBlock(String),
+    /// The payload couldn't be validated.
+    #[error("failed to validate external payload: {0}")]
+    Validation(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl ExternalPayloadError {
+    /// Creates a new error of the builder.
+    pub fn builder(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Builder(err.into())
+    }
+
+    /// Creates a new error of a failed validation.
+    pub fn validation(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Validation(err.into())
+    }
+}
+
+impl From<ProviderError> for ExternalPayloadError {
+    fn from(err: ProviderError) -> Self {
+        Self::validation(err)
+    }
+}
+
+/// Ensures that the block of an external payload matches the attributes of the payload job.
+///
+/// This only checks the fields that every payload job has, chains can check additional fields of
+/// their attributes.
+pub fn ensure_matches_attributes<Attributes: PayloadBuilderAttributes>(
+    block: &SealedBlock,
+    attributes: &Attributes,
+) -> Result<(), ExternalPayloadError> {
+    if block.parent_hash != attributes.parent() {
+        return Err(ExternalPayloadError::AttributesMismatch("parent hash"))
+    }
+    if block.timestamp != attributes.timestamp() {
+        return Err(ExternalPayloadError::AttributesMismatch("timestamp"))
+    }
+    if block.mix_hash != attributes.prev_randao() {
+        return Err(ExternalPayloadError::AttributesMismatch("prev randao"))
+    }
+    if block.parent_beacon_block_root != attributes.parent_beacon_block_root() {
+        return Err(ExternalPayloadError::AttributesMismatch("parent beacon block root"))
+    }
+    let withdrawals = block.withdrawals.as_ref().map(|withdrawals| withdrawals.as_slice());
+    let expected = attributes.withdrawals().as_slice();
+    if withdrawals.unwrap_or_default() != expected {
+        return Err(ExternalPayloadError::AttributesMismatch("withdrawals"))
+    }
+    Ok(())
+}
+
+/// The source of a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadSource {
+    /// The payload of the local payload builder.
+    Local,
+    /// The payload of the external builder.
+    External,
+}
+
+/// Decides whether the payload of the local builder or of the external builder is returned.
+///
+/// This is only consulted if both payloads are available, and the external payload matches the
+/// payload attributes. If the external payload is selected, it's only returned if it's a valid
+/// block.
+pub trait PayloadSelectionPolicy<Payload>: fmt::Debug + Send + Sync + 'static {
+    /// Selects the payload that is returned.
+    fn select(&self, local: &Payload, external: &Payload) -> PayloadSource;
+}
+
+/// Always selects the payload of the external builder.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct PreferExternal;
+
+impl<Payload> PayloadSelectionPolicy<Payload> for PreferExternal {
+    fn select(&self, _: &Payload, _: &Payload) -> PayloadSource {
+        PayloadSource::External
+    }
+}
+
+/// Selects the payload that uses more gas, preferring the local payload if they are equal.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct MostGasUsed;
+
+impl<Payload: BuiltPayload> PayloadSelectionPolicy<Payload> for MostGasUsed {
+    fn select(&self, local: &Payload, external: &Payload) -> PayloadSource {
+        if external.block().gas_used > local.block().gas_used {
+            PayloadSource::External
+        } else {
+            PayloadSource::Local
+        }
+    }
+}
+
+/// Selects the payload that collects more fees, preferring the local payload if they are equal.
+#[derive(Debug, Default, Clone, Copy)]
+#[non_exhaustive]
+pub struct MostValuable;
+
+impl<Payload: BuiltPayload> PayloadSelectionPolicy<Payload> for MostValuable {
+    fn select(&self, local: &Payload, external: &Payload) -> PayloadSource {
+        if external.fees() > local.fees() {
+            PayloadSource::External
+        } else {
+            PayloadSource::Local
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, U256};
+
+    #[derive(Debug)]
+    struct TestPayload {
+        block: SealedBlock,
+        fees: U256,
+    }
+
+    impl BuiltPayload for TestPayload {
+        fn block(&self) -> &SealedBlock {
+            &self.block
+        }
+
+        fn fees(&self) -> U256 {
+            self.fees
+        }
+    }
+
+    fn payload(gas_used: u64, fees: u64) -> TestPayload {
+        let header = Header { gas_used, ..Default::default() };
+        TestPayload {
+            block: SealedBlock { header: header.seal_slow(), ..Default::default() },
+            fees: U256::from(fees),
+        }
+    }
+
+    #[test]
+    fn selection_policies() {
+        let (low, high) = (payload(1, 1), payload(2, 2));
+
+        assert_eq!(PreferExternal.select(&high, &low), PayloadSource::External);
+
+        assert_eq!(MostGasUsed.select(&low, &high), PayloadSource::External);
+        assert_eq!(MostGasUsed.select(&high, &low), PayloadSource::Local);
+        assert_eq!(MostGasUsed.select(&low, &low), PayloadSource::Local);
+
+        assert_eq!(MostValuable.select(&low, &high), PayloadSource::External);
+        assert_eq!(MostValuable.select(&high, &low), PayloadSource::Local);
+        assert_eq!(MostValuable.select(&low, &low), PayloadSource::Local);
+
+        // the policies can disagree
+        let (more_gas, more_fees) = (payload(2, 1), payload(1, 2));
+        assert_eq!(MostGasUsed.select(&more_fees, &more_gas), PayloadSource::External);
+        assert_eq!(MostValuable.select(&more_fees, &more_gas), PayloadSource::Local);
+    }
+}
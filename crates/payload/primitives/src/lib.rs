@@ -12,6 +12,17 @@ mod error;
 
 pub use error::{EngineObjectValidationError, PayloadBuilderError, VersionSpecificValidationError};
 
+mod bundles;
+pub use bundles::{
+    BundlePool, BundlePoolError, QueuedBundle, MAX_BUNDLE_BLOCKS_AHEAD, MAX_BUNDLE_TRANSACTIONS,
+};
+
+mod external;
+pub use external::{
+    ensure_matches_attributes, ExternalPayloadError, MostGasUsed, MostValuable,
+    PayloadSelectionPolicy, PayloadSource, PreferExternal, DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT,
+};
+
 /// Contains traits to abstract over payload attributes types and default implementations of the
 /// [`PayloadAttributes`] trait for ethereum mainnet and optimism types.
 mod traits;
@@ -5,7 +5,7 @@ use reth_primitives::{
 };
 use reth_rpc_types::{
     engine::{OptimismPayloadAttributes, PayloadAttributes as EthPayloadAttributes, PayloadId},
-    Withdrawal,
+    PayloadBuildTimings, Withdrawal,
 };
 
 use crate::{
@@ -20,6 +20,12 @@ pub trait BuiltPayload: Send + Sync + std::fmt::Debug {
 
     /// Returns the fees collected for the built block
     fn fees(&self) -> U256;
+
+    /// Returns how long the payload builder spent in each phase of building the block, if it
+    /// recorded it.
+    fn timings(&self) -> Option<PayloadBuildTimings> {
+        None
+    }
 }
 
 /// This can be implemented by types that describe a currently running payload job.
@@ -3,7 +3,7 @@
 
 use super::error::TransactionConversionError;
 use crate::{
-    Address, BlobTransaction, BlobTransactionSidecar, Bytes, Signature, Transaction,
+    AccessList, Address, BlobTransaction, BlobTransactionSidecar, Bytes, Signature, Transaction,
     TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844, TxHash,
     TxLegacy, B256, EIP4844_TX_TYPE_ID,
 };
@@ -373,6 +373,16 @@ impl PooledTransactionsElement {
         }
     }
 
+    /// Returns the access list of the transaction, `None` for legacy transactions.
+    pub const fn access_list(&self) -> Option<&AccessList> {
+        match self {
+            Self::Legacy { .. } => None,
+            Self::Eip2930 { transaction, .. } => Some(&transaction.access_list),
+            Self::Eip1559 { transaction, .. } => Some(&transaction.access_list),
+            Self::BlobTransaction(tx) => Some(&tx.transaction.access_list),
+        }
+    }
+
     /// Returns the blob gas used for all blobs of the EIP-4844 transaction if it is an EIP-4844
     /// transaction.
     ///
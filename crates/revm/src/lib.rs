@@ -20,6 +20,8 @@ pub mod batch;
 /// State changes that are not related to transactions.
 pub mod state_change;
 
+pub mod witness;
+
 /// Common test helpers
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
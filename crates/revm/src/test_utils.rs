@@ -92,4 +92,8 @@ impl StateProvider for StateProviderTest {
     fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         unimplemented!("proof generation is not supported")
     }
+
+    fn witness_nodes(&self, _address: Address, _keys: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        unimplemented!("witness generation is not supported")
+    }
 }
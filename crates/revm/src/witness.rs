@@ -0,0 +1,136 @@
+//! Recording of the state accessed by the execution of a block, the base of its execution witness.
+
+use core::cell::RefCell;
+use reth_primitives::{Address, Bytes, B256, KECCAK_EMPTY, U256};
+use reth_storage_api::StateProvider;
+use reth_storage_errors::provider::ProviderResult;
+use revm::{
+    primitives::{AccountInfo, Bytecode},
+    DatabaseRef,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The state accessed through a [`WitnessRecorder`].
+#[derive(Debug, Default)]
+pub struct AccessedState {
+    /// The accessed accounts and their accessed storage slots.
+    pub accounts: BTreeMap<Address, BTreeSet<B256>>,
+    /// The accessed bytecodes, keyed by their hash.
+    pub codes: BTreeMap<B256, Bytes>,
+    /// The number of the oldest block whose hash was accessed.
+    pub oldest_block_hash: Option<u64>,
+}
+
+impl AccessedState {
+    /// Returns the deduplicated trie nodes of the accessed accounts and storage slots in the given
+    /// state, which include the siblings needed to recompute the state root after the execution.
+    pub fn witness_nodes(&self, state: &impl StateProvider) -> ProviderResult<BTreeSet<Bytes>> {
+        let mut witness = BTreeSet::new();
+        for (address, slots) in &self.accounts {
+            let slots = slots.iter().copied().collect::<Vec<_>>();
+            witness.extend(state.witness_nodes(*address, &slots)?);
+        }
+        Ok(witness)
+    }
+}
+
+/// A [`DatabaseRef`] that records the accounts, storage slots, bytecodes and block hashes read
+/// from the wrapped database.
+#[derive(Debug)]
+pub struct WitnessRecorder<DB> {
+    db: DB,
+    accessed: RefCell<AccessedState>,
+}
+
+impl<DB> WitnessRecorder<DB> {
+    /// Wraps the given database.
+    pub fn new(db: DB) -> Self {
+        Self { db, accessed: Default::default() }
+    }
+
+    /// Consumes the recorder and returns the state read from the database.
+    pub fn into_accessed(self) -> AccessedState {
+        self.accessed.into_inner()
+    }
+}
+
+impl<DB: DatabaseRef> DatabaseRef for WitnessRecorder<DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic_ref(address)?;
+        let mut accessed = self.accessed.borrow_mut();
+        accessed.accounts.entry(address).or_default();
+        if let Some(AccountInfo { code_hash, code: Some(code), .. }) = &info {
+            if *code_hash != KECCAK_EMPTY {
+                accessed.codes.insert(*code_hash, code.original_bytes());
+            }
+        }
+        Ok(info)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash_ref(code_hash)?;
+        if code_hash != KECCAK_EMPTY {
+            self.accessed.borrow_mut().codes.insert(code_hash, code.original_bytes());
+        }
+        Ok(code)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage_ref(address, index)?;
+        self.accessed.borrow_mut().accounts.entry(address).or_default().insert(B256::from(index));
+        Ok(value)
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash_ref(number)?;
+        let number = number.saturating_to::<u64>();
+        let mut accessed = self.accessed.borrow_mut();
+        accessed.oldest_block_hash =
+            Some(accessed.oldest_block_hash.map_or(number, |oldest| oldest.min(number)));
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        Database,
+    };
+
+    #[test]
+    fn records_accessed_state() {
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        // storage is only read from the database for existing accounts
+        let mut state = CacheDB::new(EmptyDB::default());
+        state.insert_account_info(bob, AccountInfo::default());
+        let mut db = CacheDB::new(WitnessRecorder::new(state));
+
+        db.basic(alice).unwrap();
+        db.storage(bob, U256::from(7)).unwrap();
+        db.storage(bob, U256::from(7)).unwrap();
+        db.block_hash(U256::from(10)).unwrap();
+        db.block_hash(U256::from(5)).unwrap();
+
+        let accessed = db.db.into_accessed();
+        assert_eq!(
+            accessed.accounts,
+            BTreeMap::from([
+                (alice, BTreeSet::new()),
+                (bob, BTreeSet::from([B256::with_last_byte(7)])),
+            ])
+        );
+        assert!(accessed.codes.is_empty());
+        assert_eq!(accessed.oldest_block_hash, Some(5));
+    }
+}
@@ -1,6 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
-use reth_rpc_types::{admin::NodeInfo, PeerInfo};
+use reth_rpc_types::{
+    admin::NodeInfo, NatInfo, PeerBandwidth, PeerInfo, TxpoolImportOutcome, TxpoolSnapshot,
+};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +36,11 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns the number of bytes sent to and received from each connected peer, broken down by
+    /// shared capability.
+    #[method(name = "peerBandwidth")]
+    async fn peer_bandwidth(&self) -> RpcResult<Vec<PeerBandwidth>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -45,4 +52,39 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the status of the external address resolution.
+    ///
+    /// Returns `None` if external address resolution is disabled.
+    #[method(name = "natStatus")]
+    fn nat_status(&self) -> RpcResult<Option<NatInfo>>;
+
+    /// Triggers a new resolution of the external address, without waiting for the next scheduled
+    /// attempt.
+    ///
+    /// Returns false if external address resolution is disabled.
+    #[method(name = "refreshNat")]
+    fn refresh_nat(&self) -> RpcResult<bool>;
+
+    /// Returns a page of a snapshot of the transaction pool: the transactions with the subpool
+    /// they're in and the values the pool ranks them by.
+    ///
+    /// The page starts at the given offset into the transactions ordered by sender and nonce and
+    /// holds at most `limit` transactions, capped at
+    /// [`MAX_TXPOOL_SNAPSHOT_PAGE_SIZE`](reth_rpc_types::MAX_TXPOOL_SNAPSHOT_PAGE_SIZE).
+    #[method(name = "exportTxpool")]
+    async fn export_txpool(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> RpcResult<TxpoolSnapshot>;
+
+    /// Adds the transactions of a snapshot to the transaction pool, with their original origin.
+    ///
+    /// Transactions are validated against the state of this node, so they may end up in a
+    /// different subpool than recorded in the snapshot. Snapshots with more than
+    /// [`MAX_TXPOOL_SNAPSHOT_PAGE_SIZE`](reth_rpc_types::MAX_TXPOOL_SNAPSHOT_PAGE_SIZE)
+    /// transactions are rejected.
+    #[method(name = "importTxpool")]
+    async fn import_txpool(&self, snapshot: TxpoolSnapshot) -> RpcResult<TxpoolImportOutcome>;
 }
@@ -1,3 +1,4 @@
+use alloy_json_abi::JsonAbi;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256};
 use reth_rpc_types::{
@@ -5,7 +6,7 @@ use reth_rpc_types::{
         BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
         TraceResult,
     },
-    Bundle, RichBlock, StateContext, TransactionRequest,
+    Bundle, ExecutionWitness, ResultChunk, RichBlock, StateContext, TransactionRequest,
 };
 
 /// Debug rpc interface.
@@ -81,6 +82,22 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>>;
 
+    /// Streaming variant of `debug_traceBlockByHash` and `debug_traceBlockByNumber`, for blocks
+    /// whose traces are too large to be sent as a single response.
+    ///
+    /// The trace of each transaction is sent in its own [ResultChunk] as soon as it's available,
+    /// followed by a chunk that marks the end of the traces. Only available over WS and IPC.
+    #[subscription(
+        name = "subscribeTraceBlock" => "traceBlockChunk",
+        unsubscribe = "unsubscribeTraceBlock",
+        item = ResultChunk<TraceResult>
+    )]
+    async fn debug_subscribe_trace_block(
+        &self,
+        block: BlockId,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceTransaction` debugging method will attempt to run the transaction in the
     /// exact same manner as it was executed on the network. It will replay any transaction that
     /// may have been executed prior to this one before it will finally attempt to execute the
@@ -132,6 +149,20 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<Vec<Vec<GethTrace>>>;
 
+    /// Re-executes a block on top of the state of its parent block and returns the execution
+    /// witness: the trie nodes, bytecodes, key preimages and ancestor headers accessed by the
+    /// execution, which are enough to execute the block statelessly.
+    #[method(name = "executionWitness")]
+    async fn debug_execution_witness(&self, block_id: BlockId) -> RpcResult<ExecutionWitness>;
+
+    /// Registers the custom error definitions of the JSON ABI, which are used to decode the
+    /// output of reverted `eth_call` and `eth_estimateGas` requests.
+    ///
+    /// Returns the number of definitions that weren't registered yet. Fails without registering
+    /// any definition if the registry would exceed its size limit.
+    #[method(name = "addRevertErrors")]
+    async fn debug_add_revert_errors(&self, abi: JsonAbi) -> RpcResult<usize>;
+
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location, the stack of the goroutine executing the log statement will
     /// be printed to stderr.
@@ -13,7 +13,8 @@ use reth_rpc_types::{
         PayloadStatus, TransitionConfiguration,
     },
     state::StateOverride,
-    BlockOverrides, Filter, Log, RichBlock, SyncStatus, TransactionRequest,
+    BlobAndProofV1, BlockOverrides, Filter, Log, PayloadBuildTimings, RichBlock, SyncStatus,
+    TransactionRequest,
 };
 
 // NOTE: We can't use associated types in the `EngineApi` trait because of jsonrpsee, so we use a
@@ -137,6 +138,19 @@ pub trait EngineApi<Engine: EngineTypes> {
     #[method(name = "getPayloadV4")]
     async fn get_payload_v4(&self, payload_id: PayloadId) -> RpcResult<Engine::ExecutionPayloadV4>;
 
+    /// Returns how long the payload builder spent in each phase of building the payload with the
+    /// given id, or `null` if the timings are unknown.
+    ///
+    /// The timings are only known for recent payloads that were returned by `engine_getPayload`,
+    /// if the payload builder recorded them and retaining them is enabled.
+    ///
+    /// Note: This is a reth-specific method.
+    #[method(name = "getPayloadBuildTimingsV1")]
+    async fn get_payload_build_timings_v1(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildTimings>>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/shanghai.md#engine_getpayloadbodiesbyhashv1>
     #[method(name = "getPayloadBodiesByHashV1")]
     async fn get_payload_bodies_by_hash_v1(
@@ -192,6 +206,18 @@ pub trait EngineApi<Engine: EngineTypes> {
         client_version: ClientVersionV1,
     ) -> RpcResult<Vec<ClientVersionV1>>;
 
+    /// Fetches blobs from the blob pool of the execution layer.
+    ///
+    /// Returns the blobs with their proofs in the order of the given versioned hashes, and `null`
+    /// for each blob that is not available.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    #[method(name = "getBlobsV1")]
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
     #[method(name = "exchangeCapabilities")]
     async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>>;
@@ -0,0 +1,13 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::EngineCapabilitiesReport;
+
+/// Inspection of the engine API capabilities that the consensus layer uses, served by the auth
+/// server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait EngineCapabilitiesApi {
+    /// Returns the engine API methods that are advertised to the consensus layer, the methods
+    /// that it supports and the number of calls of each method that it made.
+    #[method(name = "engineCapabilities")]
+    fn engine_capabilities(&self) -> RpcResult<EngineCapabilitiesReport>;
+}
@@ -0,0 +1,21 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::B256;
+use reth_rpc_types::{ExecutionPayload, PayloadDryRunResult};
+
+/// Validation of payloads without importing them, served by the auth server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "engine"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "engine"))]
+pub trait EngineDryRunApi {
+    /// Validates and executes the payload on top of its parent, without persisting it or changing
+    /// the canonical chain, and returns the outcome of the execution.
+    ///
+    /// The versioned hashes and the parent beacon block root are the additional parameters of
+    /// `engine_newPayloadV3`, and must be provided for post-cancun payloads.
+    #[method(name = "dryRunPayload")]
+    async fn dry_run_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+    ) -> RpcResult<PayloadDryRunResult>;
+}
@@ -0,0 +1,15 @@
+use jsonrpsee::proc_macros::rpc;
+
+/// Subscription to the events of the consensus engine, served by the auth server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "engine"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "engine"))]
+pub trait EngineEventsApi {
+    /// Creates a subscription that emits forkchoice updates, canonicalization and invalid block
+    /// events of the consensus engine.
+    #[subscription(
+        name = "subscribeEvents" => "events",
+        unsubscribe = "unsubscribeEvents",
+        item = reth_rpc_types::EngineEvent
+    )]
+    async fn subscribe_events(&self) -> jsonrpsee::core::SubscriptionResult;
+}
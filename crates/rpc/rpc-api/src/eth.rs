@@ -2,10 +2,10 @@ use alloy_dyn_abi::TypedData;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64};
 use reth_rpc_types::{
-    serde_helpers::JsonStorageKey, state::StateOverride, AccessListWithGasUsed,
+    serde_helpers::JsonStorageKey, state::StateOverride, AccessListWithGasUsed, AccountSummary,
     AnyTransactionReceipt, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse,
-    FeeHistory, Header, Index, RichBlock, StateContext, SyncStatus, Transaction,
-    TransactionRequest, Work,
+    FeeHistory, Header, Index, RichBlock, SimulatePayload, SimulatedBlock, StateContext,
+    SyncStatus, Transaction, TransactionRequest, Work,
 };
 
 /// Eth rpc interface: <https://ethereum.github.io/execution-apis/api-documentation/>
@@ -144,6 +144,24 @@ pub trait EthApi {
     #[method(name = "getBalance")]
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> RpcResult<U256>;
 
+    /// Returns the balance, nonce, code hash and code size of the account at the given block
+    /// number.
+    #[method(name = "getAccountInfo")]
+    async fn account_info(
+        &self,
+        address: Address,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<AccountSummary>;
+
+    /// Returns the balance, nonce, code hash and code size of each of the accounts at the given
+    /// block number, in the order of the addresses.
+    #[method(name = "getAccountInfos")]
+    async fn account_infos(
+        &self,
+        addresses: Vec<Address>,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<AccountSummary>>;
+
     /// Returns the value from a storage position at a given address
     #[method(name = "getStorageAt")]
     async fn storage_at(
@@ -193,6 +211,17 @@ pub trait EthApi {
         state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<EthCallResponse>>;
 
+    /// Simulates a sequence of blocks on top of the given block, each with its own block and state
+    /// overrides and calls, as specified by the `eth_simulateV1` spec.
+    ///
+    /// Returns the simulated blocks, with the results of their calls.
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     /// Generates an access list for a transaction.
     ///
     /// This method creates an [EIP2930](https://eips.ethereum.org/EIPS/eip-2930) type accessList based on a given Transaction.
@@ -285,6 +314,18 @@ pub trait EthApi {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
 
+    /// Sends signed transaction and waits until it is included in a canonical block, returning
+    /// its receipt.
+    ///
+    /// The optional timeout is in milliseconds and capped at the maximum configured by the node.
+    /// This method is only available if enabled by the node.
+    #[method(name = "sendRawTransactionSync")]
+    async fn send_raw_transaction_sync(
+        &self,
+        bytes: Bytes,
+        timeout: Option<u64>,
+    ) -> RpcResult<AnyTransactionReceipt>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "sign")]
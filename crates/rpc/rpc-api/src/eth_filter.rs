@@ -1,5 +1,7 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_rpc_types::{Filter, FilterChanges, FilterId, Log, PendingTransactionFilterKind};
+use reth_rpc_types::{
+    Filter, FilterChanges, FilterId, Log, PendingTransactionFilterKind, ResultChunk,
+};
 /// Rpc Interface for poll-based ethereum filter API.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
@@ -34,4 +36,16 @@ pub trait EthFilterApi {
     /// Returns logs matching given filter object.
     #[method(name = "getLogs")]
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+    /// Streaming variant of `eth_getLogs`, for ranges whose logs are too large to be sent as a
+    /// single response.
+    ///
+    /// The logs are sent in [ResultChunk]s of the logs of consecutive blocks as they are found,
+    /// followed by a chunk that marks the end of the logs. Only available over WS and IPC.
+    #[subscription(
+        name = "subscribeGetLogs" => "getLogsChunk",
+        unsubscribe = "unsubscribeGetLogs",
+        item = ResultChunk<Log>
+    )]
+    async fn subscribe_logs(&self, filter: Filter) -> jsonrpsee::core::SubscriptionResult;
 }
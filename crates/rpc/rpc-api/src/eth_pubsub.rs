@@ -1,5 +1,5 @@
 use jsonrpsee::proc_macros::rpc;
-use reth_rpc_types::pubsub::{Params, SubscriptionKind};
+use reth_rpc_types::{pubsub::SubscriptionKind, SubscriptionParams};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
@@ -13,6 +13,6 @@ pub trait EthPubSubApi {
     async fn subscribe(
         &self,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -19,6 +19,9 @@ mod anvil;
 mod bundle;
 mod debug;
 mod engine;
+mod engine_capabilities;
+mod engine_dry_run;
+mod engine_events;
 mod eth;
 mod eth_filter;
 mod eth_pubsub;
@@ -45,13 +48,16 @@ pub mod servers {
         bundle::{EthBundleApiServer, EthCallBundleApiServer},
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
+        engine_capabilities::EngineCapabilitiesApiServer,
+        engine_dry_run::EngineDryRunApiServer,
+        engine_events::EngineEventsApiServer,
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethBlobsApiServer, RethSimulateApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
@@ -73,6 +79,9 @@ pub mod clients {
         bundle::{EthBundleApiClient, EthCallBundleApiClient},
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
+        engine_capabilities::EngineCapabilitiesApiClient,
+        engine_dry_run::EngineDryRunApiClient,
+        engine_events::EngineEventsApiClient,
         eth::EthApiClient,
         eth_filter::EthFilterApiClient,
         ganache::GanacheApiClient,
@@ -80,6 +89,7 @@ pub mod clients {
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
+        reth::{RethApiClient, RethBlobsApiClient, RethSimulateApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
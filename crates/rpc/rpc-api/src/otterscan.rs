@@ -1,11 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256};
-use reth_rpc_types::{
-    trace::otterscan::{
-        BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-        TransactionsWithReceipts,
-    },
-    Transaction,
+use reth_rpc_types::trace::otterscan::{
+    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
+    TransactionsWithReceipts,
 };
 
 /// Otterscan rpc interface.
@@ -33,7 +30,7 @@ pub trait Otterscan {
     /// Extract all variations of calls, contract creation and self-destructs and returns a call
     /// tree.
     #[method(name = "traceTransaction")]
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<TraceEntry>;
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>>;
 
     /// Tailor-made and expanded version of eth_getBlockByNumber for block details page in
     /// Otterscan.
@@ -57,6 +54,9 @@ pub trait Otterscan {
     ) -> RpcResult<OtsBlockTransactions>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Only the top-level calls of transactions are matched, calls to the address from within
+    /// another contract are not found.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
@@ -66,6 +66,9 @@ pub trait Otterscan {
     ) -> RpcResult<TransactionsWithReceipts>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Only the top-level calls of transactions are matched, calls to the address from within
+    /// another contract are not found.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
@@ -80,7 +83,7 @@ pub trait Otterscan {
         &self,
         sender: Address,
         nonce: u64,
-    ) -> RpcResult<Option<Transaction>>;
+    ) -> RpcResult<Option<TxHash>>;
 
     /// Gets the transaction hash and the address who created a contract.
     #[method(name = "getContractCreator")]
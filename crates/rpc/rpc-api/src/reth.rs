@@ -1,5 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, B256, U256};
+use reth_rpc_types::{
+    state::StateOverride, BlobSidecar, Bundle, SimulatedCallResult, StateContext,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -13,3 +16,45 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 }
+
+/// Reth API namespace for simulating calls.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethSimulateApi {
+    /// Executes the calls of the bundle in order on top of the state context, with the state
+    /// changes of each call visible to the following calls, and optional state and block
+    /// overrides.
+    ///
+    /// Takes the same parameters as `eth_callMany`, but returns the gas used, the logs and the
+    /// return data of every call.
+    #[method(name = "simulateCalls")]
+    async fn simulate_calls(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<SimulatedCallResult>>;
+}
+
+/// Reth API namespace for retrieving blobs from the blob store of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethBlobsApi {
+    /// Returns the blob sidecars of all blob transactions of the given block from the blob store
+    /// of the node, or `None` if the block doesn't exist.
+    ///
+    /// Fails if a sidecar is not in the blob store, e.g. because the blobs of finalized blocks are
+    /// pruned.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlobSidecar>>>;
+
+    /// Returns the blob sidecar with the given versioned hash from the blob store of the node, or
+    /// `None` if it's not available.
+    ///
+    /// Only blobs of pooled transactions and of blocks that are not finalized yet are available.
+    #[method(name = "getBlobSidecarByVersionedHash")]
+    async fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<BlobSidecar>>;
+}
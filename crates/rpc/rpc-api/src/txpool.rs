@@ -1,6 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::{
+    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus},
+    TxpoolQuery, TxpoolSenderReport,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -16,9 +19,12 @@ pub trait TxPoolApi {
     /// Returns a summary of all the transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
+    /// The optional query filters and pages the transactions, so that large pools can be inspected
+    /// in parts.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
     #[method(name = "inspect")]
-    async fn txpool_inspect(&self) -> RpcResult<TxpoolInspect>;
+    async fn txpool_inspect(&self, query: Option<TxpoolQuery>) -> RpcResult<TxpoolInspect>;
 
     /// Retrieves the transactions contained within the txpool, returning pending as well as queued
     /// transactions of this address, grouped by nonce.
@@ -30,7 +36,26 @@ pub trait TxPoolApi {
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
+    /// The optional query filters and pages the transactions, so that large pools can be inspected
+    /// in parts.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+    async fn txpool_content(&self, query: Option<TxpoolQuery>) -> RpcResult<TxpoolContent>;
+
+    /// Returns the nonces of the sender's pending and queued transactions, the nonce gaps, and the
+    /// transaction that holds back the others with the fees a replacement must pay.
+    ///
+    /// Returns `null` if the pool has no transactions of the sender.
+    #[method(name = "senderReport")]
+    async fn txpool_sender_report(&self, sender: Address) -> RpcResult<Option<TxpoolSenderReport>>;
+
+    /// Creates a subscription that emits an event whenever a transaction is added to, promoted
+    /// within, replaced in, dropped from or mined out of the pool.
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = reth_rpc_types::TxpoolEvent
+    )]
+    async fn txpool_subscribe(&self) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -0,0 +1,677 @@
+//! Authenticates the requests of the http and ws servers with API keys, and accounts the usage of
+//! each key.
+//!
+//! The keys are configured in a JSON file, together with their optional quotas and the compute
+//! units of the methods:
+//!
+//! ```json
+//! {
+//!   "keys": {
+//!     "3f9c0e1a": { "maxCalls": 1000000, "maxComputeUnits": 50000000 },
+//!     "b71d42c8": {}
+//!   },
+//!   "computeUnits": { "eth_call": 20, "eth_getLogs": 75, "debug_traceTransaction": 300 }
+//! }
+//! ```
+//!
+//! Requests must carry a configured key in the [`API_KEY_HEADER`] header, for ws connections in the
+//! header of the upgrade request. Every call counts towards the usage of its key: the number of
+//! calls, the compute units of the method (1 if they aren't configured) and the bytes of the
+//! response. Notifications of subscriptions aren't accounted.
+//!
+//! Once a quota is used up, the calls of the key fail with [`QUOTA_EXCEEDED_CODE`]. Calls and
+//! compute units are reserved when a call is admitted, so concurrent calls can't exceed these
+//! quotas, while the bytes of a response are only known once it's sent, so concurrent calls can
+//! exceed the egress quota by the size of their responses. The usage is never reset by the node,
+//! but it's persisted to the usage file, if configured, and restored from it on startup. Removing
+//! the file resets the usage of all keys.
+
+use http::StatusCode;
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, HttpBody, HttpRequest, HttpResponse},
+    types::{ErrorObject, ErrorObjectOwned, Request},
+    MethodResponse,
+};
+use reth_rpc_server_types::constants::DEFAULT_API_KEY_USAGE_PERSIST_INTERVAL;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    future::Future,
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// The header that carries the API key of a request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The error code of calls that are rejected because their API key used up one of its quotas.
+pub const QUOTA_EXCEEDED_CODE: i32 = -32005;
+
+/// The compute units of methods that aren't configured.
+const DEFAULT_COMPUTE_UNITS: u64 = 1;
+
+/// The quotas of an API key. Quotas that aren't set are unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ApiKeyQuota {
+    /// The maximum number of calls.
+    pub max_calls: Option<u64>,
+    /// The maximum number of compute units.
+    pub max_compute_units: Option<u64>,
+    /// The maximum number of bytes of the responses.
+    pub max_egress_bytes: Option<u64>,
+}
+
+impl ApiKeyQuota {
+    /// Returns an error if the given usage used up one of the quotas.
+    pub fn check(&self, usage: &ApiKeyUsage) -> Result<(), QuotaExceeded> {
+        if let Some(max) = self.max_calls.filter(|max| usage.calls >= *max) {
+            return Err(QuotaExceeded::Calls(max))
+        }
+        if let Some(max) = self.max_compute_units.filter(|max| usage.compute_units >= *max) {
+            return Err(QuotaExceeded::ComputeUnits(max))
+        }
+        if let Some(max) = self.max_egress_bytes.filter(|max| usage.egress_bytes >= *max) {
+            return Err(QuotaExceeded::EgressBytes(max))
+        }
+        Ok(())
+    }
+}
+
+/// The usage of an API key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyUsage {
+    /// The number of calls.
+    pub calls: u64,
+    /// The compute units of the calls.
+    pub compute_units: u64,
+    /// The number of bytes of the responses.
+    pub egress_bytes: u64,
+}
+
+/// The contents of the API keys file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeysFile {
+    /// The API keys and their quotas.
+    pub keys: HashMap<String, ApiKeyQuota>,
+    /// The compute units of a call of each method.
+    #[serde(default)]
+    pub compute_units: HashMap<String, u64>,
+}
+
+/// Error of calls whose API key used up one of its quotas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum QuotaExceeded {
+    /// The call quota is used up.
+    #[error("API key exceeded its quota of {0} calls")]
+    Calls(u64),
+    /// The compute unit quota is used up.
+    #[error("API key exceeded its quota of {0} compute units")]
+    ComputeUnits(u64),
+    /// The egress quota is used up.
+    #[error("API key exceeded its quota of {0} egress bytes")]
+    EgressBytes(u64),
+}
+
+impl QuotaExceeded {
+    /// Returns the name of the quota that is used up.
+    pub const fn quota(&self) -> &'static str {
+        match self {
+            Self::Calls(_) => "calls",
+            Self::ComputeUnits(_) => "computeUnits",
+            Self::EgressBytes(_) => "egressBytes",
+        }
+    }
+
+    /// Returns the limit of the quota that is used up.
+    pub const fn limit(&self) -> u64 {
+        match self {
+            Self::Calls(limit) | Self::ComputeUnits(limit) | Self::EgressBytes(limit) => *limit,
+        }
+    }
+}
+
+impl From<QuotaExceeded> for ErrorObjectOwned {
+    fn from(err: QuotaExceeded) -> Self {
+        ErrorObject::owned(
+            QUOTA_EXCEEDED_CODE,
+            err.to_string(),
+            Some(serde_json::json!({ "quota": err.quota(), "limit": err.limit() })),
+        )
+    }
+}
+
+/// Error thrown when the API keys or their usage can't be loaded.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to load API keys from {}: {error}", path.display())]
+pub struct ApiKeysError {
+    /// The file that couldn't be loaded.
+    pub path: PathBuf,
+    /// The error that occurred.
+    pub error: io::Error,
+}
+
+/// Configures the API keys of the http and ws servers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeysConfig {
+    /// The JSON file with the keys, see [`ApiKeysFile`].
+    pub keys_file: PathBuf,
+    /// The file the usage of the keys is persisted to and restored from, if any.
+    pub usage_file: Option<PathBuf>,
+    /// The interval at which the usage is persisted.
+    pub persist_interval: Duration,
+}
+
+impl ApiKeysConfig {
+    /// Creates a new config with the keys of the given file, whose usage isn't persisted.
+    pub const fn new(keys_file: PathBuf) -> Self {
+        Self {
+            keys_file,
+            usage_file: None,
+            persist_interval: DEFAULT_API_KEY_USAGE_PERSIST_INTERVAL,
+        }
+    }
+
+    /// Configures the file the usage of the keys is persisted to and restored from.
+    pub fn with_usage_file(mut self, usage_file: Option<PathBuf>) -> Self {
+        self.usage_file = usage_file;
+        self
+    }
+
+    /// Configures the interval at which the usage is persisted.
+    pub const fn with_persist_interval(mut self, interval: Duration) -> Self {
+        self.persist_interval = interval;
+        self
+    }
+}
+
+/// The usage counters of an API key.
+#[derive(Debug, Default)]
+struct UsageCounters(Mutex<ApiKeyUsage>);
+
+impl UsageCounters {
+    fn lock(&self) -> MutexGuard<'_, ApiKeyUsage> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn load(&self) -> ApiKeyUsage {
+        *self.lock()
+    }
+
+    fn store(&self, usage: ApiKeyUsage) {
+        *self.lock() = usage;
+    }
+
+    /// Accounts a call with the given compute units, unless the usage already used up one of the
+    /// quotas.
+    fn reserve(&self, quota: &ApiKeyQuota, compute_units: u64) -> Result<(), QuotaExceeded> {
+        let mut usage = self.lock();
+        quota.check(&usage)?;
+        usage.calls += 1;
+        usage.compute_units += compute_units;
+        Ok(())
+    }
+
+    /// Accounts the bytes of a response.
+    fn record_egress(&self, egress_bytes: u64) {
+        self.lock().egress_bytes += egress_bytes;
+    }
+}
+
+/// A configured API key.
+#[derive(Debug, Default)]
+struct ApiKeyEntry {
+    quota: ApiKeyQuota,
+    usage: UsageCounters,
+}
+
+/// The API key of a request, attached to its extensions by the [`ApiKeyAuthLayer`].
+#[derive(Debug, Clone)]
+struct AuthenticatedApiKey(Arc<ApiKeyEntry>);
+
+/// The configured API keys and their usage.
+///
+/// The keys are shared by all clones.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    inner: Arc<ApiKeysInner>,
+}
+
+impl ApiKeys {
+    /// Creates the API keys of the given keys file, without usage.
+    pub fn new(file: ApiKeysFile) -> Self {
+        let keys = file
+            .keys
+            .into_iter()
+            .map(|(key, quota)| (key, Arc::new(ApiKeyEntry { quota, ..Default::default() })))
+            .collect();
+        Self {
+            inner: Arc::new(ApiKeysInner {
+                keys,
+                compute_units: file.compute_units,
+                usage_file: None,
+            }),
+        }
+    }
+
+    /// Loads the API keys of the given config.
+    ///
+    /// If a usage file is configured, the usage is restored from it if it exists, and it's written
+    /// to the file on a dedicated thread at the configured interval, and once the keys are dropped.
+    pub fn load(config: &ApiKeysConfig) -> Result<Self, ApiKeysError> {
+        let file = read_json(&config.keys_file)
+            .map_err(|error| ApiKeysError { path: config.keys_file.clone(), error })?;
+        let mut keys = Self::new(file);
+
+        let Some(usage_file) = &config.usage_file else { return Ok(keys) };
+        if config.persist_interval.is_zero() {
+            return Err(ApiKeysError {
+                path: usage_file.clone(),
+                error: io::Error::new(io::ErrorKind::InvalidInput, "persist interval is zero"),
+            })
+        }
+        if usage_file.exists() {
+            let usage = read_json::<BTreeMap<String, ApiKeyUsage>>(usage_file)
+                .map_err(|error| ApiKeysError { path: usage_file.clone(), error })?;
+            keys.restore_usage(usage);
+        }
+        Arc::get_mut(&mut keys.inner).expect("not shared").usage_file = Some(usage_file.clone());
+
+        let weak = Arc::downgrade(&keys.inner);
+        let interval = config.persist_interval;
+        std::thread::Builder::new()
+            .name("rpc-api-key-usage".to_string())
+            .spawn(move || loop {
+                std::thread::sleep(interval);
+                let Some(inner) = weak.upgrade() else { break };
+                inner.persist_usage();
+            })
+            .map_err(|error| ApiKeysError { path: usage_file.clone(), error })?;
+
+        Ok(keys)
+    }
+
+    /// Returns the usage of the given key, if it's configured.
+    pub fn usage(&self, key: &str) -> Option<ApiKeyUsage> {
+        self.inner.keys.get(key).map(|entry| entry.usage.load())
+    }
+
+    /// Returns the usage of all keys.
+    pub fn usages(&self) -> BTreeMap<String, ApiKeyUsage> {
+        self.inner.usages()
+    }
+
+    /// Sets the usage of the configured keys. The usage of keys that aren't configured is ignored.
+    pub fn restore_usage(&self, usage: impl IntoIterator<Item = (String, ApiKeyUsage)>) {
+        for (key, usage) in usage {
+            if let Some(entry) = self.inner.keys.get(&key) {
+                entry.usage.store(usage);
+            }
+        }
+    }
+
+    /// Returns the compute units of a call of the given method.
+    pub fn compute_units(&self, method: &str) -> u64 {
+        self.inner.compute_units.get(method).copied().unwrap_or(DEFAULT_COMPUTE_UNITS)
+    }
+
+    /// Returns the http middleware layer that rejects requests without a configured key.
+    pub fn auth_layer(&self) -> ApiKeyAuthLayer {
+        ApiKeyAuthLayer { keys: self.clone() }
+    }
+
+    /// Returns the rpc middleware layer that accounts the calls of the keys and enforces their
+    /// quotas.
+    pub fn usage_layer(&self) -> ApiKeyUsageLayer {
+        ApiKeyUsageLayer { keys: self.clone() }
+    }
+
+    /// Returns the key carried by the given request headers, if it's configured.
+    ///
+    /// The key is compared with all configured keys in constant time, so that the time it takes
+    /// doesn't reveal how much of a configured key it matches.
+    fn authenticate(&self, headers: &http::HeaderMap) -> Option<AuthenticatedApiKey> {
+        let key = headers.get(API_KEY_HEADER)?.as_bytes();
+        self.inner
+            .keys
+            .iter()
+            .fold(None, |found, (configured, entry)| {
+                if constant_time_eq(configured.as_bytes(), key) {
+                    Some(entry)
+                } else {
+                    found
+                }
+            })
+            .cloned()
+            .map(AuthenticatedApiKey)
+    }
+}
+
+/// The shared state of [`ApiKeys`].
+#[derive(Debug, Default)]
+struct ApiKeysInner {
+    keys: HashMap<String, Arc<ApiKeyEntry>>,
+    compute_units: HashMap<String, u64>,
+    /// The file the usage is persisted to, if any
+    usage_file: Option<PathBuf>,
+}
+
+impl ApiKeysInner {
+    fn usages(&self) -> BTreeMap<String, ApiKeyUsage> {
+        self.keys.iter().map(|(key, entry)| (key.clone(), entry.usage.load())).collect()
+    }
+
+    /// Writes the usage to the usage file, if configured.
+    ///
+    /// The usage is written to a temporary file first, so that the usage file is never truncated.
+    fn persist_usage(&self) {
+        let Some(path) = &self.usage_file else { return };
+        let tmp = path.with_extension("tmp");
+        let res = serde_json::to_vec_pretty(&self.usages())
+            .map_err(io::Error::from)
+            .and_then(|usage| fs::write(&tmp, usage))
+            .and_then(|_| fs::rename(&tmp, path));
+        if let Err(err) = res {
+            warn!(target: "rpc", %err, ?path, "Failed to persist API key usage");
+        }
+    }
+}
+
+impl Drop for ApiKeysInner {
+    fn drop(&mut self) {
+        self.persist_usage();
+    }
+}
+
+/// Returns whether the byte strings are equal, in a time that only depends on their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Reads the JSON file at the given path.
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let contents = fs::read(path)?;
+    serde_json::from_slice(&contents).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Http middleware layer that rejects requests that don't carry a configured API key, and attaches
+/// the key to the extensions of the requests that do.
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuthLayer {
+    keys: ApiKeys,
+}
+
+impl<S> Layer<S> for ApiKeyAuthLayer {
+    type Service = ApiKeyAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyAuthService { keys: self.keys.clone(), inner }
+    }
+}
+
+/// Http middleware service of the [`ApiKeyAuthLayer`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyAuthService<S> {
+    keys: ApiKeys,
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for ApiKeyAuthService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = ApiKeyAuthFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest) -> Self::Future {
+        match self.keys.authenticate(req.headers()) {
+            Some(key) => {
+                req.extensions_mut().insert(key);
+                ApiKeyAuthFuture { kind: AuthKind::Authorized { fut: self.inner.call(req) } }
+            }
+            None => {
+                let response = http::Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(HttpBody::from("missing or unknown API key"))
+                    .expect("valid response");
+                ApiKeyAuthFuture { kind: AuthKind::Unauthorized { response: Some(response) } }
+            }
+        }
+    }
+}
+
+/// Response future of the [`ApiKeyAuthService`].
+#[pin_project::pin_project]
+#[derive(Debug)]
+pub struct ApiKeyAuthFuture<F> {
+    #[pin]
+    kind: AuthKind<F>,
+}
+
+#[pin_project::pin_project(project = AuthKindProj)]
+#[derive(Debug)]
+enum AuthKind<F> {
+    /// The request carries a configured key and is handled by the inner service
+    Authorized {
+        #[pin]
+        fut: F,
+    },
+    /// The request is rejected with the error response
+    Unauthorized { response: Option<HttpResponse> },
+}
+
+impl<F, E> Future for ApiKeyAuthFuture<F>
+where
+    F: Future<Output = Result<HttpResponse, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            AuthKindProj::Authorized { fut } => fut.poll(cx),
+            AuthKindProj::Unauthorized { response } => {
+                Poll::Ready(Ok(response.take().expect("polled after completion")))
+            }
+        }
+    }
+}
+
+/// Rpc middleware layer that accounts the calls of the API keys attached by the
+/// [`ApiKeyAuthLayer`] and rejects the calls of keys that used up a quota.
+#[derive(Debug, Clone)]
+pub struct ApiKeyUsageLayer {
+    keys: ApiKeys,
+}
+
+impl<S> Layer<S> for ApiKeyUsageLayer {
+    type Service = ApiKeyUsageService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiKeyUsageService { keys: self.keys.clone(), inner }
+    }
+}
+
+/// Rpc middleware service of the [`ApiKeyUsageLayer`].
+#[derive(Debug, Clone)]
+pub struct ApiKeyUsageService<S> {
+    keys: ApiKeys,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for ApiKeyUsageService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = ApiKeyUsageFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(AuthenticatedApiKey(key)) = req.extensions().get::<AuthenticatedApiKey>().cloned()
+        else {
+            return ApiKeyUsageFuture {
+                kind: UsageKind::Call { fut: self.inner.call(req), pending: None },
+            }
+        };
+
+        let compute_units = self.keys.compute_units(req.method_name());
+        if let Err(err) = key.usage.reserve(&key.quota, compute_units) {
+            let response = MethodResponse::error(req.id, err);
+            return ApiKeyUsageFuture { kind: UsageKind::Rejected { response: Some(response) } }
+        }
+
+        ApiKeyUsageFuture {
+            kind: UsageKind::Call { fut: self.inner.call(req), pending: Some(key) },
+        }
+    }
+}
+
+/// Response future of the [`ApiKeyUsageService`].
+#[pin_project::pin_project]
+#[derive(Debug)]
+pub struct ApiKeyUsageFuture<F> {
+    #[pin]
+    kind: UsageKind<F>,
+}
+
+#[pin_project::pin_project(project = UsageKindProj)]
+#[derive(Debug)]
+enum UsageKind<F> {
+    /// The call is handled by the inner service, and the bytes of its response are accounted to
+    /// its key once resolved, if it carries a key
+    Call {
+        #[pin]
+        fut: F,
+        pending: Option<Arc<ApiKeyEntry>>,
+    },
+    /// The call is rejected with the error response, because its key used up a quota
+    Rejected { response: Option<MethodResponse> },
+}
+
+impl<F: Future<Output = MethodResponse>> Future for ApiKeyUsageFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            UsageKindProj::Call { fut, pending } => {
+                let res = fut.poll(cx);
+                if let Poll::Ready(resp) = &res {
+                    if let Some(key) = pending.take() {
+                        key.usage.record_egress(resp.as_result().len() as u64);
+                    }
+                }
+                res
+            }
+            UsageKindProj::Rejected { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> ApiKeys {
+        ApiKeys::new(
+            serde_json::from_str(
+                r#"{
+                    "keys": {
+                        "limited": { "maxCalls": 2, "maxEgressBytes": 100 },
+                        "unlimited": {}
+                    },
+                    "computeUnits": { "eth_call": 20 }
+                }"#,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn parses_keys_file() {
+        let keys = keys();
+        assert_eq!(keys.usage("limited"), Some(ApiKeyUsage::default()));
+        assert_eq!(keys.usage("unknown"), None);
+        assert_eq!(keys.compute_units("eth_call"), 20);
+        assert_eq!(keys.compute_units("eth_blockNumber"), DEFAULT_COMPUTE_UNITS);
+        assert_eq!(keys.inner.keys["unlimited"].quota, ApiKeyQuota::default());
+    }
+
+    #[test]
+    fn authenticates_with_header() {
+        let keys = keys();
+        let mut headers = http::HeaderMap::new();
+        assert!(keys.authenticate(&headers).is_none());
+
+        headers.insert(API_KEY_HEADER, "unknown".parse().unwrap());
+        assert!(keys.authenticate(&headers).is_none());
+
+        headers.insert(API_KEY_HEADER, "limited".parse().unwrap());
+        assert!(keys.authenticate(&headers).is_some());
+    }
+
+    #[test]
+    fn enforces_quotas() {
+        let keys = keys();
+        let entry = &keys.inner.keys["limited"];
+        assert_eq!(entry.quota.check(&entry.usage.load()), Ok(()));
+
+        entry.usage.reserve(&entry.quota, 20).unwrap();
+        entry.usage.record_egress(40);
+        assert_eq!(entry.quota.check(&entry.usage.load()), Ok(()));
+
+        entry.usage.reserve(&entry.quota, 20).unwrap();
+        entry.usage.record_egress(60);
+        assert_eq!(entry.quota.check(&entry.usage.load()), Err(QuotaExceeded::Calls(2)));
+        assert_eq!(entry.usage.reserve(&entry.quota, 20), Err(QuotaExceeded::Calls(2)));
+        assert_eq!(
+            keys.usage("limited"),
+            Some(ApiKeyUsage { calls: 2, compute_units: 40, egress_bytes: 100 })
+        );
+
+        let quota = ApiKeyQuota { max_egress_bytes: Some(100), ..Default::default() };
+        assert_eq!(quota.check(&entry.usage.load()), Err(QuotaExceeded::EgressBytes(100)));
+
+        let err = ErrorObjectOwned::from(QuotaExceeded::EgressBytes(100));
+        assert_eq!(err.code(), QUOTA_EXCEEDED_CODE);
+    }
+
+    #[test]
+    fn persists_and_restores_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys_file = dir.path().join("keys.json");
+        let usage_file = dir.path().join("usage.json");
+        fs::write(&keys_file, r#"{ "keys": { "key": { "maxCalls": 10 } } }"#).unwrap();
+
+        let config = ApiKeysConfig::new(keys_file)
+            .with_usage_file(Some(usage_file.clone()))
+            .with_persist_interval(Duration::from_secs(3600));
+        let keys = ApiKeys::load(&config).unwrap();
+        let entry = &keys.inner.keys["key"];
+        entry.usage.reserve(&entry.quota, 3).unwrap();
+        entry.usage.record_egress(42);
+        drop(keys);
+
+        let keys = ApiKeys::load(&config).unwrap();
+        assert_eq!(
+            keys.usage("key"),
+            Some(ApiKeyUsage { calls: 1, compute_units: 3, egress_bytes: 42 })
+        );
+
+        let config = config.with_persist_interval(Duration::ZERO);
+        assert!(ApiKeys::load(&config).is_err());
+    }
+}
@@ -1,9 +1,13 @@
-use crate::error::{RpcError, ServerKind};
+use crate::{
+    engine_recorder::{EngineApiRecorder, EngineApiRecorderLayer},
+    error::{RpcError, ServerKind},
+    IpcRpcServiceBuilder,
+};
 use http::header::AUTHORIZATION;
 use jsonrpsee::{
     core::RegisterMethodError,
     http_client::{transport::HttpBackend, HeaderMap},
-    server::{AlreadyStoppedError, RpcModule},
+    server::{AlreadyStoppedError, RpcModule, RpcServiceBuilder},
     Methods,
 };
 use reth_engine_primitives::EngineTypes;
@@ -11,7 +15,7 @@ use reth_rpc::EthSubscriptionIdProvider;
 use reth_rpc_api::servers::*;
 use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthClientService, AuthLayer, JwtAuthValidator,
-    JwtSecret,
+    JwtSecret, TraceContextLayer,
 };
 use reth_rpc_server_types::constants;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -27,12 +31,16 @@ pub struct AuthServerConfig {
     pub(crate) socket_addr: SocketAddr,
     /// The secret for the auth layer of the server.
     pub(crate) secret: JwtSecret,
+    /// Secrets that are accepted in addition to `secret`.
+    pub(crate) additional_secrets: Vec<JwtSecret>,
     /// Configs for JSON-RPC Http.
     pub(crate) server_config: ServerBuilder<Identity, Identity>,
     /// Configs for IPC server
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
     pub(crate) ipc_endpoint: Option<String>,
+    /// Records the requests of the server
+    pub(crate) recorder: Option<EngineApiRecorder>,
 }
 
 // === impl AuthServerConfig ===
@@ -50,15 +58,28 @@ impl AuthServerConfig {
 
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
-
-        // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        let Self {
+            socket_addr,
+            secret,
+            additional_secrets,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            recorder,
+        } = self;
+
+        // Create auth middleware, and record the calls in the trace context of the caller.
+        let validator = JwtAuthValidator::new(secret).with_additional_secrets(additional_secrets);
+        let middleware = tower::ServiceBuilder::new()
+            .layer(AuthLayer::new(validator))
+            .layer(TraceContextLayer::new());
 
         // By default, both http and ws are enabled.
         let server = server_config
             .set_http_middleware(middleware)
+            .set_rpc_middleware(
+                RpcServiceBuilder::new().layer(EngineApiRecorderLayer::new(recorder.clone())),
+            )
             .build(socket_addr)
             .await
             .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
@@ -74,7 +95,11 @@ impl AuthServerConfig {
             let ipc_endpoint_str = ipc_endpoint
                 .clone()
                 .unwrap_or_else(|| constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string());
-            let ipc_server = ipc_server_config.build(ipc_endpoint_str);
+            let ipc_server = ipc_server_config
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new().layer(EngineApiRecorderLayer::new(recorder)),
+                )
+                .build(ipc_endpoint_str);
             let res = ipc_server
                 .start(module.inner)
                 .await
@@ -91,9 +116,11 @@ impl AuthServerConfig {
 pub struct AuthServerConfigBuilder {
     socket_addr: Option<SocketAddr>,
     secret: JwtSecret,
+    additional_secrets: Vec<JwtSecret>,
     server_config: Option<ServerBuilder<Identity, Identity>>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
+    recorder: Option<EngineApiRecorder>,
 }
 
 // === impl AuthServerConfigBuilder ===
@@ -104,9 +131,11 @@ impl AuthServerConfigBuilder {
         Self {
             socket_addr: None,
             secret,
+            additional_secrets: Vec::new(),
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
+            recorder: None,
         }
     }
 
@@ -128,6 +157,15 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Set secrets that are accepted in addition to the secret of the server.
+    ///
+    /// This allows the consensus layer client to switch to another secret, e.g. when rotating the
+    /// secret, without restarting the server at the same time.
+    pub fn additional_secrets(mut self, secrets: Vec<JwtSecret>) -> Self {
+        self.additional_secrets = secrets;
+        self
+    }
+
     /// Configures the JSON-RPC server
     ///
     /// Note: this always configures an [`EthSubscriptionIdProvider`]
@@ -151,6 +189,12 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Records every request of the server with the given recorder.
+    pub fn with_recorder(mut self, recorder: EngineApiRecorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Build the `AuthServerConfig`.
     pub fn build(self) -> AuthServerConfig {
         AuthServerConfig {
@@ -158,6 +202,7 @@ impl AuthServerConfigBuilder {
                 SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), constants::DEFAULT_AUTH_PORT)
             }),
             secret: self.secret,
+            additional_secrets: self.additional_secrets,
             server_config: self.server_config.unwrap_or_else(|| {
                 ServerBuilder::new()
                     // This needs to large enough to handle large eth_getLogs responses and maximum
@@ -182,6 +227,7 @@ impl AuthServerConfigBuilder {
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
             ipc_endpoint: self.ipc_endpoint,
+            recorder: self.recorder,
         }
     }
 }
@@ -1,13 +1,21 @@
 use crate::{
-    auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcModuleConfig,
-    RpcServerConfig, TransportRpcModuleConfig,
+    api_keys::ApiKeysConfig,
+    auth::AuthServerConfig,
+    engine_recorder::EngineApiRecorder,
+    error::{RpcError, ServerKind},
+    response_size::ResponseSizeLimits,
+    EthConfig, IpcServerBuilder, RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
-use jsonrpsee::server::ServerBuilder;
+use jsonrpsee::server::{PingConfig, ServerBuilder};
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc::eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig};
+use reth_rpc::eth::{
+    cache::{EthStateCacheConfig, RpcResponseCacheConfig},
+    gas_oracle::GasPriceOracleConfig,
+    CallCaps, CallCapsConfig,
+};
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
-use std::{net::SocketAddr, path::PathBuf};
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use tower::layer::util::Identity;
 use tracing::debug;
 
@@ -34,9 +42,19 @@ pub trait RethRpcServerConfig {
     /// Returns the max response size in bytes.
     fn rpc_max_response_size_bytes(&self) -> u32;
 
+    /// Returns the max response sizes in bytes, with the limits of the namespaces that override
+    /// the max response size.
+    fn response_size_limits(&self) -> ResponseSizeLimits;
+
     /// Extracts the gas price oracle config from the args.
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig;
 
+    /// Returns the duration after which idle WS connections are closed, or `None` if keepalive
+    /// pings are disabled.
+    fn ws_idle_timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Creates the [`TransportRpcModuleConfig`] from cli args.
     ///
     /// This sets all the api modules, and configures additional settings like gas price oracle
@@ -89,10 +107,32 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn eth_config(&self) -> EthConfig {
         EthConfig::default()
             .max_tracing_requests(self.rpc_max_tracing_requests)
+            .trace_block_parallelism(self.rpc_trace_block_parallelism)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .call_caps(CallCapsConfig {
+                call: CallCaps::default()
+                    .with_gas_limit(self.rpc_call_gas_cap)
+                    .with_max_call_depth(self.rpc_call_max_depth)
+                    .with_max_execution_time(self.rpc_call_timeout),
+                estimate_gas: CallCaps::default()
+                    .with_gas_limit(self.rpc_estimate_gas_cap)
+                    .with_max_call_depth(self.rpc_estimate_max_depth)
+                    .with_max_execution_time(self.rpc_estimate_timeout),
+            })
+            .raw_transaction_sync_timeout(self.rpc_send_raw_transaction_sync_timeout)
+            .eth_proof_window(self.rpc_eth_proof_window)
+            .access_list_max_iterations(self.rpc_access_list_max_iterations)
+            .revert_abi_files(self.rpc_revert_abi.clone())
+            .logs_reorg_depth(self.rpc_logs_reorg_depth)
+            .max_subscription_replay_blocks(self.rpc_max_subscription_replay_blocks)
+            .trace_index_blocks(self.rpc_trace_index)
             .state_cache(self.state_cache_config())
+            .response_cache(RpcResponseCacheConfig {
+                max_responses: self.rpc_state_cache.max_responses,
+                ttl: self.rpc_state_cache.response_ttl,
+            })
             .gpo_config(self.gas_price_oracle_config())
     }
 
@@ -113,10 +153,22 @@ impl RethRpcServerConfig for RpcServerArgs {
         self.rpc_max_response_size.get().saturating_mul(1024 * 1024)
     }
 
+    fn response_size_limits(&self) -> ResponseSizeLimits {
+        self.rpc_namespace_max_response_sizes
+            .iter()
+            .fold(ResponseSizeLimits::new(self.rpc_max_response_size_bytes()), |limits, size| {
+                limits.with_namespace(size.namespace.clone(), size.max_size_bytes())
+            })
+    }
+
     fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
         self.gas_price_oracle.gas_price_oracle_config()
     }
 
+    fn ws_idle_timeout(&self) -> Option<Duration> {
+        (!self.ws_ping_interval.is_zero()).then_some(self.ws_idle_timeout)
+    }
+
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
         let mut config = TransportRpcModuleConfig::default()
             .with_config(RpcModuleConfig::new(self.eth_config()));
@@ -145,11 +197,18 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn http_ws_server_builder(&self) -> ServerBuilder<Identity, Identity> {
-        ServerBuilder::new()
+        let builder = ServerBuilder::new()
             .max_connections(self.rpc_max_connections.get())
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
-            .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get());
+
+        match self.ws_idle_timeout() {
+            Some(idle_timeout) => builder.enable_ws_ping(
+                PingConfig::new().ping_interval(self.ws_ping_interval).inactive_limit(idle_timeout),
+            ),
+            None => builder,
+        }
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity> {
@@ -161,7 +220,19 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_slow_query_threshold(self.rpc_slow_query_threshold)
+            .with_api_keys(self.rpc_api_keys.clone().map(|keys_file| {
+                ApiKeysConfig::new(keys_file)
+                    .with_usage_file(self.rpc_api_keys_usage.clone())
+                    .with_persist_interval(self.rpc_api_keys_usage_interval)
+            }))
+            .with_response_size_limits(Some(self.response_size_limits()));
+
+        if let Some(idle_timeout) = self.ws_idle_timeout() {
+            config = config.with_ws_keepalive(self.ws_ping_interval, idle_timeout);
+        }
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -188,12 +259,32 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        let additional_secrets = self
+            .auth_additional_jwtsecrets
+            .iter()
+            .map(|fpath| {
+                debug!(
+                    target: "reth::cli",
+                    user_path=?fpath,
+                    "Reading additional JWT auth secret file"
+                );
+                JwtSecret::from_file(fpath)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut builder = AuthServerConfig::builder(jwt_secret)
+            .socket_addr(address)
+            .additional_secrets(additional_secrets);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
                 .with_ipc_config(self.ipc_server_builder());
         }
+        if let Some(path) = &self.auth_record {
+            let recorder = EngineApiRecorder::new(path)
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(address)))?;
+            builder = builder.with_recorder(recorder);
+        }
         Ok(builder.build())
     }
 
@@ -218,7 +309,10 @@ mod tests {
     use reth_node_core::args::RpcServerArgs;
     use reth_rpc::eth::RPC_DEFAULT_GAS_CAP;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+        time::Duration,
+    };
 
     use crate::config::RethRpcServerConfig;
 
@@ -244,6 +338,35 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_call_caps() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().call_caps, CallCapsConfig::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.call-gas-cap",
+            "1000000",
+            "--rpc.call-timeout",
+            "2s",
+            "--rpc.estimate-max-depth",
+            "16",
+        ])
+        .args;
+        let config = args.eth_config();
+        assert_eq!(
+            config.call_caps.call,
+            CallCaps::default()
+                .with_gas_limit(Some(1_000_000))
+                .with_max_execution_time(Some(std::time::Duration::from_secs(2)))
+        );
+        assert_eq!(config.call_caps.estimate_gas.max_call_depth, Some(16));
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--rpc.call-max-depth", "0"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_transport_rpc_module_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -361,4 +484,14 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_ws_idle_timeout() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.ws_idle_timeout(), Some(Duration::from_secs(60)));
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ws.ping-interval", "0s"]).args;
+        assert_eq!(args.ws_idle_timeout(), None);
+    }
 }
@@ -0,0 +1,319 @@
+//! Records the requests and responses of the auth server.
+//!
+//! Every call is appended to a file as a line of JSON, so that a session of the consensus layer
+//! can be replayed against another node, e.g. to reproduce a consensus bug. Once the file reaches
+//! its maximum size, it's moved to the same path with a `.1` suffix, replacing the previous one,
+//! and recording continues in a new file.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{Params, Request},
+    MethodResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::{
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::mpsc,
+    task::{Context, Poll},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tower::Layer;
+use tracing::warn;
+
+/// The number of calls that can wait for the writer thread, further calls are dropped.
+const RECORDER_CHANNEL_SIZE: usize = 1024;
+
+/// The default size of the recording at which it's rotated, 1 GiB.
+pub const DEFAULT_MAX_RECORDING_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// A recorded request of the auth server, with its response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEngineCall {
+    /// When the request was received, in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// How long the request took, in microseconds.
+    pub elapsed_micros: u64,
+    /// The method of the request.
+    pub method: String,
+    /// The params of the request.
+    pub params: Option<Box<RawValue>>,
+    /// The JSON-RPC response to the request.
+    pub response: Box<RawValue>,
+}
+
+impl RecordedEngineCall {
+    /// Reads the recorded calls from the given file, in the order they were recorded.
+    pub fn read_all(path: &Path) -> io::Result<impl Iterator<Item = io::Result<Self>>> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(reader.lines().filter(|line| !matches!(line, Ok(line) if line.is_empty())).map(|line| {
+            serde_json::from_str(&line?)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+        }))
+    }
+}
+
+/// Appends the calls of the auth server to a file, one JSON object per line.
+///
+/// The calls are written on a dedicated thread, so that recording doesn't delay the responses. If
+/// the thread falls behind, calls are dropped instead of queueing up without limit.
+#[derive(Debug, Clone)]
+pub struct EngineApiRecorder {
+    /// Sends the calls to the writer thread
+    calls: mpsc::SyncSender<RecordedEngineCall>,
+}
+
+impl EngineApiRecorder {
+    /// Creates a new [`EngineApiRecorder`] that appends to the file at the given path, rotated at
+    /// [`DEFAULT_MAX_RECORDING_SIZE`].
+    pub fn new(path: &Path) -> io::Result<Self> {
+        Self::with_max_size(path, DEFAULT_MAX_RECORDING_SIZE)
+    }
+
+    /// Creates a new [`EngineApiRecorder`] that appends to the file at the given path, rotated
+    /// once it would exceed the given size in bytes.
+    pub fn with_max_size(path: &Path, max_size: u64) -> io::Result<Self> {
+        let mut writer = RecordingWriter::open(path.to_path_buf(), max_size)?;
+        let (calls, rx) = mpsc::sync_channel::<RecordedEngineCall>(RECORDER_CHANNEL_SIZE);
+        std::thread::Builder::new().name("engine-api-recorder".to_string()).spawn(move || {
+            for call in rx {
+                if let Err(err) = writer.write(&call) {
+                    warn!(target: "rpc::engine", %err, "Failed to record engine API call");
+                }
+            }
+        })?;
+        Ok(Self { calls })
+    }
+
+    /// Records the given call, or drops it if the writer thread is falling behind.
+    pub fn record(&self, call: RecordedEngineCall) {
+        // the writer thread only exits when all senders are dropped
+        if let Err(mpsc::TrySendError::Full(call)) = self.calls.try_send(call) {
+            warn!(target: "rpc::engine", method = %call.method, "Engine API recorder is falling behind, dropping call");
+        }
+    }
+}
+
+/// Writes the recorded calls to a file, and rotates it once it reaches the maximum size.
+#[derive(Debug)]
+struct RecordingWriter {
+    path: PathBuf,
+    max_size: u64,
+    writer: BufWriter<File>,
+    /// The size of the current file
+    size: u64,
+}
+
+impl RecordingWriter {
+    /// Opens the file at the given path for appending.
+    fn open(path: PathBuf, max_size: u64) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, max_size, writer: BufWriter::new(file), size })
+    }
+
+    /// Appends the call as a line, after rotating the file if the line doesn't fit.
+    fn write(&mut self, call: &RecordedEngineCall) -> io::Result<()> {
+        let mut line = serde_json::to_vec(call)?;
+        line.push(b'\n');
+        let len = line.len() as u64;
+        if self.size > 0 && self.size + len > self.max_size {
+            self.rotate()?;
+        }
+        self.writer.write_all(&line)?;
+        self.writer.flush()?;
+        self.size += len;
+        Ok(())
+    }
+
+    /// Moves the current file to the rotated path, replacing the previous one, and starts a new
+    /// file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        std::fs::rename(&self.path, rotated_path(&self.path))?;
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.writer = BufWriter::new(file);
+        self.size = 0;
+        Ok(())
+    }
+}
+
+/// Returns the path the recording at the given path is moved to when it's rotated.
+pub fn rotated_path(path: &Path) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(".1");
+    rotated.into()
+}
+
+/// A layer that records the calls of the server, if a recorder is configured.
+#[derive(Debug, Clone, Default)]
+pub struct EngineApiRecorderLayer {
+    recorder: Option<EngineApiRecorder>,
+}
+
+impl EngineApiRecorderLayer {
+    /// Creates a new [`EngineApiRecorderLayer`] with the given recorder.
+    pub const fn new(recorder: Option<EngineApiRecorder>) -> Self {
+        Self { recorder }
+    }
+}
+
+impl<S> Layer<S> for EngineApiRecorderLayer {
+    type Service = EngineApiRecorderService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EngineApiRecorderService { inner, recorder: self.recorder.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that records every call with its response.
+#[derive(Debug, Clone)]
+pub struct EngineApiRecorderService<S> {
+    inner: S,
+    recorder: Option<EngineApiRecorder>,
+}
+
+impl<'a, S> RpcServiceT<'a> for EngineApiRecorderService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = RecordedRequestFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let pending = self.recorder.clone().map(|recorder| PendingCall {
+            recorder,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis()
+                as u64,
+            started_at: Instant::now(),
+            method: req.method_name().to_string(),
+            params: params_to_owned(req.params()),
+        });
+        RecordedRequestFuture { fut: self.inner.call(req), pending }
+    }
+}
+
+/// Returns the raw JSON of the given params, if any.
+fn params_to_owned(params: Params<'_>) -> Option<Box<RawValue>> {
+    params.as_str().and_then(|params| RawValue::from_string(params.to_string()).ok())
+}
+
+/// A request that is recorded once the response is ready.
+#[derive(Debug)]
+struct PendingCall {
+    recorder: EngineApiRecorder,
+    timestamp: u64,
+    started_at: Instant,
+    method: String,
+    params: Option<Box<RawValue>>,
+}
+
+/// Response future that records the request once it's resolved.
+#[pin_project::pin_project]
+#[derive(Debug)]
+pub struct RecordedRequestFuture<F> {
+    #[pin]
+    fut: F,
+    /// The request, if it is recorded
+    pending: Option<PendingCall>,
+}
+
+impl<F: Future<Output = MethodResponse>> Future for RecordedRequestFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            if let Some(call) = this.pending.take() {
+                let Ok(response) = RawValue::from_string(resp.as_result().to_string()) else {
+                    return res
+                };
+                call.recorder.record(RecordedEngineCall {
+                    timestamp: call.timestamp,
+                    elapsed_micros: call.started_at.elapsed().as_micros() as u64,
+                    method: call.method,
+                    params: call.params,
+                    response,
+                });
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(method: &str) -> RecordedEngineCall {
+        RecordedEngineCall {
+            timestamp: 1,
+            elapsed_micros: 2,
+            method: method.to_string(),
+            params: Some(RawValue::from_string("[1]".to_string()).unwrap()),
+            response: RawValue::from_string(r#"{"jsonrpc":"2.0","result":null,"id":1}"#.into())
+                .unwrap(),
+        }
+    }
+
+    fn read_methods(path: &Path) -> Vec<String> {
+        RecordedEngineCall::read_all(path).unwrap().map(|call| call.unwrap().method).collect()
+    }
+
+    #[test]
+    fn records_calls_as_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine.jsonl");
+
+        let recorder = EngineApiRecorder::new(&path).unwrap();
+        for method in ["engine_forkchoiceUpdatedV3", "engine_newPayloadV3"] {
+            recorder.record(call(method));
+        }
+        // closes the channel, so the writer thread exits
+        drop(recorder);
+
+        let mut calls = Vec::new();
+        for _ in 0..100 {
+            calls = RecordedEngineCall::read_all(&path)
+                .unwrap()
+                .collect::<io::Result<Vec<_>>>()
+                .unwrap();
+            if calls.len() == 2 {
+                break
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].method, "engine_forkchoiceUpdatedV3");
+        assert_eq!(calls[1].params.as_ref().unwrap().get(), "[1]");
+    }
+
+    #[test]
+    fn rotates_full_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("engine.jsonl");
+        let line_len = serde_json::to_vec(&call("engine_a")).unwrap().len() as u64 + 1;
+
+        // two calls fit into a file
+        let mut writer = RecordingWriter::open(path.clone(), 2 * line_len).unwrap();
+        for method in ["engine_a", "engine_b", "engine_c", "engine_d", "engine_e"] {
+            writer.write(&call(method)).unwrap();
+        }
+
+        assert_eq!(read_methods(&rotated_path(&path)), ["engine_c", "engine_d"]);
+        assert_eq!(read_methods(&path), ["engine_e"]);
+
+        // the size of an existing recording counts towards the limit
+        drop(writer);
+        let mut writer = RecordingWriter::open(path.clone(), 2 * line_len).unwrap();
+        writer.write(&call("engine_f")).unwrap();
+        writer.write(&call("engine_g")).unwrap();
+        assert_eq!(read_methods(&rotated_path(&path)), ["engine_e", "engine_f"]);
+        assert_eq!(read_methods(&path), ["engine_g"]);
+    }
+}
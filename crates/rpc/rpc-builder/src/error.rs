@@ -1,5 +1,6 @@
-use crate::{cors::CorsDomainError, RethRpcModule};
+use crate::{api_keys::ApiKeysError, cors::CorsDomainError, RethRpcModule};
 use reth_ipc::server::IpcServerStartError;
+use reth_rpc_layer::JwtError;
 use std::{
     collections::HashSet,
     io::{self, ErrorKind},
@@ -70,6 +71,12 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Thrown when a JWT secret can't be loaded.
+    #[error(transparent)]
+    Jwt(#[from] JwtError),
+    /// Thrown when the API keys can't be loaded.
+    #[error(transparent)]
+    ApiKeys(#[from] ApiKeysError),
     /// Custom error.
     #[error("{0}")]
     Custom(String),
@@ -7,21 +7,28 @@ use reth_provider::{
 };
 use reth_rpc::{
     eth::{
-        cache::{cache_new_blocks_task, EthStateCache, EthStateCacheConfig},
+        cache::{
+            cache_new_blocks_task, EthStateCache, EthStateCacheConfig, RpcResponseCache,
+            RpcResponseCacheConfig,
+        },
         fee_history_cache_new_blocks_task,
         gas_oracle::{GasPriceOracle, GasPriceOracleConfig},
         traits::RawTransactionForwarder,
-        EthFilterConfig, FeeHistoryCache, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
+        CallCapsConfig, EthFilterConfig, FeeHistoryCache, FeeHistoryCacheConfig,
+        RPC_DEFAULT_GAS_CAP,
     },
     EthApi, EthFilter, EthPubSub,
 };
 use reth_rpc_server_types::constants::{
-    default_max_tracing_requests, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    default_max_tracing_requests, DEFAULT_ACCESS_LIST_MAX_ITERATIONS, DEFAULT_ETH_PROOF_WINDOW,
+    DEFAULT_LOGS_REORG_DEPTH, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_TRACE_BLOCK_PARALLELISM,
 };
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner};
 use reth_transaction_pool::TransactionPool;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
+use tracing::{debug, warn};
 
 /// All handlers for the `eth` namespace
 #[derive(Debug, Clone)]
@@ -193,7 +200,7 @@ where
         fee_history_cache: &FeeHistoryCache,
         blocking_task_pool: &BlockingTaskPool,
     ) -> EthApi<Provider, Pool, Network, EvmConfig> {
-        EthApi::with_spawner(
+        let api = EthApi::with_spawner(
             self.eth_handlers_config.provider.clone(),
             self.eth_handlers_config.pool.clone(),
             self.eth_handlers_config.network.clone(),
@@ -205,7 +212,26 @@ where
             fee_history_cache.clone(),
             self.eth_handlers_config.evm_config.clone(),
             self.eth_handlers_config.eth_raw_transaction_forwarder.clone(),
-        )
+        );
+        if let Some(timeout) = self.rpc_config.eth.raw_transaction_sync_timeout {
+            api.set_eth_raw_transaction_sync(
+                Arc::new(self.eth_handlers_config.events.clone()),
+                timeout,
+            );
+        }
+        if let Some(cache) = RpcResponseCache::new(self.rpc_config.eth.response_cache) {
+            api.set_response_cache(cache);
+        }
+        api.set_eth_proof_window(self.rpc_config.eth.eth_proof_window);
+        api.set_access_list_max_iterations(self.rpc_config.eth.access_list_max_iterations);
+        api.set_call_caps(self.rpc_config.eth.call_caps);
+        for path in &self.rpc_config.eth.revert_abi_files {
+            match api.revert_decoder().load_file(path) {
+                Ok(added) => debug!(target: "rpc", ?path, added, "Loaded revert error definitions"),
+                Err(err) => warn!(target: "rpc", %err, "Failed to load revert error definitions"),
+            }
+        }
+        api
     }
 
     /// Initializes the `EthFilter`.
@@ -228,6 +254,8 @@ where
             self.eth_handlers_config.network.clone(),
             Box::new(self.eth_handlers_config.executor.clone()),
         )
+        .with_logs_reorg_depth(self.rpc_config.eth.logs_reorg_depth)
+        .with_max_replay_blocks(self.rpc_config.eth.max_subscription_replay_blocks)
     }
 }
 
@@ -236,10 +264,14 @@ where
 pub struct EthConfig {
     /// Settings for the caching layer
     pub cache: EthStateCacheConfig,
+    /// Settings for the cache of expensive responses
+    pub response_cache: RpcResponseCacheConfig,
     /// Settings for the gas price oracle
     pub gas_oracle: GasPriceOracleConfig,
     /// The maximum number of tracing calls that can be executed in concurrently.
     pub max_tracing_requests: usize,
+    /// The maximum number of threads that trace the transactions of a block in parallel.
+    pub trace_block_parallelism: usize,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
@@ -248,11 +280,32 @@ pub struct EthConfig {
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Caps on the gas limit, call depth and execution time of `eth_call` and `eth_estimateGas`.
+    pub call_caps: CallCapsConfig,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Maximum duration `eth_sendRawTransactionSync` waits for the transaction to be included.
+    ///
+    /// The method is disabled if this is not set.
+    pub raw_transaction_sync_timeout: Option<std::time::Duration>,
+    /// The maximum number of blocks into the past for which `eth_getProof` generates proofs.
+    pub eth_proof_window: u64,
+    /// The maximum number of executions `eth_createAccessList` performs to find a stable access
+    /// list.
+    pub access_list_max_iterations: u64,
+    /// JSON ABI files whose custom errors are used to decode the output of reverted calls.
+    pub revert_abi_files: Vec<PathBuf>,
+    /// The number of recent blocks whose emitted logs each `logs` subscription buffers, to
+    /// notify the subscriber about their removal on reorgs.
+    pub logs_reorg_depth: u64,
+    /// The maximum number of canonical blocks a `newHeads` or `logs` subscription replays from
+    /// its `fromBlock`, if replay is enabled.
+    pub max_subscription_replay_blocks: Option<u64>,
+    /// The number of blocks whose trace addresses are indexed for `trace_filter`, if enabled.
+    pub trace_index_blocks: Option<u32>,
 }
 
 impl EthConfig {
@@ -272,13 +325,23 @@ impl Default for EthConfig {
     fn default() -> Self {
         Self {
             cache: EthStateCacheConfig::default(),
+            response_cache: RpcResponseCacheConfig::default(),
             gas_oracle: GasPriceOracleConfig::default(),
             max_tracing_requests: default_max_tracing_requests(),
+            trace_block_parallelism: DEFAULT_TRACE_BLOCK_PARALLELISM,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            call_caps: CallCapsConfig::default(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            raw_transaction_sync_timeout: None,
+            eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
+            access_list_max_iterations: DEFAULT_ACCESS_LIST_MAX_ITERATIONS,
+            revert_abi_files: Vec::new(),
+            logs_reorg_depth: DEFAULT_LOGS_REORG_DEPTH,
+            max_subscription_replay_blocks: None,
+            trace_index_blocks: None,
         }
     }
 }
@@ -290,6 +353,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the response cache settings
+    pub const fn response_cache(mut self, response_cache: RpcResponseCacheConfig) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
     /// Configures the gas price oracle settings
     pub const fn gpo_config(mut self, gas_oracle_config: GasPriceOracleConfig) -> Self {
         self.gas_oracle = gas_oracle_config;
@@ -302,6 +371,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of threads that trace the transactions of a block in parallel
+    pub const fn trace_block_parallelism(mut self, parallelism: usize) -> Self {
+        self.trace_block_parallelism = parallelism;
+        self
+    }
+
     /// Configures the maximum block length to scan per `eth_getLogs` request
     pub const fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
         self.max_blocks_per_filter = max_blocks;
@@ -319,4 +394,61 @@ impl EthConfig {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the caps of `eth_call` and `eth_estimateGas`
+    pub const fn call_caps(mut self, call_caps: CallCapsConfig) -> Self {
+        self.call_caps = call_caps;
+        self
+    }
+
+    /// Configures the maximum duration `eth_sendRawTransactionSync` waits for the transaction to
+    /// be included, or disables the method if `None`
+    pub const fn raw_transaction_sync_timeout(
+        mut self,
+        timeout: Option<std::time::Duration>,
+    ) -> Self {
+        self.raw_transaction_sync_timeout = timeout;
+        self
+    }
+
+    /// Configures the maximum number of blocks into the past for which `eth_getProof` generates
+    /// proofs
+    pub const fn eth_proof_window(mut self, window: u64) -> Self {
+        self.eth_proof_window = window;
+        self
+    }
+
+    /// Configures the maximum number of executions `eth_createAccessList` performs to find a
+    /// stable access list
+    pub const fn access_list_max_iterations(mut self, iterations: u64) -> Self {
+        self.access_list_max_iterations = iterations;
+        self
+    }
+
+    /// Configures the number of recent blocks whose emitted logs each `logs` subscription buffers
+    pub const fn logs_reorg_depth(mut self, depth: u64) -> Self {
+        self.logs_reorg_depth = depth;
+        self
+    }
+
+    /// Configures the maximum number of canonical blocks a `newHeads` or `logs` subscription
+    /// replays from its `fromBlock`, or disables replay
+    pub const fn max_subscription_replay_blocks(mut self, max_blocks: Option<u64>) -> Self {
+        self.max_subscription_replay_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the number of blocks whose trace addresses are indexed for `trace_filter`, or
+    /// disables the index if `None`
+    pub const fn trace_index_blocks(mut self, max_blocks: Option<u32>) -> Self {
+        self.trace_index_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the JSON ABI files whose custom errors are used to decode the output of
+    /// reverted calls
+    pub fn revert_abi_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.revert_abi_files = files;
+        self
+    }
 }
@@ -156,11 +156,14 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::{
+    api_keys::{ApiKeys, ApiKeysConfig},
     auth::AuthRpcModule,
     cors::CorsDomainError,
     error::WsHttpSamePortError,
     eth::{EthHandlersBuilder, EthHandlersConfig},
-    metrics::RpcRequestMetrics,
+    metrics::{RpcRequestMetrics, WsKeepalive},
+    response_size::{ResponseSizeLimitLayer, ResponseSizeLimits},
+    slow_query::SlowQueryLogLayer,
 };
 use error::{ConflictingModules, RpcError, ServerKind};
 use http::{header::AUTHORIZATION, HeaderMap};
@@ -178,9 +181,14 @@ use reth_provider::{
     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    eth::{cache::EthStateCache, traits::RawTransactionForwarder, EthBundle},
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthSubscriptionIdProvider, NetApi, OtterscanApi,
-    RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    eth::{
+        cache::EthStateCache,
+        traits::{RawTransactionForwarder, RawTransactionValidator},
+        EthBundle,
+    },
+    trace_index_new_blocks_task, AdminApi, DebugApi, EngineEthApi, EthApi,
+    EthSubscriptionIdProvider, NetApi, OtterscanApi, RPCApi, RethApi, RethBlobs, RethSimulate,
+    TraceApi, TraceIndex, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
@@ -205,6 +213,9 @@ pub use reth_ipc::server::{
 pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
 pub use tower::layer::util::{Identity, Stack};
 
+/// API key authentication and usage accounting of the rpc servers.
+pub mod api_keys;
+
 /// Auth server utilities.
 pub mod auth;
 
@@ -214,6 +225,9 @@ pub mod config;
 /// Cors utilities.
 mod cors;
 
+/// Engine API request recording.
+pub mod engine_recorder;
+
 /// Rpc error utilities.
 pub mod error;
 
@@ -224,6 +238,12 @@ pub use eth::{EthConfig, EthHandlers};
 // Rpc server metrics
 mod metrics;
 
+/// Slow query log of the rpc servers.
+pub mod slow_query;
+
+/// Response size limits of the rpc servers per namespace.
+pub mod response_size;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig>(
@@ -634,11 +654,15 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
     eth: Option<EthHandlers<Provider, Pool, Network, Events, EvmConfig>>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Index of the trace addresses of blocks, shared by all `trace` handlers
+    trace_index: Option<TraceIndex>,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
     /// Optional forwarder for `eth_sendRawTransaction`
     // TODO(mattsse): find a more ergonomic way to configure eth/rpc customizations
     eth_raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
+    /// Optional validator for `eth_sendRawTransaction`
+    eth_raw_transaction_validator: Option<Arc<dyn RawTransactionValidator>>,
 }
 
 // === impl RethModuleRegistry ===
@@ -665,9 +689,11 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             executor,
             modules: Default::default(),
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
+            trace_index: config.eth.trace_index_blocks.map(TraceIndex::new),
             config,
             events,
             eth_raw_transaction_forwarder: None,
+            eth_raw_transaction_validator: None,
         }
     }
 
@@ -685,6 +711,18 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         self.eth_raw_transaction_forwarder = Some(forwarder);
     }
 
+    /// Sets a validator for `eth_sendRawTransaction` that runs before the transaction is forwarded
+    /// or submitted to the pool.
+    pub fn set_eth_raw_transaction_validator(
+        &mut self,
+        validator: Arc<dyn RawTransactionValidator>,
+    ) {
+        if let Some(eth) = self.eth.as_ref() {
+            eth.api.set_eth_raw_transaction_validator(validator.clone());
+        }
+        self.eth_raw_transaction_validator = Some(validator);
+    }
+
     /// Returns a reference to the pool
     pub const fn pool(&self) -> &Pool {
         &self.pool
@@ -705,6 +743,11 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         &self.provider
     }
 
+    /// Returns a reference to the guard that limits the number of concurrent blocking calls
+    pub const fn blocking_pool_guard(&self) -> &BlockingTaskGuard {
+        &self.blocking_pool_guard
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -724,10 +767,17 @@ impl<Provider: ChainSpecProvider, Pool, Network, Tasks, Events, EvmConfig>
     RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
 where
     Network: NetworkInfo + Peers + Clone + 'static,
+    Pool: TransactionPool + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
 {
     /// Instantiates `AdminApi`
-    pub fn admin_api(&self) -> AdminApi<Network> {
-        AdminApi::new(self.network.clone(), self.provider.chain_spec())
+    pub fn admin_api(&self) -> AdminApi<Network, Pool> {
+        AdminApi::new(
+            self.network.clone(),
+            self.pool.clone(),
+            self.provider.chain_spec(),
+            Box::new(self.executor.clone()),
+        )
     }
 
     /// Instantiates `Web3Api`
@@ -855,8 +905,10 @@ where
     ///
     /// If called outside of the tokio runtime.
     pub fn register_reth(&mut self) -> &mut Self {
-        let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+        let mut module = self.reth_api().into_rpc();
+        module.merge(RethSimulate::new(self.eth_api()).into_rpc()).expect("No conflicts");
+        module.merge(RethBlobs::new(self.eth_api()).into_rpc()).expect("No conflicts");
+        self.modules.insert(RethRpcModule::Reth, module.into());
         self
     }
 
@@ -924,16 +976,22 @@ where
                 self.modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone(), self.provider.chain_spec())
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Admin => AdminApi::new(
+                            self.network.clone(),
+                            self.pool.clone(),
+                            self.provider.chain_spec(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                         )
+                        .with_trace_block_parallelism(self.config.eth.trace_block_parallelism)
+                        .with_response_cache(eth_api.response_cache())
+                        .with_revert_decoder(eth_api.revert_decoder().clone())
                         .into_rpc()
                         .into(),
                         RethRpcModule::Eth => {
@@ -952,12 +1010,16 @@ where
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
                         )
+                        .with_trace_index(self.trace_index.clone())
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
-                        RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
-                        }
+                        RethRpcModule::Txpool => TxPoolApi::with_spawner(
+                            self.pool.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
                                 .iter()
@@ -968,15 +1030,23 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
-                        RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module = RethApi::new(
+                                self.provider.clone(),
+                                Box::new(self.executor.clone()),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(RethSimulate::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module
+                                .merge(RethBlobs::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            module.into()
                         }
+                        RethRpcModule::EthCallBundle => EthCallBundleApiServer::into_rpc(
+                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone()),
+                        )
+                        .into(),
                     })
                     .clone()
             })
@@ -996,6 +1066,7 @@ where
     /// This will spawn the required service tasks for [`EthApi`] for:
     ///   - [`EthStateCache`]
     ///   - [`reth_rpc::eth::FeeHistoryCache`]
+    ///   - [`TraceIndex`], if enabled
     fn with_eth<F, R>(&mut self, f: F) -> R
     where
         F: FnOnce(&EthHandlers<Provider, Pool, Network, Events, EvmConfig>) -> R,
@@ -1007,7 +1078,7 @@ where
     }
 
     fn init_eth(&self) -> EthHandlers<Provider, Pool, Network, Events, EvmConfig> {
-        EthHandlersBuilder::new(
+        let handlers = EthHandlersBuilder::new(
             EthHandlersConfig {
                 provider: self.provider.clone(),
                 pool: self.pool.clone(),
@@ -1019,7 +1090,24 @@ where
             },
             self.config.clone(),
         )
-        .build()
+        .build();
+        if let Some(validator) = self.eth_raw_transaction_validator.clone() {
+            handlers.api.set_eth_raw_transaction_validator(validator);
+        }
+        if self.trace_index.is_some() {
+            let trace_api = TraceApi::new(
+                self.provider.clone(),
+                handlers.api.clone(),
+                self.blocking_pool_guard.clone(),
+            )
+            .with_trace_index(self.trace_index.clone());
+            let new_canonical_blocks = self.events.canonical_state_stream();
+            self.executor.spawn_critical(
+                "trace index task",
+                Box::pin(trace_index_new_blocks_task(trace_api, new_canonical_blocks)),
+            );
+        }
+        handlers
     }
 
     /// Returns the configured [`EthHandlers`] or creates it if it does not exist yet
@@ -1050,6 +1138,7 @@ where
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
         TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+            .with_trace_index(self.trace_index.clone())
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -1079,7 +1168,12 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
+        let response_cache = eth_api.response_cache();
+        let revert_decoder = eth_api.revert_decoder().clone();
         DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+            .with_trace_block_parallelism(self.config.eth.trace_block_parallelism)
+            .with_response_cache(response_cache)
+            .with_revert_decoder(revert_decoder)
     }
 
     /// Instantiates `NetApi`
@@ -1129,6 +1223,14 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Duration of calls above which they are written to the slow query log
+    slow_query_threshold: Option<Duration>,
+    /// Keepalive pings that close unresponsive WS connections
+    ws_keepalive: Option<WsKeepalive>,
+    /// API keys that are required by the http and ws servers
+    api_keys: Option<ApiKeysConfig>,
+    /// Maximum response sizes of the calls per namespace
+    response_size_limits: Option<ResponseSizeLimits>,
 }
 
 // === impl RpcServerConfig ===
@@ -1246,6 +1348,46 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the duration of calls above which they are written to the slow query log.
+    ///
+    /// See also [`SlowQueryLogLayer`].
+    pub const fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Configures the ping interval and idle timeout of the keepalive pings of the ws server,
+    /// which must be enabled on its [`ServerBuilder`] with [`ServerBuilder::enable_ws_ping`].
+    ///
+    /// This is used to count the connections that are closed because they stopped answering the
+    /// pings.
+    pub const fn with_ws_keepalive(
+        mut self,
+        ping_interval: Duration,
+        idle_timeout: Duration,
+    ) -> Self {
+        self.ws_keepalive = Some(WsKeepalive { ping_interval, idle_timeout });
+        self
+    }
+
+    /// Configures the API keys that are required by the http and ws servers, and whose usage is
+    /// accounted.
+    ///
+    /// See also [`ApiKeys`].
+    pub fn with_api_keys(mut self, api_keys: Option<ApiKeysConfig>) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Configures the maximum response sizes of the calls per namespace.
+    ///
+    /// The limits can only lower the maximum response size of the server builders, see also
+    /// [`ResponseSizeLimitLayer`].
+    pub fn with_response_size_limits(mut self, limits: Option<ResponseSizeLimits>) -> Self {
+        self.response_size_limits = limits;
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1285,12 +1427,18 @@ impl RpcServerConfig {
         self.jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`ResponseSizeLimitLayer`] if limits are configured
+    fn response_size_limit_layer(&self) -> Option<ResponseSizeLimitLayer> {
+        self.response_size_limits.clone().map(ResponseSizeLimitLayer::new)
+    }
+
     /// Builds the ws and http server(s).
     ///
     /// If both are on the same port, they are combined into one server.
     async fn build_ws_http(
         &mut self,
         modules: &TransportRpcModules,
+        api_keys: Option<&ApiKeys>,
     ) -> Result<WsHttpServer, RpcError> {
         let http_socket_addr = self.http_addr.unwrap_or(SocketAddr::V4(SocketAddrV4::new(
             Ipv4Addr::LOCALHOST,
@@ -1332,17 +1480,22 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(cors)?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(api_keys.map(ApiKeys::auth_layer)),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules
-                            .http
-                            .as_ref()
-                            .or(modules.ws.as_ref())
-                            .map(RpcRequestMetrics::same_port)
-                            .unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .or(modules.ws.as_ref())
+                                .map(RpcRequestMetrics::same_port)
+                                .unwrap_or_default(),
+                        )
+                        .layer(SlowQueryLogLayer::new(self.slow_query_threshold))
+                        .option_layer(api_keys.map(ApiKeys::usage_layer))
+                        .option_layer(self.response_size_limit_layer()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1369,11 +1522,21 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(api_keys.map(ApiKeys::auth_layer)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::new()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(
+                            modules
+                                .ws
+                                .as_ref()
+                                .map(|module| RpcRequestMetrics::ws(module, self.ws_keepalive))
+                                .unwrap_or_default(),
+                        )
+                        .layer(SlowQueryLogLayer::new(self.slow_query_threshold))
+                        .option_layer(api_keys.map(ApiKeys::usage_layer))
+                        .option_layer(self.response_size_limit_layer()),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1392,12 +1555,17 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(api_keys.map(ApiKeys::auth_layer)),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(SlowQueryLogLayer::new(self.slow_query_threshold))
+                        .option_layer(api_keys.map(ApiKeys::usage_layer))
+                        .option_layer(self.response_size_limit_layer()),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1425,14 +1593,21 @@ impl RpcServerConfig {
     /// [`RpcServer::start`]
     pub async fn build(mut self, modules: &TransportRpcModules) -> Result<RpcServer, RpcError> {
         let mut server = RpcServer::empty();
-        server.ws_http = self.build_ws_http(modules).await?;
+        let api_keys = self.api_keys.as_ref().map(ApiKeys::load).transpose()?;
+        server.ws_http = self.build_ws_http(modules, api_keys.as_ref()).await?;
 
+        let response_size_limit_layer = self.response_size_limit_layer();
         if let Some(builder) = self.ipc_server_config {
             let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
             let ipc_path =
                 self.ipc_endpoint.unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .layer(SlowQueryLogLayer::new(self.slow_query_threshold))
+                        .option_layer(response_size_limit_layer),
+                )
                 .build(ipc_path);
             server.ipc = Some(ipc);
         }
@@ -1646,6 +1821,48 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replaces the configured methods with the methods of the same name in the given [Methods].
+    ///
+    /// Methods in other that aren't installed on a transport are not added to it.
+    pub fn replace_configured(
+        &mut self,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        for module in
+            [self.http.as_mut(), self.ws.as_mut(), self.ipc.as_mut()].into_iter().flatten()
+        {
+            let mut replaced = Methods::new();
+            for name in other.method_names() {
+                if let Some(callback) = other.method(name) {
+                    if module.remove_method(name).is_some() {
+                        replaced.verify_and_insert(name, callback.clone())?;
+                    }
+                }
+            }
+            module.merge(replaced)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the methods of the given [Methods] to all configured transports, replacing any
+    /// configured methods of the same name.
+    pub fn add_or_replace_configured(
+        &mut self,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        for module in
+            [self.http.as_mut(), self.ws.as_mut(), self.ipc.as_mut()].into_iter().flatten()
+        {
+            for name in other.method_names() {
+                module.remove_method(name);
+            }
+            module.merge(other.clone())?;
+        }
+        Ok(())
+    }
+
     /// Convenience function for starting a server
     pub async fn start_server(self, builder: RpcServerConfig) -> Result<RpcServerHandle, RpcError> {
         builder.start(self).await
@@ -2068,4 +2285,52 @@ mod tests {
             }
         )
     }
+
+    #[tokio::test]
+    async fn test_replace_configured() {
+        let mut http = RpcModule::new(());
+        http.register_method("eth_chainId", |_, _, _| "local").unwrap();
+        let mut modules = TransportRpcModules {
+            config: TransportRpcModuleConfig::default(),
+            http: Some(http),
+            ws: Some(RpcModule::new(())),
+            ipc: None,
+        };
+
+        let mut other = RpcModule::new(());
+        other.register_method("eth_chainId", |_, _, _| "replaced").unwrap();
+        other.register_method("eth_blockNumber", |_, _, _| "added").unwrap();
+        modules.replace_configured(other).unwrap();
+
+        let http = modules.http.unwrap();
+        assert_eq!(http.method_names().collect::<Vec<_>>(), ["eth_chainId"]);
+        let res: String =
+            http.call("eth_chainId", jsonrpsee::core::EmptyServerParams::new()).await.unwrap();
+        assert_eq!(res, "replaced");
+        assert_eq!(modules.ws.unwrap().method_names().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_add_or_replace_configured() {
+        let mut http = RpcModule::new(());
+        http.register_method("eth_chainId", |_, _, _| "local").unwrap();
+        let mut modules = TransportRpcModules {
+            config: TransportRpcModuleConfig::default(),
+            http: Some(http),
+            ws: Some(RpcModule::new(())),
+            ipc: None,
+        };
+
+        let mut other = RpcModule::new(());
+        other.register_method("eth_chainId", |_, _, _| "replaced").unwrap();
+        other.register_method("eth_blockNumber", |_, _, _| "added").unwrap();
+        modules.add_or_replace_configured(other).unwrap();
+
+        let http = modules.http.unwrap();
+        let res: String =
+            http.call("eth_chainId", jsonrpsee::core::EmptyServerParams::new()).await.unwrap();
+        assert_eq!(res, "replaced");
+        assert_eq!(http.method_names().count(), 2);
+        assert_eq!(modules.ws.unwrap().method_names().count(), 2);
+    }
 }
@@ -7,9 +7,9 @@ use std::{
     collections::HashMap,
     future::Future,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tower::Layer;
 
@@ -27,9 +27,20 @@ pub(crate) struct RpcRequestMetrics {
 
 impl RpcRequestMetrics {
     pub(crate) fn new(module: &RpcModule<()>, transport: RpcTransport) -> Self {
+        Self::with_keepalive(module, transport, None)
+    }
+
+    /// Creates a new instance of the metrics layer that counts the connections that are closed by
+    /// the given keepalive pings.
+    fn with_keepalive(
+        module: &RpcModule<()>,
+        transport: RpcTransport,
+        keepalive: Option<WsKeepalive>,
+    ) -> Self {
         Self {
             inner: Arc::new(RpcServerMetricsInner {
                 connection_metrics: transport.connection_metrics(),
+                keepalive,
                 call_metrics: HashMap::from_iter(module.method_names().map(|method| {
                     (method, RpcServerCallMetrics::new_with_labels(&[("method", method)]))
                 })),
@@ -50,8 +61,10 @@ impl RpcRequestMetrics {
     }
 
     /// Creates a new instance of the metrics layer for Ws.
-    pub(crate) fn ws(module: &RpcModule<()>) -> Self {
-        Self::new(module, RpcTransport::WebSocket)
+    ///
+    /// If the keepalive pings are enabled, they are used to count the connections that are reaped.
+    pub(crate) fn ws(module: &RpcModule<()>, keepalive: Option<WsKeepalive>) -> Self {
+        Self::with_keepalive(module, RpcTransport::WebSocket, keepalive)
     }
 
     /// Creates a new instance of the metrics layer for Ws.
@@ -74,6 +87,8 @@ impl<S> Layer<S> for RpcRequestMetrics {
 struct RpcServerMetricsInner {
     /// Connection metrics per transport type
     connection_metrics: RpcServerConnectionMetrics,
+    /// The keepalive pings that close unresponsive connections, if any
+    keepalive: Option<WsKeepalive>,
     /// Call metrics per RPC method
     call_metrics: HashMap<&'static str, RpcServerCallMetrics>,
 }
@@ -84,6 +99,8 @@ struct RpcServerMetricsInner {
 #[derive(Clone)]
 pub(crate) struct RpcRequestMetricsService<S> {
     metrics: RpcRequestMetrics,
+    /// When the connection received its last request, or was opened
+    last_request_at: Arc<Mutex<Instant>>,
     inner: S,
 }
 
@@ -91,7 +108,7 @@ impl<S> RpcRequestMetricsService<S> {
     pub(crate) fn new(service: S, metrics: RpcRequestMetrics) -> Self {
         // this instance is kept alive for the duration of the connection
         metrics.inner.connection_metrics.connections_opened_total.increment(1);
-        Self { inner: service, metrics }
+        Self { inner: service, metrics, last_request_at: Arc::new(Mutex::new(Instant::now())) }
     }
 }
 
@@ -102,6 +119,9 @@ where
     type Future = MeteredRequestFuture<S::Future>;
 
     fn call(&self, req: Request<'a>) -> Self::Future {
+        if self.metrics.inner.keepalive.is_some() {
+            *self.last_request_at.lock().unwrap_or_else(|err| err.into_inner()) = Instant::now();
+        }
         self.metrics.inner.connection_metrics.requests_started_total.increment(1);
         let call_metrics = self.metrics.inner.call_metrics.get_key_value(req.method.as_ref());
         if let Some((_, call_metrics)) = &call_metrics {
@@ -120,6 +140,47 @@ impl<S> Drop for RpcRequestMetricsService<S> {
     fn drop(&mut self) {
         // update connection metrics, connection closed
         self.metrics.inner.connection_metrics.connections_closed_total.increment(1);
+
+        // the server doesn't tell why a connection was closed, so this is inferred from when it
+        // was closed
+        if let Some(keepalive) = self.metrics.inner.keepalive {
+            let last_request_at =
+                *self.last_request_at.lock().unwrap_or_else(|err| err.into_inner());
+            if keepalive.is_reaped(last_request_at.elapsed()) {
+                self.metrics.inner.connection_metrics.connections_reaped_total.increment(1);
+            }
+        }
+    }
+}
+
+/// The keepalive pings of the ws server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct WsKeepalive {
+    /// Interval of the pings
+    pub(crate) ping_interval: Duration,
+    /// Duration without pongs or requests after which a connection is closed
+    pub(crate) idle_timeout: Duration,
+}
+
+impl WsKeepalive {
+    /// How far from an inactivity check a connection can be closed to be counted as reaped.
+    const CHECK_TOLERANCE: Duration = Duration::from_millis(500);
+
+    /// Returns whether a connection that is closed the given duration after its last request was
+    /// likely closed by the server because it stopped answering the pings.
+    ///
+    /// The server checks the inactivity of a connection every ping interval after its last
+    /// request, and only closes it on these checks. Clients that are idle but answer the pings,
+    /// like subscription clients, stay connected, and are only counted if they happen to
+    /// disconnect close to a check.
+    fn is_reaped(&self, since_last_request: Duration) -> bool {
+        if since_last_request < self.idle_timeout || self.ping_interval.is_zero() {
+            return false
+        }
+        let ping_interval = self.ping_interval.as_nanos();
+        let since_check = since_last_request.as_nanos() % ping_interval;
+        let from_check = since_check.min(ping_interval - since_check);
+        from_check <= Self::CHECK_TOLERANCE.min(self.ping_interval / 4).as_nanos()
     }
 }
 
@@ -205,6 +266,9 @@ struct RpcServerConnectionMetrics {
     connections_opened_total: Counter,
     /// The number of connections closed
     connections_closed_total: Counter,
+    /// The number of connections closed after receiving no request for longer than the idle
+    /// timeout, mostly connections that stopped answering the keepalive pings
+    connections_reaped_total: Counter,
     /// The number of requests started
     requests_started_total: Counter,
     /// The number of requests finished
@@ -226,3 +290,26 @@ struct RpcServerCallMetrics {
     /// Response for a single call
     time_seconds: Histogram,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reaped_connections_close_on_inactivity_checks() {
+        let keepalive = WsKeepalive {
+            ping_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(60),
+        };
+
+        // not idle for long enough
+        assert!(!keepalive.is_reaped(Duration::from_secs(30)));
+        // closed on the first check after the idle timeout
+        assert!(keepalive.is_reaped(Duration::from_secs(60)));
+        assert!(keepalive.is_reaped(Duration::from_millis(89_900)));
+        assert!(keepalive.is_reaped(Duration::from_millis(90_100)));
+        // an idle client that answers the pings and disconnects between checks
+        assert!(!keepalive.is_reaped(Duration::from_secs(75)));
+        assert!(!keepalive.is_reaped(Duration::from_secs(3600 + 10)));
+    }
+}
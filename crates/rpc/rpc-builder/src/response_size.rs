@@ -0,0 +1,215 @@
+//! Limits the size of the responses of the calls per namespace.
+//!
+//! The namespace of a call is the prefix of its method name before the first `_`. Calls whose
+//! response exceeds the limit of their namespace fail with [`RESPONSE_TOO_LARGE_CODE`], and the
+//! error data tells the client to request less data per call.
+//!
+//! The limits of the namespaces can only lower the maximum response size of the servers, which
+//! stays the limit of all namespaces, see [`ResponseSizeLimits::new`].
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{error::OVERSIZED_RESPONSE_CODE, ErrorObject, ErrorObjectOwned, Id, Request},
+    MethodResponse,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// Error code of a call whose response exceeds the size limit of its namespace.
+pub const RESPONSE_TOO_LARGE_CODE: i32 = OVERSIZED_RESPONSE_CODE;
+
+/// The maximum response sizes of the calls, in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseSizeLimits {
+    /// The limit of the namespaces without a limit of their own.
+    default: u32,
+    /// The limits by namespace.
+    namespaces: Arc<HashMap<String, u32>>,
+}
+
+impl ResponseSizeLimits {
+    /// Creates new limits where all namespaces share the given limit.
+    ///
+    /// This must be the maximum response size of the servers, the limits of the namespaces are
+    /// capped by it.
+    pub fn new(default: u32) -> Self {
+        Self { default, namespaces: Default::default() }
+    }
+
+    /// Sets the limit of the namespace.
+    pub fn with_namespace(mut self, namespace: impl Into<String>, max_size: u32) -> Self {
+        Arc::make_mut(&mut self.namespaces).insert(namespace.into(), max_size);
+        self
+    }
+
+    /// Returns the limit of the namespace of the method.
+    pub fn max_size(&self, method: &str) -> u32 {
+        self.namespaces.get(namespace(method)).map_or(self.default, |max| self.default.min(*max))
+    }
+}
+
+/// Returns the namespace of the method.
+fn namespace(method: &str) -> &str {
+    method.split_once('_').map_or(method, |(namespace, _)| namespace)
+}
+
+/// Error of a call whose response exceeds the size limit of its namespace.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "response of {method} exceeds the {limit} bytes limit of the {namespace} namespace, \
+     request a smaller range or paginate"
+)]
+pub struct ResponseTooLarge {
+    /// The method of the call.
+    pub method: String,
+    /// The namespace of the method.
+    pub namespace: String,
+    /// The limit of the namespace, in bytes.
+    pub limit: u32,
+    /// The size of the response, if it was fully serialized.
+    pub size: Option<usize>,
+}
+
+impl From<ResponseTooLarge> for ErrorObjectOwned {
+    fn from(err: ResponseTooLarge) -> Self {
+        let data = serde_json::json!({
+            "namespace": err.namespace,
+            "limit": err.limit,
+            "size": err.size,
+        });
+        ErrorObject::owned(RESPONSE_TOO_LARGE_CODE, err.to_string(), Some(data))
+    }
+}
+
+/// A layer that enforces the [`ResponseSizeLimits`] of the calls.
+#[derive(Debug, Clone)]
+pub struct ResponseSizeLimitLayer {
+    limits: ResponseSizeLimits,
+}
+
+impl ResponseSizeLimitLayer {
+    /// Creates a new layer that enforces the given limits.
+    pub const fn new(limits: ResponseSizeLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl<S> Layer<S> for ResponseSizeLimitLayer {
+    type Service = ResponseSizeLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ResponseSizeLimitService { limits: self.limits.clone(), inner }
+    }
+}
+
+/// Rpc middleware service of the [`ResponseSizeLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct ResponseSizeLimitService<S> {
+    limits: ResponseSizeLimits,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for ResponseSizeLimitService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = ResponseSizeLimitFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let method = req.method_name().to_string();
+        let limit = self.limits.max_size(&method);
+        let pending = Some(PendingCall { id: req.id().into_owned(), method, limit });
+        ResponseSizeLimitFuture { fut: self.inner.call(req), pending }
+    }
+}
+
+/// A call whose response is checked against the limit of its namespace.
+#[derive(Debug)]
+struct PendingCall {
+    id: Id<'static>,
+    method: String,
+    limit: u32,
+}
+
+impl PendingCall {
+    /// Returns the error of the call if the response exceeds the limit.
+    fn check(self, resp: &MethodResponse) -> Option<MethodResponse> {
+        if resp.is_subscription() {
+            return None
+        }
+        // responses beyond the limit of the server are already replaced by the server
+        let size = if resp.as_error_code() == Some(OVERSIZED_RESPONSE_CODE) {
+            None
+        } else if resp.as_result().len() > self.limit as usize {
+            Some(resp.as_result().len())
+        } else {
+            return None
+        };
+
+        let err = ResponseTooLarge {
+            namespace: namespace(&self.method).to_string(),
+            method: self.method,
+            limit: self.limit,
+            size,
+        };
+        Some(MethodResponse::error(self.id, err).with_extensions(resp.extensions().clone()))
+    }
+}
+
+/// Response future of the [`ResponseSizeLimitService`].
+#[pin_project::pin_project]
+#[derive(Debug)]
+pub struct ResponseSizeLimitFuture<F> {
+    #[pin]
+    fut: F,
+    pending: Option<PendingCall>,
+}
+
+impl<F: Future<Output = MethodResponse>> Future for ResponseSizeLimitFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let resp = std::task::ready!(this.fut.poll(cx));
+        let call = this.pending.take().expect("polled after completion");
+        Poll::Ready(call.check(&resp).unwrap_or(resp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::ResponsePayload;
+
+    #[test]
+    fn limits_responses_per_namespace() {
+        let limits =
+            ResponseSizeLimits::new(1000).with_namespace("eth", 100).with_namespace("trace", 2000);
+        assert_eq!(limits.max_size("eth_getLogs"), 100);
+        assert_eq!(limits.max_size("debug_traceBlock"), 1000);
+        // the limit of the servers caps the limits of the namespaces
+        assert_eq!(limits.max_size("trace_filter"), 1000);
+
+        let response = |result: String| {
+            MethodResponse::response(Id::Number(1), ResponsePayload::success(result), usize::MAX)
+        };
+        let call = |method: &str| PendingCall {
+            id: Id::Number(1),
+            method: method.to_string(),
+            limit: limits.max_size(method),
+        };
+
+        let resp = response("a".repeat(500));
+        assert!(call("debug_traceBlock").check(&resp).is_none());
+        let err = call("eth_getLogs").check(&resp).unwrap();
+        assert_eq!(err.as_error_code(), Some(RESPONSE_TOO_LARGE_CODE));
+        assert!(err.as_result().contains(r#""namespace":"eth""#));
+        assert!(err.as_result().contains(&format!(r#""size":{}"#, resp.as_result().len())));
+    }
+}
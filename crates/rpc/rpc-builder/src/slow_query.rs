@@ -0,0 +1,151 @@
+//! Logs the RPC calls that take longer than a threshold.
+//!
+//! The calls are logged with the [`SLOW_QUERY_LOG_TARGET`] target, which is written to a
+//! dedicated file by `reth_tracing`, together with a summary of their params and the number of
+//! database reads they did.
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use reth_db_api::read_counter::{self, ReadCounter};
+use reth_rpc_server_types::constants::SLOW_QUERY_LOG_TARGET;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::Layer;
+use tracing::info;
+
+/// The maximum length of the logged params, in bytes.
+const MAX_PARAMS_SUMMARY_LEN: usize = 256;
+
+/// A layer that logs the calls of the server that take longer than the threshold, if one is
+/// configured.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlowQueryLogLayer {
+    threshold: Option<Duration>,
+}
+
+impl SlowQueryLogLayer {
+    /// Creates a new [`SlowQueryLogLayer`] with the given threshold.
+    pub const fn new(threshold: Option<Duration>) -> Self {
+        Self { threshold }
+    }
+}
+
+impl<S> Layer<S> for SlowQueryLogLayer {
+    type Service = SlowQueryLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        if self.threshold.is_some() {
+            // the database reads of the calls are only counted if slow calls are logged
+            read_counter::enable();
+        }
+        SlowQueryLogService { inner, threshold: self.threshold }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that logs the calls that take longer than the threshold.
+#[derive(Debug, Clone)]
+pub struct SlowQueryLogService<S> {
+    inner: S,
+    threshold: Option<Duration>,
+}
+
+impl<'a, S> RpcServiceT<'a> for SlowQueryLogService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = SlowQueryFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let pending = self.threshold.map(|threshold| PendingQuery {
+            threshold,
+            started_at: Instant::now(),
+            method: req.method_name().to_string(),
+            params: summarize_params(req.params().as_str().unwrap_or_default()),
+            reads: ReadCounter::default(),
+        });
+        SlowQueryFuture { fut: self.inner.call(req), pending }
+    }
+}
+
+/// Returns the params, truncated to [`MAX_PARAMS_SUMMARY_LEN`] bytes.
+fn summarize_params(params: &str) -> String {
+    if params.len() <= MAX_PARAMS_SUMMARY_LEN {
+        return params.to_string()
+    }
+    let mut end = MAX_PARAMS_SUMMARY_LEN;
+    while !params.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}... ({} bytes)", &params[..end], params.len())
+}
+
+/// A call that is logged once its response is ready, if it took longer than the threshold.
+#[derive(Debug)]
+struct PendingQuery {
+    threshold: Duration,
+    started_at: Instant,
+    method: String,
+    params: String,
+    /// The database reads of the call
+    reads: ReadCounter,
+}
+
+/// Response future that logs the call once it's resolved, if it was slow.
+///
+/// The database reads that happen while the future is polled, or on the blocking tasks it spawns,
+/// are attributed to the call.
+#[pin_project::pin_project]
+#[derive(Debug)]
+pub struct SlowQueryFuture<F> {
+    #[pin]
+    fut: F,
+    /// The call, if slow calls are logged
+    pending: Option<PendingQuery>,
+}
+
+impl<F: Future<Output = MethodResponse>> Future for SlowQueryFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let _reads = this.pending.as_ref().map(|query| query.reads.enter());
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            if let Some(query) = this.pending.take() {
+                let elapsed = query.started_at.elapsed();
+                if elapsed >= query.threshold {
+                    info!(
+                        target: SLOW_QUERY_LOG_TARGET,
+                        method = %query.method,
+                        params = %query.params,
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        db_reads = query.reads.reads(),
+                        success = resp.is_success(),
+                        "Slow RPC call"
+                    );
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_params() {
+        assert_eq!(summarize_params(r#"["0x1",true]"#), r#"["0x1",true]"#);
+
+        let params = format!(r#"["{}é"]"#, "a".repeat(MAX_PARAMS_SUMMARY_LEN - 3));
+        let summary = summarize_params(&params);
+        assert_eq!(
+            summary,
+            format!(r#"["{}... ({} bytes)"#, "a".repeat(MAX_PARAMS_SUMMARY_LEN - 3), params.len())
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! API key tests
+
+use crate::utils::{test_address, test_rpc_builder};
+use http::HeaderMap;
+use jsonrpsee::{core::client::Error, http_client::HttpClientBuilder};
+use reth_rpc_api::clients::Web3ApiClient;
+use reth_rpc_builder::{
+    api_keys::{ApiKeysConfig, API_KEY_HEADER, QUOTA_EXCEEDED_CODE},
+    RpcServerConfig, TransportRpcModuleConfig,
+};
+use reth_rpc_server_types::RethRpcModule;
+use tokio::task::JoinSet;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_api_keys_http() {
+    reth_tracing::init_test_tracing();
+
+    let dir = tempfile::tempdir().unwrap();
+    let keys_file = dir.path().join("keys.json");
+    std::fs::write(&keys_file, r#"{ "keys": { "limited": { "maxCalls": 3 } } }"#).unwrap();
+
+    let server =
+        test_rpc_builder().build(TransportRpcModuleConfig::set_http(vec![RethRpcModule::Web3]));
+    let handle = server
+        .start_server(
+            RpcServerConfig::http(Default::default())
+                .with_http_address(test_address())
+                .with_api_keys(Some(ApiKeysConfig::new(keys_file))),
+        )
+        .await
+        .unwrap();
+    let url = handle.http_url().unwrap();
+    let client_with_key = |key: &str| {
+        let headers =
+            HeaderMap::from_iter([(API_KEY_HEADER.parse().unwrap(), key.parse().unwrap())]);
+        HttpClientBuilder::default().set_headers(headers).build(&url).unwrap()
+    };
+
+    // requests without a configured key are rejected
+    let client = HttpClientBuilder::default().build(&url).unwrap();
+    assert!(Web3ApiClient::client_version(&client).await.is_err());
+    let client = client_with_key("unknown");
+    assert!(Web3ApiClient::client_version(&client).await.is_err());
+
+    // concurrent calls can't exceed the quota
+    let client = client_with_key("limited");
+    let mut calls = JoinSet::new();
+    for _ in 0..8 {
+        let client = client.clone();
+        calls.spawn(async move { Web3ApiClient::client_version(&client).await });
+    }
+    let (mut accepted, mut rejected) = (0, 0);
+    while let Some(result) = calls.join_next().await {
+        match result.unwrap() {
+            Ok(_) => accepted += 1,
+            Err(Error::Call(err)) if err.code() == QUOTA_EXCEEDED_CODE => rejected += 1,
+            Err(err) => panic!("unexpected error: {err}"),
+        }
+    }
+    assert_eq!((accepted, rejected), (3, 5));
+}
@@ -23,7 +23,9 @@ use reth_rpc_api::{
 use reth_rpc_server_types::RethRpcModule;
 use reth_rpc_types::{
     trace::filter::TraceFilter, FeeHistory, Filter, Index, Log, PendingTransactionFilterKind,
-    RichBlock, SyncStatus, Transaction, TransactionReceipt, TransactionRequest,
+    RichBlock, SyncStatus, Transaction, TransactionReceipt, TransactionRequest, TxpoolSnapshot,
+    TxpoolSnapshotSubpool, TxpoolSnapshotTransaction, TxpoolTransactionOrigin,
+    MAX_TXPOOL_SNAPSHOT_PAGE_SIZE,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
@@ -144,6 +146,28 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::peer_bandwidth(client).await.unwrap();
+    assert_eq!(AdminApiClient::nat_status(client).await.unwrap(), None);
+    assert!(!AdminApiClient::refresh_nat(client).await.unwrap());
+    let snapshot = AdminApiClient::export_txpool(client, None, None).await.unwrap();
+    assert!(snapshot.transactions.is_empty());
+    let outcome = AdminApiClient::import_txpool(client, snapshot.clone()).await.unwrap();
+    assert!(outcome.imported.is_empty() && outcome.failed.is_empty());
+    let transaction = TxpoolSnapshotTransaction {
+        hash: Default::default(),
+        sender: Address::default(),
+        nonce: 0,
+        subpool: TxpoolSnapshotSubpool::Pending,
+        origin: TxpoolTransactionOrigin::External,
+        effective_tip: None,
+        age: 0,
+        raw: Default::default(),
+    };
+    let oversized = TxpoolSnapshot {
+        transactions: vec![transaction; MAX_TXPOOL_SNAPSHOT_PAGE_SIZE + 1],
+        ..snapshot
+    };
+    assert!(AdminApiClient::import_txpool(client, oversized).await.is_err());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -309,9 +333,7 @@ where
 
     OtterscanClient::get_transaction_error(client, tx_hash).await.unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::trace_transaction(client, tx_hash).await.err().unwrap()
-    ));
+    OtterscanClient::trace_transaction(client, tx_hash).await.unwrap();
 
     OtterscanClient::get_block_details(client, block_number).await.unwrap();
 
@@ -322,27 +344,15 @@ where
         .err()
         .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_before(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_after(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_contract_creator(client, address).await.err().unwrap()
-    ));
+    OtterscanClient::search_transactions_before(client, address, block_number, page_size)
+        .await
+        .err()
+        .unwrap();
+    OtterscanClient::search_transactions_after(client, address, block_number, page_size)
+        .await
+        .unwrap();
+    OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce).await.unwrap();
+    OtterscanClient::get_contract_creator(client, address).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
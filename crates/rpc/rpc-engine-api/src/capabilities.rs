@@ -0,0 +1,241 @@
+use crate::metrics::EngineCapabilitiesMetrics;
+use jsonrpsee_core::RpcResult;
+use parking_lot::Mutex;
+use reth_rpc_api::EngineCapabilitiesApiServer;
+use reth_rpc_types::{engine::CAPABILITIES, EngineCapabilitiesReport};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use tracing::{debug, warn};
+
+/// The engine API methods that are supported in addition to [`CAPABILITIES`].
+const BLOB_CAPABILITIES: [&str; 1] = ["engine_getBlobsV1"];
+
+/// The engine API methods that are advertised to the consensus layer in
+/// `engine_exchangeCapabilities`.
+///
+/// By default, all supported methods are advertised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineCapabilities {
+    inner: BTreeSet<String>,
+}
+
+impl EngineCapabilities {
+    /// Creates a new [`EngineCapabilities`] that advertises the given methods.
+    pub fn new(capabilities: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { inner: capabilities.into_iter().map(Into::into).collect() }
+    }
+
+    /// Returns `true` if the given method is advertised.
+    pub fn contains(&self, method: &str) -> bool {
+        self.inner.contains(method)
+    }
+
+    /// Returns the advertised methods.
+    pub fn list(&self) -> Vec<String> {
+        self.inner.iter().cloned().collect()
+    }
+}
+
+impl Default for EngineCapabilities {
+    fn default() -> Self {
+        Self::new(CAPABILITIES.iter().chain(&BLOB_CAPABILITIES).copied())
+    }
+}
+
+/// Tracks the engine API methods that the consensus layer supports and calls, and compares them to
+/// the advertised [`EngineCapabilities`].
+///
+/// Mismatches are logged once and recorded in metrics. The tracker serves the
+/// `admin_engineCapabilities` method, which returns a [`EngineCapabilitiesReport`].
+#[derive(Clone)]
+pub struct EngineCapabilitiesTracker {
+    inner: Arc<EngineCapabilitiesTrackerInner>,
+}
+
+struct EngineCapabilitiesTrackerInner {
+    /// The advertised methods.
+    advertised: EngineCapabilities,
+    /// The methods that the consensus layer supports and calls.
+    state: Mutex<ConsensusClientCapabilities>,
+    /// The metrics of mismatched methods.
+    metrics: EngineCapabilitiesMetrics,
+}
+
+/// The methods that the consensus layer supports and calls.
+#[derive(Debug, Default)]
+struct ConsensusClientCapabilities {
+    /// The methods that the consensus layer sent in its last `engine_exchangeCapabilities` call.
+    supported: Option<BTreeSet<String>>,
+    /// The number of calls of each method.
+    calls: BTreeMap<&'static str, u64>,
+}
+
+impl EngineCapabilitiesTracker {
+    /// Creates a new [`EngineCapabilitiesTracker`] for the given advertised methods.
+    pub fn new(advertised: EngineCapabilities) -> Self {
+        let inner = EngineCapabilitiesTrackerInner {
+            advertised,
+            state: Default::default(),
+            metrics: Default::default(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Returns the advertised methods.
+    pub fn advertised(&self) -> &EngineCapabilities {
+        &self.inner.advertised
+    }
+
+    /// Records a call of the given method by the consensus layer.
+    ///
+    /// The first call of a method that is not advertised is logged as a warning.
+    pub(crate) fn on_call(&self, method: &'static str) {
+        let mut state = self.inner.state.lock();
+        let calls = state.calls.entry(method).or_default();
+        *calls += 1;
+        if !self.inner.advertised.contains(method) {
+            self.inner.metrics.unadvertised_method_calls.increment(1);
+            if *calls == 1 {
+                warn!(
+                    target: "rpc::engine",
+                    method,
+                    "Consensus client called an engine method that is not advertised"
+                );
+            }
+        }
+    }
+
+    /// Records the methods that the consensus layer supports, as sent in
+    /// `engine_exchangeCapabilities`, and returns the advertised methods.
+    ///
+    /// Mismatches are logged whenever the supported methods change.
+    pub(crate) fn on_exchange_capabilities(&self, supported: Vec<String>) -> Vec<String> {
+        let supported = supported.into_iter().collect::<BTreeSet<_>>();
+        let unsupported = self.unsupported(&supported);
+        let unused = self.unused(&supported);
+        self.inner.metrics.unsupported_capabilities.set(unsupported.len() as f64);
+        self.inner.metrics.unused_capabilities.set(unused.len() as f64);
+
+        let mut state = self.inner.state.lock();
+        if state.supported.as_ref() != Some(&supported) {
+            if !unsupported.is_empty() {
+                warn!(
+                    target: "rpc::engine",
+                    ?unsupported,
+                    "Consensus client supports engine methods that are not advertised"
+                );
+            }
+            if !unused.is_empty() {
+                debug!(
+                    target: "rpc::engine",
+                    ?unused,
+                    "Consensus client doesn't support advertised engine methods"
+                );
+            }
+            state.supported = Some(supported);
+        }
+
+        self.inner.advertised.list()
+    }
+
+    /// Returns the advertised methods, the methods that the consensus layer supports and calls, and
+    /// the mismatches between them.
+    pub fn report(&self) -> EngineCapabilitiesReport {
+        let state = self.inner.state.lock();
+        let (unsupported, unused) = state
+            .supported
+            .as_ref()
+            .map(|supported| (self.unsupported(supported), self.unused(supported)))
+            .unwrap_or_default();
+        EngineCapabilitiesReport {
+            advertised: self.inner.advertised.list(),
+            consensus_client: state
+                .supported
+                .as_ref()
+                .map(|supported| supported.iter().cloned().collect()),
+            calls: state.calls.iter().map(|(method, calls)| (method.to_string(), *calls)).collect(),
+            unadvertised_calls: state
+                .calls
+                .keys()
+                .filter(|method| !self.inner.advertised.contains(method))
+                .map(|method| method.to_string())
+                .collect(),
+            unsupported,
+            unused,
+        }
+    }
+
+    /// Returns the supported methods that are not advertised.
+    fn unsupported(&self, supported: &BTreeSet<String>) -> Vec<String> {
+        supported.iter().filter(|method| !self.inner.advertised.contains(method)).cloned().collect()
+    }
+
+    /// Returns the advertised methods that are not supported.
+    fn unused(&self, supported: &BTreeSet<String>) -> Vec<String> {
+        self.inner.advertised.inner.difference(supported).cloned().collect()
+    }
+}
+
+impl EngineCapabilitiesApiServer for EngineCapabilitiesTracker {
+    /// Handler for `admin_engineCapabilities`
+    fn engine_capabilities(&self) -> RpcResult<EngineCapabilitiesReport> {
+        Ok(self.report())
+    }
+}
+
+impl std::fmt::Debug for EngineCapabilitiesTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EngineCapabilitiesTracker")
+            .field("advertised", &self.inner.advertised)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_capabilities_include_blobs() {
+        let capabilities = EngineCapabilities::default();
+        assert!(CAPABILITIES.iter().all(|method| capabilities.contains(method)));
+        assert!(capabilities.contains("engine_getBlobsV1"));
+        assert!(!capabilities.contains("engine_exchangeCapabilities"));
+    }
+
+    #[test]
+    fn tracks_mismatches() {
+        let tracker = EngineCapabilitiesTracker::new(EngineCapabilities::new([
+            "engine_newPayloadV3",
+            "engine_forkchoiceUpdatedV3",
+        ]));
+
+        let report = tracker.report();
+        assert_eq!(report.consensus_client, None);
+        assert!(report.unsupported.is_empty());
+        assert!(report.unused.is_empty());
+
+        tracker.on_call("engine_newPayloadV3");
+        tracker.on_call("engine_newPayloadV4");
+        tracker.on_call("engine_newPayloadV4");
+        let advertised = tracker.on_exchange_capabilities(vec![
+            "engine_newPayloadV3".to_string(),
+            "engine_newPayloadV4".to_string(),
+        ]);
+        assert_eq!(advertised, tracker.advertised().list());
+
+        let report = tracker.report();
+        assert_eq!(
+            report.calls,
+            BTreeMap::from([
+                ("engine_newPayloadV3".to_string(), 1),
+                ("engine_newPayloadV4".to_string(), 2)
+            ])
+        );
+        assert_eq!(report.unadvertised_calls, vec!["engine_newPayloadV4".to_string()]);
+        assert_eq!(report.unsupported, vec!["engine_newPayloadV4".to_string()]);
+        assert_eq!(report.unused, vec!["engine_forkchoiceUpdatedV3".to_string()]);
+    }
+}
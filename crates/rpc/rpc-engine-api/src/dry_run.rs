@@ -0,0 +1,285 @@
+use crate::{EngineApiError, EngineApiResult};
+use async_trait::async_trait;
+use jsonrpsee_core::RpcResult;
+use reth_chainspec::ChainSpec;
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor as _};
+use reth_payload_validator::ExecutionPayloadValidator;
+use reth_primitives::{GotExpected, B256, U256};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::EngineDryRunApiServer;
+use reth_rpc_types::{
+    engine::{CancunPayloadFields, ExecutionPayload},
+    PayloadDryRunResult,
+};
+use reth_storage_api::{BlockReader, BlockSource, StateProviderFactory, StateRootProvider};
+use reth_tasks::TaskSpawner;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+use tracing::trace;
+
+/// The `engine_dryRunPayload` method, which lets builders validate their blocks against this node.
+///
+/// The payload is validated and executed on top of its parent, which can be a canonical block or a
+/// block of a fork. Neither the block nor its state are persisted, and the canonical chain is not
+/// changed.
+pub struct EngineDryRun<Provider, Executor> {
+    inner: Arc<EngineDryRunInner<Provider, Executor>>,
+}
+
+struct EngineDryRunInner<Provider, Executor> {
+    /// The provider to interact with the chain.
+    provider: Provider,
+    /// The executor of the payloads.
+    executor: Executor,
+    /// The consensus rules the payloads are validated against.
+    consensus: Arc<dyn Consensus>,
+    /// Ensures that the payloads are well formed.
+    payload_validator: ExecutionPayloadValidator,
+    /// The type that's used to spawn the blocking execution of the payloads.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<Provider, Executor> EngineDryRun<Provider, Executor>
+where
+    Provider: BlockReader + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Creates a new [`EngineDryRun`].
+    pub fn new(
+        provider: Provider,
+        chain_spec: Arc<ChainSpec>,
+        executor: Executor,
+        consensus: Arc<dyn Consensus>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = EngineDryRunInner {
+            provider,
+            executor,
+            consensus,
+            payload_validator: ExecutionPayloadValidator::new(chain_spec),
+            task_spawner,
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Validates and executes the payload, without persisting it.
+    ///
+    /// A payload that violates the consensus rules results in an invalid [`PayloadDryRunResult`],
+    /// an error is only returned if the payload couldn't be validated.
+    fn dry_run(
+        &self,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+    ) -> EngineApiResult<PayloadDryRunResult> {
+        let this = &self.inner;
+
+        let block = match this
+            .payload_validator
+            .ensure_well_formed_payload(payload, cancun_fields.into())
+        {
+            Ok(block) => block,
+            Err(err) => return Ok(PayloadDryRunResult::invalid(err)),
+        };
+
+        let parent_hash = block.parent_hash;
+        let parent = this
+            .provider
+            .find_block_by_hash(parent_hash, BlockSource::Any)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?
+            .ok_or(EngineApiError::UnknownParent(parent_hash))?
+            .header
+            .seal(parent_hash);
+
+        let validation = this
+            .consensus
+            .validate_header(&block.header)
+            .and_then(|_| this.consensus.validate_header_against_parent(&block.header, &parent))
+            .and_then(|_| this.consensus.validate_block_pre_execution(&block));
+        if let Err(err) = validation {
+            return Ok(PayloadDryRunResult::invalid(err))
+        }
+
+        let Ok(block) = block.try_seal_with_senders() else {
+            return Ok(PayloadDryRunResult::invalid("failed to recover the transaction senders"))
+        };
+        let block = block.unseal();
+
+        let state = this
+            .provider
+            .state_by_block_hash(parent_hash)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+        let executor = this.executor.executor(StateProviderDatabase::new(&state));
+        let BlockExecutionOutput { state: bundle_state, receipts, requests, gas_used } =
+            match executor.execute((&block, U256::MAX).into()) {
+                Ok(output) => output,
+                Err(err) => return Ok(PayloadDryRunResult::invalid(err)),
+            };
+
+        if let Err(err) = this
+            .consensus
+            .validate_block_post_execution(&block, PostExecutionInput::new(&receipts, &requests))
+        {
+            return Ok(PayloadDryRunResult::invalid(err).with_gas_used(gas_used))
+        }
+
+        let state_root = state
+            .state_root(&bundle_state)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+        if state_root != block.state_root {
+            let err = ConsensusError::BodyStateRootDiff(
+                GotExpected { got: state_root, expected: block.state_root }.into(),
+            );
+            return Ok(PayloadDryRunResult::invalid(err)
+                .with_gas_used(gas_used)
+                .with_state_root(state_root))
+        }
+
+        Ok(PayloadDryRunResult::valid(gas_used, state_root))
+    }
+}
+
+#[async_trait]
+impl<Provider, Executor> EngineDryRunApiServer for EngineDryRun<Provider, Executor>
+where
+    Provider: BlockReader + StateProviderFactory + 'static,
+    Executor: BlockExecutorProvider,
+{
+    /// Handler for `engine_dryRunPayload`
+    async fn dry_run_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+    ) -> RpcResult<PayloadDryRunResult> {
+        trace!(target: "rpc::engine", "Serving engine_dryRunPayload");
+        let cancun_fields = match (versioned_hashes, parent_beacon_block_root) {
+            (Some(versioned_hashes), Some(parent_beacon_block_root)) => {
+                Some(CancunPayloadFields { versioned_hashes, parent_beacon_block_root })
+            }
+            (None, None) => None,
+            _ => return Err(EngineApiError::IncompleteCancunFields.into()),
+        };
+
+        // executing the payload is blocking
+        let this = self.clone();
+        let (tx, rx) = oneshot::channel();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(this.dry_run(payload, cancun_fields));
+        }));
+        let res = rx.await.map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+        Ok(res?)
+    }
+}
+
+impl<Provider, Executor> Clone for EngineDryRun<Provider, Executor> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Provider, Executor> std::fmt::Debug for EngineDryRun<Provider, Executor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EngineDryRun").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_chainspec::MAINNET;
+    use reth_consensus::test_utils::TestConsensus;
+    use reth_evm::test_utils::MockExecutorProvider;
+    use reth_primitives::{
+        constants::EMPTY_ROOT_HASH, Block, Header, SealedBlock, EMPTY_OMMER_ROOT_HASH,
+    };
+    use reth_provider::{test_utils::MockEthProvider, ExecutionOutcome};
+    use reth_rpc_types::engine::PayloadStatusEnum;
+    use reth_rpc_types_compat::engine::payload::block_to_payload_v1;
+    use reth_tasks::TokioTaskExecutor;
+
+    const PARENT_HASH: B256 = B256::with_last_byte(1);
+
+    fn setup(
+        consensus: TestConsensus,
+    ) -> (EngineDryRun<MockEthProvider, MockExecutorProvider>, MockExecutorProvider) {
+        let provider = MockEthProvider::default();
+        provider.add_block(PARENT_HASH, Block::default());
+        let executor = MockExecutorProvider::default();
+        let dry_run = EngineDryRun::new(
+            provider,
+            MAINNET.clone(),
+            executor.clone(),
+            Arc::new(consensus),
+            Box::<TokioTaskExecutor>::default(),
+        );
+        (dry_run, executor)
+    }
+
+    fn payload(parent_hash: B256, state_root: B256) -> ExecutionPayload {
+        let header = Header {
+            parent_hash,
+            number: 1,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            transactions_root: EMPTY_ROOT_HASH,
+            state_root,
+            base_fee_per_gas: Some(7),
+            ..Default::default()
+        };
+        let block = SealedBlock::new(header.seal_slow(), Default::default());
+        ExecutionPayload::V1(block_to_payload_v1(block))
+    }
+
+    #[test]
+    fn dry_run_valid_payload() {
+        let (dry_run, executor) = setup(TestConsensus::default());
+        executor.extend([ExecutionOutcome::default()]);
+
+        // the mock provider computes a zero state root
+        let res = dry_run.dry_run(payload(PARENT_HASH, B256::ZERO), None).unwrap();
+        assert_eq!(res, PayloadDryRunResult::valid(0, B256::ZERO));
+    }
+
+    #[test]
+    fn dry_run_state_root_mismatch() {
+        let (dry_run, executor) = setup(TestConsensus::default());
+        executor.extend([ExecutionOutcome::default()]);
+
+        let res = dry_run.dry_run(payload(PARENT_HASH, B256::with_last_byte(2)), None).unwrap();
+        assert_matches!(res.status, PayloadStatusEnum::Invalid { .. });
+        assert_eq!(res.gas_used, Some(0));
+        assert_eq!(res.state_root, Some(B256::ZERO));
+    }
+
+    #[test]
+    fn dry_run_invalid_payload() {
+        let consensus = TestConsensus::default();
+        consensus.set_fail_validation(true);
+        let (dry_run, _) = setup(consensus);
+
+        // the payload is not executed if it violates the consensus rules
+        let res = dry_run.dry_run(payload(PARENT_HASH, B256::ZERO), None).unwrap();
+        assert_matches!(res.status, PayloadStatusEnum::Invalid { .. });
+        assert_eq!(res.gas_used, None);
+
+        let mut malformed = payload(PARENT_HASH, B256::ZERO);
+        if let ExecutionPayload::V1(payload) = &mut malformed {
+            payload.block_hash = B256::with_last_byte(3);
+        }
+        let res = dry_run.dry_run(malformed, None).unwrap();
+        assert_matches!(res.status, PayloadStatusEnum::Invalid { .. });
+    }
+
+    #[test]
+    fn dry_run_unknown_parent() {
+        let (dry_run, _) = setup(TestConsensus::default());
+
+        let parent_hash = B256::with_last_byte(4);
+        assert_matches!(
+            dry_run.dry_run(payload(parent_hash, B256::ZERO), None),
+            Err(EngineApiError::UnknownParent(hash)) if hash == parent_hash
+        );
+    }
+}
@@ -1,45 +1,87 @@
-use crate::{metrics::EngineApiMetrics, EngineApiError, EngineApiResult};
+use crate::{
+    external_payload::ensure_valid_status,
+    metrics::EngineApiMetrics,
+    payload_bodies::{payload_body_size, PAYLOAD_BODIES_CHUNK_SIZE},
+    EngineApiError, EngineApiResult, EngineCapabilities, EngineCapabilitiesTracker,
+    ExternalPayloadBuilder, ExternalPayloadError, PayloadBodiesLimits, SlowCallThresholds,
+    DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT, DEFAULT_EXTERNAL_PAYLOAD_VALIDATION_TIMEOUT,
+};
 use async_trait::async_trait;
 use jsonrpsee_core::RpcResult;
+use parking_lot::Mutex;
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_chainspec::ChainSpec;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::provider::EvmEnvProvider;
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, PayloadAttributes,
-    PayloadBuilderAttributes, PayloadOrAttributes,
+    ensure_matches_attributes, validate_payload_timestamp, BuiltPayload, EngineApiMessageVersion,
+    MostValuable, PayloadAttributes, PayloadBuilderAttributes, PayloadOrAttributes,
+    PayloadSelectionPolicy, PayloadSource,
+};
+use reth_primitives::{
+    BlockHash, BlockHashOrNumber, BlockNumber, Hardfork, SealedBlock, B256, U64,
 };
-use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, Hardfork, B256, U64};
 use reth_rpc_api::EngineApiServer;
-use reth_rpc_types::engine::{
-    CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
-    ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
-    ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, TransitionConfiguration,
-    CAPABILITIES,
+use reth_rpc_server_types::BlobAvailabilityCheck;
+use reth_rpc_types::{
+    engine::{
+        CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
+        ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
+        ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus, PayloadStatusEnum,
+        TransitionConfiguration,
+    },
+    BlobAndProofV1, PayloadBuildTimings,
 };
 use reth_rpc_types_compat::engine::payload::{
-    convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
+    block_to_payload, convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
 };
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::TaskSpawner;
-use std::{sync::Arc, time::Instant};
+use reth_transaction_pool::TransactionPool;
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::oneshot;
-use tracing::{trace, warn};
+use tracing::{debug, instrument, trace, warn};
 
 /// The Engine API response sender.
 pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 
-/// The upper limit for payload bodies request.
-const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
+/// The upper limit for blobs in `engine_getBlobsV1`.
+const MAX_BLOB_LIMIT: usize = 128;
+
+/// The number of recently returned payloads whose build timings are retained.
+const MAX_RETAINED_BUILD_TIMINGS: usize = 64;
 
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
-pub struct EngineApi<Provider, EngineT: EngineTypes> {
-    inner: Arc<EngineApiInner<Provider, EngineT>>,
+pub struct EngineApi<Provider, EngineT: EngineTypes, Pool> {
+    inner: Arc<EngineApiInner<Provider, EngineT, Pool>>,
+    /// The durations above which calls are slow
+    slow_call_thresholds: SlowCallThresholds,
+    /// The external builder that is asked for payloads in `engine_getPayload`, if any
+    external_builder: Option<Arc<dyn ExternalPayloadBuilder<EngineT>>>,
+    /// Selects between the local payload and the payload of the external builder
+    external_payload_policy: Arc<dyn PayloadSelectionPolicy<EngineT::BuiltPayload>>,
+    /// How long to wait for the payload of the external builder
+    external_payload_timeout: Duration,
+    /// How long to wait for the payload of the external builder to be executed
+    external_payload_validation_timeout: Duration,
+    /// The limits of payload bodies requests
+    payload_bodies_limits: PayloadBodiesLimits,
+    /// The advertised methods, and the methods that the consensus layer supports and calls
+    capabilities: EngineCapabilitiesTracker,
+    /// Whether the build timings of returned payloads are retained for
+    /// `engine_getPayloadBuildTimingsV1`
+    payload_build_timings: bool,
+    /// How strictly the blobs of new payloads are checked to be available locally
+    blob_availability: BlobAvailabilityCheck,
 }
 
-struct EngineApiInner<Provider, EngineT: EngineTypes> {
+struct EngineApiInner<Provider, EngineT: EngineTypes, Pool> {
     /// The provider to interact with the chain.
     provider: Provider,
     /// Consensus configuration
@@ -48,18 +90,23 @@ struct EngineApiInner<Provider, EngineT: EngineTypes> {
     beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
     /// The type that can communicate with the payload service to retrieve payloads.
     payload_store: PayloadStore<EngineT>,
+    /// The transaction pool, whose blob store serves `engine_getBlobs`.
+    tx_pool: Pool,
     /// For spawning and executing async tasks
     task_spawner: Box<dyn TaskSpawner>,
     /// The latency and response type metrics for engine api calls
     metrics: EngineApiMetrics,
     /// Identification of the execution client used by the consensus client
     client: ClientVersionV1,
+    /// The build timings of the most recently returned payloads, oldest first
+    build_timings: Mutex<VecDeque<(PayloadId, PayloadBuildTimings)>>,
 }
 
-impl<Provider, EngineT> EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Create new instance of [`EngineApi`].
     pub fn new(
@@ -67,6 +114,7 @@ where
         chain_spec: Arc<ChainSpec>,
         beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
         payload_store: PayloadStore<EngineT>,
+        tx_pool: Pool,
         task_spawner: Box<dyn TaskSpawner>,
         client: ClientVersionV1,
     ) -> Self {
@@ -75,11 +123,156 @@ where
             chain_spec,
             beacon_consensus,
             payload_store,
+            tx_pool,
             task_spawner,
             metrics: EngineApiMetrics::default(),
             client,
+            build_timings: Mutex::new(VecDeque::with_capacity(MAX_RETAINED_BUILD_TIMINGS)),
         });
-        Self { inner }
+        Self {
+            inner,
+            slow_call_thresholds: SlowCallThresholds::default(),
+            external_builder: None,
+            external_payload_policy: Arc::new(MostValuable),
+            external_payload_timeout: DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT,
+            external_payload_validation_timeout: DEFAULT_EXTERNAL_PAYLOAD_VALIDATION_TIMEOUT,
+            payload_bodies_limits: PayloadBodiesLimits::default(),
+            capabilities: EngineCapabilitiesTracker::new(EngineCapabilities::default()),
+            payload_build_timings: false,
+            blob_availability: BlobAvailabilityCheck::Off,
+        }
+    }
+
+    /// Sets the methods that are advertised in `engine_exchangeCapabilities`.
+    ///
+    /// Calls of methods that are not advertised are still served, but are logged and recorded in
+    /// metrics.
+    pub fn with_capabilities(mut self, capabilities: EngineCapabilities) -> Self {
+        self.capabilities = EngineCapabilitiesTracker::new(capabilities);
+        self
+    }
+
+    /// Sets whether the build timings of the payloads returned by `engine_getPayload` are retained,
+    /// so that they can be queried with `engine_getPayloadBuildTimingsV1`.
+    ///
+    /// The timings are recorded in metrics regardless.
+    pub const fn with_payload_build_timings(mut self, enabled: bool) -> Self {
+        self.payload_build_timings = enabled;
+        self
+    }
+
+    /// Sets how strictly `engine_newPayloadV3` and later check that the blobs of the payload are
+    /// available in the blob store of the pool.
+    pub const fn with_blob_availability_check(mut self, check: BlobAvailabilityCheck) -> Self {
+        self.blob_availability = check;
+        self
+    }
+
+    /// Returns the tracker of the methods that the consensus layer supports and calls, which serves
+    /// `admin_engineCapabilities`.
+    pub fn capabilities_tracker(&self) -> EngineCapabilitiesTracker {
+        self.capabilities.clone()
+    }
+
+    /// Sets the durations above which calls are logged as slow.
+    pub const fn with_slow_call_thresholds(mut self, thresholds: SlowCallThresholds) -> Self {
+        self.slow_call_thresholds = thresholds;
+        self
+    }
+
+    /// Sets the external builder that is asked for a payload in every `engine_getPayload` call.
+    ///
+    /// See [`ExternalPayloadBuilder`] for when its payload is returned instead of the local one.
+    pub fn with_external_payload_builder(
+        mut self,
+        builder: Arc<dyn ExternalPayloadBuilder<EngineT>>,
+    ) -> Self {
+        self.external_builder = Some(builder);
+        self
+    }
+
+    /// Sets the limits of `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`.
+    pub const fn with_payload_bodies_limits(mut self, limits: PayloadBodiesLimits) -> Self {
+        self.payload_bodies_limits = limits;
+        self
+    }
+
+    /// Sets the policy that selects between the local payload and the payload of the external
+    /// builder.
+    ///
+    /// Defaults to [`MostValuable`].
+    pub fn with_external_payload_policy(
+        mut self,
+        policy: Arc<dyn PayloadSelectionPolicy<EngineT::BuiltPayload>>,
+    ) -> Self {
+        self.external_payload_policy = policy;
+        self
+    }
+
+    /// Sets how long `engine_getPayload` waits for the payload of the external builder.
+    pub const fn with_external_payload_timeout(mut self, timeout: Duration) -> Self {
+        self.external_payload_timeout = timeout;
+        self
+    }
+
+    /// Sets how long `engine_getPayload` waits for the payload of the external builder to be
+    /// executed, before the local payload is returned instead.
+    pub const fn with_external_payload_validation_timeout(mut self, timeout: Duration) -> Self {
+        self.external_payload_validation_timeout = timeout;
+        self
+    }
+
+    /// Warns if an `engine_newPayload` call for the given block took longer than its threshold.
+    fn on_new_payload_elapsed(
+        &self,
+        method: &'static str,
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+        elapsed: Duration,
+    ) {
+        if elapsed > self.slow_call_thresholds.new_payload {
+            self.inner.metrics.slow_calls.new_payload_slow_calls.increment(1);
+            warn!(
+                target: "rpc::engine",
+                method,
+                block_number,
+                %block_hash,
+                ?elapsed,
+                "Slow engine API call"
+            );
+        }
+    }
+
+    /// Warns if an `engine_forkchoiceUpdated` call took longer than its threshold.
+    fn on_fork_choice_updated_elapsed(
+        &self,
+        method: &'static str,
+        head_block_hash: BlockHash,
+        elapsed: Duration,
+    ) {
+        if elapsed > self.slow_call_thresholds.fork_choice_updated {
+            self.inner.metrics.slow_calls.forkchoice_updated_slow_calls.increment(1);
+            warn!(
+                target: "rpc::engine",
+                method,
+                %head_block_hash,
+                ?elapsed,
+                "Slow engine API call"
+            );
+        }
+    }
+
+    /// Warns if an `engine_getPayload` call took longer than its threshold.
+    fn on_get_payload_elapsed(
+        &self,
+        method: &'static str,
+        payload_id: PayloadId,
+        elapsed: Duration,
+    ) {
+        if elapsed > self.slow_call_thresholds.get_payload {
+            self.inner.metrics.slow_calls.get_payload_slow_calls.increment(1);
+            warn!(target: "rpc::engine", method, %payload_id, ?elapsed, "Slow engine API call");
+        }
     }
 
     /// Fetches the client version.
@@ -89,6 +282,165 @@ where
     ) -> EngineApiResult<Vec<ClientVersionV1>> {
         Ok(vec![self.inner.client.clone()])
     }
+    /// Resolves the payload with the given id.
+    ///
+    /// If an external builder is configured, its payload is returned instead of the payload of the
+    /// local builder if it is valid and selected by the policy.
+    async fn resolve_payload(
+        &self,
+        payload_id: PayloadId,
+    ) -> EngineApiResult<EngineT::BuiltPayload> {
+        let Some(external_builder) = &self.external_builder else {
+            return self.resolve_local_payload(payload_id).await
+        };
+
+        // the attributes are gone once the local payload is resolved
+        let attributes = self.get_payload_attributes(payload_id).await?;
+        let external = tokio::time::timeout(
+            self.external_payload_timeout,
+            external_builder.get_payload(payload_id, &attributes),
+        );
+        let (local, external) = tokio::join!(self.resolve_local_payload(payload_id), external);
+        let local = local?;
+
+        let external = match external {
+            Ok(Ok(Some(external))) => external,
+            Ok(Ok(None)) => {
+                self.inner.metrics.external_payloads.local_payloads_selected.increment(1);
+                return Ok(local)
+            }
+            Ok(Err(err)) => return Ok(self.on_external_payload_error(local, payload_id, err)),
+            Err(_) => {
+                return Ok(self.on_external_payload_error(
+                    local,
+                    payload_id,
+                    ExternalPayloadError::Timeout,
+                ))
+            }
+        };
+
+        if self.external_payload_policy.select(&local, &external) == PayloadSource::Local {
+            debug!(
+                target: "rpc::engine",
+                %payload_id,
+                external_fees=%external.fees(),
+                local_fees=%local.fees(),
+                "Local payload selected over the external payload"
+            );
+            self.inner.metrics.external_payloads.local_payloads_selected.increment(1);
+            return Ok(local)
+        }
+
+        let validation = tokio::time::timeout(
+            self.external_payload_validation_timeout,
+            self.validate_external_payload(external.block(), &attributes),
+        );
+        match validation.await.unwrap_or(Err(ExternalPayloadError::Timeout)) {
+            Ok(()) => {
+                debug!(
+                    target: "rpc::engine",
+                    %payload_id,
+                    block_hash=%external.block().hash(),
+                    "Returning external payload"
+                );
+                self.inner.metrics.external_payloads.external_payloads_selected.increment(1);
+                Ok(external)
+            }
+            Err(err) => Ok(self.on_external_payload_error(local, payload_id, err)),
+        }
+    }
+
+    /// Converts the resolved payload into an `engine_getPayload` response, and records how long it
+    /// took to build.
+    ///
+    /// The build timings are retained for `engine_getPayloadBuildTimingsV1` if enabled.
+    fn payload_response<T>(
+        &self,
+        payload_id: PayloadId,
+        payload: EngineT::BuiltPayload,
+        kind: &'static str,
+    ) -> EngineApiResult<T>
+    where
+        EngineT::BuiltPayload: TryInto<T>,
+    {
+        let timings = payload.timings();
+        let envelope = payload.try_into().map_err(|_| {
+            warn!("could not transform built payload into {kind}");
+            EngineApiError::UnknownPayload
+        })?;
+        if let Some(timings) = timings {
+            self.inner.metrics.build_timings.record(&timings);
+            if self.payload_build_timings {
+                let mut retained = self.inner.build_timings.lock();
+                retained.retain(|(id, _)| *id != payload_id);
+                if retained.len() == MAX_RETAINED_BUILD_TIMINGS {
+                    retained.pop_front();
+                }
+                retained.push_back((payload_id, timings));
+            }
+        }
+        Ok(envelope)
+    }
+
+    /// Returns the build timings of a recently returned payload, if they were retained.
+    pub fn get_payload_build_timings(&self, payload_id: PayloadId) -> Option<PayloadBuildTimings> {
+        self.inner
+            .build_timings
+            .lock()
+            .iter()
+            .find_map(|(id, timings)| (*id == payload_id).then_some(*timings))
+    }
+
+    /// Resolves the payload of the local builder with the given id.
+    async fn resolve_local_payload(
+        &self,
+        payload_id: PayloadId,
+    ) -> EngineApiResult<EngineT::BuiltPayload> {
+        self.inner
+            .payload_store
+            .resolve(payload_id)
+            .await
+            .ok_or(EngineApiError::UnknownPayload)?
+            .map_err(|_| EngineApiError::UnknownPayload)
+    }
+
+    /// Ensures that the block of an external payload matches the attributes of the payload job,
+    /// and executes it.
+    async fn validate_external_payload(
+        &self,
+        block: &SealedBlock,
+        attributes: &EngineT::PayloadBuilderAttributes,
+    ) -> Result<(), ExternalPayloadError> {
+        ensure_matches_attributes(block, attributes)?;
+
+        let block_hash = block.hash();
+        let versioned_hashes = block.blob_versioned_hashes_iter().copied().collect();
+        let (payload, parent_beacon_block_root) = block_to_payload(block.clone());
+        let cancun_fields = parent_beacon_block_root.map(|parent_beacon_block_root| {
+            CancunPayloadFields { parent_beacon_block_root, versioned_hashes }
+        });
+        let status = self
+            .inner
+            .beacon_consensus
+            .new_payload(payload, cancun_fields)
+            .await
+            .map_err(ExternalPayloadError::validation)?;
+        ensure_valid_status(block_hash, status.status)
+    }
+
+    /// Falls back to the local payload if the external payload is not available or invalid.
+    fn on_external_payload_error(
+        &self,
+        local: EngineT::BuiltPayload,
+        payload_id: PayloadId,
+        err: ExternalPayloadError,
+    ) -> EngineT::BuiltPayload {
+        warn!(target: "rpc::engine", %payload_id, %err, "Falling back to local payload");
+        self.inner.metrics.external_payloads.external_payload_errors.increment(1);
+        self.inner.metrics.external_payloads.local_payloads_selected.increment(1);
+        local
+    }
+
     /// Fetches the attributes for the payload with the given id.
     async fn get_payload_attributes(
         &self,
@@ -104,6 +456,13 @@ where
 
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
+    #[instrument(
+        level = "trace",
+        name = "engine_newPayloadV1",
+        skip_all,
+        fields(block_number = payload.block_number, block_hash = %payload.block_hash),
+        target = "rpc::engine"
+    )]
     pub async fn new_payload_v1(
         &self,
         payload: ExecutionPayloadV1,
@@ -122,6 +481,16 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
+    #[instrument(
+        level = "trace",
+        name = "engine_newPayloadV2",
+        skip_all,
+        fields(
+            block_number = payload.execution_payload.block_number,
+            block_hash = %payload.execution_payload.block_hash,
+        ),
+        target = "rpc::engine"
+    )]
     pub async fn new_payload_v2(
         &self,
         payload: ExecutionPayloadInputV2,
@@ -140,6 +509,16 @@ where
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+    #[instrument(
+        level = "trace",
+        name = "engine_newPayloadV3",
+        skip_all,
+        fields(
+            block_number = payload.payload_inner.payload_inner.block_number,
+            block_hash = %payload.payload_inner.payload_inner.block_hash,
+        ),
+        target = "rpc::engine"
+    )]
     pub async fn new_payload_v3(
         &self,
         payload: ExecutionPayloadV3,
@@ -158,12 +537,28 @@ where
             payload_or_attrs,
         )?;
 
+        let block_hash = payload.block_hash();
+        let blob_check = self.blob_availability.is_enabled().then(|| versioned_hashes.clone());
         let cancun_fields = CancunPayloadFields { versioned_hashes, parent_beacon_block_root };
 
-        Ok(self.inner.beacon_consensus.new_payload(payload, Some(cancun_fields)).await?)
+        let status = self.inner.beacon_consensus.new_payload(payload, Some(cancun_fields)).await?;
+        if let Some(versioned_hashes) = blob_check {
+            self.spawn_blob_availability_check(&status, block_hash, versioned_hashes);
+        }
+        Ok(status)
     }
 
     /// See also <https://github.com/ethereum/execution-apis/blob/7907424db935b93c2fe6a3c0faab943adebe8557/src/engine/prague.md#engine_newpayloadv4>
+    #[instrument(
+        level = "trace",
+        name = "engine_newPayloadV4",
+        skip_all,
+        fields(
+            block_number = payload.payload_inner.payload_inner.payload_inner.block_number,
+            block_hash = %payload.payload_inner.payload_inner.payload_inner.block_hash,
+        ),
+        target = "rpc::engine"
+    )]
     pub async fn new_payload_v4(
         &self,
         payload: ExecutionPayloadV4,
@@ -182,9 +577,15 @@ where
             payload_or_attrs,
         )?;
 
+        let block_hash = payload.block_hash();
+        let blob_check = self.blob_availability.is_enabled().then(|| versioned_hashes.clone());
         let cancun_fields = CancunPayloadFields { versioned_hashes, parent_beacon_block_root };
 
-        Ok(self.inner.beacon_consensus.new_payload(payload, Some(cancun_fields)).await?)
+        let status = self.inner.beacon_consensus.new_payload(payload, Some(cancun_fields)).await?;
+        if let Some(versioned_hashes) = blob_check {
+            self.spawn_blob_availability_check(&status, block_hash, versioned_hashes);
+        }
+        Ok(status)
     }
 
     /// Sends a message to the beacon consensus engine to update the fork choice _without_
@@ -237,21 +638,13 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "trace", name = "engine_getPayloadV1", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v1(
         &self,
         payload_id: PayloadId,
     ) -> EngineApiResult<EngineT::ExecutionPayloadV1> {
-        self.inner
-            .payload_store
-            .resolve(payload_id)
-            .await
-            .ok_or(EngineApiError::UnknownPayload)?
-            .map_err(|_| EngineApiError::UnknownPayload)?
-            .try_into()
-            .map_err(|_| {
-                warn!("could not transform built payload into ExecutionPayloadV1");
-                EngineApiError::UnknownPayload
-            })
+        let payload = self.resolve_payload(payload_id).await?;
+        self.payload_response(payload_id, payload, "ExecutionPayloadV1")
     }
 
     /// Returns the most recent version of the payload that is available in the corresponding
@@ -261,6 +654,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "trace", name = "engine_getPayloadV2", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v2(
         &self,
         payload_id: PayloadId,
@@ -276,17 +670,8 @@ where
         )?;
 
         // Now resolve the payload
-        self.inner
-            .payload_store
-            .resolve(payload_id)
-            .await
-            .ok_or(EngineApiError::UnknownPayload)?
-            .map_err(|_| EngineApiError::UnknownPayload)?
-            .try_into()
-            .map_err(|_| {
-                warn!("could not transform built payload into ExecutionPayloadV2");
-                EngineApiError::UnknownPayload
-            })
+        let payload = self.resolve_payload(payload_id).await?;
+        self.payload_response(payload_id, payload, "ExecutionPayloadV2")
     }
 
     /// Returns the most recent version of the payload that is available in the corresponding
@@ -296,6 +681,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "trace", name = "engine_getPayloadV3", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v3(
         &self,
         payload_id: PayloadId,
@@ -311,17 +697,8 @@ where
         )?;
 
         // Now resolve the payload
-        self.inner
-            .payload_store
-            .resolve(payload_id)
-            .await
-            .ok_or(EngineApiError::UnknownPayload)?
-            .map_err(|_| EngineApiError::UnknownPayload)?
-            .try_into()
-            .map_err(|_| {
-                warn!("could not transform built payload into ExecutionPayloadV3");
-                EngineApiError::UnknownPayload
-            })
+        let payload = self.resolve_payload(payload_id).await?;
+        self.payload_response(payload_id, payload, "ExecutionPayloadV3")
     }
 
     /// Returns the most recent version of the payload that is available in the corresponding
@@ -331,6 +708,7 @@ where
     ///
     /// Note:
     /// > Provider software MAY stop the corresponding build process after serving this call.
+    #[instrument(level = "trace", name = "engine_getPayloadV4", skip(self), target = "rpc::engine")]
     pub async fn get_payload_v4(
         &self,
         payload_id: PayloadId,
@@ -346,17 +724,8 @@ where
         )?;
 
         // Now resolve the payload
-        self.inner
-            .payload_store
-            .resolve(payload_id)
-            .await
-            .ok_or(EngineApiError::UnknownPayload)?
-            .map_err(|_| EngineApiError::UnknownPayload)?
-            .try_into()
-            .map_err(|_| {
-                warn!("could not transform built payload into ExecutionPayloadV4");
-                EngineApiError::UnknownPayload
-            })
+        let payload = self.resolve_payload(payload_id).await?;
+        self.payload_response(payload_id, payload, "ExecutionPayloadV4")
     }
 
     /// Returns the execution payload bodies by the range starting at `start`, containing `count`
@@ -369,6 +738,9 @@ where
     /// Implementers should take care when acting on the input to this method, specifically
     /// ensuring that the range is limited properly, and that the range boundaries are computed
     /// correctly and without panics.
+    ///
+    /// The blocks are read in chunks, so that only the bodies and a single chunk of blocks are held
+    /// in memory, and the request is aborted as soon as the bodies exceed the size limit.
     pub async fn get_payload_bodies_by_range(
         &self,
         start: BlockNumber,
@@ -376,9 +748,10 @@ where
     ) -> EngineApiResult<ExecutionPayloadBodiesV1> {
         let (tx, rx) = oneshot::channel();
         let inner = self.inner.clone();
+        let limits = self.payload_bodies_limits;
 
         self.inner.task_spawner.spawn_blocking(Box::pin(async move {
-            if count > MAX_PAYLOAD_BODIES_LIMIT {
+            if count > limits.max_count {
                 tx.send(Err(EngineApiError::PayloadRequestTooLarge { len: count })).ok();
                 return;
             }
@@ -401,17 +774,38 @@ where
                 }
             }
 
-            for num in start..=end {
-                let block_result = inner.provider.block(BlockHashOrNumber::Number(num));
-                match block_result {
-                    Ok(block) => {
-                        result.push(block.map(convert_to_payload_body_v1));
-                    }
+            let mut size = 0usize;
+            let mut chunk_start = start;
+            while chunk_start <= end {
+                let chunk_end = end.min(chunk_start.saturating_add(PAYLOAD_BODIES_CHUNK_SIZE - 1));
+                let blocks = match inner.provider.block_range(chunk_start..=chunk_end) {
+                    Ok(blocks) => blocks,
                     Err(err) => {
                         tx.send(Err(EngineApiError::Internal(Box::new(err)))).ok();
                         return;
                     }
                 };
+
+                // blocks that are not available are missing from the chunk, and returned as null
+                let mut blocks = blocks.into_iter().peekable();
+                for num in chunk_start..=chunk_end {
+                    let block = blocks.next_if(|block| block.number == num);
+                    let body = block.map(convert_to_payload_body_v1);
+                    size = size.saturating_add(body.as_ref().map_or(0, payload_body_size));
+                    if size > limits.max_size {
+                        tx.send(Err(EngineApiError::PayloadBodiesTooLarge {
+                            limit: limits.max_size,
+                        }))
+                        .ok();
+                        return;
+                    }
+                    result.push(body);
+                }
+
+                if chunk_end == end {
+                    break
+                }
+                chunk_start = chunk_end + 1;
             }
             tx.send(Ok(result)).ok();
         }));
@@ -424,24 +818,70 @@ where
         &self,
         hashes: Vec<BlockHash>,
     ) -> EngineApiResult<ExecutionPayloadBodiesV1> {
+        let limits = self.payload_bodies_limits;
         let len = hashes.len() as u64;
-        if len > MAX_PAYLOAD_BODIES_LIMIT {
+        if len > limits.max_count {
             return Err(EngineApiError::PayloadRequestTooLarge { len })
         }
 
         let mut result = Vec::with_capacity(hashes.len());
+        let mut size = 0usize;
         for hash in hashes {
             let block = self
                 .inner
                 .provider
                 .block(BlockHashOrNumber::Hash(hash))
                 .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
-            result.push(block.map(convert_to_payload_body_v1));
+            let body = block.map(convert_to_payload_body_v1);
+            size = size.saturating_add(body.as_ref().map_or(0, payload_body_size));
+            if size > limits.max_size {
+                return Err(EngineApiError::PayloadBodiesTooLarge { limit: limits.max_size })
+            }
+            result.push(body);
         }
 
         Ok(result)
     }
 
+    /// Called to retrieve blobs by their versioned hashes from the blob store of the pool.
+    ///
+    /// Blobs that are not available are `None`.
+    pub fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        let len = versioned_hashes.len();
+        if len > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::PayloadRequestTooLarge { len: len as u64 })
+        }
+        self.inner.find_blobs(&versioned_hashes)
+    }
+
+    /// Checks in the background that the blobs of an accepted new payload are available in the
+    /// blob store of the pool, if enabled by the [`BlobAvailabilityCheck`].
+    ///
+    /// The check only runs for payloads that are `VALID` or `ACCEPTED`. Missing blobs are logged
+    /// and recorded in metrics, but never change the status of the payload, because this node may
+    /// simply not have received the blob transactions.
+    fn spawn_blob_availability_check(
+        &self,
+        status: &PayloadStatus,
+        block_hash: B256,
+        versioned_hashes: Vec<B256>,
+    ) {
+        if !self.blob_availability.is_enabled() ||
+            versioned_hashes.is_empty() ||
+            !matches!(status.status, PayloadStatusEnum::Valid | PayloadStatusEnum::Accepted)
+        {
+            return
+        }
+
+        let inner = self.inner.clone();
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            inner.check_blob_availability(block_hash, &versioned_hashes);
+        }));
+    }
+
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
     /// layers are using the latest configuration.
     pub async fn exchange_transition_configuration(
@@ -513,6 +953,13 @@ where
     ///
     /// * If the version above [`EngineApiMessageVersion::V3`], then the payload attributes will be
     ///   validated according to the Cancun rules.
+    #[instrument(
+        level = "trace",
+        name = "engine_forkchoiceUpdated",
+        skip(self, state, payload_attrs),
+        fields(head_block_hash = %state.head_block_hash, has_attributes = payload_attrs.is_some()),
+        target = "rpc::engine"
+    )]
     async fn validate_and_execute_forkchoice(
         &self,
         version: EngineApiMessageVersion,
@@ -552,21 +999,79 @@ where
 }
 
 #[async_trait]
-impl<Provider, EngineT> EngineApiServer<EngineT> for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApiInner<Provider, EngineT, Pool>
+where
+    EngineT: EngineTypes,
+    Pool: TransactionPool,
+{
+    /// Looks up the blobs of the given versioned hashes in the blob store of the pool.
+    ///
+    /// Blobs that are not available are `None`.
+    fn find_blobs(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        let blobs = self
+            .tx_pool
+            .get_blobs_for_versioned_hashes(versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+        Ok(blobs
+            .into_iter()
+            .map(|blob| blob.map(|blob| BlobAndProofV1 { blob: blob.blob, proof: blob.proof }))
+            .collect())
+    }
+
+    /// Looks up the blobs of the versioned hashes of a new payload, and logs and records the
+    /// blobs that are not available locally.
+    ///
+    /// Returns the versioned hashes of the missing blobs.
+    fn check_blob_availability(&self, block_hash: B256, versioned_hashes: &[B256]) -> Vec<B256> {
+        let blobs = match self.find_blobs(versioned_hashes) {
+            Ok(blobs) => blobs,
+            Err(error) => {
+                debug!(target: "rpc::engine", %block_hash, %error, "Failed to look up blobs");
+                return Vec::new()
+            }
+        };
+        let missing = versioned_hashes
+            .iter()
+            .zip(blobs)
+            .filter_map(|(versioned_hash, blob)| blob.is_none().then_some(*versioned_hash))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            let metrics = &self.metrics.blob_availability;
+            metrics.payloads_with_missing_blobs.increment(1);
+            metrics.missing_blobs.increment(missing.len() as u64);
+            warn!(
+                target: "rpc::engine",
+                %block_hash,
+                ?missing,
+                "Blobs of new payload are not available locally"
+            );
+        }
+        missing
+    }
+}
+
+impl<Provider, EngineT, Pool> EngineApiServer<EngineT> for EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `engine_newPayloadV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
     /// Caution: This should not accept the `withdrawals` field
     async fn new_payload_v1(&self, payload: ExecutionPayloadV1) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV1");
+        self.capabilities.on_call("engine_newPayloadV1");
         let start = Instant::now();
-        let gas_used = payload.gas_used;
+        let (gas_used, block_number, block_hash) =
+            (payload.gas_used, payload.block_number, payload.block_hash);
         let res = Self::new_payload_v1(self, payload).await;
         let elapsed = start.elapsed();
         self.inner.metrics.latency.new_payload_v1.record(elapsed);
+        self.on_new_payload_elapsed("engine_newPayloadV1", block_number, block_hash, elapsed);
         self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
         Ok(res?)
     }
@@ -575,11 +1080,15 @@ where
     /// See also <https://github.com/ethereum/execution-apis/blob/584905270d8ad665718058060267061ecfd79ca5/src/engine/shanghai.md#engine_newpayloadv2>
     async fn new_payload_v2(&self, payload: ExecutionPayloadInputV2) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV2");
+        self.capabilities.on_call("engine_newPayloadV2");
         let start = Instant::now();
-        let gas_used = payload.execution_payload.gas_used;
+        let inner = &payload.execution_payload;
+        let (gas_used, block_number, block_hash) =
+            (inner.gas_used, inner.block_number, inner.block_hash);
         let res = Self::new_payload_v2(self, payload).await;
         let elapsed = start.elapsed();
         self.inner.metrics.latency.new_payload_v2.record(elapsed);
+        self.on_new_payload_elapsed("engine_newPayloadV2", block_number, block_hash, elapsed);
         self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
         Ok(res?)
     }
@@ -593,12 +1102,16 @@ where
         parent_beacon_block_root: B256,
     ) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV3");
+        self.capabilities.on_call("engine_newPayloadV3");
         let start = Instant::now();
-        let gas_used = payload.payload_inner.payload_inner.gas_used;
+        let inner = &payload.payload_inner.payload_inner;
+        let (gas_used, block_number, block_hash) =
+            (inner.gas_used, inner.block_number, inner.block_hash);
         let res =
             Self::new_payload_v3(self, payload, versioned_hashes, parent_beacon_block_root).await;
         let elapsed = start.elapsed();
         self.inner.metrics.latency.new_payload_v3.record(elapsed);
+        self.on_new_payload_elapsed("engine_newPayloadV3", block_number, block_hash, elapsed);
         self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
         Ok(res?)
     }
@@ -612,12 +1125,16 @@ where
         parent_beacon_block_root: B256,
     ) -> RpcResult<PayloadStatus> {
         trace!(target: "rpc::engine", "Serving engine_newPayloadV4");
+        self.capabilities.on_call("engine_newPayloadV4");
         let start = Instant::now();
-        let gas_used = payload.payload_inner.payload_inner.payload_inner.gas_used;
+        let inner = &payload.payload_inner.payload_inner.payload_inner;
+        let (gas_used, block_number, block_hash) =
+            (inner.gas_used, inner.block_number, inner.block_hash);
         let res =
             Self::new_payload_v4(self, payload, versioned_hashes, parent_beacon_block_root).await;
         let elapsed = start.elapsed();
         self.inner.metrics.latency.new_payload_v4.record(elapsed);
+        self.on_new_payload_elapsed("engine_newPayloadV4", block_number, block_hash, elapsed);
         self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
         Ok(res?)
     }
@@ -632,9 +1149,13 @@ where
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV1");
+        self.capabilities.on_call("engine_forkchoiceUpdatedV1");
         let start = Instant::now();
+        let head_block_hash = fork_choice_state.head_block_hash;
         let res = Self::fork_choice_updated_v1(self, fork_choice_state, payload_attributes).await;
-        self.inner.metrics.latency.fork_choice_updated_v1.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.fork_choice_updated_v1.record(elapsed);
+        self.on_fork_choice_updated_elapsed("engine_forkchoiceUpdatedV1", head_block_hash, elapsed);
         self.inner.metrics.fcu_response.update_response_metrics(&res);
         Ok(res?)
     }
@@ -647,9 +1168,13 @@ where
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV2");
+        self.capabilities.on_call("engine_forkchoiceUpdatedV2");
         let start = Instant::now();
+        let head_block_hash = fork_choice_state.head_block_hash;
         let res = Self::fork_choice_updated_v2(self, fork_choice_state, payload_attributes).await;
-        self.inner.metrics.latency.fork_choice_updated_v2.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.fork_choice_updated_v2.record(elapsed);
+        self.on_fork_choice_updated_elapsed("engine_forkchoiceUpdatedV2", head_block_hash, elapsed);
         self.inner.metrics.fcu_response.update_response_metrics(&res);
         Ok(res?)
     }
@@ -663,9 +1188,13 @@ where
         payload_attributes: Option<EngineT::PayloadAttributes>,
     ) -> RpcResult<ForkchoiceUpdated> {
         trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedV3");
+        self.capabilities.on_call("engine_forkchoiceUpdatedV3");
         let start = Instant::now();
+        let head_block_hash = fork_choice_state.head_block_hash;
         let res = Self::fork_choice_updated_v3(self, fork_choice_state, payload_attributes).await;
-        self.inner.metrics.latency.fork_choice_updated_v3.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.fork_choice_updated_v3.record(elapsed);
+        self.on_fork_choice_updated_elapsed("engine_forkchoiceUpdatedV3", head_block_hash, elapsed);
         self.inner.metrics.fcu_response.update_response_metrics(&res);
         Ok(res?)
     }
@@ -686,9 +1215,12 @@ where
         payload_id: PayloadId,
     ) -> RpcResult<EngineT::ExecutionPayloadV1> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV1");
+        self.capabilities.on_call("engine_getPayloadV1");
         let start = Instant::now();
         let res = Self::get_payload_v1(self, payload_id).await;
-        self.inner.metrics.latency.get_payload_v1.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.get_payload_v1.record(elapsed);
+        self.on_get_payload_elapsed("engine_getPayloadV1", payload_id, elapsed);
         Ok(res?)
     }
 
@@ -706,9 +1238,12 @@ where
         payload_id: PayloadId,
     ) -> RpcResult<EngineT::ExecutionPayloadV2> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV2");
+        self.capabilities.on_call("engine_getPayloadV2");
         let start = Instant::now();
         let res = Self::get_payload_v2(self, payload_id).await;
-        self.inner.metrics.latency.get_payload_v2.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.get_payload_v2.record(elapsed);
+        self.on_get_payload_elapsed("engine_getPayloadV2", payload_id, elapsed);
         Ok(res?)
     }
 
@@ -726,9 +1261,12 @@ where
         payload_id: PayloadId,
     ) -> RpcResult<EngineT::ExecutionPayloadV3> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV3");
+        self.capabilities.on_call("engine_getPayloadV3");
         let start = Instant::now();
         let res = Self::get_payload_v3(self, payload_id).await;
-        self.inner.metrics.latency.get_payload_v3.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.get_payload_v3.record(elapsed);
+        self.on_get_payload_elapsed("engine_getPayloadV3", payload_id, elapsed);
         Ok(res?)
     }
 
@@ -746,12 +1284,24 @@ where
         payload_id: PayloadId,
     ) -> RpcResult<EngineT::ExecutionPayloadV4> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadV4");
+        self.capabilities.on_call("engine_getPayloadV4");
         let start = Instant::now();
         let res = Self::get_payload_v4(self, payload_id).await;
-        self.inner.metrics.latency.get_payload_v4.record(start.elapsed());
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.get_payload_v4.record(elapsed);
+        self.on_get_payload_elapsed("engine_getPayloadV4", payload_id, elapsed);
         Ok(res?)
     }
 
+    /// Handler for `engine_getPayloadBuildTimingsV1`
+    async fn get_payload_build_timings_v1(
+        &self,
+        payload_id: PayloadId,
+    ) -> RpcResult<Option<PayloadBuildTimings>> {
+        trace!(target: "rpc::engine", "Serving engine_getPayloadBuildTimingsV1");
+        Ok(self.get_payload_build_timings(payload_id))
+    }
+
     /// Handler for `engine_getPayloadBodiesByHashV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/shanghai.md#engine_getpayloadbodiesbyhashv1>
     async fn get_payload_bodies_by_hash_v1(
@@ -759,6 +1309,7 @@ where
         block_hashes: Vec<BlockHash>,
     ) -> RpcResult<ExecutionPayloadBodiesV1> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByHashV1");
+        self.capabilities.on_call("engine_getPayloadBodiesByHashV1");
         let start = Instant::now();
         let res = Self::get_payload_bodies_by_hash(self, block_hashes);
         self.inner.metrics.latency.get_payload_bodies_by_hash_v1.record(start.elapsed());
@@ -787,6 +1338,7 @@ where
         count: U64,
     ) -> RpcResult<ExecutionPayloadBodiesV1> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByRangeV1");
+        self.capabilities.on_call("engine_getPayloadBodiesByRangeV1");
         let start_time = Instant::now();
         let res = Self::get_payload_bodies_by_range(self, start.to(), count.to()).await;
         self.inner.metrics.latency.get_payload_bodies_by_range_v1.record(start_time.elapsed());
@@ -800,6 +1352,7 @@ where
         config: TransitionConfiguration,
     ) -> RpcResult<TransitionConfiguration> {
         trace!(target: "rpc::engine", "Serving engine_exchangeTransitionConfigurationV1");
+        self.capabilities.on_call("engine_exchangeTransitionConfigurationV1");
         let start = Instant::now();
         let res = Self::exchange_transition_configuration(self, config).await;
         self.inner.metrics.latency.exchange_transition_configuration.record(start.elapsed());
@@ -814,19 +1367,40 @@ where
         client: ClientVersionV1,
     ) -> RpcResult<Vec<ClientVersionV1>> {
         trace!(target: "rpc::engine", "Serving engine_getClientVersionV1");
+        self.capabilities.on_call("engine_getClientVersionV1");
+        let start = Instant::now();
         let res = Self::get_client_version_v1(self, client).await;
+        self.inner.metrics.latency.get_client_version_v1.record(start.elapsed());
 
         Ok(res?)
     }
 
+    /// Handler for `engine_getBlobsV1`
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV1");
+        self.capabilities.on_call("engine_getBlobsV1");
+        let start = Instant::now();
+        let res = Self::get_blobs_v1(self, versioned_hashes);
+        self.inner.metrics.latency.get_blobs_v1.record(start.elapsed());
+        Ok(res?)
+    }
+
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
-        Ok(CAPABILITIES.iter().cloned().map(str::to_owned).collect())
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> RpcResult<Vec<String>> {
+        let start = Instant::now();
+        let res = self.capabilities.on_exchange_capabilities(capabilities);
+        self.inner.metrics.latency.exchange_capabilities.record(start.elapsed());
+        Ok(res)
     }
 }
 
-impl<Provider, EngineT> std::fmt::Debug for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> std::fmt::Debug for EngineApi<Provider, EngineT, Pool>
 where
     EngineT: EngineTypes,
 {
@@ -840,20 +1414,22 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use reth_beacon_consensus::{BeaconConsensusEngineEvent, BeaconEngineMessage};
-    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use reth_ethereum_engine_primitives::{EthBuiltPayload, EthEngineTypes};
     use reth_testing_utils::generators::random_block;
 
     use reth_chainspec::MAINNET;
     use reth_payload_builder::test_utils::spawn_test_payload_service;
-    use reth_primitives::{SealedBlock, B256};
+    use reth_primitives::{Block, SealedBlock, B256, U256};
     use reth_provider::test_utils::MockEthProvider;
-    use reth_rpc_types::engine::{ClientCode, ClientVersionV1};
+    use reth_rpc_types::engine::{ClientCode, ClientVersionV1, ExecutionPayloadEnvelopeV2};
     use reth_rpc_types_compat::engine::payload::execution_payload_from_sealed_block;
     use reth_tasks::TokioTaskExecutor;
     use reth_tokio_util::EventSender;
+    use reth_transaction_pool::noop::NoopTransactionPool;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
-    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes>)
+    fn setup_engine_api(
+    ) -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes, NoopTransactionPool>)
     {
         let client = ClientVersionV1 {
             code: ClientCode::RH,
@@ -873,6 +1449,7 @@ mod tests {
             chain_spec.clone(),
             BeaconConsensusEngineHandle::new(to_engine, event_sender),
             payload_store.into(),
+            NoopTransactionPool::default(),
             task_executor,
             client,
         );
@@ -893,6 +1470,75 @@ mod tests {
         assert_eq!(res.unwrap(), vec![client]);
     }
 
+    #[tokio::test]
+    async fn engine_get_payload_build_timings() {
+        let (_, api) = setup_engine_api();
+        let timings = PayloadBuildTimings {
+            tx_selection: Duration::from_millis(1),
+            execution: Duration::from_millis(2),
+            root_computation: Duration::from_millis(3),
+        };
+        let payload_id = PayloadId::new([1; 8]);
+        let payload = EthBuiltPayload::new(payload_id, Block::default().seal_slow(), U256::ZERO)
+            .with_timings(timings);
+
+        // the response is the unchanged envelope, and the timings aren't retained by default
+        let _: ExecutionPayloadEnvelopeV2 =
+            api.payload_response(payload_id, payload.clone(), "ExecutionPayloadV2").unwrap();
+        assert_eq!(api.get_payload_build_timings(payload_id), None);
+
+        let api = api.with_payload_build_timings(true);
+        let _: ExecutionPayloadEnvelopeV2 =
+            api.payload_response(payload_id, payload.clone(), "ExecutionPayloadV2").unwrap();
+        let res = EngineApiServer::get_payload_build_timings_v1(&api, payload_id).await;
+        assert_eq!(res.unwrap(), Some(timings));
+        assert_eq!(api.get_payload_build_timings(PayloadId::new([2; 8])), None);
+
+        // only the timings of the most recent payloads are retained
+        for id in 0..MAX_RETAINED_BUILD_TIMINGS as u64 {
+            let payload_id = PayloadId::new(id.to_be_bytes());
+            let _: ExecutionPayloadEnvelopeV2 =
+                api.payload_response(payload_id, payload.clone(), "ExecutionPayloadV2").unwrap();
+        }
+        assert_eq!(api.get_payload_build_timings(payload_id), None);
+        assert_eq!(
+            api.get_payload_build_timings(PayloadId::new(63u64.to_be_bytes())),
+            Some(timings)
+        );
+    }
+
+    #[tokio::test]
+    async fn engine_get_blobs() {
+        let (_, api) = setup_engine_api();
+
+        let capabilities = EngineApiServer::exchange_capabilities(&api, vec![]).await.unwrap();
+        assert!(capabilities.iter().any(|method| method == "engine_getBlobsV1"));
+        assert!(!capabilities.iter().any(|method| method == "engine_getBlobsV2"));
+
+        let versioned_hashes = vec![B256::random(), B256::random()];
+        assert_eq!(api.get_blobs_v1(versioned_hashes).unwrap(), vec![None, None]);
+
+        let too_many = vec![B256::ZERO; MAX_BLOB_LIMIT + 1];
+        assert_matches!(
+            api.get_blobs_v1(too_many),
+            Err(EngineApiError::PayloadRequestTooLarge { len }) if len == MAX_BLOB_LIMIT as u64 + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn blob_availability_check() {
+        let (_, api) = setup_engine_api();
+        let block_hash = B256::random();
+        let versioned_hashes = vec![B256::random()];
+
+        // missing blobs are reported, but never turned into an error
+        assert!(api.inner.check_blob_availability(block_hash, &[]).is_empty());
+        assert_eq!(
+            api.inner.check_blob_availability(block_hash, &versioned_hashes),
+            versioned_hashes
+        );
+    }
+
     struct EngineApiTestHandle {
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
@@ -914,6 +1560,7 @@ mod tests {
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
+        use reth_rpc_server_types::constants::DEFAULT_MAX_PAYLOAD_BODIES;
         use reth_testing_utils::{generators, generators::random_block_range};
 
         #[tokio::test]
@@ -938,11 +1585,31 @@ mod tests {
         async fn request_too_large() {
             let (_, api) = setup_engine_api();
 
-            let request_count = MAX_PAYLOAD_BODIES_LIMIT + 1;
+            let request_count = DEFAULT_MAX_PAYLOAD_BODIES + 1;
             let res = api.get_payload_bodies_by_range(0, request_count).await;
             assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
         }
 
+        #[tokio::test]
+        async fn response_too_large() {
+            let mut rng = generators::rng();
+            let (handle, api) = setup_engine_api();
+            let api =
+                api.with_payload_bodies_limits(PayloadBodiesLimits::default().with_max_size(0));
+
+            let (start, count) = (1, 10);
+            let blocks =
+                random_block_range(&mut rng, start..=start + count - 1, B256::default(), 1..2);
+            handle.provider.extend_blocks(blocks.iter().cloned().map(|b| (b.hash(), b.unseal())));
+
+            let res = api.get_payload_bodies_by_range(start, count).await;
+            assert_matches!(res, Err(EngineApiError::PayloadBodiesTooLarge { limit: 0 }));
+
+            let hashes = blocks.iter().map(|b| b.hash()).collect();
+            let res = api.get_payload_bodies_by_hash(hashes);
+            assert_matches!(res, Err(EngineApiError::PayloadBodiesTooLarge { limit: 0 }));
+        }
+
         #[tokio::test]
         async fn returns_payload_bodies() {
             let mut rng = generators::rng();
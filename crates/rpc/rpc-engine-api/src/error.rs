@@ -42,6 +42,12 @@ pub enum EngineApiError {
         /// The length that was requested.
         len: u64,
     },
+    /// The payload bodies of a request exceed the maximum response size.
+    #[error("requested payload bodies exceed the size limit of {limit} bytes")]
+    PayloadBodiesTooLarge {
+        /// The maximum size of the payload bodies.
+        limit: usize,
+    },
     /// Thrown if `engine_getPayloadBodiesByRangeV1` contains an invalid range
     #[error("invalid start ({start}) or count ({count})")]
     InvalidBodiesRange {
@@ -84,6 +90,12 @@ pub enum EngineApiError {
     /// Fetching the payload failed
     #[error(transparent)]
     GetPayloadError(#[from] PayloadBuilderError),
+    /// The parent of a payload that should be dry-run is unknown.
+    #[error("unknown parent block {0}")]
+    UnknownParent(B256),
+    /// Only one of the versioned hashes and the parent beacon block root was provided.
+    #[error("versioned hashes and parent beacon block root must be provided together")]
+    IncompleteCancunFields,
     /// The payload or attributes are known to be malformed before processing.
     #[error(transparent)]
     EngineObjectValidationError(#[from] EngineObjectValidationError),
@@ -117,6 +129,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: EngineApiError) -> Self {
         match error {
             EngineApiError::InvalidBodiesRange { .. } |
+            EngineApiError::UnknownParent(_) |
+            EngineApiError::IncompleteCancunFields |
             EngineApiError::EngineObjectValidationError(EngineObjectValidationError::Payload(
                 _,
             )) |
@@ -147,7 +161,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 error.to_string(),
                 None::<()>,
             ),
-            EngineApiError::PayloadRequestTooLarge { .. } => {
+            EngineApiError::PayloadRequestTooLarge { .. } |
+            EngineApiError::PayloadBodiesTooLarge { .. } => {
                 jsonrpsee_types::error::ErrorObject::owned(
                     REQUEST_TOO_LARGE_CODE,
                     REQUEST_TOO_LARGE_MESSAGE,
@@ -242,6 +257,12 @@ mod tests {
             EngineApiError::PayloadRequestTooLarge { len: 0 },
         );
 
+        ensure_engine_rpc_error(
+            REQUEST_TOO_LARGE_CODE,
+            "Too large request",
+            EngineApiError::PayloadBodiesTooLarge { limit: 0 },
+        );
+
         ensure_engine_rpc_error(
             -38002,
             "Invalid forkchoice state",
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use jsonrpsee::{server::SubscriptionMessage, PendingSubscriptionSink, SubscriptionSink};
+use reth_beacon_consensus::BeaconConsensusEngineHandle;
+use reth_engine_primitives::EngineTypes;
+use reth_rpc_api::EngineEventsApiServer;
+use reth_rpc_types::EngineEvent;
+use reth_tasks::TaskSpawner;
+use tracing::debug;
+
+/// The `engine_subscribeEvents` subscription, which lets external monitoring observe the consensus
+/// engine over the auth server.
+pub struct EngineEvents<EngineT: EngineTypes> {
+    /// The handle to the consensus engine, whose events are emitted.
+    beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
+    /// The type that's used to spawn subscription tasks.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<EngineT: EngineTypes> EngineEvents<EngineT> {
+    /// Creates a new [`EngineEvents`] for the given consensus engine.
+    pub fn new(
+        beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { beacon_consensus, task_spawner }
+    }
+}
+
+#[async_trait]
+impl<EngineT: EngineTypes + 'static> EngineEventsApiServer for EngineEvents<EngineT> {
+    /// Handler for `engine_subscribeEvents`
+    async fn subscribe_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        // subscribe before accepting, so that no events are missed
+        let events = self.beacon_consensus.event_listener().map(EngineEvent::from);
+        let sink = pending.accept().await?;
+        self.task_spawner.spawn(Box::pin(pipe_events(sink, events)));
+
+        Ok(())
+    }
+}
+
+/// Pipes the engine events to the subscription sink, until either of them is closed.
+async fn pipe_events(sink: SubscriptionSink, mut events: impl Stream<Item = EngineEvent> + Unpin) {
+    loop {
+        let event = tokio::select! {
+            _ = sink.closed() => break,
+            event = events.next() => event,
+        };
+        let Some(event) = event else { break };
+        let msg = match SubscriptionMessage::from_json(&event) {
+            Ok(msg) => msg,
+            Err(err) => {
+                debug!(target: "rpc::engine", %err, "Failed to serialize engine event");
+                break
+            }
+        };
+        if sink.send(msg).await.is_err() {
+            break
+        }
+    }
+}
+
+impl<EngineT: EngineTypes> std::fmt::Debug for EngineEvents<EngineT> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EngineEvents").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use reth_engine_primitives::EngineTypes;
+use reth_payload_primitives::ExternalPayloadError;
+use reth_primitives::B256;
+use reth_rpc_types::engine::{PayloadId, PayloadStatusEnum};
+use std::{fmt, time::Duration};
+
+/// The default time to wait for the external payload to be executed in `engine_getPayload`.
+///
+/// If it takes longer, the local payload is returned.
+pub const DEFAULT_EXTERNAL_PAYLOAD_VALIDATION_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A builder outside of the node, e.g. a relay or a block builder API, that is asked for a payload
+/// whenever the consensus layer requests one with `engine_getPayload`.
+///
+/// The payload of the external builder is only returned to the consensus layer if it matches the
+/// attributes of the payload job, is selected by the
+/// [`PayloadSelectionPolicy`](reth_payload_primitives::PayloadSelectionPolicy) of the engine API,
+/// and is valid. Otherwise the local payload is returned.
+#[async_trait]
+pub trait ExternalPayloadBuilder<EngineT: EngineTypes>: fmt::Debug + Send + Sync + 'static {
+    /// Returns the payload that was built for the given attributes, if there is one.
+    async fn get_payload(
+        &self,
+        payload_id: PayloadId,
+        attributes: &EngineT::PayloadBuilderAttributes,
+    ) -> Result<Option<EngineT::BuiltPayload>, ExternalPayloadError>;
+}
+
+/// Converts the status of the validated external payload into a result.
+pub(crate) fn ensure_valid_status(
+    block_hash: B256,
+    status: PayloadStatusEnum,
+) -> Result<(), ExternalPayloadError> {
+    match status {
+        PayloadStatusEnum::Valid => Ok(()),
+        PayloadStatusEnum::Invalid { validation_error } => {
+            Err(ExternalPayloadError::InvalidBlock(validation_error))
+        }
+        status => Err(ExternalPayloadError::InvalidBlock(format!(
+            "block {block_hash} couldn't be validated: {}",
+            status.as_str()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_engine_primitives::EthPayloadBuilderAttributes;
+    use reth_payload_primitives::ensure_matches_attributes;
+    use reth_primitives::{Header, SealedBlock, Withdrawal, Withdrawals};
+
+    fn attributes() -> EthPayloadBuilderAttributes {
+        EthPayloadBuilderAttributes {
+            id: PayloadId::new([1; 8]),
+            parent: B256::with_last_byte(1),
+            timestamp: 12,
+            suggested_fee_recipient: Default::default(),
+            prev_randao: B256::with_last_byte(2),
+            withdrawals: Withdrawals::new(vec![Withdrawal { index: 1, ..Default::default() }]),
+            parent_beacon_block_root: Some(B256::with_last_byte(3)),
+        }
+    }
+
+    fn block() -> SealedBlock {
+        let header = Header {
+            parent_hash: B256::with_last_byte(1),
+            timestamp: 12,
+            mix_hash: B256::with_last_byte(2),
+            parent_beacon_block_root: Some(B256::with_last_byte(3)),
+            ..Default::default()
+        };
+        SealedBlock {
+            header: header.seal_slow(),
+            withdrawals: Some(Withdrawals::new(vec![Withdrawal {
+                index: 1,
+                ..Default::default()
+            }])),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn external_payload_matches_attributes() {
+        assert!(ensure_matches_attributes(&block(), &attributes()).is_ok());
+
+        let mut later = attributes();
+        later.timestamp = 24;
+        assert!(matches!(
+            ensure_matches_attributes(&block(), &later),
+            Err(ExternalPayloadError::AttributesMismatch("timestamp"))
+        ));
+
+        let mut block = block();
+        block.withdrawals = None;
+        assert!(matches!(
+            ensure_matches_attributes(&block, &attributes()),
+            Err(ExternalPayloadError::AttributesMismatch("withdrawals"))
+        ));
+    }
+}
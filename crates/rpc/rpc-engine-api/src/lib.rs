@@ -21,9 +21,37 @@ mod error;
 /// Engine API metrics.
 mod metrics;
 
+/// Engine API slow-call thresholds.
+mod slow_call;
+
+/// Engine API capabilities.
+mod capabilities;
+
+/// Engine API payloads of external builders.
+mod external_payload;
+
+/// Engine event subscription.
+mod events;
+
+/// Dry-run of payloads.
+mod dry_run;
+
+/// Engine API payload bodies limits.
+mod payload_bodies;
+
+pub use capabilities::{EngineCapabilities, EngineCapabilitiesTracker};
+pub use dry_run::EngineDryRun;
 pub use engine_api::{EngineApi, EngineApiSender};
 pub use error::*;
+pub use events::EngineEvents;
+pub use external_payload::{ExternalPayloadBuilder, DEFAULT_EXTERNAL_PAYLOAD_VALIDATION_TIMEOUT};
 pub use message::EngineApiMessageVersion;
+pub use payload_bodies::PayloadBodiesLimits;
+pub use reth_payload_primitives::{
+    ExternalPayloadError, MostGasUsed, MostValuable, PayloadSelectionPolicy, PayloadSource,
+    PreferExternal, DEFAULT_EXTERNAL_PAYLOAD_TIMEOUT,
+};
+pub use slow_call::*;
 
 // re-export server trait for convenience
 pub use reth_rpc_api::EngineApiServer;
@@ -1,9 +1,12 @@
 use std::time::Duration;
 
 use crate::EngineApiError;
-use metrics::{Counter, Histogram};
+use metrics::{Counter, Gauge, Histogram};
 use reth_metrics::Metrics;
-use reth_rpc_types::engine::{ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum};
+use reth_rpc_types::{
+    engine::{ForkchoiceUpdated, PayloadStatus, PayloadStatusEnum},
+    PayloadBuildTimings,
+};
 
 /// All beacon consensus engine metrics
 #[derive(Default)]
@@ -14,6 +17,14 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) fcu_response: ForkchoiceUpdatedResponseMetrics,
     /// Engine API newPayload response type metrics
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
+    /// Engine API slow call metrics
+    pub(crate) slow_calls: SlowCallMetrics,
+    /// Engine API external payload metrics
+    pub(crate) external_payloads: ExternalPayloadMetrics,
+    /// Build timings of the payloads returned by `engine_getPayload`
+    pub(crate) build_timings: PayloadBuildTimingsMetrics,
+    /// Engine API blob availability metrics
+    pub(crate) blob_availability: BlobAvailabilityMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -46,8 +57,83 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_range_v1: Histogram,
     /// Latency for `engine_getPayloadBodiesByHashV1`
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
+    /// Latency for `engine_getBlobsV1`
+    pub(crate) get_blobs_v1: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
+    /// Latency for `engine_getClientVersionV1`
+    pub(crate) get_client_version_v1: Histogram,
+    /// Latency for `engine_exchangeCapabilities`
+    pub(crate) exchange_capabilities: Histogram,
+}
+
+/// Metrics for engine API calls that took longer than their
+/// [threshold](crate::SlowCallThresholds).
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct SlowCallMetrics {
+    /// The total count of slow `engine_newPayload` calls.
+    pub(crate) new_payload_slow_calls: Counter,
+    /// The total count of slow `engine_forkchoiceUpdated` calls.
+    pub(crate) forkchoice_updated_slow_calls: Counter,
+    /// The total count of slow `engine_getPayload` calls.
+    pub(crate) get_payload_slow_calls: Counter,
+}
+
+/// Metrics for the payloads of an [external builder](crate::ExternalPayloadBuilder).
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct ExternalPayloadMetrics {
+    /// The total count of `engine_getPayload` calls that returned the external payload.
+    pub(crate) external_payloads_selected: Counter,
+    /// The total count of `engine_getPayload` calls that returned the local payload.
+    pub(crate) local_payloads_selected: Counter,
+    /// The total count of external payloads that failed, timed out or were invalid.
+    pub(crate) external_payload_errors: Counter,
+}
+
+/// Metrics for the build timings of the payloads returned by `engine_getPayload`.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct PayloadBuildTimingsMetrics {
+    /// The time the payload builder spent selecting transactions from the pool.
+    pub(crate) payload_tx_selection_duration: Histogram,
+    /// The time the payload builder spent executing transactions and system calls.
+    pub(crate) payload_execution_duration: Histogram,
+    /// The time the payload builder spent computing the roots of the block.
+    pub(crate) payload_root_computation_duration: Histogram,
+}
+
+impl PayloadBuildTimingsMetrics {
+    /// Records the build timings of a payload.
+    pub(crate) fn record(&self, timings: &PayloadBuildTimings) {
+        self.payload_tx_selection_duration.record(timings.tx_selection);
+        self.payload_execution_duration.record(timings.execution);
+        self.payload_root_computation_duration.record(timings.root_computation);
+    }
+}
+
+/// Metrics for the availability of the blobs of new payloads.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct BlobAvailabilityMetrics {
+    /// The total count of new payloads with blobs that are not available locally.
+    pub(crate) payloads_with_missing_blobs: Counter,
+    /// The total count of blobs of new payloads that are not available locally.
+    pub(crate) missing_blobs: Counter,
+}
+
+/// Metrics for mismatches between the advertised [capabilities](crate::EngineCapabilities) and
+/// the engine API methods that the consensus layer supports and calls.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc")]
+pub(crate) struct EngineCapabilitiesMetrics {
+    /// The total count of calls of methods that are not advertised.
+    pub(crate) unadvertised_method_calls: Counter,
+    /// The number of methods that the consensus layer supports, but that are not advertised.
+    pub(crate) unsupported_capabilities: Gauge,
+    /// The number of advertised methods that the consensus layer doesn't support.
+    pub(crate) unused_capabilities: Gauge,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
@@ -0,0 +1,71 @@
+use reth_rpc_server_types::constants::{
+    DEFAULT_MAX_PAYLOAD_BODIES, DEFAULT_MAX_PAYLOAD_BODIES_SIZE_MB,
+};
+use reth_rpc_types::{engine::ExecutionPayloadBodyV1, Withdrawal};
+
+/// The number of blocks that are read at once when serving `engine_getPayloadBodiesByRange`.
+pub(crate) const PAYLOAD_BODIES_CHUNK_SIZE: u64 = 64;
+
+/// The limits of `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash` requests.
+///
+/// Requests that exceed the limits are rejected with the request too large error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadBodiesLimits {
+    /// The maximum number of requested bodies.
+    pub max_count: u64,
+    /// The maximum size of the returned bodies in bytes, see [`payload_body_size`].
+    pub max_size: usize,
+}
+
+impl PayloadBodiesLimits {
+    /// Sets the maximum number of requested bodies.
+    pub const fn with_max_count(mut self, max_count: u64) -> Self {
+        self.max_count = max_count;
+        self
+    }
+
+    /// Sets the maximum size of the returned bodies in bytes.
+    pub const fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+impl Default for PayloadBodiesLimits {
+    fn default() -> Self {
+        Self {
+            max_count: DEFAULT_MAX_PAYLOAD_BODIES,
+            max_size: DEFAULT_MAX_PAYLOAD_BODIES_SIZE_MB as usize * 1024 * 1024,
+        }
+    }
+}
+
+/// Returns the size of the body in bytes: the length of its encoded transactions and the size of
+/// its withdrawals.
+pub(crate) fn payload_body_size(body: &ExecutionPayloadBodyV1) -> usize {
+    let transactions = body.transactions.iter().map(|tx| tx.len()).sum::<usize>();
+    let withdrawals = body
+        .withdrawals
+        .as_ref()
+        .map_or(0, |withdrawals| withdrawals.len() * std::mem::size_of::<Withdrawal>());
+    transactions + withdrawals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+
+    #[test]
+    fn payload_body_size_counts_transactions_and_withdrawals() {
+        let body = ExecutionPayloadBodyV1 {
+            transactions: vec![Bytes::from(vec![0u8; 100]), Bytes::from(vec![1u8; 20])],
+            withdrawals: None,
+        };
+        assert_eq!(payload_body_size(&body), 120);
+
+        let body =
+            ExecutionPayloadBodyV1 { withdrawals: Some(vec![Withdrawal::default(); 2]), ..body };
+        assert_eq!(payload_body_size(&body), 120 + 2 * std::mem::size_of::<Withdrawal>());
+    }
+}
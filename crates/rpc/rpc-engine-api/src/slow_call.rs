@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+/// The default duration above which `engine_newPayload` calls are slow.
+pub const DEFAULT_NEW_PAYLOAD_SLOW_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// The default duration above which `engine_forkchoiceUpdated` calls are slow.
+pub const DEFAULT_FORK_CHOICE_UPDATED_SLOW_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// The default duration above which `engine_getPayload` calls are slow.
+pub const DEFAULT_GET_PAYLOAD_SLOW_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// The durations above which engine API calls are slow.
+///
+/// Slow calls are logged as warnings and counted in the `engine.rpc` metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowCallThresholds {
+    /// The threshold of `engine_newPayload` calls.
+    pub new_payload: Duration,
+    /// The threshold of `engine_forkchoiceUpdated` calls.
+    pub fork_choice_updated: Duration,
+    /// The threshold of `engine_getPayload` calls.
+    pub get_payload: Duration,
+}
+
+impl SlowCallThresholds {
+    /// Sets the threshold of `engine_newPayload` calls.
+    pub const fn with_new_payload(mut self, threshold: Duration) -> Self {
+        self.new_payload = threshold;
+        self
+    }
+
+    /// Sets the threshold of `engine_forkchoiceUpdated` calls.
+    pub const fn with_fork_choice_updated(mut self, threshold: Duration) -> Self {
+        self.fork_choice_updated = threshold;
+        self
+    }
+
+    /// Sets the threshold of `engine_getPayload` calls.
+    pub const fn with_get_payload(mut self, threshold: Duration) -> Self {
+        self.get_payload = threshold;
+        self
+    }
+}
+
+impl Default for SlowCallThresholds {
+    fn default() -> Self {
+        Self {
+            new_payload: DEFAULT_NEW_PAYLOAD_SLOW_THRESHOLD,
+            fork_choice_updated: DEFAULT_FORK_CHOICE_UPDATED_SLOW_THRESHOLD,
+            get_payload: DEFAULT_GET_PAYLOAD_SLOW_THRESHOLD,
+        }
+    }
+}
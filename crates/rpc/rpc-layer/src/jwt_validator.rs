@@ -10,6 +10,8 @@ use tracing::error;
 #[allow(missing_debug_implementations)]
 pub struct JwtAuthValidator {
     secret: JwtSecret,
+    /// Secrets that are accepted in addition to `secret`, e.g. during a key rotation.
+    additional_secrets: Vec<JwtSecret>,
 }
 
 impl JwtAuthValidator {
@@ -17,14 +19,34 @@ impl JwtAuthValidator {
     /// Validation logics are implemented by the `secret`
     /// argument (see [`JwtSecret`]).
     pub const fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self { secret, additional_secrets: Vec::new() }
+    }
+
+    /// Also accepts JWTs that are signed with any of the given secrets.
+    pub fn with_additional_secrets(mut self, secrets: impl IntoIterator<Item = JwtSecret>) -> Self {
+        self.additional_secrets.extend(secrets);
+        self
+    }
+
+    /// Validates the JWT with the secret, and then with the additional secrets.
+    ///
+    /// If none of the secrets accept the JWT, the error of the secret is returned.
+    fn validate_jwt(&self, jwt: &str) -> Result<(), JwtError> {
+        let err = match self.secret.validate(jwt) {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        if self.additional_secrets.iter().any(|secret| secret.validate(jwt).is_ok()) {
+            return Ok(())
+        }
+        Err(err)
     }
 }
 
 impl AuthValidator for JwtAuthValidator {
     fn validate(&self, headers: &HeaderMap) -> Result<(), HttpResponse> {
         match get_bearer(headers) {
-            Some(jwt) => match self.secret.validate(&jwt) {
+            Some(jwt) => match self.validate_jwt(&jwt) {
                 Ok(_) => Ok(()),
                 Err(e) => {
                     error!(target: "engine::jwt-validator", "Invalid JWT: {e}");
@@ -65,7 +87,8 @@ fn err_response(err: JwtError) -> HttpResponse {
 
 #[cfg(test)]
 mod tests {
-    use crate::jwt_validator::get_bearer;
+    use crate::{jwt_validator::get_bearer, JwtAuthValidator};
+    use alloy_rpc_types_engine::{Claims, JwtError, JwtSecret};
     use http::{header, HeaderMap};
 
     #[test]
@@ -94,4 +117,18 @@ mod tests {
         let token = get_bearer(&headers);
         assert!(token.is_none());
     }
+
+    #[test]
+    fn additional_secrets_are_accepted() {
+        let secret = JwtSecret::random();
+        let additional = JwtSecret::random();
+        let validator = JwtAuthValidator::new(secret).with_additional_secrets([additional]);
+
+        let claims = Claims::with_current_timestamp();
+        assert!(validator.validate_jwt(&secret.encode(&claims).unwrap()).is_ok());
+        assert!(validator.validate_jwt(&additional.encode(&claims).unwrap()).is_ok());
+
+        let unknown = JwtSecret::random().encode(&claims).unwrap();
+        assert!(matches!(validator.validate_jwt(&unknown), Err(JwtError::InvalidSignature)));
+    }
 }
@@ -0,0 +1,73 @@
+use http::HeaderMap;
+use jsonrpsee_http_client::{HttpRequest, HttpResponse};
+use opentelemetry::propagation::Extractor;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::{instrument::Instrumented, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// This is an Http middleware layer that processes every request in a span, whose parent is the
+/// trace context of the request's `traceparent` header if present.
+///
+/// This allows the spans of a call to be inspected as part of the trace of the caller, e.g. the
+/// trace of a consensus client. The trace context is extracted with the global text map
+/// propagator, so the parent is only set if a propagator has been installed.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct TraceContextLayer;
+
+impl TraceContextLayer {
+    /// Creates an instance of [`TraceContextLayer`].
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContextService { inner }
+    }
+}
+
+/// This type is the actual implementation of the middleware, see [`TraceContextLayer`].
+#[derive(Clone, Debug)]
+pub struct TraceContextService<S> {
+    inner: S,
+}
+
+impl<S> Service<HttpRequest> for TraceContextService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Instrumented<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        let span = tracing::trace_span!(target: "rpc::engine", "http_request");
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(req.headers()))
+        });
+        span.set_parent(parent);
+        self.inner.call(req).instrument(span)
+    }
+}
+
+/// Extracts the trace context from Http headers.
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
@@ -0,0 +1,38 @@
+use strum::{Display, EnumString};
+
+/// Whether `engine_newPayloadV3` and later check that the blobs referenced by the versioned hashes
+/// of an accepted payload are available locally.
+///
+/// The blobs are looked up in the blob store of the transaction pool, which only holds the blobs of
+/// transactions that were gossiped to the node, so missing blobs never make a payload invalid and
+/// the check is only useful for monitoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum BlobAvailabilityCheck {
+    /// Blobs are not checked.
+    #[default]
+    Off,
+    /// Missing blobs are logged and recorded in metrics.
+    Warn,
+}
+
+impl BlobAvailabilityCheck {
+    /// Returns `true` if blobs are checked.
+    pub const fn is_enabled(&self) -> bool {
+        !matches!(self, Self::Off)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_blob_availability_check() {
+        for check in [BlobAvailabilityCheck::Off, BlobAvailabilityCheck::Warn] {
+            assert_eq!(check.to_string().parse::<BlobAvailabilityCheck>().unwrap(), check);
+        }
+        assert_eq!("warn".parse::<BlobAvailabilityCheck>().unwrap(), BlobAvailabilityCheck::Warn);
+        assert!("strict".parse::<BlobAvailabilityCheck>().is_err());
+    }
+}
@@ -9,12 +9,26 @@ pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
+/// The default maximum number of bodies of an `engine_getPayloadBodiesByRange` or
+/// `engine_getPayloadBodiesByHash` request.
+pub const DEFAULT_MAX_PAYLOAD_BODIES: u64 = 1024;
+
+/// The default maximum size of the bodies of an `engine_getPayloadBodies` response in MB.
+///
+/// The bodies are hex encoded in the response, which stays below the response limit of the auth
+/// server.
+pub const DEFAULT_MAX_PAYLOAD_BODIES_SIZE_MB: u32 = 256;
+
 /// The default maximum block range allowed to filter
 pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum number of blocks an `ots_searchTransactionsBefore` or
+/// `ots_searchTransactionsAfter` request searches.
+pub const DEFAULT_MAX_OTS_SEARCH_BLOCKS: u64 = 100_000;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
@@ -26,6 +40,39 @@ pub fn default_max_tracing_requests() -> usize {
         .map_or(25, |cpus| max(cpus.get().saturating_sub(RESERVED), RESERVED))
 }
 
+/// The default maximum number of threads that trace the transactions of a block in parallel.
+///
+/// Tracing is serial by default, since every parallel tracing request can occupy that many threads
+/// of the blocking pool.
+pub const DEFAULT_TRACE_BLOCK_PARALLELISM: usize = 1;
+
+/// The default maximum number of times `eth_createAccessList` executes the transaction with the
+/// access list of the previous execution, until the access list doesn't change anymore.
+pub const DEFAULT_ACCESS_LIST_MAX_ITERATIONS: u64 = 10;
+
+/// The default maximum distance of the target block of `eth_getProof` from the tip: latest only.
+pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
+
+/// The maximum distance of the target block of `eth_getProof` from the tip that can be configured.
+///
+/// Generating a historical proof requires reverting all changes since the target block in memory,
+/// so the window is capped to ~2 weeks of mainnet blocks.
+pub const MAX_ETH_PROOF_WINDOW: u64 = 100_800;
+
+/// The default number of recent blocks whose emitted logs each `logs` subscription buffers, to
+/// notify the subscriber about their removal on reorgs.
+pub const DEFAULT_LOGS_REORG_DEPTH: u64 = 64;
+
+/// The tracing target of the slow query log of the rpc servers.
+pub const SLOW_QUERY_LOG_TARGET: &str = "rpc::slow_query";
+
+/// The name of the file the slow query log is written to, in the log directory.
+pub const SLOW_QUERY_LOG_FILE_NAME: &str = "slow-queries.log";
+
+/// The default interval at which the usage of the API keys of the rpc servers is persisted.
+pub const DEFAULT_API_KEY_USAGE_PERSIST_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(60);
+
 /// The default IPC endpoint
 #[cfg(windows)]
 pub const DEFAULT_IPC_ENDPOINT: &str = r"\\.\pipe\reth.ipc";
@@ -64,6 +111,13 @@ pub mod gas_oracle {
 
     /// The default minimum gas price, under which the sample will be ignored
     pub const DEFAULT_IGNORE_GAS_PRICE: U256 = U256::from_limbs([2u64, 0, 0, 0]);
+
+    /// The default minimum priority fee suggested on OP Stack chains, 0.001 gwei
+    pub const DEFAULT_MIN_SUGGESTED_PRIORITY_FEE: U256 = U256::from_limbs([1_000_000u64, 0, 0, 0]);
+
+    /// The percentage of the gas limit that must be used by the latest block before OP Stack
+    /// chains suggest a priority fee above the minimum
+    pub const OP_CONGESTED_GAS_USED_PERCENT: u64 = 90;
 }
 
 /// Cache specific constants
@@ -91,4 +145,11 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default cache size for the response cache: disabled.
+    pub const DEFAULT_RESPONSE_CACHE_MAX_LEN: u32 = 0;
+
+    /// Default time to live of cached responses: 1 hour.
+    pub const DEFAULT_RESPONSE_CACHE_TTL: std::time::Duration =
+        std::time::Duration::from_secs(3600);
 }
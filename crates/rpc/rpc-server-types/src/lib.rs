@@ -11,5 +11,11 @@
 /// Common RPC constants.
 pub mod constants;
 
+mod blob_availability;
+pub use blob_availability::BlobAvailabilityCheck;
+
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
+
+mod response_size;
+pub use response_size::NamespaceResponseSize;
@@ -0,0 +1,64 @@
+use std::{fmt, str::FromStr};
+
+/// The maximum response size of the calls of a namespace, e.g. `debug=1000`.
+///
+/// The namespace is the prefix of the method names before the first `_`, so `debug` limits the
+/// responses of `debug_traceBlock` and all other `debug_` methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceResponseSize {
+    /// The namespace of the methods.
+    pub namespace: String,
+    /// The maximum response size, in megabytes.
+    pub max_size_mb: u32,
+}
+
+impl NamespaceResponseSize {
+    /// Returns the maximum response size in bytes.
+    pub const fn max_size_bytes(&self) -> u32 {
+        self.max_size_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+impl FromStr for NamespaceResponseSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, max_size_mb) =
+            s.split_once('=').ok_or_else(|| format!("expected <NAMESPACE>=<MB>, got `{s}`"))?;
+        if namespace.is_empty() || !namespace.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("invalid namespace `{namespace}`"))
+        }
+        let max_size_mb = max_size_mb
+            .parse()
+            .ok()
+            .filter(|size| *size > 0)
+            .ok_or_else(|| format!("invalid response size `{max_size_mb}` of `{namespace}`"))?;
+        Ok(Self { namespace: namespace.to_string(), max_size_mb })
+    }
+}
+
+impl fmt::Display for NamespaceResponseSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.namespace, self.max_size_mb)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_namespace_response_size() {
+        let size = "debug=1000".parse::<NamespaceResponseSize>().unwrap();
+        assert_eq!(
+            size,
+            NamespaceResponseSize { namespace: "debug".to_string(), max_size_mb: 1000 }
+        );
+        assert_eq!(size.to_string(), "debug=1000");
+
+        assert!("debug".parse::<NamespaceResponseSize>().is_err());
+        assert!("debug=0".parse::<NamespaceResponseSize>().is_err());
+        assert!("debug_traceBlock=10".parse::<NamespaceResponseSize>().is_err());
+        assert!("=10".parse::<NamespaceResponseSize>().is_err());
+    }
+}
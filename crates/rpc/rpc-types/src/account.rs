@@ -0,0 +1,51 @@
+//! Types of the account info endpoints
+
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The balance, nonce and code of an account, as returned by `eth_getAccountInfo`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummary {
+    /// The address of the account.
+    pub address: Address,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The hash of the code of the account, the hash of empty code if it has none.
+    pub code_hash: B256,
+    /// The size of the code of the account in bytes.
+    #[serde(with = "alloy_serde::quantity")]
+    pub code_size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn serde_account_summary() {
+        let summary = AccountSummary {
+            address: address!("00000000000000000000000000000000000000aa"),
+            balance: U256::from(1_000_000_000u64),
+            nonce: 3,
+            code_hash: b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            code_size: 0,
+        };
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "address": "0x00000000000000000000000000000000000000aa",
+                "balance": "0x3b9aca00",
+                "nonce": "0x3",
+                "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "codeSize": "0x0"
+            })
+        );
+        assert_eq!(serde_json::from_value::<AccountSummary>(json).unwrap(), summary);
+    }
+}
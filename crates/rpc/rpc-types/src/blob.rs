@@ -0,0 +1,80 @@
+//! Blob type bindings of the engine API and the blob retrieval endpoints
+
+use alloy_primitives::{BlockHash, BlockNumber, FixedBytes, TxHash, B256};
+use serde::{Deserialize, Serialize};
+
+/// The number of bytes of a blob.
+pub const BYTES_PER_BLOB: usize = 131_072;
+
+/// A blob of a blob transaction.
+pub type Blob = FixedBytes<BYTES_PER_BLOB>;
+
+/// A blob with its KZG proof, as returned by `engine_getBlobsV1`.
+///
+/// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#blobandproofv1>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobAndProofV1 {
+    /// The blob.
+    pub blob: Box<Blob>,
+    /// The KZG proof of the blob.
+    pub proof: FixedBytes<48>,
+}
+
+/// A blob of a blob transaction with its KZG commitment and proof, as returned by
+/// `reth_getBlobSidecars` and `reth_getBlobSidecarByVersionedHash`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobSidecar {
+    /// The hash of the block that includes the transaction, `None` if it's pending.
+    pub block_hash: Option<BlockHash>,
+    /// The number of the block that includes the transaction, `None` if it's pending.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub block_number: Option<BlockNumber>,
+    /// The hash of the transaction.
+    pub transaction_hash: TxHash,
+    /// The index of the blob in the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub index: u64,
+    /// The versioned hash of the blob.
+    pub versioned_hash: B256,
+    /// The blob.
+    pub blob: Box<Blob>,
+    /// The KZG commitment of the blob.
+    pub kzg_commitment: FixedBytes<48>,
+    /// The KZG proof of the blob.
+    pub kzg_proof: FixedBytes<48>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blob_and_proof_serde() {
+        let blob = BlobAndProofV1 {
+            blob: Box::new(Blob::repeat_byte(0x01)),
+            proof: FixedBytes::repeat_byte(0x02),
+        };
+        let json = serde_json::to_value(&blob).unwrap();
+        assert_eq!(json["proof"], format!("0x{}", "02".repeat(48)));
+        assert_eq!(serde_json::from_value::<BlobAndProofV1>(json).unwrap(), blob);
+    }
+
+    #[test]
+    fn blob_sidecar_serde() {
+        let sidecar = BlobSidecar {
+            block_hash: None,
+            block_number: None,
+            transaction_hash: TxHash::repeat_byte(0x03),
+            index: 1,
+            versioned_hash: B256::repeat_byte(0x04),
+            blob: Box::new(Blob::repeat_byte(0x01)),
+            kzg_commitment: FixedBytes::repeat_byte(0x05),
+            kzg_proof: FixedBytes::repeat_byte(0x02),
+        };
+        let json = serde_json::to_value(&sidecar).unwrap();
+        assert_eq!(json["index"], "0x1");
+        assert!(json["blockNumber"].is_null());
+        assert_eq!(serde_json::from_value::<BlobSidecar>(json).unwrap(), sidecar);
+    }
+}
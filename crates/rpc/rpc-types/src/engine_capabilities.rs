@@ -0,0 +1,54 @@
+//! Types of the `admin_engineCapabilities` method
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The engine API methods that are advertised to the consensus layer, compared to the methods
+/// that it supports and actually calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineCapabilitiesReport {
+    /// The methods that are advertised in `engine_exchangeCapabilities`.
+    pub advertised: Vec<String>,
+    /// The methods that the consensus layer sent in its last `engine_exchangeCapabilities` call,
+    /// if it made one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus_client: Option<Vec<String>>,
+    /// The number of calls of each method that the consensus layer called.
+    pub calls: BTreeMap<String, u64>,
+    /// The methods that the consensus layer called, but that are not advertised.
+    pub unadvertised_calls: Vec<String>,
+    /// The methods that the consensus layer supports, but that are not advertised.
+    pub unsupported: Vec<String>,
+    /// The methods that are advertised, but that the consensus layer doesn't support.
+    pub unused: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_engine_capabilities_report() {
+        let report = EngineCapabilitiesReport {
+            advertised: vec!["engine_newPayloadV3".to_string()],
+            consensus_client: None,
+            calls: BTreeMap::from([("engine_newPayloadV4".to_string(), 2)]),
+            unadvertised_calls: vec!["engine_newPayloadV4".to_string()],
+            unsupported: Vec::new(),
+            unused: Vec::new(),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "advertised": ["engine_newPayloadV3"],
+                "calls": { "engine_newPayloadV4": 2 },
+                "unadvertisedCalls": ["engine_newPayloadV4"],
+                "unsupported": [],
+                "unused": []
+            })
+        );
+        assert_eq!(serde_json::from_value::<EngineCapabilitiesReport>(json).unwrap(), report);
+    }
+}
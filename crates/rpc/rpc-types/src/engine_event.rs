@@ -0,0 +1,111 @@
+//! Types of the `engine_subscribeEvents` subscription
+
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::ForkchoiceState;
+use serde::{Deserialize, Serialize};
+
+/// An event of the consensus engine, as emitted by the `engine_subscribeEvents` subscription of
+/// the auth server.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum EngineEvent {
+    /// The forkchoice state was updated.
+    #[serde(rename_all = "camelCase")]
+    ForkchoiceUpdated {
+        /// The new forkchoice state.
+        state: ForkchoiceState,
+        /// The status of the forkchoice state.
+        status: EngineForkchoiceStatus,
+    },
+    /// A block was added to the canonical chain.
+    #[serde(rename_all = "camelCase")]
+    CanonicalBlockAdded {
+        /// The number of the block.
+        number: u64,
+        /// The hash of the block.
+        hash: B256,
+        /// How long validating the block took, in milliseconds.
+        elapsed_ms: u64,
+    },
+    /// A canonical chain was committed.
+    #[serde(rename_all = "camelCase")]
+    CanonicalChainCommitted {
+        /// The number of the new head.
+        number: u64,
+        /// The hash of the new head.
+        hash: B256,
+        /// How long committing the chain took, in milliseconds.
+        elapsed_ms: u64,
+    },
+    /// A block was added to a fork chain.
+    #[serde(rename_all = "camelCase")]
+    ForkBlockAdded {
+        /// The number of the block.
+        number: u64,
+        /// The hash of the block.
+        hash: B256,
+    },
+    /// A block was found to be invalid.
+    #[serde(rename_all = "camelCase")]
+    InvalidBlock {
+        /// The number of the block.
+        number: u64,
+        /// The hash of the block.
+        hash: B256,
+        /// The hash of the parent of the block.
+        parent_hash: B256,
+        /// Why the block is invalid.
+        error: String,
+    },
+    /// An invalid block was dumped to disk.
+    #[serde(rename_all = "camelCase")]
+    InvalidBlockDumped {
+        /// The number of the block.
+        number: u64,
+        /// The hash of the block.
+        hash: B256,
+        /// The path of the dump.
+        path: String,
+    },
+    /// The engine is downloading blocks to catch up with the consensus layer.
+    #[serde(rename_all = "camelCase")]
+    LiveSyncProgress {
+        /// The number of blocks that remain to be downloaded.
+        remaining_blocks: u64,
+        /// The hash of the block that is downloaded.
+        target: B256,
+    },
+}
+
+/// The status of a forkchoice state in an [`EngineEvent::ForkchoiceUpdated`] event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EngineForkchoiceStatus {
+    /// The forkchoice state is valid.
+    Valid,
+    /// The forkchoice state is invalid.
+    Invalid,
+    /// The forkchoice state is unknown.
+    Syncing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engine_event_serde() {
+        let event = EngineEvent::CanonicalBlockAdded {
+            number: 1,
+            hash: B256::with_last_byte(1),
+            elapsed_ms: 20,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "canonicalBlockAdded");
+        assert_eq!(json["elapsedMs"], 20);
+        assert_eq!(serde_json::from_value::<EngineEvent>(json).unwrap(), event);
+
+        let status = serde_json::to_value(EngineForkchoiceStatus::Syncing).unwrap();
+        assert_eq!(status, "SYNCING");
+    }
+}
@@ -0,0 +1,52 @@
+//! Types of the `debug_executionWitness` method
+
+use alloy_primitives::{Bytes, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The data needed to execute a block without access to the state, e.g. to verify it statelessly.
+///
+/// The witness contains the parts of the state before the block that the execution of the block
+/// accessed, as trie nodes that prove them against the state root of the parent block.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionWitness {
+    /// The nodes of the account and storage tries, keyed by their hash.
+    pub state: BTreeMap<B256, Bytes>,
+    /// The bytecodes of the accessed contracts, keyed by their hash.
+    pub codes: BTreeMap<B256, Bytes>,
+    /// The preimages of the hashed keys of the accessed accounts and storage slots, i.e. the
+    /// addresses and the slots, keyed by their hash.
+    pub keys: BTreeMap<B256, Bytes>,
+    /// The RLP encoded headers of the ancestors of the block, from the parent block to the oldest
+    /// block whose hash was accessed.
+    pub headers: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    #[test]
+    fn serde_execution_witness() {
+        let node = Bytes::from_static(&[0xc0]);
+        let witness = ExecutionWitness {
+            state: BTreeMap::from([(keccak256(&node), node.clone())]),
+            headers: vec![node],
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&witness).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "state": {
+                    "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347": "0xc0"
+                },
+                "codes": {},
+                "keys": {},
+                "headers": ["0xc0"]
+            })
+        );
+        assert_eq!(serde_json::from_value::<ExecutionWitness>(json).unwrap(), witness);
+    }
+}
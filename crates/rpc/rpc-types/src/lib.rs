@@ -9,11 +9,27 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
+mod account;
+mod blob;
+mod engine_capabilities;
+mod engine_event;
 #[allow(hidden_glob_reexports)]
 mod eth;
+mod execution_witness;
 mod mev;
+mod nat;
+mod payload_dry_run;
+mod payload_timings;
 mod peer;
+mod result_chunk;
 mod rpc;
+mod simulate;
+mod simulated_call;
+mod subscription;
+mod txpool_event;
+mod txpool_query;
+mod txpool_sender_report;
+mod txpool_snapshot;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -51,6 +67,22 @@ pub use eth::{
     transaction::{self, TransactionRequest, TypedTransactionRequest},
 };
 
+pub use account::*;
+pub use blob::*;
+pub use engine_capabilities::*;
+pub use engine_event::*;
+pub use execution_witness::*;
 pub use mev::*;
+pub use nat::*;
+pub use payload_dry_run::*;
+pub use payload_timings::*;
 pub use peer::*;
+pub use result_chunk::*;
 pub use rpc::*;
+pub use simulate::*;
+pub use simulated_call::*;
+pub use subscription::*;
+pub use txpool_event::*;
+pub use txpool_query::*;
+pub use txpool_sender_report::*;
+pub use txpool_snapshot::*;
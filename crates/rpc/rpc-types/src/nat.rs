@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// Status of the external address resolution of the node.
+///
+/// Returned by `admin_natStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NatInfo {
+    /// The configured resolver, e.g. `any`, `upnp` or `extip:<IP>`.
+    pub resolver: String,
+    /// The most recently resolved external address, if any.
+    pub external_address: Option<IpAddr>,
+    /// Unix timestamp in seconds of the last resolution attempt, if any.
+    pub last_refresh: Option<u64>,
+    /// Number of consecutive failed resolution attempts.
+    pub consecutive_failures: u64,
+}
@@ -0,0 +1,85 @@
+//! Types of the `engine_dryRunPayload` method
+
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::PayloadStatusEnum;
+use serde::{Deserialize, Serialize};
+
+/// The outcome of validating and executing a payload with `engine_dryRunPayload`, without
+/// persisting it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadDryRunResult {
+    /// Whether the payload is valid, and the validation error if it isn't.
+    #[serde(flatten)]
+    pub status: PayloadStatusEnum,
+    /// The gas used by the transactions of the payload, if it was executed.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub gas_used: Option<u64>,
+    /// The state root after executing the payload, if it was computed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_root: Option<B256>,
+}
+
+impl PayloadDryRunResult {
+    /// Creates the result of a valid payload.
+    pub const fn valid(gas_used: u64, state_root: B256) -> Self {
+        Self {
+            status: PayloadStatusEnum::Valid,
+            gas_used: Some(gas_used),
+            state_root: Some(state_root),
+        }
+    }
+
+    /// Creates the result of an invalid payload.
+    pub fn invalid(validation_error: impl ToString) -> Self {
+        Self {
+            status: PayloadStatusEnum::Invalid { validation_error: validation_error.to_string() },
+            gas_used: None,
+            state_root: None,
+        }
+    }
+
+    /// Sets the gas used by the transactions of the payload.
+    pub const fn with_gas_used(mut self, gas_used: u64) -> Self {
+        self.gas_used = Some(gas_used);
+        self
+    }
+
+    /// Sets the state root after executing the payload.
+    pub const fn with_state_root(mut self, state_root: B256) -> Self {
+        self.state_root = Some(state_root);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_payload_dry_run_result() {
+        let valid = PayloadDryRunResult::valid(21_000, B256::with_last_byte(1));
+        let json = serde_json::to_value(&valid).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "status": "VALID",
+                "gasUsed": "0x5208",
+                "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000001"
+            })
+        );
+        assert_eq!(serde_json::from_value::<PayloadDryRunResult>(json).unwrap(), valid);
+
+        let invalid = PayloadDryRunResult::invalid("gas used mismatch").with_gas_used(1);
+        let json = serde_json::to_value(&invalid).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "status": "INVALID",
+                "validationError": "gas used mismatch",
+                "gasUsed": "0x1"
+            })
+        );
+        assert_eq!(serde_json::from_value::<PayloadDryRunResult>(json).unwrap(), invalid);
+    }
+}
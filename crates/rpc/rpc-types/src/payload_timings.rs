@@ -0,0 +1,62 @@
+//! Build timings of the payloads returned by `engine_getPayload`
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::Duration;
+
+/// How long the payload builder spent in each phase of building a payload.
+///
+/// Serialized as microseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadBuildTimings {
+    /// The time spent selecting transactions from the pool.
+    #[serde(with = "micros")]
+    pub tx_selection: Duration,
+    /// The time spent executing transactions and system calls.
+    #[serde(with = "micros")]
+    pub execution: Duration,
+    /// The time spent computing the state, transactions and receipts roots.
+    #[serde(with = "micros")]
+    pub root_computation: Duration,
+}
+
+impl PayloadBuildTimings {
+    /// Returns the total time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.tx_selection + self.execution + self.root_computation
+    }
+}
+
+/// (De)serializes a [`Duration`] as microseconds.
+mod micros {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(duration: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        u64::try_from(duration.as_micros()).unwrap_or(u64::MAX).serialize(s)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        u64::deserialize(d).map(Duration::from_micros)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_payload_build_timings() {
+        let timings = PayloadBuildTimings {
+            tx_selection: Duration::from_micros(1),
+            execution: Duration::from_millis(2),
+            root_computation: Duration::from_millis(3),
+        };
+        let json = serde_json::to_value(timings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "txSelection": 1, "execution": 2000, "rootComputation": 3000 })
+        );
+        assert_eq!(serde_json::from_value::<PayloadBuildTimings>(json).unwrap(), timings);
+        assert_eq!(timings.total(), Duration::from_micros(5001));
+    }
+}
@@ -1,4 +1,27 @@
 use alloy_primitives::B512;
+use serde::{Deserialize, Serialize};
 
 /// Alias for a peer identifier
 pub type PeerId = B512;
+
+/// Bytes exchanged with a connected peer, broken down by shared capability.
+///
+/// Returned by `admin_peerBandwidth`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerBandwidth {
+    /// The identifier of the remote peer.
+    pub id: PeerId,
+    /// Bytes exchanged per shared capability.
+    pub capabilities: Vec<CapabilityBandwidthInfo>,
+}
+
+/// Bytes exchanged with a peer for a single capability.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityBandwidthInfo {
+    /// The capability in `name/version` form, e.g. `eth/68`.
+    pub capability: String,
+    /// Number of bytes received for this capability.
+    pub ingress: u64,
+    /// Number of bytes sent for this capability.
+    pub egress: u64,
+}
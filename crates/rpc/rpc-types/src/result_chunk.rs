@@ -0,0 +1,45 @@
+//! Notifications of the subscriptions that stream the results of methods with large responses
+
+use serde::{Deserialize, Serialize};
+
+/// A chunk of the results of a method whose results are streamed as subscription notifications,
+/// e.g. `debug_subscribeTraceBlock` and `eth_subscribeGetLogs`.
+///
+/// Streaming the results bounds the memory needed to serve them, and lets the client process the
+/// first results before the last ones are computed. The last notification of a subscription has
+/// `done` set and no results, after which the subscription is closed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultChunk<T> {
+    /// The results in this chunk, in the order of the results of the non-streaming method.
+    pub results: Vec<T>,
+    /// Whether all results have been sent.
+    pub done: bool,
+}
+
+impl<T> ResultChunk<T> {
+    /// Creates a chunk with the given results.
+    pub const fn new(results: Vec<T>) -> Self {
+        Self { results, done: false }
+    }
+
+    /// Creates the chunk that marks the end of the results.
+    pub const fn done() -> Self {
+        Self { results: Vec::new(), done: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_result_chunk() {
+        let chunk = ResultChunk::new(vec![1u64, 2]);
+        let json = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(json, serde_json::json!({ "results": [1, 2], "done": false }));
+        assert_eq!(serde_json::from_value::<ResultChunk<u64>>(json).unwrap(), chunk);
+
+        let json = serde_json::to_value(ResultChunk::<u64>::done()).unwrap();
+        assert_eq!(json, serde_json::json!({ "results": [], "done": true }));
+    }
+}
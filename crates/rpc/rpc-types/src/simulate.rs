@@ -0,0 +1,203 @@
+//! Types of the `eth_simulateV1` method
+
+use alloy_primitives::{address, Address, Bytes, B256, U256};
+use alloy_rpc_types::{state::StateOverride, Block, Log, TransactionRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The address of the logs emitted for ether transfers if `traceTransfers` is enabled.
+pub const SIMULATE_TRANSFER_LOG_ADDRESS: Address =
+    address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// The payload of `eth_simulateV1`: the blocks to simulate on top of the base block, and how.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to emit a log for every transfer of ether, like an ERC-20 `Transfer` event
+    /// emitted by [`SIMULATE_TRANSFER_LOG_ADDRESS`].
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to validate the calls like transactions of a real block, e.g. their nonces and the
+    /// base fee.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return the full transactions of the blocks instead of their hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// A block of an `eth_simulateV1` payload.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the block's header fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<SimBlockOverrides>,
+    /// Overrides of the state, applied before the first call of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls of the block, executed in order.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// Overrides of the header fields of a block of an `eth_simulateV1` payload.
+///
+/// Unset fields are derived from the previous block. The names of the `eth_call` block overrides
+/// are accepted as aliases.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct SimBlockOverrides {
+    /// The number of the block, the previous block's number plus one by default.
+    #[serde(
+        with = "alloy_serde::quantity::opt",
+        skip_serializing_if = "Option::is_none",
+        alias = "blockNumber"
+    )]
+    pub number: Option<u64>,
+    /// The timestamp of the block, the previous block's timestamp plus 12 by default.
+    #[serde(
+        with = "alloy_serde::quantity::opt",
+        skip_serializing_if = "Option::is_none",
+        alias = "timestamp"
+    )]
+    pub time: Option<u64>,
+    /// The gas limit of the block, the previous block's gas limit by default.
+    #[serde(with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// The beneficiary of the block, the previous block's beneficiary by default.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "coinbase")]
+    pub fee_recipient: Option<Address>,
+    /// The prevrandao of the block, the previous block's by default.
+    #[serde(skip_serializing_if = "Option::is_none", alias = "random")]
+    pub prev_randao: Option<B256>,
+    /// The base fee of the block.
+    ///
+    /// Derived from the previous block if the calls are validated, zero otherwise.
+    #[serde(
+        with = "alloy_serde::quantity::opt",
+        skip_serializing_if = "Option::is_none",
+        alias = "baseFee"
+    )]
+    pub base_fee_per_gas: Option<u64>,
+    /// The blob base fee of the block.
+    ///
+    /// Derived from the previous block if the calls are validated, zero otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_base_fee: Option<U256>,
+    /// The hashes returned by the `BLOCKHASH` opcode for the given block numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<BTreeMap<u64, B256>>,
+}
+
+/// A block simulated by `eth_simulateV1`, with the results of its calls.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the calls of the block.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// The result of a call of a block simulated by `eth_simulateV1`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call, or the revert data if it reverted.
+    pub return_data: Bytes,
+    /// The logs emitted by the call. Calls that failed emit no logs.
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// `1` if the call succeeded, `0` otherwise.
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: u64,
+    /// Why the call failed, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimCallError>,
+}
+
+/// The error of a failed `eth_simulateV1` call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimCallError {
+    /// The JSON-RPC error code: `-32000` if the call reverted, `-32015` if the execution halted.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+    /// The revert data, if the call reverted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_simulate_payload() {
+        let json = serde_json::json!({
+            "blockStateCalls": [{
+                "blockOverrides": {
+                    "number": "0x64",
+                    "baseFeePerGas": "0x9",
+                    "coinbase": "0x0000000000000000000000000000000000000001"
+                },
+                "stateOverrides": {
+                    "0x000000000000000000000000000000000000dead": { "balance": "0x1" }
+                },
+                "calls": [{
+                    "from": "0x000000000000000000000000000000000000dead",
+                    "to": "0x000000000000000000000000000000000000beef",
+                    "value": "0x1"
+                }]
+            }, {}],
+            "traceTransfers": true,
+            "validation": true
+        });
+        let payload: SimulatePayload = serde_json::from_value(json).unwrap();
+        assert_eq!(payload.block_state_calls.len(), 2);
+        assert!(payload.trace_transfers && payload.validation);
+        assert!(!payload.return_full_transactions);
+
+        let block = &payload.block_state_calls[0];
+        let overrides = block.block_overrides.as_ref().unwrap();
+        assert_eq!(overrides.number, Some(100));
+        assert_eq!(overrides.base_fee_per_gas, Some(9));
+        assert_eq!(overrides.fee_recipient, Some(Address::with_last_byte(1)));
+        let dead = address!("000000000000000000000000000000000000dead");
+        assert_eq!(block.state_overrides.as_ref().unwrap()[&dead].balance, Some(U256::from(1)));
+        assert_eq!(block.calls[0].from, Some(dead));
+        assert_eq!(payload.block_state_calls[1], SimBlock::default());
+    }
+
+    #[test]
+    fn serde_sim_call_result() {
+        let result = SimCallResult {
+            return_data: Bytes::from_static(&[1]),
+            logs: vec![],
+            gas_used: 21_000,
+            status: 0,
+            error: Some(SimCallError {
+                code: -32000,
+                message: "execution reverted".to_string(),
+                data: Some(Bytes::from_static(&[1])),
+            }),
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "returnData": "0x01",
+                "logs": [],
+                "gasUsed": "0x5208",
+                "status": "0x0",
+                "error": { "code": -32000, "message": "execution reverted", "data": "0x01" }
+            })
+        );
+        assert_eq!(serde_json::from_value::<SimCallResult>(json).unwrap(), result);
+    }
+}
@@ -0,0 +1,60 @@
+//! Types of the `reth_simulateCalls` method
+
+use alloy_primitives::{Bytes, Log};
+use serde::{Deserialize, Serialize};
+
+/// The outcome of a call of an `reth_simulateCalls` bundle.
+///
+/// Unlike `eth_callMany`, the outcome includes the gas used and the logs of the call, and the
+/// return data of reverted calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// Whether the call succeeded.
+    pub success: bool,
+    /// The data returned by the call, or the revert data if it reverted.
+    pub return_data: Bytes,
+    /// Why the call failed, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// The logs emitted by the call. Calls that failed emit no logs.
+    pub logs: Vec<Log>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, LogData, B256};
+
+    #[test]
+    fn serde_simulated_call_result() {
+        let result = SimulatedCallResult {
+            success: true,
+            return_data: Bytes::from_static(&[1]),
+            error: None,
+            gas_used: 21_000,
+            logs: vec![Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![B256::ZERO], Bytes::new()),
+            }],
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "success": true,
+                "returnData": "0x01",
+                "gasUsed": "0x5208",
+                "logs": [{
+                    "address": "0x0000000000000000000000000000000000000000",
+                    "topics": ["0x0000000000000000000000000000000000000000000000000000000000000000"],
+                    "data": "0x"
+                }]
+            })
+        );
+        assert_eq!(serde_json::from_value::<SimulatedCallResult>(json).unwrap(), result);
+    }
+}
@@ -0,0 +1,156 @@
+//! Parameters of the `eth_subscribe` method
+
+use alloy_primitives::Address;
+use alloy_rpc_types::Filter;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+
+/// The parameters of an `eth_subscribe` call.
+///
+/// Extends the standard parameters with a [`PendingTransactionsFilter`] for the
+/// `newPendingTransactions` subscription. An object is parsed as a [`PendingTransactionsFilter`] if
+/// it has any of its fields, and as a log [`Filter`] otherwise.
+///
+/// If the node enables replay, the numeric `fromBlock` of a log [`Filter`] is the block from which
+/// `newHeads` and `logs` subscriptions replay the canonical blocks before streaming new ones, e.g.
+/// to resume after a reconnect. `newHeads` subscriptions ignore the other fields of the filter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionParams {
+    /// Whether the `newPendingTransactions` subscription yields full transactions.
+    Bool(bool),
+    /// The log filter of the `logs` subscription.
+    Logs(Box<Filter>),
+    /// The filter of the `newPendingTransactions` subscription.
+    PendingTransactions(PendingTransactionsFilter),
+}
+
+impl<'de> Deserialize<'de> for SubscriptionParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = serde_json::Value::deserialize(deserializer)?;
+
+        if let Some(val) = v.as_bool() {
+            return Ok(Self::Bool(val))
+        }
+
+        let is_pending_transactions_filter = v.as_object().is_some_and(|object| {
+            object.keys().any(|key| PendingTransactionsFilter::FIELDS.contains(&key.as_str()))
+        });
+        if is_pending_transactions_filter {
+            serde_json::from_value(v).map(Self::PendingTransactions)
+        } else {
+            serde_json::from_value(v).map(|filter| Self::Logs(Box::new(filter)))
+        }
+        .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
+    }
+}
+
+/// Server side filter criteria of the `newPendingTransactions` subscription.
+///
+/// A transaction matches if it matches all criteria that are set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionsFilter {
+    /// Only match transactions sent by one of these addresses, if not empty.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub from: HashSet<Address>,
+    /// Only match transactions that call one of these addresses, if not empty.
+    ///
+    /// Contract creations never match.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub to: HashSet<Address>,
+    /// Only match transactions whose max fee per gas, or gas price for legacy transactions, is at
+    /// least this value.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub min_gas_price: Option<u128>,
+    /// Only match transactions of one of these types, if not empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "alloy_serde::quantity::vec")]
+    pub tx_types: Vec<u8>,
+    /// Whether to yield full transactions instead of hashes.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub full_transactions: bool,
+}
+
+impl PendingTransactionsFilter {
+    /// The serialized field names.
+    const FIELDS: &'static [&'static str] =
+        &["from", "to", "minGasPrice", "txTypes", "fullTransactions"];
+
+    /// Returns `true` if no criteria are set, so every transaction matches.
+    pub fn matches_all(&self) -> bool {
+        self.from.is_empty() &&
+            self.to.is_empty() &&
+            self.min_gas_price.is_none() &&
+            self.tx_types.is_empty()
+    }
+
+    /// Returns `true` if a transaction with the given properties matches all criteria.
+    pub fn matches(
+        &self,
+        from: Address,
+        to: Option<Address>,
+        max_fee_per_gas: u128,
+        tx_type: u8,
+    ) -> bool {
+        (self.from.is_empty() || self.from.contains(&from)) &&
+            (self.to.is_empty() || to.is_some_and(|to| self.to.contains(&to))) &&
+            self.min_gas_price.map_or(true, |min| max_fee_per_gas >= min) &&
+            (self.tx_types.is_empty() || self.tx_types.contains(&tx_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn deserialize_subscription_params() {
+        let params: SubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, SubscriptionParams::Bool(true));
+
+        let params: SubscriptionParams =
+            serde_json::from_str(r#"{"address":"0x00000000000000000000000000000000000000aa"}"#)
+                .unwrap();
+        assert!(matches!(params, SubscriptionParams::Logs(_)));
+
+        let params: SubscriptionParams = serde_json::from_str(
+            r#"{"to":["0x00000000000000000000000000000000000000aa"],"minGasPrice":"0x3b9aca00","txTypes":["0x2"],"fullTransactions":true}"#,
+        )
+        .unwrap();
+        let SubscriptionParams::PendingTransactions(filter) = params else {
+            panic!("expected pending transactions filter")
+        };
+        assert_eq!(filter.min_gas_price, Some(1_000_000_000));
+        assert_eq!(filter.tx_types, vec![2]);
+        assert!(filter.full_transactions);
+
+        assert!(serde_json::from_str::<SubscriptionParams>(r#"{"to":[],"topics":[]}"#).is_err());
+    }
+
+    #[test]
+    fn pending_transactions_filter_matches() {
+        let alice = address!("00000000000000000000000000000000000000aa");
+        let bob = address!("00000000000000000000000000000000000000bb");
+
+        let filter = PendingTransactionsFilter::default();
+        assert!(filter.matches_all());
+        assert!(filter.matches(alice, None, 0, 0));
+
+        let filter = PendingTransactionsFilter {
+            to: HashSet::from([bob]),
+            min_gas_price: Some(10),
+            tx_types: vec![2],
+            ..Default::default()
+        };
+        assert!(!filter.matches_all());
+        assert!(filter.matches(alice, Some(bob), 10, 2));
+        assert!(!filter.matches(alice, None, 10, 2));
+        assert!(!filter.matches(alice, Some(alice), 10, 2));
+        assert!(!filter.matches(alice, Some(bob), 9, 2));
+        assert!(!filter.matches(alice, Some(bob), 10, 0));
+    }
+}
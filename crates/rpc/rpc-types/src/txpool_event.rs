@@ -0,0 +1,97 @@
+//! Types of the `txpool_subscribe` subscription
+
+use crate::TxpoolSubpool;
+use alloy_primitives::{TxHash, B256};
+use serde::{Deserialize, Serialize};
+
+/// An event of the transaction pool, as emitted by the `txpool_subscribe` subscription.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TxpoolEvent {
+    /// A transaction was added to the pool.
+    #[serde(rename_all = "camelCase")]
+    Added {
+        /// The hash of the transaction.
+        hash: TxHash,
+        /// The subpool the transaction was added to.
+        subpool: TxpoolSubpool,
+    },
+    /// A transaction was replaced by another transaction of the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The hash of the replaced transaction.
+        hash: TxHash,
+        /// The hash of the transaction that replaced it.
+        replaced_by: TxHash,
+    },
+    /// A transaction was removed from the pool without being mined.
+    #[serde(rename_all = "camelCase")]
+    Dropped {
+        /// The hash of the transaction.
+        hash: TxHash,
+        /// Why the transaction was dropped.
+        reason: TxpoolDropReason,
+    },
+    /// A queued transaction was moved to the pending subpool.
+    #[serde(rename_all = "camelCase")]
+    Promoted {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
+    /// A transaction was included in a canonical block.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The hash of the transaction.
+        hash: TxHash,
+        /// The hash of the block that includes the transaction.
+        block_hash: B256,
+    },
+}
+
+/// Why a transaction was dropped, see [`TxpoolEvent::Dropped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolDropReason {
+    /// The transaction was evicted because of the pool limits, or removed on request.
+    Discarded,
+    /// The transaction is invalid and can never be included.
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_event() {
+        let event =
+            TxpoolEvent::Added { hash: TxHash::with_last_byte(1), subpool: TxpoolSubpool::Queued };
+        let json = serde_json::json!({
+            "type": "added",
+            "hash": TxHash::with_last_byte(1),
+            "subpool": "queued"
+        });
+        assert_eq!(serde_json::to_value(&event).unwrap(), json);
+        assert_eq!(serde_json::from_value::<TxpoolEvent>(json).unwrap(), event);
+
+        let event = TxpoolEvent::Dropped {
+            hash: TxHash::with_last_byte(2),
+            reason: TxpoolDropReason::Invalid,
+        };
+        let json = serde_json::json!({
+            "type": "dropped",
+            "hash": TxHash::with_last_byte(2),
+            "reason": "invalid"
+        });
+        assert_eq!(serde_json::to_value(&event).unwrap(), json);
+        assert_eq!(serde_json::from_value::<TxpoolEvent>(json).unwrap(), event);
+
+        let event = TxpoolEvent::Replaced {
+            hash: TxHash::with_last_byte(3),
+            replaced_by: TxHash::with_last_byte(4),
+        };
+        let json = serde_json::to_value(event).unwrap();
+        assert_eq!(json["type"], "replaced");
+        assert_eq!(json["replacedBy"], serde_json::json!(TxHash::with_last_byte(4)));
+    }
+}
@@ -0,0 +1,85 @@
+//! Filters and paging of the txpool content endpoints
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// Filters and paging of `txpool_content` and `txpool_inspect`.
+///
+/// The matching transactions are ordered by subpool, pending first, then by sender and nonce, and
+/// the page is taken in this order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolQuery {
+    /// Only include the transactions of these senders, all senders if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub senders: Vec<Address>,
+    /// Only include the transactions of these EIP-2718 types, all types if empty.
+    #[serde(default, with = "alloy_serde::quantity::vec", skip_serializing_if = "Vec::is_empty")]
+    pub tx_types: Vec<u8>,
+    /// Only include the transactions of this subpool, both subpools if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpool: Option<TxpoolSubpool>,
+    /// The number of matching transactions to skip.
+    #[serde(default, with = "alloy_serde::quantity")]
+    pub offset: u64,
+    /// The maximum number of transactions to include, all if unset.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl TxpoolQuery {
+    /// Returns `true` if the transactions of the given subpool are included.
+    pub fn includes_subpool(&self, subpool: TxpoolSubpool) -> bool {
+        self.subpool.map_or(true, |included| included == subpool)
+    }
+
+    /// Returns `true` if a transaction of the given sender and type matches the filters.
+    pub fn matches(&self, sender: &Address, tx_type: u8) -> bool {
+        (self.senders.is_empty() || self.senders.contains(sender)) &&
+            (self.tx_types.is_empty() || self.tx_types.contains(&tx_type))
+    }
+}
+
+/// A subpool of the transaction pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSubpool {
+    /// Transactions that are ready for inclusion in the next block.
+    Pending,
+    /// Transactions that are parked until they can be included, e.g. because of a nonce gap.
+    Queued,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn serde_txpool_query() {
+        let json = serde_json::json!({
+            "senders": ["0x000000000000000000000000000000000000dead"],
+            "txTypes": ["0x2", "0x3"],
+            "subpool": "queued",
+            "offset": "0x64",
+            "limit": "0x32"
+        });
+        let query: TxpoolQuery = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            query,
+            TxpoolQuery {
+                senders: vec![address!("000000000000000000000000000000000000dead")],
+                tx_types: vec![2, 3],
+                subpool: Some(TxpoolSubpool::Queued),
+                offset: 100,
+                limit: Some(50),
+            }
+        );
+        assert_eq!(serde_json::to_value(&query).unwrap(), json);
+
+        let query: TxpoolQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query, TxpoolQuery::default());
+        assert!(query.includes_subpool(TxpoolSubpool::Pending));
+        assert!(query.matches(&Address::ZERO, 0));
+    }
+}
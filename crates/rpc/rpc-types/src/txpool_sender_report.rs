@@ -0,0 +1,119 @@
+//! Types of the `txpool_senderReport` endpoint
+
+use alloy_primitives::{Address, TxHash};
+use serde::{Deserialize, Serialize};
+
+/// The state of the transactions of a sender in the pool, as returned by `txpool_senderReport`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSenderReport {
+    /// The sender.
+    pub sender: Address,
+    /// The nonce of the sender's account at the block the pool tracks.
+    #[serde(with = "alloy_serde::quantity")]
+    pub on_chain_nonce: u64,
+    /// The nonces of the sender's pending transactions.
+    #[serde(with = "alloy_serde::quantity::vec")]
+    pub pending_nonces: Vec<u64>,
+    /// The nonces of the sender's transactions that aren't pending.
+    #[serde(with = "alloy_serde::quantity::vec")]
+    pub queued_nonces: Vec<u64>,
+    /// The nonces without a transaction in the pool, between the on-chain nonce and the highest
+    /// nonce of the sender's transactions.
+    pub nonce_gaps: Vec<TxpoolNonceGap>,
+    /// The transaction with the lowest nonce that isn't pending, which holds back all
+    /// transactions of the sender with higher nonces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stuck: Option<TxpoolStuckTransaction>,
+}
+
+/// A range of missing nonces of a [`TxpoolSenderReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolNonceGap {
+    /// The first missing nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from: u64,
+    /// The last missing nonce.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to: u64,
+}
+
+/// The transaction of a [`TxpoolSenderReport`] that holds back the sender's other transactions,
+/// with the nonce that must be sent or the fees a replacement must at least pay.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolStuckTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// Why the transaction isn't pending.
+    pub reason: TxpoolStuckReason,
+    /// The nonce of the missing transaction that holds back this one, if the reason is a
+    /// [`TxpoolStuckReason::NonceGap`].
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub missing_nonce: Option<u64>,
+    /// The lowest max fee per gas of a replacement, which covers the price bump and the base fee
+    /// of the pending block. The replacement fees are omitted for a nonce gap, since a
+    /// replacement doesn't fill it.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub min_max_fee_per_gas: Option<u128>,
+    /// The lowest max priority fee per gas of a replacement, if it's checked.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub min_max_priority_fee_per_gas: Option<u128>,
+    /// The lowest max fee per blob gas of a replacement of a blob transaction, which covers the
+    /// price bump and the blob fee of the pending block.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub min_max_fee_per_blob_gas: Option<u128>,
+}
+
+/// Why a [`TxpoolStuckTransaction`] isn't pending.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolStuckReason {
+    /// A transaction with a lower nonce is missing, see [`TxpoolSenderReport::nonce_gaps`].
+    NonceGap,
+    /// The sender can't afford the transaction on top of the transactions with lower nonces.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit,
+    /// The max fee per gas is below the base fee of the pending block.
+    FeeCapBelowBaseFee,
+    /// The max fee per blob gas is below the blob fee of the pending block.
+    BlobFeeCapBelowBlobFee,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_sender_report() {
+        let report = TxpoolSenderReport {
+            sender: Address::with_last_byte(1),
+            on_chain_nonce: 4,
+            pending_nonces: vec![4],
+            queued_nonces: vec![6],
+            nonce_gaps: vec![TxpoolNonceGap { from: 5, to: 5 }],
+            stuck: Some(TxpoolStuckTransaction {
+                hash: TxHash::with_last_byte(2),
+                nonce: 6,
+                reason: TxpoolStuckReason::NonceGap,
+                missing_nonce: Some(5),
+                min_max_fee_per_gas: None,
+                min_max_priority_fee_per_gas: None,
+                min_max_fee_per_blob_gas: None,
+            }),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["pendingNonces"], serde_json::json!(["0x4"]));
+        assert_eq!(json["nonceGaps"][0]["from"], "0x5");
+        assert_eq!(json["stuck"]["reason"], "nonceGap");
+        assert_eq!(json["stuck"]["missingNonce"], "0x5");
+        assert!(json["stuck"].get("minMaxFeePerGas").is_none());
+        assert!(json["stuck"].get("minMaxPriorityFeePerGas").is_none());
+        assert_eq!(serde_json::from_value::<TxpoolSenderReport>(json).unwrap(), report);
+    }
+}
@@ -0,0 +1,123 @@
+//! Types of the `admin_exportTxpool` and `admin_importTxpool` endpoints
+
+use alloy_primitives::{Address, Bytes, TxHash, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The maximum number of transactions `admin_exportTxpool` returns in one page, and that
+/// `admin_importTxpool` accepts in one call.
+pub const MAX_TXPOOL_SNAPSHOT_PAGE_SIZE: usize = 1_000;
+
+/// The state of the transaction pool, as exported by `admin_exportTxpool`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSnapshot {
+    /// The hash of the block the pool was tracking.
+    pub block_hash: B256,
+    /// The number of the block the pool was tracking.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The base fee of the pending block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub pending_basefee: u64,
+    /// The blob fee of the pending block, if blob transactions are enabled.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub pending_blob_fee: Option<u128>,
+    /// The transactions of the pool, ordered by sender and nonce.
+    ///
+    /// `admin_exportTxpool` returns one page of the transactions per call.
+    pub transactions: Vec<TxpoolSnapshotTransaction>,
+}
+
+/// A transaction of a [`TxpoolSnapshot`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolSnapshotTransaction {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The subpool the transaction was in.
+    pub subpool: TxpoolSnapshotSubpool,
+    /// Where the transaction came from.
+    pub origin: TxpoolTransactionOrigin,
+    /// The tip per gas the transaction pays at the pending base fee, which the pool ranks pending
+    /// transactions by. Unset if the transaction can't pay the pending base fee.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub effective_tip: Option<u128>,
+    /// How long the transaction has been in the pool, in seconds.
+    #[serde(with = "alloy_serde::quantity")]
+    pub age: u64,
+    /// The EIP-2718 encoded transaction, including the sidecar of blob transactions.
+    pub raw: Bytes,
+}
+
+/// A subpool of the transaction pool, as recorded in a [`TxpoolSnapshot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolSnapshotSubpool {
+    /// Transactions that are ready for inclusion in the next block.
+    Pending,
+    /// Transactions with a nonce gap or that the sender can't afford.
+    Queued,
+    /// Transactions that can't pay the pending base fee.
+    BaseFee,
+    /// Blob transactions that can't pay the pending base fee or blob fee.
+    Blob,
+}
+
+/// Where a transaction of the pool came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxpoolTransactionOrigin {
+    /// Submitted to the node, e.g. via RPC.
+    Local,
+    /// Received from the network.
+    External,
+    /// Submitted to the node and not propagated.
+    Private,
+}
+
+/// The outcome of `admin_importTxpool`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolImportOutcome {
+    /// The hashes of the transactions that were added to the pool.
+    pub imported: Vec<TxHash>,
+    /// The transactions that were rejected, with the reason.
+    pub failed: BTreeMap<TxHash, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_snapshot() {
+        let snapshot = TxpoolSnapshot {
+            block_hash: B256::with_last_byte(1),
+            block_number: 16,
+            pending_basefee: 7,
+            pending_blob_fee: None,
+            transactions: vec![TxpoolSnapshotTransaction {
+                hash: TxHash::with_last_byte(2),
+                sender: Address::with_last_byte(3),
+                nonce: 1,
+                subpool: TxpoolSnapshotSubpool::BaseFee,
+                origin: TxpoolTransactionOrigin::External,
+                effective_tip: None,
+                age: 60,
+                raw: Bytes::from_static(&[0x02]),
+            }],
+        };
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["blockNumber"], "0x10");
+        assert_eq!(json["transactions"][0]["subpool"], "baseFee");
+        assert_eq!(json["transactions"][0]["origin"], "external");
+        assert!(json["transactions"][0].get("effectiveTip").is_none());
+        assert_eq!(serde_json::from_value::<TxpoolSnapshot>(json).unwrap(), snapshot);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::result::ToRpcResult;
+use crate::{
+    eth::utils::recover_raw_transaction,
+    result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult},
+};
 use alloy_genesis::ChainConfig;
 use alloy_primitives::B256;
 use async_trait::async_trait;
@@ -9,31 +12,46 @@ use reth_network_peers::{AnyNode, NodeRecord};
 use reth_rpc_api::AdminApiServer;
 use reth_rpc_types::{
     admin::{EthProtocolInfo, NodeInfo, Ports, ProtocolInfo},
-    PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
+    CapabilityBandwidthInfo, NatInfo, PeerBandwidth, PeerEthProtocolInfo, PeerInfo,
+    PeerNetworkInfo, PeerProtocolsInfo, TxpoolImportOutcome, TxpoolSnapshot, TxpoolSnapshotSubpool,
+    TxpoolSnapshotTransaction, TxpoolTransactionOrigin, MAX_TXPOOL_SNAPSHOT_PAGE_SIZE,
 };
-use std::sync::Arc;
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{PoolTransaction, SubPool, TransactionOrigin, TransactionPool};
+use std::{sync::Arc, time::UNIX_EPOCH};
+use tokio::sync::oneshot;
 
 /// `admin` API implementation.
 ///
 /// This type provides the functionality for handling `admin` related requests.
-pub struct AdminApi<N> {
+pub struct AdminApi<N, Pool> {
     /// An interface to interact with the network
     network: N,
+    /// The transaction pool, for exporting and importing its state.
+    pool: Pool,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// The type that can spawn tasks, to export the pool without blocking the server.
+    task_spawner: Box<dyn TaskSpawner>,
 }
 
-impl<N> AdminApi<N> {
+impl<N, Pool> AdminApi<N, Pool> {
     /// Creates a new instance of `AdminApi`.
-    pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+    pub const fn new(
+        network: N,
+        pool: Pool,
+        chain_spec: Arc<ChainSpec>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { network, pool, chain_spec, task_spawner }
     }
 }
 
 #[async_trait]
-impl<N> AdminApiServer for AdminApi<N>
+impl<N, Pool> AdminApiServer for AdminApi<N, Pool>
 where
     N: NetworkInfo + Peers + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `admin_addPeer`
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool> {
@@ -91,6 +109,29 @@ where
         Ok(peers)
     }
 
+    /// Handler for `admin_peerBandwidth`
+    async fn peer_bandwidth(&self) -> RpcResult<Vec<PeerBandwidth>> {
+        let peers = self.network.get_all_peers().await.to_rpc_result()?;
+        let peers = peers
+            .into_iter()
+            .map(|peer| PeerBandwidth {
+                id: peer.remote_id,
+                capabilities: peer
+                    .bandwidth
+                    .snapshot()
+                    .into_iter()
+                    .map(|usage| CapabilityBandwidthInfo {
+                        capability: usage.capability.to_string(),
+                        ingress: usage.ingress,
+                        egress: usage.egress,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(peers)
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -127,6 +168,104 @@ where
         Ok(node_info)
     }
 
+    /// Handler for `admin_natStatus`
+    fn nat_status(&self) -> RpcResult<Option<NatInfo>> {
+        let info = self.network.nat_status().map(|status| NatInfo {
+            resolver: status.resolver.to_string(),
+            external_address: status.external_addr,
+            last_refresh: status
+                .last_refresh
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_secs()),
+            consecutive_failures: status.consecutive_failures,
+        });
+        Ok(info)
+    }
+
+    /// Handler for `admin_refreshNat`
+    fn refresh_nat(&self) -> RpcResult<bool> {
+        if self.network.nat_status().is_none() {
+            return Ok(false)
+        }
+        self.network.refresh_nat();
+        Ok(true)
+    }
+
+    /// Handler for `admin_exportTxpool`
+    async fn export_txpool(
+        &self,
+        offset: Option<u64>,
+        limit: Option<u64>,
+    ) -> RpcResult<TxpoolSnapshot> {
+        let offset = usize::try_from(offset.unwrap_or_default()).unwrap_or(usize::MAX);
+        let limit = limit.map_or(MAX_TXPOOL_SNAPSHOT_PAGE_SIZE, |limit| {
+            usize::try_from(limit).unwrap_or(usize::MAX).min(MAX_TXPOOL_SNAPSHOT_PAGE_SIZE)
+        });
+
+        // encoding blob transactions reads their sidecars from the blob store
+        let pool = self.pool.clone();
+        let (tx, rx) = oneshot::channel();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(snapshot_page(&pool, offset, limit));
+        }));
+        rx.await.map_err(|_| internal_rpc_err("failed to export the transaction pool"))
+    }
+
+    /// Handler for `admin_importTxpool`
+    async fn import_txpool(&self, snapshot: TxpoolSnapshot) -> RpcResult<TxpoolImportOutcome> {
+        let mut transactions = snapshot.transactions;
+        if transactions.len() > MAX_TXPOOL_SNAPSHOT_PAGE_SIZE {
+            return Err(invalid_params_rpc_err(format!(
+                "at most {MAX_TXPOOL_SNAPSHOT_PAGE_SIZE} transactions can be imported at once"
+            )))
+        }
+        // add transactions in nonce order so they don't end up queued behind a nonce gap
+        transactions.sort_by_key(|tx| (tx.sender, tx.nonce));
+
+        let mut outcome = TxpoolImportOutcome::default();
+        let mut recovered = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            match recover_raw_transaction(tx.raw) {
+                Ok(transaction) => {
+                    let origin = match tx.origin {
+                        TxpoolTransactionOrigin::Local => TransactionOrigin::Local,
+                        TxpoolTransactionOrigin::External => TransactionOrigin::External,
+                        TxpoolTransactionOrigin::Private => TransactionOrigin::Private,
+                    };
+                    let transaction =
+                        <Pool::Transaction>::from_recovered_pooled_transaction(transaction);
+                    recovered.push((tx.hash, origin, transaction));
+                }
+                Err(err) => {
+                    outcome.failed.insert(tx.hash, err.to_string());
+                }
+            }
+        }
+
+        // add consecutive transactions with the same origin as one batch
+        let mut recovered = recovered.into_iter().peekable();
+        while let Some((hash, origin, transaction)) = recovered.next() {
+            let (mut hashes, mut batch) = (vec![hash], vec![transaction]);
+            while let Some((hash, _, transaction)) =
+                recovered.next_if(|(_, next_origin, _)| *next_origin == origin)
+            {
+                hashes.push(hash);
+                batch.push(transaction);
+            }
+            let results = self.pool.add_transactions(origin, batch).await;
+            for (hash, result) in hashes.into_iter().zip(results) {
+                match result {
+                    Ok(hash) => outcome.imported.push(hash),
+                    Err(err) => {
+                        outcome.failed.insert(hash, err.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
@@ -136,7 +275,54 @@ where
     }
 }
 
-impl<N> std::fmt::Debug for AdminApi<N> {
+/// Returns the page of a snapshot of the pool that starts at the given offset into its
+/// transactions.
+fn snapshot_page<Pool: TransactionPool>(
+    pool: &Pool,
+    offset: usize,
+    limit: usize,
+) -> TxpoolSnapshot {
+    let info = pool.block_info();
+    let transactions = pool
+        .all_transactions_with_subpool()
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .filter_map(|(tx, subpool)| {
+            // the transaction may have been removed from the pool in the meantime
+            let raw = pool.get_pooled_transaction_element(*tx.hash())?.envelope_encoded();
+            Some(TxpoolSnapshotTransaction {
+                hash: *tx.hash(),
+                sender: tx.sender(),
+                nonce: tx.nonce(),
+                subpool: match subpool {
+                    SubPool::Pending => TxpoolSnapshotSubpool::Pending,
+                    SubPool::Queued => TxpoolSnapshotSubpool::Queued,
+                    SubPool::BaseFee => TxpoolSnapshotSubpool::BaseFee,
+                    SubPool::Blob => TxpoolSnapshotSubpool::Blob,
+                },
+                origin: match tx.origin {
+                    TransactionOrigin::Local => TxpoolTransactionOrigin::Local,
+                    TransactionOrigin::External => TxpoolTransactionOrigin::External,
+                    TransactionOrigin::Private => TxpoolTransactionOrigin::Private,
+                },
+                effective_tip: tx.effective_tip_per_gas(info.pending_basefee),
+                age: tx.timestamp.elapsed().as_secs(),
+                raw,
+            })
+        })
+        .collect();
+
+    TxpoolSnapshot {
+        block_hash: info.last_seen_block_hash,
+        block_number: info.last_seen_block_number,
+        pending_basefee: info.pending_basefee,
+        pending_blob_fee: info.pending_blob_fee,
+        transactions,
+    }
+}
+
+impl<N, Pool> std::fmt::Debug for AdminApi<N, Pool> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
@@ -1,23 +1,37 @@
 use crate::{
     eth::{
+        cache::RpcResponseCache,
         error::{EthApiError, EthResult},
+        revert::RevertDecoder,
         revm_utils::prepare_call_env,
-        EthTransactions,
+        send_result_chunk, EthTransactions,
     },
-    result::{internal_rpc_err, ToRpcResult},
+    result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult},
+    witness::execution_witness,
     EthApiSpec,
 };
+use alloy_json_abi::JsonAbi;
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::{RpcResult, StringError, SubscriptionResult},
+    PendingSubscriptionSink,
+};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256, U256,
+    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumber, BlockNumberOrTag,
+    Bytes, TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProvider, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    state_change::{
+        apply_beacon_root_contract_call, apply_blockhashes_update,
+        apply_withdrawal_requests_contract_call, post_block_balance_increments,
+    },
+    witness::WitnessRecorder,
 };
-use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
     state::EvmOverrides,
@@ -25,25 +39,41 @@ use reth_rpc_types::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BlockError, Bundle, ExecutionWitness, ResultChunk, RichBlock, StateContext, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
-    db::CacheDB,
-    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    db::{CacheDB, DbAccount},
+    primitives::{
+        db::{Database, DatabaseCommit},
+        BlockEnv, Bytecode, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, HashMap,
+    },
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
 use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    AcquireError, OwnedSemaphorePermit,
+};
+
+/// The number of traces of `debug_subscribeTraceBlock` that are buffered for a client before
+/// tracing is aborted.
+const STREAMED_TRACES_BUFFER: usize = 16;
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Provider, Eth> {
     inner: Arc<DebugApiInner<Provider, Eth>>,
+    /// The maximum number of threads that trace the transactions of a block in parallel.
+    trace_block_parallelism: usize,
+    /// Caches the `debug_traceTransaction` responses for finalized blocks.
+    response_cache: Option<RpcResponseCache>,
+    /// The registry that `debug_addRevertErrors` adds custom error definitions to.
+    revert_decoder: Option<RevertDecoder>,
 }
 
 // === impl DebugApi ===
@@ -52,7 +82,28 @@ impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
         let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
-        Self { inner }
+        Self { inner, trace_block_parallelism: 1, response_cache: None, revert_decoder: None }
+    }
+
+    /// Sets the maximum number of threads that trace the transactions of a block in parallel.
+    ///
+    /// If this is greater than one, the block is executed once to capture the state before each
+    /// chunk of transactions, and the chunks are then traced in parallel on the blocking pool.
+    pub const fn with_trace_block_parallelism(mut self, parallelism: usize) -> Self {
+        self.trace_block_parallelism = parallelism;
+        self
+    }
+
+    /// Sets the cache for the `debug_traceTransaction` responses for finalized blocks.
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
+    /// Sets the registry that `debug_addRevertErrors` adds custom error definitions to.
+    pub fn with_revert_decoder(mut self, revert_decoder: RevertDecoder) -> Self {
+        self.revert_decoder = Some(revert_decoder);
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -88,42 +139,141 @@ where
         }
 
         // replay all transactions of the block
+        let block_hash = at.as_block_hash();
+        let envs = transaction_envs(&transactions, &cfg, &block_env);
+        let parallelism = self.trace_block_parallelism.min(transactions.len());
+        if parallelism <= 1 {
+            let this = self.clone();
+            return self
+                .eth_api()
+                .spawn_with_state_at_block(at, move |state| {
+                    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                    this.trace_transactions(&mut db, &transactions, &envs, 0, block_hash, &opts)
+                })
+                .await
+        }
+
+        // execute the block once without tracing, to capture the state before the first
+        // transaction of each chunk
+        let chunk_size = transactions.len().div_ceil(parallelism);
+        let last_chunk_start = (transactions.len() - 1) / chunk_size * chunk_size;
+        let prefix_envs = envs[..last_chunk_start].to_vec();
         let this = self.clone();
-        self.eth_api()
+        let snapshots = self
+            .eth_api()
             .spawn_with_state_at_block(at, move |state| {
-                let block_hash = at.as_block_hash();
-                let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
-                let mut transactions = transactions.into_iter().enumerate().peekable();
-                while let Some((index, tx)) = transactions.next() {
-                    let tx_hash = tx.hash;
-                    let tx = tx_env_with_recovered(&tx);
-                    let env = EnvWithHandlerCfg {
-                        env: Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx),
-                        handler_cfg: cfg.handler_cfg,
-                    };
-                    let (result, state_changes) = this.trace_transaction(
-                        opts.clone(),
-                        env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash,
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                    )?;
-
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
-                    if transactions.peek().is_some() {
-                        // need to apply the state changes of this transaction before executing the
-                        // next transaction
-                        db.commit(state_changes)
+                let mut snapshots = Vec::with_capacity(parallelism);
+                for (index, env) in prefix_envs.into_iter().enumerate() {
+                    if index % chunk_size == 0 {
+                        snapshots.push(CachedState::new(&db));
                     }
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
                 }
-
-                Ok(results)
+                snapshots.push(CachedState::new(&db));
+                Ok(snapshots)
             })
-            .await
+            .await?;
+
+        // trace the chunks in parallel on the blocking pool, each on top of the state before its
+        // first transaction
+        let chunks = snapshots
+            .into_iter()
+            .zip(transactions.chunks(chunk_size).zip(envs.chunks(chunk_size)))
+            .enumerate()
+            .map(|(chunk, (snapshot, (transactions, envs)))| {
+                let this = self.clone();
+                let (transactions, envs, opts) =
+                    (transactions.to_vec(), envs.to_vec(), opts.clone());
+                self.eth_api().spawn_with_state_at_block(at, move |state| {
+                    let mut db = snapshot.into_db(StateProviderDatabase::new(state));
+                    this.trace_transactions(
+                        &mut db,
+                        &transactions,
+                        &envs,
+                        chunk * chunk_size,
+                        block_hash,
+                        &opts,
+                    )
+                })
+            });
+        let results = futures::future::try_join_all(chunks).await?;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Traces the given transactions one after another on top of the given database, applying the
+    /// state changes of each transaction before tracing the next one.
+    ///
+    /// The index of the first transaction in the block is `first_index`.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn trace_transactions<S: StateProvider>(
+        &self,
+        db: &mut CacheDB<StateProviderDatabase<S>>,
+        transactions: &[TransactionSignedEcRecovered],
+        envs: &[EnvWithHandlerCfg],
+        first_index: usize,
+        block_hash: Option<B256>,
+        opts: &GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let mut results = Vec::with_capacity(transactions.len());
+        self.trace_transactions_with(
+            db,
+            transactions,
+            envs,
+            first_index,
+            block_hash,
+            opts,
+            |result| {
+                results.push(result);
+                true
+            },
+        )?;
+        Ok(results)
+    }
+
+    /// Like [`Self::trace_transactions`], but passes the trace of each transaction to `on_result`
+    /// instead of collecting them. Stops tracing if `on_result` returns `false`.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    #[allow(clippy::too_many_arguments)]
+    fn trace_transactions_with<S: StateProvider>(
+        &self,
+        db: &mut CacheDB<StateProviderDatabase<S>>,
+        transactions: &[TransactionSignedEcRecovered],
+        envs: &[EnvWithHandlerCfg],
+        first_index: usize,
+        block_hash: Option<B256>,
+        opts: &GethDebugTracingOptions,
+        mut on_result: impl FnMut(TraceResult) -> bool,
+    ) -> EthResult<()> {
+        let mut transactions = transactions.iter().zip(envs).enumerate().peekable();
+        while let Some((index, (tx, env))) = transactions.next() {
+            let tx_hash = tx.hash;
+            let (result, state_changes) = self.trace_transaction(
+                opts.clone(),
+                env.clone(),
+                db,
+                Some(TransactionContext {
+                    block_hash,
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(first_index + index),
+                }),
+            )?;
+
+            if !on_result(TraceResult::Success { result, tx_hash: Some(tx_hash) }) {
+                break
+            }
+            if transactions.peek().is_some() {
+                // need to apply the state changes of this transaction before executing the
+                // next transaction
+                db.commit(state_changes)
+            }
+        }
+
+        Ok(())
     }
 
     /// Replays the given block and returns the trace of each transaction.
@@ -168,12 +318,12 @@ where
         self.trace_block_with(parent.into(), transactions, cfg, block_env, opts).await
     }
 
-    /// Replays a block and returns the trace of each transaction.
-    pub async fn debug_trace_block(
+    /// Returns the hash of the parent of the block, whose state the block is replayed on, and the
+    /// transactions and environment of the block.
+    async fn block_to_replay(
         &self,
         block_id: BlockId,
-        opts: GethDebugTracingOptions,
-    ) -> EthResult<Vec<TraceResult>> {
+    ) -> EthResult<(B256, Vec<TransactionSignedEcRecovered>, CfgEnvWithHandlerCfg, BlockEnv)> {
         let block_hash = self
             .inner
             .provider
@@ -188,16 +338,179 @@ where
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
         // we need to get the state of the parent block because we're replaying this block on top of
         // its parent block's state
-        let state_at = block.parent_hash;
+        Ok((block.parent_hash, block.into_transactions_ecrecovered().collect(), cfg, block_env))
+    }
 
-        self.trace_block_with(
-            state_at.into(),
-            block.into_transactions_ecrecovered().collect(),
-            cfg,
-            block_env,
-            opts,
-        )
-        .await
+    /// Replays a block and returns the trace of each transaction.
+    pub async fn debug_trace_block(
+        &self,
+        block_id: BlockId,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let (state_at, transactions, cfg, block_env) = self.block_to_replay(block_id).await?;
+        self.trace_block_with(state_at.into(), transactions, cfg, block_env, opts).await
+    }
+
+    /// Replays a block and sends the trace of each transaction to the channel as soon as it's
+    /// available, so that the traces of the block are never held in memory at once.
+    ///
+    /// The transactions are traced one after another, and tracing stops if the receiver is
+    /// dropped. The traces are sent without waiting for the receiver, so that a slow receiver
+    /// doesn't hold the tracing thread: if the channel is full, tracing is aborted with
+    /// [`EthApiError::SubscriptionLagged`].
+    pub async fn debug_trace_block_streamed(
+        &self,
+        block_id: BlockId,
+        opts: GethDebugTracingOptions,
+        sender: mpsc::Sender<TraceResult>,
+    ) -> EthResult<()> {
+        let (state_at, transactions, cfg, block_env) = self.block_to_replay(block_id).await?;
+        if transactions.is_empty() {
+            // nothing to trace
+            return Ok(())
+        }
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(state_at.into(), move |state| {
+                let envs = transaction_envs(&transactions, &cfg, &block_env);
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut lagged = false;
+                this.trace_transactions_with(
+                    &mut db,
+                    &transactions,
+                    &envs,
+                    0,
+                    Some(state_at),
+                    &opts,
+                    |result| match sender.try_send(result) {
+                        Ok(()) => true,
+                        Err(TrySendError::Full(_)) => {
+                            lagged = true;
+                            false
+                        }
+                        Err(TrySendError::Closed(_)) => false,
+                    },
+                )?;
+                if lagged {
+                    return Err(EthApiError::SubscriptionLagged)
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    /// Re-executes a block on top of the state of its parent block and returns the witness of the
+    /// state accessed by its execution.
+    pub async fn debug_execution_witness(&self, block_id: BlockId) -> EthResult<ExecutionWitness> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id_with_senders(block_id)
+            .await?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let block_hash = block.hash();
+        let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block_hash.into()).await?;
+        let total_difficulty = self
+            .inner
+            .provider
+            .header_td(&block_hash)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let chain_spec = self.inner.provider.chain_spec();
+        let parent_number = block.number.saturating_sub(1);
+
+        let this = self.clone();
+        let (mut witness, oldest_block_hash) = self
+            .eth_api()
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = CacheDB::new(WitnessRecorder::new(StateProviderDatabase::new(&state)));
+                let system_call_env = || {
+                    EnvWithHandlerCfg::new_with_cfg_env(
+                        cfg.clone(),
+                        block_env.clone(),
+                        Default::default(),
+                    )
+                };
+
+                // execute the block like the block executor: the pre-block system calls, the
+                // transactions, the post-block system calls and the balance increments
+                let mut evm = revm::Evm::builder()
+                    .with_db(&mut db)
+                    .with_env_with_handler_cfg(system_call_env())
+                    .build();
+                apply_beacon_root_contract_call(
+                    &chain_spec,
+                    block.timestamp,
+                    block.number,
+                    block.parent_beacon_block_root,
+                    &mut evm,
+                )
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+                drop(evm);
+                apply_blockhashes_update(
+                    &mut db,
+                    &chain_spec,
+                    block.timestamp,
+                    block.number,
+                    block.parent_hash,
+                )
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                for (signer, tx) in block.transactions_with_sender() {
+                    let tx = tx.clone().with_signer(*signer);
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                if chain_spec.is_prague_active_at_timestamp(block.timestamp) {
+                    let mut evm = revm::Evm::builder()
+                        .with_db(&mut db)
+                        .with_env_with_handler_cfg(system_call_env())
+                        .build();
+                    apply_withdrawal_requests_contract_call(&mut evm)
+                        .map_err(|err| EthApiError::Internal(err.into()))?;
+                }
+
+                let balance_increments = post_block_balance_increments(
+                    &chain_spec,
+                    block.number,
+                    block.difficulty,
+                    block.beneficiary,
+                    block.timestamp,
+                    total_difficulty,
+                    &block.ommers,
+                    block.withdrawals.as_ref().map(Withdrawals::as_ref),
+                );
+                for address in balance_increments.into_keys() {
+                    db.basic(address)?;
+                }
+
+                let accessed = db.db.into_accessed();
+                let oldest_block_hash = accessed.oldest_block_hash;
+                Ok((execution_witness(accessed, &state)?, oldest_block_hash))
+            })
+            .await?;
+
+        // the parent header is always needed for its state root
+        let oldest = oldest_block_hash.map_or(parent_number, |oldest| oldest.min(parent_number));
+        witness.headers = self
+            .inner
+            .provider
+            .headers_range(oldest..=parent_number)?
+            .iter()
+            .rev()
+            .map(|header| alloy_rlp::encode(header).into())
+            .collect();
+
+        Ok(witness)
     }
 
     /// Trace the transaction according to the provided options.
@@ -212,6 +525,17 @@ where
             None => return Err(EthApiError::TransactionNotFound),
             Some(res) => res,
         };
+
+        // the traces of transactions in finalized blocks can't change, so they can be served from
+        // the cache
+        let response_cache = self.response_cache_for_block(block.number)?;
+        let cache_key = response_cache.as_ref().and_then(|_| serde_json::to_string(&opts).ok());
+        if let Some((cache, opts)) = response_cache.as_ref().zip(cache_key.as_deref()) {
+            if let Some(trace) = cache.get_trace(tx_hash, opts) {
+                return Ok(trace)
+            }
+        }
+
         let (cfg, block_env, _) = self.inner.eth_api.evm_env_at(block.hash().into()).await?;
 
         // we need to get the state of the parent block because we're essentially replaying the
@@ -221,7 +545,8 @@ where
         let block_txs = block.into_transactions_ecrecovered();
 
         let this = self.clone();
-        self.inner
+        let trace = self
+            .inner
             .eth_api
             .spawn_with_state_at_block(state_at, move |state| {
                 // configure env for the target transaction
@@ -254,7 +579,38 @@ where
                 )
                 .map(|(trace, _)| trace)
             })
-            .await
+            .await?;
+
+        if let Some((cache, opts)) = response_cache.zip(cache_key) {
+            cache.insert_trace(tx_hash, opts, trace.clone());
+        }
+
+        Ok(trace)
+    }
+
+    /// Returns the deposit transactions of the given block, by hash.
+    #[cfg(feature = "optimism")]
+    async fn block_deposits(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<std::collections::HashMap<B256, reth_primitives::TransactionSigned>> {
+        let block = self
+            .inner
+            .eth_api
+            .block_by_id(block_id)
+            .await?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        Ok(deposits(&block.body))
+    }
+
+    /// Returns the response cache if it's enabled and the given block is finalized.
+    fn response_cache_for_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> EthResult<Option<RpcResponseCache>> {
+        let Some(cache) = &self.response_cache else { return Ok(None) };
+        let finalized = self.inner.provider.finalized_block_number()?;
+        Ok(finalized.is_some_and(|finalized| block_number <= finalized).then(|| cache.clone()))
     }
 
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
@@ -420,7 +776,8 @@ where
 
         let opts = opts.unwrap_or_default();
         let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
-        let GethDebugTracingCallOptions { tracing_options, mut state_overrides, .. } = opts;
+        let GethDebugTracingCallOptions { tracing_options, mut state_overrides, block_overrides } =
+            opts;
         let gas_limit = self.inner.eth_api.call_gas_limit();
 
         // we're essentially replaying the transactions in the block here, hence we need the state
@@ -469,7 +826,10 @@ where
                     let mut results = Vec::with_capacity(bundle.transactions.len());
                     let Bundle { transactions, block_override } = bundle;
 
-                    let block_overrides = block_override.map(Box::new);
+                    // the block overrides of the bundle take precedence over the block overrides
+                    // of the tracing options, which apply to all bundles
+                    let block_overrides =
+                        block_override.or_else(|| block_overrides.clone()).map(Box::new);
 
                     let mut transactions = transactions.into_iter().peekable();
                     while let Some(tx) = transactions.next() {
@@ -514,11 +874,11 @@ where
     /// Note: this does not apply any state overrides if they're configured in the `opts`.
     ///
     /// Caution: this is blocking and should be performed on a blocking task.
-    fn trace_transaction(
+    fn trace_transaction<S: StateProvider>(
         &self,
         opts: GethDebugTracingOptions,
         env: EnvWithHandlerCfg,
-        db: &mut CacheDB<StateProviderDatabase<StateProviderBox>>,
+        db: &mut CacheDB<StateProviderDatabase<S>>,
         transaction_context: Option<TransactionContext>,
     ) -> EthResult<(GethTrace, revm_primitives::EvmState)> {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
@@ -711,7 +1071,20 @@ where
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
         let _permit = self.acquire_trace_permit().await;
-        Ok(Self::debug_trace_raw_block(self, rlp_block, opts.unwrap_or_default()).await?)
+
+        #[cfg(feature = "optimism")]
+        let deposits = {
+            let block = Block::decode(&mut rlp_block.as_ref())
+                .map_err(|err| EthApiError::from(BlockError::RlpDecodeRawBlock(err)))?;
+            deposits(&block.body)
+        };
+
+        let traces = Self::debug_trace_raw_block(self, rlp_block, opts.unwrap_or_default()).await?;
+
+        #[cfg(feature = "optimism")]
+        let traces = with_deposit_fields_in_results(traces, &deposits);
+
+        Ok(traces)
     }
 
     /// Handler for `debug_traceBlockByHash`
@@ -721,7 +1094,13 @@ where
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
         let _permit = self.acquire_trace_permit().await;
-        Ok(Self::debug_trace_block(self, block.into(), opts.unwrap_or_default()).await?)
+        let traces = Self::debug_trace_block(self, block.into(), opts.unwrap_or_default()).await?;
+
+        #[cfg(feature = "optimism")]
+        let traces =
+            with_deposit_fields_in_results(traces, &self.block_deposits(block.into()).await?);
+
+        Ok(traces)
     }
 
     /// Handler for `debug_traceBlockByNumber`
@@ -731,7 +1110,53 @@ where
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>> {
         let _permit = self.acquire_trace_permit().await;
-        Ok(Self::debug_trace_block(self, block.into(), opts.unwrap_or_default()).await?)
+        let traces = Self::debug_trace_block(self, block.into(), opts.unwrap_or_default()).await?;
+
+        #[cfg(feature = "optimism")]
+        let traces =
+            with_deposit_fields_in_results(traces, &self.block_deposits(block.into()).await?);
+
+        Ok(traces)
+    }
+
+    /// Handler for `debug_subscribeTraceBlock`
+    async fn debug_subscribe_trace_block(
+        &self,
+        pending: PendingSubscriptionSink,
+        block: BlockId,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+
+        #[cfg(feature = "optimism")]
+        let deposits = self.block_deposits(block).await?;
+
+        // tracing is aborted if the client doesn't keep up with the buffered traces, and the
+        // permit is released once the block is traced
+        let (tx, mut rx) = mpsc::channel(STREAMED_TRACES_BUFFER);
+        let trace = async {
+            let _permit = self.acquire_trace_permit().await;
+            self.debug_trace_block_streamed(block, opts.unwrap_or_default(), tx).await
+        };
+        let send = async {
+            while let Some(result) = rx.recv().await {
+                #[cfg(feature = "optimism")]
+                let result = with_deposit_fields_in_result(result, &deposits);
+
+                if !send_result_chunk(&sink, &ResultChunk::new(vec![result])).await? {
+                    break
+                }
+            }
+            // dropping the receiver stops the tracing if the subscription was closed
+            drop(rx);
+            Ok::<_, StringError>(())
+        };
+        let (traced, sent) = futures::join!(trace, send);
+        sent?;
+        traced?;
+
+        send_result_chunk(&sink, &ResultChunk::<TraceResult>::done()).await?;
+        Ok(())
     }
 
     /// Handler for `debug_traceTransaction`
@@ -741,7 +1166,20 @@ where
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTrace> {
         let _permit = self.acquire_trace_permit().await;
-        Ok(Self::debug_trace_transaction(self, tx_hash, opts.unwrap_or_default()).await?)
+        let trace = Self::debug_trace_transaction(self, tx_hash, opts.unwrap_or_default()).await?;
+
+        #[cfg(feature = "optimism")]
+        let trace = match reth_provider::TransactionsProvider::transaction_by_hash(
+            &self.inner.provider,
+            tx_hash,
+        )
+        .map_err(EthApiError::from)?
+        {
+            Some(tx) => with_deposit_fields(trace, &tx),
+            None => trace,
+        };
+
+        Ok(trace)
     }
 
     /// Handler for `debug_traceCall`
@@ -765,6 +1203,21 @@ where
         Ok(Self::debug_trace_call_many(self, bundles, state_context, opts).await?)
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(&self, block_id: BlockId) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_execution_witness(self, block_id).await?)
+    }
+
+    /// Handler for `debug_addRevertErrors`
+    async fn debug_add_revert_errors(&self, abi: JsonAbi) -> RpcResult<usize> {
+        let decoder = self
+            .revert_decoder
+            .as_ref()
+            .ok_or(EthApiError::Unsupported("revert error decoding is not available"))?;
+        decoder.add_abi(&abi).map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -985,7 +1438,12 @@ impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> Clone for DebugApi<Provider, Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            trace_block_parallelism: self.trace_block_parallelism,
+            response_cache: self.response_cache.clone(),
+            revert_decoder: self.revert_decoder.clone(),
+        }
     }
 }
 
@@ -997,3 +1455,329 @@ struct DebugApiInner<Provider, Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+/// The state cached by a [`CacheDB`], detached from the database it was loaded from.
+///
+/// This is used to restore the state before a transaction of a block on another thread.
+#[derive(Debug)]
+struct CachedState {
+    accounts: HashMap<Address, DbAccount>,
+    contracts: HashMap<B256, Bytecode>,
+    block_hashes: HashMap<U256, B256>,
+}
+
+impl CachedState {
+    /// Captures the state cached by the given database.
+    fn new<DB>(db: &CacheDB<DB>) -> Self {
+        Self {
+            accounts: db.accounts.clone(),
+            contracts: db.contracts.clone(),
+            block_hashes: db.block_hashes.clone(),
+        }
+    }
+
+    /// Returns a [`CacheDB`] with the captured state on top of the given database.
+    fn into_db<DB>(self, db: DB) -> CacheDB<DB> {
+        let Self { accounts, contracts, block_hashes } = self;
+        CacheDB { accounts, contracts, logs: Vec::new(), block_hashes, db }
+    }
+}
+
+/// Returns the environments to execute the transactions of a block in.
+fn transaction_envs(
+    transactions: &[TransactionSignedEcRecovered],
+    cfg: &CfgEnvWithHandlerCfg,
+    block_env: &BlockEnv,
+) -> Vec<EnvWithHandlerCfg> {
+    transactions
+        .iter()
+        .map(|tx| EnvWithHandlerCfg {
+            env: Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx_env_with_recovered(tx)),
+            handler_cfg: cfg.handler_cfg,
+        })
+        .collect()
+}
+
+/// Adds the fields of a deposit transaction that have no equivalent in the trace of a regular
+/// transaction to the output of the default and call tracers: the minted value, the source hash
+/// and whether it is a system transaction.
+///
+/// The trace of any other transaction or tracer is returned unchanged.
+///
+/// The traces of deposits are JSON values then, so this is only applied to the responses of the
+/// RPC methods, the traces returned by [`DebugApi`] itself keep their native frames.
+#[cfg(feature = "optimism")]
+fn with_deposit_fields(trace: GethTrace, tx: &reth_primitives::TransactionSigned) -> GethTrace {
+    let reth_primitives::Transaction::Deposit(deposit) = &tx.transaction else { return trace };
+    if !matches!(trace, GethTrace::Default(_) | GethTrace::CallTracer(_)) {
+        return trace
+    }
+    let Ok(serde_json::Value::Object(mut fields)) = serde_json::to_value(&trace) else {
+        return trace
+    };
+    fields.insert("sourceHash".to_string(), serde_json::json!(deposit.source_hash));
+    fields.insert(
+        "mint".to_string(),
+        serde_json::json!(reth_primitives::U128::from(deposit.mint.unwrap_or_default())),
+    );
+    fields.insert("isSystemTx".to_string(), deposit.is_system_transaction.into());
+    GethTrace::JS(fields.into())
+}
+
+/// Returns the deposit transactions of the given transactions, by hash.
+#[cfg(feature = "optimism")]
+fn deposits(
+    transactions: &[reth_primitives::TransactionSigned],
+) -> std::collections::HashMap<B256, reth_primitives::TransactionSigned> {
+    transactions.iter().filter(|tx| tx.is_deposit()).map(|tx| (tx.hash, tx.clone())).collect()
+}
+
+/// Adds the deposit fields to the trace of the given result if it is the trace of one of the
+/// given deposits, see [`with_deposit_fields`].
+#[cfg(feature = "optimism")]
+fn with_deposit_fields_in_result(
+    result: TraceResult,
+    deposits: &std::collections::HashMap<B256, reth_primitives::TransactionSigned>,
+) -> TraceResult {
+    match result {
+        TraceResult::Success { result, tx_hash: Some(tx_hash) } => {
+            let result = match deposits.get(&tx_hash) {
+                Some(tx) => with_deposit_fields(result, tx),
+                None => result,
+            };
+            TraceResult::Success { result, tx_hash: Some(tx_hash) }
+        }
+        result => result,
+    }
+}
+
+/// Adds the deposit fields to the traces of the given deposits, see [`with_deposit_fields`].
+#[cfg(feature = "optimism")]
+fn with_deposit_fields_in_results(
+    results: Vec<TraceResult>,
+    deposits: &std::collections::HashMap<B256, reth_primitives::TransactionSigned>,
+) -> Vec<TraceResult> {
+    results.into_iter().map(|result| with_deposit_fields_in_result(result, deposits)).collect()
+}
+
+#[cfg(test)]
+mod trace_block_tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, EthApi, FeeHistoryCache,
+        FeeHistoryCacheConfig,
+    };
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, Header, Transaction, TxKind, TxLegacy,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, generate_keys, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// Returns a debug api with a block 1 of seven transactions that increment a counter.
+    fn counter_block_debug_api(
+    ) -> DebugApi<MockEthProvider, EthApi<MockEthProvider, TestPool, (), EthEvmConfig>> {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+
+        // increments the word in slot 0 and returns the new value
+        let counter = Address::with_last_byte(1);
+        provider.add_account(
+            counter,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[
+                0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52, 0x60,
+                0x20, 0x60, 0x00, 0xf3,
+            ])),
+        );
+
+        let key_pair = generate_keys(&mut rng, 1)[0];
+        let transactions = (0..7)
+            .map(|nonce| {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 100_000,
+                    to: TxKind::Call(counter),
+                    ..Default::default()
+                });
+                sign_tx_with_key_pair(key_pair, tx)
+            })
+            .collect::<Vec<_>>();
+        let sender = transactions[0].recover_signer().unwrap();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+
+        let parent = Block {
+            header: Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() },
+            ..Default::default()
+        };
+        let parent_hash = parent.header.hash_slow();
+        provider.add_block(parent_hash, parent);
+        let block = Block {
+            header: Header {
+                number: 1,
+                parent_hash,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                ..Default::default()
+            },
+            body: transactions,
+            ..Default::default()
+        };
+        provider.add_block(block.header.hash_slow(), block);
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+        DebugApi::new(provider, eth_api, BlockingTaskGuard::new(1))
+    }
+
+    #[tokio::test]
+    async fn parallel_trace_block_matches_serial() {
+        let debug_api = counter_block_debug_api();
+        let block_id = BlockId::from(BlockNumberOrTag::Number(1));
+        let opts = GethDebugTracingOptions::default();
+        let serial = debug_api.debug_trace_block(block_id, opts.clone()).await.unwrap();
+        assert_eq!(serial.len(), 7);
+
+        // every transaction sees the increments of the transactions before it
+        let TraceResult::Success { result: GethTrace::Default(frame), .. } = &serial[6] else {
+            panic!("unexpected trace: {:?}", serial[6])
+        };
+        assert_eq!(frame.return_value, Bytes::from(U256::from(7).to_be_bytes_vec()));
+
+        for parallelism in [2, 3, 7, 16] {
+            let parallel = debug_api
+                .clone()
+                .with_trace_block_parallelism(parallelism)
+                .debug_trace_block(block_id, opts.clone())
+                .await
+                .unwrap();
+            assert_eq!(
+                serde_json::to_value(&parallel).unwrap(),
+                serde_json::to_value(&serial).unwrap(),
+                "parallelism {parallelism}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn streamed_trace_block_aborts_when_lagging() {
+        let debug_api = counter_block_debug_api();
+        let block_id = BlockId::from(BlockNumberOrTag::Number(1));
+        let opts = GethDebugTracingOptions::default();
+        let serial = debug_api.debug_trace_block(block_id, opts.clone()).await.unwrap();
+
+        // all traces are buffered
+        let (tx, mut rx) = mpsc::channel(serial.len());
+        debug_api.debug_trace_block_streamed(block_id, opts.clone(), tx).await.unwrap();
+        let mut streamed = Vec::new();
+        while let Some(result) = rx.recv().await {
+            streamed.push(result);
+        }
+        assert_eq!(
+            serde_json::to_value(&streamed).unwrap(),
+            serde_json::to_value(&serial).unwrap()
+        );
+
+        // the receiver doesn't read the traces
+        let (tx, _rx) = mpsc::channel(2);
+        let err = debug_api.debug_trace_block_streamed(block_id, opts.clone(), tx).await;
+        assert!(matches!(err, Err(EthApiError::SubscriptionLagged)));
+
+        // the receiver is dropped
+        let (tx, rx) = mpsc::channel(2);
+        drop(rx);
+        debug_api.debug_trace_block_streamed(block_id, opts, tx).await.unwrap();
+    }
+}
+
+#[cfg(all(test, feature = "optimism"))]
+mod tests {
+    use super::*;
+    use reth_primitives::{Signature, Transaction, TransactionSigned, TxDeposit, TxLegacy};
+    use reth_rpc_types::trace::geth::{CallFrame, DefaultFrame};
+
+    fn deposit(mint: Option<u128>) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Deposit(TxDeposit {
+                source_hash: B256::with_last_byte(1),
+                mint,
+                gas_limit: 100_000,
+                is_system_transaction: false,
+                ..Default::default()
+            }),
+            Signature::optimism_deposit_tx_signature(),
+        )
+    }
+
+    #[test]
+    fn deposit_fields_in_default_trace() {
+        let frame = DefaultFrame { gas: 21_000, ..Default::default() };
+        let trace = with_deposit_fields(GethTrace::Default(frame), &deposit(Some(1_000)));
+
+        let value = serde_json::to_value(trace).unwrap();
+        assert_eq!(value["gas"], 21_000);
+        assert_eq!(value["sourceHash"], serde_json::json!(B256::with_last_byte(1)));
+        assert_eq!(value["mint"], "0x3e8");
+        assert_eq!(value["isSystemTx"], false);
+    }
+
+    #[test]
+    fn deposit_fields_in_call_trace() {
+        let frame = CallFrame { gas_used: U256::from(21_000), ..Default::default() };
+        let trace = with_deposit_fields(GethTrace::CallTracer(frame), &deposit(None));
+
+        let value = serde_json::to_value(trace).unwrap();
+        assert_eq!(value["gasUsed"], "0x5208");
+        assert_eq!(value["mint"], "0x0");
+    }
+
+    #[test]
+    fn other_traces_are_unchanged() {
+        let trace = GethTrace::NoopTracer(NoopFrame::default());
+        assert_eq!(with_deposit_fields(trace.clone(), &deposit(Some(1))), trace);
+
+        let trace = GethTrace::Default(DefaultFrame::default());
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy::default()),
+            Signature::default(),
+        );
+        assert_eq!(with_deposit_fields(trace.clone(), &tx), trace);
+    }
+
+    #[test]
+    fn deposit_fields_in_block_results() {
+        let deposit = deposit(Some(1));
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy::default()),
+            Signature::default(),
+        );
+        let deposits = deposits(&[deposit.clone(), tx.clone()]);
+        assert_eq!(deposits.len(), 1);
+
+        let trace = GethTrace::Default(DefaultFrame::default());
+        let results = with_deposit_fields_in_results(
+            vec![
+                TraceResult::Success { result: trace.clone(), tx_hash: Some(deposit.hash) },
+                TraceResult::Success { result: trace.clone(), tx_hash: Some(tx.hash) },
+            ],
+            &deposits,
+        );
+        let TraceResult::Success { result, .. } = &results[0] else { panic!("expected trace") };
+        assert!(matches!(result, GethTrace::JS(_)));
+        assert_eq!(results[1], TraceResult::Success { result: trace, tx_hash: Some(tx.hash) });
+    }
+}
@@ -0,0 +1,186 @@
+//! Contains RPC handler implementations for retrieving blobs from the blob store of the pool.
+
+use crate::{
+    eth::error::{EthApiError, EthResult},
+    EthApi,
+};
+use reth_errors::RethError;
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{BlobTransactionSidecar, BlockHash, BlockId, BlockNumber, TxHash, B256};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory, TransactionsProvider,
+};
+use reth_rpc_types::BlobSidecar;
+use reth_transaction_pool::TransactionPool;
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Pool: TransactionPool + Clone + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Returns the blob sidecars of all blob transactions of the block.
+    ///
+    /// Returns `None` if the block doesn't exist, and an error if a sidecar is not in the blob
+    /// store, e.g. because the blobs of finalized blocks are pruned.
+    pub(crate) async fn blob_sidecars(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> EthResult<Option<Vec<BlobSidecar>>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+
+        let mut sidecars = Vec::new();
+        for tx in block.body.iter().filter(|tx| tx.is_eip4844()) {
+            let Some(sidecar) = self.pool().get_blob(tx.hash).map_err(RethError::other)? else {
+                return Err(self.missing_blob_sidecar_error(block.number, tx.hash)?)
+            };
+            sidecars.extend(blob_sidecars(Some((block.hash(), block.number)), tx.hash, &sidecar));
+        }
+
+        Ok(Some(sidecars))
+    }
+
+    /// Returns the blob sidecar with the given versioned hash.
+    ///
+    /// The transaction that carries the blob is looked up in the versioned hash index of the blob
+    /// store, and its block by the transaction hash. Returns `None` if the blob is not in the blob
+    /// store.
+    pub(crate) async fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> EthResult<Option<BlobSidecar>> {
+        let Some((tx_hash, sidecar)) =
+            self.pool().get_blob_by_versioned_hash(versioned_hash).map_err(RethError::other)?
+        else {
+            return Ok(None)
+        };
+
+        // the transaction is pending if it's not included in a canonical block
+        let block = self
+            .provider()
+            .transaction_by_hash_with_meta(tx_hash)?
+            .map(|(_, meta)| (meta.block_hash, meta.block_number));
+        Ok(blob_sidecars(block, tx_hash, &sidecar)
+            .find(|sidecar| sidecar.versioned_hash == versioned_hash))
+    }
+
+    /// Returns the error for a blob sidecar of the block that is not in the blob store.
+    ///
+    /// The blob store drops the blobs of blocks once they're finalized, so this distinguishes
+    /// pruned blobs from blobs that were never in the store.
+    fn missing_blob_sidecar_error(
+        &self,
+        block_number: BlockNumber,
+        tx_hash: TxHash,
+    ) -> EthResult<EthApiError> {
+        let finalized = self.provider().finalized_block_number()?;
+        if finalized.is_some_and(|finalized| block_number <= finalized) {
+            Ok(EthApiError::BlobSidecarsPruned(block_number))
+        } else {
+            Ok(EthApiError::BlobSidecarUnavailable(tx_hash))
+        }
+    }
+}
+
+/// Splits the sidecar of a transaction into the sidecars of its blobs, given the hash and number
+/// of the block that includes it.
+fn blob_sidecars<'a>(
+    block: Option<(BlockHash, BlockNumber)>,
+    tx_hash: TxHash,
+    sidecar: &'a BlobTransactionSidecar,
+) -> impl Iterator<Item = BlobSidecar> + 'a {
+    sidecar.versioned_hashes().enumerate().map(move |(index, versioned_hash)| BlobSidecar {
+        block_hash: block.map(|(hash, _)| hash),
+        block_number: block.map(|(_, number)| number),
+        transaction_hash: tx_hash,
+        index: index as u64,
+        versioned_hash,
+        blob: Box::new(sidecar.blobs[index]),
+        kzg_commitment: sidecar.commitments[index],
+        kzg_proof: sidecar.proofs[index],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache, FeeHistoryCacheConfig,
+    };
+    use alloy_primitives::FixedBytes;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Block, Header};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_types::Blob;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, random_signed_tx};
+    use reth_transaction_pool::{
+        blobstore::{BlobStore, InMemoryBlobStore},
+        test_utils::{TestPool, TestPoolBuilder},
+    };
+
+    fn sidecar(byte: u8) -> BlobTransactionSidecar {
+        BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(byte), Blob::repeat_byte(byte + 1)],
+            commitments: vec![FixedBytes::repeat_byte(byte), FixedBytes::repeat_byte(byte + 1)],
+            proofs: vec![FixedBytes::repeat_byte(byte + 2), FixedBytes::repeat_byte(byte + 3)],
+        }
+    }
+
+    #[tokio::test]
+    async fn blob_sidecar_by_versioned_hash() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+        let included = random_signed_tx(&mut rng);
+        let block = Block {
+            header: Header { number: 1, ..Default::default() },
+            body: vec![included.clone()],
+            ..Default::default()
+        };
+        let block_hash = block.header.hash_slow();
+        provider.add_block(block_hash, block);
+
+        let pending = TxHash::with_last_byte(1);
+        let store = InMemoryBlobStore::default();
+        store.insert(included.hash(), sidecar(1)).unwrap();
+        store.insert(pending, sidecar(10)).unwrap();
+        let pool: TestPool = TestPoolBuilder::default().with_blob_store(store).into();
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            pool,
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        // the blob of an included transaction has the block of the transaction
+        let versioned_hash = sidecar(1).versioned_hashes().nth(1).unwrap();
+        let found = eth_api.blob_sidecar_by_versioned_hash(versioned_hash).await.unwrap().unwrap();
+        assert_eq!(found.transaction_hash, included.hash());
+        assert_eq!(found.index, 1);
+        assert_eq!(found.versioned_hash, versioned_hash);
+        assert_eq!(*found.blob, Blob::repeat_byte(2));
+        assert_eq!(found.kzg_proof, FixedBytes::repeat_byte(4));
+        assert_eq!((found.block_hash, found.block_number), (Some(block_hash), Some(1)));
+
+        // the blob of a pending transaction has no block
+        let versioned_hash = sidecar(10).versioned_hashes().next().unwrap();
+        let found = eth_api.blob_sidecar_by_versioned_hash(versioned_hash).await.unwrap().unwrap();
+        assert_eq!(found.transaction_hash, pending);
+        assert_eq!(found.index, 0);
+        assert_eq!((found.block_hash, found.block_number), (None, None));
+
+        assert!(eth_api.blob_sidecar_by_versioned_hash(B256::ZERO).await.unwrap().is_none());
+    }
+}
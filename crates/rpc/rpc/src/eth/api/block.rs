@@ -2,11 +2,12 @@
 
 use crate::{
     eth::{
-        api::transactions::build_transaction_receipt_with_block_receipts,
+        api::transactions::build_transaction_receipt_with_log_index,
         error::{EthApiError, EthResult},
     },
     EthApi,
 };
+use rayon::prelude::*;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{BlockId, TransactionMeta};
@@ -16,6 +17,11 @@ use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
 use reth_transaction_pool::TransactionPool;
 use std::sync::Arc;
 
+#[cfg(not(feature = "optimism"))]
+use crate::eth::api::transactions::build_transaction_receipt_with_sender;
+#[cfg(not(feature = "optimism"))]
+use reth_primitives::TransactionSignedNoHash;
+
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
 where
     Provider:
@@ -63,6 +69,11 @@ where
         &self,
         block_id: BlockId,
     ) -> EthResult<Option<Vec<AnyTransactionReceipt>>> {
+        #[cfg(not(feature = "optimism"))]
+        if let Some(receipts) = self.finalized_block_receipts(block_id).await? {
+            return Ok(Some(receipts))
+        }
+
         // Fetch block and receipts based on block_id
         let block_and_receipts = if block_id.is_pending() {
             self.provider()
@@ -87,44 +98,162 @@ where
         let timestamp = block.timestamp;
         let block = block.unseal();
 
+        // the receipts of finalized blocks can't change, so they can be served from the cache
+        let response_cache = self.response_cache_for_block(block_number)?;
+        if let Some(receipts) =
+            response_cache.as_ref().and_then(|cache| cache.get_block_receipts(block_hash))
+        {
+            return Ok(Some(receipts))
+        }
+
         #[cfg(feature = "optimism")]
         let (block_timestamp, l1_block_info) = {
             let body = reth_evm_optimism::extract_l1_info(&block);
             (block.timestamp, body.ok())
         };
+        #[cfg(feature = "optimism")]
+        let this = self.clone();
+
+        // the gas used by each transaction and the number of logs in the block before it
+        let mut offsets = Vec::with_capacity(receipts.len());
+        let (mut prev_cumulative_gas_used, mut num_logs) = (0, 0);
+        for receipt in receipts.iter() {
+            offsets.push((receipt.cumulative_gas_used - prev_cumulative_gas_used, num_logs));
+            prev_cumulative_gas_used = receipt.cumulative_gas_used;
+            num_logs += receipt.logs.len();
+        }
+
+        // Build transaction receipts in parallel, mostly to recover the senders concurrently
+        let receipts = self
+            .inner
+            .blocking_task_pool
+            .spawn(move || {
+                block
+                    .body
+                    .into_par_iter()
+                    .zip(receipts.par_iter())
+                    .zip(offsets)
+                    .enumerate()
+                    .map(|(idx, ((tx, receipt), (gas_used, num_logs)))| {
+                        let meta = TransactionMeta {
+                            tx_hash: tx.hash,
+                            index: idx as u64,
+                            block_hash,
+                            block_number,
+                            base_fee,
+                            excess_blob_gas,
+                            timestamp,
+                        };
+
+                        #[cfg(feature = "optimism")]
+                        let op_tx_meta =
+                            this.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
+
+                        build_transaction_receipt_with_log_index(
+                            tx,
+                            meta,
+                            receipt.clone(),
+                            gas_used,
+                            num_logs,
+                            #[cfg(feature = "optimism")]
+                            op_tx_meta,
+                        )
+                    })
+                    .collect::<EthResult<Vec<_>>>()
+            })
+            .await
+            .map_err(|_| EthApiError::InternalBlockingTaskError)??;
+
+        if let Some(cache) = response_cache {
+            cache.insert_block_receipts(block_hash, receipts.clone());
+        }
 
-        // Build transaction receipts
-        block
-            .body
-            .into_iter()
-            .zip(receipts.iter())
-            .enumerate()
-            .map(|(idx, (tx, receipt))| {
+        Ok(Some(receipts))
+    }
+
+    /// Returns all transaction receipts in the block if it's finalized, read directly from storage.
+    ///
+    /// The receipts, transactions and senders of the block are read by its range of transaction
+    /// numbers, which for historical blocks are sequential reads of their static file segments,
+    /// and converted in one pass. This bypasses the state cache, and the senders are only
+    /// recovered if they were pruned.
+    ///
+    /// Returns `None` if the block isn't finalized, so that it's served from the state cache.
+    #[cfg(not(feature = "optimism"))]
+    async fn finalized_block_receipts(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<AnyTransactionReceipt>>> {
+        if block_id.is_pending() {
+            return Ok(None)
+        }
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Ok(None)
+        };
+        let finalized = self.provider().finalized_block_number()?;
+        if !finalized.is_some_and(|finalized| block_number <= finalized) {
+            return Ok(None)
+        }
+
+        self.on_blocking_task(|this| async move {
+            let provider = this.provider();
+            let Some(header) = provider.sealed_header(block_number)? else { return Ok(None) };
+            let block_hash = header.hash();
+
+            let response_cache = this.response_cache_for_block(block_number)?;
+            if let Some(receipts) =
+                response_cache.as_ref().and_then(|cache| cache.get_block_receipts(block_hash))
+            {
+                return Ok(Some(receipts))
+            }
+
+            let Some(body) = provider.block_body_indices(block_number)? else { return Ok(None) };
+            let tx_range = body.tx_num_range();
+            let receipts = provider.receipts_by_tx_range(tx_range.clone())?;
+            let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+            if receipts.len() != transactions.len() {
+                // the receipts were pruned
+                return Ok(None)
+            }
+            let mut senders = provider.senders_by_tx_range(tx_range)?;
+            if senders.len() != transactions.len() {
+                // the senders were pruned
+                senders =
+                    TransactionSignedNoHash::recover_signers(&transactions, transactions.len())
+                        .ok_or(EthApiError::InvalidTransactionSignature)?;
+            }
+
+            let mut block_receipts = Vec::with_capacity(receipts.len());
+            let (mut prev_cumulative_gas_used, mut num_logs) = (0, 0);
+            for (index, ((tx, from), receipt)) in
+                transactions.into_iter().zip(senders).zip(receipts).enumerate()
+            {
+                let tx = tx.with_hash();
                 let meta = TransactionMeta {
                     tx_hash: tx.hash,
-                    index: idx as u64,
+                    index: index as u64,
                     block_hash,
                     block_number,
-                    base_fee,
-                    excess_blob_gas,
-                    timestamp,
+                    base_fee: header.base_fee_per_gas,
+                    excess_blob_gas: header.excess_blob_gas,
+                    timestamp: header.timestamp,
                 };
+                let gas_used = receipt.cumulative_gas_used - prev_cumulative_gas_used;
+                prev_cumulative_gas_used = receipt.cumulative_gas_used;
+                let receipt_logs = receipt.logs.len();
+                block_receipts.push(build_transaction_receipt_with_sender(
+                    tx, from, meta, receipt, gas_used, num_logs,
+                ));
+                num_logs += receipt_logs;
+            }
 
-                #[cfg(feature = "optimism")]
-                let op_tx_meta =
-                    self.build_op_tx_meta(&tx, l1_block_info.clone(), block_timestamp)?;
-
-                build_transaction_receipt_with_block_receipts(
-                    tx,
-                    meta,
-                    receipt.clone(),
-                    &receipts,
-                    #[cfg(feature = "optimism")]
-                    op_tx_meta,
-                )
-            })
-            .collect::<EthResult<Vec<_>>>()
-            .map(Some)
+            if let Some(cache) = response_cache {
+                cache.insert_block_receipts(block_hash, block_receipts.clone());
+            }
+
+            Ok(Some(block_receipts))
+        })
+        .await
     }
 
     /// Returns the number transactions in the given block.
@@ -215,3 +344,74 @@ where
         Ok(header)
     }
 }
+
+#[cfg(all(test, not(feature = "optimism")))]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache, FeeHistoryCacheConfig,
+    };
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, Block, BlockNumHash, Bytes, Header, Log,
+        Receipt,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn finalized_block_receipts_from_storage() {
+        let provider = MockEthProvider::default();
+        let mut rng = generators::rng();
+        let block = Block {
+            header: Header { number: 1, base_fee_per_gas: Some(7), ..Default::default() },
+            body: (0..2).map(|_| generators::random_signed_tx(&mut rng)).collect(),
+            ..Default::default()
+        };
+        let block_hash = block.header.hash_slow();
+        provider.add_block(block_hash, block);
+        let receipts = (1..=2)
+            .map(|count| Receipt {
+                success: true,
+                cumulative_gas_used: 21_000 * count,
+                logs: vec![
+                    Log::new_unchecked(Address::with_last_byte(1), Vec::new(), Bytes::new());
+                    count as usize
+                ],
+                ..Default::default()
+            })
+            .collect();
+        provider.add_receipts(block_hash, receipts);
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+        let block_id = BlockId::Number(1.into());
+
+        // the block isn't finalized, so its receipts are built from the state cache
+        assert!(eth_api.finalized_block_receipts(block_id).await.unwrap().is_none());
+        let cached = eth_api.block_receipts(block_id).await.unwrap().unwrap();
+        assert_eq!(cached.len(), 2);
+
+        provider.set_finalized_block(BlockNumHash::new(1, block_hash));
+        let stored = eth_api.finalized_block_receipts(block_id).await.unwrap().unwrap();
+        assert_eq!(stored, cached);
+
+        // the state cache is used if the receipts were pruned
+        provider.receipts.lock().remove(&block_hash);
+        assert!(eth_api.finalized_block_receipts(block_id).await.unwrap().is_none());
+    }
+}
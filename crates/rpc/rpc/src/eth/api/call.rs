@@ -2,6 +2,7 @@
 
 use crate::{
     eth::{
+        call_caps::{CallCapExceeded, CallCaps, CallCapsInspector},
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
         revm_utils::{
             apply_state_overrides, build_call_evm_env, caller_gas_allowance,
@@ -20,15 +21,20 @@ use reth_provider::{
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_types::{
     state::{EvmOverrides, StateOverride},
-    AccessListWithGasUsed, Bundle, EthCallResponse, StateContext, TransactionRequest,
+    AccessList, AccessListItem, AccessListWithGasUsed, Bundle, EthCallResponse,
+    SimulatedCallResult, StateContext, TransactionRequest,
 };
 use reth_transaction_pool::TransactionPool;
 use revm::{
     db::{CacheDB, DatabaseRef},
-    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, HaltReason},
-    DatabaseCommit,
+    primitives::{
+        BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, HaltReason,
+        ResultAndState,
+    },
+    Database, DatabaseCommit,
 };
 use revm_inspectors::access_list::AccessListInspector;
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::trace;
 
 // Gas per transaction not creating a contract.
@@ -57,22 +63,64 @@ where
 
         self.on_blocking_task(|this| async move {
             let state = this.state_at(at)?;
-            this.estimate_gas_with(cfg, block_env, request, state, state_override)
+            let caps = this.call_caps().estimate_gas;
+            this.estimate_gas_with_caps(cfg, block_env, request, state, state_override, caps)
         })
         .await
+        .map_err(|err| self.decode_revert(err))
     }
 
     /// Executes the call request (`eth_call`) and returns the output
+    ///
+    /// The execution is bounded by the configured [`CallCaps`] of `eth_call`.
     pub async fn call(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
     ) -> EthResult<Bytes> {
-        let (res, _env) =
-            self.transact_call_at(request, block_number.unwrap_or_default(), overrides).await?;
+        let caps = self.call_caps().call;
+        let request_gas = request.gas;
+        let this = self.clone();
+        let (res, _env) = self
+            .spawn_with_call_at(
+                request,
+                block_number.unwrap_or_default(),
+                overrides,
+                move |db, mut env| {
+                    let lowered_to = caps.apply_gas_limit(request_gas, &mut env.tx.gas_limit)?;
+                    let (res, env) = this.transact_with_caps(db, env, &mut caps.inspector())?;
+                    if let Some(cap) = lowered_to {
+                        if is_out_of_gas(&res.result) {
+                            return Err(CallCapExceeded::GasLimit(cap).into())
+                        }
+                    }
+                    Ok((res, env))
+                },
+            )
+            .await?;
 
-        ensure_success(res.result)
+        ensure_success(res.result).map_err(|err| self.decode_revert(err))
+    }
+
+    /// Executes the [`EnvWithHandlerCfg`] like [`EthTransactions::transact`], but aborts the
+    /// execution once it exceeds the call depth or execution time caps of the inspector.
+    fn transact_with_caps<DB>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        inspector: &mut CallCapsInspector,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+    {
+        if inspector.is_unbounded() {
+            return self.transact(db, env)
+        }
+        let res = self.inspect(db, env, &mut *inspector)?;
+        inspector.ensure_within_caps()?;
+        Ok(res)
     }
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
@@ -81,8 +129,50 @@ where
         &self,
         bundle: Bundle,
         state_context: Option<StateContext>,
-        mut state_override: Option<StateOverride>,
+        state_override: Option<StateOverride>,
     ) -> EthResult<Vec<EthCallResponse>> {
+        let results = self.transact_many(bundle, state_context, state_override).await?;
+        Ok(results
+            .into_iter()
+            .map(|result| match ensure_success(result) {
+                Ok(output) => EthCallResponse { value: Some(output), error: None },
+                Err(err) => EthCallResponse { value: None, error: Some(err.to_string()) },
+            })
+            .collect())
+    }
+
+    /// Simulates the calls of the bundle like [`Self::call_many`], but returns the gas used, the
+    /// logs and the return data of every call, including reverted calls.
+    pub async fn simulate_calls(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> EthResult<Vec<SimulatedCallResult>> {
+        let results = self.transact_many(bundle, state_context, state_override).await?;
+        Ok(results
+            .into_iter()
+            .map(|result| {
+                let success = result.is_success();
+                let gas_used = result.gas_used();
+                let logs = result.logs().to_vec();
+                let return_data = result.output().cloned().unwrap_or_default();
+                let error = ensure_success(result).err().map(|err| err.to_string());
+                SimulatedCallResult { success, return_data, error, gas_used, logs }
+            })
+            .collect())
+    }
+
+    /// Executes the calls of the bundle on top of the state context, and returns their results.
+    ///
+    /// The state changes of each call are visible to the following calls. The state overrides are
+    /// applied once, before the first call.
+    async fn transact_many(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        mut state_override: Option<StateOverride>,
+    ) -> EthResult<Vec<ExecutionResult>> {
         let Bundle { transactions, block_override } = bundle;
         if transactions.is_empty() {
             return Err(EthApiError::InvalidParams(String::from("transactions are empty.")))
@@ -151,15 +241,7 @@ where
                     overrides,
                 )?;
                 let (res, _) = this.transact(&mut db, env)?;
-
-                match ensure_success(res.result) {
-                    Ok(output) => {
-                        results.push(EthCallResponse { value: Some(output), error: None });
-                    }
-                    Err(err) => {
-                        results.push(EthCallResponse { value: None, error: Some(err.to_string()) });
-                    }
-                }
+                results.push(res.result);
 
                 if transactions.peek().is_some() {
                     // need to apply the state changes of this call before executing the next call
@@ -176,16 +258,37 @@ where
     ///
     /// This will execute the [`TransactionRequest`] and find the best gas limit via binary search
     pub fn estimate_gas_with<S>(
+        &self,
+        cfg: CfgEnvWithHandlerCfg,
+        block: BlockEnv,
+        request: TransactionRequest,
+        state: S,
+        state_override: Option<StateOverride>,
+    ) -> EthResult<U256>
+    where
+        S: StateProvider,
+    {
+        self.estimate_gas_with_caps(cfg, block, request, state, state_override, CallCaps::default())
+    }
+
+    /// Estimates the gas usage of the `request` with the state like [`Self::estimate_gas_with`],
+    /// but bounds the estimation by the caps.
+    ///
+    /// The execution time cap applies to all executions of the estimation together.
+    pub fn estimate_gas_with_caps<S>(
         &self,
         mut cfg: CfgEnvWithHandlerCfg,
         block: BlockEnv,
         request: TransactionRequest,
         state: S,
         state_override: Option<StateOverride>,
+        caps: CallCaps,
     ) -> EthResult<U256>
     where
         S: StateProvider,
     {
+        let mut inspector = caps.inspector();
+
         // Disabled because eth_estimateGas is sometimes used with eoa senders
         // See <https://github.com/paradigmxyz/reth/issues/1959>
         cfg.disable_eip3607 = true;
@@ -198,7 +301,9 @@ where
         // Keep a copy of gas related request values
         let tx_request_gas_limit = request.gas;
         let tx_request_gas_price = request.gas_price;
-        let block_env_gas_limit = block.gas_limit;
+        // the gas limit of the retries that check whether a failure is gas related
+        let block_env_gas_limit =
+            caps.gas_limit.map_or(block.gas_limit, |cap| block.gas_limit.min(U256::from(cap)));
 
         // Determine the highest possible gas limit, considering both the request's specified limit
         // and the block's limit.
@@ -249,13 +354,17 @@ where
         // We can now normalize the highest gas limit to a u64
         let mut highest_gas_limit: u64 = highest_gas_limit.try_into().unwrap_or(u64::MAX);
 
+        // Lower the highest gas limit to the configured cap
+        let lowered_to = caps.apply_gas_limit(tx_request_gas_limit, &mut highest_gas_limit)?;
+
         // If the provided gas limit is less than computed cap, use that
         env.tx.gas_limit = env.tx.gas_limit.min(highest_gas_limit);
 
         trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
 
         // Execute the transaction with the highest possible gas limit.
-        let (mut res, mut env) = match self.transact(&mut db, env.clone()) {
+        let (mut res, mut env) = match self.transact_with_caps(&mut db, env.clone(), &mut inspector)
+        {
             // Handle the exceptional case where the transaction initialization uses too much gas.
             // If the gas price or gas limit was specified in the request, retry the transaction
             // with the block's gas limit to determine if the failure was due to
@@ -263,12 +372,24 @@ where
             Err(EthApiError::InvalidTransaction(RpcInvalidTransactionError::GasTooHigh))
                 if tx_request_gas_limit.is_some() || tx_request_gas_price.is_some() =>
             {
-                return Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
+                return Err(self.map_out_of_gas_err(
+                    block_env_gas_limit,
+                    env,
+                    &mut db,
+                    &mut inspector,
+                ))
             }
             // Propagate other results (successful or other errors).
             ethres => ethres?,
         };
 
+        if let Some(cap) = lowered_to {
+            if is_out_of_gas(&res.result) {
+                // the transaction might succeed with a gas limit above the cap
+                return Err(CallCapExceeded::GasLimit(cap).into())
+            }
+        }
+
         let gas_refund = match res.result {
             ExecutionResult::Success { gas_refunded, .. } => gas_refunded,
             ExecutionResult::Halt { reason, gas_used } => {
@@ -280,7 +401,7 @@ where
                 // if price or limit was included in the request then we can execute the request
                 // again with the block's gas limit to check if revert is gas related or not
                 return if tx_request_gas_limit.is_some() || tx_request_gas_price.is_some() {
-                    Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
+                    Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db, &mut inspector))
                 } else {
                     // the transaction did revert
                     Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
@@ -309,7 +430,7 @@ where
             env.tx.gas_limit = optimistic_gas_limit;
             // Re-execute the transaction with the new gas limit and update the result and
             // environment.
-            (res, env) = self.transact(&mut db, env)?;
+            (res, env) = self.transact_with_caps(&mut db, env, &mut inspector)?;
             // Update the gas used based on the new result.
             gas_used = res.result.gas_used();
             // Update the gas limit estimates (highest and lowest) based on the execution result.
@@ -344,7 +465,7 @@ where
             env.tx.gas_limit = mid_gas_limit;
 
             // Execute transaction and handle potential gas errors, adjusting limits accordingly.
-            match self.transact(&mut db, env.clone()) {
+            match self.transact_with_caps(&mut db, env.clone(), &mut inspector) {
                 // Check if the error is due to gas being too high.
                 Err(EthApiError::InvalidTransaction(RpcInvalidTransactionError::GasTooHigh)) => {
                     // Increase the lowest gas limit if gas is too high
@@ -419,24 +540,48 @@ where
         };
 
         // can consume the list since we're not using the request anymore
-        let initial = request.access_list.take().unwrap_or_default();
-
-        let precompiles = get_precompiles(env.handler_cfg.spec_id);
-        let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
-        let (result, env) = self.inspect(&mut db, env, &mut inspector)?;
+        let mut access_list = sorted_access_list(request.access_list.take().unwrap_or_default());
+        let precompiles = get_precompiles(env.handler_cfg.spec_id).into_iter().collect::<Vec<_>>();
+
+        // applying the access list changes the gas costs of the accesses, which can change the
+        // execution path, e.g. of contracts that branch on the remaining gas, so the transaction
+        // is executed with the access list of the previous execution until it's stable
+        let max_iterations = self.access_list_max_iterations().max(1);
+        for iteration in 1..=max_iterations {
+            env.tx.access_list = access_list.flattened();
+            let mut inspector = AccessListInspector::new(
+                access_list.clone(),
+                from,
+                to,
+                precompiles.iter().copied(),
+            );
+            let (result, next_env) = self.inspect(&mut db, env, &mut inspector)?;
+            env = next_env;
+
+            match result.result {
+                ExecutionResult::Halt { reason, .. } => Err(match reason {
+                    HaltReason::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
+                    halt => RpcInvalidTransactionError::EvmHalt(halt),
+                }),
+                ExecutionResult::Revert { output, .. } => {
+                    Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
+                }
+                ExecutionResult::Success { .. } => Ok(()),
+            }?;
 
-        match result.result {
-            ExecutionResult::Halt { reason, .. } => Err(match reason {
-                HaltReason::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
-                halt => RpcInvalidTransactionError::EvmHalt(halt),
-            }),
-            ExecutionResult::Revert { output, .. } => {
-                Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
+            let next = sorted_access_list(inspector.into_access_list());
+            if next == access_list {
+                break
             }
-            ExecutionResult::Success { .. } => Ok(()),
-        }?;
-
-        let access_list = inspector.into_access_list();
+            access_list = next;
+            if iteration == max_iterations {
+                trace!(
+                    target: "rpc::eth::access_list",
+                    max_iterations,
+                    "Access list did not stabilize"
+                );
+            }
+        }
 
         let cfg_with_spec_id =
             CfgEnvWithHandlerCfg { cfg_env: env.cfg.clone(), handler_cfg: env.handler_cfg };
@@ -457,13 +602,14 @@ where
         env_gas_limit: U256,
         mut env: EnvWithHandlerCfg,
         db: &mut CacheDB<StateProviderDatabase<S>>,
+        inspector: &mut CallCapsInspector,
     ) -> EthApiError
     where
         S: StateProvider,
     {
         let req_gas_limit = env.tx.gas_limit;
         env.tx.gas_limit = env_gas_limit.try_into().unwrap_or(u64::MAX);
-        let (res, _) = match self.transact(db, env) {
+        let (res, _) = match self.transact_with_caps(db, env, inspector) {
             Ok(res) => res,
             Err(err) => return err,
         };
@@ -484,6 +630,29 @@ where
     }
 }
 
+/// Returns `true` if the execution halted because it ran out of gas.
+fn is_out_of_gas(result: &ExecutionResult) -> bool {
+    matches!(result, ExecutionResult::Halt { reason: HaltReason::OutOfGas(_), .. })
+}
+
+/// Returns the access list with its entries sorted by address and their storage keys sorted, so
+/// that access lists with the same entries are equal.
+fn sorted_access_list(access_list: AccessList) -> AccessList {
+    let mut entries = BTreeMap::<_, BTreeSet<_>>::new();
+    for item in access_list.0 {
+        entries.entry(item.address).or_default().extend(item.storage_keys);
+    }
+    AccessList(
+        entries
+            .into_iter()
+            .map(|(address, storage_keys)| AccessListItem {
+                address,
+                storage_keys: storage_keys.into_iter().collect(),
+            })
+            .collect(),
+    )
+}
+
 /// Updates the highest and lowest gas limits for binary search based on the execution result.
 ///
 /// This function refines the gas limit estimates used in a binary search to find the optimal gas
@@ -528,3 +697,177 @@ fn update_estimated_gas_range(
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache, FeeHistoryCacheConfig,
+    };
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, Block, BlockNumberOrTag, Header, B256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_server_types::constants::DEFAULT_ACCESS_LIST_MAX_ITERATIONS;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::sync::Arc;
+
+    #[test]
+    fn sorted_access_list_is_order_independent() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (k1, k2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let first = AccessList(vec![
+            AccessListItem { address: b, storage_keys: vec![k2, k1] },
+            AccessListItem { address: a, storage_keys: vec![] },
+        ]);
+        let second = AccessList(vec![
+            AccessListItem { address: a, storage_keys: vec![] },
+            AccessListItem { address: b, storage_keys: vec![k1] },
+            AccessListItem { address: b, storage_keys: vec![k2, k1] },
+        ]);
+        let sorted = sorted_access_list(first);
+        assert_eq!(sorted, sorted_access_list(second));
+        assert_eq!(sorted.0[0].address, a);
+        assert_eq!(sorted.0[1].storage_keys, vec![k1, k2]);
+    }
+
+    #[tokio::test]
+    async fn create_access_list_until_stable() {
+        let provider = MockEthProvider {
+            chain_spec: Arc::new(ChainSpecBuilder::mainnet().london_activated().build()),
+            ..Default::default()
+        };
+        let block = Block {
+            header: Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() },
+            ..Default::default()
+        };
+        provider.add_block(block.header.hash_slow(), block);
+
+        let sender = Address::with_last_byte(1);
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+        // loads slot 0, and also slot 1 if less than 75000 gas is left, which is only the case
+        // once slot 0 is in the access list
+        let contract = Address::with_last_byte(2);
+        provider.add_account(
+            contract,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[
+                0x60, 0x00, 0x54, 0x50, 0x5a, 0x62, 0x01, 0x24, 0xf8, 0x11, 0x60, 0x0e, 0x57, 0x00,
+                0x5b, 0x60, 0x01, 0x54, 0x50, 0x00,
+            ])),
+        );
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        let request = TransactionRequest { gas: Some(100_000), ..Default::default() }
+            .from(sender)
+            .to(contract);
+        let at = Some(BlockNumberOrTag::Number(0).into());
+        let access_list =
+            |storage_keys| AccessList(vec![AccessListItem { address: contract, storage_keys }]);
+
+        // the access list of a single execution misses slot 1
+        eth_api.set_access_list_max_iterations(1);
+        let result = eth_api.create_access_list_at(request.clone(), at).await.unwrap();
+        assert_eq!(result.access_list, access_list(vec![B256::ZERO]));
+
+        eth_api.set_access_list_max_iterations(DEFAULT_ACCESS_LIST_MAX_ITERATIONS);
+        let result = eth_api.create_access_list_at(request, at).await.unwrap();
+        assert_eq!(result.access_list, access_list(vec![B256::ZERO, B256::with_last_byte(1)]));
+    }
+
+    #[tokio::test]
+    async fn simulate_calls() {
+        let provider = MockEthProvider::default();
+        let block = Block {
+            header: Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() },
+            ..Default::default()
+        };
+        provider.add_block(block.header.hash_slow(), block);
+
+        let sender = Address::with_last_byte(1);
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+        // stores 42 in memory, emits an empty log and returns the stored word
+        let logger = Address::with_last_byte(2);
+        provider.add_account(
+            logger,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[
+                0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x00, 0x60, 0x00, 0xa0, 0x60, 0x20, 0x60, 0x00,
+                0xf3,
+            ])),
+        );
+        // stores 42 in memory and reverts with the stored word
+        let reverter = Address::with_last_byte(3);
+        provider.add_account(
+            reverter,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&[
+                0x60, 0x2a, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xfd,
+            ])),
+        );
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        let bundle = Bundle {
+            transactions: vec![
+                TransactionRequest::default()
+                    .from(sender)
+                    .to(Address::with_last_byte(4))
+                    .value(U256::from(1)),
+                TransactionRequest::default().from(sender).to(logger),
+                TransactionRequest::default().from(sender).to(reverter),
+            ],
+            block_override: None,
+        };
+        let state_context = StateContext {
+            block_number: Some(BlockNumberOrTag::Number(0).into()),
+            transaction_index: None,
+        };
+        let results = eth_api.simulate_calls(bundle, Some(state_context), None).await.unwrap();
+        assert_eq!(results.len(), 3);
+
+        let word = Bytes::from(U256::from(42).to_be_bytes_vec());
+        assert!(results[0].success);
+        assert_eq!(results[0].gas_used, MIN_TRANSACTION_GAS);
+        assert!(results[0].logs.is_empty());
+
+        assert!(results[1].success);
+        assert!(results[1].error.is_none());
+        assert_eq!(results[1].return_data, word);
+        assert_eq!(results[1].logs.len(), 1);
+        assert_eq!(results[1].logs[0].address, logger);
+        assert!(results[1].gas_used > MIN_TRANSACTION_GAS);
+
+        assert!(!results[2].success);
+        assert!(results[2].error.is_some());
+        assert_eq!(results[2].return_data, word);
+        assert!(results[2].logs.is_empty());
+    }
+}
@@ -14,7 +14,6 @@ use reth_primitives::{
 };
 use reth_provider::{BlockReaderIdExt, CanonStateNotification, ChainSpecProvider};
 use reth_rpc_server_types::constants::gas_oracle::MAX_HEADER_HISTORY;
-use reth_rpc_types::TxGasAndReward;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, VecDeque},
@@ -50,12 +49,6 @@ impl FeeHistoryCache {
         &self.inner.config
     }
 
-    /// Returns the configured resolution for percentile approximation.
-    #[inline]
-    pub fn resolution(&self) -> u64 {
-        self.config().resolution
-    }
-
     /// Returns all blocks that are missing in the cache in the [`lower_bound`, `upper_bound`]
     /// range.
     ///
@@ -76,18 +69,14 @@ impl FeeHistoryCache {
     {
         let mut entries = self.inner.entries.write().await;
 
-        let percentiles = self.predefined_percentiles();
-        // Insert all new blocks and calculate approximated rewards
+        // Insert all new blocks and precompute their sorted effective tips
         for (block, receipts) in blocks {
             let mut fee_history_entry = FeeHistoryEntry::new(&block);
-            fee_history_entry.rewards = calculate_reward_percentiles_for_block(
-                &percentiles,
-                fee_history_entry.gas_used,
+            fee_history_entry.effective_tips = Arc::new(BlockEffectiveTips::new(
                 fee_history_entry.base_fee_per_gas,
                 &block.body,
                 &receipts,
-            )
-            .unwrap_or_default();
+            ));
             entries.insert(block.number, fee_history_entry);
         }
 
@@ -154,14 +143,6 @@ impl FeeHistoryCache {
             None
         }
     }
-
-    /// Generates predefined set of percentiles
-    ///
-    /// This returns 100 * resolution points
-    pub fn predefined_percentiles(&self) -> Vec<f64> {
-        let res = self.resolution() as f64;
-        (0..=100 * self.resolution()).map(|p| p as f64 / res).collect()
-    }
 }
 
 /// Settings for the [`FeeHistoryCache`].
@@ -173,15 +154,11 @@ pub struct FeeHistoryCacheConfig {
     /// Default is [`MAX_HEADER_HISTORY`] plus some change to also serve slightly older blocks from
     /// cache, since `fee_history` supports the entire range
     pub max_blocks: u64,
-    /// Percentile approximation resolution
-    ///
-    /// Default is 4 which means 0.25
-    pub resolution: u64,
 }
 
 impl Default for FeeHistoryCacheConfig {
     fn default() -> Self {
-        Self { max_blocks: MAX_HEADER_HISTORY + 100, resolution: 4 }
+        Self { max_blocks: MAX_HEADER_HISTORY + 100 }
     }
 }
 
@@ -192,8 +169,7 @@ struct FeeHistoryCacheInner {
     lower_bound: AtomicU64,
     /// Stores the upper bound of the cache
     upper_bound: AtomicU64,
-    /// Config for `FeeHistoryCache`, consists of resolution for percentile approximation
-    /// and max number of blocks
+    /// Config for `FeeHistoryCache`, consists of the max number of blocks
     config: FeeHistoryCacheConfig,
     /// Stores the entries of the cache
     entries: tokio::sync::RwLock<BTreeMap<u64, FeeHistoryEntry>>,
@@ -270,52 +246,91 @@ pub(crate) fn calculate_reward_percentiles_for_block(
     transactions: &[TransactionSigned],
     receipts: &[Receipt],
 ) -> Result<Vec<u128>, EthApiError> {
-    let mut transactions = transactions
-        .iter()
-        .zip(receipts)
-        .scan(0, |previous_gas, (tx, receipt)| {
-            // Convert the cumulative gas used in the receipts
-            // to the gas usage by the transaction
-            //
-            // While we will sum up the gas again later, it is worth
-            // noting that the order of the transactions will be different,
-            // so the sum will also be different for each receipt.
-            let gas_used = receipt.cumulative_gas_used - *previous_gas;
-            *previous_gas = receipt.cumulative_gas_used;
-
-            Some(TxGasAndReward {
-                gas_used,
-                reward: tx.effective_tip_per_gas(Some(base_fee_per_gas)).unwrap_or_default(),
+    Ok(BlockEffectiveTips::new(base_fee_per_gas, transactions, receipts)
+        .reward_percentiles(percentiles, gas_used))
+}
+
+/// The effective tips paid by the transactions of a block, sorted in ascending order.
+///
+/// This is computed once per block, so that the rewards for any set of percentiles can be looked
+/// up without re-scanning the block's transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockEffectiveTips {
+    /// The effective tip of each transaction and the cumulative gas used by all transactions up
+    /// to and including it, sorted by tip.
+    tips: Vec<(u128, u64)>,
+}
+
+impl BlockEffectiveTips {
+    /// Computes the sorted effective tips of the block's transactions.
+    pub fn new(
+        base_fee_per_gas: u64,
+        transactions: &[TransactionSigned],
+        receipts: &[Receipt],
+    ) -> Self {
+        Self::from_gas_and_tips(transactions.iter().zip(receipts).scan(
+            0,
+            |previous_gas, (tx, receipt)| {
+                // Convert the cumulative gas used in the receipts
+                // to the gas usage by the transaction
+                let gas_used = receipt.cumulative_gas_used - *previous_gas;
+                *previous_gas = receipt.cumulative_gas_used;
+
+                Some((
+                    gas_used,
+                    tx.effective_tip_per_gas(Some(base_fee_per_gas)).unwrap_or_default(),
+                ))
+            },
+        ))
+    }
+
+    /// Sorts the given `(gas used, effective tip)` pairs by tip and accumulates the gas used in
+    /// that order.
+    fn from_gas_and_tips(txs: impl IntoIterator<Item = (u64, u128)>) -> Self {
+        let mut txs = txs.into_iter().collect::<Vec<_>>();
+        txs.sort_by_key(|(_, tip)| *tip);
+
+        let tips = txs
+            .into_iter()
+            .scan(0u64, |cumulative_gas_used, (gas_used, tip)| {
+                *cumulative_gas_used += gas_used;
+                Some((tip, *cumulative_gas_used))
             })
-        })
-        .collect::<Vec<_>>();
-
-    // Sort the transactions by their rewards in ascending order
-    transactions.sort_by_key(|tx| tx.reward);
-
-    // Find the transaction that corresponds to the given percentile
-    //
-    // We use a `tx_index` here that is shared across all percentiles, since we know
-    // the percentiles are monotonically increasing.
-    let mut tx_index = 0;
-    let mut cumulative_gas_used = transactions.first().map(|tx| tx.gas_used).unwrap_or_default();
-    let mut rewards_in_block = Vec::new();
-    for percentile in percentiles {
-        // Empty blocks should return in a zero row
-        if transactions.is_empty() {
-            rewards_in_block.push(0);
-            continue
-        }
+            .collect();
+        Self { tips }
+    }
 
-        let threshold = (gas_used as f64 * percentile / 100.) as u64;
-        while cumulative_gas_used < threshold && tx_index < transactions.len() - 1 {
-            tx_index += 1;
-            cumulative_gas_used += transactions[tx_index].gas_used;
+    /// Returns the number of transactions in the block.
+    pub fn len(&self) -> usize {
+        self.tips.len()
+    }
+
+    /// Returns `true` if the block has no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.tips.is_empty()
+    }
+
+    /// Returns the reward at the given percentile of the block's `gas_used`.
+    ///
+    /// This is the tip of the first transaction, in ascending tip order, at which the cumulative
+    /// gas used reaches the percentile. Empty blocks have a reward of zero.
+    pub fn reward_at_percentile(&self, percentile: f64, gas_used: u64) -> u128 {
+        if self.tips.is_empty() {
+            return 0
         }
-        rewards_in_block.push(transactions[tx_index].reward);
+        let threshold = (gas_used as f64 * percentile / 100.) as u64;
+        let index =
+            self.tips.partition_point(|(_, cumulative_gas_used)| *cumulative_gas_used < threshold);
+        self.tips[index.min(self.tips.len() - 1)].0
     }
 
-    Ok(rewards_in_block)
+    /// Returns the rewards for each of the given percentiles of the block's `gas_used`.
+    pub fn reward_percentiles(&self, percentiles: &[f64], gas_used: u64) -> Vec<u128> {
+        percentiles
+            .iter()
+            .map(|percentile| self.reward_at_percentile(*percentile, gas_used))
+            .collect()
+    }
 }
 
 /// A cached entry for a block's fee history.
@@ -344,8 +359,8 @@ pub struct FeeHistoryEntry {
     pub gas_limit: u64,
     /// Hash of the block.
     pub header_hash: B256,
-    /// Approximated rewards for the configured percentiles.
-    pub rewards: Vec<u128>,
+    /// The sorted effective tips of the block's transactions.
+    pub effective_tips: Arc<BlockEffectiveTips>,
     /// The timestamp of the block.
     pub timestamp: u64,
 }
@@ -353,7 +368,7 @@ pub struct FeeHistoryEntry {
 impl FeeHistoryEntry {
     /// Creates a new entry from a sealed block.
     ///
-    /// Note: This does not calculate the rewards for the block.
+    /// Note: This does not compute the effective tips of the block.
     pub fn new(block: &SealedBlock) -> Self {
         Self {
             base_fee_per_gas: block.base_fee_per_gas.unwrap_or_default(),
@@ -366,7 +381,7 @@ impl FeeHistoryEntry {
             gas_used: block.gas_used,
             header_hash: block.hash(),
             gas_limit: block.gas_limit,
-            rewards: Vec::new(),
+            effective_tips: Default::default(),
             timestamp: block.timestamp,
         }
     }
@@ -397,3 +412,25 @@ impl FeeHistoryEntry {
         Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_percentiles_from_sorted_tips() {
+        // (gas used, tip), unsorted
+        let tips = BlockEffectiveTips::from_gas_and_tips([(50, 3), (20, 1), (30, 2)]);
+        assert_eq!(tips.len(), 3);
+
+        let rewards = tips.reward_percentiles(&[0., 10., 20., 21., 50., 51., 80., 100.], 100);
+        assert_eq!(rewards, vec![1, 1, 1, 2, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn empty_block_rewards_are_zero() {
+        let tips = BlockEffectiveTips::default();
+        assert!(tips.is_empty());
+        assert_eq!(tips.reward_percentiles(&[0., 50., 100.], 0), vec![0, 0, 0]);
+    }
+}
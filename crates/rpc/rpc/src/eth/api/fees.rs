@@ -2,7 +2,7 @@
 
 use crate::{
     eth::{
-        api::fee_history::{calculate_reward_percentiles_for_block, FeeHistoryEntry},
+        api::fee_history::calculate_reward_percentiles_for_block,
         error::{EthApiError, EthResult},
     },
     EthApi,
@@ -44,7 +44,15 @@ where
     }
 
     /// Returns a suggestion for the priority fee (the tip)
+    ///
+    /// OP Stack chains use a separate estimate, see [`GasPriceOracle::op_suggest_tip_cap`].
+    ///
+    /// [`GasPriceOracle::op_suggest_tip_cap`]: crate::eth::gas_oracle::GasPriceOracle::op_suggest_tip_cap
     pub(crate) async fn suggested_priority_fee(&self) -> EthResult<U256> {
+        #[cfg(feature = "optimism")]
+        if self.provider().chain_spec().is_optimism() {
+            return self.gas_oracle().op_suggest_tip_cap().await
+        }
         self.gas_oracle().suggest_tip_cap().await
     }
 
@@ -130,11 +138,8 @@ where
                 blob_gas_used_ratio.push(entry.blob_gas_used_ratio);
 
                 if let Some(percentiles) = &reward_percentiles {
-                    let mut block_rewards = Vec::with_capacity(percentiles.len());
-                    for &percentile in percentiles {
-                        block_rewards.push(self.approximate_percentile(entry, percentile));
-                    }
-                    rewards.push(block_rewards);
+                    rewards
+                        .push(entry.effective_tips.reward_percentiles(percentiles, entry.gas_used));
                 }
             }
             let last_entry = fee_entries.last().expect("is not empty");
@@ -211,18 +216,4 @@ where
             reward: reward_percentiles.map(|_| rewards),
         })
     }
-
-    /// Approximates reward at a given percentile for a specific block
-    /// Based on the configured resolution
-    fn approximate_percentile(&self, entry: &FeeHistoryEntry, requested_percentile: f64) -> u128 {
-        let resolution = self.fee_history_cache().resolution();
-        let rounded_percentile =
-            (requested_percentile * resolution as f64).round() / resolution as f64;
-        let clamped_percentile = rounded_percentile.clamp(0.0, 100.0);
-
-        // Calculate the index in the precomputed rewards array
-        let index = (clamped_percentile / (1.0 / resolution as f64)).round() as usize;
-        // Fetch the reward from the FeeHistoryEntry
-        entry.rewards.get(index).cloned().unwrap_or_default()
-    }
 }
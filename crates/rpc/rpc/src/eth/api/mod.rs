@@ -6,24 +6,30 @@ use crate::eth::{
         fee_history::FeeHistoryCache,
         pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin},
     },
-    cache::EthStateCache,
-    error::{EthApiError, EthResult},
+    cache::{EthStateCache, RpcResponseCache},
+    call_caps::CallCapsConfig,
+    error::{EthApiError, EthResult, RpcInvalidTransactionError},
     gas_oracle::GasPriceOracle,
+    revert::RevertDecoder,
     signer::EthSigner,
-    traits::RawTransactionForwarder,
+    traits::{RawTransactionForwarder, RawTransactionValidator},
 };
 use async_trait::async_trait;
 use reth_chainspec::ChainInfo;
+use reth_db_api::read_counter::ReadCounter;
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg},
-    Address, BlockId, BlockNumberOrTag, SealedBlockWithSenders, SealedHeader, B256, U256, U64,
+    Address, BlockId, BlockNumber, BlockNumberOrTag, SealedBlockWithSenders, SealedHeader, B256,
+    U256, U64,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderBox, StateProviderFactory,
+    BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, EvmEnvProvider, StateProviderBox,
+    StateProviderFactory,
 };
+use reth_rpc_server_types::constants::DEFAULT_ACCESS_LIST_MAX_ITERATIONS;
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
@@ -31,11 +37,15 @@ use revm_primitives::{CfgEnv, SpecId};
 use std::{
     fmt::Debug,
     future::Future,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tokio::sync::{oneshot, Mutex};
 
+mod blobs;
 mod block;
 mod call;
 pub(crate) mod fee_history;
@@ -46,6 +56,7 @@ mod optimism;
 mod pending_block;
 mod server;
 mod sign;
+mod simulate;
 mod state;
 mod transactions;
 
@@ -95,6 +106,87 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
     pub fn set_eth_raw_transaction_forwarder(&self, forwarder: Arc<dyn RawTransactionForwarder>) {
         self.inner.raw_transaction_forwarder.write().replace(forwarder);
     }
+
+    /// Sets a validator for `eth_sendRawTransaction` that runs before the transaction is forwarded
+    /// or submitted to the pool.
+    pub fn set_eth_raw_transaction_validator(&self, validator: Arc<dyn RawTransactionValidator>) {
+        self.inner.raw_transaction_validator.write().replace(validator);
+    }
+
+    /// Enables `eth_sendRawTransactionSync`, which waits for the transaction to be included in a
+    /// block of the canonical state notifications of `events`, but at most `timeout`.
+    pub fn set_eth_raw_transaction_sync(
+        &self,
+        events: Arc<dyn CanonStateSubscriptions>,
+        timeout: Duration,
+    ) {
+        self.inner.raw_transaction_sync.write().replace(RawTransactionSync { events, timeout });
+    }
+
+    /// Sets the cache for the responses of `eth_getBlockReceipts` and `eth_getProof` for finalized
+    /// blocks.
+    pub fn set_response_cache(&self, cache: RpcResponseCache) {
+        self.inner.response_cache.write().replace(cache);
+    }
+
+    /// Returns the response cache, if enabled.
+    pub fn response_cache(&self) -> Option<RpcResponseCache> {
+        self.inner.response_cache.read().clone()
+    }
+
+    /// Sets the maximum number of blocks into the past for which `eth_getProof` generates proofs.
+    ///
+    /// By default, proofs are only generated for the latest block.
+    pub fn set_eth_proof_window(&self, window: u64) {
+        self.inner.eth_proof_window.store(window, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of blocks into the past for which `eth_getProof` generates
+    /// proofs.
+    pub fn eth_proof_window(&self) -> u64 {
+        self.inner.eth_proof_window.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of times `eth_createAccessList` executes the transaction with the
+    /// access list of the previous execution applied, until the access list is stable.
+    pub fn set_access_list_max_iterations(&self, max_iterations: u64) {
+        self.inner.access_list_max_iterations.store(max_iterations, Ordering::Relaxed);
+    }
+
+    /// Returns the maximum number of executions of `eth_createAccessList`.
+    pub fn access_list_max_iterations(&self) -> u64 {
+        self.inner.access_list_max_iterations.load(Ordering::Relaxed)
+    }
+
+    /// Sets the caps on the gas limit, call depth and execution time of `eth_call` and
+    /// `eth_estimateGas` requests.
+    pub fn set_call_caps(&self, caps: CallCapsConfig) {
+        *self.inner.call_caps.write() = caps;
+    }
+
+    /// Returns the caps of `eth_call` and `eth_estimateGas` requests.
+    pub fn call_caps(&self) -> CallCapsConfig {
+        *self.inner.call_caps.read()
+    }
+
+    /// Returns the registry of custom error definitions used to decode the output of reverted
+    /// `eth_call` and `eth_estimateGas` requests.
+    pub fn revert_decoder(&self) -> &RevertDecoder {
+        &self.inner.revert_decoder
+    }
+
+    /// Decodes the custom error of a reverted call with the registered error definitions.
+    pub(crate) fn decode_revert(&self, err: EthApiError) -> EthApiError {
+        match err {
+            EthApiError::InvalidTransaction(RpcInvalidTransactionError::Revert(revert))
+                if !self.inner.revert_decoder.is_empty() =>
+            {
+                RpcInvalidTransactionError::Revert(revert.decode_with(&self.inner.revert_decoder))
+                    .into()
+            }
+            err => err,
+        }
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
@@ -168,6 +260,13 @@ where
             fee_history_cache,
             evm_config,
             raw_transaction_forwarder: parking_lot::RwLock::new(raw_transaction_forwarder),
+            raw_transaction_validator: Default::default(),
+            raw_transaction_sync: Default::default(),
+            response_cache: Default::default(),
+            eth_proof_window: Default::default(),
+            access_list_max_iterations: AtomicU64::new(DEFAULT_ACCESS_LIST_MAX_ITERATIONS),
+            revert_decoder: Default::default(),
+            call_caps: Default::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -188,7 +287,11 @@ where
         let (tx, rx) = oneshot::channel();
         let this = self.clone();
         let f = c(this);
+        let reads = ReadCounter::current();
         self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            // the future is driven to completion on the blocking thread, so the reads of the
+            // caller can be attributed for its whole lifetime
+            let _reads = reads.as_ref().map(ReadCounter::enter);
             let res = f.await;
             let _ = tx.send(res);
         }));
@@ -200,6 +303,16 @@ where
         &self.inner.eth_cache
     }
 
+    /// Returns the response cache if it's enabled and the given block is finalized.
+    pub(crate) fn response_cache_for_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> EthResult<Option<RpcResponseCache>> {
+        let Some(cache) = self.response_cache() else { return Ok(None) };
+        let finalized = self.provider().finalized_block_number()?;
+        Ok(finalized.is_some_and(|finalized| block_number <= finalized).then_some(cache))
+    }
+
     /// Returns the gas oracle frontend
     pub(crate) fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
         &self.inner.gas_oracle
@@ -500,4 +613,27 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     evm_config: EvmConfig,
     /// Allows forwarding received raw transactions
     raw_transaction_forwarder: parking_lot::RwLock<Option<Arc<dyn RawTransactionForwarder>>>,
+    /// Allows validating received raw transactions
+    raw_transaction_validator: parking_lot::RwLock<Option<Arc<dyn RawTransactionValidator>>>,
+    /// Enables waiting for the inclusion of raw transactions
+    raw_transaction_sync: parking_lot::RwLock<Option<RawTransactionSync>>,
+    /// Caches the responses of expensive read endpoints for finalized blocks
+    response_cache: parking_lot::RwLock<Option<RpcResponseCache>>,
+    /// The maximum number of blocks into the past for which `eth_getProof` generates proofs
+    eth_proof_window: AtomicU64,
+    /// The maximum number of executions of `eth_createAccessList`
+    access_list_max_iterations: AtomicU64,
+    /// Custom error definitions to decode the output of reverted calls
+    revert_decoder: RevertDecoder,
+    /// The caps of `eth_call` and `eth_estimateGas`
+    call_caps: parking_lot::RwLock<CallCapsConfig>,
+}
+
+/// The canonical state subscriptions and the maximum wait of `eth_sendRawTransactionSync`.
+#[derive(Clone)]
+struct RawTransactionSync {
+    /// Notifies about the blocks that may include the transaction
+    events: Arc<dyn CanonStateSubscriptions>,
+    /// The maximum duration to wait for the transaction to be included
+    timeout: Duration,
 }
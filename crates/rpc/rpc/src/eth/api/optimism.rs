@@ -1,5 +1,6 @@
 //! Optimism helpers.
 
+use reth_rpc_types::{OptimismTransactionReceiptFields, OtherFields};
 use revm::L1BlockInfo;
 
 /// Optimism Transaction Metadata
@@ -28,4 +29,90 @@ impl OptimismTxMeta {
     ) -> Self {
         Self { l1_block_info, l1_fee, l1_data_gas }
     }
+
+    /// Returns the L1 fee fields of a non-deposit transaction, for transaction and receipt
+    /// responses.
+    ///
+    /// After the Ecotone upgrade, this also includes the scalars and the L1 blob base fee that
+    /// the L1 fee is computed from.
+    pub(crate) fn l1_fee_fields(&self) -> OtherFields {
+        let Some(l1_block_info) = &self.l1_block_info else { return OtherFields::default() };
+
+        let fields = OptimismTransactionReceiptFields {
+            l1_fee: self.l1_fee,
+            l1_gas_used: self.l1_data_gas.map(|dg| {
+                dg + l1_block_info.l1_fee_overhead.unwrap_or_default().saturating_to::<u128>()
+            }),
+            l1_fee_scalar: Some(f64::from(l1_block_info.l1_base_fee_scalar) / 1_000_000.0),
+            l1_gas_price: Some(l1_block_info.l1_base_fee.saturating_to()),
+            ..Default::default()
+        };
+        let mut other = OtherFields::from(fields);
+
+        if let (Some(blob_base_fee), Some(blob_base_fee_scalar)) =
+            (l1_block_info.l1_blob_base_fee, l1_block_info.l1_blob_base_fee_scalar)
+        {
+            other.insert(
+                "l1BaseFeeScalar".to_string(),
+                serde_json::to_value(l1_block_info.l1_base_fee_scalar).unwrap_or_default(),
+            );
+            other.insert(
+                "l1BlobBaseFee".to_string(),
+                serde_json::to_value(blob_base_fee).unwrap_or_default(),
+            );
+            other.insert(
+                "l1BlobBaseFeeScalar".to_string(),
+                serde_json::to_value(blob_base_fee_scalar).unwrap_or_default(),
+            );
+        }
+
+        other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+
+    #[test]
+    fn l1_fee_fields_bedrock() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000_000_000u64),
+            l1_fee_overhead: Some(U256::from(188)),
+            l1_base_fee_scalar: U256::from(684_000),
+            ..Default::default()
+        };
+        let meta = OptimismTxMeta::new(Some(l1_block_info), Some(100), Some(1_000));
+        let fields = meta.l1_fee_fields();
+
+        assert_eq!(fields["l1Fee"], "0x64");
+        assert_eq!(fields["l1GasUsed"], "0x4a4");
+        assert_eq!(fields["l1GasPrice"], "0x3b9aca00");
+        assert_eq!(fields["l1FeeScalar"], "0.684");
+        assert!(!fields.contains_key("l1BlobBaseFee"));
+    }
+
+    #[test]
+    fn l1_fee_fields_ecotone() {
+        let l1_block_info = L1BlockInfo {
+            l1_base_fee: U256::from(1_000_000_000u64),
+            l1_base_fee_scalar: U256::from(1_368),
+            l1_blob_base_fee: Some(U256::from(1)),
+            l1_blob_base_fee_scalar: Some(U256::from(810_949)),
+            ..Default::default()
+        };
+        let meta = OptimismTxMeta::new(Some(l1_block_info), Some(100), Some(1_000));
+        let fields = meta.l1_fee_fields();
+
+        assert_eq!(fields["l1GasUsed"], "0x3e8");
+        assert_eq!(fields["l1BaseFeeScalar"], "0x558");
+        assert_eq!(fields["l1BlobBaseFee"], "0x1");
+        assert_eq!(fields["l1BlobBaseFeeScalar"], "0xc5fc5");
+    }
+
+    #[test]
+    fn l1_fee_fields_without_l1_block_info() {
+        assert!(OptimismTxMeta::default().l1_fee_fields().is_empty());
+    }
 }
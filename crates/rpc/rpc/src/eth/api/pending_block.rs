@@ -302,7 +302,7 @@ impl PendingBlockEnv {
 ///
 /// This uses [`apply_beacon_root_contract_call`] to ultimately apply the beacon root contract state
 /// change.
-fn pre_block_beacon_root_contract_call<DB: Database + DatabaseCommit>(
+pub(crate) fn pre_block_beacon_root_contract_call<DB: Database + DatabaseCommit>(
     db: &mut DB,
     chain_spec: &ChainSpec,
     block_number: u64,
@@ -340,7 +340,7 @@ where
 /// [`CfgEnvWithHandlerCfg`] and [`BlockEnv`].
 ///
 /// This uses [`apply_blockhashes_update`].
-fn pre_block_blockhashes_update<DB: Database<Error = ProviderError> + DatabaseCommit>(
+pub(crate) fn pre_block_blockhashes_update<DB: Database<Error = ProviderError> + DatabaseCommit>(
     db: &mut DB,
     chain_spec: &ChainSpec,
     initialized_block_env: &BlockEnv,
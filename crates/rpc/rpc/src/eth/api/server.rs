@@ -22,11 +22,12 @@ use reth_rpc_api::EthApiServer;
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey,
     state::{EvmOverrides, StateOverride},
-    AccessListWithGasUsed, AnyTransactionReceipt, BlockOverrides, Bundle,
+    AccessListWithGasUsed, AccountSummary, AnyTransactionReceipt, BlockOverrides, Bundle,
     EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header, Index, RichBlock,
-    StateContext, SyncStatus, TransactionRequest, Work,
+    SimulatePayload, SimulatedBlock, StateContext, SyncStatus, TransactionRequest, Work,
 };
 use reth_transaction_pool::TransactionPool;
+use std::time::Duration;
 use tracing::trace;
 
 #[async_trait::async_trait]
@@ -163,7 +164,10 @@ where
     /// Handler for: `eth_getTransactionByHash`
     async fn transaction_by_hash(&self, hash: B256) -> Result<Option<reth_rpc_types::Transaction>> {
         trace!(target: "rpc::eth", ?hash, "Serving eth_getTransactionByHash");
-        Ok(EthTransactions::transaction_by_hash(self, hash).await?.map(Into::into))
+        let Some(source) = EthTransactions::transaction_by_hash(self, hash).await? else {
+            return Ok(None)
+        };
+        Ok(Some(self.rpc_transaction(source).await?))
     }
 
     /// Handler for: `eth_getRawTransactionByBlockHashAndIndex`
@@ -218,6 +222,31 @@ where
         Ok(self.on_blocking_task(|this| async move { this.balance(address, block_number) }).await?)
     }
 
+    /// Handler for: `eth_getAccountInfo`
+    async fn account_info(
+        &self,
+        address: Address,
+        block_number: Option<BlockId>,
+    ) -> Result<AccountSummary> {
+        trace!(target: "rpc::eth", ?address, ?block_number, "Serving eth_getAccountInfo");
+        let mut infos = self
+            .on_blocking_task(|this| async move { this.account_infos(&[address], block_number) })
+            .await?;
+        Ok(infos.remove(0))
+    }
+
+    /// Handler for: `eth_getAccountInfos`
+    async fn account_infos(
+        &self,
+        addresses: Vec<Address>,
+        block_number: Option<BlockId>,
+    ) -> Result<Vec<AccountSummary>> {
+        trace!(target: "rpc::eth", ?addresses, ?block_number, "Serving eth_getAccountInfos");
+        Ok(self
+            .on_blocking_task(|this| async move { this.account_infos(&addresses, block_number) })
+            .await?)
+    }
+
     /// Handler for: `eth_getStorageAt`
     async fn storage_at(
         &self,
@@ -290,6 +319,16 @@ where
         Ok(Self::call_many(self, bundle, state_context, state_override).await?)
     }
 
+    /// Handler for: `eth_simulateV1`
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>> {
+        trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
+        Ok(Self::simulate_v1(self, payload, block_number).await?)
+    }
+
     /// Handler for: `eth_createAccessList`
     async fn create_access_list(
         &self,
@@ -387,6 +426,17 @@ where
         Ok(EthTransactions::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendRawTransactionSync`
+    async fn send_raw_transaction_sync(
+        &self,
+        tx: Bytes,
+        timeout: Option<u64>,
+    ) -> Result<AnyTransactionReceipt> {
+        trace!(target: "rpc::eth", ?tx, ?timeout, "Serving eth_sendRawTransactionSync");
+        let timeout = timeout.map(Duration::from_millis);
+        Ok(EthTransactions::send_raw_transaction_sync(self, tx, timeout).await?)
+    }
+
     /// Handler for: `eth_sign`
     async fn sign(&self, address: Address, message: Bytes) -> Result<Bytes> {
         trace!(target: "rpc::eth", ?address, ?message, "Serving eth_sign");
@@ -416,7 +466,7 @@ where
 
         Ok(res.map_err(|e| match e {
             EthApiError::InvalidBlockRange => {
-                internal_rpc_err("eth_getProof is unimplemented for historical blocks")
+                internal_rpc_err("eth_getProof is unimplemented for the pending block")
             }
             _ => e.into(),
         })?)
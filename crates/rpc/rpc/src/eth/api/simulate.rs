@@ -0,0 +1,542 @@
+//! Contains the implementation of `eth_simulateV1`.
+
+use crate::{
+    eth::{
+        api::pending_block::{pre_block_beacon_root_contract_call, pre_block_blockhashes_update},
+        error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        revm_utils::{apply_state_overrides, build_call_evm_env},
+        simulate::{
+            bundle_state_from_cache, simulated_transaction, SimulateError, TransferInspector,
+            MAX_SIMULATE_BLOCKS, SIMULATE_BLOCK_TIME,
+        },
+        CallCaps, EthTransactions,
+    },
+    EthApi,
+};
+use reth_chainspec::{ChainSpec, Hardfork};
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{
+    constants::{eip4844::DATA_GAS_PER_BLOB, BEACON_NONCE, EMPTY_ROOT_HASH},
+    logs_bloom, proofs,
+    revm::env::fill_block_env,
+    revm_primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, ExecutionResult, SpecId},
+    Block, BlockId, BlockWithSenders, Header, Receipt, Requests, SealedHeader, Withdrawals,
+    EMPTY_OMMER_ROOT_HASH, U256,
+};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_types::{
+    BlockTransactionsKind, Log, SimBlock, SimBlockOverrides, SimCallError, SimCallResult,
+    SimulatePayload, SimulatedBlock,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_transaction_pool::TransactionPool;
+use revm::{db::CacheDB, Database, DatabaseCommit};
+
+/// The JSON-RPC error code of a simulated call that reverted.
+const SIM_CALL_REVERTED_CODE: i32 = -32000;
+
+/// The JSON-RPC error code of a simulated call that halted.
+const SIM_CALL_HALTED_CODE: i32 = -32015;
+
+/// The database the blocks of `eth_simulateV1` are simulated on.
+type SimulateDb = CacheDB<StateProviderDatabase<StateProviderBox>>;
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Simulates the blocks of the payload on top of the given block (`eth_simulateV1`).
+    ///
+    /// The blocks are executed in order, each on top of the state of the previous one, and the
+    /// gaps between the requested block numbers are filled with empty blocks. The pending block is
+    /// not sealed, so the blocks of a `pending` request are simulated on top of the latest block.
+    ///
+    /// The gas limit of each call is capped by the RPC gas cap and the [`CallCaps`] of `eth_call`.
+    /// The state roots of the blocks are only computed if validation is requested, and are zero
+    /// otherwise.
+    pub async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> EthResult<Vec<SimulatedBlock>> {
+        let SimulatePayload {
+            block_state_calls,
+            trace_transfers,
+            validation,
+            return_full_transactions,
+        } = payload;
+        if block_state_calls.len() as u64 > MAX_SIMULATE_BLOCKS {
+            return Err(SimulateError::TooManyBlocks.into())
+        }
+
+        let base = block.unwrap_or_default();
+        let base = if base.is_pending() { BlockId::latest() } else { base };
+        let base =
+            self.provider().sealed_header_by_id(base)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let total_difficulty =
+            self.provider().header_td(&base.hash())?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let options = SimulateOptions {
+            trace_transfers,
+            validation,
+            kind: if return_full_transactions {
+                BlockTransactionsKind::Full
+            } else {
+                BlockTransactionsKind::Hashes
+            },
+            total_difficulty,
+            caps: self.call_caps().call,
+        };
+
+        let this = self.clone();
+        self.spawn_with_state_at_block(base.hash().into(), move |state| {
+            let chain_spec = this.provider().chain_spec();
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let max_number = base.number.saturating_add(MAX_SIMULATE_BLOCKS);
+
+            let mut parent = base;
+            let mut blocks = Vec::with_capacity(block_state_calls.len());
+            for SimBlock { block_overrides, state_overrides, calls } in block_state_calls {
+                let mut overrides = block_overrides.unwrap_or_default();
+                let number = *overrides.number.get_or_insert(parent.number + 1);
+                if number <= parent.number {
+                    return Err(SimulateError::BlockNumberNotIncreasing {
+                        number,
+                        parent: parent.number,
+                    }
+                    .into())
+                }
+                if number > max_number {
+                    return Err(SimulateError::TooManyBlocks.into())
+                }
+
+                // fill the gap to the requested number with empty blocks
+                while parent.number + 1 < number {
+                    let gap =
+                        SimBlockOverrides { number: Some(parent.number + 1), ..Default::default() };
+                    let (header, block) = this.simulate_block(
+                        &mut db,
+                        &chain_spec,
+                        &parent,
+                        gap,
+                        SimBlock::default(),
+                        &options,
+                    )?;
+                    blocks.push(block);
+                    parent = header;
+                }
+
+                let sim_block = SimBlock { block_overrides: None, state_overrides, calls };
+                let (header, block) = this.simulate_block(
+                    &mut db,
+                    &chain_spec,
+                    &parent,
+                    overrides,
+                    sim_block,
+                    &options,
+                )?;
+                blocks.push(block);
+                parent = header;
+            }
+
+            Ok(blocks)
+        })
+        .await
+    }
+
+    /// Simulates a block on top of its parent, and commits its state changes to the database.
+    ///
+    /// Returns the sealed header of the block, and the block with the results of its calls.
+    fn simulate_block(
+        &self,
+        db: &mut SimulateDb,
+        chain_spec: &ChainSpec,
+        parent: &SealedHeader,
+        overrides: SimBlockOverrides,
+        sim_block: SimBlock,
+        options: &SimulateOptions,
+    ) -> EthResult<(SealedHeader, SimulatedBlock)> {
+        let SimBlock { state_overrides, calls, .. } = sim_block;
+        let mut header = simulated_header(chain_spec, parent, &overrides, options.validation)?;
+        let number = header.number;
+        let timestamp = header.timestamp;
+
+        let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+        EvmConfig::fill_cfg_env(&mut cfg, chain_spec, &header, options.total_difficulty);
+        let mut block_env = BlockEnv::default();
+        let after_merge = cfg.handler_cfg.spec_id >= SpecId::MERGE;
+        fill_block_env(&mut block_env, chain_spec, &header, after_merge);
+        if let Some(blob) = block_env.blob_excess_gas_and_price.as_mut() {
+            if let Some(blob_base_fee) = overrides.blob_base_fee {
+                blob.blob_gasprice = blob_base_fee.saturating_to();
+            } else if !options.validation {
+                blob.blob_gasprice = 0;
+            }
+        }
+        if !options.validation {
+            // like `eth_call`, the calls are not required to pay for gas or to be sent by EOAs
+            cfg.disable_base_fee = true;
+            cfg.disable_eip3607 = true;
+        }
+
+        if let Some(block_hashes) = overrides.block_hash {
+            db.block_hashes
+                .extend(block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)));
+        }
+        if let Some(state_overrides) = state_overrides {
+            apply_state_overrides(state_overrides, db)?;
+        }
+
+        pre_block_beacon_root_contract_call(
+            db,
+            chain_spec,
+            number,
+            &cfg,
+            &block_env,
+            header.parent_beacon_block_root,
+        )?;
+        pre_block_blockhashes_update(db, chain_spec, &block_env, number, parent.hash())?;
+
+        let mut cumulative_gas_used = 0u64;
+        let mut blob_gas_used = 0u64;
+        let mut transactions = Vec::with_capacity(calls.len());
+        let mut senders = Vec::with_capacity(calls.len());
+        let mut receipts = Vec::with_capacity(calls.len());
+        let mut results = Vec::with_capacity(calls.len());
+        for mut call in calls {
+            let remaining_gas = header.gas_limit - cumulative_gas_used;
+            let requested_gas = call.gas.map(|gas| u64::try_from(gas).unwrap_or(u64::MAX));
+            let mut gas_limit = match requested_gas {
+                Some(gas) if gas > remaining_gas => {
+                    return Err(SimulateError::BlockGasLimitExceeded {
+                        number,
+                        gas_limit: header.gas_limit,
+                    }
+                    .into())
+                }
+                Some(gas) => gas,
+                None => remaining_gas,
+            }
+            .min(self.gas_cap());
+            // the block gas limit can be overridden, so the caps of `eth_call` apply to every call
+            options.caps.apply_gas_limit(requested_gas, &mut gas_limit)?;
+            call.gas = Some(gas_limit as u128);
+            if options.validation &&
+                call.gas_price.is_none() &&
+                call.max_fee_per_gas.is_none() &&
+                call.max_priority_fee_per_gas.is_none()
+            {
+                // pay the base fee without a tip if the call specifies no fees
+                call.max_fee_per_gas = header.base_fee_per_gas.map(u128::from);
+                call.max_priority_fee_per_gas = Some(0);
+            }
+
+            let sender = call.from.unwrap_or_default();
+            let nonce = match call.nonce {
+                Some(nonce) => nonce,
+                None => db.basic(sender)?.map(|account| account.nonce).unwrap_or_default(),
+            };
+            let mut env = build_call_evm_env(cfg.clone(), block_env.clone(), call)?;
+            // the nonce is only checked if the calls are validated
+            env.tx.nonce = options.validation.then_some(nonce);
+            let tx = simulated_transaction(&env.tx, nonce, cfg.chain_id);
+            blob_gas_used += env.tx.blob_hashes.len() as u64 * DATA_GAS_PER_BLOB;
+
+            let (res, logs) = if options.trace_transfers {
+                let mut inspector = TransferInspector::default();
+                let (res, _) =
+                    self.inspect(&mut *db, env, &mut inspector).map_err(validation_error)?;
+                (res, inspector.into_logs())
+            } else {
+                let (res, _) = self.transact(&mut *db, env).map_err(validation_error)?;
+                let logs = res.result.logs().to_vec();
+                (res, logs)
+            };
+            db.commit(res.state);
+
+            let result = res.result;
+            cumulative_gas_used += result.gas_used();
+            receipts.push(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used,
+                logs: result.logs().to_vec(),
+                #[cfg(feature = "optimism")]
+                deposit_nonce: None,
+                #[cfg(feature = "optimism")]
+                deposit_receipt_version: None,
+            });
+            results.push((sim_call_result(result, gas_limit), logs));
+            transactions.push(tx);
+            senders.push(sender);
+        }
+
+        let receipts = receipts.iter().collect::<Vec<_>>();
+        #[cfg(feature = "optimism")]
+        let receipts_root =
+            proofs::calculate_receipt_root_no_memo_optimism(&receipts, chain_spec, timestamp);
+        #[cfg(not(feature = "optimism"))]
+        let receipts_root = proofs::calculate_receipt_root_no_memo(&receipts);
+
+        header.receipts_root = receipts_root;
+        header.logs_bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+        header.transactions_root = proofs::calculate_transaction_root(&transactions);
+        header.gas_used = cumulative_gas_used;
+        header.blob_gas_used = header.excess_blob_gas.map(|_| blob_gas_used);
+        if options.validation {
+            header.state_root = db.db.state_root(&bundle_state_from_cache(db))?;
+        }
+
+        let header = header.seal_slow();
+        let block_hash = header.hash();
+        // the following blocks can access the hash with the `BLOCKHASH` opcode
+        db.block_hashes.insert(U256::from(number), block_hash);
+
+        let mut log_index = 0;
+        let calls = results
+            .into_iter()
+            .zip(&transactions)
+            .enumerate()
+            .map(|(tx_index, ((mut result, logs), tx))| {
+                if result.status == 1 {
+                    result.logs = logs
+                        .into_iter()
+                        .map(|inner| {
+                            log_index += 1;
+                            Log {
+                                inner,
+                                block_hash: Some(block_hash),
+                                block_number: Some(number),
+                                block_timestamp: Some(timestamp),
+                                transaction_hash: Some(tx.hash()),
+                                transaction_index: Some(tx_index as u64),
+                                log_index: Some(log_index - 1),
+                                removed: false,
+                            }
+                        })
+                        .collect();
+                }
+                result
+            })
+            .collect();
+
+        let withdrawals = header.withdrawals_root.map(|_| Withdrawals::default());
+        let requests = header.requests_root.map(|_| Requests::default());
+        let block = Block {
+            header: header.clone().unseal(),
+            body: transactions,
+            ommers: vec![],
+            withdrawals,
+            requests,
+        };
+        let inner = from_block(
+            BlockWithSenders { block, senders },
+            options.total_difficulty,
+            options.kind,
+            Some(block_hash),
+        )?;
+
+        Ok((header, SimulatedBlock { inner, calls }))
+    }
+}
+
+/// The options of an `eth_simulateV1` request that apply to all blocks.
+#[derive(Debug)]
+struct SimulateOptions {
+    trace_transfers: bool,
+    validation: bool,
+    kind: BlockTransactionsKind,
+    /// The total difficulty of the base block, which no longer changes after the merge
+    total_difficulty: U256,
+    /// The caps of `eth_call`, which apply to each simulated call
+    caps: CallCaps,
+}
+
+/// Returns the header of a simulated block without the fields that depend on its calls.
+fn simulated_header(
+    chain_spec: &ChainSpec,
+    parent: &SealedHeader,
+    overrides: &SimBlockOverrides,
+    validation: bool,
+) -> EthResult<Header> {
+    let number = overrides.number.unwrap_or(parent.number + 1);
+    let timestamp = overrides.time.unwrap_or(parent.timestamp + SIMULATE_BLOCK_TIME);
+    if timestamp <= parent.timestamp {
+        return Err(
+            SimulateError::TimestampNotIncreasing { timestamp, parent: parent.timestamp }.into()
+        )
+    }
+
+    let base_fee_per_gas =
+        chain_spec.is_fork_active_at_block(Hardfork::London, number).then(|| {
+            overrides.base_fee_per_gas.unwrap_or_else(|| {
+                if validation {
+                    parent
+                        .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
+                        .unwrap_or_default()
+                } else {
+                    0
+                }
+            })
+        });
+    let is_cancun = chain_spec.is_cancun_active_at_timestamp(timestamp);
+
+    Ok(Header {
+        parent_hash: parent.hash(),
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: overrides.fee_recipient.unwrap_or(parent.beneficiary),
+        withdrawals_root: chain_spec
+            .is_shanghai_active_at_timestamp(timestamp)
+            .then_some(EMPTY_ROOT_HASH),
+        difficulty: U256::ZERO,
+        number,
+        gas_limit: overrides.gas_limit.unwrap_or(parent.gas_limit),
+        timestamp,
+        mix_hash: overrides.prev_randao.unwrap_or(parent.mix_hash),
+        nonce: BEACON_NONCE,
+        base_fee_per_gas,
+        excess_blob_gas: is_cancun.then(|| parent.next_block_excess_blob_gas().unwrap_or_default()),
+        parent_beacon_block_root: is_cancun.then_some(Default::default()),
+        requests_root: chain_spec
+            .is_prague_active_at_timestamp(timestamp)
+            .then_some(EMPTY_ROOT_HASH),
+        ..Default::default()
+    })
+}
+
+/// Returns the result of a simulated call, without its logs.
+fn sim_call_result(result: ExecutionResult, gas_limit: u64) -> SimCallResult {
+    match result {
+        ExecutionResult::Success { output, gas_used, .. } => SimCallResult {
+            return_data: output.into_data(),
+            gas_used,
+            status: 1,
+            ..Default::default()
+        },
+        ExecutionResult::Revert { output, gas_used } => SimCallResult {
+            return_data: output.clone(),
+            gas_used,
+            status: 0,
+            error: Some(SimCallError {
+                code: SIM_CALL_REVERTED_CODE,
+                message: RevertError::new(output.clone()).to_string(),
+                data: Some(output),
+            }),
+            ..Default::default()
+        },
+        ExecutionResult::Halt { reason, gas_used } => SimCallResult {
+            gas_used,
+            status: 0,
+            error: Some(SimCallError {
+                code: SIM_CALL_HALTED_CODE,
+                message: RpcInvalidTransactionError::halt(reason, gas_limit).to_string(),
+                data: None,
+            }),
+            ..Default::default()
+        },
+    }
+}
+
+/// Reports calls that are not valid transactions with the codes of the spec.
+fn validation_error(err: EthApiError) -> EthApiError {
+    match err {
+        EthApiError::InvalidTransaction(err) => SimulateError::InvalidTransaction(err).into(),
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, call_caps::CallCapExceeded, gas_oracle::GasPriceOracle,
+        CallCapsConfig, FeeHistoryCache, FeeHistoryCacheConfig,
+    };
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, Bytes};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_types::TransactionRequest;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::testing_pool;
+    use std::sync::Arc;
+
+    const GAS_CAP: u64 = 100_000;
+
+    #[tokio::test]
+    async fn simulate_caps_call_gas() {
+        let provider = MockEthProvider {
+            chain_spec: Arc::new(ChainSpecBuilder::mainnet().london_activated().build()),
+            ..Default::default()
+        };
+        let header = Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() };
+        provider.add_header(header.hash_slow(), header.clone());
+        provider.add_block(header.hash_slow(), Block { header, ..Default::default() });
+
+        let sender = Address::with_last_byte(1);
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(u64::MAX)));
+        // loops until it runs out of gas
+        let looper = Address::with_last_byte(2);
+        provider.add_account(
+            looper,
+            ExtendedAccount::new(0, U256::ZERO)
+                .with_bytecode(Bytes::from_static(&[0x5b, 0x60, 0x00, 0x56])),
+        );
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            GAS_CAP,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        let simulate = |gas: Option<u128>| {
+            let eth_api = eth_api.clone();
+            let mut call = TransactionRequest::default().from(sender).to(looper);
+            call.gas = gas;
+            let payload = SimulatePayload {
+                block_state_calls: vec![SimBlock { calls: vec![call], ..Default::default() }],
+                trace_transfers: false,
+                validation: false,
+                return_full_transactions: false,
+            };
+            async move { eth_api.simulate_v1(payload, Some(BlockId::number(0))).await }
+        };
+
+        // an explicit gas limit below the block gas limit is still lowered to the gas cap
+        let blocks = simulate(Some(ETHEREUM_BLOCK_GAS_LIMIT as u128 / 2)).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].calls[0].status, 0);
+        assert_eq!(blocks[0].calls[0].gas_used, GAS_CAP);
+
+        // the gas limit cap of `eth_call` rejects higher gas limits and lowers missing ones
+        let cap = GAS_CAP / 2;
+        eth_api.set_call_caps(CallCapsConfig {
+            call: CallCaps::default().with_gas_limit(Some(cap)),
+            ..Default::default()
+        });
+        let err = simulate(Some(cap as u128 + 1)).await.unwrap_err();
+        assert!(
+            matches!(err, EthApiError::CallCapExceeded(CallCapExceeded::GasLimit(c)) if c == cap)
+        );
+        let blocks = simulate(None).await.unwrap();
+        assert_eq!(blocks[0].calls[0].gas_used, cap);
+    }
+}
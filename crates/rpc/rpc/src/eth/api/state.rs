@@ -4,15 +4,20 @@ use crate::{
     eth::error::{EthApiError, EthResult, RpcInvalidTransactionError},
     EthApi,
 };
+use reth_db_api::read_counter::ReadCounter;
 use reth_evm::ConfigureEvm;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, U256};
+use reth_primitives::{Address, BlockId, Bytes, B256, KECCAK_EMPTY, U256};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
+    AccountReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider,
+    StateProviderFactory,
 };
-use reth_rpc_types::{serde_helpers::JsonStorageKey, EIP1186AccountProofResponse};
+use reth_rpc_types::{serde_helpers::JsonStorageKey, AccountSummary, EIP1186AccountProofResponse};
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
+/// The maximum number of accounts that can be requested with a single `eth_getAccountInfos` call.
+const MAX_ACCOUNT_INFOS: usize = 1024;
+
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
 where
     Provider:
@@ -36,31 +41,84 @@ where
             .unwrap_or_default())
     }
 
+    /// Returns the balance, nonce, code hash and code size of the accounts at the given block
+    /// identifier.
+    ///
+    /// The nonce of the [`BlockNumberOrTag::Pending`](reth_primitives::BlockNumberOrTag) block
+    /// accounts for the transactions in the pool, like
+    /// [`get_transaction_count`](Self::get_transaction_count).
+    pub(crate) fn account_infos(
+        &self,
+        addresses: &[Address],
+        block_id: Option<BlockId>,
+    ) -> EthResult<Vec<AccountSummary>> {
+        if addresses.len() > MAX_ACCOUNT_INFOS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many accounts requested: {} > {MAX_ACCOUNT_INFOS}",
+                addresses.len()
+            )))
+        }
+
+        let state = self.state_at_block_id_or_latest(block_id)?;
+        addresses
+            .iter()
+            .map(|&address| {
+                let account = state.basic_account(address)?.unwrap_or_default();
+                let nonce = match self.pool_nonce(address, block_id)? {
+                    Some(nonce) => nonce,
+                    None => account.nonce,
+                };
+                let code_size = match account.bytecode_hash {
+                    Some(code_hash) if code_hash != KECCAK_EMPTY => state
+                        .bytecode_by_hash(code_hash)?
+                        .map_or(0, |code| code.original_bytes().len() as u64),
+                    _ => 0,
+                };
+                Ok(AccountSummary {
+                    address,
+                    balance: account.balance,
+                    nonce,
+                    code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                    code_size,
+                })
+            })
+            .collect()
+    }
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
-    /// If this is [`BlockNumberOrTag::Pending`] then this will look up the highest transaction in
-    /// pool and return the next nonce (highest + 1).
+    /// If this is [`BlockNumberOrTag::Pending`](reth_primitives::BlockNumberOrTag) then this will
+    /// look up the highest transaction in pool and return the next nonce (highest + 1).
     pub(crate) fn get_transaction_count(
         &self,
         address: Address,
         block_id: Option<BlockId>,
     ) -> EthResult<U256> {
-        if block_id == Some(BlockId::pending()) {
-            let address_txs = self.pool().get_transactions_by_sender(address);
-            if let Some(highest_nonce) =
-                address_txs.iter().map(|item| item.transaction.nonce()).max()
-            {
-                let tx_count = highest_nonce
-                    .checked_add(1)
-                    .ok_or(RpcInvalidTransactionError::NonceMaxValue)?;
-                return Ok(U256::from(tx_count))
-            }
+        if let Some(tx_count) = self.pool_nonce(address, block_id)? {
+            return Ok(U256::from(tx_count))
         }
 
         let state = self.state_at_block_id_or_latest(block_id)?;
         Ok(U256::from(state.account_nonce(address)?.unwrap_or_default()))
     }
 
+    /// Returns the next nonce of the address after its transactions in the pool, if the block
+    /// identifier is the pending block and the pool has transactions of the address.
+    fn pool_nonce(&self, address: Address, block_id: Option<BlockId>) -> EthResult<Option<u64>> {
+        if block_id != Some(BlockId::pending()) {
+            return Ok(None)
+        }
+        let address_txs = self.pool().get_transactions_by_sender(address);
+        address_txs
+            .iter()
+            .map(|item| item.transaction.nonce())
+            .max()
+            .map(|highest_nonce| {
+                highest_nonce.checked_add(1).ok_or(RpcInvalidTransactionError::NonceMaxValue.into())
+            })
+            .transpose()
+    }
+
     pub(crate) fn storage_at(
         &self,
         address: Address,
@@ -81,35 +139,55 @@ where
         keys: Vec<JsonStorageKey>,
         block_id: Option<BlockId>,
     ) -> EthResult<EIP1186AccountProofResponse> {
-        let chain_info = self.provider().chain_info()?;
         let block_id = block_id.unwrap_or_default();
 
-        // if we are trying to create a proof for the latest block, but have a BlockId as input
-        // that is not BlockNumberOrTag::Latest, then we need to figure out whether or not the
-        // BlockId corresponds to the latest block
-        let is_latest_block = match block_id {
-            BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
-            BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
-            BlockId::Number(BlockNumberOrTag::Latest) => true,
-            _ => false,
-        };
-
-        // TODO: remove when HistoricalStateProviderRef::proof is implemented
-        if !is_latest_block {
+        // the state of the pending block isn't committed, so it can't be proven
+        if block_id.is_pending() {
             return Err(EthApiError::InvalidBlockRange)
         }
 
+        let block_hash =
+            self.provider().block_hash_for_id(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let block_number = self
+            .provider()
+            .block_number_for_id(block_id)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        // proofs for historical blocks are generated by reverting all changes since the block in
+        // memory, so they are limited to the configured window
+        let best_number = self.provider().best_block_number()?;
+        if best_number.saturating_sub(block_number) > self.eth_proof_window() {
+            return Err(EthApiError::ExceedsMaxProofWindow)
+        }
+
+        // proofs are cached by block hash, the state of which can't change even if the block is
+        // reorged out, so they can be served from the cache regardless of finality
+        let storage_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
+        let response_cache = self.response_cache();
+        if let Some(proof) = response_cache
+            .as_ref()
+            .and_then(|cache| cache.get_proof(block_hash, address, &storage_keys))
+        {
+            return Ok(proof)
+        }
+
         let this = self.clone();
-        self.inner
+        let (proof, storage_keys) = self
+            .inner
             .blocking_task_pool
-            .spawn(move || {
-                let state = this.state_at_block_id(block_id)?;
-                let storage_keys = keys.iter().map(|key| key.0).collect::<Vec<_>>();
+            .spawn(ReadCounter::in_current(move || {
+                let state = this.state_at_hash(block_hash)?;
                 let proof = state.proof(address, &storage_keys)?;
-                Ok(from_primitive_account_proof(proof))
-            })
+                Ok::<_, EthApiError>((from_primitive_account_proof(proof), storage_keys))
+            }))
             .await
-            .map_err(|_| EthApiError::InternalBlockingTaskError)?
+            .map_err(|_| EthApiError::InternalBlockingTaskError)??;
+
+        if let Some(cache) = response_cache {
+            cache.insert_proof(block_hash, address, storage_keys, proof.clone());
+        }
+
+        Ok(proof)
     }
 }
 
@@ -117,14 +195,16 @@ where
 mod tests {
     use super::*;
     use crate::eth::{
-        cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache, FeeHistoryCacheConfig,
+        cache::{EthStateCache, RpcResponseCache, RpcResponseCacheConfig},
+        gas_oracle::GasPriceOracle,
+        FeeHistoryCache, FeeHistoryCacheConfig,
     };
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, StorageKey, StorageValue};
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Block, StorageKey, StorageValue};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::testing_pool;
-    use std::collections::HashMap;
+    use std::{collections::HashMap, time::Duration};
 
     #[tokio::test]
     async fn test_storage() {
@@ -175,4 +255,88 @@ mod tests {
         let storage = eth_api.storage_at(address, storage_key.into(), None).unwrap();
         assert_eq!(storage, storage_value.to_be_bytes());
     }
+
+    #[tokio::test]
+    async fn test_account_infos() {
+        let mock_provider = MockEthProvider::default();
+        let eoa = Address::random();
+        mock_provider.add_account(eoa, ExtendedAccount::new(7, U256::from(1337)));
+        let contract = Address::random();
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xfd]);
+        mock_provider
+            .add_account(contract, ExtendedAccount::new(1, U256::ZERO).with_bytecode(code.clone()));
+
+        let pool = testing_pool();
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            mock_provider.clone(),
+            pool,
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+
+        let missing = Address::random();
+        let infos = eth_api.account_infos(&[eoa, contract, missing], None).unwrap();
+        assert_eq!(
+            infos[0],
+            AccountSummary {
+                address: eoa,
+                balance: U256::from(1337),
+                nonce: 7,
+                code_hash: KECCAK_EMPTY,
+                code_size: 0,
+            }
+        );
+        assert_eq!(infos[1].code_hash, reth_primitives::keccak256(code));
+        assert_eq!(infos[1].code_size, 5);
+        assert_eq!(
+            infos[2],
+            AccountSummary { address: missing, code_hash: KECCAK_EMPTY, ..Default::default() }
+        );
+
+        let too_many = vec![eoa; MAX_ACCOUNT_INFOS + 1];
+        assert!(eth_api.account_infos(&too_many, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn proofs_of_unfinalized_blocks_are_cached() {
+        let mock_provider = MockEthProvider::default();
+        let block = Block::default();
+        let block_hash = block.header.hash_slow();
+        mock_provider.add_block(block_hash, block);
+        let address = Address::random();
+        mock_provider.add_account(address, ExtendedAccount::new(0, U256::ZERO));
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            mock_provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+        let response_cache = RpcResponseCache::new(RpcResponseCacheConfig {
+            max_responses: 8,
+            ttl: Duration::from_secs(60),
+        })
+        .unwrap();
+        eth_api.set_response_cache(response_cache.clone());
+
+        // no block is finalized, but the proof is cached by the hash of the latest block
+        let proof = eth_api.get_proof(address, Vec::new(), None).await.unwrap();
+        assert_eq!(response_cache.get_proof(block_hash, address, &[]), Some(proof));
+    }
 }
@@ -10,6 +10,8 @@ use crate::{
 };
 use alloy_primitives::TxKind as RpcTransactionKind;
 use async_trait::async_trait;
+use futures::StreamExt;
+use reth_db_api::read_counter::ReadCounter;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
@@ -46,7 +48,7 @@ use revm::{
     GetInspector, Inspector,
 };
 use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
-use std::future::Future;
+use std::{future::Future, time::Duration};
 
 use crate::eth::revm_utils::FillableTransaction;
 #[cfg(feature = "optimism")]
@@ -250,6 +252,16 @@ pub trait EthTransactions: Send + Sync {
         hash: B256,
     ) -> EthResult<Option<(TransactionSource, B256)>>;
 
+    /// Returns the hash of the canonical transaction of the sender with the given nonce, if the
+    /// optional sender nonce index is enabled.
+    ///
+    /// Returns `Ok(None)` if the index is not enabled or doesn't contain the transaction.
+    async fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> EthResult<Option<B256>>;
+
     /// Returns the transaction receipt for the given hash.
     ///
     /// Returns None if the transaction does not exist or is pending
@@ -261,6 +273,17 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Submits the transaction like [`Self::send_raw_transaction`] and waits until it is included
+    /// in a canonical block.
+    ///
+    /// Returns the receipt of the transaction, or an error if it is not included within the given
+    /// timeout, capped at the configured maximum.
+    async fn send_raw_transaction_sync(
+        &self,
+        tx: Bytes,
+        timeout: Option<Duration>,
+    ) -> EthResult<AnyTransactionReceipt>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
@@ -822,41 +845,55 @@ where
         }
     }
 
+    async fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> EthResult<Option<B256>> {
+        self.on_blocking_task(|this| async move {
+            Ok(this.provider().transaction_hash_by_sender_and_nonce(sender, nonce)?)
+        })
+        .await
+    }
+
     async fn transaction_receipt(&self, hash: B256) -> EthResult<Option<AnyTransactionReceipt>> {
         let result = self
             .on_blocking_task(|this| async move {
-                let (tx, meta) = match this.provider().transaction_by_hash_with_meta(hash)? {
-                    Some((tx, meta)) => (tx, meta),
-                    None => return Ok(None),
-                };
-
-                let receipt = match this.provider().receipt_by_hash(hash)? {
-                    Some(recpt) => recpt,
-                    None => return Ok(None),
-                };
-
-                Ok(Some((tx, meta, receipt)))
+                this.provider().transaction_by_hash_with_meta(hash).map_err(Into::into)
             })
             .await?;
 
-        let (tx, meta, receipt) = match result {
-            Some((tx, meta, receipt)) => (tx, meta, receipt),
+        let (tx, meta) = match result {
+            Some((tx, meta)) => (tx, meta),
             None => return Ok(None),
         };
 
-        self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
+        self.build_transaction_receipt(tx, meta).await
     }
 
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256> {
+        let recovered = recover_raw_transaction(tx.clone())?;
+
+        let maybe_validator = self.inner.raw_transaction_validator.read().clone();
+        if let Some(validator) = maybe_validator {
+            validator.validate_raw_transaction(&recovered).await?;
+        }
+
         // On optimism, transactions are forwarded directly to the sequencer to be included in
         // blocks that it builds.
         let maybe_forwarder = self.inner.raw_transaction_forwarder.read().clone();
+        let mut submit_to_pool = true;
         if let Some(client) = maybe_forwarder {
             tracing::debug!( target: "rpc::eth",  "forwarding raw transaction to");
             client.forward_raw_transaction(&tx).await?;
+            submit_to_pool = client.submit_to_pool();
+        }
+
+        if !submit_to_pool {
+            // the transaction is only handled by the forwarder
+            return Ok(*recovered.hash())
         }
 
-        let recovered = recover_raw_transaction(tx)?;
         let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
 
         // submit the transaction to the pool with a `Local` origin
@@ -865,6 +902,37 @@ where
         Ok(hash)
     }
 
+    async fn send_raw_transaction_sync(
+        &self,
+        tx: Bytes,
+        timeout: Option<Duration>,
+    ) -> EthResult<AnyTransactionReceipt> {
+        let Some(sync) = self.inner.raw_transaction_sync.read().clone() else {
+            return Err(EthApiError::Unsupported("eth_sendRawTransactionSync is not enabled"))
+        };
+        let timeout = timeout.map_or(sync.timeout, |timeout| timeout.min(sync.timeout));
+
+        // subscribe before submitting, so that the block that includes the transaction can't be
+        // missed
+        let mut notifications = sync.events.canonical_state_stream();
+        let hash = self.send_raw_transaction(tx).await?;
+
+        let wait_for_receipt = async {
+            // the stream skips notifications if it lags behind, so the receipt is looked up on
+            // every notification instead of searching the committed blocks for the transaction
+            while notifications.next().await.is_some() {
+                if let Some(receipt) = self.transaction_receipt(hash).await? {
+                    return Ok(receipt)
+                }
+            }
+            Err(EthApiError::InternalEthError)
+        };
+
+        tokio::time::timeout(timeout, wait_for_receipt)
+            .await
+            .map_err(|_| EthApiError::TransactionInclusionTimedOut { hash, timeout })?
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -1075,7 +1143,7 @@ where
         let this = self.clone();
         self.inner
             .blocking_task_pool
-            .spawn(move || {
+            .spawn(ReadCounter::in_current(move || {
                 let state = this.state_at(at)?;
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
@@ -1088,7 +1156,7 @@ where
                     overrides,
                 )?;
                 f(&mut db, env)
-            })
+            }))
             .await
             .map_err(|_| EthApiError::InternalBlockingTaskError)?
     }
@@ -1375,7 +1443,7 @@ where
         let this = self.clone();
         self.inner
             .blocking_task_pool
-            .spawn(move || f(this))
+            .spawn(ReadCounter::in_current(move || f(this)))
             .await
             .map_err(|_| EthApiError::InternalBlockingTaskError)?
     }
@@ -1472,13 +1540,19 @@ where
             let block_number = block.number;
             let base_fee_per_gas = block.base_fee_per_gas;
             if let Some(tx) = block.into_transactions_ecrecovered().nth(index.into()) {
-                return Ok(Some(from_recovered_with_block_context(
+                #[cfg(feature = "optimism")]
+                let l1_fee_fields = self.l1_fee_fields(&tx, block_hash).await?;
+                #[allow(unused_mut)]
+                let mut tx = from_recovered_with_block_context(
                     tx,
                     block_hash,
                     block_number,
                     base_fee_per_gas,
                     index.into(),
-                )))
+                );
+                #[cfg(feature = "optimism")]
+                tx.other.extend(l1_fee_fields);
+                return Ok(Some(tx))
             }
         }
 
@@ -1506,37 +1580,40 @@ where
 {
     /// Helper function for `eth_getTransactionReceipt`
     ///
-    /// Returns the receipt
+    /// Returns the receipt, which is taken from the cached receipts of the block instead of being
+    /// looked up separately.
     #[cfg(not(feature = "optimism"))]
     pub(crate) async fn build_transaction_receipt(
         &self,
         tx: TransactionSigned,
         meta: TransactionMeta,
-        receipt: Receipt,
-    ) -> EthResult<AnyTransactionReceipt> {
+    ) -> EthResult<Option<AnyTransactionReceipt>> {
         // get all receipts for the block
         let all_receipts = match self.cache().get_receipts(meta.block_hash).await? {
             Some(recpts) => recpts,
             None => return Err(EthApiError::UnknownBlockNumber),
         };
-        build_transaction_receipt_with_block_receipts(tx, meta, receipt, &all_receipts)
+        let Some(receipt) = all_receipts.get(meta.index as usize).cloned() else { return Ok(None) };
+        build_transaction_receipt_with_block_receipts(tx, meta, receipt, &all_receipts).map(Some)
     }
 
     /// Helper function for `eth_getTransactionReceipt` (optimism)
     ///
-    /// Returns the receipt
+    /// Returns the receipt, which is taken from the cached receipts of the block instead of being
+    /// looked up separately. This includes the deposit nonce and receipt version of deposit
+    /// transactions.
     #[cfg(feature = "optimism")]
     pub(crate) async fn build_transaction_receipt(
         &self,
         tx: TransactionSigned,
         meta: TransactionMeta,
-        receipt: Receipt,
-    ) -> EthResult<AnyTransactionReceipt> {
+    ) -> EthResult<Option<AnyTransactionReceipt>> {
         let (block, receipts) = self
             .cache()
             .get_block_and_receipts(meta.block_hash)
             .await?
             .ok_or(EthApiError::UnknownBlockNumber)?;
+        let Some(receipt) = receipts.get(meta.index as usize).cloned() else { return Ok(None) };
 
         let block = block.unseal();
         let l1_block_info = reth_evm_optimism::extract_l1_info(&block).ok();
@@ -1549,6 +1626,44 @@ where
             &receipts,
             optimism_tx_meta,
         )
+        .map(Some)
+    }
+
+    /// Converts the [`TransactionSource`] into an rpc [`Transaction`].
+    ///
+    /// On optimism, mined transactions include their L1 fee fields.
+    pub(crate) async fn rpc_transaction(
+        &self,
+        source: TransactionSource,
+    ) -> EthResult<Transaction> {
+        #[cfg(feature = "optimism")]
+        if let TransactionSource::Block { transaction, block_hash, .. } = &source {
+            let l1_fee_fields = self.l1_fee_fields(transaction, *block_hash).await?;
+            let mut tx = Transaction::from(source);
+            tx.other.extend(l1_fee_fields);
+            return Ok(tx)
+        }
+
+        Ok(source.into())
+    }
+
+    /// Returns the L1 fee fields of the given transaction that's part of the given block.
+    ///
+    /// Deposit transactions have no L1 fee fields.
+    #[cfg(feature = "optimism")]
+    pub(crate) async fn l1_fee_fields(
+        &self,
+        tx: &TransactionSigned,
+        block_hash: B256,
+    ) -> EthResult<reth_rpc_types::OtherFields> {
+        if tx.is_deposit() {
+            return Ok(Default::default())
+        }
+        let Some(block) = self.cache().get_block(block_hash).await? else {
+            return Ok(Default::default())
+        };
+        let l1_block_info = reth_evm_optimism::extract_l1_info(&block).ok();
+        Ok(self.build_op_tx_meta(tx, l1_block_info, block.timestamp)?.l1_fee_fields())
     }
 
     /// Builds op metadata object using the provided [`TransactionSigned`], L1 block info and
@@ -1692,11 +1807,6 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
     all_receipts: &[Receipt],
     #[cfg(feature = "optimism")] optimism_tx_meta: crate::eth::api::optimism::OptimismTxMeta,
 ) -> EthResult<AnyTransactionReceipt> {
-    // Note: we assume this transaction is valid, because it's mined (or part of pending block) and
-    // we don't need to check for pre EIP-2
-    let from =
-        transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
-
     // get the previous transaction cumulative gas used
     let gas_used = if meta.index == 0 {
         receipt.cumulative_gas_used
@@ -1708,17 +1818,66 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
             .unwrap_or_default()
     };
 
-    let blob_gas_used = transaction.transaction.blob_gas_used();
-    // Blob gas price should only be present if the transaction is a blob transaction
-    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
-    let logs_bloom = receipt.bloom_slow();
-
     // get number of logs in the block
     let mut num_logs = 0;
     for prev_receipt in all_receipts.iter().take(meta.index as usize) {
         num_logs += prev_receipt.logs.len();
     }
 
+    build_transaction_receipt_with_log_index(
+        transaction,
+        meta,
+        receipt,
+        gas_used,
+        num_logs,
+        #[cfg(feature = "optimism")]
+        optimism_tx_meta,
+    )
+}
+
+/// Helper function to construct a transaction receipt from the gas used by the transaction and the
+/// number of logs in the block before it.
+pub(crate) fn build_transaction_receipt_with_log_index(
+    transaction: TransactionSigned,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    num_logs: usize,
+    #[cfg(feature = "optimism")] optimism_tx_meta: crate::eth::api::optimism::OptimismTxMeta,
+) -> EthResult<AnyTransactionReceipt> {
+    // Note: we assume this transaction is valid, because it's mined (or part of pending block) and
+    // we don't need to check for pre EIP-2
+    let from =
+        transaction.recover_signer_unchecked().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+    Ok(build_transaction_receipt_with_sender(
+        transaction,
+        from,
+        meta,
+        receipt,
+        gas_used,
+        num_logs,
+        #[cfg(feature = "optimism")]
+        optimism_tx_meta,
+    ))
+}
+
+/// Helper function to construct a transaction receipt from the known sender of the transaction,
+/// the gas used by the transaction and the number of logs in the block before it.
+pub(crate) fn build_transaction_receipt_with_sender(
+    transaction: TransactionSigned,
+    from: Address,
+    meta: TransactionMeta,
+    receipt: Receipt,
+    gas_used: u64,
+    num_logs: usize,
+    #[cfg(feature = "optimism")] optimism_tx_meta: crate::eth::api::optimism::OptimismTxMeta,
+) -> AnyTransactionReceipt {
+    let blob_gas_used = transaction.transaction.blob_gas_used();
+    // Blob gas price should only be present if the transaction is a blob transaction
+    let blob_gas_price = blob_gas_used.and_then(|_| meta.excess_blob_gas.map(calc_blob_gasprice));
+    let logs_bloom = receipt.bloom_slow();
+
     let mut logs = Vec::with_capacity(receipt.logs.len());
     for (tx_log_idx, log) in receipt.logs.into_iter().enumerate() {
         let rpclog = Log {
@@ -1764,24 +1923,17 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
     let mut res_receipt = WithOtherFields::new(res_receipt);
 
     #[cfg(feature = "optimism")]
-    {
-        let mut op_fields = OptimismTransactionReceiptFields::default();
-
-        if transaction.is_deposit() {
-            op_fields.deposit_nonce = receipt.deposit_nonce.map(reth_primitives::U64::from);
-            op_fields.deposit_receipt_version =
-                receipt.deposit_receipt_version.map(reth_primitives::U64::from);
-        } else if let Some(l1_block_info) = optimism_tx_meta.l1_block_info {
-            op_fields.l1_fee = optimism_tx_meta.l1_fee;
-            op_fields.l1_gas_used = optimism_tx_meta.l1_data_gas.map(|dg| {
-                dg + l1_block_info.l1_fee_overhead.unwrap_or_default().saturating_to::<u128>()
-            });
-            op_fields.l1_fee_scalar =
-                Some(f64::from(l1_block_info.l1_base_fee_scalar) / 1_000_000.0);
-            op_fields.l1_gas_price = Some(l1_block_info.l1_base_fee.saturating_to());
-        }
-
+    if transaction.is_deposit() {
+        let op_fields = OptimismTransactionReceiptFields {
+            deposit_nonce: receipt.deposit_nonce.map(reth_primitives::U64::from),
+            deposit_receipt_version: receipt
+                .deposit_receipt_version
+                .map(reth_primitives::U64::from),
+            ..Default::default()
+        };
         res_receipt.other = op_fields.into();
+    } else {
+        res_receipt.other = optimism_tx_meta.l1_fee_fields();
     }
 
     match transaction.transaction.kind() {
@@ -1793,7 +1945,7 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
         }
     }
 
-    Ok(res_receipt)
+    res_receipt
 }
 
 #[cfg(test)]
@@ -1805,33 +1957,37 @@ mod tests {
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex};
-    use reth_provider::test_utils::NoopProvider;
+    use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_transaction_pool::test_utils::testing_pool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use std::sync::Arc;
 
-    #[tokio::test]
-    async fn send_raw_transaction() {
+    fn noop_eth_api(pool: TestPool) -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
         let noop_provider = NoopProvider::default();
         let noop_network_provider = NoopNetwork::default();
 
-        let pool = testing_pool();
-
         let evm_config = EthEvmConfig::default();
         let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config);
         let fee_history_cache =
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
-        let eth_api = EthApi::new(
+        EthApi::new(
             noop_provider,
-            pool.clone(),
+            pool,
             noop_network_provider,
             cache.clone(),
-            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            GasPriceOracle::new(noop_provider, Default::default(), cache),
             ETHEREUM_BLOCK_GAS_LIMIT,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             fee_history_cache,
             evm_config,
             None,
-        );
+        )
+    }
+
+    #[tokio::test]
+    async fn send_raw_transaction() {
+        let pool = testing_pool();
+        let eth_api = noop_eth_api(pool.clone());
 
         // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
         let tx_1 = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
@@ -1858,4 +2014,32 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_raw_transaction_sync() {
+        let pool = testing_pool();
+        let eth_api = noop_eth_api(pool.clone());
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        let err = eth_api.send_raw_transaction_sync(tx.clone(), None).await.unwrap_err();
+        assert!(matches!(err, EthApiError::Unsupported(_)));
+        assert!(pool.is_empty());
+
+        eth_api.set_eth_raw_transaction_sync(
+            Arc::new(TestCanonStateSubscriptions::default()),
+            Duration::from_secs(60),
+        );
+
+        // the requested timeout is below the configured maximum, and the transaction is never
+        // included
+        let timeout = Duration::from_millis(10);
+        let err = eth_api.send_raw_transaction_sync(tx, Some(timeout)).await.unwrap_err();
+        let EthApiError::TransactionInclusionTimedOut { hash, timeout: waited } = err else {
+            panic!("unexpected error: {err:?}")
+        };
+        assert_eq!(waited, timeout);
+        assert!(pool.get(&hash).is_some(), "tx not found in the pool");
+    }
 }
@@ -7,14 +7,21 @@ use crate::eth::{
     EthTransactions,
 };
 use jsonrpsee::core::RpcResult;
+use reth_payload_primitives::{
+    BundlePool, BundlePoolError, QueuedBundle, MAX_BUNDLE_BLOCKS_AHEAD, MAX_BUNDLE_TRANSACTIONS,
+};
 use reth_primitives::{
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    PooledTransactionsElement, U256,
+    BlockNumberOrTag, Bytes, PooledTransactionsElement, B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::EthCallBundleApiServer;
-use reth_rpc_types::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_api::{EthBundleApiServer, EthCallBundleApiServer};
+use reth_rpc_types::{
+    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthCallBundleTransactionResult, EthSendBundle,
+    PrivateTransactionRequest,
+};
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
@@ -32,7 +39,28 @@ pub struct EthBundle<Eth> {
 impl<Eth> EthBundle<Eth> {
     /// Create a new `EthBundle` instance.
     pub fn new(eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard }) }
+        Self { inner: Arc::new(EthBundleInner { eth_api, blocking_task_guard, bundle_pool: None }) }
+    }
+
+    /// Create a new `EthBundle` instance that accepts bundles via `eth_sendBundle` and inserts them
+    /// into the given [`BundlePool`], to be included in the payloads that are built locally.
+    pub fn with_bundle_pool(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        bundle_pool: Arc<dyn BundlePool>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(EthBundleInner {
+                eth_api,
+                blocking_task_guard,
+                bundle_pool: Some(bundle_pool),
+            }),
+        }
+    }
+
+    /// Returns the pool of the submitted bundles, if bundle submission is enabled.
+    fn bundle_pool(&self) -> EthResult<&dyn BundlePool> {
+        self.inner.bundle_pool.as_deref().ok_or(EthApiError::Unsupported("bundles are not enabled"))
     }
 }
 
@@ -207,6 +235,98 @@ where
             })
             .await
     }
+
+    /// Validates a bundle against the current head and queues it for inclusion at the top of the
+    /// payloads that are built for its target block.
+    ///
+    /// The bundle must target one of the next [`MAX_BUNDLE_BLOCKS_AHEAD`] + 1 blocks. It's
+    /// simulated at the top of the next block with the latest state, and rejected if any of its
+    /// transactions reverts that isn't listed as possibly reverting.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let pool = self.bundle_pool()?;
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        if txs.len() > MAX_BUNDLE_TRANSACTIONS {
+            return Err(EthApiError::InvalidParams(
+                BundlePoolError::TooManyTransactions(txs.len()).to_string(),
+            ))
+        }
+        if min_timestamp.zip(max_timestamp).is_some_and(|(min, max)| min > max) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::InvalidTimestampRange.to_string(),
+            ))
+        }
+
+        let (_, block_env, _) =
+            self.inner.eth_api.evm_env_at(BlockNumberOrTag::Latest.into()).await?;
+        let next_block = block_env.number.saturating_to::<u64>() + 1;
+        if block_number < next_block {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlockNumberTooLow { block_number, next_block }.to_string(),
+            ))
+        }
+        QueuedBundle::ensure_block_number(block_number, next_block)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        let simulation = self
+            .call_bundle(EthCallBundle {
+                txs: txs.clone(),
+                block_number,
+                state_block_number: BlockNumberOrTag::Latest,
+                timestamp: min_timestamp,
+            })
+            .await?;
+        if let Some(reverted) = simulation
+            .results
+            .iter()
+            .find(|res| res.revert.is_some() && !reverting_tx_hashes.contains(&res.tx_hash))
+        {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::TransactionReverted(reverted.tx_hash).to_string(),
+            ))
+        }
+
+        let transactions = txs
+            .into_iter()
+            .map(|tx| recover_raw_transaction(tx).map(|tx| tx.into_ecrecovered_transaction()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let bundle_hash = pool
+            .insert(
+                QueuedBundle {
+                    transactions,
+                    block_number,
+                    min_timestamp,
+                    max_timestamp,
+                    reverting_tx_hashes: reverting_tx_hashes.into_iter().collect(),
+                    replacement_uuid,
+                },
+                next_block,
+            )
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes a queued bundle by its hash.
+    pub fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        let bundle_hash = request
+            .bundle_hash
+            .parse::<B256>()
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        self.bundle_pool()?.remove(bundle_hash);
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -219,6 +339,42 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(Self::send_bundle(self, bundle).await?)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Ok(Self::call_bundle(self, request).await?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(Self::cancel_bundle(self, request)?)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        _request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("private transactions are not supported").into())
+    }
+
+    async fn send_private_raw_transaction(&self, _bytes: Bytes) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("private transactions are not supported").into())
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        _request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("private transactions are not supported").into())
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -227,6 +383,8 @@ struct EthBundleInner<Eth> {
     // restrict the number of concurrent tracing calls.
     #[allow(dead_code)]
     blocking_task_guard: BlockingTaskGuard,
+    /// The pool of the bundles submitted via `eth_sendBundle`, if enabled.
+    bundle_pool: Option<Arc<dyn BundlePool>>,
 }
 
 impl<Eth> std::fmt::Debug for EthBundle<Eth> {
@@ -254,4 +412,18 @@ pub enum EthBundleError {
     /// [`MAX_BLOB_GAS_PER_BLOCK`].
     #[error("blob gas usage exceeds the limit of {MAX_BLOB_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if the bundle targets a block that is already built.
+    #[error("bundle targets block {block_number}, but the next block is {next_block}")]
+    BlockNumberTooLow {
+        /// The block number of the bundle.
+        block_number: u64,
+        /// The number of the next block.
+        next_block: u64,
+    },
+    /// Thrown if the minimum timestamp of the bundle exceeds its maximum timestamp.
+    #[error("bundle minTimestamp exceeds maxTimestamp")]
+    InvalidTimestampRange,
+    /// Thrown if a transaction of the bundle reverts that isn't allowed to revert.
+    #[error("bundle transaction {0} reverted")]
+    TransactionReverted(B256),
 }
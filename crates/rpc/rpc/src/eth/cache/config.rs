@@ -1,5 +1,6 @@
 use reth_rpc_server_types::constants::cache::*;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Settings for the [`EthStateCache`](crate::eth::cache::EthStateCache).
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -33,3 +34,30 @@ impl Default for EthStateCacheConfig {
         }
     }
 }
+
+/// Settings for the [`RpcResponseCache`](crate::eth::cache::RpcResponseCache).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcResponseCacheConfig {
+    /// Max number of cached responses per endpoint.
+    ///
+    /// Default is 0, which disables the cache.
+    pub max_responses: u32,
+    /// How long a response is cached.
+    ///
+    /// Default is 1 hour.
+    pub ttl: Duration,
+}
+
+impl RpcResponseCacheConfig {
+    /// Returns `true` if responses are cached.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_responses > 0
+    }
+}
+
+impl Default for RpcResponseCacheConfig {
+    fn default() -> Self {
+        Self { max_responses: DEFAULT_RESPONSE_CACHE_MAX_LEN, ttl: DEFAULT_RESPONSE_CACHE_TTL }
+    }
+}
@@ -34,6 +34,9 @@ mod metrics;
 mod multi_consumer;
 pub use multi_consumer::MultiConsumerLruCache;
 
+mod response;
+pub use response::RpcResponseCache;
+
 /// The type that can send the response to a requested [Block]
 type BlockTransactionsResponseSender =
     oneshot::Sender<ProviderResult<Option<Vec<TransactionSigned>>>>;
@@ -0,0 +1,192 @@
+//! Cache for responses of expensive read endpoints.
+
+use super::{metrics::CacheMetrics, RpcResponseCacheConfig};
+use parking_lot::Mutex;
+use reth_primitives::{Address, B256};
+use reth_rpc_types::{trace::geth::GethTrace, AnyTransactionReceipt, EIP1186AccountProofResponse};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Key of a cached `debug_traceTransaction` response: the transaction hash and the serialized
+/// tracing options.
+type TraceKey = (B256, String);
+
+/// Key of a cached `eth_getProof` response: the block hash, the address and the storage keys.
+type ProofKey = (B256, Address, Vec<B256>);
+
+/// Caches the responses of expensive read endpoints, to absorb repeated queries for the same
+/// historical data, e.g. from indexers.
+///
+/// Traces and block receipts must only be inserted for finalized blocks, since they can't change
+/// anymore. Proofs are keyed by block hash, so they can be inserted for any block. Entries are
+/// evicted once the cache is full or after the configured time to live.
+///
+/// The cache is shared by all clones.
+#[derive(Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<RpcResponseCacheInner>,
+}
+
+struct RpcResponseCacheInner {
+    /// `debug_traceTransaction` responses
+    traces: Mutex<TtlLruCache<TraceKey, GethTrace>>,
+    /// `eth_getBlockReceipts` responses, keyed by block hash
+    block_receipts: Mutex<TtlLruCache<B256, Vec<AnyTransactionReceipt>>>,
+    /// `eth_getProof` responses
+    proofs: Mutex<TtlLruCache<ProofKey, EIP1186AccountProofResponse>>,
+}
+
+impl RpcResponseCache {
+    /// Creates a new cache with the given config.
+    ///
+    /// Returns `None` if the cache is disabled.
+    pub fn new(config: RpcResponseCacheConfig) -> Option<Self> {
+        if !config.is_enabled() {
+            return None
+        }
+        let RpcResponseCacheConfig { max_responses, ttl } = config;
+        let inner = RpcResponseCacheInner {
+            traces: Mutex::new(TtlLruCache::new(max_responses, ttl, "trace_transaction")),
+            block_receipts: Mutex::new(TtlLruCache::new(max_responses, ttl, "block_receipts")),
+            proofs: Mutex::new(TtlLruCache::new(max_responses, ttl, "proofs")),
+        };
+        Some(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns the cached trace of the transaction for the given serialized tracing options.
+    pub fn get_trace(&self, tx_hash: B256, opts: &str) -> Option<GethTrace> {
+        self.inner.traces.lock().get(&(tx_hash, opts.to_string()))
+    }
+
+    /// Caches the trace of the transaction for the given serialized tracing options.
+    pub fn insert_trace(&self, tx_hash: B256, opts: String, trace: GethTrace) {
+        self.inner.traces.lock().insert((tx_hash, opts), trace)
+    }
+
+    /// Returns the cached receipts of the block.
+    pub fn get_block_receipts(&self, block_hash: B256) -> Option<Vec<AnyTransactionReceipt>> {
+        self.inner.block_receipts.lock().get(&block_hash)
+    }
+
+    /// Caches the receipts of the block.
+    pub fn insert_block_receipts(&self, block_hash: B256, receipts: Vec<AnyTransactionReceipt>) {
+        self.inner.block_receipts.lock().insert(block_hash, receipts)
+    }
+
+    /// Returns the cached proof of the account and storage keys at the block.
+    pub fn get_proof(
+        &self,
+        block_hash: B256,
+        address: Address,
+        keys: &[B256],
+    ) -> Option<EIP1186AccountProofResponse> {
+        self.inner.proofs.lock().get(&(block_hash, address, keys.to_vec()))
+    }
+
+    /// Caches the proof of the account and storage keys at the block.
+    pub fn insert_proof(
+        &self,
+        block_hash: B256,
+        address: Address,
+        keys: Vec<B256>,
+        proof: EIP1186AccountProofResponse,
+    ) {
+        self.inner.proofs.lock().insert((block_hash, address, keys), proof)
+    }
+}
+
+impl Debug for RpcResponseCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcResponseCache")
+            .field("traces", &self.inner.traces.lock().len())
+            .field("block_receipts", &self.inner.block_receipts.lock().len())
+            .field("proofs", &self.inner.proofs.lock().len())
+            .finish()
+    }
+}
+
+/// An LRU cache whose entries expire after a time to live.
+struct TtlLruCache<K: Hash + Eq, V> {
+    /// The cached values and the time they were inserted.
+    cache: LruMap<K, (Instant, V), ByLength>,
+    /// How long an entry is served after it was inserted.
+    ttl: Duration,
+    /// Cache metrics
+    metrics: CacheMetrics,
+}
+
+impl<K: Hash + Eq, V: Clone> TtlLruCache<K, V> {
+    /// Creates a new empty cache with a given `max_len`, time to live and metric label.
+    fn new(max_len: u32, ttl: Duration, cache_id: &str) -> Self {
+        Self {
+            cache: LruMap::new(ByLength::new(max_len)),
+            ttl,
+            metrics: CacheMetrics::new_with_labels(&[("cache", cache_id.to_string())]),
+        }
+    }
+
+    /// Returns the number of cached entries, including expired ones.
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns the value for the given key if it hasn't expired yet, and promotes it to be the
+    /// most recently used.
+    fn get(&mut self, key: &K) -> Option<V> {
+        if self.cache.peek(key).is_some_and(|(inserted_at, _)| inserted_at.elapsed() >= self.ttl) {
+            self.cache.remove(key);
+            self.metrics.cached_count.set(self.cache.len() as f64);
+        }
+
+        let value = self.cache.get(key).map(|(_, value)| value.clone());
+        if value.is_some() {
+            self.metrics.hits_total.increment(1);
+        } else {
+            self.metrics.misses_total.increment(1);
+        }
+        value
+    }
+
+    /// Inserts a new value, evicting the least recently used one if the cache is full.
+    fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, (Instant::now(), value));
+        self.metrics.cached_count.set(self.cache.len() as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_without_responses() {
+        let config = RpcResponseCacheConfig { max_responses: 0, ..Default::default() };
+        assert!(RpcResponseCache::new(config).is_none());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = TtlLruCache::new(2, Duration::from_secs(60), "test");
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some("a"));
+
+        cache.insert(3, "c");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("a"));
+        assert_eq!(cache.get(&3), Some("c"));
+    }
+
+    #[test]
+    fn expires_after_ttl() {
+        let mut cache = TtlLruCache::new(2, Duration::ZERO, "test");
+        cache.insert(1, "a");
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.len(), 0);
+    }
+}
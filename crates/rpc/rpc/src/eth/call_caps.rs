@@ -0,0 +1,301 @@
+//! Server side caps on the cost of `eth_call` and `eth_estimateGas` requests.
+
+use revm::{
+    interpreter::{InstructionResult, Interpreter},
+    Database, EvmContext, Inspector,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::time::{Duration, Instant};
+
+/// The JSON-RPC error code of a [`CallCapExceeded`] error, the "limit exceeded" code of EIP-1474.
+pub const CALL_CAP_EXCEEDED_CODE: i32 = -32005;
+
+/// The number of executed instructions between two checks of the execution time cap.
+const EXECUTION_TIME_CHECK_INTERVAL: u64 = 1024;
+
+/// The caps of `eth_call` and `eth_estimateGas`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallCapsConfig {
+    /// The caps of `eth_call`.
+    pub call: CallCaps,
+    /// The caps of `eth_estimateGas`, which apply to the whole estimation instead of the
+    /// individual executions.
+    pub estimate_gas: CallCaps,
+}
+
+/// Caps on the cost of simulating a call, unbounded by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallCaps {
+    /// The maximum gas limit of the call.
+    ///
+    /// Requests with a higher gas limit are rejected, and the gas limit of requests without one
+    /// is lowered to the cap.
+    pub gas_limit: Option<u64>,
+    /// The maximum depth of nested calls and creations, including the top-level call.
+    pub max_call_depth: Option<u64>,
+    /// The maximum duration of the execution.
+    pub max_execution_time: Option<Duration>,
+}
+
+impl CallCaps {
+    /// Configures the maximum gas limit of the call
+    pub const fn with_gas_limit(mut self, gas_limit: Option<u64>) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Configures the maximum depth of nested calls
+    pub const fn with_max_call_depth(mut self, max_call_depth: Option<u64>) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Configures the maximum duration of the execution
+    pub const fn with_max_execution_time(mut self, max_execution_time: Option<Duration>) -> Self {
+        self.max_execution_time = max_execution_time;
+        self
+    }
+
+    /// Returns `true` if no caps are set.
+    pub const fn is_unbounded(&self) -> bool {
+        self.gas_limit.is_none() &&
+            self.max_call_depth.is_none() &&
+            self.max_execution_time.is_none()
+    }
+
+    /// Applies the gas limit cap to the gas limit of a request.
+    ///
+    /// Returns an error if the request specified a gas limit above the cap, and the cap if the
+    /// gas limit was lowered to it.
+    pub(crate) fn apply_gas_limit(
+        &self,
+        requested: Option<u64>,
+        gas_limit: &mut u64,
+    ) -> Result<Option<u64>, CallCapExceeded> {
+        let Some(cap) = self.gas_limit else { return Ok(None) };
+        if requested.is_some_and(|requested| requested > cap) {
+            return Err(CallCapExceeded::GasLimit(cap))
+        }
+        if *gas_limit > cap {
+            *gas_limit = cap;
+            return Ok(Some(cap))
+        }
+        Ok(None)
+    }
+
+    /// Returns an inspector that aborts the execution once it exceeds the call depth or execution
+    /// time caps.
+    ///
+    /// The execution time is measured from the creation of the inspector.
+    pub(crate) fn inspector(&self) -> CallCapsInspector {
+        CallCapsInspector {
+            max_call_depth: self.max_call_depth,
+            max_execution_time: self.max_execution_time,
+            deadline: self.max_execution_time.map(|max| Instant::now() + max),
+            steps: 0,
+            exceeded: None,
+        }
+    }
+}
+
+/// Error thrown when a call exceeds a [`CallCaps`] cap.
+///
+/// The error data is an object with the name of the `cap` and its `limit`, in milliseconds for
+/// the execution time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error, Serialize)]
+#[serde(tag = "cap", content = "limit", rename_all = "camelCase")]
+pub enum CallCapExceeded {
+    /// The call requires more gas than the gas limit cap.
+    #[error("call exceeds the gas limit cap of {0}")]
+    GasLimit(u64),
+    /// The call nests deeper than the call depth cap.
+    #[error("call exceeds the call depth cap of {0}")]
+    CallDepth(u64),
+    /// The call runs longer than the execution time cap.
+    #[error("call exceeds the execution time cap of {0:?}")]
+    ExecutionTime(#[serde(serialize_with = "serialize_millis")] Duration),
+}
+
+/// Serializes the duration as milliseconds.
+fn serialize_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis().try_into().unwrap_or(u64::MAX))
+}
+
+/// An inspector that aborts the execution once it exceeds the call depth or execution time caps.
+///
+/// Once a cap is exceeded, every frame halts on its next instruction.
+#[derive(Debug)]
+pub(crate) struct CallCapsInspector {
+    max_call_depth: Option<u64>,
+    max_execution_time: Option<Duration>,
+    deadline: Option<Instant>,
+    /// The number of executed instructions
+    steps: u64,
+    /// The cap that was exceeded, if any
+    exceeded: Option<CallCapExceeded>,
+}
+
+impl CallCapsInspector {
+    /// Returns `true` if no call depth or execution time caps are set, so inspecting is
+    /// unnecessary.
+    pub(crate) const fn is_unbounded(&self) -> bool {
+        self.max_call_depth.is_none() && self.max_execution_time.is_none()
+    }
+
+    /// Returns an error if an execution exceeded a cap.
+    pub(crate) fn ensure_within_caps(&self) -> Result<(), CallCapExceeded> {
+        self.exceeded.map_or(Ok(()), Err)
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallCapsInspector {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        if self.exceeded.is_none() {
+            if let Some(max) = self.max_call_depth {
+                if context.journaled_state.depth() > max {
+                    self.exceeded = Some(CallCapExceeded::CallDepth(max));
+                }
+            }
+            if let (Some(max), Some(deadline)) = (self.max_execution_time, self.deadline) {
+                self.steps += 1;
+                if self.steps % EXECUTION_TIME_CHECK_INTERVAL == 0 && Instant::now() >= deadline {
+                    self.exceeded = Some(CallCapExceeded::ExecutionTime(max));
+                }
+            }
+        }
+
+        match self.exceeded {
+            Some(CallCapExceeded::CallDepth(_)) => {
+                interp.instruction_result = InstructionResult::CallTooDeep
+            }
+            Some(_) => interp.instruction_result = InstructionResult::OutOfGas,
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        db::{CacheDB, EmptyDB},
+        inspector_handle_register,
+        primitives::{
+            address, bytes, AccountInfo, Address, Bytecode, Bytes, ExecutionResult, HaltReason,
+            OutOfGasError, TransactTo,
+        },
+        Evm,
+    };
+
+    const CONTRACT: Address = address!("00000000000000000000000000000000000000aa");
+
+    /// Calls the contract with the given code and gas limit under the inspector of the caps.
+    fn execute(code: Bytes, gas_limit: u64, inspector: &mut CallCapsInspector) -> ExecutionResult {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(
+            CONTRACT,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(inspector)
+            .modify_tx_env(|tx| {
+                tx.transact_to = TransactTo::Call(CONTRACT);
+                tx.gas_limit = gas_limit;
+            })
+            .append_handler_register(inspector_handle_register)
+            .build();
+        evm.transact().unwrap().result
+    }
+
+    /// `JUMPDEST PUSH1 0 JUMP`, an infinite loop
+    fn infinite_loop() -> Bytes {
+        bytes!("5b600056")
+    }
+
+    /// Calls itself with all remaining gas, recursing until the call stack limit
+    fn recursive_call() -> Bytes {
+        bytes!("60006000600060006000305af100")
+    }
+
+    #[test]
+    fn gas_limit_cap_halts_execution() {
+        let caps = CallCaps::default().with_gas_limit(Some(100_000));
+        let mut gas_limit = 30_000_000;
+        assert_eq!(caps.apply_gas_limit(None, &mut gas_limit), Ok(Some(100_000)));
+
+        let mut inspector = caps.inspector();
+        match execute(infinite_loop(), gas_limit, &mut inspector) {
+            ExecutionResult::Halt { reason: HaltReason::OutOfGas(_), gas_used } => {
+                assert_eq!(gas_used, 100_000)
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+        assert_eq!(inspector.ensure_within_caps(), Ok(()));
+    }
+
+    #[test]
+    fn call_depth_cap_halts_execution() {
+        let caps = CallCaps::default().with_max_call_depth(Some(4));
+        let mut inspector = caps.inspector();
+        assert!(!inspector.is_unbounded());
+
+        let res = execute(recursive_call(), 30_000_000, &mut inspector);
+        assert!(matches!(res, ExecutionResult::Halt { reason: HaltReason::CallTooDeep, .. }));
+        assert_eq!(inspector.ensure_within_caps(), Err(CallCapExceeded::CallDepth(4)));
+
+        // the same call stays within a deeper cap
+        let mut inspector = CallCaps::default().with_max_call_depth(Some(1025)).inspector();
+        let res = execute(recursive_call(), 30_000_000, &mut inspector);
+        assert!(res.is_success());
+        assert_eq!(inspector.ensure_within_caps(), Ok(()));
+    }
+
+    #[test]
+    fn execution_time_cap_halts_execution() {
+        let caps = CallCaps::default().with_max_execution_time(Some(Duration::ZERO));
+        let mut inspector = caps.inspector();
+
+        let res = execute(infinite_loop(), 30_000_000, &mut inspector);
+        assert!(matches!(
+            res,
+            ExecutionResult::Halt { reason: HaltReason::OutOfGas(OutOfGasError::Basic), .. }
+        ));
+        assert_eq!(
+            inspector.ensure_within_caps(),
+            Err(CallCapExceeded::ExecutionTime(Duration::ZERO))
+        );
+    }
+
+    #[test]
+    fn apply_gas_limit_cap() {
+        let caps = CallCaps::default();
+        let mut gas_limit = 30_000_000;
+        assert_eq!(caps.apply_gas_limit(Some(50_000_000), &mut gas_limit), Ok(None));
+        assert_eq!(gas_limit, 30_000_000);
+
+        let caps = caps.with_gas_limit(Some(1_000_000));
+        assert_eq!(caps.apply_gas_limit(None, &mut gas_limit), Ok(Some(1_000_000)));
+        assert_eq!(gas_limit, 1_000_000);
+
+        let mut gas_limit = 100_000;
+        assert_eq!(caps.apply_gas_limit(Some(100_000), &mut gas_limit), Ok(None));
+        assert_eq!(gas_limit, 100_000);
+        assert_eq!(
+            caps.apply_gas_limit(Some(1_000_001), &mut gas_limit),
+            Err(CallCapExceeded::GasLimit(1_000_000))
+        );
+    }
+
+    #[test]
+    fn serialize_call_cap_exceeded() {
+        assert_eq!(
+            serde_json::to_value(CallCapExceeded::GasLimit(1_000_000)).unwrap(),
+            serde_json::json!({"cap": "gasLimit", "limit": 1_000_000})
+        );
+        assert_eq!(
+            serde_json::to_value(CallCapExceeded::ExecutionTime(Duration::from_secs(5))).unwrap(),
+            serde_json::json!({"cap": "executionTime", "limit": 5000})
+        );
+    }
+}
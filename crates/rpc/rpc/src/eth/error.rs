@@ -1,10 +1,17 @@
 //! Implementation specific Errors for the `eth_` namespace.
 
-use crate::result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code};
+use crate::{
+    eth::{
+        call_caps::{CallCapExceeded, CALL_CAP_EXCEEDED_CODE},
+        revert::{DecodedRevert, RevertDecoder},
+        simulate::SimulateError,
+    },
+    result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code},
+};
 use alloy_sol_types::decode_revert_reason;
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject};
 use reth_errors::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, BlockNumber, Bytes, B256};
 use reth_rpc_types::{
     error::EthRpcErrorCode, request::TransactionInputError, BlockError, ToRpcError,
 };
@@ -14,6 +21,7 @@ use reth_transaction_pool::error::{
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use revm_inspectors::tracing::{js::JsInspectorError, MuxError};
+use serde::Serialize;
 use std::time::Duration;
 
 /// Result alias
@@ -52,6 +60,16 @@ pub enum EthApiError {
     /// When an invalid block range is provided
     #[error("invalid block range")]
     InvalidBlockRange,
+    /// Thrown when the target block of a proof is older than the configured proof window
+    #[error("distance to target block exceeds maximum proof window")]
+    ExceedsMaxProofWindow,
+    /// Thrown when the blob sidecars of a finalized block are requested, which are pruned from
+    /// the blob store
+    #[error("blob sidecars of finalized block {0} are pruned")]
+    BlobSidecarsPruned(BlockNumber),
+    /// Thrown when the blob sidecar of a transaction is not in the blob store
+    #[error("blob sidecar of transaction {0} is unavailable")]
+    BlobSidecarUnavailable(B256),
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("prevrandao not in the EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -104,6 +122,25 @@ pub enum EthApiError {
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
+    /// Error thrown when an `eth_call` or `eth_estimateGas` request exceeds a configured cap
+    #[error(transparent)]
+    CallCapExceeded(#[from] CallCapExceeded),
+    /// Error thrown when an `eth_simulateV1` request is invalid
+    #[error(transparent)]
+    Simulate(#[from] SimulateError),
+    /// Error thrown when a submitted transaction is not included within the timeout of
+    /// `eth_sendRawTransactionSync`
+    #[error("transaction {hash} was not included within {timeout:?}")]
+    TransactionInclusionTimedOut {
+        /// The hash of the submitted transaction.
+        hash: B256,
+        /// The duration that was waited for the transaction to be included.
+        timeout: Duration,
+    },
+    /// Error thrown when the client of a subscription that streams results doesn't read them as
+    /// fast as they are produced
+    #[error("subscription client did not keep up with the results")]
+    SubscriptionLagged,
     /// Internal Error thrown by the javascript tracer
     #[error("{0}")]
     InternalJsTracerError(String),
@@ -141,6 +178,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::InvalidTransactionSignature |
             EthApiError::EmptyRawTransactionData |
             EthApiError::InvalidBlockRange |
+            EthApiError::ExceedsMaxProofWindow |
             EthApiError::ConflictingFeeFieldsInRequest |
             EthApiError::Signing(_) |
             EthApiError::BothStateAndStateDiffInOverride(_) |
@@ -156,7 +194,10 @@ impl From<EthApiError> for ErrorObject<'static> {
             EthApiError::EvmCustom(_) |
             EthApiError::EvmPrecompile(_) |
             EthApiError::InvalidRewardPercentiles => internal_rpc_err(error.to_string()),
-            EthApiError::UnknownBlockNumber | EthApiError::UnknownBlockOrTxIndex => {
+            EthApiError::UnknownBlockNumber |
+            EthApiError::UnknownBlockOrTxIndex |
+            EthApiError::BlobSidecarsPruned(_) |
+            EthApiError::BlobSidecarUnavailable(_) => {
                 rpc_error_with_code(EthRpcErrorCode::ResourceNotFound.code(), error.to_string())
             }
             EthApiError::UnknownSafeOrFinalizedBlock => {
@@ -168,6 +209,12 @@ impl From<EthApiError> for ErrorObject<'static> {
             err @ EthApiError::ExecutionTimedOut(_) => {
                 rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
             }
+            EthApiError::CallCapExceeded(err) => {
+                ErrorObject::owned(CALL_CAP_EXCEEDED_CODE, err.to_string(), Some(err))
+            }
+            EthApiError::Simulate(err) => err.into(),
+            err @ EthApiError::TransactionInclusionTimedOut { .. } |
+            err @ EthApiError::SubscriptionLagged => internal_rpc_err(err.to_string()),
             err @ EthApiError::InternalBlockingTaskError | err @ EthApiError::InternalEthError => {
                 internal_rpc_err(err.to_string())
             }
@@ -409,6 +456,14 @@ impl From<RpcInvalidTransactionError> for ErrorObject<'static> {
     fn from(err: RpcInvalidTransactionError) -> Self {
         match err {
             RpcInvalidTransactionError::Revert(revert) => {
+                if let Some(decoded) = &revert.decoded {
+                    // include the decoded custom error next to the out data
+                    let data = DecodedRevertData {
+                        data: revert.output.clone().unwrap_or_default(),
+                        error: decoded,
+                    };
+                    return ErrorObject::owned(revert.error_code(), revert.to_string(), Some(data))
+                }
                 // include out data if some
                 rpc_err(
                     revert.error_code(),
@@ -490,13 +545,15 @@ impl From<reth_primitives::InvalidTransactionError> for RpcInvalidTransactionErr
 
 /// Represents a reverted transaction and its output data.
 ///
-/// Displays "execution reverted(: reason)?" if the reason is a string.
+/// Displays "execution reverted(: reason)?" if the reason is a string or a decoded custom error.
 #[derive(Debug, Clone)]
 pub struct RevertError {
     /// The transaction output data
     ///
     /// Note: this is `None` if output was empty
     output: Option<Bytes>,
+    /// The custom error decoded from the output, see [`RevertDecoder`]
+    decoded: Option<DecodedRevert>,
 }
 
 // === impl RevertError ==
@@ -507,12 +564,23 @@ impl RevertError {
     /// Note: this is intended to wrap an revm output
     pub fn new(output: Bytes) -> Self {
         if output.is_empty() {
-            Self { output: None }
+            Self { output: None, decoded: None }
         } else {
-            Self { output: Some(output) }
+            Self { output: Some(output), decoded: None }
         }
     }
 
+    /// Decodes the custom error of the output with the registered error definitions.
+    pub fn decode_with(mut self, decoder: &RevertDecoder) -> Self {
+        self.decoded = self.output.as_ref().and_then(|output| decoder.decode(output));
+        self
+    }
+
+    /// Returns the decoded custom error, if any.
+    pub const fn decoded(&self) -> Option<&DecodedRevert> {
+        self.decoded.as_ref()
+    }
+
     const fn error_code(&self) -> i32 {
         EthRpcErrorCode::ExecutionError.code()
     }
@@ -521,7 +589,11 @@ impl RevertError {
 impl std::fmt::Display for RevertError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("execution reverted")?;
-        if let Some(reason) = self.output.as_ref().and_then(|bytes| decode_revert_reason(bytes)) {
+        if let Some(decoded) = &self.decoded {
+            write!(f, ": {decoded}")?;
+        } else if let Some(reason) =
+            self.output.as_ref().and_then(|bytes| decode_revert_reason(bytes))
+        {
             write!(f, ": {reason}")?;
         }
         Ok(())
@@ -530,6 +602,15 @@ impl std::fmt::Display for RevertError {
 
 impl std::error::Error for RevertError {}
 
+/// The error data of a reverted call with a decoded custom error.
+#[derive(Serialize)]
+struct DecodedRevertData<'a> {
+    /// The raw output of the call
+    data: Bytes,
+    /// The decoded custom error
+    error: &'a DecodedRevert,
+}
+
 /// A helper error type that's mainly used to mirror `geth` Txpool's error messages
 #[derive(Debug, thiserror::Error)]
 pub enum RpcPoolError {
@@ -545,9 +626,9 @@ pub enum RpcPoolError {
     /// When the transaction pool is full
     #[error("txpool is full")]
     TxPoolOverflow,
-    /// When the replacement transaction is underpriced
-    #[error("replacement transaction underpriced")]
-    ReplaceUnderpriced,
+    /// When the replacement transaction is underpriced, with the price bump (in %) it must exceed
+    #[error("replacement transaction underpriced, price bump must exceed {0}%")]
+    ReplaceUnderpriced(u128),
     /// When the transaction exceeds the block gas limit
     #[error("exceeds block gas limit")]
     ExceedsGasLimit,
@@ -575,6 +656,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// When the sender or the recipient of the transaction isn't allowed by the node
+    #[error("address {0} is not allowed")]
+    AddressNotAllowed(Address),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn std::error::Error + Send + Sync>),
@@ -592,7 +676,9 @@ impl From<RpcPoolError> for ErrorObject<'static> {
 impl From<PoolError> for RpcPoolError {
     fn from(err: PoolError) -> Self {
         match err.kind {
-            PoolErrorKind::ReplacementUnderpriced => Self::ReplaceUnderpriced,
+            PoolErrorKind::ReplacementUnderpriced(price_bump) => {
+                Self::ReplaceUnderpriced(price_bump)
+            }
             PoolErrorKind::FeeCapBelowMinimumProtocolFeeCap(_) => Self::Underpriced,
             PoolErrorKind::SpammerExceededCapacity(_) | PoolErrorKind::DiscardedOnInsert => {
                 Self::TxPoolOverflow
@@ -623,6 +709,9 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Overdraft => {
                 Self::Invalid(RpcInvalidTransactionError::InsufficientFunds)
             }
+            InvalidPoolTransactionError::AddressNotAllowed(address) => {
+                Self::AddressNotAllowed(address)
+            }
         }
     }
 }
@@ -3,6 +3,7 @@ use crate::{
     eth::{
         error::EthApiError,
         logs_utils::{self, append_matching_block_logs},
+        send_result_chunk,
     },
     result::{rpc_error_with_code, ToRpcResult},
     EthSubscriptionIdProvider,
@@ -10,14 +11,18 @@ use crate::{
 use core::fmt;
 
 use async_trait::async_trait;
-use jsonrpsee::{core::RpcResult, server::IdProvider};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::IdProvider,
+    PendingSubscriptionSink,
+};
 use reth_chainspec::ChainInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
+use reth_primitives::{BlockNumberOrTag, IntoRecoveredTransaction, TxHash};
 use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
     BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    PendingTransactionFilterKind, ResultChunk,
 };
 
 use reth_tasks::TaskSpawner;
@@ -302,6 +307,51 @@ where
         trace!(target: "rpc::eth", "Serving eth_getLogs");
         Ok(self.inner.logs_for_filter(filter).await?)
     }
+
+    /// Sends the logs matching given filter object in chunks of the logs of at most
+    /// `max_headers_range` blocks.
+    ///
+    /// The range is limited like the range of `eth_getLogs`, but the maximum number of logs only
+    /// applies to each chunk.
+    ///
+    /// Handler for `eth_subscribeGetLogs`
+    async fn subscribe_logs(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: Filter,
+    ) -> SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving eth_subscribeGetLogs");
+        let sink = pending.accept().await?;
+
+        match filter.block_option {
+            FilterBlockOption::AtBlockHash(_) => {
+                let logs = self.inner.logs_for_filter(filter).await?;
+                if !send_result_chunk(&sink, &ResultChunk::new(logs)).await? {
+                    return Ok(())
+                }
+            }
+            FilterBlockOption::Range { from_block, to_block } => {
+                let (from_block, to_block, info) =
+                    self.inner.filter_block_range(from_block, to_block)?;
+                self.inner.ensure_valid_block_range(from_block, to_block)?;
+
+                for (from, to) in BlockRangeInclusiveIter::new(
+                    from_block..=to_block,
+                    self.inner.max_headers_range,
+                ) {
+                    let logs = self.inner.get_logs_in_block_range(&filter, from, to, info).await?;
+                    if !logs.is_empty() &&
+                        !send_result_chunk(&sink, &ResultChunk::new(logs)).await?
+                    {
+                        return Ok(())
+                    }
+                }
+            }
+        }
+
+        send_result_chunk(&sink, &ResultChunk::<Log>::done()).await?;
+        Ok(())
+    }
 }
 
 impl<Provider, Pool> std::fmt::Debug for EthFilter<Provider, Pool> {
@@ -380,27 +430,47 @@ where
                 Ok(all_logs)
             }
             FilterBlockOption::Range { from_block, to_block } => {
-                // compute the range
-                let info = self.provider.chain_info()?;
-
-                // we start at the most recent block if unset in filter
-                let start_block = info.best_number;
-                let from = from_block
-                    .map(|num| self.provider.convert_block_number(num))
-                    .transpose()?
-                    .flatten();
-                let to = to_block
-                    .map(|num| self.provider.convert_block_number(num))
-                    .transpose()?
-                    .flatten();
-                let (from_block_number, to_block_number) =
-                    logs_utils::get_filter_block_range(from, to, start_block, info);
+                let (from_block_number, to_block_number, info) =
+                    self.filter_block_range(from_block, to_block)?;
                 self.get_logs_in_block_range(&filter, from_block_number, to_block_number, info)
                     .await
             }
         }
     }
 
+    /// Returns the _inclusive_ block range of a range filter, and the chain info it's relative to.
+    fn filter_block_range(
+        &self,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> Result<(u64, u64, ChainInfo), FilterError> {
+        // compute the range
+        let info = self.provider.chain_info()?;
+
+        // we start at the most recent block if unset in filter
+        let start_block = info.best_number;
+        let from =
+            from_block.map(|num| self.provider.convert_block_number(num)).transpose()?.flatten();
+        let to = to_block.map(|num| self.provider.convert_block_number(num)).transpose()?.flatten();
+        let (from_block_number, to_block_number) =
+            logs_utils::get_filter_block_range(from, to, start_block, info);
+        Ok((from_block_number, to_block_number, info))
+    }
+
+    /// Returns an error if the _inclusive_ range is empty or exceeds the configured maximum number
+    /// of blocks per filter.
+    fn ensure_valid_block_range(&self, from_block: u64, to_block: u64) -> Result<(), FilterError> {
+        if to_block < from_block {
+            return Err(FilterError::InvalidBlockRangeParams)
+        }
+
+        if to_block - from_block > self.max_blocks_per_filter {
+            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+        }
+
+        Ok(())
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
@@ -432,13 +502,7 @@ where
         trace!(target: "rpc::eth::filter", from=from_block, to=to_block, ?filter, "finding logs in range");
         let best_number = chain_info.best_number;
 
-        if to_block < from_block {
-            return Err(FilterError::InvalidBlockRangeParams)
-        }
-
-        if to_block - from_block > self.max_blocks_per_filter {
-            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
-        }
+        self.ensure_valid_block_range(from_block, to_block)?;
 
         let mut all_logs = Vec::new();
         let filter_params = FilteredParams::new(Some(filter.clone()));
@@ -464,6 +528,35 @@ where
             return Ok(all_logs)
         }
 
+        let is_multi_block_range = from_block != to_block;
+
+        // if the optional log index covers the range, only check the blocks that it returns
+        let addresses = filter.address.iter().copied().collect::<Vec<_>>();
+        let topics = filter
+            .topics
+            .iter()
+            .map(|topics| topics.iter().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        if let Some(block_numbers) =
+            self.provider.blocks_with_matching_logs(from_block..=to_block, &addresses, &topics)?
+        {
+            for block_number in block_numbers {
+                let header = self
+                    .provider
+                    .sealed_header(block_number)?
+                    .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+                self.append_block_logs(
+                    &mut all_logs,
+                    &filter_params,
+                    BlockNumHash::new(block_number, header.hash()),
+                    header.timestamp,
+                    is_multi_block_range,
+                )
+                .await?;
+            }
+            return Ok(all_logs)
+        }
+
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
@@ -490,32 +583,52 @@ where
                             .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        BlockNumHash::new(header.number, block_hash),
+                        header.timestamp,
+                        is_multi_block_range,
+                    )
+                    .await?;
                 }
             }
         }
 
         Ok(all_logs)
     }
+
+    /// Appends the logs of the given block that match the filter.
+    ///
+    /// Returns an error if the amount of matches exceeds the configured limit, unless only a single
+    /// block is queried.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block_num_hash: BlockNumHash,
+        timestamp: u64,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
+        if let Some(receipts) = self.eth_cache.get_receipts(block_num_hash.hash).await? {
+            append_matching_block_logs(
+                all_logs,
+                &self.provider,
+                filter_params,
+                block_num_hash,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+
+            // size check but only if range is multiple blocks, so we always return all logs of a
+            // single block
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Config for the filter
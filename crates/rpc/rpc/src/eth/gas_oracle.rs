@@ -11,7 +11,10 @@ use reth_provider::BlockReaderIdExt;
 use reth_rpc_server_types::constants::gas_oracle::*;
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+};
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -39,6 +42,17 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The minimum priority fee to suggest on OP Stack chains
+    pub min_suggested_priority_fee: Option<U256>,
+
+    /// How the estimate is derived from the sampled tips of recent blocks
+    #[serde(default)]
+    pub strategy: GasPriceOracleStrategy,
+
+    /// The minimum gas price to suggest, this is also used as the sample of blocks without
+    /// transactions
+    pub min_price: Option<U256>,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -51,10 +65,62 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            min_suggested_priority_fee: Some(DEFAULT_MIN_SUGGESTED_PRIORITY_FEE),
+            strategy: GasPriceOracleStrategy::default(),
+            min_price: None,
+        }
+    }
+}
+
+/// The algorithm the [`GasPriceOracle`] uses to derive a gas price estimate from the sampled tips
+/// of recent blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GasPriceOracleStrategy {
+    /// Uses the configured percentile of the tips sampled from all recent blocks.
+    #[default]
+    Percentile,
+    /// Uses the exponential moving average of the configured percentile of the tips of each
+    /// recent block, weighting recent blocks more.
+    ///
+    /// Blocks without transactions pull the average down, so the estimate follows the fees that
+    /// are actually paid on chains with little traffic.
+    Ema,
+}
+
+impl GasPriceOracleStrategy {
+    /// Returns the name of the strategy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Percentile => "percentile",
+            Self::Ema => "ema",
         }
     }
 }
 
+impl FromStr for GasPriceOracleStrategy {
+    type Err = UnknownGasPriceOracleStrategy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "percentile" => Self::Percentile,
+            "ema" => Self::Ema,
+            _ => return Err(UnknownGasPriceOracleStrategy(s.to_string())),
+        })
+    }
+}
+
+impl fmt::Display for GasPriceOracleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown [`GasPriceOracleStrategy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown gas price oracle strategy {0:?}, expected `percentile` or `ema`")]
+pub struct UnknownGasPriceOracleStrategy(String);
+
 /// Calculates a gas price depending on recent blocks.
 #[derive(Debug)]
 pub struct GasPriceOracle<Provider> {
@@ -105,7 +171,8 @@ where
         &self.oracle_config
     }
 
-    /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    /// Suggests a gas price estimate based on recent blocks, using the configured
+    /// [`GasPriceOracleStrategy`].
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
             .provider
@@ -125,9 +192,13 @@ where
         // we only return more than check_block blocks' worth of prices if one or more return empty
         // transactions
         let mut current_hash = header.hash();
-        let mut results = Vec::new();
+        // the sampled tips of each block, starting with the latest block
+        let mut samples = Vec::new();
         let mut populated_blocks = 0;
 
+        // blocks without transactions are sampled at the minimum price if there is one
+        let empty_block_price = self.oracle_config.min_price.unwrap_or(inner.last_price.price);
+
         // we only check a maximum of 2 * max_block_history, or the number of blocks in the chain
         let max_blocks = if self.oracle_config.max_block_history * 2 > header.number {
             header.number
@@ -153,9 +224,9 @@ where
                 };
 
             if block_values.is_empty() {
-                results.push(U256::from(inner.last_price.price));
+                samples.push(vec![empty_block_price]);
             } else {
-                results.extend(block_values);
+                samples.push(block_values);
                 populated_blocks += 1;
             }
 
@@ -167,14 +238,71 @@ where
             current_hash = parent_hash;
         }
 
-        // sort results then take the configured percentile result
-        let mut price = if !results.is_empty() {
-            results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
-                "gas price index is a percent of nonzero array length, so a value always exists",
-            )
+        let percentile = self.oracle_config.percentile;
+        let price = match self.oracle_config.strategy {
+            GasPriceOracleStrategy::Percentile => {
+                let mut results = samples.into_iter().flatten().collect::<Vec<_>>();
+                percentile_of(&mut results, percentile)
+            }
+            GasPriceOracleStrategy::Ema => exponential_moving_average(
+                samples.iter_mut().rev().filter_map(|values| percentile_of(values, percentile)),
+                self.oracle_config.blocks,
+            ),
+        };
+        let mut price = price.unwrap_or(inner.last_price.price);
+
+        // constrain to the min price
+        if let Some(min_price) = self.oracle_config.min_price {
+            if price < min_price {
+                price = min_price;
+            }
+        }
+
+        // constrain to the max price
+        if let Some(max_price) = self.oracle_config.max_price {
+            if price > max_price {
+                price = max_price;
+            }
+        }
+
+        inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price };
+
+        Ok(price)
+    }
+
+    /// Suggests a priority fee on OP Stack chains.
+    ///
+    /// Blocks of OP Stack chains are rarely full, so the tips of recent transactions say little
+    /// about what's needed for inclusion, and the sequencer orders transactions by tip. If the
+    /// latest block isn't congested, the configured minimum is suggested. Otherwise the median
+    /// tip of the latest block is increased by 10%, so that a transaction outbids half of the
+    /// transactions that competed for the latest block.
+    ///
+    /// The L1 data fee is charged separately from the gas price, see the `l1Fee` field of
+    /// receipts.
+    pub async fn op_suggest_tip_cap(&self) -> EthResult<U256> {
+        let header = self
+            .provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut inner = self.inner.lock().await;
+        if inner.last_price.block_hash == header.hash() {
+            return Ok(inner.last_price.price)
+        }
+
+        let min_price = self
+            .oracle_config
+            .min_suggested_priority_fee
+            .unwrap_or(DEFAULT_MIN_SUGGESTED_PRIORITY_FEE);
+        let mut price = if is_op_congested(header.gas_used, header.gas_limit) {
+            let (_, tips) = self
+                .get_block_values(header.hash(), usize::MAX)
+                .await?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            op_congested_tip_cap(tips, min_price)
         } else {
-            inner.last_price.price
+            min_price
         };
 
         // constrain to the max price
@@ -251,6 +379,38 @@ where
     }
 }
 
+/// Returns whether a block of an OP Stack chain with the given gas usage is congested.
+const fn is_op_congested(gas_used: u64, gas_limit: u64) -> bool {
+    gas_used as u128 * 100 >= gas_limit as u128 * OP_CONGESTED_GAS_USED_PERCENT as u128
+}
+
+/// Returns the median of the given tips of a congested block increased by 10%, but at least the
+/// given minimum.
+fn op_congested_tip_cap(mut tips: Vec<U256>, min_price: U256) -> U256 {
+    tips.sort_unstable();
+    let median = tips.get(tips.len() / 2).copied().unwrap_or_default();
+    (median + median / U256::from(10)).max(min_price)
+}
+
+/// Sorts the values and returns the value at the given percentile, or `None` if there are no
+/// values.
+fn percentile_of(values: &mut [U256], percentile: u32) -> Option<U256> {
+    if values.is_empty() {
+        return None
+    }
+    values.sort_unstable();
+    Some(values[(values.len() - 1) * percentile as usize / 100])
+}
+
+/// Returns the exponential moving average of the values, ordered from oldest to newest, with a
+/// smoothing factor of `2 / (period + 1)`, or `None` if there are no values.
+fn exponential_moving_average(values: impl IntoIterator<Item = U256>, period: u32) -> Option<U256> {
+    let weight = U256::from(period.max(1) + 1);
+    values.into_iter().reduce(|average, value| {
+        (average * (weight - U256::from(2)) + value * U256::from(2)) / weight
+    })
+}
+
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
@@ -300,4 +460,63 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn percentile_of_values() {
+        let mut values = [5u64, 1, 4, 2, 3].map(U256::from);
+        assert_eq!(percentile_of(&mut values, 0), Some(U256::from(1)));
+        assert_eq!(percentile_of(&mut values, 60), Some(U256::from(3)));
+        assert_eq!(percentile_of(&mut values, 100), Some(U256::from(5)));
+        assert_eq!(percentile_of(&mut [], 60), None);
+    }
+
+    #[test]
+    fn exponential_moving_average_of_values() {
+        let values = [100u64, 100, 10, 10].map(U256::from);
+        // alpha = 2 / (3 + 1): 100 -> 100 -> 55 -> 32
+        assert_eq!(exponential_moving_average(values, 3), Some(U256::from(32)));
+        assert_eq!(exponential_moving_average([U256::from(7)], 3), Some(U256::from(7)));
+        assert_eq!(exponential_moving_average([], 3), None);
+    }
+
+    #[test]
+    fn op_congestion() {
+        // empty and lightly used blocks aren't congested
+        assert!(!is_op_congested(0, 30_000_000));
+        assert!(!is_op_congested(26_999_999, 30_000_000));
+        assert!(is_op_congested(27_000_000, 30_000_000));
+        assert!(is_op_congested(u64::MAX, u64::MAX));
+    }
+
+    #[test]
+    fn op_congested_tip_cap_of_tips() {
+        let min_price = U256::from(1_000_000);
+
+        // the median is increased by 10%
+        let tips = [3_000_000u64, 1_000_000, 2_000_000].map(U256::from).to_vec();
+        assert_eq!(op_congested_tip_cap(tips, min_price), U256::from(2_200_000));
+
+        // but never below the floor
+        let tips = [10u64, 20, 30].map(U256::from).to_vec();
+        assert_eq!(op_congested_tip_cap(tips, min_price), min_price);
+
+        // a congested block without tips, e.g. only deposits, suggests the floor
+        assert_eq!(op_congested_tip_cap(Vec::new(), min_price), min_price);
+    }
+
+    #[test]
+    fn parse_strategy() {
+        for strategy in [GasPriceOracleStrategy::Percentile, GasPriceOracleStrategy::Ema] {
+            assert_eq!(strategy.to_string().parse::<GasPriceOracleStrategy>(), Ok(strategy));
+        }
+        assert!("median".parse::<GasPriceOracleStrategy>().is_err());
+    }
+
+    #[test]
+    fn deserialize_config_without_strategy() {
+        let mut value = serde_json::to_value(GasPriceOracleConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("strategy");
+        let config: GasPriceOracleConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.strategy, GasPriceOracleStrategy::Percentile);
+    }
 }
@@ -1,9 +1,10 @@
 use super::filter::FilterError;
 use alloy_primitives::TxHash;
 use reth_chainspec::ChainInfo;
-use reth_primitives::{BlockNumHash, Receipt};
+use reth_primitives::{BlockNumHash, BlockNumber, Receipt};
 use reth_provider::{BlockReader, ProviderError};
 use reth_rpc_types::{FilteredParams, Log};
+use std::collections::VecDeque;
 
 /// Returns all matching of a block's receipts when the transaction hashes are known.
 pub(crate) fn matching_block_logs_with_tx_hashes<'a, I>(
@@ -126,6 +127,78 @@ pub(crate) fn log_matches_filter(
     true
 }
 
+/// The logs a `logs` subscription emitted for its most recent canonical blocks.
+///
+/// This is used to notify the subscriber about the removal of exactly the logs it received once
+/// their blocks are reorged out.
+#[derive(Debug)]
+pub(crate) struct EmittedLogs {
+    /// The emitted logs by block, ordered by block number
+    blocks: VecDeque<(BlockNumHash, Vec<Log>)>,
+    /// The maximum number of buffered blocks
+    max_blocks: usize,
+}
+
+impl EmittedLogs {
+    /// Creates an empty buffer for the logs of at most `max_blocks` blocks.
+    pub(crate) fn new(max_blocks: u64) -> Self {
+        let max_blocks = max_blocks.try_into().unwrap_or(usize::MAX);
+        Self { blocks: VecDeque::new(), max_blocks }
+    }
+
+    /// Returns the buffered blocks, ordered by block number.
+    pub(crate) fn blocks(&self) -> impl Iterator<Item = BlockNumHash> + '_ {
+        self.blocks.iter().map(|(block, _)| *block)
+    }
+
+    /// Returns `true` if the logs of the block are buffered.
+    pub(crate) fn contains(&self, block: BlockNumHash) -> bool {
+        self.blocks().any(|buffered| buffered == block)
+    }
+
+    /// Returns the number of the oldest buffered block.
+    pub(crate) fn oldest_block_number(&self) -> Option<BlockNumber> {
+        self.blocks.front().map(|(block, _)| block.number)
+    }
+
+    /// Returns the most recent buffered block.
+    pub(crate) fn last_block(&self) -> Option<BlockNumHash> {
+        self.blocks.back().map(|(block, _)| *block)
+    }
+
+    /// Records the logs emitted for a new canonical block, and drops the oldest blocks that exceed
+    /// the buffer size.
+    ///
+    /// Blocks without matching logs are recorded as well, so they can be checked for reorgs.
+    pub(crate) fn push(&mut self, block: BlockNumHash, logs: Vec<Log>) {
+        if self.max_blocks == 0 {
+            return
+        }
+        self.blocks.push_back((block, logs));
+        while self.blocks.len() > self.max_blocks {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// Removes the buffered blocks that are reverted and returns their logs marked as removed, in
+    /// the reverse order of their emission.
+    pub(crate) fn remove(&mut self, mut is_reverted: impl FnMut(BlockNumHash) -> bool) -> Vec<Log> {
+        let (reverted, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.blocks)
+            .into_iter()
+            .partition(|(block, _)| is_reverted(*block));
+        self.blocks = kept.into();
+        reverted
+            .into_iter()
+            .rev()
+            .flat_map(|(_, logs)| logs.into_iter().rev())
+            .map(|mut log| {
+                log.removed = true;
+                log
+            })
+            .collect()
+    }
+}
+
 /// Computes the block range based on the filter range and current block numbers
 pub(crate) fn get_filter_block_range(
     from_block: Option<u64>,
@@ -154,10 +227,47 @@ pub(crate) fn get_filter_block_range(
 
 #[cfg(test)]
 mod tests {
+    use reth_primitives::B256;
     use reth_rpc_types::Filter;
 
     use super::*;
 
+    fn log_at(block: BlockNumHash, log_index: u64) -> Log {
+        Log {
+            block_hash: Some(block.hash),
+            block_number: Some(block.number),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_emitted_logs_removal() {
+        let blocks =
+            (1..=4).map(|number| BlockNumHash::new(number, B256::random())).collect::<Vec<_>>();
+        let mut emitted = EmittedLogs::new(3);
+        for block in &blocks {
+            emitted.push(*block, vec![log_at(*block, 0), log_at(*block, 1)]);
+        }
+        assert_eq!(emitted.oldest_block_number(), Some(2));
+        assert_eq!(emitted.last_block(), Some(blocks[3]));
+        assert!(!emitted.contains(blocks[0]));
+
+        let removed = emitted.remove(|block| block.number >= 3);
+        let removed =
+            removed.iter().map(|log| (log.block_number, log.log_index)).collect::<Vec<_>>();
+        assert_eq!(
+            removed,
+            vec![(Some(4), Some(1)), (Some(4), Some(0)), (Some(3), Some(1)), (Some(3), Some(0))]
+        );
+        assert_eq!(emitted.blocks().collect::<Vec<_>>(), vec![blocks[1]]);
+        assert!(emitted.remove(|block| block.number >= 3).is_empty());
+
+        let mut disabled = EmittedLogs::new(0);
+        disabled.push(blocks[0], vec![log_at(blocks[0], 0)]);
+        assert_eq!(disabled.last_block(), None);
+    }
+
     #[test]
     fn test_log_range_from_and_to() {
         let from = 14000000u64;
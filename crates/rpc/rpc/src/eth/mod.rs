@@ -3,14 +3,17 @@
 mod api;
 pub mod bundle;
 pub mod cache;
+pub mod call_caps;
 pub mod error;
 mod filter;
 pub mod gas_oracle;
 mod id_provider;
 mod logs_utils;
 mod pubsub;
+pub mod revert;
 pub mod revm_utils;
 mod signer;
+pub mod simulate;
 pub mod traits;
 pub(crate) mod utils;
 
@@ -23,6 +26,8 @@ pub use api::{
 };
 
 pub use bundle::EthBundle;
+pub use call_caps::{CallCaps, CallCapsConfig};
 pub use filter::{EthFilter, EthFilterConfig};
 pub use id_provider::EthSubscriptionIdProvider;
+pub(crate) use pubsub::send_result_chunk;
 pub use pubsub::EthPubSub;
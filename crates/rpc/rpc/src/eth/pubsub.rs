@@ -1,32 +1,38 @@
 //! `eth_` `PubSub` RPC handler implementation
 
 use crate::{
-    eth::logs_utils,
+    eth::{filter::FilterError, logs_utils, logs_utils::EmittedLogs},
     result::{internal_rpc_err, invalid_params_rpc_err},
 };
 use futures::StreamExt;
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
+use parking_lot::Mutex;
 use reth_network_api::NetworkInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_primitives::{
+    BlockNumHash, BlockNumber, IntoRecoveredTransaction, SealedHeader, TxHash, B256,
+};
+use reth_provider::{BlockReader, CanonStateNotification, CanonStateSubscriptions, EvmEnvProvider};
 use reth_rpc_api::EthPubSubApiServer;
+use reth_rpc_server_types::constants::DEFAULT_LOGS_REORG_DEPTH;
 use reth_rpc_types::{
     pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
+        PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
         SyncStatusMetadata,
     },
-    FilteredParams, Header, Log,
+    FilteredParams, Header, Log, PendingTransactionsFilter, ResultChunk, SubscriptionParams,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
+use reth_transaction_pool::{NewTransactionEvent, PoolTransaction, TransactionPool};
 use serde::Serialize;
-use std::sync::Arc;
+use std::{collections::HashSet, ops::RangeInclusive, sync::Arc};
+use tokio::sync::oneshot;
 use tokio_stream::{
-    wrappers::{BroadcastStream, ReceiverStream},
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::{debug, warn};
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -37,6 +43,11 @@ pub struct EthPubSub<Provider, Pool, Events, Network> {
     inner: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// The number of recent blocks whose emitted logs each `logs` subscription buffers.
+    logs_reorg_depth: u64,
+    /// The maximum number of blocks a `newHeads` or `logs` subscription replays, if replay is
+    /// enabled.
+    max_replay_blocks: Option<u64>,
 }
 
 // === impl EthPubSub ===
@@ -64,7 +75,70 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
         subscription_task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
         let inner = EthPubSubInner { provider, pool, chain_events, network };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        Self {
+            inner: Arc::new(inner),
+            subscription_task_spawner,
+            logs_reorg_depth: DEFAULT_LOGS_REORG_DEPTH,
+            max_replay_blocks: None,
+        }
+    }
+
+    /// Configures the number of recent blocks whose emitted logs each `logs` subscription
+    /// buffers.
+    ///
+    /// If a buffered block is reorged out, the subscriber receives its logs again with
+    /// `removed: true`.
+    pub const fn with_logs_reorg_depth(mut self, depth: u64) -> Self {
+        self.logs_reorg_depth = depth;
+        self
+    }
+
+    /// Configures the maximum number of blocks a `newHeads` or `logs` subscription replays.
+    ///
+    /// Replay is disabled by default, in which case the `fromBlock` of the subscription params is
+    /// only used to filter logs. If enabled, subscriptions with a `fromBlock` further in the past
+    /// are rejected.
+    pub const fn with_max_replay_blocks(mut self, max_blocks: Option<u64>) -> Self {
+        self.max_replay_blocks = max_blocks;
+        self
+    }
+}
+
+impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
+where
+    Provider: BlockReader,
+{
+    /// Returns the block from which a `newHeads` or `logs` subscription replays the canonical
+    /// blocks before streaming new ones: the numeric `fromBlock` of its params, if any and if
+    /// replay is enabled.
+    ///
+    /// Returns an error if more than the maximum number of blocks would be replayed.
+    fn replay_from(
+        &self,
+        kind: SubscriptionKind,
+        params: Option<&SubscriptionParams>,
+    ) -> Result<Option<BlockNumber>, ErrorObject<'static>> {
+        let Some(max_replay_blocks) = self.max_replay_blocks else { return Ok(None) };
+        let from = match (kind, params) {
+            (
+                SubscriptionKind::NewHeads | SubscriptionKind::Logs,
+                Some(SubscriptionParams::Logs(filter)),
+            ) => filter.get_from_block(),
+            _ => None,
+        };
+        let Some(from) = from else { return Ok(None) };
+
+        let best = self
+            .inner
+            .provider
+            .best_block_number()
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        if from <= best && best - from >= max_replay_blocks {
+            return Err(invalid_params_rpc_err(format!(
+                "fromBlock is more than {max_replay_blocks} replayed blocks behind the tip"
+            )))
+        }
+        Ok(Some(from))
     }
 }
 
@@ -82,12 +156,31 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
+        let replay_from = match self.replay_from(kind, params.as_ref()) {
+            Ok(replay_from) => replay_from,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
+        let logs_reorg_depth = self.logs_reorg_depth;
+        let task_spawner = self.subscription_task_spawner.clone();
         self.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = handle_accepted(pubsub, sink, kind, params).await;
+            let _ = handle_accepted(
+                pubsub,
+                sink,
+                kind,
+                params,
+                replay_from,
+                logs_reorg_depth,
+                task_spawner,
+            )
+            .await;
         }));
 
         Ok(())
@@ -95,11 +188,18 @@ where
 }
 
 /// The actual handler for an accepted [`EthPubSub::subscribe`] call.
+///
+/// `newHeads` and `logs` subscriptions replay the canonical blocks from `replay_from` to the tip
+/// before streaming new blocks, if set. The provider reads of `newHeads` and `logs` subscriptions
+/// are spawned as blocking tasks of the `task_spawner`.
 async fn handle_accepted<Provider, Pool, Events, Network>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
-    params: Option<Params>,
+    params: Option<SubscriptionParams>,
+    replay_from: Option<BlockNumber>,
+    logs_reorg_depth: u64,
+    task_spawner: Box<dyn TaskSpawner>,
 ) -> Result<(), ErrorObject<'static>>
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
@@ -110,51 +210,59 @@ where
     match kind {
         SubscriptionKind::NewHeads => {
             let stream = pubsub
-                .new_headers_stream()
+                .new_headers_stream(replay_from, task_spawner)
                 .map(|block| EthSubscriptionResult::Header(Box::new(block.into())));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, Box::pin(stream)).await
         }
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
+                Some(SubscriptionParams::Logs(filter)) => FilteredParams::new(Some(*filter)),
+                Some(SubscriptionParams::Bool(_) | SubscriptionParams::PendingTransactions(_)) => {
                     return Err(invalid_params_rpc_err("Invalid params for logs"))
                 }
-                _ => FilteredParams::default(),
+                None => FilteredParams::default(),
             };
-            let stream =
-                pubsub.log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
-            pipe_from_stream(accepted_sink, stream).await
+            let stream = pubsub
+                .log_stream(filter, logs_reorg_depth, replay_from, task_spawner)
+                .map(|log| EthSubscriptionResult::Log(Box::new(log)));
+            pipe_from_stream(accepted_sink, Box::pin(stream)).await
         }
         SubscriptionKind::NewPendingTransactions => {
-            if let Some(params) = params {
-                match params {
-                    Params::Bool(true) => {
-                        // full transaction objects requested
-                        let stream = pubsub.full_pending_transaction_stream().map(|tx| {
-                            EthSubscriptionResult::FullTransaction(Box::new(
-                                reth_rpc_types_compat::transaction::from_recovered(
-                                    tx.transaction.to_recovered_transaction(),
-                                ),
-                            ))
-                        });
-                        return pipe_from_stream(accepted_sink, stream).await
-                    }
-                    Params::Bool(false) | Params::None => {
-                        // only hashes requested
-                    }
-                    Params::Logs(_) => {
-                        return Err(invalid_params_rpc_err(
-                            "Invalid params for newPendingTransactions",
-                        ))
-                    }
+            let filter = match params {
+                Some(SubscriptionParams::Bool(full_transactions)) => {
+                    PendingTransactionsFilter { full_transactions, ..Default::default() }
+                }
+                Some(SubscriptionParams::PendingTransactions(filter)) => filter,
+                Some(SubscriptionParams::Logs(_)) => {
+                    return Err(invalid_params_rpc_err("Invalid params for newPendingTransactions"))
                 }
+                None => PendingTransactionsFilter::default(),
+            };
+
+            if filter.full_transactions {
+                // full transaction objects requested
+                let stream = pubsub.filtered_pending_transaction_stream(filter).map(|tx| {
+                    EthSubscriptionResult::FullTransaction(Box::new(
+                        reth_rpc_types_compat::transaction::from_recovered(
+                            tx.transaction.to_recovered_transaction(),
+                        ),
+                    ))
+                });
+                return pipe_from_stream(accepted_sink, stream).await
+            }
+
+            if filter.matches_all() {
+                // only hashes of all transactions requested
+                let stream = pubsub
+                    .pending_transaction_hashes_stream()
+                    .map(EthSubscriptionResult::TransactionHash);
+                return pipe_from_stream(accepted_sink, stream).await
             }
 
             let stream = pubsub
-                .pending_transaction_hashes_stream()
-                .map(EthSubscriptionResult::TransactionHash);
+                .filtered_pending_transaction_stream(filter)
+                .map(|tx| EthSubscriptionResult::TransactionHash(*tx.transaction.hash()));
             pipe_from_stream(accepted_sink, stream).await
         }
         SubscriptionKind::Syncing => {
@@ -211,6 +319,17 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
     }
 }
 
+/// Sends a chunk of the results of a subscription that streams the results of a method.
+///
+/// Returns `false` if the subscription was closed.
+pub(crate) async fn send_result_chunk<T: Serialize>(
+    sink: &SubscriptionSink,
+    chunk: &ResultChunk<T>,
+) -> Result<bool, SubscriptionSerializeError> {
+    let msg = SubscriptionMessage::from_json(chunk)?;
+    Ok(sink.send(msg).await.is_ok())
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
@@ -296,46 +415,490 @@ where
         ReceiverStream::new(self.pool.pending_transactions_listener())
     }
 
-    /// Returns a stream that yields all transactions emitted by the txpool.
-    fn full_pending_transaction_stream(
+    /// Returns a stream that yields all transactions emitted by the txpool that match the filter.
+    fn filtered_pending_transaction_stream(
         &self,
+        filter: PendingTransactionsFilter,
     ) -> impl Stream<Item = NewTransactionEvent<<Pool as TransactionPool>::Transaction>> {
-        self.pool.new_pending_pool_transactions_listener()
+        self.pool.new_pending_pool_transactions_listener().filter(move |event| {
+            let tx = &event.transaction.transaction;
+            futures::future::ready(filter.matches(
+                tx.sender(),
+                tx.to(),
+                tx.max_fee_per_gas(),
+                tx.tx_type(),
+            ))
+        })
     }
 }
 
 impl<Provider, Pool, Events, Network> EthPubSubInner<Provider, Pool, Events, Network>
 where
-    Provider: BlockReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + EvmEnvProvider + Clone + 'static,
     Events: CanonStateSubscriptions + 'static,
     Network: NetworkInfo + 'static,
     Pool: 'static,
 {
-    /// Returns a stream that yields all new RPC blocks.
-    fn new_headers_stream(&self) -> impl Stream<Item = Header> {
-        self.chain_events.canonical_state_stream().flat_map(|new_chain| {
-            let headers = new_chain.committed().headers().collect::<Vec<_>>();
-            futures::stream::iter(
-                headers.into_iter().map(reth_rpc_types_compat::block::from_primitive_with_hash),
-            )
-        })
+    /// Returns the canonical blocks from `replay_from` to the current tip, which are replayed
+    /// before the new blocks.
+    ///
+    /// This must be called after subscribing to the new blocks, so that no block is missed.
+    fn replay_range(&self, replay_from: Option<BlockNumber>) -> RangeInclusive<BlockNumber> {
+        #[allow(clippy::reversed_empty_ranges)]
+        let nothing = 1..=0;
+        let Some(from) = replay_from else { return nothing };
+        match self.provider.best_block_number() {
+            Ok(best) => from..=best,
+            Err(err) => {
+                warn!(target: "rpc::eth::pubsub", %err, "Failed to read the tip to replay to");
+                nothing
+            }
+        }
+    }
+
+    /// Returns a stream that yields all new RPC blocks, preceded by the canonical blocks from
+    /// `replay_from` to the current tip, if set.
+    ///
+    /// The replayed headers are read from the provider on blocking tasks of the given spawner.
+    fn new_headers_stream(
+        &self,
+        replay_from: Option<BlockNumber>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> impl Stream<Item = Header> {
+        let new_blocks = self.chain_events.canonical_state_stream();
+        let replay = self.replay_range(replay_from);
+        let subscription = Arc::new(Mutex::new(HeadsSubscription {
+            provider: self.provider.clone(),
+            replayed: HashSet::new(),
+            replay_tip: *replay.end(),
+        }));
+
+        futures::stream::iter(replay.map(HeadersEvent::Replay))
+            .chain(new_blocks.map(HeadersEvent::NewChain))
+            .then(move |event| {
+                let subscription = subscription.clone();
+                let task_spawner = task_spawner.clone();
+                async move {
+                    match event {
+                        HeadersEvent::Replay(number) => {
+                            on_blocking_task(&*task_spawner, move || {
+                                subscription.lock().on_replay(number)
+                            })
+                            .await
+                        }
+                        HeadersEvent::NewChain(new_chain) => {
+                            subscription.lock().on_new_chain(&new_chain)
+                        }
+                    }
+                }
+            })
+            .flat_map(|headers| {
+                futures::stream::iter(
+                    headers.into_iter().map(reth_rpc_types_compat::block::from_primitive_with_hash),
+                )
+            })
     }
 
-    /// Returns a stream that yields all logs that match the given filter.
-    fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
-        BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
+    /// Returns a stream that yields all logs that match the given filter, preceded by the
+    /// matching logs of the canonical blocks from `replay_from` to the current tip, if set.
+    ///
+    /// The logs of the last `reorg_depth` blocks are buffered, so that the subscriber receives
+    /// exactly the logs it was sent with `removed: true` if their block is reorged out.
+    ///
+    /// Replayed blocks and the reconciliation after missed notifications read from the provider,
+    /// so they're processed on blocking tasks of the given spawner.
+    fn log_stream(
+        &self,
+        filter: FilteredParams,
+        reorg_depth: u64,
+        replay_from: Option<BlockNumber>,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> impl Stream<Item = Log> {
+        let subscription = Arc::new(Mutex::new(LogsSubscription {
+            provider: self.provider.clone(),
+            filter,
+            emitted: EmittedLogs::new(reorg_depth),
+            reorg_depth,
+            first_block: None,
+        }));
+        let new_blocks = BroadcastStream::new(self.chain_events.subscribe_to_canonical_state());
+        let replay = self.replay_range(replay_from);
+
+        futures::stream::iter(replay.map(LogsEvent::Replay))
+            .chain(new_blocks.map(LogsEvent::CanonState))
+            .then(move |event| {
+                let subscription = subscription.clone();
+                let task_spawner = task_spawner.clone();
+                async move {
+                    match event {
+                        LogsEvent::Replay(number) => {
+                            on_blocking_task(&*task_spawner, move || {
+                                subscription.lock().on_replay(number)
+                            })
+                            .await
+                        }
+                        LogsEvent::CanonState(Ok(notification)) => {
+                            subscription.lock().on_canon_state(&notification)
+                        }
+                        LogsEvent::CanonState(Err(BroadcastStreamRecvError::Lagged(skipped))) => {
+                            debug!(
+                                target: "rpc::eth::pubsub",
+                                skipped,
+                                "Logs subscription lagged behind"
+                            );
+                            on_blocking_task(&*task_spawner, move || {
+                                subscription.lock().on_lagged()
+                            })
+                            .await
+                        }
+                    }
+                }
             })
             .flat_map(futures::stream::iter)
-            .flat_map(move |(block_receipts, removed)| {
-                let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
-                    &filter,
+    }
+}
+
+/// Returns the items of the closure, which is run on a blocking task of the spawner, or no items
+/// if the task was dropped.
+async fn on_blocking_task<T: Send + 'static>(
+    task_spawner: &dyn TaskSpawner,
+    f: impl FnOnce() -> Vec<T> + Send + 'static,
+) -> Vec<T> {
+    let (tx, rx) = oneshot::channel();
+    task_spawner.spawn_blocking(Box::pin(async move {
+        let _ = tx.send(f());
+    }));
+    rx.await.unwrap_or_default()
+}
+
+/// The number of most recent replayed blocks that are skipped if they're also notified as new
+/// blocks.
+const REPLAY_DEDUP_BLOCKS: u64 = 64;
+
+/// An event of a `newHeads` subscription.
+enum HeadersEvent {
+    /// A canonical block to replay.
+    Replay(BlockNumber),
+    /// New canonical blocks.
+    NewChain(CanonStateNotification),
+}
+
+/// An event of a `logs` subscription.
+enum LogsEvent {
+    /// A canonical block to replay.
+    Replay(BlockNumber),
+    /// A canonical state notification.
+    CanonState(Result<CanonStateNotification, BroadcastStreamRecvError>),
+}
+
+/// The state of a `newHeads` subscription.
+struct HeadsSubscription<Provider> {
+    /// The provider the replayed blocks are read from.
+    provider: Provider,
+    /// The hashes of the most recent replayed blocks that weren't notified as new blocks yet.
+    replayed: HashSet<B256>,
+    /// The last block to replay.
+    replay_tip: BlockNumber,
+}
+
+impl<Provider> HeadsSubscription<Provider>
+where
+    Provider: BlockReader,
+{
+    /// Returns the header of the replayed canonical block.
+    fn on_replay(&mut self, number: BlockNumber) -> Vec<SealedHeader> {
+        match self.provider.sealed_header(number) {
+            Ok(header) => {
+                // the most recent replayed blocks may have been committed after subscribing to the
+                // new blocks, in which case they're notified again
+                if number + REPLAY_DEDUP_BLOCKS > self.replay_tip {
+                    self.replayed.extend(header.as_ref().map(|header| header.hash()));
+                }
+                header.into_iter().collect()
+            }
+            Err(err) => {
+                warn!(target: "rpc::eth::pubsub", %err, number, "Failed to replay header");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the headers of the committed blocks that weren't replayed.
+    fn on_new_chain(&mut self, new_chain: &CanonStateNotification) -> Vec<SealedHeader> {
+        new_chain
+            .committed()
+            .headers()
+            .filter(|header| !self.replayed.remove(&header.hash()))
+            .collect()
+    }
+}
+
+/// The state of a `logs` subscription.
+struct LogsSubscription<Provider> {
+    /// The provider used to catch up after missed notifications.
+    provider: Provider,
+    /// The filter of the subscription.
+    filter: FilteredParams,
+    /// The logs emitted for the most recent blocks.
+    emitted: EmittedLogs,
+    /// The number of recent blocks whose emitted logs are buffered.
+    reorg_depth: u64,
+    /// The first block the subscription received, older blocks were never emitted.
+    first_block: Option<BlockNumber>,
+}
+
+impl<Provider> LogsSubscription<Provider>
+where
+    Provider: BlockReader,
+{
+    /// Returns the logs to emit for the notification: the removed logs of the reverted blocks,
+    /// followed by the matching logs of the committed blocks.
+    fn on_canon_state(&mut self, notification: &CanonStateNotification) -> Vec<Log> {
+        let mut logs = Vec::new();
+
+        if let Some(reverted) = notification.reverted() {
+            let oldest_buffered = self.emitted.oldest_block_number();
+            let reverted_hashes =
+                reverted.blocks_iter().map(|block| block.hash()).collect::<HashSet<_>>();
+            logs.extend(self.emitted.remove(|block| reverted_hashes.contains(&block.hash)));
+
+            // reverted blocks that are older than the buffered blocks, because the reorg is deeper
+            // than the buffer, are removed on a best effort basis
+            for block_receipts in reverted.receipts_with_attachment().into_iter().rev() {
+                let number = block_receipts.block.number;
+                let was_emitted = self.first_block.is_some_and(|first| number >= first);
+                if !was_emitted || oldest_buffered.is_some_and(|oldest| number >= oldest) {
+                    continue
+                }
+                let mut block_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                    &self.filter,
                     block_receipts.block,
                     block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
-                    removed,
+                    true,
                 );
-                futures::stream::iter(all_logs)
-            })
+                block_logs.reverse();
+                logs.extend(block_logs);
+            }
+        }
+
+        for block_receipts in notification.committed().receipts_with_attachment() {
+            // the block was already emitted when catching up after missed notifications
+            if self.emitted.contains(block_receipts.block) {
+                continue
+            }
+            self.first_block.get_or_insert(block_receipts.block.number);
+            let block_logs = logs_utils::matching_block_logs_with_tx_hashes(
+                &self.filter,
+                block_receipts.block,
+                block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                false,
+            );
+            logs.extend(block_logs.iter().cloned());
+            self.emitted.push(block_receipts.block, block_logs);
+        }
+
+        logs
+    }
+
+    /// Returns the matching logs of the replayed canonical block.
+    fn on_replay(&mut self, number: BlockNumber) -> Vec<Log> {
+        self.first_block.get_or_insert(number);
+        let mut logs = Vec::new();
+        if let Err(err) = self.append_canonical_block_logs(number, &mut logs) {
+            warn!(target: "rpc::eth::pubsub", %err, number, "Failed to replay logs");
+        }
+        logs
+    }
+
+    /// Returns the logs to emit after the subscription missed notifications.
+    ///
+    /// This reconciles the buffered blocks with the canonical chain: the logs of buffered blocks
+    /// that are no longer canonical are removed, and the logs of the missed canonical blocks
+    /// within the reorg depth are emitted.
+    fn on_lagged(&mut self) -> Vec<Log> {
+        let mut logs = Vec::new();
+        if let Err(err) = self.reconcile(&mut logs) {
+            warn!(target: "rpc::eth::pubsub", %err, "Failed to reconcile logs subscription");
+        }
+        logs
+    }
+
+    /// Appends the logs to emit for reconciling the buffered blocks with the canonical chain.
+    fn reconcile(&mut self, logs: &mut Vec<Log>) -> Result<(), FilterError> {
+        let mut non_canonical = HashSet::new();
+        for block in self.emitted.blocks() {
+            if self.provider.block_hash(block.number)? != Some(block.hash) {
+                non_canonical.insert(block.hash);
+            }
+        }
+        logs.extend(self.emitted.remove(|block| non_canonical.contains(&block.hash)));
+
+        // nothing was emitted yet, so the subscription starts with the next notification
+        let Some(first_block) = self.first_block else { return Ok(()) };
+
+        let best = self.provider.best_block_number()?;
+        let from = self
+            .emitted
+            .last_block()
+            .map_or(first_block, |block| block.number + 1)
+            .max((best + 1).saturating_sub(self.reorg_depth));
+        for number in from..=best {
+            if !self.append_canonical_block_logs(number, logs)? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Appends the matching logs of the canonical block with the given number, and buffers them.
+    ///
+    /// Returns `false` if the block doesn't exist.
+    fn append_canonical_block_logs(
+        &mut self,
+        number: BlockNumber,
+        logs: &mut Vec<Log>,
+    ) -> Result<bool, FilterError> {
+        let Some(header) = self.provider.sealed_header(number)? else { return Ok(false) };
+        let Some(receipts) = self.provider.receipts_by_block(number.into())? else {
+            return Ok(false)
+        };
+        let block = BlockNumHash::new(number, header.hash());
+        let mut block_logs = Vec::new();
+        logs_utils::append_matching_block_logs(
+            &mut block_logs,
+            &self.provider,
+            &self.filter,
+            block,
+            &receipts,
+            false,
+            header.timestamp,
+        )?;
+        logs.extend(block_logs.iter().cloned());
+        self.emitted.push(block, block_logs);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Address, Block, Bytes, Header, Receipt, TransactionSigned};
+    use reth_provider::{
+        test_utils::{MockEthProvider, TestCanonStateSubscriptions},
+        Chain, ExecutionOutcome, HeaderProvider,
+    };
+    use reth_rpc_types::Filter;
+
+    /// Adds a canonical block of the given fork whose single transaction emits a log.
+    fn add_block(provider: &MockEthProvider, number: BlockNumber, fork: u8) -> BlockNumHash {
+        let header = Header { number, extra_data: Bytes::from(vec![fork]), ..Default::default() };
+        let hash = header.hash_slow();
+        provider.add_header(hash, header.clone());
+        provider.add_block(
+            hash,
+            Block { header, body: vec![TransactionSigned::default()], ..Default::default() },
+        );
+        let log = reth_primitives::Log::new_unchecked(Address::ZERO, Vec::new(), Bytes::new());
+        provider.add_receipts(hash, vec![Receipt { logs: vec![log], ..Default::default() }]);
+        BlockNumHash::new(number, hash)
+    }
+
+    /// Removes the block from the canonical chain.
+    fn remove_block(provider: &MockEthProvider, block: BlockNumHash) {
+        provider.blocks.lock().remove(&block.hash);
+        provider.headers.lock().remove(&block.hash);
+        provider.receipts.lock().remove(&block.hash);
+    }
+
+    fn blocks_of(logs: &[Log]) -> Vec<(B256, bool)> {
+        logs.iter().map(|log| (log.block_hash.unwrap(), log.removed)).collect()
+    }
+
+    #[test]
+    fn reconcile_reorged_blocks() {
+        let provider = MockEthProvider::default();
+        let old = (1..=3).map(|number| add_block(&provider, number, 0)).collect::<Vec<_>>();
+        let mut subscription = LogsSubscription {
+            provider: provider.clone(),
+            filter: FilteredParams::default(),
+            emitted: EmittedLogs::new(2),
+            reorg_depth: 2,
+            first_block: None,
+        };
+        let replayed =
+            (1..=3).flat_map(|number| subscription.on_replay(number)).collect::<Vec<_>>();
+        assert_eq!(
+            blocks_of(&replayed),
+            old.iter().map(|block| (block.hash, false)).collect::<Vec<_>>()
+        );
+
+        // the subscription misses a reorg of block 3 to a new fork with blocks 3 and 4
+        remove_block(&provider, old[2]);
+        let new = (3..=4).map(|number| add_block(&provider, number, 1)).collect::<Vec<_>>();
+
+        let logs = subscription.on_lagged();
+        assert_eq!(
+            blocks_of(&logs),
+            vec![(old[2].hash, true), (new[0].hash, false), (new[1].hash, false)]
+        );
+        assert!(subscription.emitted.contains(new[1]));
+
+        // nothing is emitted again if the chain didn't change
+        assert!(subscription.on_lagged().is_empty());
+    }
+
+    #[test]
+    fn replay_is_opt_in() {
+        let provider = MockEthProvider::default();
+        for number in 1..=5 {
+            add_block(&provider, number, 0);
+        }
+        let params = SubscriptionParams::Logs(Box::new(Filter::new().from_block(2)));
+        let pubsub = EthPubSub::new(provider, (), (), ());
+
+        // the `fromBlock` only filters logs if replay is disabled
+        assert_eq!(pubsub.replay_from(SubscriptionKind::Logs, Some(&params)).unwrap(), None);
+
+        let pubsub = pubsub.with_max_replay_blocks(Some(4));
+        assert_eq!(pubsub.replay_from(SubscriptionKind::NewHeads, Some(&params)).unwrap(), Some(2));
+        assert_eq!(pubsub.replay_from(SubscriptionKind::Logs, None).unwrap(), None);
+
+        let pubsub = pubsub.with_max_replay_blocks(Some(3));
+        assert!(pubsub.replay_from(SubscriptionKind::Logs, Some(&params)).is_err());
+    }
+
+    #[tokio::test]
+    async fn new_heads_replay_before_new_blocks() {
+        let provider = MockEthProvider::default();
+        let old = (1..=3).map(|number| add_block(&provider, number, 0)).collect::<Vec<_>>();
+        let chain_events = TestCanonStateSubscriptions::default();
+        let pubsub = EthPubSubInner {
+            pool: (),
+            provider: provider.clone(),
+            chain_events: chain_events.clone(),
+            network: NoopNetwork::default(),
+        };
+        let mut stream =
+            Box::pin(pubsub.new_headers_stream(Some(2), Box::<TokioTaskExecutor>::default()));
+
+        // block 3 was replayed, so only the new block 4 is streamed
+        let new = add_block(&provider, 4, 0);
+        let blocks = [3, 4].map(|number| {
+            let header = provider.sealed_header(number).unwrap().unwrap().unseal();
+            Block { header, ..Default::default() }.seal_slow().with_senders_unchecked(Vec::new())
+        });
+        chain_events.add_next_commit(Arc::new(Chain::new(
+            blocks,
+            ExecutionOutcome::default(),
+            None,
+        )));
+
+        let mut hashes = Vec::new();
+        for _ in 0..3 {
+            hashes.push(stream.next().await.unwrap().hash.unwrap());
+        }
+        assert_eq!(hashes, vec![old[1].hash, old[2].hash, new.hash]);
     }
 }
@@ -0,0 +1,313 @@
+//! Decoding of custom revert errors with ABI error definitions provided by the operator.
+
+use alloy_dyn_abi::{DynSolValue, ErrorExt};
+use alloy_json_abi::{Error as AbiError, JsonAbi};
+use alloy_primitives::{hex, Selector};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The default maximum number of error definitions in a [`RevertDecoder`].
+pub const DEFAULT_MAX_REVERT_ERRORS: usize = 10_000;
+
+/// Errors that can occur when registering ABI error definitions.
+#[derive(Debug, thiserror::Error)]
+pub enum RevertDecoderError {
+    /// The file could not be read.
+    #[error("failed to read ABI file {path:?}: {source}")]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// The file is neither a JSON ABI nor a compiler artifact containing one.
+    #[error("failed to parse ABI file {path:?}: {source}")]
+    Parse {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+    /// Registering the definitions would exceed the maximum number of definitions.
+    #[error("the registry is limited to {max} error definitions")]
+    TooManyErrors {
+        /// The maximum number of definitions.
+        max: usize,
+    },
+}
+
+/// The content of an ABI file: either the JSON ABI itself or a compiler artifact with an `abi`
+/// field.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbiFile {
+    Abi(JsonAbi),
+    Artifact { abi: JsonAbi },
+}
+
+/// A registry of custom error definitions, used to decode the output of reverted calls.
+///
+/// The registry is shared by all clones, so definitions can be added at runtime. It holds at most
+/// [`DEFAULT_MAX_REVERT_ERRORS`] definitions by default.
+#[derive(Debug, Clone)]
+pub struct RevertDecoder {
+    /// The registered error definitions by selector. There can be more than one definition per
+    /// selector, since parameter names don't affect the selector.
+    errors: Arc<RwLock<HashMap<Selector, Vec<AbiError>>>>,
+    /// The maximum number of registered definitions.
+    max_errors: usize,
+}
+
+impl Default for RevertDecoder {
+    fn default() -> Self {
+        Self { errors: Default::default(), max_errors: DEFAULT_MAX_REVERT_ERRORS }
+    }
+}
+
+impl RevertDecoder {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of registered definitions.
+    pub const fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Registers the given error definitions.
+    ///
+    /// Returns the number of definitions that weren't registered yet. None of the definitions are
+    /// registered if that would exceed the maximum number of definitions.
+    pub fn add_errors(
+        &self,
+        errors: impl IntoIterator<Item = AbiError>,
+    ) -> Result<usize, RevertDecoderError> {
+        let mut registered = self.errors.write();
+        let mut new_errors: Vec<AbiError> = Vec::new();
+        for error in errors {
+            let known =
+                registered.get(&error.selector()).is_some_and(|known| known.contains(&error));
+            if !known && !new_errors.contains(&error) {
+                new_errors.push(error);
+            }
+        }
+
+        let len = registered.values().map(Vec::len).sum::<usize>();
+        if len + new_errors.len() > self.max_errors {
+            return Err(RevertDecoderError::TooManyErrors { max: self.max_errors })
+        }
+
+        let added = new_errors.len();
+        for error in new_errors {
+            registered.entry(error.selector()).or_default().push(error);
+        }
+        Ok(added)
+    }
+
+    /// Registers all error definitions of the ABI.
+    ///
+    /// Returns the number of definitions that weren't registered yet.
+    pub fn add_abi(&self, abi: &JsonAbi) -> Result<usize, RevertDecoderError> {
+        self.add_errors(abi.errors().cloned())
+    }
+
+    /// Registers all error definitions of the JSON ABI file, or of the `abi` field of a compiler
+    /// artifact.
+    ///
+    /// Returns the number of definitions that weren't registered yet.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<usize, RevertDecoderError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|source| RevertDecoderError::Io { path: path.to_path_buf(), source })?;
+        let abi = match serde_json::from_str(&content)
+            .map_err(|source| RevertDecoderError::Parse { path: path.to_path_buf(), source })?
+        {
+            AbiFile::Abi(abi) | AbiFile::Artifact { abi } => abi,
+        };
+        self.add_abi(&abi)
+    }
+
+    /// Returns the number of registered error definitions.
+    pub fn len(&self) -> usize {
+        self.errors.read().values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if no error definitions are registered.
+    pub fn is_empty(&self) -> bool {
+        self.errors.read().is_empty()
+    }
+
+    /// Decodes the output of a reverted call with the registered error definitions.
+    ///
+    /// Returns `None` if no definition matches the selector and the encoded arguments.
+    pub fn decode(&self, output: &[u8]) -> Option<DecodedRevert> {
+        let selector = Selector::try_from(output.get(..4)?).ok()?;
+        let registered = self.errors.read();
+        registered.get(&selector)?.iter().find_map(|error| {
+            let decoded = error.decode_error(output).ok()?;
+            let args = error
+                .inputs
+                .iter()
+                .zip(&decoded.body)
+                .map(|(param, value)| DecodedRevertArg {
+                    name: param.name.clone(),
+                    ty: param.selector_type().into_owned(),
+                    value: value_to_json(value),
+                })
+                .collect();
+            Some(DecodedRevert { name: error.name.clone(), signature: error.signature(), args })
+        })
+    }
+}
+
+/// A custom error decoded from the output of a reverted call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedRevert {
+    /// The name of the error.
+    pub name: String,
+    /// The signature of the error, e.g. `InsufficientBalance(uint256,uint256)`.
+    pub signature: String,
+    /// The decoded arguments of the error.
+    pub args: Vec<DecodedRevertArg>,
+}
+
+impl fmt::Display for DecodedRevert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match &arg.value {
+                serde_json::Value::String(value) => f.write_str(value)?,
+                value => write!(f, "{value}")?,
+            }
+        }
+        f.write_str(")")
+    }
+}
+
+/// A decoded argument of a [`DecodedRevert`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DecodedRevertArg {
+    /// The name of the parameter, empty if it's unnamed.
+    pub name: String,
+    /// The canonical type of the parameter.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The decoded value.
+    ///
+    /// Integers are decimal strings, and bytes are hex strings.
+    pub value: serde_json::Value,
+}
+
+/// Converts a decoded ABI value to JSON.
+fn value_to_json(value: &DynSolValue) -> serde_json::Value {
+    match value {
+        DynSolValue::Bool(value) => (*value).into(),
+        DynSolValue::Int(value, _) => value.to_string().into(),
+        DynSolValue::Uint(value, _) => value.to_string().into(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]).into(),
+        DynSolValue::Address(address) => address.to_checksum(None).into(),
+        DynSolValue::Function(function) => hex::encode_prefixed(function).into(),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes).into(),
+        DynSolValue::String(value) => value.clone().into(),
+        DynSolValue::Array(values) |
+        DynSolValue::FixedArray(values) |
+        DynSolValue::Tuple(values) |
+        DynSolValue::CustomStruct { tuple: values, .. } => {
+            values.iter().map(value_to_json).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+    use std::io::Write;
+
+    fn insufficient_balance() -> AbiError {
+        AbiError::parse("InsufficientBalance(uint256 available, address account)").unwrap()
+    }
+
+    fn encode(error: &AbiError, args: &[DynSolValue]) -> Vec<u8> {
+        let mut output = error.selector().to_vec();
+        output.extend(DynSolValue::Tuple(args.to_vec()).abi_encode_params());
+        output
+    }
+
+    #[test]
+    fn decodes_registered_error() {
+        let decoder = RevertDecoder::new();
+        assert_eq!(decoder.add_errors([insufficient_balance()]).unwrap(), 1);
+        assert_eq!(decoder.add_errors([insufficient_balance()]).unwrap(), 0);
+        assert_eq!(decoder.len(), 1);
+
+        let account = address!("00000000000000000000000000000000000000aa");
+        let output = encode(
+            &insufficient_balance(),
+            &[DynSolValue::Uint(U256::from(100), 256), DynSolValue::Address(account)],
+        );
+        let decoded = decoder.decode(&output).unwrap();
+        assert_eq!(decoded.signature, "InsufficientBalance(uint256,address)");
+        assert_eq!(decoded.args[0].name, "available");
+        assert_eq!(decoded.args[0].value, serde_json::json!("100"));
+        assert_eq!(decoded.to_string(), format!("InsufficientBalance(100, {account})"));
+    }
+
+    #[test]
+    fn ignores_unknown_or_malformed_output() {
+        let decoder = RevertDecoder::new();
+        decoder.add_errors([insufficient_balance()]).unwrap();
+
+        assert!(decoder.decode(&[]).is_none());
+        assert!(decoder.decode(&[0xde, 0xad, 0xbe, 0xef]).is_none());
+        // selector matches but the arguments are missing
+        assert!(decoder.decode(insufficient_balance().selector().as_slice()).is_none());
+    }
+
+    #[test]
+    fn limits_registered_errors() {
+        let decoder = RevertDecoder::new().with_max_errors(2);
+        let unauthorized = AbiError::parse("Unauthorized()").unwrap();
+        let expired = AbiError::parse("Expired(uint256 deadline)").unwrap();
+        assert_eq!(decoder.add_errors([insufficient_balance(), unauthorized.clone()]).unwrap(), 2);
+
+        // known definitions don't count towards the limit
+        assert_eq!(decoder.add_errors([unauthorized.clone()]).unwrap(), 0);
+        assert!(matches!(
+            decoder.add_errors([unauthorized, expired.clone()]),
+            Err(RevertDecoderError::TooManyErrors { max: 2 })
+        ));
+        assert_eq!(decoder.len(), 2);
+        let output = encode(&expired, &[DynSolValue::Uint(U256::from(1), 256)]);
+        assert!(decoder.decode(&output).is_none());
+    }
+
+    #[test]
+    fn loads_artifact_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{"abi":[{{"type":"error","name":"Unauthorized","inputs":[]}},
+            {{"type":"function","name":"owner","inputs":[],"outputs":[],"stateMutability":"view"}}]}}"#
+        )
+        .unwrap();
+
+        let decoder = RevertDecoder::new();
+        assert_eq!(decoder.load_file(file.path()).unwrap(), 1);
+
+        let output = AbiError::parse("Unauthorized()").unwrap().selector();
+        assert_eq!(decoder.decode(output.as_slice()).unwrap().to_string(), "Unauthorized()");
+    }
+}
@@ -0,0 +1,330 @@
+//! Helpers of `eth_simulateV1`, the simulation of a sequence of blocks on top of a base block.
+
+use crate::eth::error::RpcInvalidTransactionError;
+use jsonrpsee::types::ErrorObject;
+use reth_primitives::{
+    keccak256, AccessList, AccessListItem, Address, Bytes, Log, LogData, Signature, Transaction,
+    TransactionSigned, TxEip1559, TxEip2930, TxEip4844, TxKind, TxLegacy, B256, U256,
+};
+use reth_rpc_types::SIMULATE_TRANSFER_LOG_ADDRESS;
+use revm::{
+    db::{states::StorageSlot, AccountState, AccountStatus, BundleAccount, BundleState, CacheDB},
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::TxEnv,
+    Database, EvmContext, Inspector,
+};
+
+/// The maximum number of blocks of an `eth_simulateV1` request, including the blocks that fill
+/// the gaps between the requested block numbers.
+pub const MAX_SIMULATE_BLOCKS: u64 = 256;
+
+/// The timestamp increment between a block and its parent, if the timestamp is not overridden.
+pub(crate) const SIMULATE_BLOCK_TIME: u64 = 12;
+
+/// Errors of `eth_simulateV1`, with the codes of the spec.
+#[derive(Debug, thiserror::Error)]
+pub enum SimulateError {
+    /// A block number is not greater than the number of the previous block.
+    #[error("block number {number} is not greater than the previous block number {parent}")]
+    BlockNumberNotIncreasing {
+        /// The number of the block.
+        number: u64,
+        /// The number of the previous block.
+        parent: u64,
+    },
+    /// A block timestamp is not greater than the timestamp of the previous block.
+    #[error("block timestamp {timestamp} is not greater than the previous timestamp {parent}")]
+    TimestampNotIncreasing {
+        /// The timestamp of the block.
+        timestamp: u64,
+        /// The timestamp of the previous block.
+        parent: u64,
+    },
+    /// The calls of a block need more gas than the block gas limit.
+    #[error("calls of block {number} exceed the block gas limit of {gas_limit}")]
+    BlockGasLimitExceeded {
+        /// The number of the block.
+        number: u64,
+        /// The gas limit of the block.
+        gas_limit: u64,
+    },
+    /// The request simulates more than [`MAX_SIMULATE_BLOCKS`] blocks.
+    #[error("too many blocks, at most {MAX_SIMULATE_BLOCKS} blocks can be simulated")]
+    TooManyBlocks,
+    /// A call is not a valid transaction, if the calls are validated.
+    #[error(transparent)]
+    InvalidTransaction(RpcInvalidTransactionError),
+}
+
+impl SimulateError {
+    /// Returns the JSON-RPC error code of the error.
+    pub const fn error_code(&self) -> i32 {
+        match self {
+            Self::BlockNumberNotIncreasing { .. } => -38020,
+            Self::TimestampNotIncreasing { .. } => -38021,
+            Self::BlockGasLimitExceeded { .. } => -38015,
+            Self::TooManyBlocks => -38026,
+            Self::InvalidTransaction(err) => match err {
+                RpcInvalidTransactionError::NonceTooLow => -38010,
+                RpcInvalidTransactionError::NonceTooHigh => -38011,
+                RpcInvalidTransactionError::FeeCapTooLow => -38012,
+                RpcInvalidTransactionError::GasTooLow => -38013,
+                RpcInvalidTransactionError::InsufficientFunds |
+                RpcInvalidTransactionError::InsufficientFundsForTransfer => -38014,
+                RpcInvalidTransactionError::SenderNoEOA => -38024,
+                RpcInvalidTransactionError::MaxInitCodeSizeExceeded => -38025,
+                _ => -32602,
+            },
+        }
+    }
+}
+
+impl From<SimulateError> for ErrorObject<'static> {
+    fn from(err: SimulateError) -> Self {
+        ErrorObject::owned(err.error_code(), err.to_string(), None::<()>)
+    }
+}
+
+/// An inspector that collects the logs of a call, and emits a log for every transfer of ether.
+///
+/// The transfers are logged like the `Transfer` event of ERC-20 tokens, emitted by
+/// [`SIMULATE_TRANSFER_LOG_ADDRESS`]. The logs of reverted frames are discarded.
+#[derive(Debug, Default)]
+pub(crate) struct TransferInspector {
+    logs: Vec<Log>,
+    /// The number of logs at the start of each open frame
+    frames: Vec<usize>,
+}
+
+impl TransferInspector {
+    /// Returns the collected logs.
+    pub(crate) fn into_logs(self) -> Vec<Log> {
+        self.logs
+    }
+
+    fn push_transfer(&mut self, from: Address, to: Address, value: U256) {
+        self.logs.push(transfer_log(from, to, value));
+    }
+
+    /// Closes the innermost frame, discarding its logs if it failed.
+    fn end_frame(&mut self, success: bool) -> usize {
+        let start = self.frames.pop().unwrap_or_default();
+        if !success {
+            self.logs.truncate(start);
+        }
+        start
+    }
+}
+
+impl<DB: Database> Inspector<DB> for TransferInspector {
+    fn log(&mut self, _context: &mut EvmContext<DB>, log: &Log) {
+        self.logs.push(log.clone());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.frames.push(self.logs.len());
+        if let Some(value) = inputs.transfer_value().filter(|value| !value.is_zero()) {
+            self.push_transfer(inputs.caller, inputs.target_address, value);
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.end_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.frames.push(self.logs.len());
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let start = self.end_frame(outcome.result.is_ok());
+        // the created address is only known once the creation ended, the transfer precedes the
+        // logs of the init code
+        if let Some(created) = outcome.address.filter(|_| !inputs.value.is_zero()) {
+            if outcome.result.is_ok() {
+                self.logs.insert(start, transfer_log(inputs.caller, created, inputs.value));
+            }
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if !value.is_zero() {
+            self.push_transfer(contract, target, value);
+        }
+    }
+}
+
+/// Returns the log of a transfer of ether.
+fn transfer_log(from: Address, to: Address, value: U256) -> Log {
+    Log {
+        address: SIMULATE_TRANSFER_LOG_ADDRESS,
+        data: LogData::new_unchecked(
+            vec![keccak256("Transfer(address,address,uint256)"), from.into_word(), to.into_word()],
+            Bytes::from(value.to_be_bytes::<32>()),
+        ),
+    }
+}
+
+/// Returns a [`BundleState`] with the state of the accounts cached by the [`CacheDB`], to
+/// compute the state root after the simulated calls.
+///
+/// Accounts that were not modified are included with their current state, which does not change
+/// the state root.
+pub(crate) fn bundle_state_from_cache<DB>(db: &CacheDB<DB>) -> BundleState {
+    let state = db
+        .accounts
+        .iter()
+        .map(|(address, account)| {
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (*slot, StorageSlot::new_changed(U256::ZERO, *value)))
+                .collect();
+            let status = match account.account_state {
+                AccountState::NotExisting | AccountState::StorageCleared => {
+                    AccountStatus::DestroyedChanged
+                }
+                AccountState::Touched | AccountState::None => AccountStatus::Changed,
+            };
+            (*address, BundleAccount::new(None, account.info(), storage, status))
+        })
+        .collect();
+    BundleState { state, ..Default::default() }
+}
+
+/// Returns the transaction of a simulated call, signed with an empty signature.
+///
+/// The transaction type is derived from the fee fields like for `eth_sendTransaction`.
+pub(crate) fn simulated_transaction(tx: &TxEnv, nonce: u64, chain_id: u64) -> TransactionSigned {
+    let access_list = AccessList(
+        tx.access_list
+            .iter()
+            .map(|(address, slots)| AccessListItem {
+                address: *address,
+                storage_keys: slots.iter().map(|slot| B256::from(*slot)).collect(),
+            })
+            .collect(),
+    );
+    let gas_price = tx.gas_price.saturating_to();
+    let transaction = match (tx.gas_priority_fee, tx.transact_to) {
+        (_, TxKind::Call(to)) if !tx.blob_hashes.is_empty() => Transaction::Eip4844(TxEip4844 {
+            chain_id,
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: tx.gas_priority_fee.unwrap_or_default().saturating_to(),
+            placeholder: Some(()),
+            to,
+            value: tx.value,
+            access_list,
+            blob_versioned_hashes: tx.blob_hashes.clone(),
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas.unwrap_or_default().saturating_to(),
+            input: tx.data.clone(),
+        }),
+        (Some(priority_fee), to) => Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: priority_fee.saturating_to(),
+            to,
+            value: tx.value,
+            access_list,
+            input: tx.data.clone(),
+        }),
+        (None, to) if !access_list.is_empty() => Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value: tx.value,
+            access_list,
+            input: tx.data.clone(),
+        }),
+        (None, to) => Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price,
+            gas_limit: tx.gas_limit,
+            to,
+            value: tx.value,
+            input: tx.data.clone(),
+        }),
+    };
+    TransactionSigned::from_transaction_and_signature(transaction, Signature::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::address;
+    use revm::db::EmptyDB;
+
+    #[test]
+    fn discards_logs_of_failed_frames() {
+        let mut inspector = TransferInspector::default();
+        let (from, to) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        inspector.frames.push(0);
+        inspector.push_transfer(from, to, U256::from(1));
+        inspector.frames.push(inspector.logs.len());
+        inspector.push_transfer(to, from, U256::from(2));
+        inspector.end_frame(false);
+        inspector.end_frame(true);
+
+        let logs = inspector.into_logs();
+        assert_eq!(logs, vec![transfer_log(from, to, U256::from(1))]);
+        assert_eq!(logs[0].address, address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"));
+        assert_eq!(logs[0].topics()[2], to.into_word());
+        assert_eq!(logs[0].data.data, Bytes::from(U256::from(1).to_be_bytes::<32>()));
+    }
+
+    #[test]
+    fn bundle_state_of_cache() {
+        let mut db = CacheDB::new(EmptyDB::default());
+        let (changed, cleared) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        db.insert_account_storage(changed, U256::from(1), U256::from(2)).unwrap();
+        db.replace_account_storage(cleared, Default::default()).unwrap();
+
+        let bundle = bundle_state_from_cache(&db);
+        let account = &bundle.state[&changed];
+        assert!(!account.status.was_destroyed());
+        assert_eq!(account.storage[&U256::from(1)].present_value, U256::from(2));
+        assert!(bundle.state[&cleared].status.was_destroyed());
+    }
+
+    #[test]
+    fn simulated_transaction_types() {
+        let mut tx = TxEnv { gas_limit: 21_000, ..Default::default() };
+        assert!(simulated_transaction(&tx, 0, 1).is_legacy());
+
+        tx.gas_priority_fee = Some(U256::from(1));
+        let signed = simulated_transaction(&tx, 7, 1);
+        assert!(matches!(signed.transaction, Transaction::Eip1559(_)));
+        assert_eq!(signed.nonce(), 7);
+        assert_eq!(signed.hash(), simulated_transaction(&tx, 7, 1).hash());
+    }
+}
@@ -1,6 +1,7 @@
 //! Additional helper traits that allow for more customization.
 
 use crate::eth::error::EthResult;
+use reth_primitives::PooledTransactionsElementEcRecovered;
 use std::fmt;
 
 /// A trait that allows for forwarding raw transactions.
@@ -10,4 +11,26 @@ use std::fmt;
 pub trait RawTransactionForwarder: fmt::Debug + Send + Sync + 'static {
     /// Forwards raw transaction bytes for `eth_sendRawTransaction`
     async fn forward_raw_transaction(&self, raw: &[u8]) -> EthResult<()>;
+
+    /// Returns `true` if forwarded transactions are also submitted to the local pool.
+    ///
+    /// If `false`, `eth_sendRawTransaction` only forwards the transaction and returns its hash.
+    fn submit_to_pool(&self) -> bool {
+        true
+    }
+}
+
+/// A trait that allows for validating raw transactions before they are forwarded or submitted to
+/// the pool.
+///
+/// For example to check the cross-chain messages of a transaction.
+#[async_trait::async_trait]
+pub trait RawTransactionValidator: fmt::Debug + Send + Sync + 'static {
+    /// Validates the decoded transaction of `eth_sendRawTransaction`.
+    ///
+    /// The transaction is rejected with the returned error.
+    async fn validate_raw_transaction(
+        &self,
+        tx: &PooledTransactionsElementEcRecovered,
+    ) -> EthResult<()>;
 }
@@ -41,17 +41,20 @@ mod otterscan;
 mod reth;
 mod rpc;
 mod trace;
+mod trace_index;
 mod txpool;
 mod web3;
+mod witness;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{RethApi, RethBlobs, RethSimulate};
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+pub use trace::{trace_index_new_blocks_task, TraceApi, MAX_TRACE_FILTER_BLOCKS};
+pub use trace_index::TraceIndex;
 pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
 pub mod result;
@@ -1,20 +1,28 @@
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use revm_inspectors::transfer::{TransferInspector, TransferKind};
+use revm_inspectors::{
+    tracing::TracingInspectorConfig,
+    transfer::{TransferInspector, TransferKind},
+};
 use revm_primitives::ExecutionResult;
+use std::{cmp::Reverse, future::Future};
 
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256, U256};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
+use reth_rpc_server_types::constants::DEFAULT_MAX_OTS_SEARCH_BLOCKS;
 use reth_rpc_types::{
     trace::otterscan::{
         BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
         OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
     },
-    BlockTransactions, Transaction,
+    AnyTransactionReceipt, BlockTransactions, RichBlock, Transaction,
 };
 
-use crate::{eth::EthTransactions, result::internal_rpc_err};
+use crate::{
+    eth::{error::EthApiError, EthTransactions},
+    result::{internal_rpc_err, invalid_params_rpc_err},
+};
 
 const API_LEVEL: u64 = 8;
 
@@ -22,12 +30,104 @@ const API_LEVEL: u64 = 8;
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
     eth: Eth,
+    /// The maximum number of blocks a transaction search goes through.
+    max_search_blocks: u64,
 }
 
 impl<Eth> OtterscanApi<Eth> {
     /// Creates a new instance of `Otterscan`.
     pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+        Self { eth, max_search_blocks: DEFAULT_MAX_OTS_SEARCH_BLOCKS }
+    }
+
+    /// Sets the maximum number of blocks a transaction search goes through.
+    pub const fn with_max_search_blocks(mut self, max_search_blocks: u64) -> Self {
+        self.max_search_blocks = max_search_blocks;
+        self
+    }
+}
+
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer + EthTransactions,
+{
+    /// Searches the given blocks, in order, for transactions that were sent by or to the address,
+    /// or that created it, until at least `page_size` transactions were found.
+    ///
+    /// Blocks are always searched completely, so more than `page_size` transactions may be
+    /// returned. The transactions are sorted in descending order, and the returned flag is `true`
+    /// if all blocks were searched.
+    ///
+    /// At most `max_search_blocks` blocks are searched. If no transaction was found in them, an
+    /// error is returned, so that the search can be narrowed down.
+    ///
+    /// Note: only the top-level calls of transactions are matched, calls to the address from
+    /// within another contract are not found.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        blocks: impl Iterator<Item = u64> + Send,
+        page_size: usize,
+    ) -> RpcResult<(Vec<(Transaction, OtsTransactionReceipt)>, bool)> {
+        let mut matches = Vec::new();
+        let mut searched_all = true;
+        for (searched, num) in (0u64..).zip(blocks) {
+            if matches.len() >= page_size {
+                searched_all = false;
+                break
+            }
+            if searched >= self.max_search_blocks {
+                if matches.is_empty() {
+                    return Err(invalid_params_rpc_err(format!(
+                        "no transactions found in the searched {searched} blocks"
+                    )))
+                }
+                searched_all = false;
+                break
+            }
+
+            let block = self
+                .eth
+                .block_by_id_with_senders(num.into())
+                .await?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let indices = block
+                .transactions_with_sender()
+                .enumerate()
+                .filter(|(_, (sender, tx))| {
+                    **sender == address ||
+                        tx.to() == Some(address) ||
+                        (tx.kind().is_create() && sender.create(tx.nonce()) == address)
+                })
+                .map(|(idx, _)| idx)
+                .collect::<Vec<_>>();
+            if indices.is_empty() {
+                continue
+            }
+
+            // retrieve the rpc representation of the block and its receipts
+            let block = self.eth.block_by_number(num.into(), true);
+            let receipts = self.eth.block_receipts(num.into());
+            let (block, receipts) = futures::try_join!(block, receipts)?;
+            let block = block.ok_or(EthApiError::UnknownBlockNumber)?;
+            let receipts = receipts.ok_or_else(|| internal_rpc_err("receipts not found"))?;
+            let timestamp = block.header.timestamp;
+            let BlockTransactions::Full(transactions) = block.inner.transactions else {
+                return Err(internal_rpc_err("block is not full"));
+            };
+
+            for idx in indices {
+                let (Some(tx), Some(receipt)) = (transactions.get(idx), receipts.get(idx)) else {
+                    return Err(internal_rpc_err(
+                        "the number of transactions does not match the number of receipts",
+                    ))
+                };
+                matches.push((tx.clone(), ots_receipt(receipt.clone(), timestamp)));
+            }
+        }
+
+        matches.sort_by_key(|(tx, _)| Reverse((tx.block_number, tx.transaction_index)));
+        Ok((matches, searched_all))
     }
 }
 
@@ -90,8 +190,33 @@ where
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+        let traces = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_tx_info, inspector, _, _| Ok(inspector.into_traces().into_nodes()),
+            )
+            .await?
+            .map(|nodes| {
+                nodes
+                    .into_iter()
+                    .map(|node| TraceEntry {
+                        r#type: if node.trace.is_selfdestruct() {
+                            "SELFDESTRUCT".to_string()
+                        } else {
+                            node.trace.kind.to_str().to_string()
+                        },
+                        depth: node.trace.depth as u32,
+                        from: node.trace.caller,
+                        to: node.trace.address,
+                        value: node.trace.value,
+                        input: node.trace.data,
+                    })
+                    .collect::<Vec<_>>()
+            });
+        Ok(traces)
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -99,14 +224,18 @@ where
         &self,
         block_number: BlockNumberOrTag,
     ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+        let block = self.eth.block_by_number(block_number, true);
+        let receipts = self.eth.block_receipts(BlockId::Number(block_number));
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+        Ok(block.map(|block| block_details(block, receipts.unwrap_or_default())))
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        let block = self.eth.block_by_hash(block_hash, true);
+        let receipts = self.eth.block_receipts(BlockId::Hash(block_hash.into()));
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+        Ok(block.map(|block| block_details(block, receipts.unwrap_or_default())))
     }
 
     /// Handler for `getBlockTransactions`
@@ -153,25 +282,10 @@ where
         }
 
         // Crop receipts and transform them into OtsTransactionReceipt
-        let timestamp = Some(block.header.timestamp);
+        let timestamp = block.header.timestamp;
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt
-                        .inner
-                        .receipt
-                        .status
-                        .as_eip658()
-                        .expect("ETH API returned pre-EIP-658 status"),
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| ots_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
@@ -179,34 +293,306 @@ where
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // the search starts at the tip for block number 0, and before the given block otherwise
+        let (end, first_page) = match block_number {
+            BlockNumberOrTag::Number(num) if num > 0 => (num, false),
+            _ => (self.eth.block_number()?.saturating_to::<u64>().saturating_add(1), true),
+        };
+        let (matches, last_page) =
+            self.search_transactions(address, (0..end).rev(), page_size).await?;
+        let (txs, receipts) = matches.into_iter().unzip();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // the search starts at genesis for block number 0, and after the given block otherwise
+        let tip = self.eth.block_number()?.saturating_to::<u64>();
+        let (start, last_page) = match block_number {
+            BlockNumberOrTag::Number(0) | BlockNumberOrTag::Earliest => (0, true),
+            BlockNumberOrTag::Number(num) => (num.saturating_add(1), false),
+            _ => (tip.saturating_add(1), false),
+        };
+        let (matches, first_page) =
+            self.search_transactions(address, start..=tip, page_size).await?;
+        let (txs, receipts) = matches.into_iter().unzip();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
-    ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        sender: Address,
+        nonce: u64,
+    ) -> RpcResult<Option<TxHash>> {
+        let highest =
+            EthApiServer::transaction_count(&self.eth, sender, None).await?.saturating_to::<u64>();
+        // the transaction is pending or doesn't exist
+        if nonce >= highest {
+            return Ok(None)
+        }
+
+        // look the transaction up in the sender nonce index, if it is enabled
+        if let Some(hash) = self.eth.transaction_hash_by_sender_and_nonce(sender, nonce).await? {
+            return Ok(Some(hash))
+        }
+
+        // find the first block after which the nonce of the sender is above the given nonce
+        let tip = self.eth.block_number()?.saturating_to::<u64>();
+        let num = binary_search(1, tip, |mid| async move {
+            let nonce_at = EthApiServer::transaction_count(&self.eth, sender, Some(mid.into()))
+                .await?
+                .saturating_to::<u64>();
+            Ok(nonce_at > nonce)
+        })
+        .await?;
+
+        let block = self
+            .eth
+            .block_by_id_with_senders(num.into())
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        Ok(block
+            .transactions_with_sender()
+            .find(|(from, tx)| **from == sender && tx.nonce() == nonce)
+            .map(|(_, tx)| tx.hash()))
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        if !self.has_code(address, None).await? {
+            return Ok(None)
+        }
+        // contracts of the genesis state have no creator
+        if self.has_code(address, Some(BlockNumberOrTag::Earliest.into())).await? {
+            return Ok(None)
+        }
+
+        // find the block in which the contract was created
+        let tip = self.eth.block_number()?.saturating_to::<u64>();
+        let num = binary_search(1, tip, |mid| self.has_code(address, Some(mid.into()))).await?;
+
+        // find the transaction and the caller that created the contract
+        let creation = self
+            .eth
+            .trace_block_with(
+                num.into(),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let creator = inspector
+                        .into_traces()
+                        .into_nodes()
+                        .into_iter()
+                        .find(|node| {
+                            node.trace.kind.is_any_create() && node.trace.address == address
+                        })
+                        .map(|node| node.trace.caller);
+                    Ok(tx_info.hash.zip(creator))
+                },
+            )
+            .await?
+            .and_then(|creations| creations.into_iter().flatten().next());
+        let Some((tx_hash, creator)) = creation else { return Ok(None) };
+
+        let tx = EthApiServer::transaction_by_hash(&self.eth, tx_hash)
+            .await?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        Ok(Some(ContractCreator { tx, creator }))
+    }
+}
+
+/// Returns the details of the block with the total fees paid by its transactions.
+///
+/// Note: the issuance is not computed and always zero.
+fn block_details(block: RichBlock, receipts: Vec<AnyTransactionReceipt>) -> BlockDetails {
+    let total_fees = receipts
+        .iter()
+        .map(|receipt| {
+            U256::from(receipt.inner.gas_used) * U256::from(receipt.inner.effective_gas_price)
+        })
+        .sum();
+    BlockDetails { total_fees, ..BlockDetails::from(block) }
+}
+
+/// Converts the receipt into the receipt of the Otterscan API, which omits the logs.
+fn ots_receipt(receipt: AnyTransactionReceipt, timestamp: u64) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt
+            .inner
+            .receipt
+            .status
+            .as_eip658()
+            .expect("ETH API returned pre-EIP-658 status"),
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+
+    OtsTransactionReceipt { receipt, timestamp: Some(timestamp) }
+}
+
+/// Performs a binary search over the blocks `low..=high` and returns the lowest block for which
+/// the check holds, or `high` if it holds for none.
+///
+/// The check must hold for all blocks above a block for which it holds.
+async fn binary_search<F, Fut>(low: u64, high: u64, check: F) -> RpcResult<u64>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = RpcResult<bool>>,
+{
+    let (mut low, mut high) = (low, high);
+    let mut num = high;
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        if check(mid).await? {
+            num = mid;
+            if mid == 0 {
+                break
+            }
+            high = mid - 1;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(num)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, EthApi, FeeHistoryCache,
+        FeeHistoryCacheConfig,
+    };
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, Block, Header, Receipt, Transaction, TxKind, TxLegacy,
+        TxType,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn search_transactions() {
+        let mut rng = generators::rng();
+        let provider = MockEthProvider::default();
+
+        // blocks 1 and 3 each contain a transfer to a different recipient
+        let recipients = [Address::with_last_byte(1), Address::with_last_byte(2)];
+        let mut senders = Vec::new();
+        let mut hashes = Vec::new();
+        let mut parent_hash = B256::ZERO;
+        for number in 0..4 {
+            let recipient = match number {
+                1 => Some(recipients[0]),
+                3 => Some(recipients[1]),
+                _ => None,
+            };
+            let body = recipient
+                .map(|to| {
+                    let tx = Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        gas_price: 1,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(to),
+                        ..Default::default()
+                    });
+                    let tx = sign_tx_with_random_key_pair(&mut rng, tx);
+                    senders.push(tx.recover_signer().unwrap());
+                    hashes.push(tx.hash());
+                    tx
+                })
+                .into_iter()
+                .collect::<Vec<_>>();
+            let receipts = body
+                .iter()
+                .map(|_| Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 21_000,
+                    ..Default::default()
+                })
+                .collect();
+            let block = Block {
+                header: Header { number, parent_hash, ..Default::default() },
+                body,
+                ..Default::default()
+            };
+            parent_hash = block.header.hash_slow();
+            provider.add_block(parent_hash, block);
+            provider.add_receipts(parent_hash, receipts);
+        }
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+        let ots = OtterscanApi::new(eth_api.clone());
+
+        // the recipient of block 1 is found when searching backwards from the tip
+        let res = ots
+            .search_transactions_before(recipients[0], BlockNumberOrTag::Number(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(res.txs.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![hashes[0]]);
+        assert_eq!(res.txs[0].block_number, Some(1));
+        assert_eq!(res.receipts.len(), 1);
+        assert!(res.first_page);
+        assert!(res.last_page);
+
+        // the sender of block 3 is found when searching forwards from genesis
+        let res = ots
+            .search_transactions_after(senders[1], BlockNumberOrTag::Number(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(res.txs.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![hashes[1]]);
+        assert!(res.first_page);
+        assert!(res.last_page);
+
+        // the search stops after the configured number of blocks
+        let ots = OtterscanApi::new(eth_api).with_max_search_blocks(2);
+        let res = ots
+            .search_transactions_before(recipients[1], BlockNumberOrTag::Number(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(res.txs.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![hashes[1]]);
+        assert!(!res.last_page);
+        assert!(ots
+            .search_transactions_before(recipients[0], BlockNumberOrTag::Number(0), 10)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn binary_search_lowest_block() {
+        for target in 1..=10 {
+            let num = binary_search(1, 10, |mid| async move { Ok(mid >= target) }).await.unwrap();
+            assert_eq!(num, target);
+        }
+
+        let num = binary_search(1, 10, |_| async { Ok(false) }).await.unwrap();
+        assert_eq!(num, 10);
     }
 }
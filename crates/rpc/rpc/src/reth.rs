@@ -1,13 +1,25 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::{
+    eth::error::{EthApiError, EthResult},
+    EthApi,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
-use reth_rpc_api::RethApiServer;
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{Address, BlockId, B256, U256};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+};
+use reth_rpc_api::{RethApiServer, RethBlobsApiServer, RethSimulateApiServer};
+use reth_rpc_types::{
+    state::StateOverride, BlobSidecar, Bundle, SimulatedCallResult, StateContext,
+};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::TransactionPool;
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
+use tracing::trace;
 
 /// `reth` API implementation.
 ///
@@ -115,3 +127,77 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth` API implementation of the call simulation methods, which are served by the [`EthApi`].
+#[derive(Debug, Clone)]
+pub struct RethSimulate<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethSimulate<Eth> {
+    /// Create a new instance of the [`RethSimulate`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool, Network, EvmConfig> RethSimulateApiServer
+    for RethSimulate<EthApi<Provider, Pool, Network, EvmConfig>>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Handler for `reth_simulateCalls`
+    async fn simulate_calls(
+        &self,
+        bundle: Bundle,
+        state_context: Option<StateContext>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<Vec<SimulatedCallResult>> {
+        trace!(target: "rpc::reth", ?bundle, ?state_context, ?state_override, "Serving reth_simulateCalls");
+        Ok(self.eth_api.simulate_calls(bundle, state_context, state_override).await?)
+    }
+}
+
+/// `reth` API implementation of the blob retrieval methods, which are served by the [`EthApi`].
+#[derive(Debug, Clone)]
+pub struct RethBlobs<Eth> {
+    eth_api: Eth,
+}
+
+impl<Eth> RethBlobs<Eth> {
+    /// Create a new instance of the [`RethBlobs`]
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool, Network, EvmConfig> RethBlobsApiServer
+    for RethBlobs<EthApi<Provider, Pool, Network, EvmConfig>>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Handler for `reth_getBlobSidecars`
+    async fn blob_sidecars(&self, block_id: BlockId) -> RpcResult<Option<Vec<BlobSidecar>>> {
+        trace!(target: "rpc::reth", ?block_id, "Serving reth_getBlobSidecars");
+        Ok(self.eth_api.blob_sidecars(block_id).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecarByVersionedHash`
+    async fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<BlobSidecar>> {
+        trace!(target: "rpc::reth", ?versioned_hash, "Serving reth_getBlobSidecarByVersionedHash");
+        Ok(self.eth_api.blob_sidecar_by_versioned_hash(versioned_hash).await?)
+    }
+}
@@ -1,16 +1,24 @@
-use crate::eth::{
-    error::{EthApiError, EthResult},
-    revm_utils::prepare_call_env,
-    utils::recover_raw_transaction,
-    EthTransactions,
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        revm_utils::prepare_call_env,
+        utils::recover_raw_transaction,
+        EthTransactions,
+    },
+    TraceIndex,
 };
 use async_trait::async_trait;
+use futures::{FutureExt, Stream, StreamExt};
 use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{
     base_block_reward, base_block_reward_pre_merge, block_reward, ommer_reward,
 };
-use reth_primitives::{revm::env::tx_env_with_recovered, BlockId, Bytes, Header, B256, U256};
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Address, BlockId, BlockNumber, Bytes, Header, B256, U256,
+};
+use reth_provider::{
+    BlockReader, CanonStateNotification, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
@@ -21,7 +29,7 @@ use reth_rpc_types::{
         parity::*,
         tracerequest::TraceCallRequest,
     },
-    BlockError, BlockOverrides, Index, TransactionRequest,
+    BlockOverrides, Index, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
@@ -34,12 +42,18 @@ use revm_inspectors::{
 };
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::debug;
+
+/// The maximum number of blocks `trace_filter` traces per request.
+pub const MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
 /// `trace` API implementation.
 ///
 /// This type provides the functionality for handling `trace` related requests.
 pub struct TraceApi<Provider, Eth> {
     inner: Arc<TraceApiInner<Provider, Eth>>,
+    /// Lets `trace_filter` skip blocks that can't contain matching traces.
+    trace_index: Option<TraceIndex>,
 }
 
 // === impl TraceApi ===
@@ -53,7 +67,13 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     /// Create a new instance of the [`TraceApi`]
     pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
         let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
-        Self { inner }
+        Self { inner, trace_index: None }
+    }
+
+    /// Sets the index of the addresses in the traces of blocks, used by `trace_filter`.
+    pub fn with_trace_index(mut self, trace_index: Option<TraceIndex>) -> Self {
+        self.trace_index = trace_index;
+        self
     }
 
     /// Acquires a permit to execute a tracing call.
@@ -232,18 +252,21 @@ where
         Ok(self.trace_transaction(hash).await?.and_then(|traces| traces.into_iter().nth(index)))
     }
 
-    /// Returns all transaction traces that match the given filter.
+    /// Returns all traces that match the given filter.
+    ///
+    /// A trace matches if its sender or recipient matches the addresses of the filter, this
+    /// includes the internal calls of transactions. The matching traces are paginated with the
+    /// `after` and `count` fields of the filter.
     ///
-    /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
-    /// the filter.
+    /// If a [`TraceIndex`] is configured, indexed blocks that can't contain a matching trace are
+    /// skipped without executing them, so the range may span as many blocks as the index holds,
+    /// as long as at most [`MAX_TRACE_FILTER_BLOCKS`] of them need to be traced.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
-        let matcher = filter.matcher();
-        let TraceFilter { from_block, to_block, .. } = filter;
-        let start = from_block.unwrap_or(0);
-        let end = if let Some(to_block) = to_block {
+        let start = filter.from_block.unwrap_or(0);
+        let end = if let Some(to_block) = filter.to_block {
             to_block
         } else {
             self.provider().best_block_number()?
@@ -255,83 +278,67 @@ where
             ))
         }
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
+        // ensure that the range is not too large, since we need to trace the blocks in the range
         let distance = end.saturating_sub(start);
-        if distance > 100 {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            ))
+        let max_distance = self.trace_index.as_ref().map_or(MAX_TRACE_FILTER_BLOCKS, |index| {
+            u64::from(index.max_blocks()).max(MAX_TRACE_FILTER_BLOCKS)
+        });
+        if distance > max_distance {
+            return Err(EthApiError::InvalidParams(format!(
+                "Block range too large; currently limited to {max_distance} blocks"
+            )))
         }
 
-        // fetch all blocks in that range
-        let blocks = self.provider().block_range(start..=end)?;
-
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
-        for block in &blocks {
-            let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
-            for (tx_idx, tx) in block.body.iter().enumerate() {
-                let from = tx.recover_signer_unchecked().ok_or(BlockError::InvalidSignature)?;
-                let to = tx.to();
-                if matcher.matches(from, to) {
-                    let idx = tx_idx as u64;
-                    transaction_indices.insert(idx);
-                    highest_matching_index = idx;
-                }
-            }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
-            }
-        }
-
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, res, _, _| {
-                    if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
-                            // only record traces for relevant transactions
-                            return Ok(None)
-                        }
-                    }
-                    let traces = inspector
-                        .with_transaction_gas_used(res.gas_used())
-                        .into_parity_builder()
-                        .into_localized_transaction_traces(tx_info);
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
+        // find the blocks that may contain matching traces
+        let hashes = self.provider().canonical_hashes_range(start, end + 1)?;
+        let may_match = |number, hash| {
+            self.trace_index.as_ref().and_then(|index| index.may_match(number, hash, &filter))
+        };
+        let target_blocks = (start..=end)
+            .zip(hashes)
+            .filter(|(number, hash)| may_match(*number, *hash) != Some(false))
+            .collect::<Vec<_>>();
+        if self.trace_index.is_some() && target_blocks.len() as u64 > MAX_TRACE_FILTER_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "Too many blocks to trace; currently limited to {MAX_TRACE_FILTER_BLOCKS} blocks"
+            )))
         }
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let mut all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect::<Vec<_>>();
+        // trace all relevant blocks, including their reward traces
+        let block_traces = futures::future::try_join_all(
+            target_blocks.iter().map(|(number, _)| self.trace_block((*number).into())),
+        )
+        .await?;
 
-        // add reward traces for all blocks
-        for block in &blocks {
-            if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
-                all_traces.extend(self.extract_reward_traces(
-                    &block.header,
-                    &block.ommers,
-                    base_block_reward,
-                ));
-            } else {
-                // no block reward, means we're past the Paris hardfork and don't expect any rewards
-                // because the blocks in ascending order
-                break
+        let matcher = filter.matcher();
+        let mut matching_traces = Vec::new();
+        for ((number, hash), traces) in target_blocks.into_iter().zip(block_traces) {
+            let Some(traces) = traces else { continue };
+            if let Some(index) = &self.trace_index {
+                index.insert(number, hash, indexed_addresses(&traces));
             }
+            matching_traces.extend(traces.into_iter().filter(|trace| {
+                let (from, to) = trace_addresses(&trace.trace);
+                matcher.matches(from, to)
+            }));
         }
 
-        Ok(all_traces)
+        let after = filter.after.unwrap_or_default() as usize;
+        let count = filter.count.map_or(usize::MAX, |count| count as usize);
+        Ok(matching_traces.into_iter().skip(after).take(count).collect())
+    }
+
+    /// Traces the block and indexes the addresses of its traces, unless it's already indexed.
+    async fn index_block(&self, index: &TraceIndex, number: BlockNumber, hash: B256) {
+        if index.contains(number, hash) {
+            return
+        }
+        let _permit = self.acquire_trace_permit().await;
+        match self.trace_block(hash.into()).await {
+            Ok(Some(traces)) => index.insert(number, hash, indexed_addresses(&traces)),
+            Ok(None) => {}
+            Err(err) => debug!(target: "rpc::trace", %err, number, "Failed to index block traces"),
+        }
     }
 
     /// Returns all traces for the given transaction hash
@@ -670,7 +677,7 @@ impl<Provider, Eth> std::fmt::Debug for TraceApi<Provider, Eth> {
 }
 impl<Provider, Eth> Clone for TraceApi<Provider, Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), trace_index: self.trace_index.clone() }
     }
 }
 
@@ -683,6 +690,77 @@ struct TraceApiInner<Provider, Eth> {
     blocking_task_guard: BlockingTaskGuard,
 }
 
+/// Indexes the traces of new canonical blocks, after backfilling the most recent blocks that fit
+/// into the [`TraceIndex`] of the [`TraceApi`].
+///
+/// New blocks are indexed before the backfill continues. Does nothing if the [`TraceApi`] has no
+/// index.
+pub async fn trace_index_new_blocks_task<Provider, Eth, St>(
+    trace_api: TraceApi<Provider, Eth>,
+    mut events: St,
+) where
+    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Eth: EthTransactions + 'static,
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+{
+    let Some(index) = trace_api.trace_index.clone() else { return };
+
+    let best_block = trace_api.provider().best_block_number().unwrap_or_default();
+    let oldest_block = (best_block + 1).saturating_sub(u64::from(index.max_blocks()));
+    let mut next_backfill = Some(best_block);
+
+    loop {
+        let event = match next_backfill {
+            Some(number) => match events.next().now_or_never() {
+                Some(event) => event,
+                None => {
+                    if let Ok(Some(hash)) = trace_api.provider().block_hash(number) {
+                        trace_api.index_block(&index, number, hash).await;
+                    }
+                    next_backfill = number.checked_sub(1).filter(|number| *number >= oldest_block);
+                    continue
+                }
+            },
+            None => events.next().await,
+        };
+        let Some(event) = event else {
+            // the stream ended, we are done
+            break
+        };
+        for block in event.committed().blocks_iter() {
+            trace_api.index_block(&index, block.number, block.hash()).await;
+        }
+    }
+}
+
+/// Returns the addresses that appear in the traces, as indexed by the [`TraceIndex`].
+fn indexed_addresses(traces: &[LocalizedTransactionTrace]) -> impl Iterator<Item = Address> + '_ {
+    traces.iter().flat_map(|trace| {
+        let (from, to) = trace_addresses(&trace.trace);
+        std::iter::once(from).chain(to)
+    })
+}
+
+/// Returns the sender and recipient of the trace, as matched by `trace_filter`.
+///
+/// Rewards have no sender, the zero address is returned instead.
+fn trace_addresses(trace: &TransactionTrace) -> (Address, Option<Address>) {
+    match &trace.action {
+        Action::Call(call) => (call.from, Some(call.to)),
+        Action::Create(create) => {
+            let created = match &trace.result {
+                Some(TraceOutput::Create(output)) => Some(output.address),
+                _ => None,
+            };
+            (create.from, created)
+        }
+        Action::Selfdestruct(selfdestruct) => {
+            (selfdestruct.address, Some(selfdestruct.refund_address))
+        }
+        Action::Reward(reward) => (Address::ZERO, Some(reward.author)),
+    }
+}
+
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
 /// beneficiary.
 fn reward_trace(header: &Header, reward: RewardAction) -> LocalizedTransactionTrace {
@@ -700,3 +778,50 @@ fn reward_trace(header: &Header, reward: RewardAction) -> LocalizedTransactionTr
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call_trace(
+        from: Address,
+        to: Address,
+        trace_address: Vec<usize>,
+    ) -> LocalizedTransactionTrace {
+        LocalizedTransactionTrace {
+            block_hash: Some(B256::with_last_byte(1)),
+            block_number: Some(1),
+            transaction_hash: Some(B256::with_last_byte(2)),
+            transaction_position: Some(0),
+            trace: TransactionTrace {
+                action: Action::Call(CallAction { from, to, ..Default::default() }),
+                trace_address,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn filter_matches_internal_calls() {
+        let (alice, contract, carol) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let traces =
+            vec![call_trace(alice, contract, vec![]), call_trace(contract, carol, vec![0])];
+        let index = TraceIndex::new(1);
+        index.insert(1, B256::with_last_byte(1), indexed_addresses(&traces));
+
+        let filter = TraceFilter::default().to_address(vec![carol]);
+        assert_eq!(index.may_match(1, B256::with_last_byte(1), &filter), Some(true));
+
+        let matcher = filter.matcher();
+        let matching = traces
+            .into_iter()
+            .filter(|trace| {
+                let (from, to) = trace_addresses(&trace.trace);
+                matcher.matches(from, to)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].trace.trace_address, vec![0]);
+    }
+}
@@ -0,0 +1,136 @@
+//! Index of the addresses in the traces of blocks, used by `trace_filter` to skip blocks.
+
+use parking_lot::Mutex;
+use reth_primitives::{Address, BlockNumber, Bloom, BloomInput, B256};
+use reth_rpc_types::trace::filter::{TraceFilter, TraceFilterMode};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Bloom filters of the addresses that appear in the parity traces of blocks.
+///
+/// The index is filled in the background with the most recent canonical blocks, see
+/// [`trace_index_new_blocks_task`](crate::trace_index_new_blocks_task), and `trace_filter` skips
+/// indexed blocks whose bloom rules out the addresses of the filter without executing them. An
+/// entry is only used while its block is canonical.
+///
+/// The index is shared by all clones.
+#[derive(Clone)]
+pub struct TraceIndex {
+    /// The indexed blocks by number.
+    blocks: Arc<Mutex<LruMap<BlockNumber, TraceIndexEntry, ByLength>>>,
+    /// The maximum number of indexed blocks.
+    max_blocks: u32,
+}
+
+impl TraceIndex {
+    /// Creates a new index that holds at most `max_blocks` blocks.
+    pub fn new(max_blocks: u32) -> Self {
+        Self { blocks: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_blocks)))), max_blocks }
+    }
+
+    /// Returns the maximum number of indexed blocks.
+    pub const fn max_blocks(&self) -> u32 {
+        self.max_blocks
+    }
+
+    /// Indexes the addresses that appear in the traces of the block.
+    pub fn insert(
+        &self,
+        block_number: BlockNumber,
+        block_hash: B256,
+        addresses: impl IntoIterator<Item = Address>,
+    ) {
+        let mut bloom = Bloom::default();
+        for address in addresses {
+            bloom.accrue(BloomInput::Raw(address.as_slice()));
+        }
+        self.blocks.lock().insert(block_number, TraceIndexEntry { block_hash, bloom });
+    }
+
+    /// Returns true if the block with this hash is indexed.
+    pub fn contains(&self, block_number: BlockNumber, block_hash: B256) -> bool {
+        self.blocks.lock().peek(&block_number).is_some_and(|entry| entry.block_hash == block_hash)
+    }
+
+    /// Returns whether the traces of the block may match the addresses of the filter.
+    ///
+    /// Returns `None` if the block with this hash isn't indexed.
+    pub fn may_match(
+        &self,
+        block_number: BlockNumber,
+        block_hash: B256,
+        filter: &TraceFilter,
+    ) -> Option<bool> {
+        let mut blocks = self.blocks.lock();
+        let entry = blocks.get(&block_number).filter(|entry| entry.block_hash == block_hash)?;
+        Some(bloom_matches(&entry.bloom, filter))
+    }
+}
+
+impl Debug for TraceIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TraceIndex")
+            .field("blocks", &self.blocks.lock().len())
+            .field("max_blocks", &self.max_blocks)
+            .finish()
+    }
+}
+
+/// The bloom of the addresses in the traces of a block.
+struct TraceIndexEntry {
+    /// The hash of the indexed block.
+    block_hash: B256,
+    /// The bloom of the addresses.
+    bloom: Bloom,
+}
+
+/// Returns whether the bloom may contain the addresses required by the filter.
+///
+/// Senders and recipients share the bloom, so this is checked per address set only.
+fn bloom_matches(bloom: &Bloom, filter: &TraceFilter) -> bool {
+    let contains_any = |addresses: &[Address]| {
+        addresses.iter().any(|address| bloom.contains_input(BloomInput::Raw(address.as_slice())))
+    };
+    match (filter.from_address.is_empty(), filter.to_address.is_empty()) {
+        (true, true) => true,
+        (false, true) => contains_any(&filter.from_address),
+        (true, false) => contains_any(&filter.to_address),
+        (false, false) => match filter.mode {
+            TraceFilterMode::Union => {
+                contains_any(&filter.from_address) || contains_any(&filter.to_address)
+            }
+            TraceFilterMode::Intersection => {
+                contains_any(&filter.from_address) && contains_any(&filter.to_address)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_blocks_without_filter_addresses() {
+        let (alice, bob, carol) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+        let index = TraceIndex::new(2);
+        let hash = B256::with_last_byte(1);
+        index.insert(1, hash, [alice, bob]);
+        assert!(index.contains(1, hash));
+
+        let filter = TraceFilter::default().from_address(vec![carol]);
+        assert_eq!(index.may_match(1, hash, &filter), Some(false));
+        let filter = filter.to_address(vec![bob]);
+        assert_eq!(index.may_match(1, hash, &filter), Some(true));
+        let filter = filter.mode(TraceFilterMode::Intersection);
+        assert_eq!(index.may_match(1, hash, &filter), Some(false));
+
+        // the block was reorged
+        assert_eq!(index.may_match(1, B256::with_last_byte(2), &filter), None);
+        assert_eq!(index.may_match(2, hash, &filter), None);
+    }
+}
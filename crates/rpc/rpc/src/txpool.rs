@@ -1,14 +1,26 @@
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult as Result;
-use reth_primitives::Address;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::RpcResult as Result, server::SubscriptionMessage, PendingSubscriptionSink,
+    SubscriptionSink,
+};
+use reth_primitives::{Address, TxHash};
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
     txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
-    Transaction,
+    Transaction, TxpoolDropReason, TxpoolEvent, TxpoolNonceGap, TxpoolQuery, TxpoolSenderReport,
+    TxpoolStuckReason, TxpoolStuckTransaction, TxpoolSubpool,
+};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use reth_transaction_pool::{
+    AllPoolTransactions, AllTransactionsEvents, FullTransactionEvent, PoolTransaction,
+    SenderReport, StuckReason, TransactionPool, ValidPoolTransaction,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
-use std::collections::BTreeMap;
-use tracing::trace;
+use tracing::{debug, trace};
 
 /// `txpool` API implementation.
 ///
@@ -17,12 +29,22 @@ use tracing::trace;
 pub struct TxPoolApi<Pool> {
     /// An interface to interact with the pool
     pool: Pool,
+    /// The type that's used to spawn subscription tasks, [`tokio::task::spawn`] if not set.
+    subscription_task_spawner: Option<Box<dyn TaskSpawner>>,
 }
 
 impl<Pool> TxPoolApi<Pool> {
     /// Creates a new instance of `TxpoolApi`.
+    ///
+    /// Subscription tasks are spawned via [`tokio::task::spawn`]
     pub const fn new(pool: Pool) -> Self {
-        Self { pool }
+        Self { pool, subscription_task_spawner: None }
+    }
+
+    /// Creates a new instance of `TxpoolApi` that spawns subscription tasks with the given
+    /// spawner.
+    pub fn with_spawner(pool: Pool, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { pool, subscription_task_spawner: Some(subscription_task_spawner) }
     }
 }
 
@@ -30,7 +52,57 @@ impl<Pool> TxPoolApi<Pool>
 where
     Pool: TransactionPool + 'static,
 {
-    fn content(&self) -> TxpoolContent {
+    /// Returns the transactions of the pool that match the query, if any.
+    fn transactions(&self, query: Option<TxpoolQuery>) -> AllPoolTransactions<Pool::Transaction> {
+        let Some(query) = query else { return self.pool.all_transactions() };
+        let AllPoolTransactions { pending, queued } = if query.senders.is_empty() {
+            self.pool.all_transactions()
+        } else {
+            self.transactions_of(&query.senders)
+        };
+
+        let select = |txs: Vec<Arc<ValidPoolTransaction<Pool::Transaction>>>, subpool| {
+            if !query.includes_subpool(subpool) {
+                return Vec::new()
+            }
+            let mut txs = txs
+                .into_iter()
+                .filter(|tx| query.matches(&tx.sender(), tx.tx_type()))
+                .collect::<Vec<_>>();
+            txs.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+            txs
+        };
+        let pending = select(pending, TxpoolSubpool::Pending);
+        let queued = select(queued, TxpoolSubpool::Queued);
+
+        // the page spans the pending and then the queued transactions
+        let mut skip = usize::try_from(query.offset).unwrap_or(usize::MAX);
+        let mut take =
+            query.limit.map_or(usize::MAX, |limit| usize::try_from(limit).unwrap_or(usize::MAX));
+        AllPoolTransactions {
+            pending: page(pending, &mut skip, &mut take),
+            queued: page(queued, &mut skip, &mut take),
+        }
+    }
+
+    /// Returns the transactions of the given senders, which are looked up by sender instead of
+    /// taken from all transactions of the pool.
+    fn transactions_of(&self, senders: &[Address]) -> AllPoolTransactions<Pool::Transaction> {
+        let mut transactions = AllPoolTransactions::default();
+        for sender in senders.iter().copied().collect::<HashSet<_>>() {
+            let Some(report) = self.pool.sender_report(sender) else { continue };
+            for (tx, subpool) in report.transactions {
+                if subpool.is_pending() {
+                    transactions.pending.push(tx);
+                } else {
+                    transactions.queued.push(tx);
+                }
+            }
+        }
+        transactions
+    }
+
+    fn content(&self, query: Option<TxpoolQuery>) -> TxpoolContent {
         #[inline]
         fn insert<T: PoolTransaction>(
             tx: &T,
@@ -42,7 +114,7 @@ where
             );
         }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let AllPoolTransactions { pending, queued } = self.transactions(query);
 
         let mut content = TxpoolContent::default();
         for pending in pending {
@@ -56,6 +128,16 @@ where
     }
 }
 
+/// Returns the page of the transactions, and advances the number of transactions that are still
+/// to be skipped and taken.
+fn page<T>(txs: Vec<T>, skip: &mut usize, take: &mut usize) -> Vec<T> {
+    let skipped = (*skip).min(txs.len());
+    *skip -= skipped;
+    let page = txs.into_iter().skip(skipped).take(*take).collect::<Vec<_>>();
+    *take -= page.len();
+    page
+}
+
 #[async_trait]
 impl<Pool> TxPoolApiServer for TxPoolApi<Pool>
 where
@@ -78,8 +160,8 @@ where
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect) for more details
     ///
     /// Handler for `txpool_inspect`
-    async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
-        trace!(target: "rpc::eth", "Serving txpool_inspect");
+    async fn txpool_inspect(&self, query: Option<TxpoolQuery>) -> Result<TxpoolInspect> {
+        trace!(target: "rpc::eth", ?query, "Serving txpool_inspect");
 
         #[inline]
         fn insert<T: PoolTransaction>(
@@ -99,7 +181,7 @@ where
             );
         }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let AllPoolTransactions { pending, queued } = self.transactions(query);
 
         Ok(TxpoolInspect {
             pending: pending.iter().fold(Default::default(), |mut acc, tx| {
@@ -120,7 +202,8 @@ where
     /// Handler for `txpool_contentFrom`
     async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().remove_from(&from))
+        let query = TxpoolQuery { senders: vec![from], ..Default::default() };
+        Ok(self.content(Some(query)).remove_from(&from))
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -128,9 +211,141 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> Result<TxpoolContent> {
-        trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.content())
+    async fn txpool_content(&self, query: Option<TxpoolQuery>) -> Result<TxpoolContent> {
+        trace!(target: "rpc::eth", ?query, "Serving txpool_content");
+        Ok(self.content(query))
+    }
+
+    /// Handler for `txpool_senderReport`
+    async fn txpool_sender_report(&self, sender: Address) -> Result<Option<TxpoolSenderReport>> {
+        trace!(target: "rpc::eth", ?sender, "Serving txpool_senderReport");
+        let Some(SenderReport { state_nonce, transactions, nonce_gaps, stuck }) =
+            self.pool.sender_report(sender)
+        else {
+            return Ok(None)
+        };
+
+        let (pending, queued): (Vec<_>, Vec<_>) =
+            transactions.into_iter().partition(|(_, subpool)| subpool.is_pending());
+        let stuck = stuck.map(|stuck| TxpoolStuckTransaction {
+            hash: *stuck.transaction.hash(),
+            nonce: stuck.transaction.nonce(),
+            reason: match stuck.reason {
+                StuckReason::NonceGap => TxpoolStuckReason::NonceGap,
+                StuckReason::InsufficientBalance => TxpoolStuckReason::InsufficientBalance,
+                StuckReason::ExceedsBlockGasLimit => TxpoolStuckReason::ExceedsBlockGasLimit,
+                StuckReason::FeeCapBelowBaseFee => TxpoolStuckReason::FeeCapBelowBaseFee,
+                StuckReason::BlobFeeCapBelowBlobFee => TxpoolStuckReason::BlobFeeCapBelowBlobFee,
+            },
+            missing_nonce: stuck.missing_nonce,
+            min_max_fee_per_gas: stuck.min_replacement_max_fee_per_gas,
+            min_max_priority_fee_per_gas: stuck.min_replacement_max_priority_fee_per_gas,
+            min_max_fee_per_blob_gas: stuck.min_replacement_max_fee_per_blob_gas,
+        });
+
+        Ok(Some(TxpoolSenderReport {
+            sender,
+            on_chain_nonce: state_nonce,
+            pending_nonces: pending.iter().map(|(tx, _)| tx.nonce()).collect(),
+            queued_nonces: queued.iter().map(|(tx, _)| tx.nonce()).collect(),
+            nonce_gaps: nonce_gaps
+                .into_iter()
+                .map(|gap| TxpoolNonceGap { from: *gap.start(), to: *gap.end() })
+                .collect(),
+            stuck,
+        }))
+    }
+
+    /// Handler for `txpool_subscribe`
+    async fn txpool_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        // subscribe before reading the queued transactions, so that no promotion is missed
+        let events = self.pool.all_transactions_event_listener();
+        let queued = self.pool.queued_transactions().iter().map(|tx| *tx.hash()).collect();
+        let sink = pending.accept().await?;
+        let fut = Box::pin(pipe_events(sink, events, TxpoolEventTracker { queued }));
+        match &self.subscription_task_spawner {
+            Some(spawner) => spawner.spawn(fut),
+            None => TokioTaskExecutor::default().spawn(fut),
+        };
+
+        Ok(())
+    }
+}
+
+/// Converts the events of the pool into [`TxpoolEvent`]s.
+#[derive(Debug, Default)]
+struct TxpoolEventTracker {
+    /// The hashes of the queued transactions, whose move to the pending subpool is a promotion.
+    queued: HashSet<TxHash>,
+}
+
+impl TxpoolEventTracker {
+    /// Returns the [`TxpoolEvent`] of the pool event, if any.
+    fn on_event<T: PoolTransaction>(
+        &mut self,
+        event: FullTransactionEvent<T>,
+    ) -> Option<TxpoolEvent> {
+        let event = match event {
+            FullTransactionEvent::Pending(hash) => {
+                if self.queued.remove(&hash) {
+                    TxpoolEvent::Promoted { hash }
+                } else {
+                    TxpoolEvent::Added { hash, subpool: TxpoolSubpool::Pending }
+                }
+            }
+            FullTransactionEvent::Queued(hash) => {
+                self.queued.insert(hash);
+                TxpoolEvent::Added { hash, subpool: TxpoolSubpool::Queued }
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                let hash = *transaction.hash();
+                self.queued.remove(&hash);
+                TxpoolEvent::Replaced { hash, replaced_by }
+            }
+            FullTransactionEvent::Discarded(hash) => {
+                self.queued.remove(&hash);
+                TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Discarded }
+            }
+            FullTransactionEvent::Invalid(hash) => {
+                self.queued.remove(&hash);
+                TxpoolEvent::Dropped { hash, reason: TxpoolDropReason::Invalid }
+            }
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                self.queued.remove(&tx_hash);
+                TxpoolEvent::Mined { hash: tx_hash, block_hash }
+            }
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+        Some(event)
+    }
+}
+
+/// Pipes the events of the pool to the subscription sink, until either of them is closed.
+async fn pipe_events<T: PoolTransaction>(
+    sink: SubscriptionSink,
+    mut events: AllTransactionsEvents<T>,
+    mut tracker: TxpoolEventTracker,
+) {
+    loop {
+        let event = tokio::select! {
+            _ = sink.closed() => break,
+            event = events.next() => event,
+        };
+        let Some(event) = event else { break };
+        let Some(event) = tracker.on_event(event) else { continue };
+        let msg = match SubscriptionMessage::from_json(&event) {
+            Ok(msg) => msg,
+            Err(err) => {
+                debug!(target: "rpc::txpool", %err, "Failed to serialize txpool event");
+                break
+            }
+        };
+        if sink.send(msg).await.is_err() {
+            break
+        }
     }
 }
 
@@ -139,3 +354,79 @@ impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::B256;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    #[test]
+    fn page_spans_subpools() {
+        let (mut skip, mut take) = (2, 3);
+        assert_eq!(page(vec![1, 2, 3], &mut skip, &mut take), vec![3]);
+        assert_eq!(page(vec![4, 5, 6], &mut skip, &mut take), vec![4, 5]);
+        assert_eq!((skip, take), (0, 0));
+
+        let (mut skip, mut take) = (5, usize::MAX);
+        assert!(page(vec![1, 2, 3], &mut skip, &mut take).is_empty());
+        assert_eq!(page(vec![4, 5, 6], &mut skip, &mut take), vec![6]);
+    }
+
+    #[tokio::test]
+    async fn queries_transactions_of_senders() {
+        let pool = testing_pool();
+        let tx = MockTransaction::eip1559();
+        let gapped = tx.skip(1);
+        let other = MockTransaction::eip1559();
+        for tx in [&tx, &gapped, &other] {
+            pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        }
+        let api = TxPoolApi::new(pool);
+
+        let query = TxpoolQuery { senders: vec![tx.sender(), tx.sender()], ..Default::default() };
+        let AllPoolTransactions { pending, queued } = api.transactions(Some(query));
+        let hashes = pending.iter().chain(&queued).map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        assert_eq!(hashes, HashSet::from([tx.get_hash(), gapped.get_hash()]));
+        assert!(queued.iter().any(|queued| *queued.hash() == gapped.get_hash()));
+
+        let all = api.transactions(None);
+        assert_eq!(all.pending.len() + all.queued.len(), 3);
+    }
+
+    #[test]
+    fn converts_pool_events() {
+        let mut tracker = TxpoolEventTracker::default();
+        let (queued, pending) = (TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+        let mut on_event =
+            |event: FullTransactionEvent<MockTransaction>| tracker.on_event(event).unwrap();
+
+        assert_eq!(
+            on_event(FullTransactionEvent::Queued(queued)),
+            TxpoolEvent::Added { hash: queued, subpool: TxpoolSubpool::Queued }
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Pending(pending)),
+            TxpoolEvent::Added { hash: pending, subpool: TxpoolSubpool::Pending }
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Pending(queued)),
+            TxpoolEvent::Promoted { hash: queued }
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Mined { tx_hash: queued, block_hash: B256::ZERO }),
+            TxpoolEvent::Mined { hash: queued, block_hash: B256::ZERO }
+        );
+        assert_eq!(
+            on_event(FullTransactionEvent::Invalid(pending)),
+            TxpoolEvent::Dropped { hash: pending, reason: TxpoolDropReason::Invalid }
+        );
+        assert!(tracker.queued.is_empty());
+        assert!(tracker
+            .on_event::<MockTransaction>(FullTransactionEvent::Propagated(Default::default()))
+            .is_none());
+    }
+}
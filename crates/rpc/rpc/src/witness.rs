@@ -0,0 +1,29 @@
+//! Building of the `debug_executionWitness` response from the state accessed by a block.
+
+use reth_primitives::{keccak256, Bytes};
+use reth_provider::{ProviderResult, StateProvider};
+use reth_revm::witness::AccessedState;
+use reth_rpc_types::ExecutionWitness;
+
+/// Builds the witness of the accessed state from the witness nodes of the accessed accounts and
+/// storage slots in the given state, which include the siblings needed to recompute the state root
+/// after the execution.
+///
+/// The headers of the witness are left empty.
+pub(crate) fn execution_witness(
+    accessed: AccessedState,
+    state: &impl StateProvider,
+) -> ProviderResult<ExecutionWitness> {
+    let mut witness = ExecutionWitness::default();
+    for node in accessed.witness_nodes(state)? {
+        witness.state.insert(keccak256(&node), node);
+    }
+    for (address, slots) in accessed.accounts {
+        witness.keys.insert(keccak256(address), Bytes::copy_from_slice(address.as_slice()));
+        for slot in slots {
+            witness.keys.insert(keccak256(slot), Bytes::copy_from_slice(slot.as_slice()));
+        }
+    }
+    witness.codes = accessed.codes;
+    Ok(witness)
+}
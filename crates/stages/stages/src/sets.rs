@@ -36,10 +36,10 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, SenderRecoveryStage,
-        StorageHashingStage, TransactionLookupStage,
+        IndexAccountHistoryStage, IndexLogsStage, IndexSenderNoncesStage, IndexStorageHistoryStage,
+        MerkleStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
-    StageSet, StageSetBuilder,
+    StageId, StageSet, StageSetBuilder,
 };
 use reth_config::config::StageConfig;
 use reth_consensus::Consensus;
@@ -356,6 +356,9 @@ impl<DB: Database> StageSet<DB> for HashingStages {
 }
 
 /// A set containing all stages that do additional indexing for historical state.
+///
+/// The [`IndexLogsStage`] and the [`IndexSenderNoncesStage`] are only enabled if the log index and
+/// the sender nonce index are enabled in the [`StageConfig`].
 #[derive(Debug, Default)]
 #[non_exhaustive]
 pub struct HistoryIndexingStages {
@@ -383,5 +386,17 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage(IndexLogsStage::new(
+                self.stages_config.index_logs,
+                self.stages_config.etl.clone(),
+            ))
+            .disable_if(StageId::IndexLogs, || !self.stages_config.index_logs.enabled)
+            .add_stage(IndexSenderNoncesStage::new(
+                self.stages_config.index_sender_nonces,
+                self.stages_config.etl.clone(),
+            ))
+            .disable_if(StageId::IndexSenderNonces, || {
+                !self.stages_config.index_sender_nonces.enabled
+            })
     }
 }
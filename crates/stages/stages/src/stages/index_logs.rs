@@ -0,0 +1,275 @@
+use super::{load_history_indices, DEFAULT_CACHE_THRESHOLD};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::ShardedKey,
+    table::{Decode, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_etl::Collector;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::{DatabaseProviderRW, HistoryWriter, ReceiptProvider};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, hash::Hash, ops::RangeInclusive};
+use tracing::info;
+
+/// Stage is indexing the addresses and topics of the logs in the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. The indices are used to answer `eth_getLogs`
+/// requests without scanning the receipts of every block in the range. For more information on
+/// index sharding take a look at [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The log index is optional, so the stage is only part of the pipeline if it is enabled in the
+/// [`IndexLogsConfig`]. Once the stage ran, the index is also kept up to date when blocks are
+/// appended or unwound outside of the pipeline.
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(config: IndexLogsConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync the index might have been written up to another block before. We clear
+        // the tables since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, "Collecting indices");
+        let (address_collector, topic_collector) =
+            collect_log_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider.tx_ref(),
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider.tx_ref(),
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_indices(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the addresses and topics of the logs in the given block range into one [`Collector`]
+/// each, keyed by the sharded keys of [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// Same as [`collect_history_indices`](super::collect_history_indices), the indices are cached in
+/// memory and moved to the collectors every [`DEFAULT_CACHE_THRESHOLD`] blocks.
+#[allow(clippy::type_complexity)]
+fn collect_log_indices<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>),
+    StageError,
+> {
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut address_cache = HashMap::<Address, Vec<u64>>::new();
+    let mut topic_cache = HashMap::<B256, Vec<u64>>::new();
+
+    let mut flush_counter = 0;
+    for entry in provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+        let (block_number, body) = entry?;
+        let tx_range = body.tx_num_range();
+        if !tx_range.is_empty() {
+            for receipt in provider.receipts_by_tx_range(tx_range)? {
+                for log in &receipt.logs {
+                    push_block(address_cache.entry(log.address).or_default(), block_number);
+                    for topic in log.topics() {
+                        push_block(topic_cache.entry(*topic).or_default(), block_number);
+                    }
+                }
+            }
+        }
+
+        flush_counter += 1;
+        if flush_counter > DEFAULT_CACHE_THRESHOLD {
+            flush::<tables::LogAddressIndex, _>(&mut address_cache, &mut address_collector)?;
+            flush::<tables::LogTopicIndex, _>(&mut topic_cache, &mut topic_collector)?;
+            flush_counter = 0;
+        }
+    }
+    flush::<tables::LogAddressIndex, _>(&mut address_cache, &mut address_collector)?;
+    flush::<tables::LogTopicIndex, _>(&mut topic_cache, &mut topic_collector)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+/// Appends the block number to the ascending list of blocks, unless it's already the last one.
+fn push_block(blocks: &mut Vec<u64>, block_number: BlockNumber) {
+    if blocks.last() != Some(&block_number) {
+        blocks.push(block_number);
+    }
+}
+
+/// Moves the cached indices to the collector, keyed by the highest block number of each list.
+fn flush<H, P>(
+    cache: &mut HashMap<P, Vec<u64>>,
+    collector: &mut Collector<H::Key, H::Value>,
+) -> Result<(), StageError>
+where
+    H: Table<Key = ShardedKey<P>, Value = BlockNumberList>,
+    P: Eq + Hash,
+{
+    for (key, indice_list) in cache.drain() {
+        let last = *indice_list.last().expect("qed");
+        collector
+            .insert(ShardedKey::new(key, last), BlockNumberList::new_pre_sorted(indice_list))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use rand::Rng;
+    use reth_primitives::{address, b256, Bytes, Log};
+    use reth_provider::StageCheckpointWriter;
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+
+    const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
+    const TOPIC: B256 = b256!("0000000000000000000000000000000000000000000000000000000000000002");
+
+    fn address_shard(db: &TestStageDB, address: Address) -> Vec<u64> {
+        db.query(|tx| Ok(tx.get::<tables::LogAddressIndex>(ShardedKey::last(address))?))
+            .unwrap()
+            .map(|list| list.iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn topic_shard(db: &TestStageDB, topic: B256) -> Vec<u64> {
+        db.query(|tx| Ok(tx.get::<tables::LogTopicIndex>(ShardedKey::last(topic))?))
+            .unwrap()
+            .map(|list| list.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // Every even block has a log of `ADDRESS`, blocks 4 and 7 have a log with `TOPIC`.
+        let mut receipts = Vec::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+                let address = if block.number % 2 == 0 { ADDRESS } else { rng.gen() };
+                let topics =
+                    if [4, 7].contains(&block.number) { vec![TOPIC] } else { vec![rng.gen()] };
+                receipt.logs = vec![Log::new_unchecked(address, topics, Bytes::new())];
+                receipts.push((receipts.len() as u64, receipt));
+            }
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+        let output = IndexLogsStage::default().execute(&provider, input).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.save_stage_checkpoint(StageId::IndexLogs, output.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(address_shard(&db, ADDRESS), vec![0, 2, 4, 6, 8, 10]);
+        assert_eq!(topic_shard(&db, TOPIC), vec![4, 7]);
+
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(
+            provider.blocks_with_matching_logs(0..=10, &[ADDRESS], &[vec![TOPIC]]).unwrap(),
+            Some(vec![4])
+        );
+        assert_eq!(
+            provider.blocks_with_matching_logs(3..=8, &[ADDRESS], &[]).unwrap(),
+            Some(vec![4, 6, 8])
+        );
+        assert_eq!(
+            provider.blocks_with_matching_logs(5..=10, &[], &[vec![], vec![TOPIC]]).unwrap(),
+            Some(vec![7])
+        );
+        assert_eq!(provider.blocks_with_matching_logs(0..=10, &[], &[]).unwrap(), None);
+        assert_eq!(provider.blocks_with_matching_logs(0..=11, &[ADDRESS], &[]).unwrap(), None);
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 5, bad_block: None };
+        let output = IndexLogsStage::default().unwind(&provider, input).unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        assert_eq!(address_shard(&db, ADDRESS), vec![0, 2, 4]);
+        assert_eq!(topic_shard(&db, TOPIC), vec![4]);
+    }
+}
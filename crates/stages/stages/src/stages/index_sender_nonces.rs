@@ -0,0 +1,189 @@
+use num_traits::Zero;
+use reth_config::config::{EtlConfig, IndexSenderNoncesConfig};
+use reth_db::{tables, RawKey, RawValue};
+use reth_db_api::{
+    cursor::DbCursorRW, database::Database, models::AddressNonce, transaction::DbTxMut,
+};
+use reth_etl::Collector;
+use reth_primitives::TxHash;
+use reth_provider::{DatabaseProviderRW, HistoryWriter, StatsReader};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::*;
+
+/// The sender nonce index stage.
+///
+/// This stage walks over the transactions of each block, and maps the sender and nonce of each
+/// transaction to its hash in [`tables::SenderNonceTransactions`]. The index is used to look up the
+/// transaction of a sender with a given nonce without searching the history of the sender.
+///
+/// The index is optional, so the stage is only part of the pipeline if it is enabled in the
+/// [`IndexSenderNoncesConfig`]. Once the stage ran, the index is also kept up to date when blocks
+/// are appended or unwound outside of the pipeline.
+///
+/// It uses [`reth_etl::Collector`] to collect all entries before finally writing them to disk.
+#[derive(Debug, Clone)]
+pub struct IndexSenderNoncesStage {
+    /// The maximum number of transactions to process before returning control to the loop that
+    /// collects the index entries.
+    chunk_size: u64,
+    etl_config: EtlConfig,
+}
+
+impl Default for IndexSenderNoncesStage {
+    fn default() -> Self {
+        Self { chunk_size: 5_000_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl IndexSenderNoncesStage {
+    /// Create new instance of [`IndexSenderNoncesStage`].
+    pub const fn new(config: IndexSenderNoncesConfig, etl_config: EtlConfig) -> Self {
+        Self { chunk_size: config.chunk_size, etl_config }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexSenderNoncesStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexSenderNonces
+    }
+
+    /// Write sender and nonce -> transaction hash entries
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync the index might have been written up to another block before. We clear
+        // the table since it's faster to rebuild from scratch.
+        if input.checkpoint().block_number == 0 {
+            provider.tx_ref().clear::<tables::SenderNonceTransactions>()?;
+        }
+
+        let mut collector: Collector<AddressNonce, TxHash> =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+
+        loop {
+            let (tx_range, block_range, is_final_range) =
+                input.next_block_range_with_transaction_threshold(provider, self.chunk_size)?;
+
+            info!(
+                target: "sync::stages::index_sender_nonces",
+                ?tx_range,
+                "Collecting sender nonces"
+            );
+
+            for (key, hash) in provider.sender_nonce_hashes_by_tx_range(tx_range)? {
+                collector.insert(key, hash)?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*block_range.end()));
+            if is_final_range {
+                break
+            }
+        }
+
+        let append_only = provider.count_entries::<tables::SenderNonceTransactions>()?.is_zero();
+        let mut cursor = provider
+            .tx_ref()
+            .cursor_write::<tables::RawTable<tables::SenderNonceTransactions>>()?;
+
+        info!(
+            target: "sync::stages::index_sender_nonces",
+            entries = collector.len(),
+            ?append_only,
+            "Inserting sender nonces"
+        );
+        for entry in collector.iter()? {
+            let (key, hash) = entry?;
+            let key = RawKey::<AddressNonce>::from_vec(key);
+            let value = RawValue::<TxHash>::from_vec(hash);
+            if append_only {
+                cursor.append(key, value)?
+            } else {
+                cursor.insert(key, value)?
+            }
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_to, _) = input.unwind_block_range_with_threshold(self.chunk_size);
+
+        provider.unwind_sender_nonce_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_primitives::B256;
+    use reth_provider::{StageCheckpointWriter, TransactionsProvider};
+    use reth_testing_utils::{generators, generators::random_block_range};
+    use std::ops::RangeInclusive;
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+        let entries = |range: RangeInclusive<usize>| {
+            blocks[range]
+                .iter()
+                .flat_map(|block| &block.body)
+                .map(|tx| (tx.recover_signer().unwrap(), tx.nonce(), tx.hash()))
+                .collect::<Vec<_>>()
+        };
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+        let output = IndexSenderNoncesStage::default().execute(&provider, input).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(10), done: true });
+        provider.save_stage_checkpoint(StageId::IndexSenderNonces, output.checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        // The genesis block is not indexed, it can't contain transactions.
+        let provider = db.factory.provider().unwrap();
+        for (sender, nonce, hash) in entries(1..=10) {
+            assert_eq!(
+                provider.transaction_hash_by_sender_and_nonce(sender, nonce).unwrap(),
+                Some(hash)
+            );
+        }
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(10), unwind_to: 5, bad_block: None };
+        let output = IndexSenderNoncesStage::default().unwind(&provider, input).unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+
+        let provider = db.factory.provider().unwrap();
+        for (sender, nonce, hash) in entries(1..=5) {
+            assert_eq!(
+                provider.transaction_hash_by_sender_and_nonce(sender, nonce).unwrap(),
+                Some(hash)
+            );
+        }
+        for (sender, nonce, _) in entries(6..=10) {
+            assert_eq!(provider.transaction_hash_by_sender_and_nonce(sender, nonce).unwrap(), None);
+        }
+    }
+}
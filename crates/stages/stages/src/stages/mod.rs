@@ -12,6 +12,10 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index of log addresses and topics
+mod index_logs;
+/// Index of transactions by sender and nonce
+mod index_sender_nonces;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -28,6 +32,8 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_logs::*;
+pub use index_sender_nonces::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 
@@ -30,6 +30,14 @@ pub enum StageId {
     IndexStorageHistory,
     /// Index account history stage in the process.
     IndexAccountHistory,
+    /// Index logs stage in the process.
+    ///
+    /// The log index is optional, so this stage is not part of [`StageId::ALL`].
+    IndexLogs,
+    /// Index sender nonces stage in the process.
+    ///
+    /// The sender nonce index is optional, so this stage is not part of [`StageId::ALL`].
+    IndexSenderNonces,
     /// Finish stage in the process.
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -80,6 +88,8 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexLogs => "IndexLogs",
+            Self::IndexSenderNonces => "IndexSenderNonces",
             Self::Finish => "Finish",
             Self::Other(s) => s,
         }
@@ -123,6 +133,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::IndexSenderNonces.to_string(), "IndexSenderNonces");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
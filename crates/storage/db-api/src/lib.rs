@@ -70,6 +70,7 @@ pub mod database;
 /// Database metrics trait extensions.
 pub mod database_metrics;
 pub mod mock;
+pub mod read_counter;
 /// Table traits
 pub mod table;
 /// Transaction database traits.
@@ -153,7 +153,57 @@ impl Decode for AddressStorageKey {
     }
 }
 
-impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52));
+/// [`Address`] concatenated with a transaction nonce. Used by the sender nonce index.
+///
+/// Since it's used as a key, it isn't compressed when encoding it.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Ord, PartialOrd, Hash,
+)]
+pub struct AddressNonce(pub (Address, u64));
+
+impl AddressNonce {
+    /// Create a new key from the sender and the nonce
+    pub const fn new(address: Address, nonce: u64) -> Self {
+        Self((address, nonce))
+    }
+
+    /// Return the address
+    pub const fn address(&self) -> Address {
+        self.0 .0
+    }
+
+    /// Return the nonce
+    pub const fn nonce(&self) -> u64 {
+        self.0 .1
+    }
+}
+
+impl Encode for AddressNonce {
+    type Encoded = [u8; 28];
+
+    fn encode(self) -> Self::Encoded {
+        let address = self.0 .0;
+        let nonce = self.0 .1;
+
+        let mut buf = [0u8; 28];
+
+        buf[..20].copy_from_slice(address.as_slice());
+        buf[20..].copy_from_slice(&nonce.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for AddressNonce {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        let value = value.as_ref();
+        let address = Address::from_slice(&value[..20]);
+        let nonce = u64::from_be_bytes(value[20..].try_into().map_err(|_| DatabaseError::Decode)?);
+
+        Ok(Self((address, nonce)))
+    }
+}
+
+impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52), (AddressNonce, 28));
 
 #[cfg(test)]
 mod tests {
@@ -203,6 +253,23 @@ mod tests {
         assert_eq!(decoded, key);
     }
 
+    #[test]
+    fn test_address_nonce() {
+        let address = Address::from_str("ba5e000000000000000000000000000000000000").unwrap();
+        let key = AddressNonce::new(address, 7);
+
+        let mut bytes = [0u8; 28];
+        bytes[..20].copy_from_slice(address.as_slice());
+        bytes[20..].copy_from_slice(&7u64.to_be_bytes());
+
+        let encoded = Encode::encode(key);
+        assert_eq!(encoded, bytes);
+
+        let decoded: AddressNonce = Decode::decode(encoded).unwrap();
+        assert_eq!(decoded, key);
+        assert_eq!((decoded.address(), decoded.nonce()), (address, 7));
+    }
+
     #[test]
     fn test_address_storage_key_rand() {
         let mut bytes = [0u8; 52];
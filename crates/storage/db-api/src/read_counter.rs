@@ -0,0 +1,131 @@
+//! Counts the database reads of a unit of work, e.g. an RPC request.
+//!
+//! A [`ReadCounter`] is entered on the threads that do the work, and every read of the database
+//! on these threads is attributed to it.
+//!
+//! Reads are only recorded once counting is [enabled](enable), so that database reads don't look
+//! up the entered counter if nothing counts them.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Whether reads are recorded
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// The counter of the work that is currently executed on this thread
+    static CURRENT: RefCell<Option<ReadCounter>> = const { RefCell::new(None) };
+}
+
+/// Enables recording reads for the rest of the process.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Records a read of the database, if counting is enabled and a [`ReadCounter`] is entered on the
+/// current thread.
+#[inline]
+pub fn record_read() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return
+    }
+    CURRENT.with(|current| {
+        if let Some(counter) = current.borrow().as_ref() {
+            counter.reads.fetch_add(1, Ordering::Relaxed);
+        }
+    })
+}
+
+/// A counter of database reads that can be shared between threads.
+#[derive(Debug, Clone, Default)]
+pub struct ReadCounter {
+    reads: Arc<AtomicU64>,
+}
+
+impl ReadCounter {
+    /// Returns the counter that is entered on the current thread, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Returns the number of recorded reads.
+    pub fn reads(&self) -> u64 {
+        self.reads.load(Ordering::Relaxed)
+    }
+
+    /// Attributes the reads of the current thread to this counter, until the returned guard is
+    /// dropped.
+    pub fn enter(&self) -> ReadCounterGuard {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        ReadCounterGuard { previous }
+    }
+
+    /// Wraps the closure, so that its reads are attributed to the counter that is entered on the
+    /// current thread, if any, when it is called on another thread.
+    pub fn in_current<F, R>(f: F) -> impl FnOnce() -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let counter = Self::current();
+        move || {
+            let _guard = counter.as_ref().map(Self::enter);
+            f()
+        }
+    }
+}
+
+/// Restores the previously entered [`ReadCounter`] of the thread when dropped.
+#[derive(Debug)]
+#[must_use = "the counter is exited when the guard is dropped"]
+pub struct ReadCounterGuard {
+    previous: Option<ReadCounter>,
+}
+
+impl Drop for ReadCounterGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_reads_of_entered_counter() {
+        enable();
+        record_read();
+
+        let outer = ReadCounter::default();
+        let inner = ReadCounter::default();
+        {
+            let _outer = outer.enter();
+            record_read();
+            {
+                let _inner = inner.enter();
+                record_read();
+                record_read();
+            }
+            record_read();
+
+            std::thread::spawn(|| {
+                assert!(ReadCounter::current().is_none());
+                record_read();
+            })
+            .join()
+            .unwrap();
+            std::thread::spawn(ReadCounter::in_current(record_read)).join().unwrap();
+        }
+        record_read();
+
+        assert_eq!(outer.reads(), 3);
+        assert_eq!(inner.reads(), 2);
+        assert!(ReadCounter::current().is_none());
+    }
+}
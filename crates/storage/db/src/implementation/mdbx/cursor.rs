@@ -11,6 +11,7 @@ use reth_db_api::{
         DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
         ReverseWalker, Walker,
     },
+    read_counter,
     table::{Compress, Decode, Decompress, DupSort, Encode, Table},
 };
 use reth_libmdbx::{Error as MDBXError, TransactionKind, WriteFlags, RO, RW};
@@ -90,30 +91,37 @@ macro_rules! compress_to_buf_or_ref {
 
 impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
     fn first(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.first())
     }
 
     fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.set_key(key.encode().as_ref()))
     }
 
     fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.set_range(key.encode().as_ref()))
     }
 
     fn next(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.next())
     }
 
     fn prev(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.prev())
     }
 
     fn last(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.last())
     }
 
     fn current(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.get_current())
     }
 
@@ -160,16 +168,19 @@ impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
 impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
     /// Returns the next `(key, value)` pair of a DUPSORT table.
     fn next_dup(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.next_dup())
     }
 
     /// Returns the next `(key, value)` pair skipping the duplicates.
     fn next_no_dup(&mut self) -> PairResult<T> {
+        read_counter::record_read();
         decode::<T>(self.inner.next_nodup())
     }
 
     /// Returns the next `value` of a duplicate `key`.
     fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        read_counter::record_read();
         self.inner
             .next_dup()
             .map_err(|e| DatabaseError::Read(e.into()))?
@@ -182,6 +193,7 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         key: <T as Table>::Key,
         subkey: <T as DupSort>::SubKey,
     ) -> ValueOnlyResult<T> {
+        read_counter::record_read();
         self.inner
             .get_both_range(key.encode().as_ref(), subkey.encode().as_ref())
             .map_err(|e| DatabaseError::Read(e.into()))?
@@ -199,6 +211,7 @@ impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
         key: Option<T::Key>,
         subkey: Option<T::SubKey>,
     ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        read_counter::record_read();
         let start = match (key, subkey) {
             (Some(key), Some(subkey)) => {
                 // encode key and decode it after.
@@ -23,7 +23,7 @@ pub(crate) mod utils;
 
 use reth_db_api::{
     models::{
-        accounts::{AccountBeforeTx, BlockNumberAddress},
+        accounts::{AccountBeforeTx, AddressNonce, BlockNumberAddress},
         blocks::{HeaderHash, StoredBlockOmmers},
         client_version::ClientVersion,
         storage_sharded_key::StorageShardedKey,
@@ -301,6 +301,12 @@ tables! {
     /// Stores the mapping of the transaction hash to the transaction number.
     table TransactionHashNumbers<Key = TxHash, Value = TxNumber>;
 
+    /// Stores the hash of the canonical transaction of each sender and nonce.
+    ///
+    /// Only written if the optional sender nonce index is enabled, up to the checkpoint of the
+    /// `IndexSenderNonces` stage.
+    table SenderNonceTransactions<Key = AddressNonce, Value = TxHash>;
+
     /// Stores the mapping of transaction number to the blocks number.
     ///
     /// The key is the highest transaction ID in the block.
@@ -361,6 +367,20 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores the numbers of the blocks that contain logs emitted by each address.
+    ///
+    /// Only written if the optional log index is enabled, up to the checkpoint of the
+    /// `IndexLogs` stage. Shards are keyed the same way as in [`AccountsHistory`], with the last
+    /// shard of each address containing `u64::MAX` `BlockNumber`.
+    table LogAddressIndex<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the blocks that contain logs with each topic, at any position.
+    ///
+    /// Only written if the optional log index is enabled, up to the checkpoint of the
+    /// `IndexLogs` stage. Shards are keyed the same way as in [`AccountsHistory`], with the last
+    /// shard of each topic containing `u64::MAX` `BlockNumber`.
+    table LogTopicIndex<Key = ShardedKey<B256>, Value = BlockNumberList>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -411,13 +431,18 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the hashes of generic chain state blocks, like the last safe block.
+    table ChainStateHashes<Key = ChainStateKey, Value = BlockHash>;
 }
 
-/// Keys for the `ChainState` table.
+/// Keys for the `ChainState` and `ChainStateHashes` tables.
 #[derive(Ord, Clone, Eq, PartialOrd, PartialEq, Debug, Deserialize, Serialize, Hash)]
 pub enum ChainStateKey {
     /// Last finalized block key
     LastFinalizedBlock,
+    /// Last safe block key
+    LastSafeBlock,
 }
 
 impl Encode for ChainStateKey {
@@ -426,16 +451,17 @@ impl Encode for ChainStateKey {
     fn encode(self) -> Self::Encoded {
         match self {
             Self::LastFinalizedBlock => [0],
+            Self::LastSafeBlock => [1],
         }
     }
 }
 
 impl Decode for ChainStateKey {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db_api::DatabaseError> {
-        if value.as_ref() == [0] {
-            Ok(Self::LastFinalizedBlock)
-        } else {
-            Err(reth_db_api::DatabaseError::Decode)
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            [1] => Ok(Self::LastSafeBlock),
+            _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
 }
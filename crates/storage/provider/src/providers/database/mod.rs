@@ -421,6 +421,14 @@ impl<DB: Database> TransactionsProvider for ProviderFactory<DB> {
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         self.provider()?.transaction_sender(id)
     }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        self.provider()?.transaction_hash_by_sender_and_nonce(sender, nonce)
+    }
 }
 
 impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
@@ -453,6 +461,15 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
             |_| true,
         )
     }
+
+    fn blocks_with_matching_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<Vec<BlockNumber>>> {
+        self.provider()?.blocks_with_matching_logs(range, addresses, topics)
+    }
 }
 
 impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
@@ -593,7 +610,9 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, FinalizedBlockReader, FinalizedBlockWriter,
+        HeaderSyncGapProvider, HistoryWriter, ReceiptProvider, StageCheckpointReader,
+        StageCheckpointWriter, TransactionsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -603,8 +622,15 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        models::{AddressNonce, ShardedKey},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::{
+        hex_literal::hex, Address, SealedBlock, StaticFileSegment, TxNumber, B256, U256,
     };
-    use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
@@ -640,6 +666,69 @@ mod tests {
         provider.block_hash(0).unwrap();
     }
 
+    #[test]
+    fn finalized_and_safe_blocks() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_finalized_block_number().unwrap(), 0);
+        assert_eq!(provider.last_safe_block_hash().unwrap(), None);
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_safe_block_hash(B256::with_last_byte(2)).unwrap();
+        provider_rw.commit().unwrap();
+        // the safe block is not mistaken for the finalized block
+        assert_eq!(factory.provider().unwrap().last_finalized_block_number().unwrap(), 0);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_finalized_block_number(1).unwrap();
+        provider_rw.commit().unwrap();
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_finalized_block_number().unwrap(), 1);
+        assert_eq!(provider.last_safe_block_hash().unwrap(), Some(B256::with_last_byte(2)));
+    }
+
+    #[test]
+    fn drop_optional_indices() {
+        let factory = create_test_provider_factory();
+        let (address, hash) = (Address::with_last_byte(1), B256::with_last_byte(1));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        tx.put::<tables::LogAddressIndex>(
+            ShardedKey::new(address, u64::MAX),
+            BlockNumberList::new_pre_sorted([0]),
+        )
+        .unwrap();
+        tx.put::<tables::SenderNonceTransactions>(AddressNonce::new(address, 0), hash).unwrap();
+        provider_rw.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(0)).unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexSenderNonces, StageCheckpoint::new(0))
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.blocks_with_matching_logs(0..=0, &[address], &[]).unwrap(),
+            Some(vec![0])
+        );
+        assert_eq!(provider.transaction_hash_by_sender_and_nonce(address, 0).unwrap(), Some(hash));
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.drop_log_indices().unwrap();
+        provider_rw.drop_sender_nonce_index().unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.get_stage_checkpoint(StageId::IndexLogs).unwrap(), None);
+        assert_eq!(provider.get_stage_checkpoint(StageId::IndexSenderNonces).unwrap(), None);
+        assert_eq!(provider.blocks_with_matching_logs(0..=0, &[address], &[]).unwrap(), None);
+        assert_eq!(provider.transaction_hash_by_sender_and_nonce(address, 0).unwrap(), None);
+        assert_eq!(provider.tx_ref().entries::<tables::LogAddressIndex>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::SenderNonceTransactions>().unwrap(), 0);
+    }
+
     #[test]
     fn provider_factory_with_database_path() {
         let chain_spec = ChainSpecBuilder::mainnet().build();
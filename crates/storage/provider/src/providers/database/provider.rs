@@ -21,8 +21,9 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, RangeWalker},
     database::Database,
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, AddressNonce,
+        BlockNumberAddress, ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers,
+        StoredBlockWithdrawals,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
@@ -516,6 +517,107 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             assemble_block(header, body, ommers, withdrawals, requests, senders)
         })
     }
+
+    /// Returns the addresses and topics of the logs in the given block range, each with the
+    /// ascending numbers of the blocks that contain them.
+    fn log_addresses_and_topics_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>)> {
+        fn push_block(blocks: &mut Vec<u64>, block_number: BlockNumber) {
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+        }
+
+        let mut addresses = BTreeMap::<Address, Vec<u64>>::new();
+        let mut topics = BTreeMap::<B256, Vec<u64>>::new();
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+            let (block_number, body) = entry?;
+            let tx_range = body.tx_num_range();
+            if tx_range.is_empty() {
+                continue
+            }
+
+            for receipt in self.receipts_by_tx_range(tx_range)? {
+                for log in &receipt.logs {
+                    push_block(addresses.entry(log.address).or_default(), block_number);
+                    for topic in log.topics() {
+                        push_block(topics.entry(*topic).or_default(), block_number);
+                    }
+                }
+            }
+        }
+
+        Ok((addresses, topics))
+    }
+
+    /// Returns the numbers of the blocks in the range that are recorded for any of the keys in
+    /// the log index table `T`.
+    fn log_index_blocks<T, K>(
+        &self,
+        keys: &[K],
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut blocks = BTreeSet::new();
+        for key in keys {
+            // The first shard that can contain the start of the range is the first one whose
+            // highest block number is not below it.
+            let mut entry = cursor.seek(ShardedKey::new(*key, *range.start()))?;
+            while let Some((sharded_key, list)) = entry {
+                if sharded_key.key != *key {
+                    break
+                }
+                blocks.extend(list.iter().filter(|block_number| range.contains(block_number)));
+                if sharded_key.highest_block_number >= *range.end() {
+                    break
+                }
+                entry = cursor.next()?;
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the sender and nonce of each transaction in the given range, with the hash of the
+    /// transaction.
+    ///
+    /// The senders are recovered if they are not stored, e.g. because they were pruned.
+    pub fn sender_nonce_hashes_by_tx_range(
+        &self,
+        tx_range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(AddressNonce, TxHash)>> {
+        let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+        let mut senders = self.senders_by_tx_range(tx_range)?;
+        if senders.len() != transactions.len() {
+            senders = TransactionSignedNoHash::recover_signers(&transactions, transactions.len())
+                .ok_or(ProviderError::SenderRecoveryError)?;
+        }
+
+        Ok(senders
+            .into_iter()
+            .zip(transactions)
+            .map(|(sender, tx)| (AddressNonce::new(sender, tx.transaction.nonce()), tx.hash()))
+            .collect())
+    }
+
+    /// Returns the sender and nonce of each transaction in the given block range, with the hash of
+    /// the transaction.
+    fn sender_nonce_hashes_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(AddressNonce, TxHash)>> {
+        let mut entries = Vec::new();
+        for entry in self.tx.cursor_read::<tables::BlockBodyIndices>()?.walk_range(range)? {
+            let (_, body) = entry?;
+            entries.extend(self.sender_nonce_hashes_by_tx_range(body.tx_num_range())?);
+        }
+        Ok(entries)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -1203,6 +1305,35 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Unwind the log index table `T` for the given keys, each with the ascending numbers of the
+    /// unwound blocks that contain them.
+    fn unwind_log_index<T, K>(&self, keys: &BTreeMap<K, Vec<u64>>) -> ProviderResult<()>
+    where
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+        K: Copy + PartialEq,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for (&key, blocks) in keys {
+            let Some(&rem_index) = blocks.first() else { continue };
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                rem_index,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1872,6 +2003,18 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         Ok(self.tx.get::<tables::TransactionSenders>(id)?)
     }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        // The index is only written once the optional stage that builds it ran.
+        if self.get_stage_checkpoint(StageId::IndexSenderNonces)?.is_none() {
+            return Ok(None)
+        }
+        Ok(self.tx.get::<tables::SenderNonceTransactions>(AddressNonce::new(sender, nonce))?)
+    }
 }
 
 impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
@@ -1918,6 +2061,33 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    fn blocks_with_matching_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<Vec<BlockNumber>>> {
+        // The index is only complete up to the checkpoint of the optional stage that builds it.
+        let indexed = self.get_stage_checkpoint(StageId::IndexLogs)?;
+        if !indexed.is_some_and(|checkpoint| checkpoint.block_number >= *range.end()) {
+            return Ok(None)
+        }
+
+        let mut blocks = None;
+        if !addresses.is_empty() {
+            blocks = Some(self.log_index_blocks::<tables::LogAddressIndex, _>(addresses, &range)?);
+        }
+        for topics in topics.iter().filter(|topics| !topics.is_empty()) {
+            let topic_blocks = self.log_index_blocks::<tables::LogTopicIndex, _>(topics, &range)?;
+            blocks = Some(match blocks {
+                Some(blocks) => blocks.intersection(&topic_blocks).copied().collect(),
+                None => topic_blocks,
+            });
+        }
+
+        Ok(blocks.map(|blocks| blocks.into_iter().collect()))
+    }
 }
 
 impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
@@ -2490,6 +2660,53 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         )
     }
 
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+        self.unwind_log_index::<tables::LogAddressIndex, _>(&addresses)?;
+        self.unwind_log_index::<tables::LogTopicIndex, _>(&topics)?;
+        Ok(addresses.len() + topics.len())
+    }
+
+    fn insert_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        let (addresses, topics) = self.log_addresses_and_topics_with_range(range)?;
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
+    fn drop_log_indices(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::LogAddressIndex>()?;
+        self.tx.clear::<tables::LogTopicIndex>()?;
+        self.tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+        Ok(())
+    }
+
+    fn unwind_sender_nonce_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let entries = self.sender_nonce_hashes_with_range(range)?;
+        let mut cursor = self.tx.cursor_write::<tables::SenderNonceTransactions>()?;
+        for (key, _) in &entries {
+            if cursor.seek_exact(*key)?.is_some() {
+                cursor.delete_current()?;
+            }
+        }
+        Ok(entries.len())
+    }
+
+    fn insert_sender_nonce_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        for (key, hash) in self.sender_nonce_hashes_with_range(range)? {
+            self.tx.put::<tables::SenderNonceTransactions>(key, hash)?;
+        }
+        Ok(())
+    }
+
+    fn drop_sender_nonce_index(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::SenderNonceTransactions>()?;
+        self.tx.delete::<tables::StageCheckpoints>(StageId::IndexSenderNonces.to_string(), None)?;
+        Ok(())
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -2530,6 +2747,30 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind account history indices.
             self.unwind_account_history_indices(range.clone())?;
 
+            // Unwind the optional log index while the receipts are still available.
+            if let Some(checkpoint) = self
+                .get_stage_checkpoint(StageId::IndexLogs)?
+                .filter(|checkpoint| checkpoint.block_number >= *range.start())
+            {
+                self.unwind_log_indices(*range.start()..=checkpoint.block_number)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexLogs,
+                    StageCheckpoint::new(range.start().saturating_sub(1)),
+                )?;
+            }
+
+            // Unwind the optional sender nonce index while the transactions are still available.
+            if let Some(checkpoint) = self
+                .get_stage_checkpoint(StageId::IndexSenderNonces)?
+                .filter(|checkpoint| checkpoint.block_number >= *range.start())
+            {
+                self.unwind_sender_nonce_index(*range.start()..=checkpoint.block_number)?;
+                self.save_stage_checkpoint(
+                    StageId::IndexSenderNonces,
+                    StageCheckpoint::new(range.start().saturating_sub(1)),
+                )?;
+            }
+
             // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
             // sets.
             let mut storage_prefix_sets = HashMap::<B256, PrefixSet>::default();
@@ -2783,6 +3024,30 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
         self.update_history_indices(first_number..=last_block_number)?;
+
+        // Extend the optional log index, but only if it is enabled and up to date.
+        if self
+            .get_stage_checkpoint(StageId::IndexLogs)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number)
+        {
+            self.insert_log_indices(first_number..=last_block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexLogs,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
+
+        // Extend the optional sender nonce index, but only if it is enabled and up to date.
+        if self
+            .get_stage_checkpoint(StageId::IndexSenderNonces)?
+            .is_some_and(|checkpoint| checkpoint.block_number + 1 == first_number)
+        {
+            self.insert_sender_nonce_index(first_number..=last_block_number)?;
+            self.save_stage_checkpoint(
+                StageId::IndexSenderNonces,
+                StageCheckpoint::new(last_block_number),
+            )?;
+        }
         durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
 
         // Update pipeline progress
@@ -2829,17 +3094,14 @@ impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
 
 impl<TX: DbTx> FinalizedBlockReader for DatabaseProvider<TX> {
     fn last_finalized_block_number(&self) -> ProviderResult<BlockNumber> {
-        let mut finalized_blocks = self
+        Ok(self
             .tx
-            .cursor_read::<tables::ChainState>()?
-            .walk(Some(tables::ChainStateKey::LastFinalizedBlock))?
-            .take(1)
-            .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
+            .get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?
+            .unwrap_or_default())
+    }
 
-        let last_finalized_block_number = finalized_blocks
-            .pop_first()
-            .unwrap_or((tables::ChainStateKey::LastFinalizedBlock, 0_u64));
-        Ok(last_finalized_block_number.1)
+    fn last_safe_block_hash(&self) -> ProviderResult<Option<BlockHash>> {
+        Ok(self.tx.get::<tables::ChainStateHashes>(tables::ChainStateKey::LastSafeBlock)?)
     }
 }
 
@@ -2849,6 +3111,12 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
             .tx
             .put::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock, block_number)?)
     }
+
+    fn save_safe_block_hash(&self, block_hash: BlockHash) -> ProviderResult<()> {
+        Ok(self
+            .tx
+            .put::<tables::ChainStateHashes>(tables::ChainStateKey::LastSafeBlock, block_hash)?)
+    }
 }
 
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
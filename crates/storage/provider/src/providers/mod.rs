@@ -2,10 +2,11 @@ use crate::{
     AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
     BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
     CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, FullExecutionDataProvider,
+    HeaderProvider, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, TreeViewer,
+    WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -34,7 +35,7 @@ use std::{
     sync::Arc,
     time::Instant,
 };
-use tracing::trace;
+use tracing::{trace, warn};
 
 mod database;
 pub use database::*;
@@ -54,6 +55,9 @@ pub use state::{
 mod bundle_state_provider;
 pub use bundle_state_provider::BundleStateProvider;
 
+mod witness;
+pub use witness::ExecutionWitnessProvider;
+
 mod chain_info;
 use chain_info::ChainInfoTracker;
 
@@ -111,16 +115,45 @@ where
 {
     /// Create a new provider using only the database and the tree, fetching the latest header from
     /// the database to initialize the provider.
+    ///
+    /// The last persisted safe and finalized blocks are restored as well, so that the `safe` and
+    /// `finalized` block tags can be served before the consensus layer sends a forkchoice update.
     pub fn new(database: ProviderFactory<DB>, tree: Arc<dyn TreeViewer>) -> ProviderResult<Self> {
         let provider = database.provider()?;
         let best: ChainInfo = provider.chain_info()?;
-        match provider.header_by_number(best.best_number)? {
-            Some(header) => {
-                drop(provider);
-                Ok(Self::with_latest(database, tree, header.seal(best.best_hash)))
+        let latest = match provider.header_by_number(best.best_number)? {
+            Some(header) => header.seal(best.best_hash),
+            None => return Err(ProviderError::HeaderNotFound(best.best_number.into())),
+        };
+
+        let finalized = match provider.last_finalized_block_number()? {
+            0 => None,
+            number => provider.sealed_header(number)?,
+        };
+        // the database only has canonical headers, so a safe block that was reorged out isn't
+        // restored
+        let safe = match provider.last_safe_block_hash()? {
+            Some(hash) => provider.header(&hash)?.map(|header| header.seal(hash)),
+            None => None,
+        };
+        // the safe block is saved when a block is finalized, before the safe block of the same
+        // forkchoice update is set, so it may be behind the finalized block
+        let safe = match (safe, &finalized) {
+            (Some(safe), Some(finalized)) if safe.number < finalized.number => {
+                Some(finalized.clone())
             }
-            None => Err(ProviderError::HeaderNotFound(best.best_number.into())),
+            (safe, _) => safe,
+        };
+        drop(provider);
+
+        let this = Self::with_latest(database, tree, latest);
+        if let Some(finalized) = finalized {
+            this.chain_info.set_finalized(finalized);
         }
+        if let Some(safe) = safe {
+            this.chain_info.set_safe(safe);
+        }
+        Ok(this)
     }
 }
 
@@ -412,6 +445,14 @@ where
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
         self.database.transaction_sender(id)
     }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        self.database.transaction_hash_by_sender_and_nonce(sender, nonce)
+    }
 }
 
 impl<DB> ReceiptProvider for BlockchainProvider<DB>
@@ -436,6 +477,15 @@ where
     ) -> ProviderResult<Vec<Receipt>> {
         self.database.receipts_by_tx_range(range)
     }
+
+    fn blocks_with_matching_logs(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<Vec<BlockNumber>>> {
+        self.database.blocks_with_matching_logs(range, addresses, topics)
+    }
 }
 
 impl<DB> ReceiptProviderIdExt for BlockchainProvider<DB>
@@ -678,7 +728,7 @@ where
 
 impl<DB> BlockchainTreeEngine for BlockchainProvider<DB>
 where
-    DB: Send + Sync,
+    DB: Database,
 {
     fn buffer_block(&self, block: SealedBlockWithSenders) -> Result<(), InsertBlockError> {
         self.tree.buffer_block(block)
@@ -693,7 +743,25 @@ where
     }
 
     fn finalize_block(&self, finalized_block: BlockNumber) -> ProviderResult<()> {
-        self.tree.finalize_block(finalized_block)
+        self.tree.finalize_block(finalized_block)?;
+
+        // the safe block is saved along with the finalized block instead of on every forkchoice
+        // update, so that it can be restored after a restart
+        if let Some(safe) = self.chain_info.get_safe_num_hash() {
+            let res = self.database.provider_rw().and_then(|provider| {
+                provider.save_safe_block_hash(safe.hash)?;
+                provider.commit().map(|_| ())
+            });
+            if let Err(err) = res {
+                warn!(
+                    target: "providers::blockchain",
+                    %err,
+                    number = safe.number,
+                    "Failed to persist safe block"
+                );
+            }
+        }
+        Ok(())
     }
 
     fn connect_buffered_blocks_to_canonical_hashes_and_finalize(
@@ -10,8 +10,8 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{
-    constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey,
-    StorageValue, B256,
+    constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment,
+    StorageKey, StorageValue, B256,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState};
@@ -126,7 +126,7 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             tracing::warn!(
                 target: "provider::historical_sp",
                 target = self.block_number,
-                "Attempt to calculate state root for an old block might result in OOM, treat carefully"
+                "Attempt to calculate state root or proof for an old block might result in OOM, treat carefully"
             );
         }
 
@@ -308,8 +308,19 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    ///
+    /// The proof is generated by overlaying the reverts since the historical block on top of the
+    /// trie of the latest state, so it gets more expensive the older the block is.
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        self.revert_state()?
+            .account_proof(self.tx, address, slots)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn witness_nodes(&self, address: Address, slots: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        self.revert_state()?
+            .witness_nodes(self.tx, address, slots)
+            .map_err(|err| ProviderError::Database(err.into()))
     }
 }
 
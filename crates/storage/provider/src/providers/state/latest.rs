@@ -8,7 +8,8 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, StaticFileSegment, StorageKey, StorageValue, B256,
+    Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment, StorageKey, StorageValue,
+    B256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{proof::Proof, updates::TrieUpdates, AccountProof, HashedPostState};
@@ -116,6 +117,12 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
             .account_proof(address, slots)
             .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
+
+    fn witness_nodes(&self, address: Address, slots: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        Ok(Proof::new(self.tx)
+            .witness_nodes(address, slots)
+            .map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
 }
 
 /// State provider for the latest state.
@@ -0,0 +1,66 @@
+use crate::{BlockReader, StateProviderFactory, TransactionVariant, WitnessReader};
+use reth_evm::execute::{BlockExecutorProvider, Executor};
+use reth_primitives::{Bytes, B256};
+use reth_revm::{database::StateProviderDatabase, witness::WitnessRecorder};
+use reth_storage_errors::provider::ProviderResult;
+use revm::db::WrapDatabaseRef;
+use tracing::debug;
+
+/// A [`WitnessReader`] that generates the execution witness of a block by re-executing it on top
+/// of the state of its parent block.
+///
+/// The witness consists of the trie nodes of all accounts and storage slots accessed by the
+/// execution, including the siblings needed to recompute the state root, and the accessed
+/// bytecodes.
+#[derive(Debug, Clone)]
+pub struct ExecutionWitnessProvider<P, E> {
+    provider: P,
+    executor: E,
+}
+
+impl<P, E> ExecutionWitnessProvider<P, E> {
+    /// Creates a new witness provider that executes blocks of the provider with the given
+    /// executor.
+    pub const fn new(provider: P, executor: E) -> Self {
+        Self { provider, executor }
+    }
+}
+
+impl<P, E> WitnessReader for ExecutionWitnessProvider<P, E>
+where
+    P: StateProviderFactory + BlockReader,
+    E: BlockExecutorProvider,
+{
+    fn has_witnesses(&self) -> bool {
+        self.provider.best_block_number().is_ok_and(|number| number > 0)
+    }
+
+    fn witness_by_block_hash(&self, block_hash: B256) -> ProviderResult<Option<Vec<Bytes>>> {
+        let Some(block) =
+            self.provider.block_with_senders(block_hash.into(), TransactionVariant::WithHash)?
+        else {
+            return Ok(None)
+        };
+        // the genesis block isn't executed
+        if block.number == 0 {
+            return Ok(None)
+        }
+        let Some(total_difficulty) = self.provider.header_td(&block_hash)? else { return Ok(None) };
+
+        let state = self.provider.history_by_block_hash(block.parent_hash)?;
+        let recorder = WitnessRecorder::new(StateProviderDatabase::new(&state));
+        if let Err(err) = self
+            .executor
+            .executor(WrapDatabaseRef(&recorder))
+            .execute((&block, total_difficulty).into())
+        {
+            debug!(target: "providers::witness", %err, %block_hash, "failed to execute block");
+            return Ok(None)
+        }
+
+        let accessed = recorder.into_accessed();
+        let mut witness = accessed.witness_nodes(&state)?.into_iter().collect::<Vec<_>>();
+        witness.extend(accessed.codes.into_values());
+        Ok(Some(witness))
+    }
+}
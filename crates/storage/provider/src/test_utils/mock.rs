@@ -4,16 +4,18 @@ use crate::{
     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, FullExecutionDataProvider, HeaderProvider,
     ReceiptProviderIdExt, RequestsProvider, StateProvider, StateProviderBox, StateProviderFactory,
     StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    WitnessReader,
 };
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber,
-    BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumber, BlockWithSenders, Bytecode, Bytes, Header, Receipt, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{updates::TrieUpdates, AccountProof};
@@ -36,6 +38,12 @@ pub struct MockEthProvider {
     pub headers: Arc<Mutex<HashMap<B256, Header>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Local execution witness store
+    pub witnesses: Arc<Mutex<HashMap<B256, Vec<Bytes>>>>,
+    /// Local receipt store
+    pub receipts: Arc<Mutex<HashMap<B256, Vec<Receipt>>>>,
+    /// Local finalized block
+    pub finalized_block: Arc<Mutex<Option<BlockNumHash>>>,
     /// Local chain spec
     pub chain_spec: Arc<ChainSpec>,
 }
@@ -46,6 +54,9 @@ impl Default for MockEthProvider {
             blocks: Default::default(),
             headers: Default::default(),
             accounts: Default::default(),
+            witnesses: Default::default(),
+            receipts: Default::default(),
+            finalized_block: Default::default(),
             chain_spec: Arc::new(reth_chainspec::ChainSpecBuilder::mainnet().build()),
         }
     }
@@ -126,6 +137,21 @@ impl MockEthProvider {
             self.add_account(address, account)
         }
     }
+
+    /// Add the execution witness of a block to local witness store
+    pub fn add_witness(&self, block_hash: B256, witness: Vec<Bytes>) {
+        self.witnesses.lock().insert(block_hash, witness);
+    }
+
+    /// Add the receipts of a block to local receipt store
+    pub fn add_receipts(&self, block_hash: B256, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(block_hash, receipts);
+    }
+
+    /// Set the finalized block
+    pub fn set_finalized_block(&self, block: BlockNumHash) {
+        *self.finalized_block.lock() = Some(block);
+    }
 }
 
 impl HeaderProvider for MockEthProvider {
@@ -346,15 +372,41 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        let hash = match block {
+            BlockHashOrNumber::Hash(hash) => hash,
+            BlockHashOrNumber::Number(num) => match self.block_hash(num)? {
+                Some(hash) => hash,
+                None => return Ok(None),
+            },
+        };
+        Ok(self.receipts.lock().get(&hash).cloned())
     }
 
     fn receipts_by_tx_range(
         &self,
-        _range: impl RangeBounds<TxNumber>,
+        range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>> {
-        Ok(vec![])
+        let blocks = self.blocks.lock();
+        let mut blocks = blocks.iter().collect::<Vec<_>>();
+        blocks.sort_by_key(|(_, block)| block.number);
+
+        let receipts = self.receipts.lock();
+        let mut first_tx_num = 0;
+        let mut range_receipts = Vec::new();
+        for (hash, block) in blocks {
+            if let Some(block_receipts) = receipts.get(hash) {
+                range_receipts.extend(
+                    (first_tx_num..)
+                        .zip(block_receipts)
+                        .filter(|(tx_num, _)| range.contains(tx_num))
+                        .map(|(_, receipt)| receipt.clone()),
+                );
+            }
+            first_tx_num += block.body.len() as TxNumber;
+        }
+
+        Ok(range_receipts)
     }
 }
 
@@ -425,7 +477,7 @@ impl BlockIdReader for MockEthProvider {
     }
 
     fn finalized_block_num_hash(&self) -> ProviderResult<Option<reth_primitives::BlockNumHash>> {
-        Ok(None)
+        Ok(*self.finalized_block.lock())
     }
 }
 
@@ -462,16 +514,23 @@ impl BlockReader for MockEthProvider {
         Ok(None)
     }
 
-    fn block_body_indices(&self, _num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
-        Ok(None)
+    fn block_body_indices(&self, num: u64) -> ProviderResult<Option<StoredBlockBodyIndices>> {
+        let lock = self.blocks.lock();
+        let Some(block) = lock.values().find(|block| block.number == num) else { return Ok(None) };
+        let first_tx_num = lock
+            .values()
+            .filter(|block| block.number < num)
+            .map(|block| block.body.len() as u64)
+            .sum();
+        Ok(Some(StoredBlockBodyIndices { first_tx_num, tx_count: block.body.len() as u64 }))
     }
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(|block| block.with_recovered_senders()))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
@@ -570,6 +629,10 @@ impl StateProvider for MockEthProvider {
     fn proof(&self, address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::new(address))
     }
+
+    fn witness_nodes(&self, _address: Address, _keys: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for MockEthProvider {
@@ -696,6 +759,16 @@ impl RequestsProvider for MockEthProvider {
     }
 }
 
+impl WitnessReader for MockEthProvider {
+    fn has_witnesses(&self) -> bool {
+        !self.witnesses.lock().is_empty()
+    }
+
+    fn witness_by_block_hash(&self, block_hash: B256) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(self.witnesses.lock().get(&block_hash).cloned())
+    }
+}
+
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
@@ -4,16 +4,16 @@ use crate::{
     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
     ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateProvider, StateProviderBox,
     StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    WithdrawalsProvider, WitnessReader,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
 use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders,
-    Bytecode, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey,
-    StorageValue, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    Bytecode, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    StorageKey, StorageValue, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash,
+    TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -329,6 +329,10 @@ impl StateProvider for NoopProvider {
     fn proof(&self, address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
         Ok(AccountProof::new(address))
     }
+
+    fn witness_nodes(&self, _address: Address, _keys: &[B256]) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
 }
 
 impl EvmEnvProvider for NoopProvider {
@@ -473,3 +477,13 @@ impl PruneCheckpointReader for NoopProvider {
         Ok(None)
     }
 }
+
+impl WitnessReader for NoopProvider {
+    fn has_witnesses(&self) -> bool {
+        false
+    }
+
+    fn witness_by_block_hash(&self, _block_hash: B256) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+}
@@ -1,14 +1,20 @@
 use reth_errors::ProviderResult;
-use reth_primitives::BlockNumber;
+use reth_primitives::{BlockHash, BlockNumber};
 
-/// Functionality to read the last known finalized block from the database.
+/// Functionality to read the last known finalized and safe blocks from the database.
 pub trait FinalizedBlockReader: Send + Sync {
     /// Returns the last finalized block number.
     fn last_finalized_block_number(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns the hash of the last saved safe block, if one was saved.
+    fn last_safe_block_hash(&self) -> ProviderResult<Option<BlockHash>>;
 }
 
-/// Functionality to write the last known finalized block to the database.
+/// Functionality to write the last known finalized and safe blocks to the database.
 pub trait FinalizedBlockWriter: Send + Sync {
     /// Saves the given finalized block number in the DB.
     fn save_finalized_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Saves the hash of the given safe block in the DB.
+    fn save_safe_block_hash(&self, block_hash: BlockHash) -> ProviderResult<()>;
 }
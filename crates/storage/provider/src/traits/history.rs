@@ -38,6 +38,35 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the log address and topic indices.
+    ///
+    /// Returns number of addresses and topics walked.
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Read the receipts of the blocks in the range and insert their log addresses and topics to
+    /// the log indices.
+    fn insert_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Clear the log address and topic indices, and the checkpoint of the stage that builds them,
+    /// so that they aren't used until the stage rebuilds them.
+    fn drop_log_indices(&self) -> ProviderResult<()>;
+
+    /// Unwind and clear the sender nonce index of the transactions in the block range.
+    ///
+    /// Returns number of transactions walked.
+    fn unwind_sender_nonce_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Read the transactions of the blocks in the range and insert their senders and nonces to the
+    /// sender nonce index.
+    fn insert_sender_nonce_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Clear the sender nonce index, and the checkpoint of the stage that builds it, so that it
+    /// isn't used until the stage rebuilds it.
+    fn drop_sender_nonce_index(&self) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -51,3 +51,6 @@ pub use trie::*;
 
 mod withdrawals;
 pub use withdrawals::*;
+
+mod witness;
+pub use witness::*;
@@ -1,7 +1,10 @@
 use crate::BlockIdReader;
-use reth_primitives::{BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, TxHash, TxNumber};
+use reth_primitives::{
+    Address, BlockHashOrNumber, BlockId, BlockNumber, BlockNumberOrTag, Receipt, TxHash, TxNumber,
+    B256,
+};
 use reth_storage_errors::provider::ProviderResult;
-use std::ops::RangeBounds;
+use std::ops::{RangeBounds, RangeInclusive};
 
 /// Client trait for fetching [Receipt] data .
 #[auto_impl::auto_impl(&, Arc)]
@@ -26,6 +29,24 @@ pub trait ReceiptProvider: Send + Sync {
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Receipt>>;
+
+    /// Get the numbers of the blocks in the range that may contain logs matching the given
+    /// addresses and topics, using the log index.
+    ///
+    /// A block matches if it contains a log of any of the addresses, and a log with any of the
+    /// topics of each position. Empty addresses or topics match any log. The returned blocks are a
+    /// superset of the blocks with matching logs, because the index doesn't record which log the
+    /// address and topics belong to.
+    ///
+    /// Returns `None` if the log index doesn't cover the range, or if there is nothing to look up.
+    fn blocks_with_matching_logs(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+        _addresses: &[Address],
+        _topics: &[Vec<B256>],
+    ) -> ProviderResult<Option<Vec<BlockNumber>>> {
+        Ok(None)
+    }
 }
 
 /// Trait extension for `ReceiptProvider`, for types that implement `BlockId` conversion.
@@ -2,8 +2,8 @@ use super::{AccountReader, BlockHashReader, BlockIdReader, StateRootProvider};
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, StorageKey,
-    StorageValue, B256, KECCAK_EMPTY, U256,
+    Address, BlockHash, BlockId, BlockNumHash, BlockNumber, BlockNumberOrTag, Bytecode, Bytes,
+    StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::AccountProof;
@@ -27,6 +27,10 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     /// Get account and storage proofs.
     fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof>;
 
+    /// Get the trie nodes needed to update the account and storage slots: the nodes of their
+    /// proofs, and the siblings of the branch nodes with two children on the proven paths.
+    fn witness_nodes(&self, address: Address, keys: &[B256]) -> ProviderResult<Vec<Bytes>>;
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
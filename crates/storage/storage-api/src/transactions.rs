@@ -78,6 +78,19 @@ pub trait TransactionsProvider: BlockNumReader + Send + Sync {
     ///
     /// Returns None if the transaction is not found.
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>>;
+
+    /// Get the hash of the canonical transaction of the sender with the given nonce, using the
+    /// sender nonce index.
+    ///
+    /// Returns None if the sender nonce index is not enabled, or if it doesn't contain the
+    /// transaction.
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        _sender: Address,
+        _nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        Ok(None)
+    }
 }
 
 ///  Client trait for fetching additional [TransactionSigned] related data.
@@ -0,0 +1,19 @@
+use reth_primitives::{Bytes, B256};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Client trait for fetching execution witnesses of blocks.
+///
+/// An execution witness contains all trie nodes and contract bytecodes that are accessed while
+/// executing a block, which allows stateless clients to re-execute the block without access to the
+/// full state.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait WitnessReader: Send + Sync {
+    /// Returns whether this provider has any witness data available.
+    ///
+    /// This is used to decide whether witnesses should be offered to peers at all.
+    fn has_witnesses(&self) -> bool;
+
+    /// Get the execution witness of the block with the given hash. Returns `None` if no witness is
+    /// available for this block.
+    fn witness_by_block_hash(&self, block_hash: B256) -> ProviderResult<Option<Vec<Bytes>>>;
+}
@@ -1,6 +1,18 @@
-use std::path::{Path, PathBuf};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
+#[cfg(feature = "otlp")]
+use opentelemetry::KeyValue;
+#[cfg(feature = "otlp")]
+use opentelemetry_otlp::WithExportConfig;
+#[cfg(feature = "otlp")]
+use opentelemetry_sdk::{propagation::TraceContextPropagator, Resource};
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
+#[cfg(feature = "otlp")]
+use std::sync::OnceLock;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
 
@@ -17,6 +29,14 @@ pub(crate) type BoxedLayer<S> = Box<dyn Layer<S> + Send + Sync>;
 
 const RETH_LOG_FILE_NAME: &str = "reth.log";
 
+/// The service name of the spans exported over OTLP.
+#[cfg(feature = "otlp")]
+const OTLP_SERVICE_NAME: &str = "reth";
+
+/// The runtime the OTLP exporter runs on.
+#[cfg(feature = "otlp")]
+static OTLP_RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
 /// Default [directives](Directive) for [`EnvFilter`] which disables high-frequency debug logs from
 /// `hyper`, `trust-dns`, `jsonrpsee-server`, and `discv5`.
 const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
@@ -60,6 +80,48 @@ impl Layers {
         Ok(())
     }
 
+    /// Adds a layer that exports spans to an OpenTelemetry collector over OTLP, e.g. Jaeger.
+    ///
+    /// The exporter runs on a dedicated runtime, because tracing is initialized before the runtime
+    /// of the node. The W3C trace context propagator is installed globally, so that the trace
+    /// context of incoming requests can be extracted.
+    ///
+    /// # Arguments
+    /// * `endpoint` - The gRPC endpoint of the collector.
+    /// * `filter` - A string containing additional filter directives for this layer.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    #[cfg(feature = "otlp")]
+    pub(crate) fn otlp(&mut self, endpoint: &str, filter: &str) -> eyre::Result<()> {
+        let otlp_filter = build_env_filter(None, filter)?;
+
+        let runtime = OTLP_RUNTIME.get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("otlp-exporter")
+                .enable_all()
+                .build()
+                .expect("Could not initialize OTLP exporter runtime")
+        });
+        let _guard = runtime.enter();
+
+        let tracer =
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+                .with_trace_config(opentelemetry_sdk::trace::config().with_resource(Resource::new(
+                    [KeyValue::new("service.name", OTLP_SERVICE_NAME)],
+                )))
+                .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let layer =
+            tracing_opentelemetry::layer().with_tracer(tracer).with_filter(otlp_filter).boxed();
+        self.inner.push(layer);
+        Ok(())
+    }
+
     /// Adds a stdout layer with specified formatting and filtering.
     ///
     /// # Type Parameters
@@ -107,6 +169,31 @@ impl Layers {
         self.inner.push(layer);
         Ok(guard)
     }
+
+    /// Adds a layer that writes the events of a target to a dedicated file, as JSON lines.
+    ///
+    /// The events are written synchronously and unbuffered, since the dedicated files are meant
+    /// for rare events, so that no worker guard needs to be held to flush them. The file is only
+    /// created once the first event of the target is written.
+    ///
+    /// # Arguments
+    /// * `target` - The target whose events are written to the file.
+    /// * `file_info` - Information about the file including path and rotation strategy.
+    ///
+    /// # Returns
+    /// An `eyre::Result<()>` indicating the success or failure of the operation.
+    pub(crate) fn target_file(&mut self, target: &str, file_info: FileInfo) -> eyre::Result<()> {
+        let filter = EnvFilter::try_new(target)?;
+        let writer = Mutex::new(LazyFileWriter { file_info, appender: None });
+        let layer = tracing_subscriber::fmt::layer()
+            .json()
+            .with_ansi(false)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed();
+        self.inner.push(layer);
+        Ok(())
+    }
 }
 
 /// Holds configuration information for file logging.
@@ -126,6 +213,12 @@ impl FileInfo {
         Self { dir, file_name: RETH_LOG_FILE_NAME.to_string(), max_size_bytes, max_files }
     }
 
+    /// Sets the name of the log file.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = file_name.into();
+        self
+    }
+
     /// Creates the log directory if it doesn't exist.
     ///
     /// # Returns
@@ -154,6 +247,36 @@ impl FileInfo {
         );
         (writer, guard)
     }
+
+    /// Creates a writer that writes to the log file without buffering.
+    fn create_unbuffered_writer(&self) -> io::Result<RollingFileAppender<RollingConditionBasic>> {
+        RollingFileAppender::new_with_buffer_capacity(
+            self.create_log_dir().join(&self.file_name),
+            RollingConditionBasic::new().max_size(self.max_size_bytes),
+            self.max_files,
+            0,
+        )
+    }
+}
+
+/// A writer that creates its unbuffered log file on the first write.
+struct LazyFileWriter {
+    file_info: FileInfo,
+    appender: Option<RollingFileAppender<RollingConditionBasic>>,
+}
+
+impl io::Write for LazyFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let appender = match &mut self.appender {
+            Some(appender) => appender,
+            appender @ None => appender.insert(self.file_info.create_unbuffered_writer()?),
+        };
+        io::Write::write(appender, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.appender.as_mut().map_or(Ok(()), io::Write::flush)
+    }
 }
 
 /// Builds an environment filter for logging.
@@ -65,21 +65,32 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 ///  Tracer for application logging.
 ///
 ///  Manages the configuration and initialization of logging layers,
-/// including standard output, optional journald, and optional file logging.
+/// including standard output, optional journald, optional file logging, and optional export of
+/// spans over OTLP.
 #[derive(Debug, Clone)]
 pub struct RethTracer {
     stdout: LayerInfo,
     journald: Option<String>,
     file: Option<(LayerInfo, FileInfo)>,
+    #[cfg(feature = "otlp")]
+    otlp: Option<(String, String)>,
+    target_files: Vec<(String, FileInfo)>,
 }
 
 impl RethTracer {
     ///  Constructs a new `Tracer` with default settings.
     ///
     ///  Initializes with default stdout layer configuration.
-    ///  Journald and file layers are not set by default.
+    ///  Journald, file and OTLP layers are not set by default.
     pub fn new() -> Self {
-        Self { stdout: LayerInfo::default(), journald: None, file: None }
+        Self {
+            stdout: LayerInfo::default(),
+            journald: None,
+            file: None,
+            #[cfg(feature = "otlp")]
+            otlp: None,
+            target_files: Vec::new(),
+        }
     }
 
     ///  Sets a custom configuration for the stdout layer.
@@ -109,6 +120,30 @@ impl RethTracer {
         self.file = Some((config, file_info));
         self
     }
+
+    ///  Sets the endpoint of the OpenTelemetry collector that spans are exported to, and the
+    ///  filter of the exported spans.
+    ///
+    ///  # Arguments
+    ///  * `endpoint` - The gRPC endpoint of the collector.
+    ///  * `filter` - The `filter` to use for the OTLP layer.
+    #[cfg(feature = "otlp")]
+    pub fn with_otlp(mut self, endpoint: String, filter: String) -> Self {
+        self.otlp = Some((endpoint, filter));
+        self
+    }
+
+    ///  Writes the events of the given target to a dedicated file, as JSON lines.
+    ///
+    ///  The file is created once the first event of the target is written.
+    ///
+    ///  # Arguments
+    ///  * `target` - The target whose events are written to the file.
+    ///  * `file_info` - The `FileInfo` containing details about the file.
+    pub fn with_target_file(mut self, target: String, file_info: FileInfo) -> Self {
+        self.target_files.push((target, file_info));
+        self
+    }
 }
 
 impl Default for RethTracer {
@@ -183,7 +218,7 @@ impl Tracer for RethTracer {
     ///  Initializes the logging system based on the configured layers.
     ///
     ///  This method sets up the global tracing subscriber with the specified
-    ///  stdout, journald, and file layers.
+    ///  stdout, journald, file, and OTLP layers, and the dedicated files of targets.
     ///
     ///  The default layer is stdout.
     ///
@@ -204,6 +239,15 @@ impl Tracer for RethTracer {
             layers.journald(&config)?;
         }
 
+        #[cfg(feature = "otlp")]
+        if let Some((endpoint, filter)) = self.otlp {
+            layers.otlp(&endpoint, &filter)?;
+        }
+
+        for (target, file_info) in self.target_files {
+            layers.target_file(&target, file_info)?;
+        }
+
         let file_guard = if let Some((config, file_info)) = self.file {
             Some(layers.file(config.format, &config.filters, file_info)?)
         } else {
@@ -217,6 +261,16 @@ impl Tracer for RethTracer {
     }
 }
 
+///  Flushes the spans buffered by the OTLP exporter and shuts it down.
+///
+///  This should be called before the process exits, otherwise the spans that haven't been exported
+///  yet are lost. Does nothing if spans aren't exported over OTLP.
+#[allow(clippy::missing_const_for_fn)]
+pub fn shutdown_otlp() {
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
 ///  Initializes a tracing subscriber for tests.
 ///
 ///  The filter is configurable via `RUST_LOG`.
@@ -1,6 +1,9 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::blobstore::{
+    BlobAndProof, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+    VersionedHashIndex,
+};
 use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
@@ -80,6 +83,7 @@ impl BlobStore for DiskFileBlobStore {
         let mut subsize = 0;
         debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from disk");
         for tx in txs_to_delete {
+            self.inner.versioned_hashes.remove(&tx);
             let path = self.inner.blob_disk_file(tx);
             let filesize = fs::metadata(&path).map_or(0, |meta| meta.len());
             match fs::remove_file(&path) {
@@ -124,6 +128,24 @@ impl BlobStore for DiskFileBlobStore {
         self.inner.get_exact(txs)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        if versioned_hashes.is_empty() {
+            return Ok(Vec::new())
+        }
+        self.inner.versioned_hashes.get_blobs(self, versioned_hashes)
+    }
+
+    fn get_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+        let Some(tx) = self.inner.versioned_hashes.get_tx(&versioned_hash) else { return Ok(None) };
+        Ok(self.get(tx)?.map(|sidecar| (tx, sidecar)))
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
@@ -136,6 +158,7 @@ impl BlobStore for DiskFileBlobStore {
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
     blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
+    versioned_hashes: VersionedHashIndex,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
@@ -147,6 +170,7 @@ impl DiskFileBlobStoreInner {
         Self {
             blob_dir,
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
+            versioned_hashes: Default::default(),
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
@@ -176,6 +200,7 @@ impl DiskFileBlobStoreInner {
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
+        self.versioned_hashes.insert(tx, &data);
         self.blob_cache.lock().insert(tx, data);
         let size = self.write_one_encoded(tx, &buf)?;
 
@@ -198,6 +223,7 @@ impl DiskFileBlobStoreInner {
         {
             let mut cache = self.blob_cache.lock();
             for (tx, data) in txs {
+                self.versioned_hashes.insert(tx, &data);
                 cache.insert(tx, data);
             }
         }
@@ -445,6 +471,7 @@ pub enum OpenDiskFileBlobStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_eips::eip4844::{Blob, Bytes48};
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -499,4 +526,43 @@ mod tests {
         assert_eq!(store.data_size_hint(), Some(0));
         assert_eq!(store.inner.size_tracker.num_blobs.load(Ordering::Relaxed), 0);
     }
+
+    #[test]
+    fn disk_get_by_versioned_hashes() {
+        let (store, _dir) = tmp_store();
+
+        let sidecar = |byte: u8| BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(byte), Blob::repeat_byte(byte + 1)],
+            commitments: vec![Bytes48::repeat_byte(byte), Bytes48::repeat_byte(byte + 1)],
+            proofs: vec![Bytes48::repeat_byte(byte + 2), Bytes48::repeat_byte(byte + 3)],
+        };
+        let (tx1, tx2) = (TxHash::with_last_byte(1), TxHash::with_last_byte(2));
+        let (sidecar1, sidecar2) = (sidecar(1), sidecar(10));
+        store.insert(tx1, sidecar1.clone()).unwrap();
+        store.insert_all(vec![(tx2, sidecar2.clone())]).unwrap();
+        store.clear_cache();
+
+        let hashes1 = sidecar1.versioned_hashes().collect::<Vec<_>>();
+        let hashes2 = sidecar2.versioned_hashes().collect::<Vec<_>>();
+        let blobs = store
+            .get_by_versioned_hashes(&[hashes2[1], B256::ZERO, hashes1[0], hashes1[0]])
+            .unwrap();
+        let expected = |sidecar: &BlobTransactionSidecar, index: usize| {
+            Some(BlobAndProof {
+                blob: Box::new(sidecar.blobs[index]),
+                proof: sidecar.proofs[index],
+            })
+        };
+        assert_eq!(
+            blobs,
+            vec![expected(&sidecar2, 1), None, expected(&sidecar1, 0), expected(&sidecar1, 0)]
+        );
+        assert_eq!(store.get_by_versioned_hash(hashes2[1]).unwrap(), Some((tx2, sidecar2.clone())));
+        assert_eq!(store.get_by_versioned_hash(B256::ZERO).unwrap(), None);
+
+        store.delete(tx1).unwrap();
+        store.cleanup();
+        let blobs = store.get_by_versioned_hashes(&[hashes1[1], hashes2[0]]).unwrap();
+        assert_eq!(blobs, vec![None, expected(&sidecar2, 0)]);
+    }
 }
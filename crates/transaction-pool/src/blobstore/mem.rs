@@ -1,5 +1,6 @@
 use crate::blobstore::{
-    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecar,
+    BlobAndProof, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+    BlobTransactionSidecar, VersionedHashIndex,
 };
 use parking_lot::RwLock;
 use reth_primitives::B256;
@@ -15,6 +16,8 @@ pub struct InMemoryBlobStore {
 struct InMemoryBlobStoreInner {
     /// Storage for all blob data.
     store: RwLock<HashMap<B256, BlobTransactionSidecar>>,
+    /// The transactions of the stored blobs by versioned hash.
+    versioned_hashes: VersionedHashIndex,
     size_tracker: BlobStoreSize,
 }
 
@@ -27,6 +30,7 @@ impl PartialEq for InMemoryBlobStoreInner {
 impl BlobStore for InMemoryBlobStore {
     fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
+        self.inner.versioned_hashes.insert(tx, &data);
         self.inner.size_tracker.add_size(insert_size(&mut store, tx, data));
         self.inner.size_tracker.update_len(store.len());
         Ok(())
@@ -39,6 +43,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut store = self.inner.store.write();
         let mut total_add = 0;
         for (tx, data) in txs {
+            self.inner.versioned_hashes.insert(tx, &data);
             let add = insert_size(&mut store, tx, data);
             total_add += add;
         }
@@ -49,6 +54,7 @@ impl BlobStore for InMemoryBlobStore {
 
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
+        self.inner.versioned_hashes.remove(&tx);
         let sub = remove_size(&mut store, &tx);
         self.inner.size_tracker.sub_size(sub);
         self.inner.size_tracker.update_len(store.len());
@@ -62,6 +68,7 @@ impl BlobStore for InMemoryBlobStore {
         let mut store = self.inner.store.write();
         let mut total_sub = 0;
         for tx in txs {
+            self.inner.versioned_hashes.remove(&tx);
             total_sub += remove_size(&mut store, &tx);
         }
         self.inner.size_tracker.sub_size(total_sub);
@@ -113,6 +120,21 @@ impl BlobStore for InMemoryBlobStore {
         Ok(items)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        self.inner.versioned_hashes.get_blobs(self, versioned_hashes)
+    }
+
+    fn get_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+        let Some(tx) = self.inner.versioned_hashes.get_tx(&versioned_hash) else { return Ok(None) };
+        Ok(self.get(tx)?.map(|sidecar| (tx, sidecar)))
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
@@ -1,10 +1,13 @@
 //! Storage for blob data of EIP4844 transactions.
 
+use alloy_eips::eip4844::{Blob, Bytes48};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
+use parking_lot::RwLock;
 use reth_primitives::{BlobTransactionSidecar, B256};
 use std::{
+    collections::HashMap,
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
 };
@@ -64,6 +67,22 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Returns an error if any of the blobs are not found in the blob store.
     fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 
+    /// Returns the blobs with their proofs for the given versioned hashes, in the order they were
+    /// requested.
+    ///
+    /// Blobs that are not in the store are `None`.
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError>;
+
+    /// Returns the hash and the [`BlobTransactionSidecar`] of the transaction that carries the
+    /// blob with the given versioned hash.
+    fn get_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(B256, BlobTransactionSidecar)>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -71,6 +90,87 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn blobs_len(&self) -> usize;
 }
 
+/// A blob with its KZG proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob.
+    pub blob: Box<Blob>,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
+/// Maps the versioned hashes of the blobs in a store to the transactions that carry them.
+#[derive(Debug, Default)]
+pub(crate) struct VersionedHashIndex {
+    inner: RwLock<VersionedHashIndexInner>,
+}
+
+#[derive(Debug, Default)]
+struct VersionedHashIndexInner {
+    /// The transaction of every versioned hash.
+    txs: HashMap<B256, B256>,
+    /// The versioned hashes of every transaction.
+    versioned_hashes: HashMap<B256, Vec<B256>>,
+}
+
+impl VersionedHashIndex {
+    /// Indexes the versioned hashes of the blobs of the transaction.
+    pub(crate) fn insert(&self, tx: B256, sidecar: &BlobTransactionSidecar) {
+        let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        let mut inner = self.inner.write();
+        for versioned_hash in &versioned_hashes {
+            inner.txs.insert(*versioned_hash, tx);
+        }
+        inner.versioned_hashes.insert(tx, versioned_hashes);
+    }
+
+    /// Removes the versioned hashes of the blobs of the transaction.
+    pub(crate) fn remove(&self, tx: &B256) {
+        let mut inner = self.inner.write();
+        let Some(versioned_hashes) = inner.versioned_hashes.remove(tx) else { return };
+        for versioned_hash in versioned_hashes {
+            // the blob may also be carried by a transaction that was inserted later
+            if inner.txs.get(&versioned_hash) == Some(tx) {
+                inner.txs.remove(&versioned_hash);
+            }
+        }
+    }
+
+    /// Returns the transaction that carries the blob with the versioned hash.
+    pub(crate) fn get_tx(&self, versioned_hash: &B256) -> Option<B256> {
+        self.inner.read().txs.get(versioned_hash).copied()
+    }
+
+    /// Looks up the blobs of the versioned hashes in the sidecars of their transactions.
+    pub(crate) fn get_blobs<S: BlobStore + ?Sized>(
+        &self,
+        store: &S,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let txs = {
+            let inner = self.inner.read();
+            versioned_hashes.iter().map(|hash| inner.txs.get(hash).copied()).collect::<Vec<_>>()
+        };
+        let mut unique_txs = txs.iter().flatten().copied().collect::<Vec<_>>();
+        unique_txs.sort_unstable();
+        unique_txs.dedup();
+        let sidecars = store.get_all(unique_txs)?.into_iter().collect::<HashMap<_, _>>();
+
+        Ok(versioned_hashes
+            .iter()
+            .zip(txs)
+            .map(|(versioned_hash, tx)| {
+                let sidecar = sidecars.get(&tx?)?;
+                let index = sidecar.versioned_hashes().position(|hash| hash == *versioned_hash)?;
+                Some(BlobAndProof {
+                    blob: Box::new(sidecar.blobs[index]),
+                    proof: sidecar.proofs[index],
+                })
+            })
+            .collect())
+    }
+}
+
 /// Error variants that can occur when interacting with a blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum BlobStoreError {
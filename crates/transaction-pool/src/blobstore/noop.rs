@@ -1,4 +1,6 @@
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobTransactionSidecar};
+use crate::blobstore::{
+    BlobAndProof, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobTransactionSidecar,
+};
 use reth_primitives::B256;
 
 /// A blobstore implementation that does nothing
@@ -49,6 +51,20 @@ impl BlobStore for NoopBlobStore {
         Err(BlobStoreError::MissingSidecar(txs[0]))
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
+
+    fn get_by_versioned_hash(
+        &self,
+        _versioned_hash: B256,
+    ) -> Result<Option<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+        Ok(None)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(0)
     }
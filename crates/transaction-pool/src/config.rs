@@ -1,6 +1,6 @@
 use crate::{PoolSize, TransactionOrigin};
 use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
@@ -10,6 +10,12 @@ pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 /// The default maximum allowed size of the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 
+/// The default maximum allowed number of transactions in all subpools combined.
+pub const TXPOOL_MAX_TXS_DEFAULT: usize = 4 * TXPOOL_SUBPOOL_MAX_TXS_DEFAULT;
+
+/// The default maximum allowed size of all subpools combined.
+pub const TXPOOL_MAX_SIZE_MB_DEFAULT: usize = 4 * TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT;
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -18,6 +24,18 @@ pub const DEFAULT_PRICE_BUMP: u128 = 10;
 /// This enforces that a blob transaction requires a 100% price bump to be replaced
 pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 
+/// Default occupancy (in %) of the pool above which the dynamic fee floor is raised.
+pub const DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY: u64 = 80;
+
+/// Default occupancy (in %) of the pool below which the dynamic fee floor is lowered.
+pub const DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY: u64 = 50;
+
+/// Default floor the dynamic fee floor starts from when it's first raised: 1 gwei.
+pub const DEFAULT_FEE_FLOOR_MIN: u128 = 1_000_000_000;
+
+/// Default maximum of the dynamic fee floor: 500 gwei.
+pub const DEFAULT_FEE_FLOOR_MAX: u128 = 500_000_000_000;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -29,6 +47,11 @@ pub struct PoolConfig {
     pub queued_limit: SubPoolLimit,
     /// Max number of transactions in the blob sub-pool
     pub blob_limit: SubPoolLimit,
+    /// Max number of transactions in all sub-pools combined.
+    ///
+    /// If exceeded, transactions are evicted from the queued, basefee, blob and pending sub-pools,
+    /// in that order.
+    pub total_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
@@ -36,6 +59,25 @@ pub struct PoolConfig {
     /// How to handle locally received transactions:
     /// [`TransactionOrigin::Local`](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// How long a transaction may stay pending before it's dropped, counted from when it became
+    /// pending.
+    ///
+    /// Local transactions are exempt, unless the local exemptions are disabled.
+    pub pending_lifetime: Option<Duration>,
+    /// How long a transaction may stay queued, or parked for its fees, before it's dropped,
+    /// counted from when it was added or stopped being pending.
+    ///
+    /// Local transactions are subject to the [`local_lifetime`](Self::local_lifetime) instead,
+    /// unless the local exemptions are disabled.
+    pub queued_lifetime: Option<Duration>,
+    /// How long a local transaction may stay queued, or parked for its fees, before it's dropped,
+    /// counted from when it was added or stopped being pending.
+    ///
+    /// Pending local transactions never expire.
+    pub local_lifetime: Option<Duration>,
+    /// The controller of the fee floor that non-local transactions must pay to enter the pool,
+    /// disabled if unset.
+    pub fee_floor: Option<FeeFloorConfig>,
 }
 
 impl PoolConfig {
@@ -45,7 +87,23 @@ impl PoolConfig {
         self.blob_limit.is_exceeded(pool_size.blob, pool_size.blob_size) ||
             self.pending_limit.is_exceeded(pool_size.pending, pool_size.pending_size) ||
             self.basefee_limit.is_exceeded(pool_size.basefee, pool_size.basefee_size) ||
-            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size)
+            self.queued_limit.is_exceeded(pool_size.queued, pool_size.queued_size) ||
+            self.total_limit.is_exceeded(pool_size.total, pool_size.total_size())
+    }
+
+    /// Returns the occupancy (in %) of the fullest sub-pool, or of all sub-pools combined, by
+    /// either number or size of transactions.
+    pub fn occupancy(&self, pool_size: PoolSize) -> u64 {
+        [
+            self.pending_limit.occupancy(pool_size.pending, pool_size.pending_size),
+            self.basefee_limit.occupancy(pool_size.basefee, pool_size.basefee_size),
+            self.queued_limit.occupancy(pool_size.queued, pool_size.queued_size),
+            self.blob_limit.occupancy(pool_size.blob, pool_size.blob_size),
+            self.total_limit.occupancy(pool_size.total, pool_size.total_size()),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
     }
 }
 
@@ -56,9 +114,17 @@ impl Default for PoolConfig {
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
             blob_limit: Default::default(),
+            total_limit: SubPoolLimit::new(
+                TXPOOL_MAX_TXS_DEFAULT,
+                TXPOOL_MAX_SIZE_MB_DEFAULT * 1024 * 1024,
+            ),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
+            pending_lifetime: None,
+            queued_lifetime: None,
+            local_lifetime: None,
+            fee_floor: None,
         }
     }
 }
@@ -83,6 +149,13 @@ impl SubPoolLimit {
     pub const fn is_exceeded(&self, txs: usize, size: usize) -> bool {
         self.max_txs < txs || self.max_size < size
     }
+
+    /// Returns the occupancy (in %) of the limits by either number or size of transactions.
+    #[inline]
+    pub fn occupancy(&self, txs: usize, size: usize) -> u64 {
+        let percent = |used: usize, max: usize| (used as u128 * 100 / max.max(1) as u128) as u64;
+        percent(txs, self.max_txs).max(percent(size, self.max_size))
+    }
 }
 
 impl Default for SubPoolLimit {
@@ -124,6 +197,98 @@ impl Default for PriceBumpConfig {
     }
 }
 
+/// Configuration of the controller that raises the fee floor of the pool as it fills up, and
+/// lowers it as it drains.
+///
+/// The floor is the minimum tip per gas at the base fee of the head block that non-local
+/// transactions must pay to enter the pool. While the [occupancy](PoolConfig::occupancy) of the
+/// pool is at or above `raise_occupancy`, the floor is raised by `adjustment` percent each
+/// `interval`, starting from `min_floor`. While it's below `lower_occupancy`, the floor is
+/// lowered by the same percentage, and dropped once it falls below `min_floor`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FeeFloorConfig {
+    /// Occupancy (in %) at or above which the floor is raised.
+    pub raise_occupancy: u64,
+    /// Occupancy (in %) below which the floor is lowered.
+    pub lower_occupancy: u64,
+    /// Change of the floor (in %) per adjustment.
+    pub adjustment: u128,
+    /// The floor set when it's first raised.
+    pub min_floor: u128,
+    /// The maximum floor.
+    pub max_floor: u128,
+    /// How often the floor is adjusted.
+    pub interval: Duration,
+}
+
+impl FeeFloorConfig {
+    /// Ensures that the occupancies and floors are ordered, without which the floor would swing
+    /// between its maximum and zero.
+    pub const fn validate(&self) -> Result<(), FeeFloorConfigError> {
+        if self.lower_occupancy > self.raise_occupancy {
+            return Err(FeeFloorConfigError::Occupancy {
+                lower: self.lower_occupancy,
+                raise: self.raise_occupancy,
+            })
+        }
+        if self.min_floor > self.max_floor {
+            return Err(FeeFloorConfigError::Floor { min: self.min_floor, max: self.max_floor })
+        }
+        Ok(())
+    }
+
+    /// Returns the floor that follows the current floor at the given occupancy (in %).
+    pub fn next_floor(&self, floor: u128, occupancy: u64) -> u128 {
+        let step = (floor.saturating_mul(self.adjustment) / 100).max(1);
+        if occupancy >= self.raise_occupancy {
+            floor.saturating_add(step).max(self.min_floor).min(self.max_floor)
+        } else if occupancy < self.lower_occupancy {
+            let floor = floor.saturating_sub(step);
+            if floor < self.min_floor {
+                0
+            } else {
+                floor.min(self.max_floor)
+            }
+        } else {
+            floor.min(self.max_floor)
+        }
+    }
+}
+
+impl Default for FeeFloorConfig {
+    fn default() -> Self {
+        Self {
+            raise_occupancy: DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY,
+            lower_occupancy: DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY,
+            adjustment: 12,
+            min_floor: DEFAULT_FEE_FLOOR_MIN,
+            max_floor: DEFAULT_FEE_FLOOR_MAX,
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// An invalid [`FeeFloorConfig`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, thiserror::Error)]
+pub enum FeeFloorConfigError {
+    /// The floor is lowered at a higher occupancy than it's raised at.
+    #[error("fee floor lower occupancy {lower}% exceeds raise occupancy {raise}%")]
+    Occupancy {
+        /// The occupancy below which the floor is lowered.
+        lower: u64,
+        /// The occupancy at or above which the floor is raised.
+        raise: u64,
+    },
+    /// The minimum floor exceeds the maximum floor.
+    #[error("minimum fee floor {min} exceeds maximum fee floor {max}")]
+    Floor {
+        /// The minimum floor.
+        min: u128,
+        /// The maximum floor.
+        max: u128,
+    },
+}
+
 /// Configuration options for the locally received transactions:
 /// [`TransactionOrigin::Local`](crate::TransactionOrigin)
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -225,4 +390,47 @@ mod tests {
         // now this should be above the limits
         assert!(config.is_exceeded(pool_size));
     }
+
+    #[test]
+    fn test_pool_occupancy() {
+        let config = PoolConfig::default();
+        assert_eq!(config.occupancy(PoolSize::default()), 0);
+
+        let pool_size = PoolSize {
+            pending: config.pending_limit.max_txs / 2,
+            queued_size: config.queued_limit.max_size * 9 / 10,
+            ..Default::default()
+        };
+        assert_eq!(config.occupancy(pool_size), 90);
+    }
+
+    #[test]
+    fn test_fee_floor_adjustment() {
+        let config = FeeFloorConfig { min_floor: 100, max_floor: 150, ..Default::default() };
+
+        // nothing changes while the pool is neither full nor drained
+        assert_eq!(config.next_floor(0, 60), 0);
+        assert_eq!(config.next_floor(120, 60), 120);
+
+        // the floor starts at the minimum and is raised up to the maximum
+        assert_eq!(config.next_floor(0, 80), 100);
+        assert_eq!(config.next_floor(100, 80), 112);
+        assert_eq!(config.next_floor(140, 100), 150);
+
+        // the floor is lowered and dropped once below the minimum
+        assert_eq!(config.next_floor(150, 10), 132);
+        assert_eq!(config.next_floor(112, 10), 0);
+    }
+
+    #[test]
+    fn test_fee_floor_config_validation() {
+        assert_eq!(FeeFloorConfig::default().validate(), Ok(()));
+
+        let config =
+            FeeFloorConfig { lower_occupancy: 90, raise_occupancy: 80, ..Default::default() };
+        assert_eq!(config.validate(), Err(FeeFloorConfigError::Occupancy { lower: 90, raise: 80 }));
+
+        let config = FeeFloorConfig { min_floor: 2, max_floor: 1, ..Default::default() };
+        assert_eq!(config.validate(), Err(FeeFloorConfigError::Floor { min: 2, max: 1 }));
+    }
 }
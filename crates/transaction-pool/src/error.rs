@@ -40,9 +40,10 @@ pub enum PoolErrorKind {
     /// Same transaction already imported
     #[error("already imported")]
     AlreadyImported,
-    /// Thrown if a replacement transaction's gas price is below the already imported transaction
-    #[error("insufficient gas price to replace existing transaction")]
-    ReplacementUnderpriced,
+    /// Thrown if a replacement transaction's gas price doesn't exceed the price of the already
+    /// imported transaction by more than the configured price bump (in %).
+    #[error("insufficient gas price to replace existing transaction, price bump must exceed {0}%")]
+    ReplacementUnderpriced(u128),
     /// The fee cap of the transaction is below the minimum fee cap determined by the protocol
     #[error("transaction feeCap {0} below chain minimum")]
     FeeCapBelowMinimumProtocolFeeCap(u128),
@@ -98,7 +99,7 @@ impl PoolError {
                 // already imported but not bad
                 false
             }
-            PoolErrorKind::ReplacementUnderpriced => {
+            PoolErrorKind::ReplacementUnderpriced(_) => {
                 // already imported but not bad
                 false
             }
@@ -205,6 +206,10 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the sender or the recipient of the transaction isn't allowed by the configured
+    /// address lists.
+    #[error("address {0} is not allowed")]
+    AddressNotAllowed(Address),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -261,6 +266,10 @@ impl InvalidPoolTransactionError {
             }
             Self::IntrinsicGasTooLow => true,
             Self::Overdraft => false,
+            Self::AddressNotAllowed(_) => {
+                // local setting
+                false
+            }
             Self::Other(err) => err.is_bad_transaction(),
             Self::Eip4844(eip4844_err) => {
                 match eip4844_err {
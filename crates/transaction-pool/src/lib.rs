@@ -153,18 +153,22 @@
 use crate::{identifier::TransactionId, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, B256, U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
-    blobstore::{BlobStore, BlobStoreError},
+    blobstore::{BlobAndProof, BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        FeeFloorConfig, FeeFloorConfigError, LocalTransactionConfig, PoolConfig, PriceBumpConfig,
+        SubPoolLimit, DEFAULT_FEE_FLOOR_LOWER_OCCUPANCY, DEFAULT_FEE_FLOOR_MAX,
+        DEFAULT_FEE_FLOOR_MIN, DEFAULT_FEE_FLOOR_RAISE_OCCUPANCY, DEFAULT_PRICE_BUMP,
+        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_MAX_TXS_DEFAULT, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
@@ -451,6 +455,12 @@ where
         self.pool.all_transactions()
     }
 
+    fn all_transactions_with_subpool(
+        &self,
+    ) -> Vec<(Arc<ValidPoolTransaction<Self::Transaction>>, SubPool)> {
+        self.pool.get_pool_data().all_transactions_with_subpool()
+    }
+
     fn remove_transactions(
         &self,
         hashes: Vec<TxHash>,
@@ -484,6 +494,10 @@ where
         self.pool.get_transactions_by_sender(sender)
     }
 
+    fn sender_report(&self, sender: Address) -> Option<SenderReport<Self::Transaction>> {
+        self.pool.sender_report(sender)
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         sender: Address,
@@ -522,6 +536,20 @@ where
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get_exact(tx_hashes)
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
+    }
+
+    fn get_blob_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(TxHash, BlobTransactionSidecar)>, BlobStoreError> {
+        self.pool.blob_store().get_by_versioned_hash(versioned_hash)
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -544,6 +572,10 @@ where
         self.pool.update_accounts(accounts);
     }
 
+    fn remove_expired_transactions(&self) {
+        self.pool.remove_expired_transactions()
+    }
+
     fn delete_blob(&self, tx: TxHash) {
         self.pool.delete_blob(tx)
     }
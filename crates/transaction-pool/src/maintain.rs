@@ -3,10 +3,17 @@
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
     error::PoolError,
-    metrics::MaintainPoolMetrics,
+    metrics::{FeeFloorMetrics, MaintainPoolMetrics},
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo,
+    validate::FeeFloor,
+    BlockInfo, PoolConfig, TransactionOrigin,
 };
+#[cfg(feature = "serde")]
+use crate::{
+    validate::{AddressFilter, AddressLists},
+    AllPoolTransactions,
+};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -15,8 +22,8 @@ use reth_execution_types::ExecutionOutcome;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
+    TransactionSigned, TryFromRecoveredTransaction,
 };
 use reth_provider::{
     BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ProviderError,
@@ -27,7 +34,9 @@ use std::{
     borrow::Borrow,
     collections::HashSet,
     hash::{Hash, Hasher},
+    io::Write,
     path::{Path, PathBuf},
+    time::Duration,
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -57,15 +66,49 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Path to the backup file of the blob transactions and their sidecars
+    pub blob_transactions_path: Option<PathBuf>,
+    /// How often the local transactions backup is rewritten while the node is running, so that
+    /// it also survives a crash. If `None`, it's only written on shutdown.
+    pub rejournal_interval: Option<Duration>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self {
+            transactions_path: Some(transactions_path),
+            blob_transactions_path: None,
+            rejournal_interval: None,
+        }
+    }
+
+    /// Rewrites the local transactions backup at the given interval while the node is running.
+    pub const fn with_rejournal_interval(mut self, rejournal_interval: Duration) -> Self {
+        self.rejournal_interval = Some(rejournal_interval);
+        self
+    }
+
+    /// Also backs up all blob transactions of the pool, with their sidecars, to the given file.
+    ///
+    /// The backup is rewritten every [`BLOB_TXS_BACKUP_INTERVAL`] and on shutdown.
+    pub fn with_blob_txs_backup(mut self, blob_transactions_path: PathBuf) -> Self {
+        self.blob_transactions_path = Some(blob_transactions_path);
+        self
     }
 }
 
+/// How often the pool is checked for transactions that outlived the lifetime of their subpool.
+pub const EXPIRED_TRANSACTIONS_CHECK_INTERVAL: Duration = Duration::from_secs(12);
+
+/// How often the blob transactions backup is rewritten while the node is running, so that the
+/// blob transactions also survive a crash.
+pub const BLOB_TXS_BACKUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The origins of the blob transactions backup, indexed by the entries of the file.
+const BLOB_BACKUP_ORIGINS: [TransactionOrigin; 3] =
+    [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private];
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -134,6 +177,10 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
+    // expired transactions are also removed on new blocks, this covers stalled chains
+    let mut expiry_interval = tokio::time::interval(EXPIRED_TRANSACTIONS_CHECK_INTERVAL);
+    expiry_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
     // The update loop that waits for new blocks and reorgs and performs pool updated
     // Listen for new chain events and derive the update action for the pool
     loop {
@@ -221,6 +268,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                 }
                 event = ev;
             }
+            _ = expiry_interval.tick() => {
+                pool.remove_expired_transactions();
+            }
         }
 
         // handle the result of the account reload
@@ -604,28 +654,43 @@ where
     Ok(())
 }
 
+/// Writes the local transactions of the pool to the file, replacing an earlier backup.
+///
+/// The backup is written to a temporary file first, so a crash while writing leaves the earlier
+/// backup intact. If there are no local transactions, an earlier backup is removed.
 fn save_local_txs_backup<P>(pool: P, file_path: &Path)
 where
     P: TransactionPool,
 {
-    let local_transactions = pool.get_local_transactions();
+    // blob transactions can't be reinserted without their sidecars, see `save_blob_txs_backup`
+    let local_transactions = pool
+        .get_local_transactions()
+        .into_iter()
+        .filter(|tx| !tx.is_eip4844())
+        .map(|tx| tx.to_recovered_transaction().into_signed())
+        .collect::<Vec<_>>();
     if local_transactions.is_empty() {
         trace!(target: "txpool", "no local transactions to save");
+        // the transactions of an earlier backup have left the pool since
+        if file_path.exists() {
+            if let Err(err) = reth_fs_util::remove_file(file_path) {
+                warn!(target: "txpool", %err, txs_file=?file_path, "Failed to remove local transactions file");
+            }
+        }
         return
     }
 
-    let local_transactions = local_transactions
-        .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
-        .collect::<Vec<_>>();
-
     let num_txs = local_transactions.len();
     let mut buf = Vec::new();
     alloy_rlp::encode_list(&local_transactions, &mut buf);
     info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
-    let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
+    let tmp_path = file_path.with_extension("tmp");
+    let result = file_path.parent().map_or(Ok(()), reth_fs_util::create_dir_all).and_then(|_| {
+        reth_fs_util::write(&tmp_path, buf)?;
+        reth_fs_util::rename(&tmp_path, file_path)
+    });
 
-    match parent_dir.map(|_| reth_fs_util::write(file_path, buf)) {
+    match result {
         Ok(_) => {
             info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
         }
@@ -635,6 +700,142 @@ where
     }
 }
 
+/// Loads the blob transactions and their sidecars from a file, and inserts them into the
+/// transaction pool with their original origin.
+///
+/// The transactions are validated against the current head like any new transaction, so the ones
+/// that were mined or became invalid while the node was down are dropped. The file is removed
+/// after the transactions have been processed.
+async fn load_and_reinsert_blob_transactions<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", txs_file =?file_path, "Check persistent storage for saved blob transactions");
+    let data = reth_fs_util::read(file_path)?;
+    let mut transactions = BLOB_BACKUP_ORIGINS.map(|_| Vec::new());
+    let mut buf = data.as_slice();
+    while !buf.is_empty() {
+        let entry = <BlobBackupEntry as alloy_rlp::Decodable>::decode(&mut buf)?;
+        let Some(transactions) = transactions.get_mut(entry.origin as usize) else { continue };
+        if let Ok(transaction) = entry.transaction.try_into_ecrecovered() {
+            transactions.push(
+                <P as TransactionPool>::Transaction::from_recovered_pooled_transaction(transaction),
+            );
+        }
+    }
+
+    for (origin, transactions) in BLOB_BACKUP_ORIGINS.into_iter().zip(transactions) {
+        if transactions.is_empty() {
+            continue
+        }
+
+        let outcome = pool.add_transactions(origin, transactions).await;
+        let num_reinserted = outcome.iter().filter(|res| res.is_ok()).count();
+        info!(
+            target: "txpool",
+            txs_file =?file_path,
+            ?origin,
+            num_txs=%outcome.len(),
+            %num_reinserted,
+            "Reinserted blob transactions from file"
+        );
+    }
+
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Writes all blob transactions of the pool with their sidecars to the file, replacing an earlier
+/// backup.
+///
+/// The sidecars are loaded and written one at a time, to a temporary file first, so a crash
+/// while writing leaves the earlier backup intact. If there are no blob transactions, an earlier
+/// backup is removed.
+fn save_blob_txs_backup<P>(pool: P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let transactions = BLOB_BACKUP_ORIGINS
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, origin)| {
+            pool.get_transactions_by_origin(origin)
+                .into_iter()
+                .filter(|tx| tx.is_eip4844())
+                .map(move |tx| (index as u8, *tx.hash()))
+        })
+        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        trace!(target: "txpool", "no blob transactions to save");
+        // the transactions of an earlier backup have left the pool since
+        if file_path.exists() {
+            if let Err(err) = reth_fs_util::remove_file(file_path) {
+                warn!(target: "txpool", %err, txs_file=?file_path, "Failed to remove blob transactions file");
+            }
+        }
+        return
+    }
+
+    debug!(target: "txpool", txs_file =?file_path, num_txs=%transactions.len(), "Saving current blob transactions");
+    let tmp_path = file_path.with_extension("tmp");
+    let write = || -> Result<usize, FsPathError> {
+        file_path.parent().map_or(Ok(()), reth_fs_util::create_dir_all)?;
+        let file = std::fs::File::create(&tmp_path)
+            .map_err(|err| FsPathError::create_file(err, &tmp_path))?;
+        let mut writer = std::io::BufWriter::new(file);
+        let mut num_txs = 0;
+        let mut buf = Vec::new();
+        for (origin, hash) in transactions {
+            // the transaction may have left the pool since
+            let Some(transaction) = pool.get_pooled_transaction_element(hash) else { continue };
+            buf.clear();
+            alloy_rlp::Encodable::encode(&BlobBackupEntry { origin, transaction }, &mut buf);
+            writer.write_all(&buf).map_err(|err| FsPathError::write(err, &tmp_path))?;
+            num_txs += 1;
+        }
+        writer.flush().map_err(|err| FsPathError::write(err, &tmp_path))?;
+        drop(writer);
+        reth_fs_util::rename(&tmp_path, file_path)?;
+        Ok(num_txs)
+    };
+
+    match write() {
+        Ok(num_txs) => {
+            debug!(target: "txpool", txs_file=?file_path, %num_txs, "Wrote blob transactions to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write blob transactions to file");
+        }
+    }
+}
+
+/// Writes the blob transactions backup on a blocking thread, see [`save_blob_txs_backup`].
+async fn spawn_save_blob_txs_backup<P>(pool: P, file_path: PathBuf)
+where
+    P: TransactionPool + 'static,
+{
+    let task = tokio::task::spawn_blocking(move || save_blob_txs_backup(pool, &file_path));
+    if let Err(err) = task.await {
+        warn!(target: "txpool", %err, "Blob transactions backup task failed");
+    }
+}
+
+/// A blob transaction of the blob transactions backup, with its sidecar.
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct BlobBackupEntry {
+    /// The index of the origin of the transaction in [`BLOB_BACKUP_ORIGINS`].
+    origin: u8,
+    /// The transaction with its sidecar.
+    transaction: PooledTransactionsElement,
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
@@ -651,40 +852,187 @@ pub enum TransactionsBackupError {
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If a rejournal interval is configured, the local transactions are also saved periodically while
+/// the node is running.
+///
+/// If configured, all blob transactions are saved and reloaded as well, with their sidecars. They
+/// are saved every [`BLOB_TXS_BACKUP_INTERVAL`] while the node is running.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
     config: LocalTransactionBackupConfig,
 ) where
-    P: TransactionPool + Clone,
+    P: TransactionPool + Clone + 'static,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig {
+        transactions_path,
+        blob_transactions_path,
+        rejournal_interval,
+    } = config;
+    if transactions_path.is_none() && blob_transactions_path.is_none() {
         // nothing to do
         return
-    };
+    }
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
-        error!(target: "txpool", "{}", err)
+    if let Some(transactions_path) = &transactions_path {
+        if let Err(err) = load_and_reinsert_transactions(pool.clone(), transactions_path).await {
+            error!(target: "txpool", "{}", err)
+        }
+    }
+    if let Some(blob_transactions_path) = &blob_transactions_path {
+        if let Err(err) =
+            load_and_reinsert_blob_transactions(pool.clone(), blob_transactions_path).await
+        {
+            error!(target: "txpool", "{}", err)
+        }
     }
 
-    let graceful_guard = shutdown.await;
+    // the first ticks complete immediately, which writes back the reinserted transactions
+    let mut rejournal =
+        rejournal_interval.filter(|_| transactions_path.is_some()).map(tokio::time::interval);
+    let mut blob_backup =
+        blob_transactions_path.as_ref().map(|_| tokio::time::interval(BLOB_TXS_BACKUP_INTERVAL));
+    let mut shutdown = std::pin::pin!(shutdown);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            _ = tick(&mut rejournal) => {
+                if let Some(transactions_path) = &transactions_path {
+                    save_local_txs_backup(pool.clone(), transactions_path);
+                }
+            }
+            _ = tick(&mut blob_backup) => {
+                if let Some(blob_transactions_path) = &blob_transactions_path {
+                    spawn_save_blob_txs_backup(pool.clone(), blob_transactions_path.clone()).await;
+                }
+            }
+        }
+    };
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    if let Some(transactions_path) = &transactions_path {
+        save_local_txs_backup(pool.clone(), transactions_path);
+    }
+    if let Some(blob_transactions_path) = blob_transactions_path {
+        spawn_save_blob_txs_backup(pool, blob_transactions_path).await;
+    }
 
     drop(graceful_guard)
 }
 
+/// Completes at the next tick of the interval, or never if there's no interval.
+async fn tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Interval at which the file of the address lists is checked for changes.
+#[cfg(feature = "serde")]
+pub const ADDRESS_LISTS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A task that reloads the lists of the [`AddressFilter`] whenever their JSON file changes, and
+/// removes the transactions of addresses that the new lists disallow from the pool.
+///
+/// If the changed file can't be read, the previous lists are kept.
+#[cfg(feature = "serde")]
+pub async fn reload_address_lists_task<P>(pool: P, filter: AddressFilter, path: PathBuf)
+where
+    P: TransactionPool,
+{
+    let modified = |path: &Path| reth_fs_util::metadata(path).ok()?.modified().ok();
+    let mut last_modified = modified(&path);
+    let mut interval = tokio::time::interval(ADDRESS_LISTS_RELOAD_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let modified = modified(&path);
+        if modified == last_modified {
+            continue
+        }
+        last_modified = modified;
+
+        match AddressLists::from_file(&path) {
+            Ok(lists) => {
+                filter.set_lists(lists);
+                let removed = remove_disallowed_transactions(&pool, &filter);
+                info!(target: "txpool", ?path, removed, "Reloaded address lists");
+            }
+            Err(err) => {
+                warn!(target: "txpool", %err, "Failed to reload address lists, keeping the previous lists")
+            }
+        }
+    }
+}
+
+/// Removes the transactions of the addresses that the filter disallows from the pool.
+///
+/// Returns the number of removed transactions.
+#[cfg(feature = "serde")]
+fn remove_disallowed_transactions<P>(pool: &P, filter: &AddressFilter) -> usize
+where
+    P: TransactionPool,
+{
+    let AllPoolTransactions { pending, queued } = pool.all_transactions();
+    let disallowed = pending
+        .iter()
+        .chain(&queued)
+        .filter(|tx| filter.disallowed_address(tx.sender(), tx.to()).is_some())
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+    if disallowed.is_empty() {
+        return 0
+    }
+    pool.remove_transactions(disallowed).len()
+}
+
+/// A task that adjusts the [`FeeFloor`] to the occupancy of the pool, as configured by the
+/// [`FeeFloorConfig`](crate::FeeFloorConfig) of the [`PoolConfig`].
+///
+/// Returns immediately if the fee floor isn't configured.
+pub async fn fee_floor_task<P>(pool: P, fee_floor: FeeFloor, pool_config: PoolConfig)
+where
+    P: TransactionPool,
+{
+    let Some(config) = pool_config.fee_floor else { return };
+    let metrics = FeeFloorMetrics::default();
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let occupancy = pool_config.occupancy(pool.pool_size());
+        let floor = fee_floor.get();
+        let next_floor = config.next_floor(floor, occupancy);
+        match next_floor.cmp(&floor) {
+            std::cmp::Ordering::Greater => metrics.fee_floor_raises.increment(1),
+            std::cmp::Ordering::Less => metrics.fee_floor_drops.increment(1),
+            std::cmp::Ordering::Equal => {}
+        }
+        if next_floor != floor {
+            debug!(target: "txpool", occupancy, floor, next_floor, "Adjusted fee floor");
+            fee_floor.set(next_floor);
+        }
+        metrics.fee_floor.set(next_floor as f64);
+        metrics.occupancy.set(occupancy as f64);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
+        blobstore::InMemoryBlobStore,
+        test_utils::{testing_pool, MockTransaction},
+        validate::EthTransactionValidatorBuilder,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction,
     };
     use reth_chainspec::MAINNET;
     use reth_fs_util as fs;
-    use reth_primitives::{hex, PooledTransactionsElement, U256};
+    use reth_primitives::{hex, sign_message, BlobTransaction, Transaction, TxEip4844, B256, U256};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -748,4 +1096,95 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test]
+    async fn test_rejournal_local_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let txpool = testing_pool();
+        txpool.add_transaction(TransactionOrigin::Local, MockTransaction::eip1559()).await.unwrap();
+
+        save_local_txs_backup(txpool.clone(), &transactions_path);
+        assert!(transactions_path.exists());
+
+        // once the transaction left the pool, the next save removes it from the backup
+        let hashes = txpool.get_local_transactions().iter().map(|tx| *tx.hash()).collect();
+        txpool.remove_transactions(hashes);
+        save_local_txs_backup(txpool, &transactions_path);
+        assert!(!transactions_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_save_blob_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join("blob_transactions").with_extension(EXTENSION);
+        let txpool = testing_pool();
+
+        let blob_tx = MockTransaction::eip4844_with_sidecar(Default::default());
+        txpool.add_transaction(TransactionOrigin::External, blob_tx).await.unwrap();
+        txpool.add_transaction(TransactionOrigin::Local, MockTransaction::eip1559()).await.unwrap();
+
+        save_blob_txs_backup(txpool, &transactions_path);
+
+        // only the blob transaction is saved, with its origin
+        let data = fs::read(&transactions_path).unwrap();
+        let mut buf = data.as_slice();
+        let entry = <BlobBackupEntry as alloy_rlp::Decodable>::decode(&mut buf).unwrap();
+        assert!(buf.is_empty());
+        assert_eq!(BLOB_BACKUP_ORIGINS[entry.origin as usize], TransactionOrigin::External);
+        assert!(matches!(entry.transaction, PooledTransactionsElement::BlobTransaction(_)));
+    }
+
+    #[tokio::test]
+    async fn test_load_and_reinsert_blob_transactions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join("blob_transactions").with_extension(EXTENSION);
+
+        let transaction = Transaction::Eip4844(TxEip4844 {
+            chain_id: 1,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1,
+            max_fee_per_blob_gas: 1,
+            ..Default::default()
+        });
+        let signature = sign_message(B256::random(), transaction.signature_hash()).unwrap();
+        let transaction = TransactionSigned::from_transaction_and_signature(transaction, signature);
+        let blob_tx = BlobTransaction::try_from_signed(transaction, Default::default()).unwrap();
+        let hash = blob_tx.hash;
+
+        let mut data = Vec::new();
+        let transaction = PooledTransactionsElement::BlobTransaction(blob_tx);
+        alloy_rlp::Encodable::encode(&BlobBackupEntry { origin: 2, transaction }, &mut data);
+        fs::write(&transactions_path, data).unwrap();
+
+        let txpool = testing_pool();
+        load_and_reinsert_blob_transactions(txpool.clone(), &transactions_path).await.unwrap();
+
+        // the transaction is reinserted with its origin, and the backup is removed
+        let reinserted = txpool.get_transactions_by_origin(TransactionOrigin::Private);
+        assert_eq!(reinserted.len(), 1);
+        assert_eq!(*reinserted[0].hash(), hash);
+        assert!(!transactions_path.exists());
+    }
+
+    #[cfg(feature = "serde")]
+    #[tokio::test]
+    async fn test_remove_disallowed_transactions() {
+        let txpool = testing_pool();
+        let (allowed, denied) = (MockTransaction::eip1559(), MockTransaction::eip1559());
+        for tx in [allowed.clone(), denied.clone()] {
+            txpool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        let filter = AddressFilter::default();
+        assert_eq!(remove_disallowed_transactions(&txpool, &filter), 0);
+
+        filter.set_lists(AddressLists {
+            denied_senders: HashSet::from([denied.sender()]),
+            ..Default::default()
+        });
+        assert_eq!(remove_disallowed_transactions(&txpool, &filter), 1);
+        assert!(txpool.contains(allowed.hash()));
+        assert!(!txpool.contains(denied.hash()));
+    }
 }
@@ -15,6 +15,9 @@ pub struct TxPoolMetrics {
     pub(crate) invalid_transactions: Counter,
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
+    /// Number of transactions that were dropped because they stayed in the pool for too long,
+    /// including their descendants
+    pub(crate) expired_transactions: Counter,
 
     /// Number of transactions in the pending sub-pool
     pub(crate) pending_pool_transactions: Gauge,
@@ -94,6 +97,20 @@ impl MaintainPoolMetrics {
     }
 }
 
+/// Dynamic fee floor metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct FeeFloorMetrics {
+    /// The current fee floor that non-local transactions must pay to enter the pool, in wei
+    pub(crate) fee_floor: Gauge,
+    /// Occupancy (in %) of the fullest sub-pool, or of all sub-pools combined
+    pub(crate) occupancy: Gauge,
+    /// How often the fee floor was raised
+    pub(crate) fee_floor_raises: Counter,
+    /// How often the fee floor was lowered
+    pub(crate) fee_floor_drops: Counter,
+}
+
 /// All Transactions metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
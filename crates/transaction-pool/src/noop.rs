@@ -4,7 +4,7 @@
 //! to be generic over it.
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProof, BlobStoreError},
     error::PoolError,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
@@ -13,11 +13,12 @@ use crate::{
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
     EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize, PoolTransaction,
-    PooledTransactionsElement, PropagatedTransactions, TransactionEvents, TransactionOrigin,
-    TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    PooledTransactionsElement, PropagatedTransactions, SenderReport, TransactionEvents,
+    TransactionOrigin, TransactionPool, TransactionValidationOutcome, TransactionValidator,
+    ValidPoolTransaction,
 };
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, TxHash, U256};
+use reth_primitives::{Address, BlobTransactionSidecar, TxHash, B256, U256};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
@@ -203,6 +204,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn sender_report(&self, _sender: Address) -> Option<SenderReport<Self::Transaction>> {
+        None
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         _sender: Address,
@@ -242,6 +247,20 @@ impl TransactionPool for NoopTransactionPool {
         }
         Err(BlobStoreError::MissingSidecar(tx_hashes[0]))
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
+
+    fn get_blob_by_versioned_hash(
+        &self,
+        _versioned_hash: B256,
+    ) -> Result<Option<(TxHash, BlobTransactionSidecar)>, BlobStoreError> {
+        Ok(None)
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
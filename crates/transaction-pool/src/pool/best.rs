@@ -264,6 +264,89 @@ impl<I: fmt::Debug, P> fmt::Debug for BestTransactionFilter<I, P> {
     }
 }
 
+/// A [`BestTransactions`](crate::traits::BestTransactions) implementation that yields the
+/// priority transactions before all other transactions, regardless of their priority in the pool.
+///
+/// Both iterators must yield the same transactions, e.g. two iterators of the best transactions of
+/// the pool. The predicate must accept either all or none of the transactions of a sender,
+/// otherwise the descendants of the transactions of the other lane are skipped.
+pub struct BestTransactionsWithPriority<I, P> {
+    /// Yields the priority transactions.
+    priority: I,
+    /// Yields the remaining transactions.
+    rest: I,
+    /// Determines whether a transaction is a priority transaction.
+    is_priority: P,
+}
+
+impl<I, P> BestTransactionsWithPriority<I, P> {
+    /// Creates a new iterator that yields the transactions of `priority` that satisfy
+    /// `is_priority`, then the remaining transactions of `rest`.
+    pub const fn new(priority: I, rest: I, is_priority: P) -> Self {
+        Self { priority, rest, is_priority }
+    }
+}
+
+impl<I, P> Iterator for BestTransactionsWithPriority<I, P>
+where
+    I: crate::traits::BestTransactions,
+    P: FnMut(&<I as Iterator>::Item) -> bool,
+{
+    type Item = <I as Iterator>::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the priority lane is checked first on every call, so that priority transactions that
+        // arrive while the payload is built are still preferred
+        while let Some(best) = self.priority.next() {
+            if (self.is_priority)(&best) {
+                return Some(best)
+            }
+            self.priority.mark_invalid(&best);
+        }
+        loop {
+            let best = self.rest.next()?;
+            if !(self.is_priority)(&best) {
+                return Some(best)
+            }
+            self.rest.mark_invalid(&best);
+        }
+    }
+}
+
+impl<I, P> crate::traits::BestTransactions for BestTransactionsWithPriority<I, P>
+where
+    I: crate::traits::BestTransactions,
+    P: FnMut(&<I as Iterator>::Item) -> bool + Send,
+{
+    fn mark_invalid(&mut self, tx: &Self::Item) {
+        self.priority.mark_invalid(tx);
+        self.rest.mark_invalid(tx);
+    }
+
+    fn no_updates(&mut self) {
+        self.priority.no_updates();
+        self.rest.no_updates();
+    }
+
+    fn skip_blobs(&mut self) {
+        self.set_skip_blobs(true)
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.priority.set_skip_blobs(skip_blobs);
+        self.rest.set_skip_blobs(skip_blobs);
+    }
+}
+
+impl<I: fmt::Debug, P> fmt::Debug for BestTransactionsWithPriority<I, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BestTransactionsWithPriority")
+            .field("priority", &self.priority)
+            .field("rest", &self.rest)
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,4 +404,34 @@ mod tests {
         // iterator is empty
         assert!(best.next().is_none());
     }
+
+    #[test]
+    fn test_best_with_priority() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let operator = MockTransaction::eip1559().with_max_fee(10).with_priority_fee(1);
+        let user = MockTransaction::eip1559().with_max_fee(200).with_priority_fee(100);
+        for nonce in 0..2 {
+            for tx in [&operator, &user] {
+                let tx = tx.clone().rng_hash().with_nonce(nonce);
+                pool.add_transaction(Arc::new(f.validated(tx)), 0);
+            }
+        }
+
+        let operator_sender = operator.sender();
+        let is_operator =
+            |tx: &Arc<ValidPoolTransaction<MockTransaction>>| tx.sender() == operator_sender;
+        let best = BestTransactionsWithPriority::new(pool.best(), pool.best(), is_operator);
+        let yielded = best.map(|tx| (tx.sender(), tx.nonce())).collect::<Vec<_>>();
+        assert_eq!(
+            yielded,
+            vec![
+                (operator.sender(), 0),
+                (operator.sender(), 1),
+                (user.sender(), 0),
+                (user.sender(), 1)
+            ]
+        );
+    }
 }
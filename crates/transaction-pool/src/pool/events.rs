@@ -28,9 +28,12 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
-    /// Transaction was dropped due to configured limits.
+    /// Transaction was dropped due to configured limits, or because it couldn't be validated.
     Discarded(TxHash),
     /// Transaction became invalid indefinitely.
+    ///
+    /// This is also emitted for a transaction that is rejected as invalid when it's added to the
+    /// pool, these were reported as [`FullTransactionEvent::Discarded`] before.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
@@ -68,9 +71,12 @@ pub enum TransactionEvent {
     ///
     /// E.g. same (sender + nonce) pair
     Replaced(TxHash),
-    /// Transaction was dropped due to configured limits.
+    /// Transaction was dropped due to configured limits, or because it couldn't be validated.
     Discarded,
     /// Transaction became invalid indefinitely.
+    ///
+    /// This is also emitted for a transaction that is rejected as invalid when it's added to the
+    /// pool, these were reported as [`TransactionEvent::Discarded`] before.
     Invalid,
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
@@ -80,6 +86,6 @@ impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
     pub const fn is_final(&self) -> bool {
-        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
+        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded | Self::Invalid)
     }
 }
@@ -75,7 +75,7 @@ use crate::{
     },
     traits::{
         AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
-        PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        PoolTransaction, PropagatedTransactions, SenderReport, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, ChangedAccount, PoolConfig, TransactionOrdering, TransactionValidator,
@@ -103,7 +103,7 @@ use crate::{
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
     validate::ValidTransaction,
 };
-pub use best::BestTransactionFilter;
+pub use best::{BestTransactionFilter, BestTransactionsWithPriority};
 pub use blob::{blob_tx_priority, fee_delta};
 pub use events::{FullTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
@@ -407,6 +407,16 @@ where
         self.delete_discarded_blobs(discarded.iter());
     }
 
+    /// Removes the transactions that outlived the lifetime of their subpool.
+    pub(crate) fn remove_expired_transactions(&self) {
+        let expired = self.pool.write().remove_expired();
+        let mut listener = self.event_listener.write();
+        expired.iter().for_each(|tx| listener.discarded(tx.hash()));
+        drop(listener);
+
+        self.delete_discarded_blobs(expired.iter());
+    }
+
     /// Add a single validated transaction into the pool.
     ///
     /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
@@ -481,7 +491,7 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.invalid(tx.hash());
                 Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
@@ -725,6 +735,12 @@ where
         self.get_pool_data().get_transactions_by_sender(sender_id)
     }
 
+    /// Returns the state of the transactions of the given sender, see [`SenderReport`].
+    pub(crate) fn sender_report(&self, sender: Address) -> Option<SenderReport<T::Transaction>> {
+        let sender_id = self.identifiers.read().sender_id(&sender)?;
+        self.get_pool_data().sender_report(sender_id)
+    }
+
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
     pub(crate) fn get_transactions_by_origin(
         &self,
@@ -1133,9 +1149,11 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 mod tests {
     use crate::{
         blobstore::{BlobStore, InMemoryBlobStore},
+        error::InvalidPoolTransactionError,
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, FullTransactionEvent, PoolConfig, PoolTransaction, SubPoolLimit,
+        TransactionOrigin, TransactionValidationOutcome, U256,
     };
     use reth_primitives::{kzg::Blob, transaction::generate_blob_sidecar};
     use std::{fs, path::PathBuf};
@@ -1219,4 +1237,31 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    #[test]
+    fn test_rejected_transaction_events() {
+        let test_pool = &TestPoolBuilder::default().pool;
+        let mut events = test_pool.add_all_transactions_event_listener();
+
+        // a transaction that is rejected as invalid emits `Invalid`, not `Discarded`
+        let invalid = MockTransaction::eip1559();
+        let outcome = TransactionValidationOutcome::Invalid(
+            invalid.clone(),
+            InvalidPoolTransactionError::Underpriced,
+        );
+        assert!(test_pool.add_transaction(TransactionOrigin::External, outcome).is_err());
+        assert!(matches!(
+            events.events.try_recv(),
+            Ok(FullTransactionEvent::Invalid(hash)) if hash == *invalid.hash()
+        ));
+
+        // a transaction that couldn't be validated is still `Discarded`
+        let hash = *MockTransaction::eip1559().hash();
+        let outcome = TransactionValidationOutcome::Error(hash, "validation failed".into());
+        assert!(test_pool.add_transaction(TransactionOrigin::External, outcome).is_err());
+        assert!(matches!(
+            events.events.try_recv(),
+            Ok(FullTransactionEvent::Discarded(discarded)) if discarded == hash
+        ));
+    }
 }
@@ -14,8 +14,11 @@ use crate::{
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
-    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
+    traits::{
+        BestTransactionsAttributes, BlockInfo, PoolSize, SenderReport, StuckReason,
+        StuckTransaction,
+    },
+    PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, SubPoolLimit, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
 use reth_primitives::{
@@ -32,6 +35,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Instant,
 };
 use tracing::trace;
 
@@ -71,6 +75,9 @@ pub struct TxPool<T: TransactionOrdering> {
     blob_pool: BlobTransactions<T::Transaction>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
+    /// The earliest time at which a transaction can expire, the pool isn't checked for expired
+    /// transactions before then.
+    next_expiry: Option<Instant>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
 }
@@ -88,6 +95,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             blob_pool: Default::default(),
             all_transactions: AllTransactions::new(&config),
             config,
+            next_expiry: None,
             metrics: Default::default(),
         }
     }
@@ -160,7 +168,7 @@ impl<T: TransactionOrdering> TxPool<T> {
 
                         // the blob fee is too high now, unset the blob fee cap block flag
                         tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
+                        tx.update_subpool();
                         tx.subpool
                     };
                     self.add_transaction_to_subpool(to, tx);
@@ -176,7 +184,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
                         tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
                         tx.state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
+                        tx.update_subpool();
                         tx.subpool
                     };
                     self.add_transaction_to_subpool(to, tx);
@@ -205,7 +213,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
                         tx.state.remove(TxState::ENOUGH_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
+                        tx.update_subpool();
                         tx.subpool
                     };
                     self.add_transaction_to_subpool(to, tx);
@@ -222,7 +230,7 @@ impl<T: TransactionOrdering> TxPool<T> {
                         let tx =
                             self.all_transactions.txs.get_mut(tx.id()).expect("tx exists in set");
                         tx.state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
-                        tx.subpool = tx.state.into();
+                        tx.update_subpool();
                         tx.subpool
                     };
                     self.add_transaction_to_subpool(to, tx);
@@ -327,6 +335,36 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.all().chain(self.queued_pool.all()).collect()
     }
 
+    /// Returns all transactions of the pool with their subpool, ordered by sender and nonce.
+    pub(crate) fn all_transactions_with_subpool(
+        &self,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, SubPool)> {
+        self.all_transactions.txs.values().map(|tx| (tx.transaction.clone(), tx.subpool)).collect()
+    }
+
+    /// Returns the state of the transactions of the given sender, see [`SenderReport`].
+    pub(crate) fn sender_report(&self, sender: SenderId) -> Option<SenderReport<T::Transaction>> {
+        let state_nonce = self.sender_info.get(&sender)?.state_nonce;
+        let mut transactions = Vec::new();
+        let mut nonce_gaps = Vec::new();
+        let mut stuck = None;
+        let mut next_nonce = state_nonce;
+        for (id, tx) in self.all_transactions.txs_iter(sender) {
+            if id.nonce > next_nonce {
+                nonce_gaps.push(next_nonce..=id.nonce - 1);
+            }
+            if stuck.is_none() && !tx.subpool.is_pending() {
+                stuck = Some(self.all_transactions.stuck_transaction(tx, next_nonce));
+            }
+            next_nonce = id.nonce + 1;
+            transactions.push((Arc::clone(&tx.transaction), tx.subpool));
+        }
+        if transactions.is_empty() {
+            return None
+        }
+        Some(SenderReport { state_nonce, transactions, nonce_gaps, stuck })
+    }
+
     /// Returns queued and pending transactions for the specified sender
     pub fn queued_and_pending_txs_by_sender(
         &self,
@@ -399,8 +437,8 @@ impl<T: TransactionOrdering> TxPool<T> {
 
     /// Updates the entire pool after a new block was mined.
     ///
-    /// This removes all mined transactions, updates according to the new base fee and rechecks
-    /// sender allowance.
+    /// This removes all mined and expired transactions, updates according to the new base fee and
+    /// rechecks sender allowance.
     pub(crate) fn on_canonical_state_change(
         &mut self,
         block_info: BlockInfo,
@@ -419,7 +457,10 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let expired = self.remove_expired();
+
+        let UpdateOutcome { promoted, mut discarded } = self.update_accounts(changed_senders);
+        discarded.extend(expired);
 
         self.metrics.performed_state_updates.increment(1);
 
@@ -512,10 +553,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                 // Update invalid transactions metric
                 self.metrics.invalid_transactions.increment(1);
                 match err {
-                    InsertErr::Underpriced { existing: _, transaction } => Err(PoolError::new(
-                        *transaction.hash(),
-                        PoolErrorKind::ReplacementUnderpriced,
-                    )),
+                    InsertErr::Underpriced { existing: _, transaction, price_bump } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::ReplacementUnderpriced(price_bump),
+                        ))
+                    }
                     InsertErr::FeeCapBelowMinimumProtocolFeeCap { transaction, fee_cap } => {
                         Err(PoolError::new(
                             *transaction.hash(),
@@ -620,6 +663,67 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs
     }
 
+    /// Removes and returns the transactions that stayed pending, or stayed in the other subpools,
+    /// for longer than the lifetime of their subpool, and their descendants.
+    ///
+    /// The pending lifetime applies to the pending subpool, the queued lifetime to all other
+    /// subpools. Local transactions only expire if they aren't pending, after the local lifetime,
+    /// unless the local exemptions are disabled.
+    ///
+    /// The pool is only checked once the earliest transaction can expire.
+    pub(crate) fn remove_expired(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let PoolConfig { pending_lifetime, queued_lifetime, local_lifetime, .. } = self.config;
+        let Some(min_lifetime) =
+            pending_lifetime.into_iter().chain(queued_lifetime).chain(local_lifetime).min()
+        else {
+            return Vec::new()
+        };
+        let now = Instant::now();
+        if self.next_expiry.is_some_and(|next_expiry| now < next_expiry) {
+            return Vec::new()
+        }
+
+        // transactions that enter a subpool from now on expire no earlier than this
+        let mut next_expiry = now + min_lifetime;
+        let mut expired = Vec::new();
+        let local_transactions_config = &self.config.local_transactions_config;
+        for (id, tx) in &self.all_transactions.txs {
+            let transaction = &tx.transaction;
+            let is_local =
+                local_transactions_config.is_local(transaction.origin, transaction.sender());
+            let lifetime = match (tx.subpool.is_pending(), is_local) {
+                (true, false) => pending_lifetime,
+                (false, false) => queued_lifetime,
+                (true, true) => None,
+                (false, true) => local_lifetime,
+            };
+            let Some(lifetime) = lifetime else { continue };
+            let expiry = tx.subpool_since + lifetime;
+            if expiry < now {
+                expired.push(*id);
+            } else {
+                next_expiry = next_expiry.min(expiry);
+            }
+        }
+        self.next_expiry = Some(next_expiry);
+
+        let mut removed = Vec::new();
+        for id in expired {
+            // the transaction may already be removed as a descendant of an expired transaction
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+
+        if !removed.is_empty() {
+            trace!(target: "txpool", num_txs = removed.len(), "removed expired transactions");
+            self.metrics.expired_transactions.increment(removed.len() as u64);
+            self.update_size_metrics();
+        }
+        removed
+    }
+
     /// Remove the transaction from the __entire__ pool.
     ///
     /// This includes the total set of transaction and the subpool it currently resides in.
@@ -813,6 +917,44 @@ impl<T: TransactionOrdering> TxPool<T> {
             ]
         );
 
+        // Helper macro that discards the worst transactions of the pools, in the given order, until
+        // all pools combined are within the total limit
+        macro_rules! discard_worst_total {
+            ($this:ident, $removed:ident, [$($pool:ident),* $(,)*]) => {
+                $ (
+                let size = $this.size();
+                let limit = &$this.config.total_limit;
+                if limit.is_exceeded(size.total, size.total_size()) {
+                    // the limit of the subpool at which all pools combined are within the limit
+                    let excess_txs = size.total.saturating_sub(limit.max_txs);
+                    let excess_size = size.total_size().saturating_sub(limit.max_size);
+                    let subpool_limit = SubPoolLimit::new(
+                        $this.$pool.len().saturating_sub(excess_txs),
+                        $this.$pool.size().saturating_sub(excess_size),
+                    );
+
+                    trace!(
+                        target: "txpool",
+                        "discarding transactions from {} for the total limit, limit: {:?}, curr size: {}, curr len: {}",
+                        stringify!($pool),
+                        limit,
+                        size.total_size(),
+                        size.total,
+                    );
+
+                    for tx in $this.$pool.truncate_pool(subpool_limit) {
+                        $this.all_transactions.remove_transaction(tx.id());
+                        let id = *tx.id();
+                        $removed.push(tx);
+                        $this.remove_descendants(&id, &mut $removed);
+                    }
+                }
+                )*
+            };
+        }
+
+        discard_worst_total!(self, removed, [queued_pool, basefee_pool, blob_pool, pending_pool]);
+
         removed
     }
 
@@ -1154,7 +1296,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     /// `PoolUpdate` for this transaction to move it to the new sub-pool.
     fn record_subpool_update(updates: &mut Vec<PoolUpdate>, tx: &mut PoolInternalTransaction<T>) {
         let current_pool = tx.subpool;
-        tx.subpool = tx.state.into();
+        tx.update_subpool();
         if current_pool != tx.subpool {
             updates.push(PoolUpdate {
                 id: *tx.transaction.id(),
@@ -1299,7 +1441,9 @@ impl<T: PoolTransaction> AllTransactions<T> {
         if !self.local_transactions_config.is_local(transaction.origin, transaction.sender()) {
             let current_txs =
                 self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
-            if current_txs >= self.max_account_slots {
+            // a replacement doesn't occupy another slot
+            let is_replacement = self.txs.contains_key(transaction.id());
+            if current_txs >= self.max_account_slots && !is_replacement {
                 return Err(InsertErr::ExceededSenderTransactionsCapacity {
                     transaction: Arc::new(transaction),
                 })
@@ -1379,6 +1523,60 @@ impl<T: PoolTransaction> AllTransactions<T> {
         Ok(new_blob_tx)
     }
 
+    /// Returns why the given transaction isn't pending, and the fees a replacement must pay.
+    ///
+    /// The `next_nonce` is the lowest nonce of the sender after the transactions with lower nonces
+    /// in the pool, which is the missing nonce if the transaction has a nonce gap.
+    fn stuck_transaction(
+        &self,
+        tx: &PoolInternalTransaction<T>,
+        next_nonce: u64,
+    ) -> StuckTransaction<T> {
+        let transaction = &tx.transaction;
+        if tx.state.has_nonce_gap() {
+            // replacing the transaction doesn't help, the missing transaction must be sent
+            return StuckTransaction {
+                transaction: Arc::clone(transaction),
+                reason: StuckReason::NonceGap,
+                missing_nonce: Some(next_nonce),
+                min_replacement_max_fee_per_gas: None,
+                min_replacement_max_priority_fee_per_gas: None,
+                min_replacement_max_fee_per_blob_gas: None,
+            }
+        }
+
+        let reason = if !tx.state.contains(TxState::ENOUGH_BALANCE) {
+            StuckReason::InsufficientBalance
+        } else if !tx.state.contains(TxState::NOT_TOO_MUCH_GAS) {
+            StuckReason::ExceedsBlockGasLimit
+        } else if !tx.state.contains(TxState::ENOUGH_FEE_CAP_BLOCK) {
+            StuckReason::FeeCapBelowBaseFee
+        } else {
+            StuckReason::BlobFeeCapBelowBlobFee
+        };
+
+        // the lowest fees that pass `is_underpriced`
+        let price_bump = self.price_bumps.price_bump(transaction.tx_type());
+        let bumped = |fee: u128| fee * (100 + price_bump) / 100 + 1;
+        StuckTransaction {
+            transaction: Arc::clone(transaction),
+            reason,
+            missing_nonce: None,
+            min_replacement_max_fee_per_gas: Some(
+                bumped(transaction.max_fee_per_gas()).max(self.pending_fees.base_fee as u128),
+            ),
+            min_replacement_max_priority_fee_per_gas: transaction
+                .transaction
+                .max_priority_fee_per_gas()
+                .filter(|fee| *fee != 0)
+                .map(bumped),
+            min_replacement_max_fee_per_blob_gas: transaction
+                .transaction
+                .max_fee_per_blob_gas()
+                .map(|fee| bumped(fee).max(self.pending_fees.blob_fee)),
+        }
+    }
+
     /// Returns true if the replacement candidate is underpriced and can't replace the existing
     /// transaction.
     #[inline]
@@ -1522,6 +1720,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         let pool_tx = PoolInternalTransaction {
             transaction: Arc::clone(&transaction),
             subpool: state.into(),
+            subpool_since: transaction.timestamp,
             state,
             cumulative_cost,
         };
@@ -1542,6 +1741,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 if Self::is_underpriced(existing_transaction, maybe_replacement, &self.price_bumps)
                 {
                     return Err(InsertErr::Underpriced {
+                        price_bump: self.price_bumps.price_bump(existing_transaction.tx_type()),
                         transaction: pool_tx.transaction,
                         existing: *entry.get().transaction.hash(),
                     })
@@ -1614,7 +1814,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 has_parked_ancestor = !tx.state.is_pending();
 
                 // update the pool based on the state
-                tx.subpool = tx.state.into();
+                tx.update_subpool();
 
                 if inserted_tx_id.eq(id) {
                     // if it is the new transaction, track its updated state
@@ -1718,6 +1918,8 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
         transaction: Arc<ValidPoolTransaction<T>>,
         #[allow(dead_code)]
         existing: TxHash,
+        /// The price bump (in %) the replacement must exceed.
+        price_bump: u128,
     },
     /// Attempted to insert a blob transaction with a nonce gap
     BlobTxHasNonceGap { transaction: Arc<ValidPoolTransaction<T>> },
@@ -1766,6 +1968,10 @@ pub(crate) struct PoolInternalTransaction<T: PoolTransaction> {
     pub(crate) transaction: Arc<ValidPoolTransaction<T>>,
     /// The `SubPool` that currently contains this transaction.
     pub(crate) subpool: SubPool,
+    /// When the transaction was added to the pending subpool, or to one of the other subpools.
+    ///
+    /// Moves between the subpools other than pending don't change this.
+    pub(crate) subpool_since: Instant,
     /// Keeps track of the current state of the transaction and therefore in which subpool it
     /// should reside
     pub(crate) state: TxState,
@@ -1782,6 +1988,17 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
     fn next_cumulative_cost(&self) -> U256 {
         self.cumulative_cost + self.transaction.cost()
     }
+
+    /// Updates the subpool of the transaction to its state.
+    ///
+    /// Resets [`Self::subpool_since`] if the transaction enters or leaves the pending subpool.
+    fn update_subpool(&mut self) {
+        let subpool: SubPool = self.state.into();
+        if subpool.is_pending() != self.subpool.is_pending() {
+            self.subpool_since = Instant::now();
+        }
+        self.subpool = subpool;
+    }
 }
 
 /// Tracks the result after updating the pool
@@ -1825,8 +2042,9 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        DEFAULT_PRICE_BUMP,
     };
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_insert_blob() {
@@ -2341,7 +2559,7 @@ mod tests {
         replacement.transaction.set_max_fee(109);
         let err =
             pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap_err();
-        assert!(matches!(err, InsertErr::Underpriced { .. }));
+        assert!(matches!(err, InsertErr::Underpriced { price_bump: DEFAULT_PRICE_BUMP, .. }));
 
         // ensure first tx is not removed
         assert!(pool.contains(first.hash()));
@@ -2788,6 +3006,188 @@ mod tests {
         }
     }
 
+    #[test]
+    fn remove_expired_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            pending_lifetime: Some(Duration::from_secs(60)),
+            queued_lifetime: Some(Duration::from_secs(1)),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        let mut add = |pool: &mut TxPool<MockOrdering>, tx: MockTransaction, age: u64| {
+            let mut validated = f.validated(tx);
+            validated.timestamp = Instant::now().checked_sub(Duration::from_secs(age)).unwrap();
+            let hash = *validated.hash();
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            hash
+        };
+
+        // pending for less than the pending lifetime
+        let pending = add(&mut pool, MockTransaction::eip1559().inc_price_by(10), 2);
+        // queued for longer than the queued lifetime
+        let queued = add(&mut pool, MockTransaction::eip1559().inc_price_by(10).inc_nonce(), 2);
+        // pending for longer than the pending lifetime, with a descendant
+        let expired = MockTransaction::eip1559().inc_price_by(10);
+        let expired_pending = add(&mut pool, expired.clone(), 120);
+        let descendant = add(&mut pool, expired.next(), 0);
+        assert_eq!(pool.pending_pool.len(), 3);
+
+        let removed = pool.remove_expired().iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        assert_eq!(removed, HashSet::from([queued, expired_pending, descendant]));
+        assert!(pool.contains(&pending));
+        assert_eq!(pool.len(), 1);
+        pool.assert_invariants();
+
+        // the pool isn't checked again before the remaining transaction can expire
+        assert!(pool.next_expiry.is_some_and(|next_expiry| next_expiry > Instant::now()));
+    }
+
+    #[test]
+    fn remove_expired_local_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            pending_lifetime: Some(Duration::from_secs(1)),
+            queued_lifetime: Some(Duration::from_secs(1)),
+            local_lifetime: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        let mut add = |pool: &mut TxPool<MockOrdering>, tx: MockTransaction, age: u64| {
+            let mut validated = f.validated_with_origin(TransactionOrigin::Local, tx);
+            validated.timestamp = Instant::now().checked_sub(Duration::from_secs(age)).unwrap();
+            let hash = *validated.hash();
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            hash
+        };
+
+        // pending local transactions don't expire
+        let pending = add(&mut pool, MockTransaction::eip1559().inc_price_by(10), 120);
+        // queued for less than the local lifetime
+        let queued = add(&mut pool, MockTransaction::eip1559().inc_price_by(10).inc_nonce(), 2);
+        // queued for longer than the local lifetime
+        let expired = add(&mut pool, MockTransaction::eip1559().inc_price_by(10).inc_nonce(), 120);
+        assert_eq!(pool.queued_pool.len(), 2);
+
+        let removed = pool.remove_expired().iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(removed, vec![expired]);
+        assert!(pool.contains(&pending));
+        assert!(pool.contains(&queued));
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn promoted_transaction_expires_after_pending_lifetime() {
+        let mut f = MockTransactionFactory::default();
+        let config =
+            PoolConfig { pending_lifetime: Some(Duration::from_secs(60)), ..Default::default() };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+        let mut add = |pool: &mut TxPool<MockOrdering>, tx: MockTransaction, age: u64| {
+            let mut validated = f.validated(tx);
+            validated.timestamp = Instant::now().checked_sub(Duration::from_secs(age)).unwrap();
+            let hash = *validated.hash();
+            pool.add_transaction(validated, U256::from(1_000), 0).unwrap();
+            hash
+        };
+
+        // queued for longer than the pending lifetime, until its ancestor is added
+        let tx = MockTransaction::eip1559().inc_price_by(10);
+        let promoted = add(&mut pool, tx.next(), 120);
+        assert_eq!(pool.queued_pool.len(), 1);
+        add(&mut pool, tx, 0);
+        assert_eq!(pool.pending_pool.len(), 2);
+
+        assert!(pool.remove_expired().is_empty());
+        assert!(pool.contains(&promoted));
+    }
+
+    #[test]
+    fn sender_report() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+        let tx = MockTransaction::eip1559().inc_price_by(10);
+        let first = f.validated(tx.clone());
+        let sender = first.sender_id();
+        pool.add_transaction(first, U256::from(1_000), 0).unwrap();
+        // nonce 1 is missing
+        let gapped = f.validated(tx.next().next());
+        let gapped_hash = *gapped.hash();
+        pool.add_transaction(gapped, U256::from(1_000), 0).unwrap();
+
+        let report = pool.sender_report(sender).unwrap();
+        assert_eq!(report.state_nonce, 0);
+        assert_eq!(
+            report.transactions.iter().map(|(_, subpool)| *subpool).collect::<Vec<_>>(),
+            vec![SubPool::Pending, SubPool::Queued]
+        );
+        assert_eq!(report.nonce_gaps, vec![1..=1]);
+        let stuck = report.stuck.unwrap();
+        assert_eq!(*stuck.transaction.hash(), gapped_hash);
+        assert_eq!(stuck.reason, StuckReason::NonceGap);
+        assert_eq!(stuck.missing_nonce, Some(1));
+        // a replacement doesn't fill the gap
+        assert_eq!(stuck.min_replacement_max_fee_per_gas, None);
+        assert_eq!(stuck.min_replacement_max_priority_fee_per_gas, None);
+
+        // a transaction that can't pay the base fee is replaced by one that does
+        pool.all_transactions.pending_fees.base_fee = 100;
+        let underpriced = f.validated(MockTransaction::eip1559().inc_price_by(10));
+        let sender = underpriced.sender_id();
+        pool.add_transaction(underpriced, U256::from(1_000), 0).unwrap();
+        let stuck = pool.sender_report(sender).unwrap().stuck.unwrap();
+        assert_eq!(stuck.reason, StuckReason::FeeCapBelowBaseFee);
+        assert_eq!(stuck.missing_nonce, None);
+        assert_eq!(stuck.min_replacement_max_fee_per_gas, Some(100));
+
+        assert!(pool.sender_report(f.ids.sender_id_or_create(Address::random())).is_none());
+    }
+
+    #[test]
+    fn discard_at_total_capacity() {
+        let mut f = MockTransactionFactory::default();
+        let total_limit = SubPoolLimit::new(100, usize::MAX);
+        let mut pool =
+            TxPool::new(MockOrdering::default(), PoolConfig { total_limit, ..Default::default() });
+
+        // executable transactions, which don't go into the queued pool
+        for _ in 0..50 {
+            let tx = MockTransaction::eip1559();
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap();
+        }
+        assert_eq!(pool.size().queued, 0);
+        for _ in 0..100 {
+            let tx = MockTransaction::eip1559().inc_nonce();
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap();
+        }
+        assert!(pool.is_exceeded());
+
+        // the queued transactions are discarded first
+        pool.discard_worst();
+        pool.assert_invariants();
+        let size = pool.size();
+        assert_eq!(size.total, total_limit.max_txs);
+        assert_eq!(size.queued, 50);
+    }
+
+    #[test]
+    fn replaces_transaction_of_sender_at_capacity() {
+        let on_chain_balance = U256::from(1_000);
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+
+        let mut tx = MockTransaction::eip1559();
+        for _ in 0..pool.max_account_slots {
+            tx = tx.next();
+            pool.insert_tx(f.validated(tx.clone()), on_chain_balance, 0).unwrap();
+        }
+
+        // a replacement doesn't need another slot
+        let replacement = f.validated(tx.rng_hash().inc_price());
+        let InsertOk { replaced_tx, .. } =
+            pool.insert_tx(replacement, on_chain_balance, 0).unwrap();
+        assert!(replaced_tx.is_some());
+    }
+
     #[test]
     fn discard_blobs_at_capacity() {
         let mut f = MockTransactionFactory::default();
@@ -1,7 +1,7 @@
 #![allow(deprecated)]
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProof, BlobStoreError},
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
@@ -22,6 +22,7 @@ use std::{
     collections::{HashMap, HashSet},
     fmt,
     future::Future,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -289,6 +290,25 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns all transactions that are currently in the pool with the subpool they're in,
+    /// ordered by sender and nonce.
+    ///
+    /// By default this is assembled from the [`SenderReport`] of every sender, which takes the
+    /// pool lock once per sender.
+    ///
+    /// Consumer: RPC
+    fn all_transactions_with_subpool(
+        &self,
+    ) -> Vec<(Arc<ValidPoolTransaction<Self::Transaction>>, SubPool)> {
+        let mut senders = self.unique_senders().into_iter().collect::<Vec<_>>();
+        senders.sort_unstable();
+        senders
+            .into_iter()
+            .filter_map(|sender| self.sender_report(sender))
+            .flat_map(|report| report.transactions)
+            .collect()
+    }
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Also removes all _dependent_ transactions.
@@ -332,6 +352,14 @@ pub trait TransactionPool: Send + Sync + Clone {
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns the transactions of the given sender with the subpool they're in, any nonce gaps
+    /// and the transaction that holds back the others, see [`SenderReport`].
+    ///
+    /// Returns `None` if the pool has no transactions of the sender.
+    ///
+    /// Consumer: RPC
+    fn sender_report(&self, sender: Address) -> Option<SenderReport<Self::Transaction>>;
+
     /// Returns a transaction sent by a given user with a given nonce
     fn get_transactions_by_sender_and_nonce(
         &self,
@@ -385,6 +413,22 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns the blobs with their proofs for the given versioned hashes from the blob store, in
+    /// the order they were requested.
+    ///
+    /// Blobs that are not in the blob store are `None`.
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError>;
+
+    /// Returns the hash and the [BlobTransactionSidecar] of the transaction that carries the blob
+    /// with the given versioned hash from the blob store.
+    fn get_blob_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<(TxHash, BlobTransactionSidecar)>, BlobStoreError>;
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -413,6 +457,14 @@ pub trait TransactionPoolExt: TransactionPool {
     /// Updates the accounts in the pool
     fn update_accounts(&self, accounts: Vec<ChangedAccount>);
 
+    /// Removes the transactions that stayed in their subpool for longer than its configured
+    /// lifetime.
+    ///
+    /// See [`PoolConfig::pending_lifetime`](crate::PoolConfig::pending_lifetime),
+    /// [`PoolConfig::queued_lifetime`](crate::PoolConfig::queued_lifetime) and
+    /// [`PoolConfig::local_lifetime`](crate::PoolConfig::local_lifetime).
+    fn remove_expired_transactions(&self);
+
     /// Deletes the blob sidecar for the given transaction from the blob store
     fn delete_blob(&self, tx: B256);
 
@@ -475,6 +527,61 @@ impl<T: PoolTransaction> Default for AllPoolTransactions<T> {
     }
 }
 
+/// The transactions of a sender in the pool, see [`TransactionPool::sender_report`].
+#[derive(Debug, Clone)]
+pub struct SenderReport<T: PoolTransaction> {
+    /// The nonce of the sender's account at the block the pool tracks.
+    pub state_nonce: u64,
+    /// The transactions of the sender with the subpool they're in, ordered by nonce.
+    pub transactions: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
+    /// The nonces between the state nonce and the highest nonce of the sender's transactions that
+    /// have no transaction in the pool.
+    pub nonce_gaps: Vec<RangeInclusive<u64>>,
+    /// The transaction with the lowest nonce that isn't pending, if any. It holds back all
+    /// transactions of the sender with higher nonces.
+    pub stuck: Option<StuckTransaction<T>>,
+}
+
+/// A transaction that isn't pending and holds back the transactions of its sender with higher
+/// nonces, see [`SenderReport`].
+#[derive(Debug, Clone)]
+pub struct StuckTransaction<T: PoolTransaction> {
+    /// The transaction.
+    pub transaction: Arc<ValidPoolTransaction<T>>,
+    /// Why the transaction isn't pending.
+    pub reason: StuckReason,
+    /// The nonce of the missing transaction that holds back this one, if the reason is a
+    /// [`StuckReason::NonceGap`].
+    pub missing_nonce: Option<u64>,
+    /// The lowest max fee per gas of a transaction that replaces this one and pays the base fee
+    /// of the pending block.
+    ///
+    /// The replacement fees are `None` for a [`StuckReason::NonceGap`], since replacing the
+    /// transaction doesn't fill the gap.
+    pub min_replacement_max_fee_per_gas: Option<u128>,
+    /// The lowest max priority fee per gas of a transaction that replaces this one, if the
+    /// replacement's priority fee is checked.
+    pub min_replacement_max_priority_fee_per_gas: Option<u128>,
+    /// The lowest max fee per blob gas of a blob transaction that replaces this one and pays the
+    /// blob fee of the pending block, if this is a blob transaction.
+    pub min_replacement_max_fee_per_blob_gas: Option<u128>,
+}
+
+/// Why a transaction isn't pending, see [`StuckTransaction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StuckReason {
+    /// A transaction with a lower nonce is missing.
+    NonceGap,
+    /// The sender can't afford the transaction on top of the transactions with lower nonces.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit,
+    /// The max fee per gas is below the base fee of the pending block.
+    FeeCapBelowBaseFee,
+    /// The max fee per blob gas is below the blob fee of the pending block.
+    BlobFeeCapBelowBlobFee,
+}
+
 /// Represents a transaction that was propagated over the network.
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct PropagatedTransactions(pub HashMap<TxHash, Vec<PropagateKind>>);
@@ -713,6 +820,24 @@ pub trait BestTransactions: Iterator + Send {
     }
 }
 
+impl<T: BestTransactions + ?Sized> BestTransactions for Box<T> {
+    fn mark_invalid(&mut self, transaction: &Self::Item) {
+        (**self).mark_invalid(transaction)
+    }
+
+    fn no_updates(&mut self) {
+        (**self).no_updates()
+    }
+
+    fn skip_blobs(&mut self) {
+        (**self).skip_blobs()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        (**self).set_skip_blobs(skip_blobs)
+    }
+}
+
 /// A no-op implementation that yields no transactions.
 impl<T> BestTransactions for std::iter::Empty<T> {
     fn mark_invalid(&mut self, _tx: &T) {}
@@ -1191,6 +1316,11 @@ pub struct PoolSize {
 // === impl PoolSize ===
 
 impl PoolSize {
+    /// Returns the reported size of the transactions of all sub-pools.
+    pub const fn total_size(&self) -> usize {
+        self.pending_size + self.basefee_size + self.queued_size + self.blob_size
+    }
+
     /// Asserts that the invariants of the pool size are met.
     #[cfg(test)]
     pub(crate) fn assert_invariants(&self) {
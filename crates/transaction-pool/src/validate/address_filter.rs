@@ -0,0 +1,187 @@
+//! Static allow and deny lists of senders and recipients.
+
+use crate::{
+    error::InvalidPoolTransactionError,
+    traits::{PoolTransaction, TransactionOrigin},
+    validate::AdmissionValidator,
+};
+use parking_lot::RwLock;
+use reth_primitives::Address;
+use std::{collections::HashSet, future::Future, sync::Arc};
+
+#[cfg(feature = "serde")]
+use reth_fs_util::FsPathError;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::path::Path;
+
+/// Lists of the senders and recipients whose transactions are allowed or denied.
+///
+/// A transaction is allowed if neither its sender nor its recipient is denied, and both are
+/// allowed by the allow lists. An empty allow list allows all addresses, and contract creations
+/// have no recipient, so they're only checked against the sender lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct AddressLists {
+    /// The only senders that are allowed, all if empty.
+    pub allowed_senders: HashSet<Address>,
+    /// The senders that are denied.
+    pub denied_senders: HashSet<Address>,
+    /// The only recipients that are allowed, all if empty.
+    pub allowed_recipients: HashSet<Address>,
+    /// The recipients that are denied.
+    pub denied_recipients: HashSet<Address>,
+}
+
+impl AddressLists {
+    /// Reads the lists from the JSON file at the given path.
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: &Path) -> Result<Self, AddressListsError> {
+        let contents = reth_fs_util::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| AddressListsError::Parse { path: path.to_path_buf(), err })
+    }
+
+    /// Returns the first address of the transaction that isn't allowed, if any.
+    pub fn disallowed_address(
+        &self,
+        sender: Address,
+        recipient: Option<Address>,
+    ) -> Option<Address> {
+        let is_allowed = |allowed: &HashSet<Address>, denied: &HashSet<Address>, address| {
+            !denied.contains(address) && (allowed.is_empty() || allowed.contains(address))
+        };
+        if !is_allowed(&self.allowed_senders, &self.denied_senders, &sender) {
+            return Some(sender)
+        }
+        recipient.filter(|recipient| {
+            !is_allowed(&self.allowed_recipients, &self.denied_recipients, recipient)
+        })
+    }
+}
+
+/// Errors of reading [`AddressLists`] from a file.
+#[cfg(feature = "serde")]
+#[derive(Debug, thiserror::Error)]
+pub enum AddressListsError {
+    /// The file couldn't be read.
+    #[error(transparent)]
+    Read(#[from] FsPathError),
+    /// The file isn't valid JSON of the lists.
+    #[error("failed to parse address lists {path:?}: {err}")]
+    Parse {
+        /// The path of the file.
+        path: std::path::PathBuf,
+        /// The parse error.
+        err: serde_json::Error,
+    },
+}
+
+/// A shared handle to [`AddressLists`] that can be replaced while the node is running.
+///
+/// All clones of the filter share the same lists. As an [`AdmissionValidator`] it rejects the
+/// transactions of disallowed addresses, a filter with the default lists allows all transactions.
+#[derive(Debug, Clone, Default)]
+pub struct AddressFilter {
+    lists: Arc<RwLock<AddressLists>>,
+}
+
+impl AddressFilter {
+    /// Creates a new filter with the given lists.
+    pub fn new(lists: AddressLists) -> Self {
+        Self { lists: Arc::new(RwLock::new(lists)) }
+    }
+
+    /// Returns a copy of the current lists.
+    pub fn lists(&self) -> AddressLists {
+        self.lists.read().clone()
+    }
+
+    /// Replaces the lists of all clones of the filter.
+    pub fn set_lists(&self, lists: AddressLists) {
+        *self.lists.write() = lists;
+    }
+
+    /// Returns the first address of the transaction that isn't allowed, if any.
+    pub fn disallowed_address(
+        &self,
+        sender: Address,
+        recipient: Option<Address>,
+    ) -> Option<Address> {
+        self.lists.read().disallowed_address(sender, recipient)
+    }
+
+    /// Ensures that the sender and the recipient of the transaction are allowed.
+    pub fn ensure_allowed<T: PoolTransaction>(
+        &self,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        match self.disallowed_address(transaction.sender(), transaction.to()) {
+            Some(address) => Err(InvalidPoolTransactionError::AddressNotAllowed(address)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionValidator<T> for AddressFilter {
+    fn validate_admission(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> impl Future<Output = Result<(), InvalidPoolTransactionError>> + Send {
+        std::future::ready(self.ensure_allowed(transaction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_senders_and_recipients() {
+        let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+        let filter = AddressFilter::default();
+        assert_eq!(filter.disallowed_address(alice, Some(bob)), None);
+
+        filter.set_lists(AddressLists {
+            denied_senders: HashSet::from([alice]),
+            allowed_recipients: HashSet::from([bob]),
+            ..Default::default()
+        });
+        assert_eq!(filter.disallowed_address(alice, Some(bob)), Some(alice));
+        assert_eq!(filter.disallowed_address(carol, Some(bob)), None);
+        assert_eq!(filter.disallowed_address(carol, Some(alice)), Some(alice));
+        assert_eq!(filter.disallowed_address(carol, None), None);
+
+        filter.set_lists(AddressLists {
+            allowed_senders: HashSet::from([alice]),
+            denied_recipients: HashSet::from([bob]),
+            ..Default::default()
+        });
+        assert_eq!(filter.disallowed_address(carol, None), Some(carol));
+        assert_eq!(filter.disallowed_address(alice, Some(bob)), Some(bob));
+        assert_eq!(filter.disallowed_address(alice, Some(carol)), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_address_lists() {
+        use reth_primitives::address;
+
+        let lists: AddressLists = serde_json::from_str(
+            r#"{"deniedSenders": ["0x000000000000000000000000000000000000dead"]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            lists,
+            AddressLists {
+                denied_senders: HashSet::from([address!(
+                    "000000000000000000000000000000000000dead"
+                )]),
+                ..Default::default()
+            }
+        );
+    }
+}
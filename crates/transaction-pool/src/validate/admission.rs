@@ -0,0 +1,264 @@
+//! Additional admission checks on top of a [`TransactionValidator`].
+
+use crate::{
+    error::InvalidPoolTransactionError,
+    traits::{PoolTransaction, TransactionOrigin},
+    TransactionValidationOutcome, TransactionValidator,
+};
+use reth_primitives::SealedBlock;
+use std::{future::Future, sync::Arc};
+
+/// An additional check a transaction must pass to be admitted to the pool, e.g. a compliance
+/// denylist or custom chain rules.
+///
+/// The check only runs for transactions the wrapped validator considers valid, see
+/// [`ValidatorWithAdmission`].
+///
+/// Synchronous checks can be plain closures:
+///
+/// ```
+/// use reth_primitives::Address;
+/// use reth_transaction_pool::{
+///     error::{InvalidPoolTransactionError, PoolTransactionError},
+///     validate::AdmissionValidator,
+///     PoolTransaction, TransactionOrigin,
+/// };
+///
+/// #[derive(Debug, thiserror::Error)]
+/// #[error("sender is denylisted")]
+/// struct Denylisted;
+///
+/// impl PoolTransactionError for Denylisted {
+///     fn is_bad_transaction(&self) -> bool {
+///         false
+///     }
+/// }
+///
+/// fn denylist<T: PoolTransaction>(denied: Vec<Address>) -> impl AdmissionValidator<T> {
+///     move |_origin: TransactionOrigin, tx: &T| {
+///         if denied.contains(&tx.sender()) {
+///             return Err(InvalidPoolTransactionError::Other(Box::new(Denylisted)))
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// Checks that need to await, e.g. to query an external service, implement the trait directly.
+///
+/// Several checks are combined as a tuple, and an optional check as an `Option`.
+pub trait AdmissionValidator<T: PoolTransaction>: Send + Sync {
+    /// Checks whether the valid transaction may be admitted to the pool.
+    ///
+    /// The transaction is rejected as invalid with the returned error.
+    fn validate_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> impl Future<Output = Result<(), InvalidPoolTransactionError>> + Send;
+
+    /// Invoked when the head block changes.
+    fn on_new_head_block(&self, _new_tip_block: &SealedBlock) {}
+}
+
+impl<T, F> AdmissionValidator<T> for F
+where
+    T: PoolTransaction,
+    F: Fn(TransactionOrigin, &T) -> Result<(), InvalidPoolTransactionError> + Send + Sync,
+{
+    fn validate_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> impl Future<Output = Result<(), InvalidPoolTransactionError>> + Send {
+        std::future::ready(self(origin, transaction))
+    }
+}
+
+/// An optional check, that admits all transactions if unset.
+impl<T, A> AdmissionValidator<T> for Option<A>
+where
+    T: PoolTransaction,
+    A: AdmissionValidator<T>,
+{
+    async fn validate_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        match self {
+            Some(admission) => admission.validate_admission(origin, transaction).await,
+            None => Ok(()),
+        }
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        if let Some(admission) = self {
+            admission.on_new_head_block(new_tip_block);
+        }
+    }
+}
+
+/// Two checks that a transaction must both pass, in order.
+impl<T, A, B> AdmissionValidator<T> for (A, B)
+where
+    T: PoolTransaction,
+    A: AdmissionValidator<T>,
+    B: AdmissionValidator<T>,
+{
+    async fn validate_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self.0.validate_admission(origin, transaction).await?;
+        self.1.validate_admission(origin, transaction).await
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.0.on_new_head_block(new_tip_block);
+        self.1.on_new_head_block(new_tip_block);
+    }
+}
+
+/// A [`TransactionValidator`] that runs an [`AdmissionValidator`] after the wrapped validator.
+///
+/// Transactions that the wrapped validator considers valid but fail the admission check are
+/// rejected as [`TransactionValidationOutcome::Invalid`].
+#[derive(Debug)]
+pub struct ValidatorWithAdmission<V, A> {
+    /// The wrapped validator.
+    validator: V,
+    /// The additional admission check.
+    admission: Arc<A>,
+}
+
+impl<V, A> ValidatorWithAdmission<V, A> {
+    /// Creates a new validator that checks the valid transactions of `validator` with
+    /// `admission`.
+    pub fn new(validator: V, admission: A) -> Self {
+        Self { validator, admission: Arc::new(admission) }
+    }
+
+    /// Returns the wrapped validator.
+    pub const fn validator(&self) -> &V {
+        &self.validator
+    }
+}
+
+impl<V: Clone, A> Clone for ValidatorWithAdmission<V, A> {
+    fn clone(&self) -> Self {
+        Self { validator: self.validator.clone(), admission: Arc::clone(&self.admission) }
+    }
+}
+
+impl<V, A> TransactionValidator for ValidatorWithAdmission<V, A>
+where
+    V: TransactionValidator,
+    A: AdmissionValidator<V::Transaction>,
+{
+    type Transaction = V::Transaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        match self.validator.validate_transaction(origin, transaction).await {
+            TransactionValidationOutcome::Valid {
+                balance,
+                state_nonce,
+                transaction,
+                propagate,
+            } => {
+                let admission =
+                    self.admission.validate_admission(origin, transaction.transaction()).await;
+                if let Err(err) = admission {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction.into_transaction(),
+                        err,
+                    )
+                }
+                TransactionValidationOutcome::Valid { balance, state_nonce, transaction, propagate }
+            }
+            outcome => outcome,
+        }
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.validator.on_new_head_block(new_tip_block);
+        self.admission.on_new_head_block(new_tip_block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::PoolTransactionError, noop::MockTransactionValidator, test_utils::MockTransaction,
+    };
+    use reth_primitives::Address;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("sender is denylisted")]
+    struct Denylisted;
+
+    impl PoolTransactionError for Denylisted {
+        fn is_bad_transaction(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_transactions_that_fail_admission() {
+        let denied = Address::random();
+        let validator = ValidatorWithAdmission::new(
+            MockTransactionValidator::<MockTransaction>::default(),
+            move |_origin: TransactionOrigin, tx: &MockTransaction| {
+                if tx.sender() == denied {
+                    return Err(InvalidPoolTransactionError::Other(Box::new(Denylisted)))
+                }
+                Ok(())
+            },
+        );
+
+        let outcome = validator
+            .validate_transaction(TransactionOrigin::External, MockTransaction::eip1559())
+            .await;
+        assert!(outcome.is_valid());
+
+        let outcome = validator
+            .validate_transaction(
+                TransactionOrigin::External,
+                MockTransaction::eip1559().with_sender(denied),
+            )
+            .await;
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Other(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn combines_admission_checks() {
+        let denied = Address::random();
+        let deny = move |_origin: TransactionOrigin, tx: &MockTransaction| {
+            if tx.sender() == denied {
+                return Err(InvalidPoolTransactionError::Other(Box::new(Denylisted)))
+            }
+            Ok(())
+        };
+        let admit_all = |_origin: TransactionOrigin, _tx: &MockTransaction| {
+            Ok::<_, InvalidPoolTransactionError>(())
+        };
+        let tx = MockTransaction::eip1559().with_sender(denied);
+        let origin = TransactionOrigin::External;
+
+        assert!((admit_all, deny).validate_admission(origin, &tx).await.is_err());
+        assert!((deny, admit_all).validate_admission(origin, &tx).await.is_err());
+        assert!((admit_all, admit_all).validate_admission(origin, &tx).await.is_ok());
+        assert!(Some(deny).validate_admission(origin, &tx).await.is_err());
+        let none: Option<fn(TransactionOrigin, &MockTransaction) -> Result<(), _>> = None;
+        assert!(none.validate_admission(origin, &tx).await.is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+//! The fee floor that non-local transactions must pay to enter the pool.
+
+use crate::{
+    config::LocalTransactionConfig,
+    error::InvalidPoolTransactionError,
+    traits::{PoolTransaction, TransactionOrigin},
+    validate::AdmissionValidator,
+};
+use parking_lot::RwLock;
+use reth_primitives::SealedBlock;
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// A shared handle to the minimum tip per gas that non-local transactions must pay at the base
+/// fee of the head block to enter the pool.
+///
+/// The floor is adjusted while the node is running, see
+/// [`fee_floor_task`](crate::maintain::fee_floor_task). All clones of the handle share the same
+/// floor, which is zero until it's first raised.
+///
+/// The floor is checked as an [`AdmissionValidator`], which tracks the base fee of the head
+/// block.
+#[derive(Debug, Clone, Default)]
+pub struct FeeFloor {
+    inner: Arc<FeeFloorInner>,
+}
+
+#[derive(Debug, Default)]
+struct FeeFloorInner {
+    /// The current floor.
+    floor: RwLock<u128>,
+    /// The base fee of the head block.
+    base_fee: AtomicU64,
+    /// Which transactions are local, and exempt from the floor.
+    local_transactions_config: LocalTransactionConfig,
+}
+
+impl FeeFloor {
+    /// Creates a new floor that exempts the local transactions of the given config.
+    pub fn new(local_transactions_config: LocalTransactionConfig) -> Self {
+        Self { inner: Arc::new(FeeFloorInner { local_transactions_config, ..Default::default() }) }
+    }
+
+    /// Returns the current floor.
+    pub fn get(&self) -> u128 {
+        *self.inner.floor.read()
+    }
+
+    /// Sets the floor of all clones of the handle.
+    pub fn set(&self, floor: u128) {
+        *self.inner.floor.write() = floor;
+    }
+
+    /// Returns the base fee the tips of transactions are computed at.
+    pub fn base_fee(&self) -> u64 {
+        self.inner.base_fee.load(Ordering::Relaxed)
+    }
+
+    /// Sets the base fee the tips of transactions are computed at.
+    pub fn set_base_fee(&self, base_fee: u64) {
+        self.inner.base_fee.store(base_fee, Ordering::Relaxed);
+    }
+
+    /// Ensures that the transaction pays at least the current floor as its effective tip at the
+    /// current base fee, unless it's local.
+    ///
+    /// A transaction that can't pay the base fee pays no tip.
+    pub fn ensure_above_floor<T: PoolTransaction>(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if self.inner.local_transactions_config.is_local(origin, transaction.sender()) {
+            return Ok(())
+        }
+        let tip = transaction.effective_tip_per_gas(self.base_fee()).unwrap_or_default();
+        if tip < self.get() {
+            return Err(InvalidPoolTransactionError::Underpriced)
+        }
+        Ok(())
+    }
+}
+
+impl<T: PoolTransaction> AdmissionValidator<T> for FeeFloor {
+    fn validate_admission(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> impl Future<Output = Result<(), InvalidPoolTransactionError>> + Send {
+        std::future::ready(self.ensure_above_floor(origin, transaction))
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.set_base_fee(new_tip_block.base_fee_per_gas.unwrap_or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn compares_the_effective_tip() {
+        let transaction = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(10);
+        let fee_floor = FeeFloor::default();
+        fee_floor.set(10);
+        assert!(fee_floor.ensure_above_floor(TransactionOrigin::External, &transaction).is_ok());
+
+        // only 5 of the max tip are left above the base fee
+        fee_floor.set_base_fee(95);
+        assert!(matches!(
+            fee_floor.ensure_above_floor(TransactionOrigin::External, &transaction),
+            Err(InvalidPoolTransactionError::Underpriced)
+        ));
+
+        // local transactions are exempt
+        assert!(fee_floor.ensure_above_floor(TransactionOrigin::Local, &transaction).is_ok());
+    }
+}
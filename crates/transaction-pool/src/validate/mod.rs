@@ -11,13 +11,27 @@ use reth_primitives::{
 };
 use std::{fmt, future::Future, time::Instant};
 
+mod address_filter;
+mod admission;
 mod constants;
 mod eth;
+mod fee_floor;
 mod task;
 
+/// Static allow and deny lists of senders and recipients.
+#[cfg(feature = "serde")]
+pub use address_filter::AddressListsError;
+pub use address_filter::{AddressFilter, AddressLists};
+
+/// Additional admission checks on top of a `TransactionValidator`.
+pub use admission::{AdmissionValidator, ValidatorWithAdmission};
+
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// The fee floor that non-local transactions must pay to enter the pool.
+pub use fee_floor::FeeFloor;
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -2,7 +2,10 @@
 
 use crate::{
     blobstore::BlobStore,
-    validate::{EthTransactionValidatorBuilder, TransactionValidatorError},
+    validate::{
+        AdmissionValidator, EthTransactionValidatorBuilder, TransactionValidatorError,
+        ValidatorWithAdmission,
+    },
     EthTransactionValidator, PoolTransaction, TransactionOrigin, TransactionValidationOutcome,
     TransactionValidator,
 };
@@ -109,6 +112,23 @@ impl<V> TransactionValidationTaskExecutor<V> {
             to_validation_task: self.to_validation_task,
         }
     }
+
+    /// Runs the given [`AdmissionValidator`] on the transactions the validator considers valid.
+    ///
+    /// The admission check runs on the validation tasks, after the validator.
+    pub fn with_admission_validator<A>(
+        self,
+        admission: A,
+    ) -> TransactionValidationTaskExecutor<ValidatorWithAdmission<V, A>>
+    where
+        V: TransactionValidator,
+        A: AdmissionValidator<V::Transaction>,
+    {
+        TransactionValidationTaskExecutor {
+            validator: ValidatorWithAdmission::new(self.validator, admission),
+            to_validation_task: self.to_validation_task,
+        }
+    }
 }
 
 impl<Client, Tx> TransactionValidationTaskExecutor<EthTransactionValidator<Client, Tx>>
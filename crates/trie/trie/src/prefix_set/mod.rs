@@ -20,6 +20,35 @@ pub struct TriePrefixSets {
     pub destroyed_accounts: HashSet<B256>,
 }
 
+/// Collection of mutable trie prefix sets.
+///
+/// See also [`TriePrefixSetsMut::freeze`].
+#[derive(Default, Debug, Clone)]
+pub struct TriePrefixSetsMut {
+    /// A set of account prefixes that have changed.
+    pub account_prefix_set: PrefixSetMut,
+    /// A map containing storage changes with the hashed address as key and a set of storage key
+    /// prefixes as the value.
+    pub storage_prefix_sets: HashMap<B256, PrefixSetMut>,
+    /// A set of hashed addresses of destroyed accounts.
+    pub destroyed_accounts: HashSet<B256>,
+}
+
+impl TriePrefixSetsMut {
+    /// Returns [`TriePrefixSets`] with the same elements as these sets.
+    pub fn freeze(self) -> TriePrefixSets {
+        TriePrefixSets {
+            account_prefix_set: self.account_prefix_set.freeze(),
+            storage_prefix_sets: self
+                .storage_prefix_sets
+                .into_iter()
+                .map(|(hashed_address, prefix_set)| (hashed_address, prefix_set.freeze()))
+                .collect(),
+            destroyed_accounts: self.destroyed_accounts,
+        }
+    }
+}
+
 /// A container for efficiently storing and checking for the presence of key prefixes.
 ///
 /// This data structure stores a set of `Nibbles` and provides methods to insert
@@ -102,6 +131,12 @@ impl PrefixSetMut {
         self.keys.push(nibbles);
     }
 
+    /// Inserts all the given `nibbles` into the set.
+    pub fn extend(&mut self, nibbles: impl IntoIterator<Item = Nibbles>) {
+        self.sorted = false;
+        self.keys.extend(nibbles);
+    }
+
     /// Returns the number of elements in the set.
     pub fn len(&self) -> usize {
         self.keys.len()
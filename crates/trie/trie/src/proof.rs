@@ -1,17 +1,22 @@
 use crate::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{TrieElement, TrieNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::TriePrefixSetsMut,
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     walker::TrieWalker,
     HashBuilder, Nibbles,
 };
-use alloy_rlp::{BufMut, Encodable};
+use alloy_rlp::{BufMut, Decodable, Encodable};
 use reth_db::tables;
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
-use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, B256};
-use reth_trie_common::{proof::ProofRetainer, AccountProof, StorageProof, TrieAccount};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Account, Address, Bytes, B256, U256};
+use reth_trie_common::{
+    nodes::{TrieNode, CHILD_INDEX_RANGE},
+    proof::ProofRetainer,
+    AccountProof, StorageProof, TrieAccount,
+};
+use std::collections::BTreeMap;
 /// A struct for generating merkle proofs.
 ///
 /// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
@@ -23,12 +28,30 @@ pub struct Proof<'a, TX, H> {
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// The prefix sets of the keys that changed in the hashed state, compared to the trie nodes in
+    /// the database.
+    prefix_sets: TriePrefixSetsMut,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
-    pub const fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+    pub fn new(tx: &'a TX) -> Self {
+        Self { tx, hashed_cursor_factory: tx, prefix_sets: TriePrefixSetsMut::default() }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets of the keys that changed in the hashed state.
+    ///
+    /// The proof targets are added to these sets, which is why they have to be mutable.
+    pub fn with_prefix_sets_mut(mut self, prefix_sets: TriePrefixSetsMut) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
@@ -47,17 +70,88 @@ where
         let target_nibbles = Nibbles::unpack(target_hashed_address);
         let mut account_proof = AccountProof::new(address);
 
+        let proofs = self.account_trie_nodes(vec![target_nibbles], |hashed_address, account| {
+            if hashed_address == target_hashed_address {
+                let (storage_root, storage_proofs) =
+                    self.storage_root_with_proofs(hashed_address, slots)?;
+                account_proof.set_account(account, storage_root, storage_proofs);
+                Ok(storage_root)
+            } else {
+                Ok(self.storage_root(hashed_address)?)
+            }
+        })?;
+        account_proof.set_proof(proofs.into_values().collect());
+
+        Ok(account_proof)
+    }
+
+    /// Generate the trie nodes needed to update the account and storage slots: the nodes of their
+    /// proofs, and the siblings of the branch nodes with two children on the proven paths.
+    ///
+    /// Removing a proven key from such a branch node collapses it into its remaining child, which
+    /// can only be done with the node of that child. The nodes are not ordered.
+    pub fn witness_nodes(
+        &self,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<Vec<Bytes>, StateRootError> {
+        let account_proof = self.account_proof(address, slots)?;
+        let hashed_address = keccak256(address);
+        let mut nodes = account_proof.proof.clone();
+
+        let account_siblings =
+            collapsible_siblings(&Nibbles::unpack(hashed_address), &account_proof.proof);
+        if !account_siblings.is_empty() {
+            let proofs = self
+                .account_trie_nodes(account_siblings.clone(), |hashed_address, _| {
+                    Ok(self.storage_root(hashed_address)?)
+                })?;
+            nodes.extend(
+                proofs
+                    .into_iter()
+                    .filter(|(path, _)| account_siblings.contains(path))
+                    .map(|(_, node)| node),
+            );
+        }
+
+        let mut storage_siblings = Vec::new();
+        for storage_proof in account_proof.storage_proofs {
+            storage_siblings
+                .extend(collapsible_siblings(&storage_proof.nibbles, &storage_proof.proof));
+            nodes.extend(storage_proof.proof);
+        }
+        if !storage_siblings.is_empty() {
+            let (_, proofs) =
+                self.storage_trie_nodes(hashed_address, storage_siblings.clone(), |_, _| {})?;
+            nodes.extend(
+                proofs
+                    .into_iter()
+                    .filter(|(path, _)| storage_siblings.contains(path))
+                    .map(|(_, node)| node),
+            );
+        }
+
+        Ok(nodes)
+    }
+
+    /// Walk the account trie and retain the nodes on the paths to the targets, calling `on_leaf`
+    /// to compute the storage root of every account.
+    fn account_trie_nodes(
+        &self,
+        targets: Vec<Nibbles>,
+        mut on_leaf: impl FnMut(B256, Account) -> Result<B256, StateRootError>,
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StateRootError> {
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let trie_cursor =
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
+        let mut prefix_set = self.prefix_sets.account_prefix_set.clone();
+        prefix_set.extend(targets.clone());
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
-        let retainer = ProofRetainer::from_iter([target_nibbles]);
+        let retainer = ProofRetainer::from_iter(targets);
         let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
 
         let mut account_rlp = Vec::with_capacity(128);
@@ -68,14 +162,7 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 TrieElement::Leaf(hashed_address, account) => {
-                    let storage_root = if hashed_address == target_hashed_address {
-                        let (storage_root, storage_proofs) =
-                            self.storage_root_with_proofs(hashed_address, slots)?;
-                        account_proof.set_account(account, storage_root, storage_proofs);
-                        storage_root
-                    } else {
-                        self.storage_root(hashed_address)?
-                    };
+                    let storage_root = on_leaf(hashed_address, account)?;
 
                     account_rlp.clear();
                     let account = TrieAccount::from((account, storage_root));
@@ -88,10 +175,7 @@ where
 
         let _ = hash_builder.root();
 
-        let proofs = hash_builder.take_proofs();
-        account_proof.set_proof(proofs.values().cloned().collect());
-
-        Ok(account_proof)
+        Ok(hash_builder.take_proofs())
     }
 
     /// Compute storage root.
@@ -106,25 +190,55 @@ where
         hashed_address: B256,
         slots: &[B256],
     ) -> Result<(B256, Vec<StorageProof>), StorageRootError> {
+        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+
+        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
+        let (root, all_proof_nodes) =
+            self.storage_trie_nodes(hashed_address, target_nibbles, |nibbles, value| {
+                if let Some(proof) = proofs.iter_mut().find(|proof| &proof.nibbles == nibbles) {
+                    proof.set_value(value);
+                }
+            })?;
+
+        for proof in &mut proofs {
+            // Iterate over all proof nodes and find the matching ones.
+            // The filtered results are guaranteed to be in order.
+            let matching_proof_nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| proof.nibbles.starts_with(path))
+                .map(|(_, node)| node.clone());
+            proof.set_proof(matching_proof_nodes.collect());
+        }
+
+        Ok((root, proofs))
+    }
+
+    /// Compute the storage root and retain the nodes on the paths to the targets, calling
+    /// `on_leaf` with every slot.
+    fn storage_trie_nodes(
+        &self,
+        hashed_address: B256,
+        targets: Vec<Nibbles>,
+        mut on_leaf: impl FnMut(&Nibbles, U256),
+    ) -> Result<(B256, BTreeMap<Nibbles, Bytes>), StorageRootError> {
         let mut hashed_storage_cursor =
             self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
 
-        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
-
         // short circuit on empty storage
         if hashed_storage_cursor.is_storage_empty()? {
-            return Ok((EMPTY_ROOT_HASH, proofs))
+            return Ok((EMPTY_ROOT_HASH, BTreeMap::default()))
         }
 
-        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let mut prefix_set =
+            self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default();
+        prefix_set.extend(targets.clone());
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
         );
-        let walker = TrieWalker::new(trie_cursor, prefix_set);
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
-        let retainer = ProofRetainer::from_iter(target_nibbles);
+        let retainer = ProofRetainer::from_iter(targets);
         let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
         let mut storage_node_iter = TrieNodeIter::new(walker, hashed_storage_cursor);
         while let Some(node) = storage_node_iter.try_next()? {
@@ -134,9 +248,7 @@ where
                 }
                 TrieElement::Leaf(hashed_slot, value) => {
                     let nibbles = Nibbles::unpack(hashed_slot);
-                    if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == nibbles) {
-                        proof.set_value(value);
-                    }
+                    on_leaf(&nibbles, value);
                     hash_builder.add_leaf(nibbles, alloy_rlp::encode_fixed_size(&value).as_ref());
                 }
             }
@@ -144,32 +256,55 @@ where
 
         let root = hash_builder.root();
 
-        let all_proof_nodes = hash_builder.take_proofs();
-        for proof in &mut proofs {
-            // Iterate over all proof nodes and find the matching ones.
-            // The filtered results are guaranteed to be in order.
-            let matching_proof_nodes = all_proof_nodes
-                .iter()
-                .filter(|(path, _)| proof.nibbles.starts_with(path))
-                .map(|(_, node)| node.clone());
-            proof.set_proof(matching_proof_nodes.collect());
-        }
+        Ok((root, hash_builder.take_proofs()))
+    }
+}
 
-        Ok((root, proofs))
+/// Returns the paths of the siblings of the branch nodes with two children on the path to the
+/// key, given the nodes of its proof.
+fn collapsible_siblings(key: &Nibbles, proof: &[Bytes]) -> Vec<Nibbles> {
+    let mut siblings = Vec::new();
+    let mut path = Nibbles::default();
+    for node in proof {
+        // The nodes were encoded by the hash builder.
+        let Ok(node) = TrieNode::decode(&mut &node[..]) else { break };
+        match node {
+            TrieNode::Branch(branch) => {
+                let Some(&nibble) = key.get(path.len()) else { break };
+                if branch.state_mask.get().count_ones() == 2 && branch.state_mask.is_bit_set(nibble)
+                {
+                    let sibling = CHILD_INDEX_RANGE
+                        .find(|&index| index != nibble && branch.state_mask.is_bit_set(index));
+                    if let Some(sibling) = sibling {
+                        let mut sibling_path = path.clone();
+                        sibling_path.push(sibling);
+                        siblings.push(sibling_path);
+                    }
+                }
+                path.push(nibble);
+            }
+            TrieNode::Extension(extension) => path.extend_from_slice(&extension.key),
+            TrieNode::Leaf(_) => break,
+        }
     }
+    siblings
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, HashedStorage, StateRoot};
     use once_cell::sync::Lazy;
     use reth_chainspec::{Chain, ChainSpec, HOLESKY, MAINNET};
     use reth_db_api::database::Database;
-    use reth_primitives::{Account, Bytes, StorageEntry, U256};
+    use reth_primitives::StorageEntry;
     use reth_provider::{test_utils::create_test_provider_factory, HashingWriter, ProviderFactory};
     use reth_storage_errors::provider::ProviderResult;
-    use std::{str::FromStr, sync::Arc};
+    use reth_trie_common::{
+        nodes::{word_rlp, ExtensionNode, LeafNode},
+        TrieMask,
+    };
+    use std::{collections::HashMap, str::FromStr, sync::Arc};
 
     /*
         World State (sampled from <https://ethereum.stackexchange.com/questions/268/ethereum-block-architecture/6413#6413>)
@@ -291,6 +426,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn testspec_reverted_state_proof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let target = Address::from_str("0x1ed9b1dd266b607ee278726d324b855a093394a6").unwrap();
+        let other = Address::from_str("0x62b0dd4aab2b1a0a04e279e2b828791a10755528").unwrap();
+        let slots = Vec::from([B256::with_last_byte(1), B256::with_last_byte(3)]);
+
+        let mut provider = factory.provider_rw().unwrap();
+        let expected = Proof::new(provider.tx_ref()).account_proof(target, &slots).unwrap();
+        let target_account = expected.info.unwrap();
+        let other_account = provider.tx_ref().get::<tables::HashedAccounts>(keccak256(other));
+        let other_account = other_account.unwrap().unwrap();
+
+        // Change the target account and its storage, and destroy the other account.
+        let changed_account = Account { nonce: 1, ..target_account };
+        let changes = HashedPostState::default()
+            .with_accounts([(keccak256(target), Some(changed_account)), (keccak256(other), None)])
+            .with_storages([(
+                keccak256(target),
+                HashedStorage::from_iter(false, [(keccak256(slots[0]), U256::from(5))]),
+            )]);
+        let (changed_root, updates) = changes.state_root_with_updates(provider.tx_ref()).unwrap();
+        provider
+            .insert_account_for_hashing([(target, Some(changed_account)), (other, None)])
+            .unwrap();
+        provider
+            .insert_storage_for_hashing([(
+                target,
+                [StorageEntry { key: slots[0], value: U256::from(5) }],
+            )])
+            .unwrap();
+        updates.flush(provider.tx_mut()).unwrap();
+
+        let latest = Proof::new(provider.tx_ref()).account_proof(target, &slots).unwrap();
+        assert_eq!(latest.verify(changed_root), Ok(()));
+
+        // Overlaying the reverts restores the proof of the original state.
+        let reverts = HashedPostState::default()
+            .with_accounts([
+                (keccak256(target), Some(target_account)),
+                (keccak256(other), Some(other_account)),
+            ])
+            .with_storages([(
+                keccak256(target),
+                HashedStorage::from_iter(false, [(keccak256(slots[0]), U256::ZERO)]),
+            )]);
+        let reverted = reverts.account_proof(provider.tx_ref(), target, &slots).unwrap();
+        similar_asserts::assert_eq!(reverted, expected);
+        assert_eq!(reverted.verify(root), Ok(()));
+    }
+
     #[test]
     fn testspec_empty_storage_proof() {
         // Create test database and insert genesis accounts.
@@ -313,6 +502,98 @@ mod tests {
         assert_eq!(account_proof.verify(root), Ok(()));
     }
 
+    /// Decodes the node referenced by its RLP, looking up hashed nodes in the witness.
+    fn resolve_node(witness: &HashMap<B256, Bytes>, node_ref: &[u8]) -> TrieNode {
+        let node = if node_ref.len() == B256::len_bytes() + 1 {
+            &witness.get(&B256::from_slice(&node_ref[1..])).expect("node missing from the witness")
+                [..]
+        } else {
+            node_ref
+        };
+        TrieNode::decode(&mut &node[..]).unwrap()
+    }
+
+    /// Prefixes the node with the key, merging the key into leaves and extensions.
+    fn prefix_node(key: Nibbles, node: TrieNode) -> TrieNode {
+        match node {
+            TrieNode::Branch(_) => {
+                TrieNode::Extension(ExtensionNode::new(key, node.rlp(&mut Vec::new())))
+            }
+            TrieNode::Extension(extension) => {
+                TrieNode::Extension(ExtensionNode::new(key.join(&extension.key), extension.child))
+            }
+            TrieNode::Leaf(leaf) => TrieNode::Leaf(LeafNode::new(key.join(&leaf.key), leaf.value)),
+        }
+    }
+
+    /// Removes the leaf at the path from the trie below the node, using only the witness nodes.
+    fn remove_leaf(
+        witness: &HashMap<B256, Bytes>,
+        node: TrieNode,
+        path: &[u8],
+    ) -> Option<TrieNode> {
+        match node {
+            TrieNode::Leaf(leaf) => {
+                assert_eq!(&leaf.key[..], path);
+                None
+            }
+            TrieNode::Extension(extension) => {
+                let child = resolve_node(witness, &extension.child);
+                let child = remove_leaf(witness, child, &path[extension.key.len()..])?;
+                Some(prefix_node(extension.key, child))
+            }
+            TrieNode::Branch(mut branch) => {
+                let nibble = path[0];
+                let index = (0..nibble).filter(|&i| branch.state_mask.is_bit_set(i)).count();
+                let child = resolve_node(witness, &branch.stack[index]);
+                if let Some(child) = remove_leaf(witness, child, &path[1..]) {
+                    branch.stack[index] = child.rlp(&mut Vec::new());
+                    return Some(TrieNode::Branch(branch))
+                }
+
+                branch.stack.remove(index);
+                branch.state_mask = TrieMask::new(branch.state_mask.get() & !(1 << nibble));
+                if branch.stack.len() > 1 {
+                    return Some(TrieNode::Branch(branch))
+                }
+                let sibling = CHILD_INDEX_RANGE.find(|&i| branch.state_mask.is_bit_set(i)).unwrap();
+                let sibling_node = resolve_node(witness, &branch.stack[0]);
+                Some(prefix_node(Nibbles::from_nibbles_unchecked([sibling]), sibling_node))
+            }
+        }
+    }
+
+    #[test]
+    fn testspec_witness_recomputes_root_without_account() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        // The account is one of the two children of the branch node at `0xa77d3`.
+        let target = Address::from_str("0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2").unwrap();
+        let provider = factory.provider().unwrap();
+        let witness = Proof::new(provider.tx_ref())
+            .witness_nodes(target, &[])
+            .unwrap()
+            .into_iter()
+            .map(|node| (keccak256(&node), node))
+            .collect::<HashMap<_, _>>();
+
+        // Remove the account from the trie built from the witness.
+        let root_node = resolve_node(&witness, &word_rlp(&root));
+        let root_node =
+            remove_leaf(&witness, root_node, &Nibbles::unpack(keccak256(target))).unwrap();
+        let mut root_rlp = Vec::new();
+        root_node.encode(&mut root_rlp);
+
+        // Compare with the root of the genesis without the account.
+        let mut chain_spec = (**TEST_SPEC).clone();
+        chain_spec.genesis.alloc.remove(&target);
+        let expected_root =
+            insert_genesis(&create_test_provider_factory(), Arc::new(chain_spec)).unwrap();
+        assert_eq!(keccak256(root_rlp), expected_root);
+    }
+
     #[test]
     fn mainnet_genesis_account_proof() {
         // Create test database and insert genesis accounts.
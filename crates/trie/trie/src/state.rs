@@ -1,6 +1,7 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
-    prefix_set::{PrefixSetMut, TriePrefixSets},
+    prefix_set::{PrefixSetMut, TriePrefixSets, TriePrefixSetsMut},
+    proof::Proof,
     updates::TrieUpdates,
     Nibbles, StateRoot,
 };
@@ -12,7 +13,8 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_execution_errors::StateRootError;
-use reth_primitives::{keccak256, Account, Address, BlockNumber, B256, U256};
+use reth_primitives::{keccak256, Account, Address, BlockNumber, Bytes, B256, U256};
+use reth_trie_common::AccountProof;
 use revm::db::BundleAccount;
 use std::{
     collections::{hash_map, HashMap, HashSet},
@@ -173,6 +175,12 @@ impl HashedPostState {
     /// The prefix sets contain the hashed account and storage keys that have been changed in the
     /// post state.
     pub fn construct_prefix_sets(&self) -> TriePrefixSets {
+        self.construct_prefix_sets_mut().freeze()
+    }
+
+    /// Construct [`TriePrefixSetsMut`] from hashed post state.
+    /// See [`Self::construct_prefix_sets`] for more info.
+    pub fn construct_prefix_sets_mut(&self) -> TriePrefixSetsMut {
         // Populate account prefix set.
         let mut account_prefix_set = PrefixSetMut::with_capacity(self.accounts.len());
         let mut destroyed_accounts = HashSet::default();
@@ -193,14 +201,10 @@ impl HashedPostState {
             for hashed_slot in hashed_storage.storage.keys() {
                 prefix_set.insert(Nibbles::unpack(hashed_slot));
             }
-            storage_prefix_sets.insert(*hashed_address, prefix_set.freeze());
+            storage_prefix_sets.insert(*hashed_address, prefix_set);
         }
 
-        TriePrefixSets {
-            account_prefix_set: account_prefix_set.freeze(),
-            storage_prefix_sets,
-            destroyed_accounts,
-        }
+        TriePrefixSetsMut { account_prefix_set, storage_prefix_sets, destroyed_accounts }
     }
 
     /// Calculate the state root for this [`HashedPostState`].
@@ -255,6 +259,43 @@ impl HashedPostState {
             .with_prefix_sets(prefix_sets)
             .root_with_updates()
     }
+
+    /// Generates the proof of the account and storage slots in the state that results from
+    /// overlaying this [`HashedPostState`] on top of the database state.
+    ///
+    /// This can be used to generate proofs for historical state by overlaying the reverts returned
+    /// by [`Self::from_revert_range`].
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets_mut();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets_mut(prefix_sets)
+            .account_proof(address, slots)
+    }
+
+    /// Generates the witness nodes of the account and storage slots in the state that results
+    /// from overlaying this [`HashedPostState`] on top of the database state.
+    ///
+    /// See [`Proof::witness_nodes`] for the nodes that are included.
+    pub fn witness_nodes<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<Vec<Bytes>, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets_mut();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets_mut(prefix_sets)
+            .witness_nodes(address, slots)
+    }
 }
 
 /// Representation of in-memory hashed storage.
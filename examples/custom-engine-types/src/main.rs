@@ -290,6 +290,7 @@ where
             extra_data,
             attributes,
             chain_spec,
+            forced_transactions,
         } = config;
 
         // This reuses the default EthereumPayloadBuilder to build the payload
@@ -305,6 +306,7 @@ where
                 extra_data,
                 attributes: attributes.0,
                 chain_spec,
+                forced_transactions,
             },
             cancel,
             best_payload,
@@ -323,9 +325,10 @@ where
             extra_data,
             attributes,
             chain_spec,
+            forced_transactions,
         } = config;
         <reth_ethereum_payload_builder::EthereumPayloadBuilder as PayloadBuilder<Pool, Client>>::build_empty_payload(&reth_ethereum_payload_builder::EthereumPayloadBuilder::default(),client,
-                                                                                                                     PayloadConfig { initialized_block_env, initialized_cfg, parent_block, extra_data, attributes: attributes.0, chain_spec })
+                                                                                                                     PayloadConfig { initialized_block_env, initialized_cfg, parent_block, extra_data, attributes: attributes.0, chain_spec, forced_transactions })
     }
 }
 